@@ -62,6 +62,9 @@ fn main() {
                 option_style: *style,
                 dividend_yield: pos_or_panic!(dividend_yield),
                 exotic_params: None,
+                yield_curve: None,
+                dividends: Default::default(),
+                day_count: Default::default(),
             };
 
             let price = option.price(&PricingEngine::ClosedFormBS).unwrap();
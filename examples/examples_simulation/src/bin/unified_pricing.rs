@@ -70,6 +70,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         option_style: OptionStyle::Call,
         dividend_yield: pos_or_panic!(0.02),
         exotic_params: None,
+        yield_curve: None,
+        dividends: Default::default(),
+        day_count: Default::default(),
     };
 
     info!("Option Details:");
@@ -35,6 +35,9 @@ fn calculate_error(
         option_style,
         dividend_yield,
         exotic_params: None,
+        yield_curve: None,
+        dividends: Default::default(),
+        day_count: Default::default(),
     };
     let price = black_scholes(&option)?;
 
@@ -0,0 +1,698 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Reductions from the raw output of the [`Backtester`](super::Backtester)
+//! (equity curve, margin series, trade log) into the metric structs carried by
+//! [`BacktestResult`](super::BacktestResult).
+//!
+//! Statistics are computed in `f64` and converted back to `Decimal` through
+//! [`finite_decimal`], so a degenerate input surfaces as
+//! [`BacktestError::NonFinite`] rather than a silent `NaN`.
+
+use crate::backtesting::engine::{ClosedTrade, days_between};
+use crate::backtesting::metrics::{
+    AdvancedRiskMetrics, GeneralPerformanceMetrics, OptionsSpecificMetrics,
+};
+use crate::backtesting::types::{
+    CapitalUtilization, DrawdownAnalysis, DrawdownEvent, TradeRecord, TradeStatistics,
+};
+use crate::error::BacktestError;
+use crate::greeks::Greek;
+use crate::model::decimal::{d_add, d_div, d_sub, d_sum, d_sum_iter, finite_decimal};
+use chrono::{DateTime, Utc};
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+
+/// Calendar days in a year, used to annualise returns and volatility.
+const DAYS_PER_YEAR: f64 = 365.0;
+
+#[inline]
+fn to_decimal(value: f64, context: &'static str) -> Result<Decimal, BacktestError> {
+    finite_decimal(value).ok_or_else(|| BacktestError::non_finite(context, value))
+}
+
+#[inline]
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+/// Arithmetic mean of a slice, `None` when empty.
+pub(crate) fn mean_decimal(values: &[Decimal]) -> Result<Option<Decimal>, BacktestError> {
+    if values.is_empty() {
+        return Ok(None);
+    }
+    let total = d_sum(values, "backtest::analysis::mean::sum")?;
+    Ok(Some(d_div(
+        total,
+        Decimal::from(values.len()),
+        "backtest::analysis::mean::div",
+    )?))
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(values);
+    let var = values.iter().map(|v| (v - m).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    var.sqrt()
+}
+
+fn median(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    if n == 0 {
+        return 0.0;
+    }
+    let mid = n / 2;
+    if n.is_multiple_of(2) {
+        (sorted.get(mid - 1).copied().unwrap_or(0.0) + sorted.get(mid).copied().unwrap_or(0.0))
+            / 2.0
+    } else {
+        sorted.get(mid).copied().unwrap_or(0.0)
+    }
+}
+
+/// Empirical quantile (lower interpolation) of an ascending slice.
+fn quantile(sorted: &[f64], q: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (q * (sorted.len() - 1) as f64).floor() as usize;
+    sorted.get(rank).copied()
+}
+
+/// Simple per-bar returns of an equity curve.
+pub(crate) fn bar_returns(equity: &[Decimal]) -> Result<Vec<f64>, BacktestError> {
+    let mut returns = Vec::with_capacity(equity.len().saturating_sub(1));
+    for pair in equity.windows(2) {
+        if let [prev, next] = pair {
+            if prev.is_zero() {
+                returns.push(0.0);
+                continue;
+            }
+            let change = d_sub(*next, *prev, "backtest::analysis::returns::diff")?;
+            returns.push(to_f64(d_div(
+                change,
+                *prev,
+                "backtest::analysis::returns::div",
+            )?));
+        }
+    }
+    Ok(returns)
+}
+
+/// Average number of bars per year implied by the timestamps.
+fn bars_per_year(timestamps: &[DateTime<Utc>]) -> f64 {
+    match (timestamps.first(), timestamps.last()) {
+        (Some(first), Some(last)) if timestamps.len() > 1 => {
+            let span_days = (*last - *first).num_seconds() as f64 / 86_400.0;
+            if span_days <= 0.0 {
+                DAYS_PER_YEAR
+            } else {
+                (timestamps.len() - 1) as f64 * DAYS_PER_YEAR / span_days
+            }
+        }
+        _ => DAYS_PER_YEAR,
+    }
+}
+
+/// Drawdown analysis of an equity curve plus the per-bar drawdown curve,
+/// expressed as a fraction of the running peak.
+pub(crate) fn drawdown_analysis(
+    timestamps: &[DateTime<Utc>],
+    equity: &[Decimal],
+) -> Result<(DrawdownAnalysis, Vec<Decimal>), BacktestError> {
+    let mut curve = Vec::with_capacity(equity.len());
+    let mut events: Vec<DrawdownEvent> = Vec::new();
+    let mut peak = Decimal::ZERO;
+    let mut peak_time: Option<DateTime<Utc>> = None;
+    let mut current: Option<(DateTime<Utc>, DateTime<Utc>, Decimal)> = None;
+    let mut underwater_bars = 0usize;
+    let mut max_dd = Decimal::ZERO;
+    let mut max_dd_time: Option<DateTime<Utc>> = None;
+
+    for (ts, value) in timestamps.iter().zip(equity.iter()) {
+        if *value >= peak {
+            if let Some((start, bottom, magnitude)) = current.take() {
+                let start_ts = peak_time.unwrap_or(start);
+                events.push(DrawdownEvent {
+                    start_date: start_ts.naive_utc(),
+                    bottom_date: bottom.naive_utc(),
+                    recovery_date: Some(ts.naive_utc()),
+                    magnitude,
+                    duration: days_between(start_ts, *ts)?,
+                    recovery_duration: Some(days_between(bottom, *ts)?),
+                });
+            }
+            peak = *value;
+            peak_time = Some(*ts);
+            curve.push(Decimal::ZERO);
+            continue;
+        }
+
+        underwater_bars += 1;
+        let dd = if peak.is_zero() {
+            Decimal::ZERO
+        } else {
+            d_div(
+                d_sub(peak, *value, "backtest::analysis::drawdown::diff")?,
+                peak,
+                "backtest::analysis::drawdown::div",
+            )?
+        };
+        curve.push(dd);
+        if dd > max_dd {
+            max_dd = dd;
+            max_dd_time = Some(*ts);
+        }
+        current = match current {
+            Some((start, _, magnitude)) if dd > magnitude => Some((start, *ts, dd)),
+            Some(existing) => Some(existing),
+            None => Some((*ts, *ts, dd)),
+        };
+    }
+
+    let last_ts = timestamps.last().copied();
+    if let (Some((start, bottom, magnitude)), Some(end)) = (current, last_ts) {
+        let start_ts = peak_time.unwrap_or(start);
+        events.push(DrawdownEvent {
+            start_date: start_ts.naive_utc(),
+            bottom_date: bottom.naive_utc(),
+            recovery_date: None,
+            magnitude,
+            duration: days_between(start_ts, end)?,
+            recovery_duration: None,
+        });
+    }
+
+    let first_ts = timestamps.first().copied();
+    let time_to_max_drawdown = match (first_ts, max_dd_time) {
+        (Some(first), Some(at)) => days_between(first, at)?,
+        _ => Positive::ZERO,
+    };
+    let max_event = events
+        .iter()
+        .filter(|e| e.magnitude == max_dd)
+        .max_by_key(|e| e.duration);
+    let max_drawdown_duration = max_event.map(|e| e.duration).unwrap_or(Positive::ZERO);
+    let recovery_duration = max_event.and_then(|e| e.recovery_duration);
+    let avg_drawdown = mean_decimal(&events.iter().map(|e| e.magnitude).collect::<Vec<_>>())?
+        .unwrap_or(Decimal::ZERO);
+    let recoveries: Vec<Decimal> = events
+        .iter()
+        .filter_map(|e| e.recovery_duration.map(|d| d.to_dec()))
+        .collect();
+    let avg_recovery_time = match mean_decimal(&recoveries)? {
+        Some(avg) => Some(Positive::new_decimal(avg)?),
+        None => None,
+    };
+    let total_underwater_days = Positive::new_decimal(d_sum_iter(
+        events.iter().map(|e| e.duration.to_dec()),
+        "backtest::analysis::drawdown::underwater",
+    )?)?;
+    let underwater_percentage = if equity.is_empty() {
+        Decimal::ZERO
+    } else {
+        d_div(
+            Decimal::from(underwater_bars),
+            Decimal::from(equity.len()),
+            "backtest::analysis::drawdown::underwater_pct",
+        )?
+    };
+
+    Ok((
+        DrawdownAnalysis {
+            max_drawdown: max_dd,
+            max_drawdown_duration,
+            recovery_duration,
+            time_to_max_drawdown,
+            drawdowns: events,
+            avg_drawdown,
+            avg_recovery_time,
+            total_underwater_days,
+            underwater_percentage,
+        },
+        curve,
+    ))
+}
+
+/// Headline performance metrics from the equity curve and closed trades.
+pub(crate) fn performance_metrics(
+    timestamps: &[DateTime<Utc>],
+    equity: &[Decimal],
+    returns: &[f64],
+    closed: &[ClosedTrade],
+    max_drawdown: Decimal,
+    risk_free_rate: Decimal,
+) -> Result<GeneralPerformanceMetrics, BacktestError> {
+    let mut metrics = GeneralPerformanceMetrics::default();
+    let (Some(first), Some(last)) = (equity.first(), equity.last()) else {
+        return Ok(metrics);
+    };
+    if first.is_zero() {
+        return Ok(metrics);
+    }
+    metrics.total_return = d_div(
+        d_sub(*last, *first, "backtest::analysis::perf::total::diff")?,
+        *first,
+        "backtest::analysis::perf::total::div",
+    )?;
+
+    let periods = bars_per_year(timestamps);
+    let span_years = match (timestamps.first(), timestamps.last()) {
+        (Some(a), Some(b)) => (*b - *a).num_seconds() as f64 / 86_400.0 / DAYS_PER_YEAR,
+        _ => 0.0,
+    };
+    let total = to_f64(metrics.total_return);
+    let annualized = if span_years > 0.0 && total > -1.0 {
+        (1.0 + total).powf(1.0 / span_years) - 1.0
+    } else {
+        total
+    };
+    metrics.annualized_return = to_decimal(annualized, "backtest::analysis::perf::annualized")?;
+
+    if returns.len() > 1 {
+        let rf_per_bar = to_f64(risk_free_rate) / periods;
+        let excess: Vec<f64> = returns.iter().map(|r| r - rf_per_bar).collect();
+        let vol = std_dev(returns) * periods.sqrt();
+        metrics.volatility = Some(Positive::new_decimal(to_decimal(
+            vol,
+            "backtest::analysis::perf::volatility",
+        )?)?);
+        let downside: Vec<f64> = excess.iter().map(|r| r.min(0.0)).collect();
+        let downside_dev =
+            (downside.iter().map(|r| r * r).sum::<f64>() / downside.len() as f64).sqrt();
+        let downside_ann = downside_dev * periods.sqrt();
+        metrics.downside_deviation = Some(Positive::new_decimal(to_decimal(
+            downside_ann,
+            "backtest::analysis::perf::downside",
+        )?)?);
+        let mean_excess_ann = mean(&excess) * periods;
+        if vol > 0.0 {
+            metrics.sharpe_ratio = Some(to_decimal(
+                mean_excess_ann / vol,
+                "backtest::analysis::perf::sharpe",
+            )?);
+        }
+        if downside_ann > 0.0 {
+            metrics.sortino_ratio = Some(to_decimal(
+                mean_excess_ann / downside_ann,
+                "backtest::analysis::perf::sortino",
+            )?);
+        }
+    }
+    if max_drawdown > Decimal::ZERO {
+        metrics.calmar_ratio = Some(d_div(
+            metrics.annualized_return,
+            max_drawdown,
+            "backtest::analysis::perf::calmar",
+        )?);
+    }
+
+    if !closed.is_empty() {
+        let gains: Vec<Decimal> = closed
+            .iter()
+            .map(|t| t.pnl)
+            .filter(|p| *p > Decimal::ZERO)
+            .collect();
+        let losses: Vec<Decimal> = closed
+            .iter()
+            .map(|t| t.pnl)
+            .filter(|p| *p < Decimal::ZERO)
+            .collect();
+        metrics.win_rate = Some(d_div(
+            Decimal::from(gains.len()),
+            Decimal::from(closed.len()),
+            "backtest::analysis::perf::win_rate",
+        )?);
+        let gross_gain = d_sum(&gains, "backtest::analysis::perf::gross_gain")?;
+        let gross_loss = d_sum(&losses, "backtest::analysis::perf::gross_loss")?.abs();
+        if gross_loss > Decimal::ZERO {
+            metrics.profit_factor = Some(d_div(
+                gross_gain,
+                gross_loss,
+                "backtest::analysis::perf::profit_factor",
+            )?);
+        }
+        metrics.avg_gain = mean_decimal(&gains)?;
+        metrics.avg_loss = mean_decimal(&losses)?;
+        if let (Some(gain), Some(loss)) = (metrics.avg_gain, metrics.avg_loss)
+            && !loss.is_zero()
+        {
+            metrics.gain_loss_ratio = Some(d_div(
+                gain,
+                loss.abs(),
+                "backtest::analysis::perf::gain_loss",
+            )?);
+        }
+    }
+    Ok(metrics)
+}
+
+/// Strategy-level trade statistics.
+pub(crate) fn trade_statistics(closed: &[ClosedTrade]) -> Result<TradeStatistics, BacktestError> {
+    let mut stats = TradeStatistics {
+        number_of_trades: closed.len(),
+        ..TradeStatistics::default()
+    };
+    if closed.is_empty() {
+        return Ok(stats);
+    }
+    let mut returns = Vec::with_capacity(closed.len());
+    let mut holding = Vec::with_capacity(closed.len());
+    for trade in closed {
+        match trade.pnl.cmp(&Decimal::ZERO) {
+            std::cmp::Ordering::Greater => stats.winners += 1,
+            std::cmp::Ordering::Less => stats.losers += 1,
+            std::cmp::Ordering::Equal => stats.break_even += 1,
+        }
+        if trade.is_debit {
+            stats.long_trades += 1;
+        } else {
+            stats.short_trades += 1;
+        }
+        if trade.legs > 1 {
+            stats.spread_trades += 1;
+        }
+        if trade.calls > 0 {
+            stats.call_trades += 1;
+        }
+        if trade.puts > 0 {
+            stats.put_trades += 1;
+        }
+        returns.push(to_f64(trade.return_on_margin));
+        holding.push(days_between(trade.entry_time, trade.exit_time)?.to_f64());
+    }
+    stats.average_trade_return = to_decimal(mean(&returns), "backtest::analysis::trades::avg")?;
+    returns.sort_by(|a, b| a.total_cmp(b));
+    stats.median_trade_return = to_decimal(median(&returns), "backtest::analysis::trades::median")?;
+    stats.largest_win = closed
+        .iter()
+        .map(|t| t.pnl)
+        .filter(|p| *p > Decimal::ZERO)
+        .max();
+    stats.largest_loss = closed
+        .iter()
+        .map(|t| t.pnl)
+        .filter(|p| *p < Decimal::ZERO)
+        .min();
+
+    holding.sort_by(|a, b| a.total_cmp(b));
+    let hold = |value: f64, context: &'static str| -> Result<Positive, BacktestError> {
+        Ok(Positive::new_decimal(to_decimal(value, context)?)?)
+    };
+    stats.average_holding_period = hold(mean(&holding), "backtest::analysis::trades::hold_avg")?;
+    stats.median_holding_period =
+        hold(median(&holding), "backtest::analysis::trades::hold_median")?;
+    stats.min_holding_period = hold(
+        holding.first().copied().unwrap_or(0.0),
+        "backtest::analysis::trades::hold_min",
+    )?;
+    stats.max_holding_period = hold(
+        holding.last().copied().unwrap_or(0.0),
+        "backtest::analysis::trades::hold_max",
+    )?;
+    Ok(stats)
+}
+
+/// Capital usage and premium flow statistics.
+pub(crate) fn capital_utilization(
+    margin_usage: &[Decimal],
+    records: &[TradeRecord],
+    closed: &[ClosedTrade],
+    initial_capital: Decimal,
+) -> Result<CapitalUtilization, BacktestError> {
+    let mut util = CapitalUtilization::default();
+    util.max_margin_used = margin_usage.iter().copied().max().unwrap_or(Decimal::ZERO);
+    util.avg_margin_used = mean_decimal(margin_usage)?.unwrap_or(Decimal::ZERO);
+    util.max_capital_used = util.max_margin_used;
+    util.avg_capital_used = util.avg_margin_used;
+    util.total_margin_used = d_sum_iter(
+        closed.iter().map(|t| t.margin),
+        "backtest::analysis::capital::total_margin",
+    )?;
+
+    for record in records {
+        let flow = record.position.premium.to_dec() * record.position.option.quantity.to_dec();
+        if record.position.is_long() {
+            util.total_premium_paid = d_add(
+                util.total_premium_paid,
+                flow,
+                "backtest::analysis::capital::paid",
+            )?;
+        } else {
+            util.total_premium_received = d_add(
+                util.total_premium_received,
+                flow,
+                "backtest::analysis::capital::received",
+            )?;
+        }
+    }
+    util.net_premium = d_sub(
+        util.total_premium_received,
+        util.total_premium_paid,
+        "backtest::analysis::capital::net",
+    )?;
+
+    if !initial_capital.is_zero() {
+        let sizes: Vec<Decimal> = closed
+            .iter()
+            .map(|t| {
+                d_div(
+                    t.margin,
+                    initial_capital,
+                    "backtest::analysis::capital::size",
+                )
+            })
+            .collect::<Result<_, _>>()?;
+        util.max_position_size = sizes.iter().copied().max().unwrap_or(Decimal::ZERO);
+        util.avg_position_size = mean_decimal(&sizes)?.unwrap_or(Decimal::ZERO);
+    }
+    if util.avg_capital_used > Decimal::ZERO {
+        let pnl = d_sum_iter(
+            closed.iter().map(|t| t.pnl),
+            "backtest::analysis::capital::pnl",
+        )?;
+        util.capital_efficiency = d_div(
+            pnl,
+            util.avg_capital_used,
+            "backtest::analysis::capital::efficiency",
+        )?;
+    }
+    Ok(util)
+}
+
+/// Options-specific metrics: returns on margin and premium, average Greek
+/// exposures and the call/put and long/short composition of the trade log.
+pub(crate) fn options_metrics(
+    closed: &[ClosedTrade],
+    records: &[TradeRecord],
+    greeks: &[Greek],
+) -> Result<OptionsSpecificMetrics, BacktestError> {
+    let mut metrics = OptionsSpecificMetrics::default();
+    let pnl = d_sum_iter(
+        closed.iter().map(|t| t.pnl),
+        "backtest::analysis::opts::pnl",
+    )?;
+    let margin = d_sum_iter(
+        closed.iter().map(|t| t.margin),
+        "backtest::analysis::opts::margin",
+    )?;
+    if margin > Decimal::ZERO {
+        metrics.return_on_margin = Some(d_div(pnl, margin, "backtest::analysis::opts::rom")?);
+    }
+    let premium = d_sum_iter(
+        closed.iter().map(|t| t.net_credit.abs()),
+        "backtest::analysis::opts::premium",
+    )?;
+    if premium > Decimal::ZERO {
+        metrics.return_on_premium = Some(d_div(pnl, premium, "backtest::analysis::opts::rop")?);
+    }
+    let credit = d_sum_iter(
+        closed.iter().filter(|t| !t.is_debit).map(|t| t.net_credit),
+        "backtest::analysis::opts::credit",
+    )?;
+    if credit > Decimal::ZERO {
+        let credit_pnl = d_sum_iter(
+            closed.iter().filter(|t| !t.is_debit).map(|t| t.pnl),
+            "backtest::analysis::opts::credit_pnl",
+        )?;
+        metrics.premium_capture = Some(d_div(
+            credit_pnl,
+            credit,
+            "backtest::analysis::opts::capture",
+        )?);
+    }
+
+    if !greeks.is_empty() {
+        let avg = |f: fn(&Greek) -> Decimal| -> Result<Option<Decimal>, BacktestError> {
+            mean_decimal(&greeks.iter().map(f).collect::<Vec<_>>())
+        };
+        metrics.avg_delta_exposure = avg(|g| g.delta)?;
+        metrics.avg_gamma_exposure = avg(|g| g.gamma)?;
+        metrics.avg_theta_exposure = avg(|g| g.theta)?;
+        metrics.avg_vega_exposure = avg(|g| g.vega)?;
+        metrics.avg_vanna_exposure = avg(|g| g.vanna)?;
+        metrics.avg_vomma_exposure = avg(|g| g.vomma)?;
+        metrics.avg_veta_exposure = avg(|g| g.veta)?;
+        metrics.avg_charm_exposure = avg(|g| g.charm)?;
+        metrics.avg_color_exposure = avg(|g| g.color)?;
+    }
+
+    let legs: usize = closed.iter().map(|t| t.legs).sum();
+    if legs > 0 {
+        let share =
+            |count: usize, op: &'static str| d_div(Decimal::from(count), Decimal::from(legs), op);
+        let calls: usize = closed.iter().map(|t| t.calls).sum();
+        let puts: usize = closed.iter().map(|t| t.puts).sum();
+        let longs: usize = closed.iter().map(|t| t.long_legs).sum();
+        let shorts: usize = closed.iter().map(|t| t.short_legs).sum();
+        metrics.calls_percentage = Some(share(calls, "backtest::analysis::opts::calls")?);
+        metrics.puts_percentage = Some(share(puts, "backtest::analysis::opts::puts")?);
+        metrics.long_percentage = Some(share(longs, "backtest::analysis::opts::longs")?);
+        metrics.short_percentage = Some(share(shorts, "backtest::analysis::opts::shorts")?);
+    } else if !records.is_empty() {
+        let total = Decimal::from(records.len());
+        let longs = records.iter().filter(|r| r.position.is_long()).count();
+        metrics.long_percentage = Some(d_div(
+            Decimal::from(longs),
+            total,
+            "backtest::analysis::opts::open_longs",
+        )?);
+        metrics.short_percentage = Some(d_div(
+            Decimal::from(records.len() - longs),
+            total,
+            "backtest::analysis::opts::open_shorts",
+        )?);
+    }
+    Ok(metrics)
+}
+
+/// Tail-risk metrics from per-bar returns and the drawdown curve.
+pub(crate) fn advanced_risk_metrics(
+    returns: &[f64],
+    closed: &[ClosedTrade],
+    drawdown_curve: &[Decimal],
+) -> Result<AdvancedRiskMetrics, BacktestError> {
+    let mut metrics = AdvancedRiskMetrics::default();
+
+    let mut streak = 0usize;
+    for trade in closed {
+        if trade.pnl < Decimal::ZERO {
+            streak += 1;
+            metrics.max_consecutive_losses = metrics.max_consecutive_losses.max(streak);
+        } else {
+            streak = 0;
+        }
+    }
+
+    if !returns.is_empty() {
+        let mut sorted = returns.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        if let Some(q05) = quantile(&sorted, 0.05) {
+            metrics.value_at_risk_95 = Some(to_decimal(
+                (-q05).max(0.0),
+                "backtest::analysis::risk::var95",
+            )?);
+            let tail: Vec<f64> = sorted.iter().copied().filter(|r| *r <= q05).collect();
+            metrics.expected_shortfall = Some(to_decimal(
+                (-mean(&tail)).max(0.0),
+                "backtest::analysis::risk::es",
+            )?);
+            if let Some(q95) = quantile(&sorted, 0.95)
+                && q05 < 0.0
+            {
+                metrics.tail_ratio = Some(to_decimal(
+                    q95 / q05.abs(),
+                    "backtest::analysis::risk::tail_ratio",
+                )?);
+            }
+        }
+        if let Some(q01) = quantile(&sorted, 0.01) {
+            metrics.value_at_risk_99 = Some(to_decimal(
+                (-q01).max(0.0),
+                "backtest::analysis::risk::var99",
+            )?);
+        }
+    }
+
+    if !drawdown_curve.is_empty() {
+        let dd: Vec<f64> = drawdown_curve.iter().map(|d| to_f64(*d)).collect();
+        let ulcer = (dd.iter().map(|d| d * d).sum::<f64>() / dd.len() as f64).sqrt();
+        metrics.ulcer_index = Some(to_decimal(ulcer, "backtest::analysis::risk::ulcer")?);
+        metrics.pain_index = Some(to_decimal(mean(&dd), "backtest::analysis::risk::pain")?);
+    }
+    Ok(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use rust_decimal_macros::dec;
+
+    fn stamps(n: usize) -> Vec<DateTime<Utc>> {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        (0..n).map(|i| start + Duration::days(i as i64)).collect()
+    }
+
+    #[test]
+    fn test_drawdown_analysis_single_recovered_event() {
+        let equity = vec![dec!(100), dec!(110), dec!(99), dec!(104.5), dec!(120)];
+        let (analysis, curve) = drawdown_analysis(&stamps(5), &equity).unwrap();
+        assert_eq!(analysis.max_drawdown, dec!(0.1));
+        assert_eq!(analysis.drawdowns.len(), 1);
+        assert!(analysis.drawdowns[0].recovery_date.is_some());
+        assert_eq!(analysis.time_to_max_drawdown, Positive::TWO);
+        assert_eq!(curve.len(), 5);
+        assert_eq!(curve[2], dec!(0.1));
+        assert_eq!(analysis.underwater_percentage, dec!(0.4));
+    }
+
+    #[test]
+    fn test_drawdown_analysis_open_event_has_no_recovery() {
+        let equity = vec![dec!(100), dec!(90), dec!(80)];
+        let (analysis, _) = drawdown_analysis(&stamps(3), &equity).unwrap();
+        assert_eq!(analysis.max_drawdown, dec!(0.2));
+        assert!(analysis.drawdowns[0].recovery_date.is_none());
+        assert!(analysis.recovery_duration.is_none());
+    }
+
+    #[test]
+    fn test_bar_returns_simple() {
+        let r = bar_returns(&[dec!(100), dec!(110), dec!(99)]).unwrap();
+        assert_eq!(r.len(), 2);
+        assert!((r[0] - 0.1).abs() < 1e-12);
+        assert!((r[1] + 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_advanced_risk_metrics_var_is_loss_magnitude() {
+        let returns: Vec<f64> = (0..100).map(|i| (i as f64 - 50.0) / 1000.0).collect();
+        let m = advanced_risk_metrics(&returns, &[], &[dec!(0), dec!(0.1)]).unwrap();
+        let var95 = m.value_at_risk_95.unwrap();
+        let var99 = m.value_at_risk_99.unwrap();
+        assert!(var95 > Decimal::ZERO);
+        assert!(var99 >= var95);
+        assert!(m.expected_shortfall.unwrap() >= var95);
+        assert_eq!(m.pain_index, Some(dec!(0.05)));
+    }
+
+    #[test]
+    fn test_mean_decimal_empty_is_none() {
+        assert_eq!(mean_decimal(&[]).unwrap(), None);
+        assert_eq!(mean_decimal(&[dec!(1), dec!(3)]).unwrap(), Some(dec!(2)));
+    }
+}
@@ -0,0 +1,1413 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Event-driven, bar-by-bar backtesting engine.
+//!
+//! The [`Backtester`] walks a chronologically ordered series of
+//! [`MarketSnapshot`]s (an [`OptionChain`] observed at a point in time). On
+//! every bar it marks the open strategies to market, closes those whose
+//! [`ExitPolicy`] fired or whose legs expired, and then asks an
+//! [`EntryRule`] whether a new strategy should be opened. When the data is
+//! exhausted the collected equity curve and trade log are reduced into a
+//! fully populated [`BacktestResult`].
+
+use crate::backtesting::analysis::{
+    advanced_risk_metrics, bar_returns, capital_utilization, drawdown_analysis, mean_decimal,
+    options_metrics, performance_metrics, trade_statistics,
+};
+use crate::backtesting::results::BacktestResult;
+use crate::backtesting::types::{ExitReason, TimeSeriesData, TradeRecord, VolatilityData};
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::error::{BacktestError, ChainError};
+use crate::greeks::{Greek, Greeks, GreeksSnapshot};
+use crate::model::decimal::{d_add, d_div, d_mul, d_sub, d_sum_iter};
+use crate::model::types::{OptionStyle, Side};
+use crate::model::{ExpirationDate, Options, Position};
+use crate::pricing::{PricingEngine, price_option};
+use crate::risk::RegTMargin;
+use crate::simulation::{ExitPolicy, check_exit_policy};
+use crate::strategies::base::Strategies;
+use crate::utils::OhlcvCandle;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use tracing::{debug, trace};
+use uuid::Uuid;
+
+/// Number of seconds in a calendar day, used to express remaining life in days.
+const SECONDS_PER_DAY: Decimal = dec!(86400);
+
+/// A point-in-time view of the market used to drive the backtest.
+///
+/// Each snapshot pairs the timestamp of the bar with the option chain
+/// observed at that time. The chain's `underlying_price` is the spot used to
+/// mark every open leg.
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    /// Timestamp of the bar.
+    pub timestamp: DateTime<Utc>,
+    /// Option chain observed at `timestamp`.
+    pub chain: OptionChain,
+}
+
+impl MarketSnapshot {
+    /// Creates a new snapshot from a timestamp and an option chain.
+    #[must_use]
+    pub fn new(timestamp: DateTime<Utc>, chain: OptionChain) -> Self {
+        Self { timestamp, chain }
+    }
+
+    /// Builds one snapshot per OHLCV candle using a chain generator.
+    ///
+    /// The candle's `date` and `time` fields form the bar timestamp and the
+    /// `generator` turns each candle into the option chain observed at its
+    /// close (typically via [`OptionChain::build_chain`] with the candle's
+    /// close as underlying price).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BacktestError::NoMarketData`] when `candles` is empty,
+    /// [`BacktestError::InvalidMarketData`] when a candle time cannot be
+    /// parsed as `HH:MM:SS` (or `HH:MM`), and propagates any
+    /// [`ChainError`] raised by `generator` as [`BacktestError::Chain`].
+    pub fn from_candles<F>(
+        candles: &[OhlcvCandle],
+        mut generator: F,
+    ) -> Result<Vec<Self>, BacktestError>
+    where
+        F: FnMut(&OhlcvCandle) -> Result<OptionChain, ChainError>,
+    {
+        if candles.is_empty() {
+            return Err(BacktestError::NoMarketData {
+                context: "backtesting::MarketSnapshot::from_candles",
+            });
+        }
+        candles
+            .iter()
+            .map(|candle| {
+                let time = NaiveTime::parse_from_str(&candle.time, "%H:%M:%S")
+                    .or_else(|_| NaiveTime::parse_from_str(&candle.time, "%H:%M"))
+                    .map_err(|e| {
+                        BacktestError::invalid_market_data(&format!(
+                            "invalid candle time '{}': {e}",
+                            candle.time
+                        ))
+                    })?;
+                let timestamp = candle.date.and_time(time).and_utc();
+                Ok(Self::new(timestamp, generator(candle)?))
+            })
+            .collect()
+    }
+}
+
+/// Determines how open legs are marked to market on every bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum MarkMode {
+    /// Use the chain's mid quote when the snapshot lists the leg's exact
+    /// strike for the leg's expiration, otherwise fall back to the model.
+    #[default]
+    QuotesThenModel,
+    /// Always reprice legs with the configured pricing engine, using the
+    /// chain's implied volatility at the closest strike when the chain is
+    /// listed for the leg's expiration.
+    Model,
+}
+
+/// Configuration of a [`Backtester`] run.
+#[derive(Debug, Clone)]
+pub struct BacktestConfig {
+    /// Name reported in [`BacktestResult::strategy_name`].
+    pub strategy_name: String,
+
+    /// Starting account equity.
+    pub initial_capital: Positive,
+
+    /// Exit policy evaluated on every bar for every open strategy.
+    pub exit_policy: ExitPolicy,
+
+    /// Maximum number of strategies that may be open at the same time.
+    pub max_open_trades: usize,
+
+    /// Adverse price adjustment charged per contract on entry and on exit.
+    pub slippage_per_contract: Positive,
+
+    /// Annualised risk-free rate used for the Sharpe and Sortino ratios.
+    pub risk_free_rate: Decimal,
+
+    /// How open legs are marked to market.
+    pub mark_mode: MarkMode,
+
+    /// Engine used whenever a leg is repriced theoretically.
+    pub pricing_engine: PricingEngine,
+
+    /// Whether to record portfolio Greek exposures on every bar.
+    pub track_greeks: bool,
+
+    /// Whether strategies still open on the last bar are closed at its marks.
+    pub close_open_trades_at_end: bool,
+
+    /// Broker margin model for the capital committed by each strategy. When
+    /// `None`, the maximum loss (or short notional) is used.
+    pub margin_model: Option<RegTMargin>,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            strategy_name: "Backtest".to_string(),
+            initial_capital: Positive::TEN_THOUSAND,
+            exit_policy: ExitPolicy::Expiration,
+            max_open_trades: 1,
+            slippage_per_contract: Positive::ZERO,
+            risk_free_rate: Decimal::ZERO,
+            mark_mode: MarkMode::default(),
+            pricing_engine: PricingEngine::ClosedFormBS,
+            track_greeks: true,
+            close_open_trades_at_end: true,
+            margin_model: None,
+        }
+    }
+}
+
+impl BacktestConfig {
+    /// Creates a configuration with the given name, capital and exit policy.
+    #[must_use]
+    pub fn new(strategy_name: &str, initial_capital: Positive, exit_policy: ExitPolicy) -> Self {
+        Self {
+            strategy_name: strategy_name.to_string(),
+            initial_capital,
+            exit_policy,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the maximum number of concurrently open strategies.
+    #[must_use]
+    pub fn with_max_open_trades(mut self, max_open_trades: usize) -> Self {
+        self.max_open_trades = max_open_trades;
+        self
+    }
+
+    /// Sets the per-contract slippage charged on entry and exit.
+    #[must_use]
+    pub fn with_slippage(mut self, slippage_per_contract: Positive) -> Self {
+        self.slippage_per_contract = slippage_per_contract;
+        self
+    }
+
+    /// Sets the annualised risk-free rate used by risk-adjusted ratios.
+    #[must_use]
+    pub fn with_risk_free_rate(mut self, risk_free_rate: Decimal) -> Self {
+        self.risk_free_rate = risk_free_rate;
+        self
+    }
+
+    /// Sets how open legs are marked to market.
+    #[must_use]
+    pub fn with_mark_mode(mut self, mark_mode: MarkMode) -> Self {
+        self.mark_mode = mark_mode;
+        self
+    }
+
+    /// Sets the engine used for theoretical repricing.
+    #[must_use]
+    pub fn with_pricing_engine(mut self, pricing_engine: PricingEngine) -> Self {
+        self.pricing_engine = pricing_engine;
+        self
+    }
+
+    /// Enables or disables per-bar Greek exposure tracking.
+    #[must_use]
+    pub fn with_track_greeks(mut self, track_greeks: bool) -> Self {
+        self.track_greeks = track_greeks;
+        self
+    }
+
+    /// Enables or disables closing the remaining strategies on the last bar.
+    #[must_use]
+    pub fn with_close_open_trades_at_end(mut self, close: bool) -> Self {
+        self.close_open_trades_at_end = close;
+        self
+    }
+
+    /// Commits the Reg-T buying-power reduction of each strategy instead of
+    /// its maximum loss, so return on margin matches a retail account.
+    #[must_use]
+    pub fn with_margin_model(mut self, margin_model: RegTMargin) -> Self {
+        self.margin_model = Some(margin_model);
+        self
+    }
+
+    fn validate(&self) -> Result<(), BacktestError> {
+        if self.initial_capital.is_zero() {
+            return Err(BacktestError::invalid_config(
+                "initial capital must be greater than zero",
+            ));
+        }
+        if self.max_open_trades == 0 {
+            return Err(BacktestError::invalid_config(
+                "max_open_trades must be at least one",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Information handed to an [`EntryRule`] on every bar.
+#[derive(Debug, Clone, Copy)]
+pub struct EntryContext<'a> {
+    /// The current market snapshot.
+    pub snapshot: &'a MarketSnapshot,
+    /// Zero-based index of the current bar.
+    pub bar_index: usize,
+    /// Number of strategies currently open.
+    pub open_trades: usize,
+    /// Current marked-to-market account equity.
+    pub equity: Decimal,
+    /// Equity not committed as capital at risk by open strategies.
+    pub available_capital: Decimal,
+}
+
+/// Decides, bar by bar, whether a new strategy should be opened.
+///
+/// Any `FnMut(&EntryContext) -> Result<Option<S>, BacktestError>` closure is
+/// an entry rule, so simple rules can be written inline.
+pub trait EntryRule<S: Strategies> {
+    /// Returns the strategy to open on this bar, or `None` to stay flat.
+    ///
+    /// # Errors
+    ///
+    /// Implementations return a [`BacktestError`] when the strategy cannot
+    /// be constructed from the snapshot; the backtest is aborted.
+    fn evaluate(&mut self, context: &EntryContext<'_>) -> Result<Option<S>, BacktestError>;
+}
+
+impl<S, F> EntryRule<S> for F
+where
+    S: Strategies,
+    F: FnMut(&EntryContext<'_>) -> Result<Option<S>, BacktestError>,
+{
+    fn evaluate(&mut self, context: &EntryContext<'_>) -> Result<Option<S>, BacktestError> {
+        self(context)
+    }
+}
+
+/// One option leg of an open strategy, with its absolute expiry resolved.
+#[derive(Debug, Clone)]
+struct OpenLeg {
+    position: Position,
+    expiry: DateTime<Utc>,
+    entry_greeks: Option<GreeksSnapshot>,
+}
+
+/// Mark of a single leg on a given bar.
+#[derive(Debug, Clone)]
+struct LegMark {
+    price: Positive,
+    option: Options,
+    days_left: Positive,
+}
+
+/// A strategy opened by the engine and not yet closed.
+#[derive(Debug, Clone)]
+struct OpenTrade<S> {
+    id: Uuid,
+    strategy: S,
+    legs: Vec<OpenLeg>,
+    entry_bar: usize,
+    entry_time: DateTime<Utc>,
+    initial_premium: Decimal,
+    net_credit: Decimal,
+    is_debit: bool,
+    margin: Decimal,
+}
+
+/// Strategy-level summary of a closed trade used to build the statistics.
+#[derive(Debug, Clone)]
+pub(crate) struct ClosedTrade {
+    pub(crate) entry_time: DateTime<Utc>,
+    pub(crate) exit_time: DateTime<Utc>,
+    pub(crate) pnl: Decimal,
+    pub(crate) return_on_margin: Decimal,
+    pub(crate) net_credit: Decimal,
+    pub(crate) margin: Decimal,
+    pub(crate) is_debit: bool,
+    pub(crate) legs: usize,
+    pub(crate) calls: usize,
+    pub(crate) puts: usize,
+    pub(crate) long_legs: usize,
+    pub(crate) short_legs: usize,
+}
+
+/// Bar-by-bar backtester for any strategy implementing [`Strategies`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use optionstratlib::backtesting::{BacktestConfig, Backtester, EntryContext, MarketSnapshot};
+/// use optionstratlib::error::BacktestError;
+/// use optionstratlib::simulation::ExitPolicy;
+/// use optionstratlib::strategies::ShortPut;
+/// use positive::Positive;
+/// use rust_decimal_macros::dec;
+///
+/// # fn run(snapshots: Vec<MarketSnapshot>) -> Result<(), BacktestError> {
+/// let config = BacktestConfig::new(
+///     "Short put",
+///     Positive::TEN_THOUSAND,
+///     ExitPolicy::profit_or_loss(dec!(0.5), dec!(1.0)),
+/// );
+/// let entry = |ctx: &EntryContext<'_>| -> Result<Option<ShortPut>, BacktestError> {
+///     // Build a ShortPut from `ctx.snapshot.chain` here.
+///     Ok(None)
+/// };
+/// let result = Backtester::new(config, entry).run(&snapshots)?;
+/// assert!(result.final_capital > dec!(0));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Backtester<S, E>
+where
+    S: Strategies,
+    E: EntryRule<S>,
+{
+    config: BacktestConfig,
+    entry_rule: E,
+    _strategy: PhantomData<S>,
+}
+
+impl<S, E> Backtester<S, E>
+where
+    S: Strategies,
+    E: EntryRule<S>,
+{
+    /// Creates a new backtester from a configuration and an entry rule.
+    #[must_use]
+    pub fn new(config: BacktestConfig, entry_rule: E) -> Self {
+        Self {
+            config,
+            entry_rule,
+            _strategy: PhantomData,
+        }
+    }
+
+    /// Returns the configuration used by this backtester.
+    #[must_use]
+    pub fn config(&self) -> &BacktestConfig {
+        &self.config
+    }
+
+    /// Runs the backtest over the given snapshots.
+    ///
+    /// On each bar the engine (1) marks every open strategy, (2) closes
+    /// strategies whose legs expired or whose exit policy fired, (3) asks the
+    /// entry rule for a new strategy when capacity and capital allow, and
+    /// (4) records equity, drawdown, capital at risk and Greek exposures.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BacktestError::InvalidConfig`] for an invalid configuration,
+    /// [`BacktestError::NoMarketData`] when `snapshots` is empty,
+    /// [`BacktestError::UnorderedSnapshots`] when timestamps are not strictly
+    /// increasing, [`BacktestError::InvalidEntry`] when the entry rule yields
+    /// a strategy without positions, and propagates pricing, Greeks,
+    /// position and arithmetic failures raised while marking legs.
+    #[tracing::instrument(skip(self, snapshots), fields(bars = snapshots.len()))]
+    pub fn run(&mut self, snapshots: &[MarketSnapshot]) -> Result<BacktestResult, BacktestError> {
+        self.config.validate()?;
+        let (first, last) = match (snapshots.first(), snapshots.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => {
+                return Err(BacktestError::NoMarketData {
+                    context: "backtesting::Backtester::run",
+                });
+            }
+        };
+        for (index, pair) in snapshots.windows(2).enumerate() {
+            if let [prev, next] = pair
+                && next.timestamp <= prev.timestamp
+            {
+                return Err(BacktestError::UnorderedSnapshots { index: index + 1 });
+            }
+        }
+
+        let initial_capital = self.config.initial_capital.to_dec();
+        let mut realized = Decimal::ZERO;
+        let mut open: Vec<OpenTrade<S>> = Vec::new();
+        let mut records: Vec<TradeRecord> = Vec::new();
+        let mut closed: Vec<ClosedTrade> = Vec::new();
+        let mut series = TimeSeriesData::default();
+        let mut greek_series: Vec<Greek> = Vec::new();
+        let mut entry_ivs: Vec<Decimal> = Vec::new();
+
+        let last_index = snapshots.len().saturating_sub(1);
+        for (bar, snapshot) in snapshots.iter().enumerate() {
+            // 1-2. Mark and close.
+            let mut still_open = Vec::with_capacity(open.len());
+            for trade in open.drain(..) {
+                let marks = self.mark_trade(&trade, snapshot)?;
+                let force_close = bar == last_index && self.config.close_open_trades_at_end;
+                match self.exit_reason(&trade, &marks, snapshot, bar, force_close)? {
+                    Some(reason) => {
+                        let summary =
+                            self.close_trade(&trade, &marks, snapshot, reason, &mut records)?;
+                        realized = d_add(realized, summary.pnl, "backtest::run::realized")?;
+                        closed.push(summary);
+                    }
+                    None => still_open.push(trade),
+                }
+            }
+            open = still_open;
+
+            // 3. Entry.
+            if open.len() < self.config.max_open_trades && bar < last_index {
+                let (equity, committed) = self.equity_and_margin(&open, snapshot, realized)?;
+                let context = EntryContext {
+                    snapshot,
+                    bar_index: bar,
+                    open_trades: open.len(),
+                    equity,
+                    available_capital: d_sub(equity, committed, "backtest::run::available")?,
+                };
+                if let Some(strategy) = self.entry_rule.evaluate(&context)? {
+                    match self.open_trade(strategy, snapshot, bar, context.available_capital)? {
+                        Some(trade) => {
+                            for leg in &trade.legs {
+                                entry_ivs.push(leg.position.option.implied_volatility.to_dec());
+                            }
+                            open.push(trade);
+                        }
+                        None => debug!(bar, "entry skipped: insufficient capital"),
+                    }
+                }
+            }
+
+            // 4. Record.
+            let (equity, committed) = self.equity_and_margin(&open, snapshot, realized)?;
+            series.timestamps.push(snapshot.timestamp);
+            series.equity_curve.push(equity);
+            series.margin_usage.push(committed);
+            series.position_count.push(open.len());
+            if self.config.track_greeks {
+                greek_series.push(self.portfolio_greeks(&open, snapshot)?);
+            }
+        }
+
+        // Trades still open when the data ends are reported as open records.
+        for trade in &open {
+            let marks = self.mark_trade(trade, last)?;
+            for (leg, mark) in trade.legs.iter().zip(marks.iter()) {
+                let pnl = leg.position.unrealized_pnl(mark.price)?;
+                records.push(TradeRecord {
+                    id: Uuid::new_v4(),
+                    entry_date: trade.entry_time,
+                    strategy: Some(trade.id),
+                    position: leg.position.clone(),
+                    profit_loss: Some(pnl),
+                    margin_required: Some(trade.margin),
+                    entry_greeks: leg.entry_greeks.clone(),
+                    notes: Some(trade.strategy.get_title()),
+                    ..TradeRecord::default()
+                });
+            }
+        }
+
+        let final_capital = *series.equity_curve.last().unwrap_or(&initial_capital);
+        let (drawdown, drawdown_curve) =
+            drawdown_analysis(&series.timestamps, &series.equity_curve)?;
+        series.drawdown_curve = drawdown_curve;
+        if self.config.track_greeks {
+            fill_greek_series(&mut series, &greek_series);
+        }
+        let returns = bar_returns(&series.equity_curve)?;
+        let general_performance = performance_metrics(
+            &series.timestamps,
+            &series.equity_curve,
+            &returns,
+            &closed,
+            drawdown.max_drawdown,
+            self.config.risk_free_rate,
+        )?;
+        let trade_statistics = trade_statistics(&closed)?;
+        let capital_utilization =
+            capital_utilization(&series.margin_usage, &records, &closed, initial_capital)?;
+        let options_metrics = options_metrics(&closed, &records, &greek_series)?;
+        let risk_metrics = advanced_risk_metrics(&returns, &closed, &series.drawdown_curve)?;
+        let volatility_data = VolatilityData {
+            implied_volatility_used: !entry_ivs.is_empty(),
+            avg_iv_traded: mean_decimal(&entry_ivs)?,
+            iv_percentile_traded: None,
+            iv_rank_traded: None,
+        };
+
+        let mut custom_metrics = HashMap::new();
+        custom_metrics.insert("bars".to_string(), Decimal::from(snapshots.len()));
+        custom_metrics.insert("open_trades_at_end".to_string(), Decimal::from(open.len()));
+        custom_metrics.insert(
+            "total_realized_pnl".to_string(),
+            d_sum_iter(closed.iter().map(|t| t.pnl), "backtest::run::custom::pnl")?,
+        );
+
+        Ok(BacktestResult {
+            general_performance,
+            options_metrics,
+            trade_statistics,
+            drawdown_analysis: drawdown,
+            capital_utilization,
+            time_series: series,
+            trades: records,
+            market_conditions: None,
+            volatility_data: Some(volatility_data),
+            risk_metrics: Some(risk_metrics),
+            monte_carlo_simulation: None,
+            strategy_name: self.config.strategy_name.clone(),
+            test_period_start: first.timestamp,
+            test_period_end: last.timestamp,
+            initial_capital,
+            final_capital,
+            custom_metrics,
+        })
+    }
+
+    /// Opens `strategy` on `snapshot`, applying entry slippage and resolving
+    /// every leg's absolute expiry. Returns `None` when the strategy's
+    /// capital at risk exceeds `available`.
+    fn open_trade(
+        &self,
+        strategy: S,
+        snapshot: &MarketSnapshot,
+        bar: usize,
+        available: Decimal,
+    ) -> Result<Option<OpenTrade<S>>, BacktestError> {
+        let positions: Vec<Position> = strategy.get_positions()?.into_iter().cloned().collect();
+        if positions.is_empty() {
+            return Err(BacktestError::invalid_entry(&format!(
+                "strategy '{}' has no positions",
+                strategy.get_title()
+            )));
+        }
+        let margin = match &self.config.margin_model {
+            Some(model) => {
+                model
+                    .positions_margin(&positions, &[])?
+                    .buying_power_reduction
+            }
+            None => capital_at_risk(&strategy, &positions)?,
+        };
+        if margin > available {
+            return Ok(None);
+        }
+
+        let slippage = self.config.slippage_per_contract;
+        let mut legs = Vec::with_capacity(positions.len());
+        let mut net_credit = Decimal::ZERO;
+        for mut position in positions {
+            position.premium = match position.option.side {
+                Side::Long => position.premium + slippage,
+                Side::Short => floor_sub(position.premium, slippage),
+            };
+            position.date = snapshot.timestamp;
+            let expiry = resolve_expiry(&position.option.expiration_date, snapshot.timestamp)?;
+            let flow = d_mul(
+                position.premium.to_dec(),
+                position.option.quantity.to_dec(),
+                "backtest::open_trade::flow",
+            )?;
+            net_credit = match position.option.side {
+                Side::Long => d_sub(net_credit, flow, "backtest::open_trade::net_credit")?,
+                Side::Short => d_add(net_credit, flow, "backtest::open_trade::net_credit")?,
+            };
+            let entry_greeks = if self.config.track_greeks {
+                Some(greeks_snapshot(&position.option.greeks()?))
+            } else {
+                None
+            };
+            legs.push(OpenLeg {
+                position,
+                expiry,
+                entry_greeks,
+            });
+        }
+        trace!(bar, %net_credit, %margin, "opening strategy");
+        Ok(Some(OpenTrade {
+            id: Uuid::new_v4(),
+            strategy,
+            legs,
+            entry_bar: bar,
+            entry_time: snapshot.timestamp,
+            initial_premium: net_credit.abs(),
+            net_credit,
+            is_debit: net_credit < Decimal::ZERO,
+            margin,
+        }))
+    }
+
+    /// Marks every leg of `trade` on `snapshot`.
+    fn mark_trade(
+        &self,
+        trade: &OpenTrade<S>,
+        snapshot: &MarketSnapshot,
+    ) -> Result<Vec<LegMark>, BacktestError> {
+        trade
+            .legs
+            .iter()
+            .map(|leg| self.mark_leg(leg, snapshot))
+            .collect()
+    }
+
+    /// Marks a single leg: intrinsic value at or after expiry, the chain's
+    /// mid quote when allowed and available, and the pricing engine otherwise.
+    fn mark_leg(&self, leg: &OpenLeg, snapshot: &MarketSnapshot) -> Result<LegMark, BacktestError> {
+        let chain = &snapshot.chain;
+        let days_left = days_between(snapshot.timestamp, leg.expiry)?;
+        let mut option = leg.position.option.clone();
+        option.underlying_price = chain.underlying_price;
+        option.expiration_date = ExpirationDate::Days(days_left);
+        if let Some(rate) = chain.risk_free_rate {
+            option.risk_free_rate = rate;
+        }
+        if let Ok(data) = chain.get_optiondata_with_strike(&option.strike_price)
+            && quotes_expiry(chain, data, snapshot.timestamp, leg.expiry)
+            && !data.implied_volatility.is_zero()
+        {
+            option.implied_volatility = data.implied_volatility;
+        }
+
+        if days_left.is_zero() {
+            let price = intrinsic_per_contract(&option);
+            return Ok(LegMark {
+                price,
+                option,
+                days_left,
+            });
+        }
+
+        if self.config.mark_mode == MarkMode::QuotesThenModel
+            && let Some(price) = quoted_mid(chain, &option, snapshot.timestamp, leg.expiry)
+        {
+            return Ok(LegMark {
+                price,
+                option,
+                days_left,
+            });
+        }
+
+        let price = price_option(&option, &self.config.pricing_engine)?;
+        Ok(LegMark {
+            price,
+            option,
+            days_left,
+        })
+    }
+
+    /// Determines whether `trade` must be closed on this bar.
+    fn exit_reason(
+        &self,
+        trade: &OpenTrade<S>,
+        marks: &[LegMark],
+        snapshot: &MarketSnapshot,
+        bar: usize,
+        force_close: bool,
+    ) -> Result<Option<ExitReason>, BacktestError> {
+        let days_left = marks
+            .iter()
+            .map(|m| m.days_left)
+            .min()
+            .unwrap_or(Positive::ZERO);
+        if days_left.is_zero() {
+            return Ok(Some(ExitReason::Expiration));
+        }
+
+        let mut current_value = Decimal::ZERO;
+        for (leg, mark) in trade.legs.iter().zip(marks.iter()) {
+            let flow = d_mul(
+                mark.price.to_dec(),
+                leg.position.option.quantity.to_dec(),
+                "backtest::exit_reason::flow",
+            )?;
+            current_value = match leg.position.option.side {
+                Side::Long => d_sub(current_value, flow, "backtest::exit_reason::value")?,
+                Side::Short => d_add(current_value, flow, "backtest::exit_reason::value")?,
+            };
+        }
+
+        let bars_held = bar.saturating_sub(trade.entry_bar);
+        if let Some(triggered) = check_exit_policy(
+            &self.config.exit_policy,
+            trade.initial_premium,
+            current_value.abs(),
+            bars_held,
+            days_left,
+            snapshot.chain.underlying_price,
+            trade.is_debit,
+        ) {
+            return Ok(Some(exit_reason_for(&triggered)));
+        }
+
+        if force_close {
+            return Ok(Some(ExitReason::ManualClose));
+        }
+        Ok(None)
+    }
+
+    /// Closes `trade` at `marks`, appending one [`TradeRecord`] per leg and
+    /// returning the strategy-level summary.
+    fn close_trade(
+        &self,
+        trade: &OpenTrade<S>,
+        marks: &[LegMark],
+        snapshot: &MarketSnapshot,
+        reason: ExitReason,
+        records: &mut Vec<TradeRecord>,
+    ) -> Result<ClosedTrade, BacktestError> {
+        let slippage = if reason == ExitReason::Expiration {
+            Positive::ZERO
+        } else {
+            self.config.slippage_per_contract
+        };
+        let duration = days_between(trade.entry_time, snapshot.timestamp)?;
+        let mut pnl = Decimal::ZERO;
+        let (mut calls, mut puts, mut long_legs, mut short_legs) = (0, 0, 0, 0);
+
+        for (leg, mark) in trade.legs.iter().zip(marks.iter()) {
+            let exit_price = match leg.position.option.side {
+                Side::Long => {
+                    long_legs += 1;
+                    floor_sub(mark.price, slippage)
+                }
+                Side::Short => {
+                    short_legs += 1;
+                    mark.price + slippage
+                }
+            };
+            match leg.position.option.option_style {
+                OptionStyle::Call => calls += 1,
+                OptionStyle::Put => puts += 1,
+            }
+            let leg_pnl = leg.position.unrealized_pnl(exit_price)?;
+            pnl = d_add(pnl, leg_pnl, "backtest::close_trade::pnl")?;
+            let exit_greeks = if self.config.track_greeks && !mark.days_left.is_zero() {
+                Some(greeks_snapshot(&mark.option.greeks()?))
+            } else {
+                None
+            };
+            let slippage_cost = d_mul(
+                slippage.to_dec(),
+                leg.position.option.quantity.to_dec(),
+                "backtest::close_trade::slippage",
+            )?;
+            records.push(TradeRecord {
+                id: Uuid::new_v4(),
+                entry_date: trade.entry_time,
+                exit_date: Some(snapshot.timestamp),
+                duration: Some(duration),
+                strategy: Some(trade.id),
+                position: leg.position.clone(),
+                exit_price: Some(exit_price.to_dec()),
+                slippage: Some(slippage_cost),
+                profit_loss: Some(leg_pnl),
+                return_percentage: None,
+                margin_required: Some(trade.margin),
+                exit_reason: Some(reason.clone()),
+                notes: Some(trade.strategy.get_title()),
+                entry_greeks: leg.entry_greeks.clone(),
+                exit_greeks,
+            });
+        }
+
+        let return_on_margin = if trade.margin > Decimal::ZERO {
+            d_div(pnl, trade.margin, "backtest::close_trade::return")?
+        } else {
+            Decimal::ZERO
+        };
+        let first_leg = records.len().saturating_sub(trade.legs.len());
+        for record in records.iter_mut().skip(first_leg) {
+            record.return_percentage = Some(return_on_margin);
+        }
+        debug!(%pnl, ?reason, "closed strategy");
+
+        Ok(ClosedTrade {
+            entry_time: trade.entry_time,
+            exit_time: snapshot.timestamp,
+            pnl,
+            return_on_margin,
+            net_credit: trade.net_credit,
+            margin: trade.margin,
+            is_debit: trade.is_debit,
+            legs: trade.legs.len(),
+            calls,
+            puts,
+            long_legs,
+            short_legs,
+        })
+    }
+
+    /// Returns the marked-to-market equity and the capital committed to the
+    /// open strategies.
+    fn equity_and_margin(
+        &self,
+        open: &[OpenTrade<S>],
+        snapshot: &MarketSnapshot,
+        realized: Decimal,
+    ) -> Result<(Decimal, Decimal), BacktestError> {
+        let mut equity = d_add(
+            self.config.initial_capital.to_dec(),
+            realized,
+            "backtest::equity::realized",
+        )?;
+        let mut committed = Decimal::ZERO;
+        for trade in open {
+            let marks = self.mark_trade(trade, snapshot)?;
+            for (leg, mark) in trade.legs.iter().zip(marks.iter()) {
+                let unrealized = leg.position.unrealized_pnl(mark.price)?;
+                equity = d_add(equity, unrealized, "backtest::equity::unrealized")?;
+            }
+            committed = d_add(committed, trade.margin, "backtest::equity::margin")?;
+        }
+        Ok((equity, committed))
+    }
+
+    /// Aggregates the Greeks of every live leg on `snapshot`.
+    fn portfolio_greeks(
+        &self,
+        open: &[OpenTrade<S>],
+        snapshot: &MarketSnapshot,
+    ) -> Result<Greek, BacktestError> {
+        let mut options = Vec::new();
+        for trade in open {
+            for mark in self.mark_trade(trade, snapshot)? {
+                if !mark.days_left.is_zero() {
+                    options.push(mark.option);
+                }
+            }
+        }
+        let book = LiveLegs(options);
+        Ok(book.greeks()?)
+    }
+}
+
+/// Thin wrapper so a set of revalued legs can reuse the [`Greeks`] sums.
+struct LiveLegs(Vec<Options>);
+
+impl Greeks for LiveLegs {
+    fn get_options(&self) -> Result<Vec<&Options>, crate::error::GreeksError> {
+        Ok(self.0.iter().collect())
+    }
+}
+
+/// Converts an aggregated [`Greek`] into the serialisable snapshot stored in
+/// trade records.
+fn greeks_snapshot(greek: &Greek) -> GreeksSnapshot {
+    GreeksSnapshot {
+        delta: greek.delta,
+        gamma: greek.gamma,
+        theta: greek.theta,
+        vega: greek.vega,
+        rho: Some(greek.rho),
+        rho_d: Some(greek.rho_d),
+        alpha: Some(greek.alpha),
+        vanna: greek.vanna,
+        vomma: greek.vomma,
+        veta: greek.veta,
+        charm: greek.charm,
+        color: greek.color,
+    }
+}
+
+/// Copies the per-bar Greek aggregates into the optional exposure series.
+fn fill_greek_series(series: &mut TimeSeriesData, greeks: &[Greek]) {
+    series.delta_exposure = Some(greeks.iter().map(|g| g.delta).collect());
+    series.gamma_exposure = Some(greeks.iter().map(|g| g.gamma).collect());
+    series.theta_exposure = Some(greeks.iter().map(|g| g.theta).collect());
+    series.vega_exposure = Some(greeks.iter().map(|g| g.vega).collect());
+    series.vanna_exposure = Some(greeks.iter().map(|g| g.vanna).collect());
+    series.vomma_exposure = Some(greeks.iter().map(|g| g.vomma).collect());
+    series.veta_exposure = Some(greeks.iter().map(|g| g.veta).collect());
+    series.charm_exposure = Some(greeks.iter().map(|g| g.charm).collect());
+    series.color_exposure = Some(greeks.iter().map(|g| g.color).collect());
+}
+
+/// Maps a triggered exit policy to the reason stored in the trade log.
+fn exit_reason_for(policy: &ExitPolicy) -> ExitReason {
+    match policy {
+        ExitPolicy::ProfitPercent(_) => ExitReason::TargetReached,
+        ExitPolicy::LossPercent(_) => ExitReason::StopLoss,
+        ExitPolicy::And(inner) | ExitPolicy::Or(inner) => match inner.first() {
+            Some(first) => exit_reason_for(first),
+            None => ExitReason::Other(policy.to_string()),
+        },
+        other => ExitReason::Other(other.to_string()),
+    }
+}
+
+/// Resolves a leg's expiration to an absolute timestamp. Relative
+/// expirations are anchored at the bar on which the strategy is opened.
+fn resolve_expiry(
+    expiration: &ExpirationDate,
+    anchor: DateTime<Utc>,
+) -> Result<DateTime<Utc>, BacktestError> {
+    match expiration {
+        ExpirationDate::DateTime(dt) => Ok(*dt),
+        ExpirationDate::Days(days) => {
+            let seconds = d_mul(days.to_dec(), SECONDS_PER_DAY, "backtest::resolve_expiry")?
+                .round()
+                .try_into()
+                .map_err(|_| {
+                    BacktestError::invalid_entry(&format!("expiration of {days} days is too far"))
+                })?;
+            Ok(anchor + Duration::seconds(seconds))
+        }
+    }
+}
+
+/// Calendar days from `from` to `to`, floored at zero.
+pub(crate) fn days_between(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Positive, BacktestError> {
+    let seconds = Decimal::from(to.signed_duration_since(from).num_seconds());
+    if seconds <= Decimal::ZERO {
+        return Ok(Positive::ZERO);
+    }
+    Ok(Positive::new_decimal(d_div(
+        seconds,
+        SECONDS_PER_DAY,
+        "backtest::days_between",
+    )?)?)
+}
+
+/// `lhs - rhs`, floored at zero.
+#[inline]
+fn floor_sub(lhs: Positive, rhs: Positive) -> Positive {
+    Positive::new_decimal(lhs.to_dec().saturating_sub(rhs.to_dec()).max(Decimal::ZERO))
+        .unwrap_or(Positive::ZERO)
+}
+
+/// Per-contract intrinsic value of `option` at its current underlying price.
+fn intrinsic_per_contract(option: &Options) -> Positive {
+    let spot = option.underlying_price.to_dec();
+    let strike = option.strike_price.to_dec();
+    let value = match option.option_style {
+        OptionStyle::Call => spot - strike,
+        OptionStyle::Put => strike - spot,
+    };
+    Positive::new_decimal(value.max(Decimal::ZERO)).unwrap_or(Positive::ZERO)
+}
+
+/// Whether the chain row `data` quotes contracts expiring on the same
+/// calendar day as a leg expiring at `expiry`. The row's own expiration is
+/// used when set, the chain's otherwise; relative expirations count from the
+/// snapshot `timestamp`.
+fn quotes_expiry(
+    chain: &OptionChain,
+    data: &OptionData,
+    timestamp: DateTime<Utc>,
+    expiry: DateTime<Utc>,
+) -> bool {
+    data.expiration_date
+        .or_else(|| chain.get_expiration())
+        .and_then(|listed| resolve_expiry(&listed, timestamp).ok())
+        .is_some_and(|listed| listed.date_naive() == expiry.date_naive())
+}
+
+/// Mid quote for `option` when the chain lists its exact strike for the
+/// leg's expiration.
+fn quoted_mid(
+    chain: &OptionChain,
+    option: &Options,
+    timestamp: DateTime<Utc>,
+    expiry: DateTime<Utc>,
+) -> Option<Positive> {
+    let data = chain
+        .options
+        .iter()
+        .find(|o| o.strike_price == option.strike_price)?;
+    if !quotes_expiry(chain, data, timestamp, expiry) {
+        return None;
+    }
+    let (call_mid, put_mid) = data.get_mid_prices();
+    match option.option_style {
+        OptionStyle::Call => call_mid.or_else(|| mid(data.call_bid, data.call_ask)),
+        OptionStyle::Put => put_mid.or_else(|| mid(data.put_bid, data.put_ask)),
+    }
+}
+
+fn mid(bid: Option<Positive>, ask: Option<Positive>) -> Option<Positive> {
+    match (bid, ask) {
+        (Some(bid), Some(ask)) => Some((bid + ask) / Positive::TWO),
+        (None, Some(only)) | (Some(only), None) => Some(only),
+        (None, None) => None,
+    }
+}
+
+/// Capital committed while `strategy` is open: its maximum loss when
+/// bounded, otherwise the notional of its short legs.
+fn capital_at_risk<S: Strategies>(
+    strategy: &S,
+    positions: &[Position],
+) -> Result<Decimal, BacktestError> {
+    if let Ok(max_loss) = strategy.get_max_loss()
+        && max_loss < Positive::MAX
+        && !max_loss.is_zero()
+    {
+        return Ok(max_loss.to_dec());
+    }
+    let mut notional = Decimal::ZERO;
+    for position in positions.iter().filter(|p| p.is_short()) {
+        let leg = d_mul(
+            position.option.strike_price.to_dec(),
+            position.option.quantity.to_dec(),
+            "backtest::capital_at_risk::notional",
+        )?;
+        notional = d_add(notional, leg, "backtest::capital_at_risk::sum")?;
+    }
+    if notional.is_zero() {
+        // Long-only structure: the debit paid is the capital at risk.
+        for position in positions {
+            notional = d_add(
+                notional,
+                position.total_cost()?.to_dec(),
+                "backtest::capital_at_risk::debit",
+            )?;
+        }
+    }
+    Ok(notional)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::strategies::{BullPutSpread, ShortPut};
+    use chrono::TimeZone;
+    use positive::{pos_or_panic, spos};
+
+    fn chain_at(price: f64, days_to_expiry: f64) -> OptionChain {
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(pos_or_panic!(price))),
+            Some(ExpirationDate::Days(pos_or_panic!(days_to_expiry))),
+            Some(dec!(0.0)),
+            spos!(0.0),
+            Some("TEST".to_string()),
+        );
+        let params = OptionChainBuildParams::new(
+            "TEST".to_string(),
+            None,
+            10,
+            spos!(5.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            price_params,
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).expect("chain")
+    }
+
+    fn snapshots(prices: &[f64]) -> Vec<MarketSnapshot> {
+        let start = Utc.with_ymd_and_hms(2025, 1, 2, 16, 0, 0).unwrap();
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let remaining = 30.0 - i as f64;
+                MarketSnapshot::new(
+                    start + Duration::days(i as i64),
+                    chain_at(*p, remaining.max(1.0)),
+                )
+            })
+            .collect()
+    }
+
+    fn short_put_entry(ctx: &EntryContext<'_>) -> Result<Option<ShortPut>, BacktestError> {
+        let chain = &ctx.snapshot.chain;
+        let strike = pos_or_panic!(95.0);
+        let data = chain.get_optiondata_with_strike(&strike)?;
+        let premium = data.put_bid.unwrap_or(Positive::ONE);
+        Ok(Some(ShortPut::new(
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            data.implied_volatility,
+            Positive::ONE,
+            chain.underlying_price,
+            dec!(0.0),
+            Positive::ZERO,
+            premium,
+            Positive::ZERO,
+            Positive::ZERO,
+        )?))
+    }
+
+    #[test]
+    fn test_backtest_no_data_is_rejected() {
+        let mut engine = Backtester::new(BacktestConfig::default(), short_put_entry);
+        let err = engine.run(&[]).unwrap_err();
+        assert!(matches!(err, BacktestError::NoMarketData { .. }));
+    }
+
+    #[test]
+    fn test_backtest_unordered_snapshots_are_rejected() {
+        let mut data = snapshots(&[100.0, 101.0]);
+        data.swap(0, 1);
+        let mut engine = Backtester::new(BacktestConfig::default(), short_put_entry);
+        let err = engine.run(&data).unwrap_err();
+        assert!(matches!(
+            err,
+            BacktestError::UnorderedSnapshots { index: 1 }
+        ));
+    }
+
+    #[test]
+    fn test_backtest_invalid_config_is_rejected() {
+        let config = BacktestConfig::default().with_max_open_trades(0);
+        let mut engine = Backtester::new(config, short_put_entry);
+        let err = engine.run(&snapshots(&[100.0, 101.0])).unwrap_err();
+        assert!(matches!(err, BacktestError::InvalidConfig { .. }));
+    }
+
+    #[test]
+    fn test_backtest_flat_rule_keeps_capital() {
+        let flat = |_: &EntryContext<'_>| -> Result<Option<ShortPut>, BacktestError> { Ok(None) };
+        let mut engine = Backtester::new(BacktestConfig::default(), flat);
+        let result = engine.run(&snapshots(&[100.0, 99.0, 101.0])).unwrap();
+        assert_eq!(result.final_capital, result.initial_capital);
+        assert!(result.trades.is_empty());
+        assert_eq!(result.time_series.equity_curve.len(), 3);
+        assert_eq!(result.drawdown_analysis.max_drawdown, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_backtest_short_put_rally_hits_profit_target() {
+        let config = BacktestConfig::new(
+            "Short put",
+            Positive::TEN_THOUSAND,
+            ExitPolicy::profit_or_loss(dec!(0.5), dec!(2.0)),
+        );
+        let prices = [100.0, 104.0, 108.0, 112.0, 116.0, 120.0];
+        let result = Backtester::new(config, short_put_entry)
+            .run(&snapshots(&prices))
+            .unwrap();
+
+        assert!(!result.trades.is_empty());
+        let first = &result.trades[0];
+        assert_eq!(first.exit_reason, Some(ExitReason::TargetReached));
+        assert!(first.profit_loss.unwrap() > Decimal::ZERO);
+        assert!(result.final_capital > result.initial_capital);
+        assert!(result.general_performance.total_return > Decimal::ZERO);
+        assert!(result.trade_statistics.winners >= 1);
+        assert_eq!(
+            result.time_series.equity_curve.len(),
+            result.time_series.timestamps.len()
+        );
+        assert!(result.time_series.delta_exposure.is_some());
+    }
+
+    #[test]
+    fn test_backtest_reg_t_margin_model() {
+        let prices = [100.0, 104.0, 108.0, 112.0, 116.0, 120.0];
+        let run = |config: BacktestConfig| {
+            Backtester::new(config, short_put_entry)
+                .run(&snapshots(&prices))
+                .unwrap()
+        };
+        let policy = ExitPolicy::profit_or_loss(dec!(0.5), dec!(2.0));
+        let notional = run(BacktestConfig::new(
+            "Short put",
+            Positive::TEN_THOUSAND,
+            policy.clone(),
+        ));
+        let reg_t = run(
+            BacktestConfig::new("Short put", Positive::TEN_THOUSAND, policy)
+                .with_margin_model(RegTMargin::default()),
+        );
+
+        let margin = reg_t.trades[0].margin_required.unwrap();
+        assert!(margin > dec!(10.0) && margin < dec!(20.0));
+        assert!(margin < notional.trades[0].margin_required.unwrap());
+        assert!(
+            reg_t.options_metrics.return_on_margin.unwrap()
+                > notional.options_metrics.return_on_margin.unwrap()
+        );
+    }
+
+    #[test]
+    fn test_backtest_short_put_selloff_records_drawdown() {
+        let config = BacktestConfig::new(
+            "Short put",
+            Positive::TEN_THOUSAND,
+            ExitPolicy::stop_loss(dec!(1.0)),
+        );
+        let prices = [100.0, 97.0, 94.0, 91.0, 88.0, 85.0];
+        let result = Backtester::new(config, short_put_entry)
+            .run(&snapshots(&prices))
+            .unwrap();
+
+        let first = &result.trades[0];
+        assert_eq!(first.exit_reason, Some(ExitReason::StopLoss));
+        assert!(first.profit_loss.unwrap() < Decimal::ZERO);
+        assert!(result.drawdown_analysis.max_drawdown > Decimal::ZERO);
+        assert!(!result.drawdown_analysis.drawdowns.is_empty());
+        assert!(result.trade_statistics.losers >= 1);
+        assert!(result.final_capital < result.initial_capital);
+    }
+
+    #[test]
+    fn test_backtest_spread_expires_and_is_recorded_per_leg() {
+        let entry = |ctx: &EntryContext<'_>| -> Result<Option<BullPutSpread>, BacktestError> {
+            if ctx.bar_index > 0 {
+                return Ok(None);
+            }
+            let chain = &ctx.snapshot.chain;
+            let long = chain.get_optiondata_with_strike(&pos_or_panic!(90.0))?;
+            let short = chain.get_optiondata_with_strike(&pos_or_panic!(95.0))?;
+            Ok(Some(BullPutSpread::new(
+                "TEST".to_string(),
+                chain.underlying_price,
+                long.strike_price,
+                short.strike_price,
+                ExpirationDate::Days(pos_or_panic!(3.0)),
+                long.implied_volatility,
+                dec!(0.0),
+                Positive::ZERO,
+                Positive::ONE,
+                long.put_ask.unwrap_or(Positive::ONE),
+                short.put_bid.unwrap_or(Positive::TWO),
+                Positive::ZERO,
+                Positive::ZERO,
+                Positive::ZERO,
+                Positive::ZERO,
+            )?))
+        };
+        let config =
+            BacktestConfig::new("Bull put", Positive::TEN_THOUSAND, ExitPolicy::Expiration);
+        let result = Backtester::new(config, entry)
+            .run(&snapshots(&[100.0, 101.0, 102.0, 103.0, 104.0, 105.0]))
+            .unwrap();
+
+        assert_eq!(result.trades.len(), 2);
+        let strategy_ids: Vec<_> = result.trades.iter().map(|t| t.strategy).collect();
+        assert_eq!(strategy_ids[0], strategy_ids[1]);
+        for record in &result.trades {
+            assert_eq!(record.exit_reason, Some(ExitReason::Expiration));
+        }
+        assert_eq!(result.trade_statistics.number_of_trades, 1);
+        assert_eq!(result.trade_statistics.spread_trades, 1);
+        assert_eq!(result.trade_statistics.put_trades, 1);
+        // Both puts expire worthless: the full credit is kept.
+        let total: Decimal = result.trades.iter().filter_map(|t| t.profit_loss).sum();
+        assert!(total > Decimal::ZERO);
+        assert_eq!(result.final_capital, result.initial_capital + total);
+    }
+
+    #[test]
+    fn test_backtest_open_trades_left_open_when_configured() {
+        let config = BacktestConfig::default().with_close_open_trades_at_end(false);
+        let result = Backtester::new(config, short_put_entry)
+            .run(&snapshots(&[100.0, 100.5, 101.0]))
+            .unwrap();
+        assert!(result.trades.iter().all(|t| t.exit_date.is_none()));
+        assert_eq!(result.trade_statistics.number_of_trades, 0);
+        assert_eq!(result.custom_metrics["open_trades_at_end"], Decimal::ONE);
+    }
+
+    #[test]
+    fn test_mark_leg_reads_quotes_only_for_the_listed_expiry() {
+        let timestamp = Utc.with_ymd_and_hms(2025, 1, 2, 16, 0, 0).unwrap();
+        let snapshot = MarketSnapshot::new(timestamp, chain_at(100.0, 30.0));
+        let listed = snapshot
+            .chain
+            .get_optiondata_with_strike(&Positive::HUNDRED)
+            .unwrap()
+            .implied_volatility;
+        let backtester = Backtester::new(
+            BacktestConfig::default().with_mark_mode(MarkMode::Model),
+            short_put_entry,
+        );
+        let mark = |days: i64| {
+            let option = Options {
+                strike_price: Positive::HUNDRED,
+                implied_volatility: pos_or_panic!(0.5),
+                ..Options::default()
+            };
+            let leg = OpenLeg {
+                position: Position::new(
+                    option,
+                    Positive::ONE,
+                    timestamp,
+                    Positive::ZERO,
+                    Positive::ZERO,
+                    None,
+                    None,
+                ),
+                expiry: timestamp + Duration::days(days),
+                entry_greeks: None,
+            };
+            backtester.mark_leg(&leg, &snapshot).unwrap()
+        };
+
+        assert_ne!(listed, pos_or_panic!(0.5));
+        assert_eq!(mark(30).option.implied_volatility, listed);
+        // The back-month leg is not listed in this snapshot: it keeps its
+        // own volatility instead of the front month's.
+        assert_eq!(mark(60).option.implied_volatility, pos_or_panic!(0.5));
+    }
+
+    #[test]
+    fn test_market_snapshot_from_candles() {
+        let candles = vec![
+            OhlcvCandle {
+                date: chrono::NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+                time: "16:00:00".to_string(),
+                open: dec!(100),
+                high: dec!(101),
+                low: dec!(99),
+                close: dec!(100.5),
+                volume: 1000,
+            },
+            OhlcvCandle {
+                date: chrono::NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(),
+                time: "16:00".to_string(),
+                open: dec!(100.5),
+                high: dec!(102),
+                low: dec!(100),
+                close: dec!(101.5),
+                volume: 1000,
+            },
+        ];
+        let snaps = MarketSnapshot::from_candles(&candles, |c| {
+            Ok(chain_at(c.close.try_into().unwrap_or(100.0), 30.0))
+        })
+        .unwrap();
+        assert_eq!(snaps.len(), 2);
+        assert!(snaps[1].timestamp > snaps[0].timestamp);
+        assert_eq!(snaps[1].chain.underlying_price, pos_or_panic!(101.5));
+
+        let bad = vec![OhlcvCandle {
+            time: "not a time".to_string(),
+            ..candles[0].clone()
+        }];
+        assert!(matches!(
+            MarketSnapshot::from_candles(&bad, |_| Ok(chain_at(100.0, 30.0))),
+            Err(BacktestError::InvalidMarketData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_days_between_floors_at_zero() {
+        let a = Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap();
+        let b = a + Duration::hours(36);
+        assert_eq!(days_between(a, b).unwrap(), pos_or_panic!(1.5));
+        assert_eq!(days_between(b, a).unwrap(), Positive::ZERO);
+    }
+}
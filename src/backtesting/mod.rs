@@ -353,6 +353,17 @@
 //! ```
 //!
 
+mod analysis;
+
+/// Event-driven backtesting engine.
+///
+/// Walks a chronologically ordered series of `MarketSnapshot`s (option chains
+/// observed at a point in time, either supplied directly or generated from
+/// `OhlcvCandle` data), opens strategies produced by an `EntryRule`, closes
+/// them on `ExitPolicy` triggers or expiration, and reduces the resulting
+/// equity curve and trade log into a populated `BacktestResult`.
+pub mod engine;
+
 /// GeneralPerformanceMetrics
 ///
 /// Purpose:
@@ -404,6 +415,7 @@ pub mod results;
 /// It is designed to be fully serializable (serde) for easy storage, reporting, or integration into larger analytics systems.
 pub mod types;
 
+pub use engine::{BacktestConfig, Backtester, EntryContext, EntryRule, MarkMode, MarketSnapshot};
 pub use metrics::*;
 pub use results::*;
 pub use types::*;
@@ -0,0 +1,610 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Static Arbitrage Module
+//!
+//! Detects and repairs static-arbitrage violations in the quotes of a single
+//! option chain. With strikes `K₁ < K₂ < K₃`, discount factor `D = e^{-rT}`
+//! and discounted spot `S̃ = S e^{-qT}`, arbitrage-free prices satisfy:
+//!
+//! - **Bounds**: `max(S̃ - K D, 0) ≤ C ≤ S̃` and `max(K D - S̃, 0) ≤ P ≤ K D`.
+//! - **Monotonicity** (vertical spreads): `0 ≤ C(K₁) - C(K₂) ≤ D (K₂ - K₁)`
+//!   and `0 ≤ P(K₂) - P(K₁) ≤ D (K₂ - K₁)`.
+//! - **Convexity** (butterflies): prices are convex in strike.
+//! - **Put–call parity**: `C - P = S̃ - K D` (European exercise only).
+//!
+//! Each violation is measured on mid prices and classified by whether it
+//! can actually be traded at the quoted bid and ask. The repair step
+//! projects the mids onto the set of prices satisfying every condition, in
+//! the least-squares sense, with Dykstra's alternating projections.
+
+use crate::chains::OptionChain;
+use crate::error::ChainError;
+use crate::model::decimal::finite_decimal;
+use crate::model::types::OptionStyle;
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tracing::{debug, instrument};
+
+/// Maximum number of Dykstra sweeps over the constraint set.
+const MAX_REPAIR_SWEEPS: usize = 20_000;
+
+/// Sweep-to-sweep change below which the repair is considered converged.
+const REPAIR_TOLERANCE: f64 = 1e-10;
+
+/// The no-arbitrage condition broken by a violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChainArbitrageKind {
+    /// A price lies outside its model-free lower or upper bound.
+    Bounds(OptionStyle),
+    /// A vertical spread is priced below zero or above its discounted width.
+    Monotonicity(OptionStyle),
+    /// A butterfly has a negative price (prices are not convex in strike).
+    Convexity(OptionStyle),
+    /// Call and put mids at one strike break put–call parity.
+    PutCallParity,
+}
+
+/// How serious a violation is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ArbitrageSeverity {
+    /// The mids violate the condition, but the bid–ask spreads absorb it.
+    WithinSpread,
+    /// The violation survives crossing the spread and could be traded.
+    Executable,
+}
+
+/// A single static-arbitrage violation in an option chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainArbitrageViolation {
+    /// Condition that is broken.
+    pub kind: ChainArbitrageKind,
+    /// Strikes involved, in increasing order.
+    pub strikes: Vec<Positive>,
+    /// Size of the violation on mid prices, in premium units.
+    pub amount: Decimal,
+    /// Whether the violation can be traded at the quoted bid and ask.
+    pub severity: ArbitrageSeverity,
+}
+
+/// Result of a static-arbitrage analysis of an option chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainArbitrageReport {
+    /// Every violation found, grouped by check.
+    pub violations: Vec<ChainArbitrageViolation>,
+}
+
+impl ChainArbitrageReport {
+    /// Returns `true` when no violation was found.
+    #[must_use]
+    pub fn is_arbitrage_free(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Returns the violations that survive crossing the bid–ask spread.
+    pub fn executable(&self) -> impl Iterator<Item = &ChainArbitrageViolation> {
+        self.violations
+            .iter()
+            .filter(|v| v.severity == ArbitrageSeverity::Executable)
+    }
+}
+
+/// Configuration of the static-arbitrage checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticArbitrageConfig {
+    /// Violations up to this size (in premium units) are ignored, which
+    /// absorbs the rounding of quoted prices.
+    pub tolerance: Decimal,
+    /// Whether put–call parity is checked and enforced. Disable for
+    /// American-style chains, where parity only holds as an inequality.
+    pub check_parity: bool,
+}
+
+impl Default for StaticArbitrageConfig {
+    fn default() -> Self {
+        Self {
+            tolerance: dec!(0.001),
+            check_parity: true,
+        }
+    }
+}
+
+impl StaticArbitrageConfig {
+    /// Sets the tolerance below which violations are ignored.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: Decimal) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Enables or disables the put–call parity check.
+    #[must_use]
+    pub fn with_parity(mut self, check_parity: bool) -> Self {
+        self.check_parity = check_parity;
+        self
+    }
+}
+
+/// Trait for detecting and repairing static arbitrage in option quotes.
+pub trait StaticArbitrage {
+    /// Checks bounds, monotonicity, convexity and (optionally) put–call
+    /// parity of the mid prices.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChainError::ExpirationDate` when the expiry cannot be
+    /// converted to a year fraction and `ChainError::ChainBuildError` when
+    /// the expiration string cannot be parsed.
+    fn check_static_arbitrage(
+        &self,
+        config: &StaticArbitrageConfig,
+    ) -> Result<ChainArbitrageReport, ChainError>;
+
+    /// Returns a copy whose mid prices are the nearest (least-squares)
+    /// arbitrage-free prices.
+    ///
+    /// Bids and asks are shifted with their mid, so quoted spreads are
+    /// kept (bids are floored at zero). Implied volatilities are left
+    /// untouched; call `OptionChain::update_implied_volatilities` on the
+    /// result to refresh them.
+    ///
+    /// # Errors
+    ///
+    /// Same conditions as [`StaticArbitrage::check_static_arbitrage`].
+    fn repair_static_arbitrage(
+        &self,
+        config: &StaticArbitrageConfig,
+    ) -> Result<OptionChain, ChainError>;
+}
+
+/// Mid, bid and ask of one quote; a missing side falls back to the mid.
+#[derive(Debug, Clone, Copy)]
+struct Quote {
+    mid: f64,
+    bid: f64,
+    ask: f64,
+}
+
+impl Quote {
+    fn new(middle: Option<Positive>, bid: Option<Positive>, ask: Option<Positive>) -> Option<Self> {
+        let mid = match (middle, bid, ask) {
+            (Some(mid), _, _) => mid.to_f64(),
+            (None, Some(bid), Some(ask)) => (bid.to_f64() + ask.to_f64()) / 2.0,
+            _ => return None,
+        };
+        Some(Self {
+            mid,
+            bid: bid.map_or(mid, |b| b.to_f64()),
+            ask: ask.map_or(mid, |a| a.to_f64()),
+        })
+    }
+}
+
+/// Market inputs shared by every check.
+#[derive(Debug, Clone)]
+struct Market {
+    discount: f64,
+    discounted_spot: f64,
+    strikes: Vec<f64>,
+    calls: Vec<Option<Quote>>,
+    puts: Vec<Option<Quote>>,
+}
+
+impl Market {
+    fn from_chain(chain: &OptionChain) -> Result<Self, ChainError> {
+        let expiration = chain.get_expiration().ok_or_else(|| {
+            ChainError::invalid_parameters(
+                "expiration_date",
+                &format!("cannot parse '{}'", chain.get_expiration_date()),
+            )
+        })?;
+        let years = expiration.get_years()?.to_f64();
+        let rate = chain.risk_free_rate.and_then(|r| r.to_f64()).unwrap_or(0.0);
+        let dividend = chain.dividend_yield.map_or(0.0, |q| q.to_f64());
+        Ok(Self {
+            discount: (-rate * years).exp(),
+            discounted_spot: chain.underlying_price.to_f64() * (-dividend * years).exp(),
+            strikes: chain
+                .options
+                .iter()
+                .map(|o| o.strike_price.to_f64())
+                .collect(),
+            calls: chain
+                .options
+                .iter()
+                .map(|o| Quote::new(o.call_middle, o.call_bid, o.call_ask))
+                .collect(),
+            puts: chain
+                .options
+                .iter()
+                .map(|o| Quote::new(o.put_middle, o.put_bid, o.put_ask))
+                .collect(),
+        })
+    }
+
+    fn quotes(&self, style: OptionStyle) -> &[Option<Quote>] {
+        match style {
+            OptionStyle::Call => &self.calls,
+            OptionStyle::Put => &self.puts,
+        }
+    }
+
+    /// `(index, strike, quote)` of the quoted strikes of one style.
+    fn quoted(&self, style: OptionStyle) -> Vec<(usize, f64, Quote)> {
+        self.strikes
+            .iter()
+            .zip(self.quotes(style))
+            .enumerate()
+            .filter_map(|(i, (&k, q))| q.map(|q| (i, k, q)))
+            .collect()
+    }
+
+    fn bounds(&self, style: OptionStyle, strike: f64) -> (f64, f64) {
+        let forward_gap = self.discounted_spot - strike * self.discount;
+        match style {
+            OptionStyle::Call => (forward_gap.max(0.0), self.discounted_spot),
+            OptionStyle::Put => ((-forward_gap).max(0.0), strike * self.discount),
+        }
+    }
+}
+
+/// A linear constraint `Σ coef · x[index] ≤ bound` over the stacked
+/// `(calls, puts)` price vector, together with its executable counterpart.
+#[derive(Debug, Clone)]
+struct Constraint {
+    kind: ChainArbitrageKind,
+    strikes: Vec<f64>,
+    terms: Vec<(usize, f64)>,
+    bound: f64,
+}
+
+impl Constraint {
+    /// Excess of the left-hand side over the bound at the given prices.
+    fn excess(&self, prices: impl Fn(usize) -> f64) -> f64 {
+        self.terms.iter().map(|&(i, c)| c * prices(i)).sum::<f64>() - self.bound
+    }
+
+    /// Excess when every long leg is bought at the ask and every short leg
+    /// sold at the bid, i.e. the profit actually available.
+    fn executable_excess(&self, quotes: impl Fn(usize) -> Option<Quote>) -> f64 {
+        self.terms
+            .iter()
+            .map(|&(i, c)| quotes(i).map_or(0.0, |q| if c > 0.0 { c * q.bid } else { c * q.ask }))
+            .sum::<f64>()
+            - self.bound
+    }
+}
+
+/// Builds every no-arbitrage constraint over the stacked `(calls, puts)`
+/// vector; the put of strike index `i` lives at `n + i`.
+fn constraints(market: &Market, check_parity: bool) -> Vec<Constraint> {
+    let n = market.strikes.len();
+    let mut out = Vec::new();
+    for style in [OptionStyle::Call, OptionStyle::Put] {
+        let offset = match style {
+            OptionStyle::Call => 0,
+            OptionStyle::Put => n,
+        };
+        let quoted = market.quoted(style);
+        for &(i, k, _) in &quoted {
+            let (low, high) = market.bounds(style, k);
+            let kind = ChainArbitrageKind::Bounds(style);
+            out.push(Constraint {
+                kind,
+                strikes: vec![k],
+                terms: vec![(offset + i, -1.0)],
+                bound: -low,
+            });
+            out.push(Constraint {
+                kind,
+                strikes: vec![k],
+                terms: vec![(offset + i, 1.0)],
+                bound: high,
+            });
+        }
+        for pair in quoted.windows(2) {
+            let [(i, k1, _), (j, k2, _)] = pair else {
+                continue;
+            };
+            let (lo, hi) = (offset + i, offset + j);
+            // Calls fall with strike, puts rise; neither faster than D per unit.
+            let sign = match style {
+                OptionStyle::Call => 1.0,
+                OptionStyle::Put => -1.0,
+            };
+            let kind = ChainArbitrageKind::Monotonicity(style);
+            out.push(Constraint {
+                kind,
+                strikes: vec![*k1, *k2],
+                terms: vec![(lo, -sign), (hi, sign)],
+                bound: 0.0,
+            });
+            out.push(Constraint {
+                kind,
+                strikes: vec![*k1, *k2],
+                terms: vec![(lo, sign), (hi, -sign)],
+                bound: market.discount * (k2 - k1),
+            });
+        }
+        for triple in quoted.windows(3) {
+            let [(i, k1, _), (j, k2, _), (l, k3, _)] = triple else {
+                continue;
+            };
+            let weight = (k3 - k2) / (k3 - k1);
+            out.push(Constraint {
+                kind: ChainArbitrageKind::Convexity(style),
+                strikes: vec![*k1, *k2, *k3],
+                terms: vec![
+                    (offset + i, -weight),
+                    (offset + j, 1.0),
+                    (offset + l, -(1.0 - weight)),
+                ],
+                bound: 0.0,
+            });
+        }
+    }
+    if check_parity {
+        for (i, &k) in market.strikes.iter().enumerate() {
+            let (Some(Some(_)), Some(Some(_))) = (market.calls.get(i), market.puts.get(i)) else {
+                continue;
+            };
+            let forward_gap = market.discounted_spot - k * market.discount;
+            for sign in [1.0, -1.0] {
+                out.push(Constraint {
+                    kind: ChainArbitrageKind::PutCallParity,
+                    strikes: vec![k],
+                    terms: vec![(i, sign), (n + i, -sign)],
+                    bound: sign * forward_gap,
+                });
+            }
+        }
+    }
+    out
+}
+
+/// Runs the static-arbitrage checks on a chain.
+#[instrument(skip(chain, config), fields(symbol = %chain.symbol, strikes = chain.options.len()))]
+pub(crate) fn check_chain(
+    chain: &OptionChain,
+    config: &StaticArbitrageConfig,
+) -> Result<ChainArbitrageReport, ChainError> {
+    let market = Market::from_chain(chain)?;
+    let n = market.strikes.len();
+    let quote = |i: usize| {
+        if i < n {
+            market.calls.get(i).copied().flatten()
+        } else {
+            market.puts.get(i - n).copied().flatten()
+        }
+    };
+    let tolerance = config.tolerance.to_f64().unwrap_or(0.0);
+    let mut report = ChainArbitrageReport::default();
+    for constraint in constraints(&market, config.check_parity) {
+        let excess = constraint.excess(|i| quote(i).map_or(0.0, |q| q.mid));
+        if excess <= tolerance {
+            continue;
+        }
+        let severity = if constraint.executable_excess(quote) > tolerance {
+            ArbitrageSeverity::Executable
+        } else {
+            ArbitrageSeverity::WithinSpread
+        };
+        let strikes = constraint
+            .strikes
+            .iter()
+            .map(|&k| Positive::new(k))
+            .collect::<Result<Vec<_>, _>>()?;
+        report.violations.push(ChainArbitrageViolation {
+            kind: constraint.kind,
+            strikes,
+            amount: finite_decimal(excess).unwrap_or(Decimal::MAX),
+            severity,
+        });
+    }
+    debug!(
+        violations = report.violations.len(),
+        "static arbitrage check"
+    );
+    Ok(report)
+}
+
+/// Projects the chain's mids onto the arbitrage-free set and rewrites the quotes.
+#[instrument(skip(chain, config), fields(symbol = %chain.symbol, strikes = chain.options.len()))]
+pub(crate) fn repair_chain(
+    chain: &OptionChain,
+    config: &StaticArbitrageConfig,
+) -> Result<OptionChain, ChainError> {
+    let market = Market::from_chain(chain)?;
+    let n = market.strikes.len();
+    let mids: Vec<f64> = market
+        .calls
+        .iter()
+        .chain(&market.puts)
+        .map(|q| q.map_or(0.0, |q| q.mid))
+        .collect();
+    let constraints = constraints(&market, config.check_parity);
+
+    // Dykstra's algorithm: cyclic projections onto each half-space with
+    // correction terms converge to the Euclidean projection of the mids.
+    let mut prices = mids.clone();
+    let mut corrections: Vec<Vec<f64>> = constraints
+        .iter()
+        .map(|c| vec![0.0; c.terms.len()])
+        .collect();
+    let mut sweeps = 0;
+    while sweeps < MAX_REPAIR_SWEEPS {
+        sweeps += 1;
+        let mut change = 0.0f64;
+        for (constraint, correction) in constraints.iter().zip(corrections.iter_mut()) {
+            let shifted: Vec<f64> = constraint
+                .terms
+                .iter()
+                .zip(correction.iter())
+                .map(|(&(i, _), p)| prices.get(i).copied().unwrap_or(0.0) + p)
+                .collect();
+            let excess = constraint
+                .terms
+                .iter()
+                .zip(&shifted)
+                .map(|(&(_, c), y)| c * y)
+                .sum::<f64>()
+                - constraint.bound;
+            let norm = constraint.terms.iter().map(|(_, c)| c * c).sum::<f64>();
+            let step = if excess > 0.0 { excess / norm } else { 0.0 };
+            for ((&(i, c), y), p) in constraint
+                .terms
+                .iter()
+                .zip(&shifted)
+                .zip(correction.iter_mut())
+            {
+                let projected = y - step * c;
+                *p = y - projected;
+                if let Some(x) = prices.get_mut(i) {
+                    change = change.max((projected - *x).abs());
+                    *x = projected;
+                }
+            }
+        }
+        if change < REPAIR_TOLERANCE {
+            break;
+        }
+    }
+    debug!(sweeps, "static arbitrage repair");
+
+    let shift = |quote: Option<Quote>, price: Option<&f64>| -> Option<(f64, f64, f64)> {
+        let (quote, &price) = (quote?, price?);
+        let delta = price.max(0.0) - quote.mid;
+        Some((
+            price.max(0.0),
+            (quote.bid + delta).max(0.0),
+            quote.ask + delta,
+        ))
+    };
+    let mut repaired = chain.clone();
+    repaired.options = chain
+        .options
+        .iter()
+        .enumerate()
+        .map(|(i, option)| -> Result<_, ChainError> {
+            let mut option = option.clone();
+            let call = shift(market.calls.get(i).copied().flatten(), prices.get(i));
+            if let Some((mid, bid, ask)) = call {
+                option.call_middle = Some(Positive::new(mid)?);
+                option.call_bid = option.call_bid.map(|_| Positive::new(bid)).transpose()?;
+                option.call_ask = option.call_ask.map(|_| Positive::new(ask)).transpose()?;
+            }
+            let put = shift(market.puts.get(i).copied().flatten(), prices.get(n + i));
+            if let Some((mid, bid, ask)) = put {
+                option.put_middle = Some(Positive::new(mid)?);
+                option.put_bid = option.put_bid.map(|_| Positive::new(bid)).transpose()?;
+                option.put_ask = option.put_ask.map(|_| Positive::new(ask)).transpose()?;
+            }
+            Ok(option)
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(repaired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chains::chain::OptionChain;
+    use positive::pos_or_panic;
+
+    /// Chain with zero rates and one-year expiry whose mids are exactly the
+    /// given call and put prices.
+    fn chain(strikes: &[f64], calls: &[f64], puts: &[f64], spread: f64) -> OptionChain {
+        let mut chain = OptionChain::new("TEST", Positive::HUNDRED, "365".to_string(), None, None);
+        for ((&k, &c), &p) in strikes.iter().zip(calls).zip(puts) {
+            chain.add_option(
+                pos_or_panic!(k),
+                Some(pos_or_panic!(c - spread / 2.0)),
+                Some(pos_or_panic!(c + spread / 2.0)),
+                Some(pos_or_panic!(p - spread / 2.0)),
+                Some(pos_or_panic!(p + spread / 2.0)),
+                pos_or_panic!(0.2),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+        chain
+    }
+
+    const STRIKES: [f64; 5] = [80.0, 90.0, 100.0, 110.0, 120.0];
+    const CALLS: [f64; 5] = [22.0, 14.0, 8.0, 4.0, 2.0];
+
+    fn parity_puts(calls: &[f64]) -> Vec<f64> {
+        calls
+            .iter()
+            .zip(STRIKES)
+            .map(|(c, k)| c - 100.0 + k)
+            .collect()
+    }
+
+    #[test]
+    fn test_static_arbitrage_clean_chain_has_no_violations() {
+        let chain = chain(&STRIKES, &CALLS, &parity_puts(&CALLS), 0.2);
+        let report = check_chain(&chain, &StaticArbitrageConfig::default()).unwrap();
+        assert!(report.is_arbitrage_free(), "{report:?}");
+    }
+
+    #[test]
+    fn test_static_arbitrage_flags_convexity_and_monotonicity() {
+        // The 100 call is too expensive: a butterfly and a call spread break.
+        let calls = [22.0, 14.0, 15.0, 4.0, 2.0];
+        let chain = chain(&STRIKES, &calls, &parity_puts(&calls), 0.2);
+        let config = StaticArbitrageConfig::default();
+        let report = check_chain(&chain, &config).unwrap();
+        let kinds: Vec<_> = report.violations.iter().map(|v| v.kind).collect();
+        assert!(kinds.contains(&ChainArbitrageKind::Convexity(OptionStyle::Call)));
+        assert!(kinds.contains(&ChainArbitrageKind::Monotonicity(OptionStyle::Call)));
+        let spread = report
+            .violations
+            .iter()
+            .find(|v| v.kind == ChainArbitrageKind::Monotonicity(OptionStyle::Call))
+            .unwrap();
+        assert_eq!(spread.strikes, vec![pos_or_panic!(90.0), Positive::HUNDRED]);
+        assert_eq!(spread.amount, Decimal::ONE);
+        assert_eq!(spread.severity, ArbitrageSeverity::Executable);
+
+        let repaired = repair_chain(&chain, &config).unwrap();
+        let after = check_chain(&repaired, &config).unwrap();
+        assert!(after.is_arbitrage_free(), "{after:?}");
+        // Untouched quotes stay close to the market.
+        let first = repaired.options.iter().next().unwrap();
+        assert!((first.call_middle.unwrap().to_f64() - 22.0).abs() < 1.5);
+    }
+
+    #[test]
+    fn test_static_arbitrage_parity_and_spread_severity() {
+        let mut puts = parity_puts(&CALLS);
+        puts[2] += 0.3;
+        let wide = chain(&STRIKES, &CALLS, &puts, 1.0);
+        let report = check_chain(&wide, &StaticArbitrageConfig::default()).unwrap();
+        let parity: Vec<_> = report
+            .violations
+            .iter()
+            .filter(|v| v.kind == ChainArbitrageKind::PutCallParity)
+            .collect();
+        assert_eq!(parity.len(), 1);
+        assert_eq!(parity[0].strikes, vec![Positive::HUNDRED]);
+        assert_eq!(parity[0].severity, ArbitrageSeverity::WithinSpread);
+        assert_eq!(report.executable().count(), 0);
+
+        let without_parity = StaticArbitrageConfig::default().with_parity(false);
+        assert!(
+            check_chain(&wide, &without_parity)
+                .unwrap()
+                .is_arbitrage_free()
+        );
+    }
+}
@@ -3,16 +3,20 @@
    Email: jb@taunais.com
    Date: 26/9/24
 ******************************************************************************/
+use crate::chains::arbitrage::{check_chain, repair_chain};
 use crate::chains::utils::{
     OptionChainBuildParams, OptionChainParams, OptionDataPriceParams, RandomPositionsParams,
     adjust_volatility, default_empty_string, rounder, strike_step,
 };
-use crate::chains::{OptionData, OptionsInStrike, RNDAnalysis, RNDParameters, RNDResult};
+use crate::chains::{
+    ChainArbitrageReport, OptionData, OptionsInStrike, RNDAnalysis, RNDParameters, RNDResult,
+    StaticArbitrage, StaticArbitrageConfig,
+};
 use crate::curves::{BasicCurves, Curve, Point2D};
 use crate::error::chains::{ChainError, OptionDataErrorKind};
 use crate::error::{CurveError, SurfaceError};
 use crate::geometrics::LinearInterpolation;
-use crate::greeks::Greeks;
+use crate::greeks::{BetaWeightedGreeks, BetaWeighting, DollarGreeks, Greeks};
 use crate::metrics::{
     BidAskSpreadCurve, CharmCurve, CharmSurface, ColorCurve, ColorSurface, DeltaGammaProfileCurve,
     DeltaGammaProfileSurface, DollarGammaCurve, ImpliedVolatilityCurve, ImpliedVolatilitySurface,
@@ -30,7 +34,7 @@ use crate::strategies::utils::FindOptimalSide;
 use crate::surfaces::{BasicSurfaces, Point3D, Surface};
 use crate::utils::Len;
 use crate::utils::others::get_random_element;
-use crate::volatility::VolatilitySmile;
+use crate::volatility::{IvSolverConfig, VolatilityModel, VolatilitySmile};
 use chrono::{NaiveDate, Utc};
 use num_traits::{FromPrimitive, ToPrimitive};
 use positive::Positive;
@@ -38,6 +42,7 @@ use positive::Positive;
 use positive::pos_or_panic;
 use pretty_simple_display::DebugSimple;
 use prettytable::{Attr, Cell, Row, Table, color, format};
+use rayon::prelude::*;
 use rust_decimal::{Decimal, MathematicalOps, RoundingStrategy};
 use rust_decimal_macros::dec;
 use serde::de::{MapAccess, Visitor};
@@ -1190,6 +1195,101 @@ impl OptionChain {
         self.options = modified_options;
     }
 
+    /// Recomputes the implied volatility of every strike from its mid quotes.
+    ///
+    /// Chain-level pricing parameters are first propagated to each
+    /// [`OptionData`], then every strike is calibrated in parallel with
+    /// [`OptionData::calculate_implied_volatility`]. Strikes whose quotes are
+    /// missing or cannot be inverted keep their previous volatility and are
+    /// logged at `debug` level.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Solver configuration shared by all strikes.
+    ///
+    /// # Returns
+    ///
+    /// The number of strikes whose implied volatility was updated.
+    pub fn update_implied_volatilities(&mut self, config: &IvSolverConfig) -> usize {
+        if let Err(e) = self.set_optiondata_extra_params() {
+            warn!("Failed to propagate pricing parameters: {}", e);
+        }
+        let mut options: Vec<OptionData> = std::mem::take(&mut self.options).into_iter().collect();
+        let updated = options
+            .par_iter_mut()
+            .map(|option| match option.calculate_implied_volatility(config) {
+                Ok(_) => 1,
+                Err(e) => {
+                    debug!(
+                        "Implied volatility not updated at strike {}: {}",
+                        option.strike_price, e
+                    );
+                    0
+                }
+            })
+            .sum();
+        self.options = options.into_iter().collect();
+        updated
+    }
+
+    /// Reprices every strike of the chain off a volatility model.
+    ///
+    /// Each strike's implied volatility is replaced by the model volatility
+    /// (for example a [`crate::volatility::SabrSmile`] or a
+    /// [`crate::pricing::HestonSmile`]), after which its call and put prices
+    /// and its delta and gamma are recomputed. Strategies built from the
+    /// chain therefore price off the same model. Strikes at which the model
+    /// fails keep their previous data and are logged at `debug` level.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Volatility model queried at every strike.
+    /// * `spread` - Optional bid/ask spread applied around the model prices.
+    ///
+    /// # Returns
+    ///
+    /// The number of strikes that were repriced.
+    pub fn apply_volatility_model<M>(&mut self, model: &M, spread: Option<Positive>) -> usize
+    where
+        M: VolatilityModel + Sync + ?Sized,
+    {
+        if let Err(e) = self.set_optiondata_extra_params() {
+            warn!("Failed to propagate pricing parameters: {}", e);
+        }
+        let mut options: Vec<OptionData> = std::mem::take(&mut self.options).into_iter().collect();
+        let updated = options
+            .par_iter_mut()
+            .map(|option| {
+                let repriced = model
+                    .volatility_at(option.strike_price)
+                    .map_err(ChainError::from)
+                    .and_then(|vol| {
+                        let mut candidate = option.clone();
+                        candidate.implied_volatility = vol;
+                        candidate.calculate_prices(spread)?;
+                        Ok(candidate)
+                    });
+                match repriced {
+                    Ok(mut candidate) => {
+                        candidate.calculate_delta();
+                        candidate.calculate_gamma();
+                        *option = candidate;
+                        1
+                    }
+                    Err(e) => {
+                        debug!(
+                            "Volatility model not applied at strike {}: {}",
+                            option.strike_price, e
+                        );
+                        0
+                    }
+                }
+            })
+            .sum();
+        self.options = options.into_iter().collect();
+        updated
+    }
+
     /// Saves the option chain data to a CSV file.
     ///
     /// This method writes the option chain data to a CSV file at the specified path.
@@ -2235,6 +2335,46 @@ impl OptionChain {
         Ok(vanna_exposure)
     }
 
+    /// Calculates the delta, gamma and vega exposures of the chain in money
+    /// terms at the chain's underlying price.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DollarGreeks, ChainError>` - Raw and dollar exposures, or an error if calculation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if any delta, gamma or vega exposure calculation fails.
+    ///
+    /// # Note
+    ///
+    /// This method requires options greeks to be initialized first by calling the `update_greeks` method.
+    pub fn dollar_exposure(&self) -> Result<DollarGreeks, ChainError> {
+        Ok(DollarGreeks::from_greeks(
+            self.underlying_price,
+            self.delta_exposure()?,
+            self.gamma_exposure()?,
+            self.vega_exposure()?,
+        ))
+    }
+
+    /// Calculates the delta and gamma exposures of the chain in units of a
+    /// reference underlying.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<BetaWeightedGreeks, ChainError>` - Beta-weighted exposures, or an error if calculation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ChainError` if the dollar exposure calculation fails.
+    pub fn beta_weighted_exposure(
+        &self,
+        weighting: &BetaWeighting,
+    ) -> Result<BetaWeightedGreeks, ChainError> {
+        Ok(self.dollar_exposure()?.beta_weighted(weighting))
+    }
+
     /// Calculates the total vomma exposure for all options in the chain.
     ///
     /// Vomma exposure represents the aggregate sensitivity of option Vega to changes
@@ -3160,6 +3300,22 @@ impl OptionChainParams for OptionChain {
     }
 }
 
+impl StaticArbitrage for OptionChain {
+    fn check_static_arbitrage(
+        &self,
+        config: &StaticArbitrageConfig,
+    ) -> Result<ChainArbitrageReport, ChainError> {
+        check_chain(self, config)
+    }
+
+    fn repair_static_arbitrage(
+        &self,
+        config: &StaticArbitrageConfig,
+    ) -> Result<OptionChain, ChainError> {
+        repair_chain(self, config)
+    }
+}
+
 impl RNDAnalysis for OptionChain {
     /// Implementation of RND calculation for option chains
     ///
@@ -13694,3 +13850,163 @@ mod tests_price_metrics_traits {
         assert_decimal_eq!(strike_concentration_vec[4].y, dec!(1.31928), epsilon);
     }
 }
+
+#[cfg(test)]
+mod tests_update_implied_volatilities {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::spos;
+
+    fn flat_chain() -> OptionChain {
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(Positive::HUNDRED)),
+            Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+            Some(dec!(0.02)),
+            spos!(0.01),
+            Some("TEST".to_string()),
+        );
+        let params = OptionChainBuildParams::new(
+            "TEST".to_string(),
+            None,
+            10,
+            spos!(2.5),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos_or_panic!(0.02),
+            4,
+            price_params,
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    #[test]
+    fn test_update_implied_volatilities_recovers_flat_smile() {
+        let mut chain = flat_chain();
+        chain.mutate_single_options(|option| option.implied_volatility = pos_or_panic!(0.9));
+
+        let updated = chain.update_implied_volatilities(&IvSolverConfig::default());
+        assert!(updated > 0);
+        let atm = chain.atm_option_data().unwrap();
+        assert!((atm.implied_volatility.to_f64() - 0.2).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_update_implied_volatilities_skips_strikes_without_quotes() {
+        let mut chain = flat_chain();
+        let total = chain.options.len();
+        chain.options = std::mem::take(&mut chain.options)
+            .into_iter()
+            .map(|mut option| {
+                if option.strike_price < Positive::HUNDRED {
+                    option.call_bid = None;
+                    option.call_ask = None;
+                    option.call_middle = None;
+                    option.put_bid = None;
+                    option.put_ask = None;
+                    option.put_middle = None;
+                    option.implied_volatility = pos_or_panic!(0.9);
+                }
+                option
+            })
+            .collect();
+
+        let updated = chain.update_implied_volatilities(&IvSolverConfig::default());
+        assert!(updated < total);
+        for option in chain
+            .options
+            .iter()
+            .filter(|o| o.strike_price < Positive::HUNDRED)
+        {
+            assert_eq!(option.implied_volatility, pos_or_panic!(0.9));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_apply_volatility_model {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::volatility::{SabrParams, SabrSmile};
+    use positive::spos;
+
+    fn chain() -> OptionChain {
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(Positive::HUNDRED)),
+            Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+            Some(Decimal::ZERO),
+            spos!(0.0),
+            Some("TEST".to_string()),
+        );
+        let params = OptionChainBuildParams::new(
+            "TEST".to_string(),
+            None,
+            10,
+            spos!(2.5),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos_or_panic!(0.02),
+            4,
+            price_params,
+            pos_or_panic!(0.2),
+        );
+        OptionChain::build_chain(&params).unwrap()
+    }
+
+    #[test]
+    fn test_apply_volatility_model_reprices_every_strike() {
+        let mut chain = chain();
+        let sabr = SabrSmile::new(
+            Positive::HUNDRED,
+            pos_or_panic!(30.0 / 365.0),
+            SabrParams::new(pos_or_panic!(0.2), Decimal::ONE, dec!(-0.4), Positive::ONE).unwrap(),
+            Vec::new(),
+        );
+
+        let updated = chain.apply_volatility_model(&sabr, None);
+        assert_eq!(updated, chain.options.len());
+        for option in chain.options.iter() {
+            let expected = sabr.volatility_at(option.strike_price).unwrap();
+            assert_eq!(option.implied_volatility, expected);
+            let call = option
+                .get_option(Side::Long, OptionStyle::Call)
+                .unwrap()
+                .calculate_price_black_scholes()
+                .unwrap();
+            let middle = option.call_middle.unwrap().to_dec();
+            assert!((middle - call).abs() < dec!(1e-10));
+        }
+
+        let strikes = chain.get_strikes().unwrap();
+        let low = chain.get_optiondata_with_strike(&strikes[0]).unwrap();
+        let high = chain
+            .get_optiondata_with_strike(strikes.last().unwrap())
+            .unwrap();
+        assert!(low.implied_volatility > high.implied_volatility);
+    }
+}
+
+#[cfg(test)]
+mod tests_dollar_exposure {
+    use super::*;
+    use crate::utils::time::get_x_days_formatted;
+
+    #[test]
+    fn test_dollar_and_beta_weighted_exposure() {
+        let mut chain =
+            OptionChain::load_from_json("examples/Chains/SP500-18-oct-2024-5781.88.json").unwrap();
+        chain.expiration_date = get_x_days_formatted(30);
+        chain.update_greeks();
+
+        let exposure = chain.dollar_exposure().unwrap();
+        let price = chain.underlying_price.to_dec();
+        assert_eq!(exposure.delta, chain.delta_exposure().unwrap());
+        assert_eq!(exposure.dollar_delta, exposure.delta * price);
+
+        let weighted = chain
+            .beta_weighted_exposure(&BetaWeighting::new(Decimal::ONE, chain.underlying_price))
+            .unwrap();
+        assert_eq!(weighted.dollar_delta, exposure.dollar_delta);
+        assert!((weighted.delta - exposure.delta).abs() < Decimal::new(1, 10));
+    }
+}
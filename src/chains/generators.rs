@@ -302,6 +302,122 @@ mod tests {
         );
     }
 
+    fn regime_chain_walk_params(walk_type: WalkType) -> WalkParams<Positive, OptionChain> {
+        use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(Positive::HUNDRED)),
+            Some(ExpirationDate::Days(pos_or_panic!(60.0))),
+            Some(dec!(0.05)),
+            Some(pos_or_panic!(0.02)),
+            Some("TEST".to_string()),
+        );
+        let chain_params = OptionChainBuildParams::new(
+            "TEST".to_string(),
+            None,
+            10,
+            Some(pos_or_panic!(5.0)),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            price_params,
+            pos_or_panic!(0.2),
+        );
+        let initial_chain = match OptionChain::build_chain(&chain_params) {
+            Ok(chain) => chain,
+            Err(e) => panic!("initial chain build failed: {e}"),
+        };
+        WalkParams {
+            size: 6,
+            init_step: Step {
+                x: Xstep::new(
+                    Positive::ONE,
+                    TimeFrame::Day,
+                    ExpirationDate::Days(pos_or_panic!(60.0)),
+                ),
+                y: Ystep::new(0, initial_chain),
+            },
+            walk_type,
+            walker: Box::new(WalkerOptionChain::new()),
+        }
+    }
+
+    #[test]
+    fn test_generator_optionchain_regime_switching_vol_reaches_chains() {
+        use crate::simulation::Regime;
+
+        let walk_params = regime_chain_walk_params(WalkType::RegimeSwitching {
+            dt: pos_or_panic!(0.004),
+            regimes: vec![
+                Regime::new(dec!(0.0), pos_or_panic!(0.15)),
+                Regime::new(dec!(0.0), pos_or_panic!(0.45)),
+            ],
+            // Deterministic chain: the regime flips on every step.
+            transition_matrix: vec![vec![dec!(0.0), dec!(1.0)], vec![dec!(1.0), dec!(0.0)]],
+            initial_regime: 0,
+        });
+
+        let steps = match generator_optionchain(&walk_params) {
+            Ok(steps) => steps,
+            Err(e) => panic!("generator_optionchain failed: {e}"),
+        };
+        assert_eq!(steps.len(), 6);
+
+        let atm_ivs: Vec<Positive> = steps
+            .iter()
+            .skip(1)
+            .map(|step| match step.y.value().get_atm_implied_volatility() {
+                Ok(iv) => *iv,
+                Err(e) => panic!("rebuilt chain has no ATM IV: {e}"),
+            })
+            .collect();
+        // Step 1 is driven by the high-volatility regime, step 2 by the low one, ...
+        for (i, pair) in atm_ivs.windows(2).enumerate() {
+            if i % 2 == 0 {
+                assert!(pair[0] > pair[1], "{atm_ivs:?}");
+            } else {
+                assert!(pair[0] < pair[1], "{atm_ivs:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_generator_optionchain_block_bootstrap() {
+        use crate::simulation::BootstrapMethod;
+
+        let history: Vec<Positive> = [100.0, 102.0, 101.0, 104.0, 103.0, 107.0]
+            .iter()
+            .map(|p| pos_or_panic!(*p))
+            .collect();
+        let ratios: Vec<rust_decimal::Decimal> = history
+            .windows(2)
+            .map(|pair| pair[1].to_dec() / pair[0].to_dec())
+            .collect();
+        let walk_params = regime_chain_walk_params(WalkType::BlockBootstrap {
+            timeframe: TimeFrame::Day,
+            prices: history,
+            block_length: 2,
+            method: BootstrapMethod::Stationary,
+        });
+
+        let steps = match generator_optionchain(&walk_params) {
+            Ok(steps) => steps,
+            Err(e) => panic!("generator_optionchain failed: {e}"),
+        };
+        assert_eq!(steps.len(), 6);
+
+        // Every move of the underlying is one of the historical moves.
+        for pair in steps.windows(2) {
+            let ratio = pair[1].y.value().underlying_price.to_dec()
+                / pair[0].y.value().underlying_price.to_dec();
+            assert!(
+                ratios.iter().any(|r| (*r - ratio).abs() < dec!(1e-9)),
+                "move {ratio} is not in the history"
+            );
+        }
+    }
+
     /// Multi-step behavior under a deterministic ramp walker: rebuilt chains
     /// must track the walked price, keep the walk volatility, decay their
     /// expiration with the x-step, and increment the y index.
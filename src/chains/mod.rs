@@ -17,6 +17,7 @@
 //! * Import/export capabilities (CSV, JSON)
 //! * Multiple-leg strategy support
 //! * Price calculation and volatility adjustments
+//! * Static-arbitrage detection and repair of quoted prices (`StaticArbitrage`)
 //!
 //! ## Example Usage
 //!
@@ -231,11 +232,18 @@ mod optiondata;
 
 mod generators;
 
+/// * `arbitrage` - Private module for static-arbitrage detection and repair of chain quotes
+mod arbitrage;
+
 #[deprecated(
     since = "0.17.4",
     note = "moved to `optionstratlib::simulation::generator_positive`; it never depended on option chains"
 )]
 pub use crate::simulation::generator_positive;
+pub use arbitrage::{
+    ArbitrageSeverity, ChainArbitrageKind, ChainArbitrageReport, ChainArbitrageViolation,
+    StaticArbitrage, StaticArbitrageConfig,
+};
 pub use chain::OptionChain;
 pub use generators::generator_optionchain;
 pub use legs::StrategyLegs;
@@ -11,6 +11,7 @@ use crate::greeks::{delta, gamma};
 use crate::model::Position;
 use crate::model::utils::sub_floor_zero;
 use crate::strategies::{BasicAble, FindOptimalSide};
+use crate::volatility::{IvSolution, IvSolverConfig, solve_implied_volatility};
 use crate::{ExpirationDate, OptionStyle, Options, Side};
 use chrono::{DateTime, Utc};
 use positive::Positive;
@@ -1085,6 +1086,69 @@ impl OptionData {
         (self.call_middle, self.put_middle)
     }
 
+    /// Recomputes the implied volatility of this strike from its mid quotes.
+    ///
+    /// The out-of-the-money side is preferred (the put below the underlying
+    /// price, the call at or above it) because its quote carries no intrinsic
+    /// value; the other side is used when the preferred one has no mid price.
+    /// Mid prices are taken from `call_middle` / `put_middle`, or from the
+    /// bid/ask pair when the mids have not been set. On success the solved
+    /// volatility replaces `implied_volatility`.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Solver configuration (tolerances, iteration budgets and bounds).
+    ///
+    /// # Returns
+    ///
+    /// The solver's [`IvSolution`] with convergence diagnostics.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChainError::OptionDataError`] when the pricing parameters
+    /// (symbol, expiration, underlying price) are missing or neither side
+    /// has a quote, and [`ChainError::Volatility`] when the solver rejects
+    /// the quote or fails to converge.
+    pub fn calculate_implied_volatility(
+        &mut self,
+        config: &IvSolverConfig,
+    ) -> Result<IvSolution, ChainError> {
+        let option = self.get_option(Side::Long, OptionStyle::Call)?;
+        let call_mid = self
+            .call_middle
+            .or_else(|| mid_quote(self.call_bid, self.call_ask));
+        let put_mid = self
+            .put_middle
+            .or_else(|| mid_quote(self.put_bid, self.put_ask));
+        let (style, price) = if self.strike_price >= option.underlying_price {
+            match (call_mid, put_mid) {
+                (Some(call), _) => (OptionStyle::Call, call),
+                (None, Some(put)) => (OptionStyle::Put, put),
+                (None, None) => return Err(self.missing_quotes_error()),
+            }
+        } else {
+            match (put_mid, call_mid) {
+                (Some(put), _) => (OptionStyle::Put, put),
+                (None, Some(call)) => (OptionStyle::Call, call),
+                (None, None) => return Err(self.missing_quotes_error()),
+            }
+        };
+        let mut option = option;
+        option.option_style = style;
+        let solution = solve_implied_volatility(&option, price, config)?;
+        self.implied_volatility = solution.volatility;
+        Ok(solution)
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn missing_quotes_error(&self) -> ChainError {
+        ChainError::OptionDataError(OptionDataErrorKind::Other(format!(
+            "no call or put quote available at strike {}",
+            self.strike_price
+        )))
+    }
+
     /// Checks and corrects implied volatility if it's represented as a percentage greater than 1.0.
     ///
     /// This function checks if the `implied_volatility` field is present. If it is and its value
@@ -1203,6 +1267,15 @@ impl fmt::Display for OptionData {
     }
 }
 
+/// Mid price of a bid/ask pair, or the single available side.
+#[inline]
+fn mid_quote(bid: Option<Positive>, ask: Option<Positive>) -> Option<Positive> {
+    match (bid, ask) {
+        (Some(bid), Some(ask)) => Some((bid + ask) / Positive::TWO),
+        (Some(only), None) | (None, Some(only)) => Some(only),
+        (None, None) => None,
+    }
+}
 #[cfg(test)]
 mod optiondata_coverage_tests {
     use super::*;
@@ -8,6 +8,7 @@ use positive::Positive;
 use positive::pos_or_panic;
 
 use crate::chains::OptionData;
+use crate::chains::StrategyLegs;
 use crate::chains::chain::{SKEW_SLOPE, SKEW_SMILE_CURVE};
 use crate::error::chains::ChainError;
 use crate::model::ExpirationDate;
@@ -73,6 +74,35 @@ pub enum OptionDataGroup<'a> {
     Any(Vec<&'a OptionData>),
 }
 
+impl<'a> OptionDataGroup<'a> {
+    /// The legs to pass to `Optimizable::create_strategy` for this group.
+    ///
+    /// A single option is repeated as both legs, as same-strike strategies
+    /// such as straddles expect. Returns `None` for `Any`, which has no fixed
+    /// leg layout.
+    pub fn strategy_legs(&self) -> Option<StrategyLegs<'a>> {
+        match *self {
+            OptionDataGroup::One(option) => Some(StrategyLegs::TwoLegs {
+                first: option,
+                second: option,
+            }),
+            OptionDataGroup::Two(first, second) => Some(StrategyLegs::TwoLegs { first, second }),
+            OptionDataGroup::Three(first, second, third) => Some(StrategyLegs::ThreeLegs {
+                first,
+                second,
+                third,
+            }),
+            OptionDataGroup::Four(first, second, third, fourth) => Some(StrategyLegs::FourLegs {
+                first,
+                second,
+                third,
+                fourth,
+            }),
+            OptionDataGroup::Any(_) => None,
+        }
+    }
+}
+
 /// Parameters for building an option chain dataset.
 ///
 /// This structure encapsulates all necessary configuration parameters to generate
@@ -1622,3 +1652,30 @@ mod tests_sample {
         assert_eq!(result, "");
     }
 }
+
+#[cfg(test)]
+mod tests_option_data_group {
+    use super::*;
+
+    #[test]
+    fn test_strategy_legs_layouts() {
+        let option = OptionData::default();
+        assert!(matches!(
+            OptionDataGroup::One(&option).strategy_legs(),
+            Some(StrategyLegs::TwoLegs { .. })
+        ));
+        assert!(matches!(
+            OptionDataGroup::Three(&option, &option, &option).strategy_legs(),
+            Some(StrategyLegs::ThreeLegs { .. })
+        ));
+        assert!(matches!(
+            OptionDataGroup::Four(&option, &option, &option, &option).strategy_legs(),
+            Some(StrategyLegs::FourLegs { .. })
+        ));
+        assert!(
+            OptionDataGroup::Any(vec![&option])
+                .strategy_legs()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,180 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+use crate::error::{
+    ChainError, DecimalError, GreeksError, PositionError, PricingError, StrategyError,
+};
+use thiserror::Error;
+
+/// Error type for the event-driven backtesting engine.
+///
+/// Covers invalid engine configuration, malformed market data (empty or
+/// out-of-order snapshots) and every domain failure surfaced while opening,
+/// marking and closing strategies bar by bar.
+#[derive(Error, Debug)]
+pub enum BacktestError {
+    /// The backtest configuration is inconsistent (e.g. zero initial capital).
+    #[error("Invalid backtest configuration: {reason}")]
+    InvalidConfig {
+        /// Detailed reason for the invalid configuration
+        reason: String,
+    },
+
+    /// No market data was supplied to the engine.
+    #[error("backtest requires at least one market snapshot: {context}")]
+    NoMarketData {
+        /// Static tag identifying the call site that received no data.
+        context: &'static str,
+    },
+
+    /// Market snapshots are not in strictly increasing timestamp order.
+    #[error("market snapshot {index} is not after the previous snapshot")]
+    UnorderedSnapshots {
+        /// Index of the first snapshot that breaks the ordering.
+        index: usize,
+    },
+
+    /// A market snapshot could not be built from the supplied raw data.
+    #[error("Invalid market data: {reason}")]
+    InvalidMarketData {
+        /// Detailed reason for the invalid market data
+        reason: String,
+    },
+
+    /// An entry rule returned a strategy that cannot be traded.
+    #[error("Invalid entry: {reason}")]
+    InvalidEntry {
+        /// Detailed reason for rejecting the strategy
+        reason: String,
+    },
+
+    /// A metric kernel produced a non-finite value (`NaN` or `±inf`).
+    #[error("non-finite value in {context}: {value}")]
+    NonFinite {
+        /// Static tag identifying the metric that produced the value.
+        context: &'static str,
+        /// The offending value.
+        value: f64,
+    },
+
+    /// Decimal arithmetic error surfaced during valuation or metric computation.
+    #[error(transparent)]
+    Decimal(#[from] DecimalError),
+
+    /// Position domain error surfaced while marking a leg.
+    #[error(transparent)]
+    Position(#[from] PositionError),
+
+    /// Pricing error surfaced while marking a leg theoretically.
+    #[error(transparent)]
+    Pricing(#[from] PricingError),
+
+    /// Greeks error surfaced while recording exposures.
+    #[error(transparent)]
+    Greeks(#[from] GreeksError),
+
+    /// Positive value errors
+    #[error(transparent)]
+    PositiveError(#[from] positive::PositiveError),
+
+    /// Strategy-layer error surfaced by an entry rule or a traded strategy.
+    #[error(transparent)]
+    Strategy(Box<StrategyError>),
+
+    /// Chain domain error surfaced while building snapshots.
+    #[error(transparent)]
+    Chain(Box<ChainError>),
+}
+
+impl BacktestError {
+    /// Creates a new `InvalidConfig` variant.
+    ///
+    /// # Arguments
+    /// * `reason` - Detailed reason for the invalid configuration
+    #[must_use]
+    #[cold]
+    #[inline(never)]
+    pub fn invalid_config(reason: &str) -> Self {
+        BacktestError::InvalidConfig {
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Creates a new `InvalidMarketData` variant.
+    ///
+    /// # Arguments
+    /// * `reason` - Detailed reason for the invalid market data
+    #[must_use]
+    #[cold]
+    #[inline(never)]
+    pub fn invalid_market_data(reason: &str) -> Self {
+        BacktestError::InvalidMarketData {
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Creates a new `InvalidEntry` variant.
+    ///
+    /// # Arguments
+    /// * `reason` - Detailed reason for rejecting the strategy
+    #[must_use]
+    #[cold]
+    #[inline(never)]
+    pub fn invalid_entry(reason: &str) -> Self {
+        BacktestError::InvalidEntry {
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Creates a new `NonFinite` variant.
+    ///
+    /// # Arguments
+    /// * `context` - Static tag identifying the metric
+    /// * `value` - The non-finite value that was produced
+    #[must_use]
+    #[cold]
+    #[inline(never)]
+    pub fn non_finite(context: &'static str, value: f64) -> Self {
+        BacktestError::NonFinite { context, value }
+    }
+}
+
+impl From<StrategyError> for BacktestError {
+    #[inline]
+    fn from(err: StrategyError) -> Self {
+        BacktestError::Strategy(Box::new(err))
+    }
+}
+
+impl From<ChainError> for BacktestError {
+    #[inline]
+    fn from(err: ChainError) -> Self {
+        BacktestError::Chain(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backtest_error_display() {
+        let err = BacktestError::invalid_config("initial capital must be positive");
+        assert_eq!(
+            err.to_string(),
+            "Invalid backtest configuration: initial capital must be positive"
+        );
+
+        let err = BacktestError::UnorderedSnapshots { index: 3 };
+        assert!(err.to_string().contains("snapshot 3"));
+    }
+
+    #[test]
+    fn test_backtest_error_from_strategy_error() {
+        let err: BacktestError = StrategyError::operation_not_supported("x", "y").into();
+        assert!(matches!(err, BacktestError::Strategy(_)));
+    }
+}
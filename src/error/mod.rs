@@ -224,11 +224,26 @@ mod volatility;
 /// * Date and decimal parsing issues
 mod csv;
 
+/// ### Backtest Errors (`BacktestError`)
+/// Handles:
+/// * Backtest engine configuration
+/// * Market snapshot validation and ordering
+/// * Strategy entry, valuation and exit failures
+pub mod backtest;
+
+/// ### Portfolio Errors (`PortfolioError`)
+/// Handles:
+/// * Strategy lookup by identifier
+/// * Missing underlying prices
+/// * Valuation, Greeks, margin and JSON persistence failures
+pub mod portfolio;
+
 /// ### Unified Error Type
 /// Top-level error type that encompasses all errors in the library.
 /// Provides a single error type for unified error handling across modules.
 pub mod unified;
 
+pub use backtest::BacktestError;
 pub use chains::ChainError;
 pub use common::OperationErrorKind;
 pub use csv::OhlcvError;
@@ -239,6 +254,7 @@ pub use greeks::GreeksError;
 pub use interpolation::InterpolationError;
 pub use metrics::MetricsError;
 pub use options::{OptionsError, OptionsResult};
+pub use portfolio::PortfolioError;
 pub use position::PositionError;
 pub use pricing::{PricingError, PricingResult};
 pub use probability::ProbabilityError;
@@ -0,0 +1,138 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+use crate::error::{GreeksError, OptionsError, PositionError, PricingError, StrategyError};
+use expiration_date::error::ExpirationDateError;
+use thiserror::Error;
+
+/// Error type for the portfolio aggregate.
+///
+/// Covers lookups of strategies and market data by key, JSON persistence and
+/// every domain failure surfaced while valuing, margining or computing the
+/// Greeks of the held strategies and legs.
+#[derive(Error, Debug)]
+pub enum PortfolioError {
+    /// A strategy with the same identifier is already held.
+    #[error("portfolio already holds a strategy with id '{id}'")]
+    DuplicateStrategy {
+        /// Identifier of the strategy
+        id: String,
+    },
+
+    /// No strategy with the identifier is held.
+    #[error("portfolio holds no strategy with id '{id}'")]
+    StrategyNotFound {
+        /// Identifier of the strategy
+        id: String,
+    },
+
+    /// No price is known for an underlying.
+    #[error("no price for underlying '{symbol}'")]
+    MissingPrice {
+        /// Symbol of the underlying
+        symbol: String,
+    },
+
+    /// A strategy cannot be held, e.g. because it has no positions.
+    #[error("Invalid strategy: {reason}")]
+    InvalidStrategy {
+        /// Detailed reason for rejecting the strategy
+        reason: String,
+    },
+
+    /// The portfolio could not be serialised to or parsed from JSON.
+    #[error("portfolio JSON error: {reason}")]
+    Json {
+        /// Detailed reason reported by the serialiser
+        reason: String,
+    },
+
+    /// Position domain error surfaced while valuing a position.
+    #[error(transparent)]
+    Position(#[from] PositionError),
+
+    /// Option domain error surfaced while pricing an option.
+    #[error(transparent)]
+    Options(#[from] OptionsError),
+
+    /// Pricing error surfaced while marking an option.
+    #[error(transparent)]
+    Pricing(#[from] PricingError),
+
+    /// Greeks error surfaced while aggregating exposures.
+    #[error(transparent)]
+    Greeks(#[from] GreeksError),
+
+    /// Expiration date error surfaced while building the expiration ladder.
+    #[error(transparent)]
+    ExpirationDate(#[from] ExpirationDateError),
+
+    /// Positive value errors
+    #[error(transparent)]
+    PositiveError(#[from] positive::PositiveError),
+
+    /// Strategy-layer error surfaced while reading or margining a strategy.
+    #[error(transparent)]
+    Strategy(Box<StrategyError>),
+}
+
+impl PortfolioError {
+    /// Creates a new `InvalidStrategy` variant.
+    ///
+    /// # Arguments
+    /// * `reason` - Detailed reason for rejecting the strategy
+    #[must_use]
+    #[cold]
+    #[inline(never)]
+    pub fn invalid_strategy(reason: &str) -> Self {
+        PortfolioError::InvalidStrategy {
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl From<StrategyError> for PortfolioError {
+    #[inline]
+    fn from(err: StrategyError) -> Self {
+        PortfolioError::Strategy(Box::new(err))
+    }
+}
+
+impl From<serde_json::Error> for PortfolioError {
+    #[inline]
+    fn from(err: serde_json::Error) -> Self {
+        PortfolioError::Json {
+            reason: err.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portfolio_error_display() {
+        let err = PortfolioError::StrategyNotFound {
+            id: "spx-ic".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "portfolio holds no strategy with id 'spx-ic'"
+        );
+
+        let err = PortfolioError::MissingPrice {
+            symbol: "AAPL".to_string(),
+        };
+        assert!(err.to_string().contains("AAPL"));
+    }
+
+    #[test]
+    fn test_portfolio_error_from_strategy_error() {
+        let err: PortfolioError = StrategyError::operation_not_supported("x", "y").into();
+        assert!(matches!(err, PortfolioError::Strategy(_)));
+    }
+}
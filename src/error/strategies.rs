@@ -448,6 +448,15 @@ impl From<crate::error::PricingError> for StrategyError {
     }
 }
 
+impl From<crate::error::ProbabilityError> for StrategyError {
+    fn from(err: crate::error::ProbabilityError) -> Self {
+        StrategyError::OperationError(OperationErrorKind::InvalidParameters {
+            operation: "Probability".to_string(),
+            reason: err.to_string(),
+        })
+    }
+}
+
 impl From<TradeError> for StrategyError {
     fn from(value: TradeError) -> Self {
         StrategyError::OperationError(OperationErrorKind::InvalidParameters {
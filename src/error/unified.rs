@@ -106,6 +106,14 @@ pub enum Error {
     #[error(transparent)]
     Trade(#[from] crate::error::TradeError),
 
+    /// Backtest engine errors.
+    #[error(transparent)]
+    Backtest(#[from] crate::error::BacktestError),
+
+    /// Portfolio aggregate errors.
+    #[error(transparent)]
+    Portfolio(#[from] crate::error::PortfolioError),
+
     /// Empty input collection supplied to a utility that requires at least one element.
     #[error("empty collection: {context}")]
     EmptyCollection {
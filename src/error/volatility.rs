@@ -140,6 +140,30 @@ pub enum VolatilityError {
     #[error(transparent)]
     DecimalError(#[from] crate::error::DecimalError),
 
+    /// A volatility model parameter lies outside its admissible range.
+    ///
+    /// Emitted by parametric smile models such as SABR when, for example,
+    /// a correlation is outside `(-1, 1)` or an exponent outside `[0, 1]`.
+    #[error("invalid volatility model parameter {parameter}: {reason}")]
+    InvalidParameter {
+        /// Name of the offending parameter.
+        parameter: &'static str,
+        /// Why the value is not admissible.
+        reason: String,
+    },
+
+    /// A model calibration could not produce a usable fit.
+    ///
+    /// Emitted by the SVI / SSVI surface fits when there are too few
+    /// quotes, or when the optimiser ends at a non-finite objective.
+    #[error("{model} calibration failed: {reason}")]
+    CalibrationFailed {
+        /// Name of the model being calibrated.
+        model: &'static str,
+        /// Why the calibration failed.
+        reason: String,
+    },
+
     /// A volatility kernel produced a non-finite `f64` value (`NaN` /
     /// `±∞`) at an `f64` → `Decimal` boundary.
     ///
@@ -234,6 +258,32 @@ mod tests_volatility_errors {
         assert_eq!(error.to_string(), "Option error: Invalid option parameters");
     }
 
+    #[test]
+    fn test_invalid_parameter_error() {
+        let error = VolatilityError::InvalidParameter {
+            parameter: "rho",
+            reason: "must lie in (-1, 1)".to_string(),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "invalid volatility model parameter rho: must lie in (-1, 1)"
+        );
+    }
+
+    #[test]
+    fn test_calibration_failed_error() {
+        let error = VolatilityError::CalibrationFailed {
+            model: "SVI",
+            reason: "needs at least 5 quotes, got 3".to_string(),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "SVI calibration failed: needs at least 5 quotes, got 3"
+        );
+    }
+
     #[test]
     fn test_no_convergence_error() {
         let error = VolatilityError::NoConvergence {
@@ -90,7 +90,7 @@ fn discount_factor(option: &Options, t: Decimal) -> Decimal {
 ))]
 pub fn delta_b76(option: &Options) -> Result<Decimal, GreeksError> {
     ensure_european(option)?;
-    let t = option.expiration_date.get_years()?.to_dec();
+    let t = option.years_to_expiry()?.to_dec();
     let (d1, _d2) = calculate_d_values_black_76(option)?;
 
     let df = discount_factor(option, t);
@@ -130,7 +130,7 @@ pub fn delta_b76(option: &Options) -> Result<Decimal, GreeksError> {
 #[instrument(skip(option), fields(strike = %option.strike_price))]
 pub fn gamma_b76(option: &Options) -> Result<Decimal, GreeksError> {
     ensure_european(option)?;
-    let t = option.expiration_date.get_years()?;
+    let t = option.years_to_expiry()?;
     let (d1, _d2) = calculate_d_values_black_76(option)?;
 
     let df = discount_factor(option, t.to_dec());
@@ -176,7 +176,7 @@ pub fn gamma_b76(option: &Options) -> Result<Decimal, GreeksError> {
 #[instrument(skip(option), fields(strike = %option.strike_price))]
 pub fn vega_b76(option: &Options) -> Result<Decimal, GreeksError> {
     ensure_european(option)?;
-    let t = option.expiration_date.get_years()?;
+    let t = option.years_to_expiry()?;
     let (d1, _d2) = calculate_d_values_black_76(option)?;
 
     let df = discount_factor(option, t.to_dec());
@@ -225,7 +225,7 @@ pub fn vega_b76(option: &Options) -> Result<Decimal, GreeksError> {
 #[instrument(skip(option), fields(strike = %option.strike_price))]
 pub fn theta_b76(option: &Options) -> Result<Decimal, GreeksError> {
     ensure_european(option)?;
-    let t = option.expiration_date.get_years()?;
+    let t = option.years_to_expiry()?;
     let (d1, d2) = calculate_d_values_black_76(option)?;
 
     let df = discount_factor(option, t.to_dec());
@@ -271,7 +271,7 @@ pub fn theta_b76(option: &Options) -> Result<Decimal, GreeksError> {
     )?;
     let result = d_div(
         weighted,
-        Decimal::from(365),
+        option.day_count.days_per_year(),
         "greeks::black_76::theta::per_day",
     )?;
     trace!(
@@ -305,7 +305,7 @@ pub fn theta_b76(option: &Options) -> Result<Decimal, GreeksError> {
 #[instrument(skip(option), fields(strike = %option.strike_price))]
 pub fn rho_b76(option: &Options) -> Result<Decimal, GreeksError> {
     ensure_european(option)?;
-    let t = option.expiration_date.get_years()?;
+    let t = option.years_to_expiry()?;
     let (d1, d2) = calculate_d_values_black_76(option)?;
 
     let df = discount_factor(option, t.to_dec());
@@ -0,0 +1,401 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Dollar Greeks and beta-weighting.
+//!
+//! Raw Greeks are expressed in units of each option's own underlying, so they
+//! cannot be added across underlyings. Dollar Greeks convert them to money:
+//!
+//! * Dollar delta: `Δ·S`, the value of the equivalent underlying position.
+//! * Dollar gamma: `Γ·S²/100`, the change in dollar delta for a 1% move.
+//! * Dollar vega: vega, already the value change per volatility point.
+//!
+//! Beta-weighting expresses those exposures in units of a reference
+//! underlying (e.g. SPY) with price `S_ref`, assuming the underlying moves
+//! `β` times the reference in relative terms: the weighted delta is
+//! `β·Δ·S/S_ref` and the weighted gamma `Γ·(β·S/S_ref)²`.
+
+use crate::error::GreeksError;
+use crate::error::greeks::MathErrorKind;
+use crate::greeks::Greeks;
+use crate::model::option::Options;
+use crate::utils::OhlcvCandle;
+use chrono::NaiveDate;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::MathematicalOps;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::{Add, AddAssign};
+use utoipa::ToSchema;
+
+/// Delta, gamma and vega of an exposure in its own units and in money.
+///
+/// Gamma and vega carry the sign of the position side, so short options
+/// report negative gamma and vega.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DollarGreeks {
+    /// Delta in units of the underlying.
+    pub delta: Decimal,
+    /// Gamma in units of the underlying.
+    pub gamma: Decimal,
+    /// Vega per volatility point.
+    pub vega: Decimal,
+    /// Delta times the underlying price.
+    pub dollar_delta: Decimal,
+    /// Change in dollar delta for a 1% move of the underlying.
+    pub dollar_gamma: Decimal,
+    /// Value change per volatility point.
+    pub dollar_vega: Decimal,
+}
+
+impl DollarGreeks {
+    /// Converts delta, gamma and vega measured at `underlying_price`.
+    #[must_use]
+    pub fn from_greeks(
+        underlying_price: Positive,
+        delta: Decimal,
+        gamma: Decimal,
+        vega: Decimal,
+    ) -> Self {
+        let price = underlying_price.to_dec();
+        DollarGreeks {
+            delta,
+            gamma,
+            vega,
+            dollar_delta: delta * price,
+            dollar_gamma: gamma * price * price / Decimal::ONE_HUNDRED,
+            dollar_vega: vega,
+        }
+    }
+
+    /// Converts the Greeks of `option`, with gamma and vega signed by side.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any [`GreeksError`] from the Greek computations.
+    pub fn from_option(option: &Options) -> Result<Self, GreeksError> {
+        option.dollar_greeks()
+    }
+
+    /// Scales every Greek by a contract `multiplier`, e.g. 100 shares per
+    /// equity option contract.
+    #[must_use]
+    pub fn scaled(self, multiplier: Positive) -> Self {
+        let multiplier = multiplier.to_dec();
+        DollarGreeks {
+            delta: self.delta * multiplier,
+            gamma: self.gamma * multiplier,
+            vega: self.vega * multiplier,
+            dollar_delta: self.dollar_delta * multiplier,
+            dollar_gamma: self.dollar_gamma * multiplier,
+            dollar_vega: self.dollar_vega * multiplier,
+        }
+    }
+
+    /// Delta and gamma expressed in units of the reference underlying.
+    #[must_use]
+    pub fn beta_weighted(&self, weighting: &BetaWeighting) -> BetaWeightedGreeks {
+        let reference_price = weighting.reference_price.to_dec();
+        let beta = weighting.beta;
+        let dollar_delta = beta * self.dollar_delta;
+        let dollar_gamma = beta * beta * self.dollar_gamma;
+        let (delta, gamma) = if reference_price.is_zero() {
+            (Decimal::ZERO, Decimal::ZERO)
+        } else {
+            (
+                dollar_delta / reference_price,
+                dollar_gamma * Decimal::ONE_HUNDRED / (reference_price * reference_price),
+            )
+        };
+        BetaWeightedGreeks {
+            delta,
+            gamma,
+            dollar_delta,
+            dollar_gamma,
+        }
+    }
+}
+
+impl Add for DollarGreeks {
+    type Output = DollarGreeks;
+
+    fn add(self, other: DollarGreeks) -> DollarGreeks {
+        DollarGreeks {
+            delta: self.delta + other.delta,
+            gamma: self.gamma + other.gamma,
+            vega: self.vega + other.vega,
+            dollar_delta: self.dollar_delta + other.dollar_delta,
+            dollar_gamma: self.dollar_gamma + other.dollar_gamma,
+            dollar_vega: self.dollar_vega + other.dollar_vega,
+        }
+    }
+}
+
+impl AddAssign for DollarGreeks {
+    fn add_assign(&mut self, other: DollarGreeks) {
+        *self = *self + other;
+    }
+}
+
+/// Beta of an underlying against a reference underlying at a given price.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BetaWeighting {
+    /// Beta of the underlying against the reference.
+    pub beta: Decimal,
+    /// Current price of the reference underlying.
+    pub reference_price: Positive,
+}
+
+impl BetaWeighting {
+    /// Creates a weighting with a supplied beta.
+    #[must_use]
+    pub fn new(beta: Decimal, reference_price: Positive) -> Self {
+        BetaWeighting {
+            beta,
+            reference_price,
+        }
+    }
+
+    /// Creates a weighting with the beta estimated from candle histories,
+    /// see [`estimate_beta`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`estimate_beta`].
+    pub fn from_candles(
+        asset: &[OhlcvCandle],
+        reference: &[OhlcvCandle],
+        reference_price: Positive,
+    ) -> Result<Self, GreeksError> {
+        Ok(Self::new(estimate_beta(asset, reference)?, reference_price))
+    }
+}
+
+/// Delta and gamma in units of a reference underlying.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BetaWeightedGreeks {
+    /// Equivalent delta in reference units.
+    pub delta: Decimal,
+    /// Equivalent gamma in reference units.
+    pub gamma: Decimal,
+    /// Beta-weighted dollar delta.
+    pub dollar_delta: Decimal,
+    /// Beta-weighted dollar gamma.
+    pub dollar_gamma: Decimal,
+}
+
+impl Add for BetaWeightedGreeks {
+    type Output = BetaWeightedGreeks;
+
+    fn add(self, other: BetaWeightedGreeks) -> BetaWeightedGreeks {
+        BetaWeightedGreeks {
+            delta: self.delta + other.delta,
+            gamma: self.gamma + other.gamma,
+            dollar_delta: self.dollar_delta + other.dollar_delta,
+            dollar_gamma: self.dollar_gamma + other.dollar_gamma,
+        }
+    }
+}
+
+impl AddAssign for BetaWeightedGreeks {
+    fn add_assign(&mut self, other: BetaWeightedGreeks) {
+        *self = *self + other;
+    }
+}
+
+/// Estimates the beta of `asset` against `reference` by ordinary least
+/// squares on the log returns of their closes.
+///
+/// Candles are matched on date and time, so both histories may have gaps;
+/// returns are taken between consecutive matched candles.
+///
+/// # Errors
+///
+/// Returns [`GreeksError::MathError`] when fewer than three candles match,
+/// a close is not positive, or the reference returns have no variance.
+pub fn estimate_beta(
+    asset: &[OhlcvCandle],
+    reference: &[OhlcvCandle],
+) -> Result<Decimal, GreeksError> {
+    let reference_closes: BTreeMap<(NaiveDate, &str), Decimal> = reference
+        .iter()
+        .map(|candle| ((candle.date, candle.time.as_str()), candle.close))
+        .collect();
+    let mut matched: Vec<(NaiveDate, &str, Decimal, Decimal)> = asset
+        .iter()
+        .filter_map(|candle| {
+            reference_closes
+                .get(&(candle.date, candle.time.as_str()))
+                .map(|close| (candle.date, candle.time.as_str(), candle.close, *close))
+        })
+        .collect();
+    matched.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    if matched.len() < 3 {
+        return Err(GreeksError::MathError(MathErrorKind::InvalidDomain {
+            value: matched.len() as f64,
+            reason: "beta estimation needs at least three matching candles".to_string(),
+        }));
+    }
+
+    let returns = matched
+        .windows(2)
+        .map(|pair| match pair {
+            [
+                (_, _, asset_start, reference_start),
+                (_, _, asset_end, reference_end),
+            ] => Ok((
+                log_return(*asset_start, *asset_end)?,
+                log_return(*reference_start, *reference_end)?,
+            )),
+            _ => Err(GreeksError::MathError(MathErrorKind::InvalidDomain {
+                value: 0.0,
+                reason: "incomplete return window".to_string(),
+            })),
+        })
+        .collect::<Result<Vec<(Decimal, Decimal)>, GreeksError>>()?;
+
+    let count = Decimal::from(returns.len());
+    let asset_mean = returns.iter().map(|(asset, _)| *asset).sum::<Decimal>() / count;
+    let reference_mean = returns
+        .iter()
+        .map(|(_, reference)| *reference)
+        .sum::<Decimal>()
+        / count;
+    let covariance: Decimal = returns
+        .iter()
+        .map(|(asset, reference)| (*asset - asset_mean) * (*reference - reference_mean))
+        .sum();
+    let variance: Decimal = returns
+        .iter()
+        .map(|(_, reference)| (*reference - reference_mean) * (*reference - reference_mean))
+        .sum();
+    if variance.is_zero() {
+        return Err(GreeksError::MathError(MathErrorKind::DivisionByZero));
+    }
+    Ok(covariance / variance)
+}
+
+fn log_return(start: Decimal, end: Decimal) -> Result<Decimal, GreeksError> {
+    if start <= Decimal::ZERO || end <= Decimal::ZERO {
+        return Err(GreeksError::MathError(MathErrorKind::InvalidDomain {
+            value: 0.0,
+            reason: format!("closes must be positive, got {start} and {end}"),
+        }));
+    }
+    Ok((end / start).ln())
+}
+
+#[cfg(test)]
+mod tests_dollar {
+    use super::*;
+    use crate::Options;
+    use crate::model::types::{OptionStyle, Side};
+    use crate::model::utils::create_sample_option_with_days;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn candles(closes: &[f64]) -> Vec<OhlcvCandle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(day, close)| {
+                let close = Decimal::try_from(*close).unwrap();
+                OhlcvCandle {
+                    date: NaiveDate::from_ymd_opt(2026, 3, 1 + day as u32).unwrap(),
+                    time: "16:00:00".to_string(),
+                    open: close,
+                    high: close,
+                    low: close,
+                    close,
+                    volume: 0,
+                }
+            })
+            .collect()
+    }
+
+    fn option(side: Side) -> Options {
+        Options {
+            dividend_yield: Positive::ZERO,
+            ..create_sample_option_with_days(
+                OptionStyle::Call,
+                side,
+                Positive::HUNDRED,
+                Positive::ONE,
+                Positive::HUNDRED,
+                pos_or_panic!(0.25),
+                pos_or_panic!(30.0),
+            )
+        }
+    }
+
+    #[test]
+    fn test_from_greeks_and_scaling() {
+        let greeks =
+            DollarGreeks::from_greeks(Positive::HUNDRED, dec!(0.5), dec!(0.04), dec!(0.11));
+        assert_eq!(greeks.dollar_delta, dec!(50));
+        assert_eq!(greeks.dollar_gamma, dec!(4));
+        assert_eq!(greeks.dollar_vega, dec!(0.11));
+
+        let contract = greeks.scaled(Positive::HUNDRED);
+        assert_eq!(contract.dollar_delta, dec!(5000));
+        assert_eq!(contract.delta, dec!(50));
+    }
+
+    #[test]
+    fn test_short_option_signs() {
+        let long = DollarGreeks::from_option(&option(Side::Long)).unwrap();
+        let short = DollarGreeks::from_option(&option(Side::Short)).unwrap();
+        assert!(long.gamma > Decimal::ZERO);
+        assert_eq!(short.gamma, -long.gamma);
+        assert_eq!(short.dollar_vega, -long.dollar_vega);
+        assert_eq!((long + short).dollar_delta, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_beta_weighted() {
+        let greeks = DollarGreeks::from_greeks(Positive::HUNDRED, dec!(100), dec!(2), dec!(0));
+        let weighted = greeks.beta_weighted(&BetaWeighting::new(dec!(1.5), pos_or_panic!(500.0)));
+        // 100 shares at 100 with beta 1.5 behave like 30 units at 500.
+        assert_eq!(weighted.delta, dec!(30));
+        assert_eq!(weighted.dollar_delta, dec!(15000));
+        // Γ·(β·S/S_ref)² = 2·0.09
+        assert_eq!(weighted.gamma, dec!(0.18));
+    }
+
+    #[test]
+    fn test_estimate_beta() {
+        let reference = candles(&[100.0, 101.0, 99.0, 102.0, 103.0, 100.0]);
+        let doubled: Vec<f64> = reference
+            .windows(2)
+            .scan(50.0_f64, |price, pair| {
+                let ratio = (pair[1].close / pair[0].close).ln();
+                *price *= (2.0 * f64::try_from(ratio).unwrap()).exp();
+                Some(*price)
+            })
+            .collect();
+        let mut asset_closes = vec![50.0];
+        asset_closes.extend(doubled);
+        let asset = candles(&asset_closes);
+        let beta = estimate_beta(&asset, &reference).unwrap();
+        assert!((beta - dec!(2)).abs() < dec!(0.000001));
+
+        let weighting =
+            BetaWeighting::from_candles(&asset, &reference, pos_or_panic!(500.0)).unwrap();
+        assert_eq!(weighting.beta, beta);
+    }
+
+    #[test]
+    fn test_estimate_beta_errors() {
+        let short = candles(&[100.0, 101.0]);
+        assert!(estimate_beta(&short, &short).is_err());
+        let flat = candles(&[100.0, 100.0, 100.0, 100.0]);
+        assert!(matches!(
+            estimate_beta(&flat, &flat),
+            Err(GreeksError::MathError(MathErrorKind::DivisionByZero))
+        ));
+    }
+}
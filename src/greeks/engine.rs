@@ -0,0 +1,968 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Engine-aware Greeks
+//!
+//! Dispatches Greek calculations on a [`PricingEngine`] and on the option's
+//! exercise style, so that a collection mixing equity, futures and FX options
+//! with European or American exercise reports every leg under the model that
+//! actually prices it.
+//!
+//! | Engine              | European          | American               | Bermuda         | Exotic          |
+//! |---------------------|-------------------|------------------------|-----------------|-----------------|
+//! | `ClosedFormBS`      | analytic BSM      | BAW bump-and-reprice   | binomial bump   | BS-kernel bump  |
+//! | `ClosedFormBlack76` | analytic Black-76 | BAW (`b = 0`) bump     | unsupported     | unsupported     |
+//! | `ClosedFormGK`      | analytic GK       | BAW (`q = r_f`) bump   | binomial bump   | unsupported     |
+//! | `MonteCarlo`        | engine bump       | engine bump            | engine bump     | engine bump     |
+//! | `Binomial`          | lattice bump      | lattice bump           | lattice bump    | unsupported     |
+//! | `BaroneAdesiWhaley` | unsupported       | BAW bump               | unsupported     | unsupported     |
+//! | `Telegraph`         | unsupported       | unsupported            | unsupported     | unsupported     |
+//! | `ClosedFormExotic`  | unsupported       | unsupported            | unsupported     | exotic bump     |
+//! | `MonteCarloGbm`     | seeded MC bump    | unsupported            | unsupported     | seeded MC bump  |
+//! | `LongstaffSchwartz` | unsupported       | unsupported            | unsupported     | unsupported     |
+//! | `FiniteDifference`  | grid bump         | grid bump              | grid bump       | grid bump       |
+//! | `Heston`            | model bump        | unsupported            | unsupported     | unsupported     |
+//! | `Sabr`              | model bump        | unsupported            | unsupported     | unsupported     |
+//!
+//! Greeks without a closed form under the selected model (for example vanna
+//! under Black-76) are obtained by bump-and-reprice on that model's pricing
+//! kernel.
+//!
+//! ## Units
+//!
+//! Bumped Greeks use exactly the units of the analytic BSM functions in
+//! [`crate::greeks`]: delta carries the long/short sign, every Greek scales
+//! with `quantity`, vega/rho/rho_d are per 1 % move, theta, charm and color
+//! are per calendar day, and veta follows the `vega / (100 · trading days)`
+//! convention of [`crate::greeks::veta`].
+//!
+//! ## Limitations
+//!
+//! * Monte Carlo Greeks re-run the simulator on every bump; unless the
+//!   simulator is seeded they carry sampling noise of the order of the
+//!   price standard error divided by the bump size.
+//! * The Heston and SABR engines ignore `implied_volatility`, so their vega,
+//!   vanna, vomma and veta are zero; spot Greeks under SABR include the
+//!   shift of the smile with the forward.
+
+use crate::constants::{DEFAULT_BINOMIAL_STEPS, TRADING_DAYS};
+use crate::error::PricingError;
+use crate::error::greeks::GreeksError;
+use crate::greeks::black_76::{delta_b76, gamma_b76, rho_b76, theta_b76, vega_b76};
+use crate::greeks::equations::{
+    Greek, alpha, charm, color, delta, gamma, rho, rho_d, theta, vanna, vega, veta, vomma,
+};
+use crate::greeks::garman_kohlhagen::{
+    delta_gk, gamma_gk, rho_domestic_gk, rho_foreign_gk, theta_gk, vega_gk,
+};
+use crate::model::decimal::{d_add, d_div, d_mul, d_sub};
+use crate::model::types::{OptionType, Side};
+use crate::pricing::american::barone_adesi_whaley;
+use crate::pricing::black_76::black_76;
+use crate::pricing::black_scholes_model::black_scholes;
+use crate::pricing::garman_kohlhagen::garman_kohlhagen;
+use crate::pricing::unified::{PricingEngine, price_option};
+use crate::utils::DayCountBasis;
+use crate::{ExpirationDate, Options};
+use positive::Positive;
+use positive::constants::DAYS_IN_A_YEAR;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tracing::instrument;
+
+/// Relative spot bump (0.5 % of the underlying price).
+const SPOT_BUMP: Decimal = dec!(0.005);
+
+/// Absolute volatility bump (one vol point).
+const VOL_BUMP: Decimal = dec!(0.01);
+
+/// Absolute bump for the risk-free rate and the dividend yield (one basis point).
+const RATE_BUMP: Decimal = dec!(0.0001);
+
+/// The individual Greeks served by [`greek_with_engine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GreekKind {
+    Delta,
+    Gamma,
+    Theta,
+    Vega,
+    Rho,
+    RhoD,
+    Vanna,
+    Vomma,
+    Veta,
+    Charm,
+    Color,
+}
+
+/// Pricing kernel used for bump-and-reprice Greeks.
+#[derive(Debug, Clone, Copy)]
+enum Kernel<'a> {
+    /// `black_scholes`, which also dispatches the exotic closed forms.
+    BlackScholes,
+    /// Black-76 closed form on the forward price.
+    Black76,
+    /// Garman–Kohlhagen closed form on the FX spot rate.
+    GarmanKohlhagen,
+    /// Barone-Adesi–Whaley with the option's own dividend yield.
+    BaroneAdesiWhaley,
+    /// Barone-Adesi–Whaley with zero cost of carry (`q = r`), for futures.
+    BaroneAdesiWhaleyFutures,
+    /// Cox–Ross–Rubinstein lattice with early exercise, at the default
+    /// number of steps.
+    Binomial,
+    /// Any other engine, priced through [`price_option`].
+    Engine(&'a PricingEngine),
+}
+
+/// How a Greek of a given option is obtained under a given engine.
+#[derive(Debug, Clone, Copy)]
+enum Route<'a> {
+    AnalyticBsm,
+    AnalyticBlack76,
+    AnalyticGk,
+    Reprice(Kernel<'a>),
+}
+
+/// Market state perturbed by the finite-difference stencils.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    spot: Decimal,
+    vol: Decimal,
+    rate: Decimal,
+    dividend: Decimal,
+    years: Decimal,
+}
+
+/// Coordinate of [`Node`] along which a derivative is taken.
+#[derive(Debug, Clone, Copy)]
+enum Axis {
+    Spot,
+    Vol,
+    Rate,
+    Dividend,
+    Years,
+}
+
+impl Axis {
+    fn get(self, node: &Node) -> Decimal {
+        match self {
+            Axis::Spot => node.spot,
+            Axis::Vol => node.vol,
+            Axis::Rate => node.rate,
+            Axis::Dividend => node.dividend,
+            Axis::Years => node.years,
+        }
+    }
+
+    fn with(self, node: &Node, value: Decimal) -> Node {
+        let mut bumped = *node;
+        match self {
+            Axis::Spot => bumped.spot = value,
+            Axis::Vol => bumped.vol = value,
+            Axis::Rate => bumped.rate = value,
+            Axis::Dividend => bumped.dividend = value,
+            Axis::Years => bumped.years = value,
+        }
+        bumped
+    }
+
+    fn step(self, node: &Node) -> Decimal {
+        match self {
+            Axis::Spot => node.spot * SPOT_BUMP,
+            Axis::Vol => VOL_BUMP,
+            Axis::Rate | Axis::Dividend => RATE_BUMP,
+            Axis::Years => Decimal::ONE / DAYS_IN_A_YEAR.to_dec(),
+        }
+    }
+
+    /// Whether `value` is an admissible coordinate along this axis. Spot,
+    /// volatility and time stay strictly positive so that the closed forms
+    /// never see a degenerate `d1`.
+    fn admits(self, value: Decimal) -> bool {
+        match self {
+            Axis::Rate => true,
+            Axis::Dividend => value >= Decimal::ZERO,
+            Axis::Spot | Axis::Vol | Axis::Years => value > Decimal::ZERO,
+        }
+    }
+
+    /// Returns the `(down, up)` abscissae of a first-order stencil: central
+    /// when the downward bump stays admissible, forward otherwise.
+    fn stencil(self, node: &Node) -> (Decimal, Decimal) {
+        let x = self.get(node);
+        let h = self.step(node);
+        let down = x - h;
+        if self.admits(down) {
+            (down, x + h)
+        } else {
+            (x, x + h)
+        }
+    }
+}
+
+/// Bump-and-reprice evaluator for a single option under a single kernel.
+struct Repricer<'a> {
+    option: &'a Options,
+    kernel: Kernel<'a>,
+    base: Node,
+}
+
+impl<'a> Repricer<'a> {
+    fn new(option: &'a Options, kernel: Kernel<'a>) -> Result<Self, GreeksError> {
+        let base = Node {
+            spot: option.underlying_price.to_dec(),
+            vol: option.implied_volatility.to_dec(),
+            rate: option.risk_free_rate,
+            dividend: option.dividend_yield.to_dec(),
+            years: option.years_to_expiry()?.to_dec(),
+        };
+        Ok(Self {
+            option,
+            kernel,
+            base,
+        })
+    }
+
+    /// Price of one long contract at `node`.
+    fn price(&self, node: &Node) -> Result<Decimal, GreeksError> {
+        let mut unit = self.option.clone();
+        unit.side = Side::Long;
+        unit.quantity = Positive::ONE;
+        unit.underlying_price = Positive::new_decimal(node.spot)?;
+        unit.implied_volatility = Positive::new_decimal(node.vol)?;
+        unit.risk_free_rate = node.rate;
+        unit.yield_curve = self
+            .option
+            .yield_curve
+            .as_ref()
+            .map(|curve| curve.parallel_shift(node.rate - self.base.rate));
+        unit.dividend_yield = Positive::new_decimal(node.dividend)?;
+        let years = Positive::new_decimal(node.years)?;
+        // `years` was measured under the option's basis; restate it in
+        // calendar days.
+        unit.expiration_date = ExpirationDate::Days(years * DAYS_IN_A_YEAR);
+        unit.day_count = DayCountBasis::Actual365Fixed;
+
+        let price = match self.kernel {
+            Kernel::BlackScholes => black_scholes(&unit)?,
+            Kernel::Black76 => black_76(&unit)?,
+            Kernel::GarmanKohlhagen => garman_kohlhagen(&unit)?,
+            Kernel::BaroneAdesiWhaley => {
+                price_option(&unit, &PricingEngine::BaroneAdesiWhaley)?.to_dec()
+            }
+            Kernel::BaroneAdesiWhaleyFutures => barone_adesi_whaley(
+                unit.underlying_price,
+                unit.strike_price,
+                years,
+                unit.risk_free_rate,
+                Positive::new_decimal(unit.risk_free_rate)?,
+                unit.implied_volatility,
+                &unit.option_style,
+            )?,
+            Kernel::Binomial => price_option(
+                &unit,
+                &PricingEngine::Binomial {
+                    steps: DEFAULT_BINOMIAL_STEPS,
+                },
+            )?
+            .to_dec(),
+            Kernel::Engine(engine) => price_option(&unit, engine)?.to_dec(),
+        };
+        Ok(price)
+    }
+
+    /// First derivative of the unit price along `axis` at `node`.
+    fn first(&self, node: &Node, axis: Axis) -> Result<Decimal, GreeksError> {
+        let (down, up) = axis.stencil(node);
+        let p_up = self.price(&axis.with(node, up))?;
+        let p_down = self.price(&axis.with(node, down))?;
+        let diff = d_sub(p_up, p_down, "greeks::engine::first::diff")?;
+        Ok(d_div(diff, up - down, "greeks::engine::first::scaled")?)
+    }
+
+    /// Second derivative of the unit price along `axis` at `node`, shifting
+    /// the three-point stencil upwards when the central one is inadmissible.
+    fn second(&self, node: &Node, axis: Axis) -> Result<Decimal, GreeksError> {
+        let (down, up) = axis.stencil(node);
+        let h = up - axis.get(node);
+        let (lo, mid, hi) = if down == axis.get(node) {
+            (down, down + h, down + h + h)
+        } else {
+            (down, axis.get(node), up)
+        };
+        let p_lo = self.price(&axis.with(node, lo))?;
+        let p_mid = self.price(&axis.with(node, mid))?;
+        let p_hi = self.price(&axis.with(node, hi))?;
+        let two_mid = d_mul(Decimal::TWO, p_mid, "greeks::engine::second::two_mid")?;
+        let step = d_sub(p_hi, two_mid, "greeks::engine::second::step")?;
+        let numer = d_add(step, p_lo, "greeks::engine::second::numer")?;
+        let h_squared = d_mul(h, h, "greeks::engine::second::h_squared")?;
+        Ok(d_div(numer, h_squared, "greeks::engine::second::scaled")?)
+    }
+
+    /// Mixed second derivative of the unit price along `a` and `b`.
+    fn cross(&self, node: &Node, a: Axis, b: Axis) -> Result<Decimal, GreeksError> {
+        let (b_down, b_up) = b.stencil(node);
+        let upper = self.first(&b.with(node, b_up), a)?;
+        let lower = self.first(&b.with(node, b_down), a)?;
+        let diff = d_sub(upper, lower, "greeks::engine::cross::diff")?;
+        Ok(d_div(diff, b_up - b_down, "greeks::engine::cross::scaled")?)
+    }
+
+    /// Raw (unit, long, unscaled) sensitivity for `kind`.
+    fn raw(&self, kind: GreekKind) -> Result<Decimal, GreeksError> {
+        let node = &self.base;
+        match kind {
+            GreekKind::Delta => self.first(node, Axis::Spot),
+            GreekKind::Gamma => self.second(node, Axis::Spot),
+            GreekKind::Theta => Ok(-self.first(node, Axis::Years)?),
+            GreekKind::Vega => self.first(node, Axis::Vol),
+            GreekKind::Rho => self.first(node, Axis::Rate),
+            GreekKind::RhoD => self.first(node, Axis::Dividend),
+            GreekKind::Vanna => self.cross(node, Axis::Spot, Axis::Vol),
+            GreekKind::Vomma => self.second(node, Axis::Vol),
+            GreekKind::Veta => self.cross(node, Axis::Vol, Axis::Years),
+            GreekKind::Charm => Ok(-self.cross(node, Axis::Spot, Axis::Years)?),
+            GreekKind::Color => {
+                let (down, up) = Axis::Years.stencil(node);
+                let upper = self.second(&Axis::Years.with(node, up), Axis::Spot)?;
+                let lower = self.second(&Axis::Years.with(node, down), Axis::Spot)?;
+                let diff = d_sub(upper, lower, "greeks::engine::color::diff")?;
+                Ok(-d_div(diff, up - down, "greeks::engine::color::scaled")?)
+            }
+        }
+    }
+
+    /// Sensitivity for `kind` in the units and sign convention of the
+    /// analytic BSM Greeks.
+    fn greek(&self, kind: GreekKind) -> Result<Decimal, GreeksError> {
+        let raw = self.raw(kind)?;
+        let divisor = reporting_divisor(kind, self.option.day_count.days_per_year());
+        let scaled = d_div(raw, divisor, "greeks::engine::greek::unit")?;
+        let signed = if kind == GreekKind::Delta && !self.option.is_long() {
+            -scaled
+        } else {
+            scaled
+        };
+        Ok(d_mul(
+            signed,
+            self.option.quantity.to_dec(),
+            "greeks::engine::greek::position_weighted",
+        )?)
+    }
+}
+
+/// Divisor mapping a raw derivative onto the reporting unit of the analytic
+/// BSM Greek of the same kind; time Greeks are reported per unit day of the
+/// option's day-count basis.
+fn reporting_divisor(kind: GreekKind, days_per_year: Decimal) -> Decimal {
+    match kind {
+        GreekKind::Delta | GreekKind::Gamma | GreekKind::Vanna => Decimal::ONE,
+        GreekKind::Vega | GreekKind::Rho | GreekKind::RhoD | GreekKind::Vomma => {
+            Decimal::ONE_HUNDRED
+        }
+        GreekKind::Theta | GreekKind::Charm | GreekKind::Color => days_per_year,
+        GreekKind::Veta => Decimal::ONE_HUNDRED * Decimal::ONE_HUNDRED * TRADING_DAYS.to_dec(),
+    }
+}
+
+#[cold]
+fn unsupported(label: &str, engine: &str) -> GreeksError {
+    GreeksError::Pricing(Box::new(PricingError::unsupported_option_type(
+        label, engine,
+    )))
+}
+
+fn exercise_label(option_type: &OptionType) -> &'static str {
+    match option_type {
+        OptionType::European => "European",
+        OptionType::American => "American",
+        OptionType::Bermuda { .. } => "Bermuda",
+        _ => "exotic",
+    }
+}
+
+/// Selects how Greeks of `option` are computed under `engine`.
+fn route<'a>(option: &Options, engine: &'a PricingEngine) -> Result<Route<'a>, GreeksError> {
+    let route = match (engine, &option.option_type) {
+        (PricingEngine::ClosedFormBS, OptionType::European) => Route::AnalyticBsm,
+        (PricingEngine::ClosedFormBS, OptionType::American) => {
+            Route::Reprice(Kernel::BaroneAdesiWhaley)
+        }
+        (PricingEngine::ClosedFormBS, OptionType::Bermuda { .. }) => {
+            Route::Reprice(Kernel::Binomial)
+        }
+        (PricingEngine::ClosedFormBS, _) => Route::Reprice(Kernel::BlackScholes),
+        (PricingEngine::ClosedFormBlack76, OptionType::European) => Route::AnalyticBlack76,
+        (PricingEngine::ClosedFormBlack76, OptionType::American) => {
+            Route::Reprice(Kernel::BaroneAdesiWhaleyFutures)
+        }
+        (PricingEngine::ClosedFormBlack76, other) => {
+            return Err(unsupported(exercise_label(other), "Black-76"));
+        }
+        (PricingEngine::ClosedFormGK, OptionType::European) => Route::AnalyticGk,
+        (PricingEngine::ClosedFormGK, OptionType::American) => {
+            Route::Reprice(Kernel::BaroneAdesiWhaley)
+        }
+        (PricingEngine::ClosedFormGK, OptionType::Bermuda { .. }) => {
+            Route::Reprice(Kernel::Binomial)
+        }
+        (PricingEngine::ClosedFormGK, other) => {
+            return Err(unsupported(exercise_label(other), "Garman-Kohlhagen"));
+        }
+        (PricingEngine::MonteCarlo { .. }, _) => Route::Reprice(Kernel::Engine(engine)),
+        (PricingEngine::Telegraph { .. }, other) => {
+            // A single simulated path per price makes finite differences meaningless.
+            return Err(unsupported(exercise_label(other), "Telegraph"));
+        }
+        (PricingEngine::LongstaffSchwartz { .. }, other) => {
+            // The simulated paths are fixed, so bumped inputs would not move them.
+            return Err(unsupported(exercise_label(other), "Longstaff-Schwartz"));
+        }
+        (
+            PricingEngine::Binomial { .. }
+            | PricingEngine::BaroneAdesiWhaley
+            | PricingEngine::ClosedFormExotic
+            | PricingEngine::MonteCarloGbm { .. }
+            | PricingEngine::FiniteDifference { .. }
+            | PricingEngine::Heston { .. }
+            | PricingEngine::Sabr { .. },
+            other,
+        ) => {
+            if !engine.supports(other) {
+                return Err(unsupported(exercise_label(other), engine.name()));
+            }
+            Route::Reprice(Kernel::Engine(engine))
+        }
+    };
+    Ok(route)
+}
+
+/// Bump-and-reprice fallback for a Greek without a closed form under `kernel`.
+fn reprice(option: &Options, kernel: Kernel<'_>, kind: GreekKind) -> Result<Decimal, GreeksError> {
+    Repricer::new(option, kernel)?.greek(kind)
+}
+
+#[instrument(skip(option, engine), fields(
+    strike = %option.strike_price,
+    style = ?option.option_style,
+    kind = ?kind,
+))]
+fn greek_with_engine(
+    option: &Options,
+    engine: &PricingEngine,
+    kind: GreekKind,
+) -> Result<Decimal, GreeksError> {
+    match route(option, engine)? {
+        Route::AnalyticBsm => match kind {
+            GreekKind::Delta => delta(option),
+            GreekKind::Gamma => gamma(option),
+            GreekKind::Theta => theta(option),
+            GreekKind::Vega => vega(option),
+            GreekKind::Rho => rho(option),
+            GreekKind::RhoD => rho_d(option),
+            GreekKind::Vanna => vanna(option),
+            GreekKind::Vomma => vomma(option),
+            GreekKind::Veta => veta(option),
+            GreekKind::Charm => charm(option),
+            GreekKind::Color => color(option),
+        },
+        Route::AnalyticBlack76 => match kind {
+            GreekKind::Delta => delta_b76(option),
+            GreekKind::Gamma => gamma_b76(option),
+            GreekKind::Theta => theta_b76(option),
+            GreekKind::Vega => vega_b76(option),
+            GreekKind::Rho => rho_b76(option),
+            // The forward already embeds the carry: no dividend sensitivity.
+            GreekKind::RhoD => Ok(Decimal::ZERO),
+            _ => reprice(option, Kernel::Black76, kind),
+        },
+        Route::AnalyticGk => match kind {
+            GreekKind::Delta => delta_gk(option),
+            GreekKind::Gamma => gamma_gk(option),
+            GreekKind::Theta => theta_gk(option),
+            GreekKind::Vega => vega_gk(option),
+            GreekKind::Rho => rho_domestic_gk(option),
+            GreekKind::RhoD => rho_foreign_gk(option),
+            _ => reprice(option, Kernel::GarmanKohlhagen, kind),
+        },
+        Route::Reprice(kernel) => {
+            if option.years_to_expiry()? == Positive::ZERO {
+                return expired_greek(option, kind);
+            }
+            reprice(option, kernel, kind)
+        }
+    }
+}
+
+/// Greeks of an expired option: the payoff is locked, so only delta survives.
+fn expired_greek(option: &Options, kind: GreekKind) -> Result<Decimal, GreeksError> {
+    if kind != GreekKind::Delta {
+        return Ok(Decimal::ZERO);
+    }
+    let mut european = option.clone();
+    european.option_type = OptionType::European;
+    delta(&european)
+}
+
+/// Calculates the delta of `option` under `engine`.
+///
+/// Uses the closed-form delta of the engine for European exercise and
+/// bump-and-reprice otherwise (see the [module documentation](self)). The
+/// result carries the long/short sign and scales with `quantity`.
+///
+/// # Errors
+///
+/// Returns [`GreeksError::Pricing`] wrapping
+/// [`PricingError::UnsupportedOptionType`] when the engine cannot price the
+/// option's exercise style, and propagates any pricing, expiration or
+/// decimal error raised by the underlying kernel.
+pub fn delta_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Delta)
+}
+
+/// Calculates the gamma of `option` under `engine`.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn gamma_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Gamma)
+}
+
+/// Calculates the theta of `option` under `engine`, per calendar day.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn theta_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Theta)
+}
+
+/// Calculates the vega of `option` under `engine`, per 1 % change in volatility.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn vega_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Vega)
+}
+
+/// Calculates the rho of `option` under `engine`, per 1 % change in the
+/// risk-free (domestic) rate.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn rho_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Rho)
+}
+
+/// Calculates the dividend-yield (foreign-rate under Garman–Kohlhagen) rho of
+/// `option` under `engine`, per 1 % change.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn rho_d_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::RhoD)
+}
+
+/// Calculates the vanna of `option` under `engine`.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn vanna_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Vanna)
+}
+
+/// Calculates the vomma of `option` under `engine`.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn vomma_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Vomma)
+}
+
+/// Calculates the veta of `option` under `engine`.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn veta_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Veta)
+}
+
+/// Calculates the charm of `option` under `engine`, per calendar day.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn charm_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Charm)
+}
+
+/// Calculates the color of `option` under `engine`, per calendar day.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn color_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    greek_with_engine(option, engine, GreekKind::Color)
+}
+
+/// Calculates the alpha (gamma / theta) of `option` under `engine`.
+///
+/// Mirrors [`crate::greeks::alpha`]: zero when gamma is zero and
+/// `Decimal::MAX` when theta is zero.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn alpha_with_engine(option: &Options, engine: &PricingEngine) -> Result<Decimal, GreeksError> {
+    if matches!(route(option, engine)?, Route::AnalyticBsm) {
+        return alpha(option);
+    }
+    let gamma = gamma_with_engine(option, engine)?;
+    let theta = theta_with_engine(option, engine)?;
+    if gamma == Decimal::ZERO {
+        return Ok(Decimal::ZERO);
+    }
+    if theta == Decimal::ZERO {
+        return Ok(Decimal::MAX);
+    }
+    Ok(d_div(gamma, theta, "greeks::engine::alpha")?)
+}
+
+/// Calculates the full set of Greeks of `option` under `engine`.
+///
+/// # Errors
+///
+/// Same failure modes as [`delta_with_engine`].
+pub fn greeks_with_engine(option: &Options, engine: &PricingEngine) -> Result<Greek, GreeksError> {
+    Ok(Greek {
+        delta: delta_with_engine(option, engine)?,
+        gamma: gamma_with_engine(option, engine)?,
+        theta: theta_with_engine(option, engine)?,
+        vega: vega_with_engine(option, engine)?,
+        rho: rho_with_engine(option, engine)?,
+        rho_d: rho_d_with_engine(option, engine)?,
+        alpha: alpha_with_engine(option, engine)?,
+        vanna: vanna_with_engine(option, engine)?,
+        vomma: vomma_with_engine(option, engine)?,
+        veta: veta_with_engine(option, engine)?,
+        charm: charm_with_engine(option, engine)?,
+        color: color_with_engine(option, engine)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::greeks::Greeks;
+    use crate::model::types::OptionStyle;
+    use crate::model::utils::create_sample_option_with_days;
+    use crate::pricing::YieldCurve;
+    use positive::pos_or_panic;
+
+    fn option(option_type: OptionType, style: OptionStyle, side: Side) -> Options {
+        Options {
+            option_type,
+            dividend_yield: Positive::ZERO,
+            ..create_sample_option_with_days(
+                style,
+                side,
+                pos_or_panic!(95.0),
+                Positive::ONE,
+                Positive::HUNDRED,
+                pos_or_panic!(0.25),
+                pos_or_panic!(90.0),
+            )
+        }
+    }
+
+    fn close(a: Decimal, b: Decimal, tol: Decimal) -> bool {
+        (a - b).abs() <= tol * (Decimal::ONE + b.abs())
+    }
+
+    #[test]
+    fn test_bumped_bsm_matches_analytic_units() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            let european = option(OptionType::European, style, Side::Long);
+            let repricer = Repricer::new(&european, Kernel::BlackScholes).unwrap();
+            let cases = [
+                (GreekKind::Delta, delta(&european).unwrap()),
+                (GreekKind::Gamma, gamma(&european).unwrap()),
+                (GreekKind::Theta, theta(&european).unwrap()),
+                (GreekKind::Vega, vega(&european).unwrap()),
+                (GreekKind::Rho, rho(&european).unwrap()),
+                (GreekKind::RhoD, rho_d(&european).unwrap()),
+                (GreekKind::Vanna, vanna(&european).unwrap()),
+                (GreekKind::Vomma, vomma(&european).unwrap()),
+                (GreekKind::Veta, veta(&european).unwrap()),
+                (GreekKind::Charm, charm(&european).unwrap()),
+                (GreekKind::Color, color(&european).unwrap()),
+            ];
+            for (kind, analytic) in cases {
+                let bumped = repricer.greek(kind).unwrap();
+                assert!(
+                    close(bumped, analytic, dec!(0.02)),
+                    "{style:?} {kind:?}: bumped {bumped} vs analytic {analytic}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_american_call_without_dividend_matches_european() {
+        let american = option(OptionType::American, OptionStyle::Call, Side::Long);
+        let european = option(OptionType::European, OptionStyle::Call, Side::Long);
+        let engine = PricingEngine::ClosedFormBS;
+        assert!(close(
+            delta_with_engine(&american, &engine).unwrap(),
+            delta(&european).unwrap(),
+            dec!(0.01)
+        ));
+        assert!(close(
+            vega_with_engine(&american, &engine).unwrap(),
+            vega(&european).unwrap(),
+            dec!(0.01)
+        ));
+    }
+
+    #[test]
+    fn test_american_put_greeks_reflect_early_exercise() {
+        let american = option(OptionType::American, OptionStyle::Put, Side::Long);
+        let european = option(OptionType::European, OptionStyle::Put, Side::Long);
+
+        let american_delta = delta(&american).unwrap();
+        assert!(american_delta < Decimal::ZERO && american_delta > Decimal::NEGATIVE_ONE);
+        assert!(american_delta < delta(&european).unwrap());
+        assert!(gamma(&american).unwrap() > Decimal::ZERO);
+        assert!(vega(&american).unwrap() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_american_rho_bumps_the_yield_curve() {
+        let flat = option(OptionType::American, OptionStyle::Put, Side::Long);
+        let curved = Options {
+            risk_free_rate: Decimal::ZERO,
+            ..flat.clone()
+        }
+        .with_yield_curve(YieldCurve::flat(flat.risk_free_rate));
+        let engine = PricingEngine::ClosedFormBS;
+        let expected = rho_with_engine(&flat, &engine).unwrap();
+        assert!(expected < Decimal::ZERO);
+        assert!(close(
+            rho_with_engine(&curved, &engine).unwrap(),
+            expected,
+            dec!(1e-6)
+        ));
+    }
+
+    #[test]
+    fn test_american_side_and_quantity_follow_bsm_conventions() {
+        let long = option(OptionType::American, OptionStyle::Put, Side::Long);
+        let mut short = option(OptionType::American, OptionStyle::Put, Side::Short);
+        short.quantity = pos_or_panic!(3.0);
+
+        let engine = PricingEngine::ClosedFormBS;
+        let long_delta = delta_with_engine(&long, &engine).unwrap();
+        let short_delta = delta_with_engine(&short, &engine).unwrap();
+        assert!(close(short_delta, -long_delta * dec!(3), dec!(1e-9)));
+
+        let long_gamma = gamma_with_engine(&long, &engine).unwrap();
+        let short_gamma = gamma_with_engine(&short, &engine).unwrap();
+        assert!(close(short_gamma, long_gamma * dec!(3), dec!(1e-9)));
+    }
+
+    #[test]
+    fn test_black_76_engine_uses_closed_form() {
+        let future = option(OptionType::European, OptionStyle::Call, Side::Long);
+        let engine = PricingEngine::ClosedFormBlack76;
+        assert_eq!(
+            delta_with_engine(&future, &engine).unwrap(),
+            delta_b76(&future).unwrap()
+        );
+        assert_eq!(rho_d_with_engine(&future, &engine).unwrap(), Decimal::ZERO);
+        assert!(vanna_with_engine(&future, &engine).is_ok());
+
+        let american = option(OptionType::American, OptionStyle::Put, Side::Long);
+        let american_delta = delta_with_engine(&american, &engine).unwrap();
+        assert!(american_delta < Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_black_76_engine_rejects_bermuda() {
+        let bermuda = option(
+            OptionType::Bermuda {
+                exercise_dates: vec![pos_or_panic!(0.1), pos_or_panic!(0.2)],
+            },
+            OptionStyle::Put,
+            Side::Long,
+        );
+        let result = delta_with_engine(&bermuda, &PricingEngine::ClosedFormBlack76);
+        assert!(matches!(result, Err(GreeksError::Pricing(_))));
+    }
+
+    #[test]
+    fn test_closed_form_engines_price_what_their_greeks_bump() {
+        let mut american = option(OptionType::American, OptionStyle::Put, Side::Long);
+        american.dividend_yield = pos_or_panic!(0.02);
+        let bermuda = option(
+            OptionType::Bermuda {
+                exercise_dates: vec![pos_or_panic!(0.1), pos_or_panic!(0.2)],
+            },
+            OptionStyle::Put,
+            Side::Long,
+        );
+        let baw = price_option(&american, &PricingEngine::BaroneAdesiWhaley).unwrap();
+        let lattice = price_option(
+            &bermuda,
+            &PricingEngine::Binomial {
+                steps: DEFAULT_BINOMIAL_STEPS,
+            },
+        )
+        .unwrap();
+
+        for engine in [PricingEngine::ClosedFormBS, PricingEngine::ClosedFormGK] {
+            assert!(engine.supports(&american.option_type));
+            assert!(engine.supports(&bermuda.option_type));
+            assert_eq!(price_option(&american, &engine).unwrap(), baw);
+            assert_eq!(price_option(&bermuda, &engine).unwrap(), lattice);
+
+            // The engine delta is the slope of the engine price.
+            let h = american.underlying_price.to_dec() * SPOT_BUMP;
+            let bumped = |shift: Decimal| {
+                let mut moved = american.clone();
+                moved.underlying_price =
+                    Positive::new_decimal(american.underlying_price.to_dec() + shift).unwrap();
+                price_option(&moved, &engine).unwrap().to_dec()
+            };
+            let slope = (bumped(h) - bumped(-h)) / (h + h);
+            let engine_delta = delta_with_engine(&american, &engine).unwrap();
+            assert!(
+                (engine_delta - slope).abs() < dec!(1e-6),
+                "{}: delta {engine_delta} vs price slope {slope}",
+                engine.name()
+            );
+        }
+
+        let black_76 = PricingEngine::ClosedFormBlack76;
+        assert!(black_76.supports(&american.option_type));
+        assert!(price_option(&american, &black_76).is_ok());
+        assert!(!black_76.supports(&bermuda.option_type));
+        assert!(matches!(
+            price_option(&bermuda, &black_76),
+            Err(PricingError::UnsupportedOptionType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_garman_kohlhagen_engine_maps_foreign_rho() {
+        let mut fx = option(OptionType::European, OptionStyle::Call, Side::Long);
+        fx.dividend_yield = pos_or_panic!(0.03);
+        let engine = PricingEngine::ClosedFormGK;
+        assert_eq!(
+            rho_d_with_engine(&fx, &engine).unwrap(),
+            rho_foreign_gk(&fx).unwrap()
+        );
+        assert_eq!(
+            delta_with_engine(&fx, &engine).unwrap(),
+            delta_gk(&fx).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bermuda_delta_uses_lattice() {
+        let bermuda = option(
+            OptionType::Bermuda {
+                exercise_dates: vec![
+                    pos_or_panic!(0.05),
+                    pos_or_panic!(0.1),
+                    pos_or_panic!(0.15),
+                    pos_or_panic!(0.2),
+                ],
+            },
+            OptionStyle::Put,
+            Side::Long,
+        );
+        let value = delta(&bermuda).unwrap();
+        assert!(value < Decimal::ZERO && value > Decimal::NEGATIVE_ONE);
+    }
+
+    #[test]
+    fn test_expired_american_reports_intrinsic_delta() {
+        let mut american = option(OptionType::American, OptionStyle::Put, Side::Long);
+        american.expiration_date = ExpirationDate::Days(Positive::ZERO);
+        let engine = PricingEngine::ClosedFormBS;
+        assert_eq!(
+            delta_with_engine(&american, &engine).unwrap(),
+            Decimal::NEGATIVE_ONE
+        );
+        assert_eq!(
+            gamma_with_engine(&american, &engine).unwrap(),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_lattice_and_baw_engines_agree_on_american_delta() {
+        let american = option(OptionType::American, OptionStyle::Put, Side::Long);
+        let lattice = PricingEngine::Binomial {
+            steps: std::num::NonZeroUsize::new(300).unwrap(),
+        };
+        let baw = delta_with_engine(&american, &PricingEngine::BaroneAdesiWhaley).unwrap();
+        let tree = delta_with_engine(&american, &lattice).unwrap();
+        assert!(
+            (baw - tree).abs() < dec!(0.02),
+            "BAW {baw} vs lattice {tree}"
+        );
+
+        let european = option(OptionType::European, OptionStyle::Put, Side::Long);
+        assert!(delta_with_engine(&european, &PricingEngine::BaroneAdesiWhaley).is_err());
+        let telegraph = PricingEngine::Telegraph {
+            steps: std::num::NonZeroUsize::new(10).unwrap(),
+        };
+        assert!(delta_with_engine(&european, &telegraph).is_err());
+    }
+
+    struct MixedBook {
+        legs: Vec<Options>,
+    }
+
+    impl Greeks for MixedBook {
+        fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+            Ok(self.legs.iter().collect())
+        }
+
+        fn greeks_engine(&self, option: &Options) -> PricingEngine {
+            if option.underlying_symbol == "FUT" {
+                PricingEngine::ClosedFormBlack76
+            } else {
+                PricingEngine::ClosedFormBS
+            }
+        }
+    }
+
+    #[test]
+    fn test_greeks_trait_dispatches_per_leg_engine() {
+        let mut future = option(OptionType::European, OptionStyle::Call, Side::Long);
+        future.underlying_symbol = "FUT".to_string();
+        let american = option(OptionType::American, OptionStyle::Put, Side::Short);
+        let book = MixedBook {
+            legs: vec![future.clone(), american.clone()],
+        };
+
+        let expected = delta_b76(&future).unwrap()
+            + delta_with_engine(&american, &PricingEngine::ClosedFormBS).unwrap();
+        assert_eq!(book.delta().unwrap(), expected);
+        assert!(book.greeks().is_ok());
+    }
+}
@@ -5,9 +5,16 @@
 ******************************************************************************/
 use crate::constants::{TRADING_DAYS, ZERO};
 use crate::error::greeks::GreeksError;
+use crate::greeks::dollar::{BetaWeightedGreeks, BetaWeighting, DollarGreeks};
+use crate::greeks::engine::{
+    alpha_with_engine, charm_with_engine, color_with_engine, delta_with_engine, gamma_with_engine,
+    rho_d_with_engine, rho_with_engine, theta_with_engine, vanna_with_engine, vega_with_engine,
+    veta_with_engine, vomma_with_engine,
+};
 use crate::greeks::utils::{big_n, d1, d2, n};
 use crate::model::decimal::{d_div, d_mul};
 use crate::model::types::{OptionStyle, OptionType};
+use crate::pricing::unified::PricingEngine;
 use crate::{Options, Side};
 use positive::Positive;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
@@ -149,6 +156,17 @@ pub trait Greeks {
     /// Returns a `GreeksError` if there is an issue retrieving the options.
     fn get_options(&self) -> Result<Vec<&Options>, GreeksError>;
 
+    /// Returns the pricing engine under which the Greeks of `option` are computed.
+    ///
+    /// Defaults to [`PricingEngine::ClosedFormBS`], which already dispatches on
+    /// the exercise style (analytic for European legs, Barone-Adesi–Whaley
+    /// bump-and-reprice for American legs, binomial lattice for Bermuda legs).
+    /// Override it to report futures legs under Black-76 or FX legs under
+    /// Garman–Kohlhagen; see [`crate::greeks::greeks_with_engine`].
+    fn greeks_engine(&self, _option: &Options) -> PricingEngine {
+        PricingEngine::ClosedFormBS
+    }
+
     /// Calculates and returns all Greeks as a single `Greek` struct.
     ///
     /// This method provides a convenient way to obtain all Greek values at once.
@@ -198,7 +216,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut delta_value = Decimal::ZERO;
         for option in options {
-            delta_value += delta(option)?;
+            delta_value += delta_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(delta_value)
     }
@@ -215,7 +233,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut gamma_value = Decimal::ZERO;
         for option in options {
-            gamma_value += gamma(option)?;
+            gamma_value += gamma_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(gamma_value)
     }
@@ -232,7 +250,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut theta_value = Decimal::ZERO;
         for option in options {
-            theta_value += theta(option)?;
+            theta_value += theta_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(theta_value)
     }
@@ -249,7 +267,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut vega_value = Decimal::ZERO;
         for option in options {
-            vega_value += vega(option)?;
+            vega_value += vega_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(vega_value)
     }
@@ -266,7 +284,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut rho_value = Decimal::ZERO;
         for option in options {
-            rho_value += rho(option)?;
+            rho_value += rho_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(rho_value)
     }
@@ -283,7 +301,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut rho_d_value = Decimal::ZERO;
         for option in options {
-            rho_d_value += rho_d(option)?;
+            rho_d_value += rho_d_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(rho_d_value)
     }
@@ -300,7 +318,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut alpha_value = Decimal::ZERO;
         for option in options {
-            alpha_value += alpha(option)?;
+            alpha_value += alpha_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(alpha_value)
     }
@@ -317,7 +335,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut vanna_value = Decimal::ZERO;
         for option in options {
-            vanna_value += vanna(option)?;
+            vanna_value += vanna_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(vanna_value)
     }
@@ -334,7 +352,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut vomma_value = Decimal::ZERO;
         for option in options {
-            vomma_value += vomma(option)?;
+            vomma_value += vomma_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(vomma_value)
     }
@@ -351,7 +369,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut veta_value = Decimal::ZERO;
         for option in options {
-            veta_value += veta(option)?;
+            veta_value += veta_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(veta_value)
     }
@@ -368,7 +386,7 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut charm_value = Decimal::ZERO;
         for option in options {
-            charm_value += charm(option)?;
+            charm_value += charm_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(charm_value)
     }
@@ -385,10 +403,51 @@ pub trait Greeks {
         let options = self.get_options()?;
         let mut color_value = Decimal::ZERO;
         for option in options {
-            color_value += color(option)?;
+            color_value += color_with_engine(option, &self.greeks_engine(option))?;
         }
         Ok(color_value)
     }
+
+    /// Calculates delta, gamma and vega in money terms for all options.
+    ///
+    /// Each option is converted at its own underlying price, so the dollar
+    /// figures can be added across underlyings. Gamma and vega are signed by
+    /// the position side.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or any Greek calculation fails.
+    fn dollar_greeks(&self) -> Result<DollarGreeks, GreeksError> {
+        let options = self.get_options()?;
+        let mut dollar_greeks = DollarGreeks::default();
+        for option in options {
+            let engine = self.greeks_engine(option);
+            let sign = if option.is_short() {
+                -Decimal::ONE
+            } else {
+                Decimal::ONE
+            };
+            dollar_greeks += DollarGreeks::from_greeks(
+                option.underlying_price,
+                delta_with_engine(option, &engine)?,
+                gamma_with_engine(option, &engine)? * sign,
+                vega_with_engine(option, &engine)? * sign,
+            );
+        }
+        Ok(dollar_greeks)
+    }
+
+    /// Calculates delta and gamma in units of a reference underlying.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the dollar Greeks can't be calculated.
+    fn beta_weighted_greeks(
+        &self,
+        weighting: &BetaWeighting,
+    ) -> Result<BetaWeightedGreeks, GreeksError> {
+        Ok(self.dollar_greeks()?.beta_weighted(weighting))
+    }
 }
 
 /// Calculates the delta of an option.
@@ -466,6 +525,9 @@ pub trait Greeks {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "AAPL".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match delta(&option) {
@@ -475,9 +537,12 @@ pub trait Greeks {
 /// ```
 pub fn delta(option: &Options) -> Result<Decimal, GreeksError> {
     if !matches!(option.option_type, OptionType::European) {
-        return crate::greeks::numerical::numerical_delta(option);
+        return delta_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return delta(&adjusted);
     }
-    let expiration_date = option.expiration_date.get_years()?;
+    let expiration_date = option.years_to_expiry()?;
 
     // For an option when the time to expiration is zero (i.e., at the moment of expiration),
     // the delta takes discrete values based solely on whether the option is In-The-Money (ITM) or
@@ -634,6 +699,9 @@ pub fn delta(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match gamma(&option) {
@@ -658,12 +726,15 @@ pub fn delta(option: &Options) -> Result<Decimal, GreeksError> {
 /// evaluation fails).
 pub fn gamma(option: &Options) -> Result<Decimal, GreeksError> {
     if !matches!(option.option_type, OptionType::European) {
-        return crate::greeks::numerical::numerical_gamma(option);
+        return gamma_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return gamma(&adjusted);
     }
     if option.implied_volatility == ZERO {
         return Ok(Decimal::ZERO);
     }
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    let expiration_date: Positive = option.years_to_expiry()?;
     if expiration_date == Decimal::ZERO {
         // At expiration, gamma is 0 for all cases
         return Ok(Decimal::ZERO);
@@ -777,6 +848,9 @@ pub fn gamma(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match theta(&option) {
@@ -798,7 +872,13 @@ pub fn gamma(option: &Options) -> Result<Decimal, GreeksError> {
 /// [`GreeksError`] surfaced by `numerical_theta` for non-European
 /// options.
 pub fn theta(option: &Options) -> Result<Decimal, GreeksError> {
-    let t = option.expiration_date.get_years()?;
+    if !matches!(option.option_type, OptionType::European) {
+        return theta_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return theta(&adjusted);
+    }
+    let t = option.years_to_expiry()?;
     if t == Decimal::ZERO {
         return Ok(Decimal::ZERO);
     }
@@ -848,7 +928,7 @@ pub fn theta(option: &Options) -> Result<Decimal, GreeksError> {
     )?;
     Ok(d_div(
         weighted,
-        Decimal::from(365),
+        option.day_count.days_per_year(),
         "greeks::theta::per_day",
     )?)
 }
@@ -924,6 +1004,9 @@ pub fn theta(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match vega(&option) {
@@ -946,7 +1029,13 @@ pub fn theta(option: &Options) -> Result<Decimal, GreeksError> {
 /// [`GreeksError`] surfaced by `numerical_vega` for non-European
 /// options.
 pub fn vega(option: &Options) -> Result<Decimal, GreeksError> {
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    if !matches!(option.option_type, OptionType::European) {
+        return vega_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return vega(&adjusted);
+    }
+    let expiration_date: Positive = option.years_to_expiry()?;
     if expiration_date == Decimal::ZERO {
         // At expiration, volatility has no impact on option price
         return Ok(Decimal::ZERO);
@@ -1055,6 +1144,9 @@ pub fn vega(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match rho(&option) {
@@ -1077,8 +1169,14 @@ pub fn vega(option: &Options) -> Result<Decimal, GreeksError> {
 /// [`GreeksError`] surfaced by `numerical_rho` for non-European
 /// options.
 pub fn rho(option: &Options) -> Result<Decimal, GreeksError> {
+    if !matches!(option.option_type, OptionType::European) {
+        return rho_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return rho(&adjusted);
+    }
     // Get time to expiration first and validate
-    let t = option.expiration_date.get_years()?;
+    let t = option.years_to_expiry()?;
     if t == Decimal::ZERO {
         return Ok(Decimal::ZERO);
     }
@@ -1204,6 +1302,9 @@ pub fn rho(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match rho_d(&option) {
@@ -1228,7 +1329,13 @@ pub fn rho(option: &Options) -> Result<Decimal, GreeksError> {
 /// [`GreeksError`] surfaced by intermediate Black–Scholes kernels
 /// (typically [`GreeksError::Pricing`] on numerical failure).
 pub fn rho_d(option: &Options) -> Result<Decimal, GreeksError> {
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    if !matches!(option.option_type, OptionType::European) {
+        return rho_d_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return rho_d(&adjusted);
+    }
+    let expiration_date: Positive = option.years_to_expiry()?;
     let d1 = d1(
         option.underlying_price,
         option.strike_price,
@@ -1344,6 +1451,9 @@ pub fn alpha(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match vanna(&option) {
@@ -1364,11 +1474,17 @@ pub fn alpha(option: &Options) -> Result<Decimal, GreeksError> {
 /// [`GreeksError`] surfaced by the underlying Black–Scholes
 /// evaluation (typically [`GreeksError::Pricing`]).
 pub fn vanna(option: &Options) -> Result<Decimal, GreeksError> {
+    if !matches!(option.option_type, OptionType::European) {
+        return vanna_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return vanna(&adjusted);
+    }
     if option.implied_volatility == ZERO {
         return Ok(Decimal::ZERO);
     }
 
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    let expiration_date: Positive = option.years_to_expiry()?;
     let d1 = d1(
         option.underlying_price,
         option.strike_price,
@@ -1464,6 +1580,9 @@ pub fn vanna(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match vomma(&option) {
@@ -1489,7 +1608,13 @@ pub fn vanna(option: &Options) -> Result<Decimal, GreeksError> {
 /// [`GreeksError`] surfaced by the underlying Black–Scholes
 /// evaluation.
 pub fn vomma(option: &Options) -> Result<Decimal, GreeksError> {
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    if !matches!(option.option_type, OptionType::European) {
+        return vomma_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return vomma(&adjusted);
+    }
+    let expiration_date: Positive = option.years_to_expiry()?;
     if expiration_date == Decimal::ZERO {
         // At expiration, volatility has no impact on option price
         return Ok(Decimal::ZERO);
@@ -1583,6 +1708,9 @@ pub fn vomma(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match veta(&option) {
@@ -1603,7 +1731,13 @@ pub fn vomma(option: &Options) -> Result<Decimal, GreeksError> {
 /// [`GreeksError`] surfaced by the underlying Black–Scholes
 /// evaluation.
 pub fn veta(option: &Options) -> Result<Decimal, GreeksError> {
-    let expiration_date: Positive = option.expiration_date.get_years()?;
+    if !matches!(option.option_type, OptionType::European) {
+        return veta_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return veta(&adjusted);
+    }
+    let expiration_date: Positive = option.years_to_expiry()?;
     if expiration_date == Decimal::ZERO {
         // At expiration, volatility has no impact on option price
         return Ok(Decimal::ZERO);
@@ -1739,6 +1873,9 @@ pub fn veta(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match charm(&option) {
@@ -1758,7 +1895,13 @@ pub fn veta(option: &Options) -> Result<Decimal, GreeksError> {
 /// cannot be converted to a positive year fraction, and propagates any
 /// [`GreeksError`] surfaced by intermediate Black–Scholes kernels.
 pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
-    let tau = option.expiration_date.get_years()?;
+    if !matches!(option.option_type, OptionType::European) {
+        return charm_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return charm(&adjusted);
+    }
+    let tau = option.years_to_expiry()?;
     // if DTE is zero we can assume Charm is also zero
     if tau == Decimal::ZERO {
         return Ok(Decimal::ZERO);
@@ -1800,7 +1943,7 @@ pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
     )?;
     Ok(d_div(
         weighted,
-        Decimal::from(365),
+        option.day_count.days_per_year(),
         "greeks::charm::per_day",
     )?)
 }
@@ -1887,6 +2030,9 @@ pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
 ///     option_style: OptionStyle::Call,
 ///     underlying_symbol: "".to_string(),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// match color(&option) {
@@ -1907,7 +2053,13 @@ pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
 /// cannot be converted to a positive year fraction, and propagates any
 /// [`GreeksError`] surfaced by intermediate Black–Scholes kernels.
 pub fn color(option: &Options) -> Result<Decimal, GreeksError> {
-    let tau = option.expiration_date.get_years()?;
+    if !matches!(option.option_type, OptionType::European) {
+        return color_with_engine(option, &PricingEngine::ClosedFormBS);
+    }
+    if let Some(adjusted) = option.escrowed()? {
+        return color(&adjusted);
+    }
+    let tau = option.years_to_expiry()?;
     // if DTE is zero we can assume Color is also zero
     if tau == Decimal::ZERO {
         return Ok(Decimal::ZERO);
@@ -1951,7 +2103,11 @@ pub fn color(option: &Options) -> Result<Decimal, GreeksError> {
         option.quantity.to_dec(),
         "greeks::color::numerator_quantity",
     )?;
-    let color = d_div(numerator, Decimal::from(365), "greeks::color::per_day")?;
+    let color = d_div(
+        numerator,
+        option.day_count.days_per_year(),
+        "greeks::color::per_day",
+    )?;
     Ok(color)
 }
 
@@ -2525,6 +2681,9 @@ pub mod tests_rho_equations {
             option_style: style,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -4327,3 +4486,59 @@ pub mod tests_color_equations {
         assert_relative_eq!(color_value.to_f64().unwrap(), -0.00046416, epsilon = 1e-8);
     }
 }
+
+#[cfg(test)]
+mod tests_day_count_basis {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::utils::{DayCountBasis, MarketZone, TradingCalendar};
+    use chrono::NaiveTime;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn option(expiration_date: ExpirationDate) -> Options {
+        Options::new(
+            OptionType::European,
+            Side::Long,
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            expiration_date,
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        )
+    }
+
+    fn weekdays() -> DayCountBasis {
+        DayCountBasis::BusinessDays(TradingCalendar::new(
+            "Weekdays",
+            MarketZone::Utc,
+            NaiveTime::MIN,
+            NaiveTime::from_hms_opt(23, 59, 59).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn test_business_day_basis_drives_time_and_theta() {
+        // Any seven calendar days hold exactly five business days.
+        let business = option(ExpirationDate::Days(pos_or_panic!(7.0))).with_day_count(weekdays());
+        let years = business.years_to_expiry().unwrap();
+        assert!((years.to_f64() - 5.0 / 252.0).abs() < 1e-6, "{years}");
+
+        // Same year fraction on the calendar basis.
+        let calendar = option(ExpirationDate::Days(years * pos_or_panic!(365.0)));
+        let delta_gap = delta(&business).unwrap() - delta(&calendar).unwrap();
+        assert!(delta_gap.abs() < dec!(0.0001));
+
+        // Theta is per business day rather than per calendar day.
+        let per_business_day = theta(&business).unwrap();
+        let per_calendar_day = theta(&calendar).unwrap();
+        let rescaled = per_calendar_day * dec!(365) / dec!(252);
+        assert!((per_business_day - rescaled).abs() < dec!(0.001));
+        assert!(per_business_day < per_calendar_day);
+    }
+}
@@ -99,7 +99,7 @@ fn cost_of_carry(option: &Options) -> Decimal {
 /// BSM Greeks in `src/greeks/equations.rs`, where the d-values are
 /// undefined and the Greeks collapse to discrete intrinsic-state values.
 fn time_to_expiry(option: &Options) -> Result<Option<Positive>, GreeksError> {
-    let years = option.expiration_date.get_years()?;
+    let years = option.years_to_expiry()?;
     if years == Positive::ZERO {
         Ok(None)
     } else {
@@ -132,7 +132,7 @@ fn delta_at_expiry(option: &Options) -> Decimal {
 /// Computes (`d1`, `d2`) for Garman–Kohlhagen using `b = r_d − r_f` as the
 /// drift term, mirroring the helper used by the GK pricing kernel.
 fn calculate_d_values_gk(option: &Options) -> Result<(Decimal, Decimal), GreeksError> {
-    let years = option.expiration_date.get_years()?;
+    let years = option.years_to_expiry()?;
     let b = cost_of_carry(option);
     let d1_value = d1(
         option.underlying_price,
@@ -354,7 +354,11 @@ pub fn theta_gk(option: &Options) -> Result<Decimal, GreeksError> {
         option.quantity.to_dec(),
         "greeks::gk::theta::quantity",
     )?;
-    let result = d_div(weighted, Decimal::from(365), "greeks::gk::theta::per_day")?;
+    let result = d_div(
+        weighted,
+        option.day_count.days_per_year(),
+        "greeks::gk::theta::per_day",
+    )?;
     Ok(result)
 }
 
@@ -15,6 +15,10 @@ use positive::pos_or_panic;
 //! * `equations` - Implementation of Greek calculations (delta, gamma, theta, vega, rho, vanna,
 //! vomma, veta)
 //! * `utils` - Utility functions for Greek calculations and related math
+//! * `engine` - Engine-aware dispatch (`*_with_engine`) covering Black-76, Garman–Kohlhagen
+//!   and bump-and-reprice Greeks for American and Bermuda exercise
+//! * `dollar` - Dollar delta, gamma and vega, beta-weighting against a reference
+//!   underlying and regression beta estimation from candle histories
 //!
 //! ## Greeks Provided
 //!
@@ -66,6 +70,9 @@ use positive::pos_or_panic;
 //!             option_style: OptionStyle::Call,
 //!             dividend_yield: pos_or_panic!(0.01),
 //!             exotic_params: None,
+//!             yield_curve: None,
+//!             dividends: Default::default(),
+//!             day_count: Default::default(),
 //!         };
 //!
 //! // Calculate Greeks
@@ -184,12 +191,20 @@ use positive::pos_or_panic;
 //! ```
 
 mod black_76;
+mod dollar;
+mod engine;
 mod equations;
 mod garman_kohlhagen;
 pub mod numerical;
 mod utils;
 
 pub use black_76::{Black76Greeks, delta_b76, gamma_b76, rho_b76, theta_b76, vega_b76};
+pub use dollar::{BetaWeightedGreeks, BetaWeighting, DollarGreeks, estimate_beta};
+pub use engine::{
+    alpha_with_engine, charm_with_engine, color_with_engine, delta_with_engine, gamma_with_engine,
+    greeks_with_engine, rho_d_with_engine, rho_with_engine, theta_with_engine, vanna_with_engine,
+    vega_with_engine, veta_with_engine, vomma_with_engine,
+};
 pub use equations::{
     Greek, Greeks, GreeksSnapshot, charm, color, delta, gamma, rho, rho_d, theta, vanna, vega,
     veta, vomma,
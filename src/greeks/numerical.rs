@@ -134,7 +134,7 @@ pub fn numerical_vega(option: &Options) -> Result<Decimal, GreeksError> {
 /// the unified-pricing evaluator on the perturbed option clones
 /// (wrapped as [`GreeksError::Pricing`]).
 pub fn numerical_theta(option: &Options) -> Result<Decimal, GreeksError> {
-    let t = option.expiration_date.get_years()?;
+    let t = option.years_to_expiry()?;
     if t < H {
         return Ok(Decimal::ZERO);
     }
@@ -454,14 +454,14 @@ pub(crate) fn calculate_d_values(option: &Options) -> Result<(Decimal, Decimal),
         option.underlying_price,
         option.strike_price,
         b,
-        option.expiration_date.get_years()?,
+        option.years_to_expiry()?,
         option.implied_volatility,
     );
     let d2_value = d2(
         option.underlying_price,
         option.strike_price,
         b,
-        option.expiration_date.get_years()?,
+        option.years_to_expiry()?,
         option.implied_volatility,
     );
     Ok((d1_value?, d2_value?))
@@ -482,7 +482,7 @@ pub(crate) fn calculate_d_values(option: &Options) -> Result<(Decimal, Decimal),
 pub fn calculate_d_values_black_76(option: &Options) -> Result<(Decimal, Decimal), GreeksError> {
     // Black-76: cost of carry b = 0 (forward pricing, no carry term in d1/d2)
     let b = Decimal::ZERO;
-    let years = option.expiration_date.get_years()?;
+    let years = option.years_to_expiry()?;
     let d1_value = d1(
         option.underlying_price,
         option.strike_price,
@@ -658,6 +658,9 @@ mod tests_calculate_d_values {
             option_style: OptionStyle::Call,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
         let (d1_value, d2_value) = calculate_d_values(&option).unwrap();
 
@@ -2254,6 +2257,9 @@ mod tests_calculate_d_values_bis {
             option_style: OptionStyle::Call,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
         let (d1, d2) = calculate_d_values(&option).unwrap();
         assert_decimal_eq!(d1, dec!(0.1003), dec!(0.0001));
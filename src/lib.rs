@@ -38,6 +38,8 @@
 //! - **Monte Carlo Simulations**: Complex pricing scenarios and path-dependent options
 //! - **Telegraph Process Model**: Advanced stochastic modeling for jump-diffusion processes
 //! - **American Options**: Barone-Adesi-Whaley approximation for early exercise
+//! - **Term Structure and Dividends**: Bootstrapped yield curves and discrete cash-dividend schedules
+//! - **Trading Calendars**: NYSE, CME and Eurex holidays, AM/PM settlement times and business-day or trading-minute time to expiry
 //! - **Exotic Options**: Complete support for 14 exotic option types (see below)
 //!
 //! ### 2. **Greeks Calculation**
@@ -45,6 +47,7 @@
 //!   Charm, Color
 //! - Real-time sensitivity analysis
 //! - Greeks visualization and risk profiling
+//! - Dollar and beta-weighted Greeks with regression beta estimation
 //! - Custom Greeks implementations with adjustable parameters
 //!
 //! ### 3. **Volatility Models**
@@ -62,10 +65,11 @@
 //!
 //! ### 5. **Trading Strategies (25+ Strategies)**
 //! - **Single Leg**: Long/Short Calls and Puts
-//! - **Spreads**: Bull/Bear Call/Put Spreads
-//! - **Butterflies**: Long/Short Butterfly Spreads, Call Butterfly
-//! - **Complex**: Iron Condor, Iron Butterfly
-//! - **Volatility**: Long/Short Straddles and Strangles
+//! - **Spreads**: Bull/Bear Call/Put Spreads, Ratio Call/Put Spreads, Call/Put Back Spreads, Box Spread, Risk Reversal
+//! - **Butterflies**: Long/Short Butterfly Spreads, Call Butterfly, Call/Put Broken Wing Butterflies
+//! - **Complex**: Iron Condor, Iron Butterfly, Call/Put Condors, Jade Lizard, Reverse Jade Lizard
+//! - **Volatility**: Long/Short Straddles and Strangles, Strap, Strip
+//! - **Time Spreads**: Long/Short Calendar Spreads, Diagonal Spread, Double Calendar Spread
 //! - **Income**: Covered Calls (with spot leg support), Poor Man's Covered Call
 //! - **Protection**: Protective Puts, Collars
 //! - **Custom**: Flexible custom strategy framework
@@ -77,6 +81,7 @@
 //! - Profit/Loss calculations at various price points
 //! - Risk profiles and comprehensive visualizations
 //! - Delta neutrality analysis and adjustment
+//! - Delta hedging simulation with transaction costs and re-hedge bands
 //! - Probability analysis for strategy outcomes
 //!
 //! ### 7. **Backtesting Framework**
@@ -88,9 +93,12 @@
 //! ### 8. **Simulation Tools**
 //! - Monte Carlo simulations for strategy testing
 //! - Telegraph process implementation
+//! - Markov regime-switching walks and block bootstrap of historical returns
 //! - Random walk simulations
 //! - Custom simulation frameworks
 //! - Parametrized simulations with adjustable inputs
+//! - Walk model calibration from historical prices and candles (GARCH, OU, Merton, Heston) with AIC/BIC fit statistics
+//! - Correlated multi-asset simulation for rainbow, spread and exchange Monte Carlo and joint portfolio scenarios
 //!
 //! ### 9. **Visualization & Plotting**
 //! - Strategy payoff diagrams
@@ -250,6 +258,8 @@
 //! - `telegraph.rs`: Jump-diffusion process modeling
 //! - `payoff.rs`: Payoff function implementations
 //! - `american.rs`: Barone-Adesi-Whaley approximation
+//! - `yield_curve.rs`: Yield curve bootstrapping from deposits, swaps and treasuries
+//! - `dividends.rs`: Discrete cash-dividend schedules (escrowed dividend model)
 //! - **Exotic Options**:
 //!   - `asian.rs`: Asian option pricing
 //!   - `barrier.rs`: Barrier option pricing
@@ -276,7 +286,7 @@
 //! - **Protection**: `protective_put.rs`, `collar.rs`
 //! - `custom.rs`: Flexible custom strategy framework
 //! - `probabilities/`: Probability analysis for strategy outcomes
-//! - `delta_neutral/`: Delta neutrality analysis and adjustment
+//! - `delta_neutral/`: Delta neutrality analysis, adjustment and hedging simulation
 //!
 //! ### **Volatility** (`volatility/`)
 //! Volatility modeling and analysis:
@@ -289,6 +299,7 @@
 //! - Delta, Gamma, Theta, Vega, Rho, Vanna, Vomma, Veta, Charm, Color calculations
 //! - Real-time sensitivity analysis
 //! - Greeks-based risk management
+//! - Dollar delta, gamma and vega, beta-weighted to a reference underlying
 //!
 //! ### **Chains** (`chains/`)
 //! Option chain management and analysis:
@@ -307,8 +318,11 @@
 //! Monte Carlo and stochastic simulations:
 //! - Random walk implementations
 //! - Telegraph process modeling
+//! - Regime-switching and block bootstrap walks
 //! - Custom simulation frameworks
 //! - Parametrized simulation tools
+//! - `calibration.rs`: Fitting walk models to historical data
+//! - `multi_asset.rs`: Correlated multi-asset paths
 //!
 //! ### **Visualization** (`visualization/`)
 //! Comprehensive plotting and charting:
@@ -347,6 +361,16 @@
 //! - Position risk metrics
 //! - Break-even analysis
 //! - Risk profile generation
+//! - Portfolio SPAN margin over multi-instrument legs
+//! - Reg-T/CBOE strategy-based margin and buying-power reduction
+//! - Delta-gamma, historical and Monte Carlo VaR / Expected Shortfall
+//! - Stress scenarios with historical crash replays
+//!
+//! ### **Portfolio** (`portfolio/`)
+//! Aggregation across strategies and legs:
+//! - Add, remove and roll whole strategies
+//! - P&L, beta-weighted Greeks, margin and expiration ladders
+//! - JSON persistence
 //!
 //! ### **P&L** (`pnl/`)
 //! Profit and loss calculation:
@@ -383,6 +407,9 @@
 //! +option_style: OptionStyle
 //! +dividend_yield: Positive
 //! +exotic_params: Option~ExoticParams~
+//! +yield_curve: Option~YieldCurve~
+//! +dividends: DividendSchedule
+//! +day_count: DayCountBasis
 //! +calculate_price_black_scholes()
 //! +calculate_price_binomial()
 //! +time_to_expiration()
@@ -727,17 +754,25 @@
 //! - **Bear Call Spread**: Moderately bearish credit spread
 //! - **Bull Put Spread**: Moderately bullish credit spread
 //! - **Bear Put Spread**: Moderately bearish debit spread
+//! - **Ratio Call/Put Spreads**: Sell more options than bought beyond the long strike, profiting most at the short strike
+//! - **Call/Put Back Spreads**: Buy more options than sold beyond the short strike for a large move
+//! - **Box Spread**: Bull call spread plus bear put spread with a fixed payoff at expiration
+//! - **Risk Reversal**: Short out-of-the-money put financing a long out-of-the-money call
 //!
 //! ### **Butterfly Strategies**
 //! Market neutral strategies profiting from low volatility:
 //! - **Long Butterfly Spread**: Profits from price staying near middle strike
 //! - **Short Butterfly Spread**: Profits from price moving away from middle strike
 //! - **Call Butterfly**: Butterfly using only call options
+//! - **Call/Put Broken Wing Butterflies**: Butterflies with unequal wings for a lower debit or a credit
 //!
 //! ### **Complex Multi-Leg Strategies**
 //! Advanced strategies for experienced traders:
 //! - **Iron Condor**: Market neutral strategy with wide profit zone
 //! - **Iron Butterfly**: Market neutral strategy with narrow profit zone
+//! - **Call/Put Condors**: Condor spreads built from a single option style
+//! - **Jade Lizard**: Short put plus short call spread with no upside risk when the credit covers the spread
+//! - **Reverse Jade Lizard**: Short call plus short put spread with no downside risk when the credit covers the spread
 //!
 //! ### **Volatility Strategies**
 //! Strategies that profit from volatility changes:
@@ -745,6 +780,15 @@
 //! - **Short Straddle**: Profits from low volatility (range-bound market)
 //! - **Long Strangle**: Similar to straddle but with different strikes
 //! - **Short Strangle**: Credit strategy profiting from low volatility
+//! - **Strap**: Straddle with extra calls for a bullish bias
+//! - **Strip**: Straddle with extra puts for a bearish bias
+//!
+//! ### **Time Spread Strategies**
+//! Strategies whose legs expire on different dates, valued at the front expiry:
+//! - **Long Calendar Spread**: Short near-term and long far-term option at the same strike
+//! - **Short Calendar Spread**: Long near-term and short far-term option at the same strike
+//! - **Diagonal Spread**: Calendar spread with different strikes for a directional bias
+//! - **Double Calendar Spread**: Put calendar and call calendar at two strikes
 //!
 //! ### **Income Generation Strategies**
 //! Strategies focused on generating regular income:
@@ -1311,6 +1355,13 @@ pub mod metrics;
 /// scenario analysis.
 pub mod pnl;
 
+/// * `portfolio` - Aggregation of strategies and legs across underlyings.
+///
+/// Holds strategies of any type alongside spot, futures and perpetual legs,
+/// and aggregates their P&L, beta-weighted Greeks, margin and expiration
+/// ladder. Portfolios serialise to JSON.
+pub mod portfolio;
+
 /// * `pricing` - Option pricing models including Black-Scholes and numerical methods.
 ///
 /// Implementations of various option pricing models including Black-Scholes-Merton,
@@ -40,6 +40,11 @@
 //! - **Curve representation by strike**: P&L for price shock at each strike
 //! - **Surface representation (price vs volatility)**: Combined shock scenarios
 //!
+//! These metrics apply a single uniform shock. For named scenarios combining
+//! spot, volatility surface, rate and time shocks with full revaluation of a
+//! portfolio, including historical crash replays, see
+//! [`crate::risk::ScenarioEngine`].
+//!
 //! ## Usage Examples
 //!
 //! ### Volatility Sensitivity
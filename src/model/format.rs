@@ -38,13 +38,17 @@ impl fmt::Display for Options {
         if let Some(exotic) = &self.exotic_params {
             write!(f, "\nExotic Parameters: {exotic:?}")?;
         }
+        if !self.dividends.is_empty() {
+            write!(f, "\nCash Dividends: {}", self.dividends.dividends().len())?;
+        }
         Ok(())
     }
 }
 
 impl fmt::Debug for Options {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Options")
+        let mut debug = f.debug_struct("Options");
+        debug
             .field("option_type", &self.option_type)
             .field("side", &self.side)
             .field("underlying_symbol", &self.underlying_symbol)
@@ -56,8 +60,17 @@ impl fmt::Debug for Options {
             .field("risk_free_rate", &self.risk_free_rate)
             .field("option_style", &self.option_style)
             .field("dividend_yield", &self.dividend_yield)
-            .field("exotic_params", &self.exotic_params)
-            .finish()
+            .field("exotic_params", &self.exotic_params);
+        if let Some(yield_curve) = &self.yield_curve {
+            debug.field("yield_curve", yield_curve);
+        }
+        if !self.dividends.is_empty() {
+            debug.field("dividends", &self.dividends);
+        }
+        if !self.day_count.is_default() {
+            debug.field("day_count", &self.day_count);
+        }
+        debug.finish()
     }
 }
 
@@ -238,6 +251,9 @@ mod tests_options {
             option_style: OptionStyle::Call,
             dividend_yield: pos_or_panic!(0.02),
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
 
         let debug_output = format!("{options:?}");
@@ -279,6 +295,9 @@ mod tests_options {
             option_style: OptionStyle::Call,
             dividend_yield: pos_or_panic!(0.02),
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
 
         let display_output = format!("{options}");
@@ -341,6 +360,9 @@ mod tests_options {
             option_style: OptionStyle::Call,
             dividend_yield: pos_or_panic!(0.01),
             exotic_params: Some(exotic_params),
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
 
         let display_output = format!("{options}");
@@ -452,6 +474,9 @@ mod tests_position_type_display_debug {
                 option_style: OptionStyle::Call,
                 dividend_yield: pos_or_panic!(0.02),
                 exotic_params: None,
+                yield_curve: None,
+                dividends: Default::default(),
+                day_count: Default::default(),
             },
             Utc.from_utc_datetime(&naive_date),
         )
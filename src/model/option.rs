@@ -1,6 +1,6 @@
 use crate::ExpirationDate;
 use crate::chains::OptionData;
-use crate::constants::{IV_TOLERANCE, MAX_ITERATIONS_IV, ZERO};
+use crate::constants::ZERO;
 use crate::error::{
     GreeksError, OptionsError, OptionsResult, PricingError, StrategyError, VolatilityError,
 };
@@ -10,19 +10,21 @@ use crate::model::utils::calculate_optimal_price_range;
 use crate::pnl::utils::{PnL, PnLCalculator};
 use crate::pricing::monte_carlo::price_option_monte_carlo;
 use crate::pricing::{
-    BinomialPricingParams, Payoff, PayoffInfo, Profit, black_scholes, generate_binomial_tree,
-    price_binomial, telegraph,
+    BinomialPricingParams, DividendSchedule, Payoff, PayoffInfo, Profit, YieldCurve, black_scholes,
+    generate_binomial_tree, price_binomial, telegraph,
 };
 use crate::strategies::base::BasicAble;
+use crate::utils::DayCountBasis;
 use crate::visualization::{
     ColorScheme, Graph, GraphConfig, GraphData, LineStyle, Series2D, TraceMode,
 };
+use crate::volatility::{IvSolverConfig, solve_implied_volatility};
+use expiration_date::error::ExpirationDateError;
 use num_traits::FromPrimitive;
 use positive::Positive;
 #[cfg(test)]
 use positive::pos_or_panic;
 use rust_decimal::Decimal;
-use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
@@ -166,6 +168,21 @@ pub struct Options {
     /// Additional parameters required for exotic option types like Asian or Lookback options.
     /// This field is None for standard (vanilla) options.
     pub exotic_params: Option<ExoticParams>,
+
+    /// Term structure of risk-free rates. When set, pricing uses its zero rate to
+    /// expiration instead of `risk_free_rate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yield_curve: Option<YieldCurve>,
+
+    /// Cash dividends paid by the underlying, priced with the escrowed-dividend model
+    /// on top of the continuous `dividend_yield`.
+    #[serde(default, skip_serializing_if = "DividendSchedule::is_empty")]
+    pub dividends: DividendSchedule,
+
+    /// Convention measuring the time to expiration. Pricing, Greeks and theta
+    /// all read the year fraction through it.
+    #[serde(default, skip_serializing_if = "DayCountBasis::is_default")]
+    pub day_count: DayCountBasis,
 }
 
 impl Options {
@@ -228,7 +245,108 @@ impl Options {
             option_style,
             dividend_yield,
             exotic_params,
+            yield_curve: None,
+            dividends: DividendSchedule::default(),
+            day_count: DayCountBasis::default(),
+        }
+    }
+
+    /// Sets the convention measuring the time to expiration.
+    #[must_use]
+    pub fn with_day_count(mut self, day_count: DayCountBasis) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Returns the time to expiration in years under the option's day-count
+    /// basis.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpirationDateError`] when the expiration cannot be resolved
+    /// to a positive year fraction.
+    pub fn years_to_expiry(&self) -> Result<Positive, ExpirationDateError> {
+        self.day_count.years_to(&self.expiration_date)
+    }
+
+    /// Sets the term structure of risk-free rates used for pricing.
+    #[must_use]
+    pub fn with_yield_curve(mut self, yield_curve: YieldCurve) -> Self {
+        self.yield_curve = Some(yield_curve);
+        self
+    }
+
+    /// Sets the cash dividends paid by the underlying.
+    #[must_use]
+    pub fn with_dividends(mut self, dividends: DividendSchedule) -> Self {
+        self.dividends = dividends;
+        self
+    }
+
+    /// Returns the discount curve of the option: its yield curve, or a flat
+    /// curve at `risk_free_rate`.
+    #[must_use]
+    pub fn discount_curve(&self) -> YieldCurve {
+        self.yield_curve
+            .clone()
+            .unwrap_or_else(|| YieldCurve::flat(self.risk_free_rate))
+    }
+
+    /// Returns the continuously compounded risk-free rate to expiration: the
+    /// zero rate of the yield curve when one is set, else `risk_free_rate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PricingError::ExpirationDate`] when the expiration cannot be
+    /// resolved.
+    pub fn term_risk_free_rate(&self) -> Result<Decimal, PricingError> {
+        match &self.yield_curve {
+            Some(curve) => Ok(curve.zero_rate(self.years_to_expiry()?)),
+            None => Ok(self.risk_free_rate),
+        }
+    }
+
+    /// Returns the present value of the cash dividends going ex before
+    /// expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PricingError::ExpirationDate`] when the expiration cannot be
+    /// resolved.
+    pub fn dividends_present_value(&self) -> Result<Decimal, PricingError> {
+        if self.dividends.is_empty() {
+            return Ok(Decimal::ZERO);
         }
+        Ok(self.dividends.present_value(
+            &self.day_count,
+            Positive::ZERO,
+            self.years_to_expiry()?,
+            &self.discount_curve(),
+        )?)
+    }
+
+    /// Returns the option restated for scalar-rate pricers, or `None` when it
+    /// has neither a yield curve nor cash dividends.
+    ///
+    /// The restated option uses the zero rate to expiration and the escrowed
+    /// spot `S - PV(dividends)`.
+    pub(crate) fn escrowed(&self) -> Result<Option<Options>, PricingError> {
+        if self.yield_curve.is_none() && self.dividends.is_empty() {
+            return Ok(None);
+        }
+        let spot = self.underlying_price.to_dec() - self.dividends_present_value()?;
+        if spot <= Decimal::ZERO {
+            return Err(PricingError::other(
+                "present value of dividends exceeds the underlying price",
+            ));
+        }
+        Ok(Some(Options {
+            underlying_price: Positive::new_decimal(spot)?,
+            risk_free_rate: self.term_risk_free_rate()?,
+            yield_curve: None,
+            dividends: DividendSchedule::default(),
+            ..self.clone()
+        }))
     }
 
     /// Updates option parameters using data from an OptionData structure.
@@ -261,11 +379,11 @@ impl Options {
     /// # Errors
     ///
     /// Propagates any [`expiration_date::error::ExpirationDateError`] returned by
-    /// [`ExpirationDate::get_years`] (wrapped as [`OptionsError::ExpirationDate`])
+    /// [`Options::years_to_expiry`] (wrapped as [`OptionsError::ExpirationDate`])
     /// when the stored expiration cannot be converted to a positive
     /// year fraction (e.g. past expiration or invalid date).
     pub fn time_to_expiration(&self) -> OptionsResult<Positive> {
-        Ok(self.expiration_date.get_years()?)
+        Ok(self.years_to_expiry()?)
     }
 
     /// Determines if the option position is long (purchased).
@@ -332,6 +450,7 @@ impl Options {
             asset: self.underlying_price,
             volatility: self.implied_volatility,
             int_rate: self.risk_free_rate,
+            dividend_yield: self.dividend_yield,
             strike: self.strike_price,
             expiry,
             no_steps,
@@ -376,6 +495,7 @@ impl Options {
             asset: self.underlying_price,
             volatility: self.implied_volatility,
             int_rate: self.risk_free_rate,
+            dividend_yield: self.dividend_yield,
             strike: self.strike_price,
             expiry,
             no_steps,
@@ -683,27 +803,24 @@ impl Options {
     ///
     /// ### Implementation Details:
     ///
-    /// - **Binary Search**: The function uses a binary search approach to iteratively find
-    ///   the implied volatility (`volatility`) that narrows the difference between the calculated
-    ///   option price (via Black-Scholes) and the target `market_price`.
-    ///
-    /// - **Short Options Adjustment**: For short options, the market price is inverted (negated),
-    ///   and this adjustment ensures proper calculation of implied volatility.
+    /// - **Solver**: Delegates to
+    ///   [`solve_implied_volatility`](crate::volatility::solve_implied_volatility)
+    ///   with its default configuration: a Jäckel-style rational initial guess,
+    ///   Newton–Raphson on vega and a bracketed Brent fallback bounded by
+    ///   `MAX_ITERATIONS_IV`. The option's dividend yield is honoured.
     ///
-    /// - **Bounds and Iteration**: The method starts with a maximum bound (`5.0`, representing 500%
-    ///   volatility) and a lower bound (`0.0`). It adjusts these bounds based on whether the computed
-    ///   price is above or below the target and repeats until convergence or the maximum number of
-    ///   iterations is reached (`MAX_ITERATIONS_IV`).
+    /// - **Short Options Adjustment**: Short premiums may be quoted with a negative
+    ///   sign; the absolute value of `market_price` is matched.
     ///
-    /// - **Convergence Tolerance**: The function stops iterating when the computed price is within `IV_TOLERANCE`
-    ///   of the target market price or when the difference between the high and low bounds is smaller
-    ///   than a threshold (`0.0001`).
+    /// - **Convergence Tolerance**: The solver stops once the computed price is within
+    ///   `IV_TOLERANCE` of the target market price.
     ///
     /// ### Error Cases:
-    /// - **No Convergence**: If the binary search exhausts the allowed number of iterations (`MAX_ITERATIONS_IV`)
-    ///   without sufficiently narrowing down the implied volatility, the function returns an `ImpliedVolatilityError::NoConvergence`.
-    /// - **Invalid Parameters**: Bounds violations or invalid market inputs can potentially cause other errors
-    ///   during calculations.
+    /// - **No Convergence**: If both solver stages exhaust their iteration budgets the
+    ///   function returns `VolatilityError::NoConvergence`.
+    /// - **Invalid Parameters**: Prices outside the no-arbitrage bounds return
+    ///   `VolatilityError::InvalidPrice`; expired options or zero strikes / underlying
+    ///   prices return `VolatilityError::Options`.
     ///
     /// ### Example Usage:
     /// ```rust
@@ -737,73 +854,18 @@ impl Options {
     ///
     /// # Errors
     ///
-    /// Returns [`VolatilityError::PositiveError`] when the midpoint
-    /// volatility breaches the `Positive` invariant,
-    /// [`VolatilityError::NoConvergence`] when the bisection exhausts
-    /// `MAX_ITERATIONS_IV` without matching the target price, or propagates
-    /// [`VolatilityError::Options`] from the underlying Black–Scholes
-    /// evaluation (wrapped as [`OptionsError::ImpliedVolatilityInvariant`]
-    /// when the invariant check fails).
+    /// Returns [`VolatilityError::Options`] when the option is expired or has a
+    /// zero strike or underlying price, [`VolatilityError::InvalidPrice`] when
+    /// `market_price` is zero or violates the no-arbitrage bounds, and
+    /// [`VolatilityError::NoConvergence`] when the solver exhausts its
+    /// iteration budgets.
     pub fn calculate_implied_volatility(
         &self,
         market_price: Decimal,
     ) -> Result<Positive, VolatilityError> {
-        let is_short = self.is_short();
-        let target_price = if is_short {
-            -market_price
-        } else {
-            market_price
-        };
-
-        // Initialize high and low bounds for volatility (500% max).
-        let mut high = Positive::new(5.0)?;
-        let mut low = Positive::ZERO;
-
-        // Binary search through volatilities until we find one that gives us our target price
-        // or until we reach maximum iterations
-        for _ in 0..MAX_ITERATIONS_IV {
-            // Calculate midpoint volatility
-            let mid_vol = (high.to_dec() + low.to_dec()) / Decimal::TWO;
-            // mid_vol is the average of two non-negative bounds, so it is
-            // structurally non-negative; a None here would indicate a
-            // breached invariant on the bounds themselves.
-            let volatility = Positive::new_decimal(mid_vol).map_err(|e| {
-                OptionsError::ImpliedVolatilityInvariant {
-                    reason: format!("mid_vol invariant breached: {e}"),
-                }
-            })?;
-
-            // Calculate option price at this volatility
-            let mut option_copy = self.clone();
-            option_copy.implied_volatility = volatility;
-            let price = option_copy.calculate_price_black_scholes()?;
-
-            // Adjust price for short positions
-            let actual_price = if is_short { -price } else { price };
-
-            // Check if we're close enough to the target price
-            if (actual_price - target_price).abs() < IV_TOLERANCE {
-                return Ok(volatility);
-            }
-
-            // Update bounds based on whether this price was too high or too low
-            if actual_price > target_price {
-                high = volatility;
-            } else {
-                low = volatility;
-            }
-
-            // Check if our range is too small (meaning we've converged)
-            if (high - low).to_dec() < dec!(0.0001) {
-                return Ok(volatility);
-            }
-        }
-
-        // If we haven't found a solution after max iterations
-        Err(VolatilityError::NoConvergence {
-            iterations: MAX_ITERATIONS_IV,
-            last_volatility: (high + low) / Positive::TWO,
-        })
+        let market_price = Positive::new_decimal(market_price.abs())?;
+        let solution = solve_implied_volatility(self, market_price, &IvSolverConfig::default())?;
+        Ok(solution.volatility)
     }
 }
 
@@ -850,6 +912,9 @@ impl TryFrom<&OptionData> for Options {
             option_style: OptionStyle::Call,
             dividend_yield: option_data.dividend_yield.unwrap_or(Positive::ZERO),
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         })
     }
 }
@@ -869,6 +934,9 @@ impl Default for Options {
             option_style: OptionStyle::Call,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: DividendSchedule::default(),
+            day_count: DayCountBasis::default(),
         }
     }
 }
@@ -1311,6 +1379,9 @@ mod tests_valid_option {
             option_style: OptionStyle::Call,
             dividend_yield: pos_or_panic!(0.01),
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -2407,10 +2478,20 @@ mod tests_options_black_scholes {
 #[cfg(test)]
 mod tests_calculate_implied_volatility {
     use super::*;
+    use crate::constants::IV_TOLERANCE;
     use crate::error::VolatilityError;
     use positive::assert_pos_relative_eq;
     use rust_decimal_macros::dec;
 
+    /// Black-Scholes premium of `option` at `iv` minus the quoted premium.
+    fn reprice_residual(option: &Options, iv: Positive, market_price: Decimal) -> Decimal {
+        let repriced = Options {
+            implied_volatility: iv,
+            ..option.clone()
+        };
+        repriced.calculate_price_black_scholes().unwrap().abs() - market_price.abs()
+    }
+
     #[test]
     fn test_implied_volatility_call() {
         let option = Options::new(
@@ -2433,9 +2514,10 @@ mod tests_calculate_implied_volatility {
 
         assert_pos_relative_eq!(
             iv,
-            pos_or_panic!(0.111618041),
+            pos_or_panic!(0.111686771),
             Positive::new_decimal(IV_TOLERANCE).unwrap()
         );
+        assert!(reprice_residual(&option, iv, market_price).abs() < dec!(1e-6));
     }
 
     #[test]
@@ -2459,9 +2541,10 @@ mod tests_calculate_implied_volatility {
         let iv = option.calculate_implied_volatility(market_price).unwrap();
         assert_pos_relative_eq!(
             iv,
-            pos_or_panic!(0.125961),
+            pos_or_panic!(0.125937707),
             Positive::new_decimal(IV_TOLERANCE).unwrap()
         );
+        assert!(reprice_residual(&option, iv, market_price).abs() < dec!(1e-6));
     }
 
     #[test]
@@ -2486,9 +2569,10 @@ mod tests_calculate_implied_volatility {
 
         assert_pos_relative_eq!(
             iv,
-            pos_or_panic!(0.1258087),
+            pos_or_panic!(0.125753127),
             Positive::new_decimal(IV_TOLERANCE).unwrap()
         );
+        assert!(reprice_residual(&option, iv, market_price).abs() < dec!(1e-6));
     }
 
     #[test]
@@ -2512,9 +2596,10 @@ mod tests_calculate_implied_volatility {
         let iv = option.calculate_implied_volatility(market_price).unwrap();
         assert_pos_relative_eq!(
             iv,
-            pos_or_panic!(0.12611389),
+            pos_or_panic!(0.126050506),
             Positive::new_decimal(IV_TOLERANCE).unwrap()
         );
+        assert!(reprice_residual(&option, iv, market_price).abs() < dec!(1e-6));
     }
 
     #[test]
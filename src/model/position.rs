@@ -1420,6 +1420,9 @@ mod tests_position {
             option_style,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -2137,6 +2140,9 @@ mod tests_position_break_even {
             option_style,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -2370,6 +2376,9 @@ mod tests_position_max_loss_profit {
             option_style,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -182,6 +182,9 @@ pub fn create_sample_position(
             option_style,
             dividend_yield: pos_lit(dec!(0.01)),
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         },
         premium: pos_lit(dec!(5.0)),
         date: Utc::now(),
@@ -0,0 +1,83 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Portfolio Module
+//!
+//! Aggregates strategies of any type and standalone spot, futures, perpetual
+//! and option legs across underlyings into one [`Portfolio`].
+//!
+//! ## Core Features
+//!
+//! - **Strategies by identifier**: add, remove and roll whole strategies.
+//!   Each is stored as its [`StrategyType`](crate::strategies::StrategyType)
+//!   and positions, so it can be rebuilt through a
+//!   [`StrategyRequest`](crate::strategies::StrategyRequest).
+//! - **P&L**: unrealized P&L of every strategy and leg at current prices.
+//! - **Greeks**: net and dollar Greeks per underlying, plus delta and gamma
+//!   beta-weighted to a reference underlying with supplied or estimated betas.
+//! - **Margin**: Reg-T requirement per strategy and posted margin of
+//!   futures and perpetuals.
+//! - **Expiration ladder**: contracts expiring on each date.
+//! - **Persistence**: JSON serialisation of the whole portfolio.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use optionstratlib::ExpirationDate;
+//! use optionstratlib::model::leg::{Leg, SpotPosition};
+//! use optionstratlib::portfolio::Portfolio;
+//! use optionstratlib::risk::RegTMargin;
+//! use optionstratlib::strategies::ShortPut;
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal_macros::dec;
+//!
+//! fn main() -> Result<(), optionstratlib::error::Error> {
+//!     let short_put = ShortPut::new(
+//!         "AAPL".to_string(),
+//!         pos_or_panic!(95.0),
+//!         ExpirationDate::Days(pos_or_panic!(30.0)),
+//!         pos_or_panic!(0.2),
+//!         Positive::ONE,
+//!         Positive::HUNDRED,
+//!         dec!(0.05),
+//!         Positive::ZERO,
+//!         pos_or_panic!(2.0),
+//!         Positive::ZERO,
+//!         Positive::ZERO,
+//!     )?;
+//!
+//!     let mut portfolio = Portfolio::new("income")
+//!         .with_price("AAPL", pos_or_panic!(101.0))
+//!         .with_price("SPY", pos_or_panic!(500.0))
+//!         .with_beta("AAPL", dec!(1.2))
+//!         .with_reference("SPY");
+//!     portfolio.add_strategy("aapl-put", &short_put)?;
+//!     portfolio.add_leg(Leg::spot(SpotPosition::long(
+//!         "SPY".to_string(),
+//!         Positive::TEN,
+//!         pos_or_panic!(490.0),
+//!     )));
+//!
+//!     let pnl = portfolio.unrealized_pnl()?;
+//!     let greeks = portfolio.greeks()?;
+//!     let margin = portfolio.margin(&RegTMargin::default())?;
+//!     let ladder = portfolio.expiration_ladder()?;
+//!     println!("P&L {} margin {} rungs {}", pnl.total, margin.total, ladder.len());
+//!     if let (Some(reference), Some(weighted)) = (&greeks.reference, greeks.beta_weighted) {
+//!         println!("{reference}-weighted delta {}", weighted.delta);
+//!     }
+//!
+//!     let json = portfolio.to_json()?;
+//!     assert_eq!(Portfolio::from_json(&json)?, portfolio);
+//!     Ok(())
+//! }
+//! ```
+
+mod model;
+
+pub use model::{
+    ExpirationRung, Portfolio, PortfolioExposure, PortfolioMargin, PortfolioPnL, PortfolioStrategy,
+};
@@ -0,0 +1,978 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+use crate::error::PortfolioError;
+use crate::greeks::{
+    BetaWeightedGreeks, BetaWeighting, DollarGreeks, delta_with_engine, estimate_beta,
+    gamma_with_engine, rho_with_engine, theta_with_engine, vega_with_engine,
+};
+use crate::model::leg::{Leg, LegAble};
+use crate::model::option::Options;
+use crate::model::position::Position;
+use crate::pricing::{PricingEngine, price_option};
+use crate::risk::{MarginRequirement, RegTMargin};
+use crate::strategies::base::{Strategable, StrategyType};
+use crate::strategies::{PortfolioGreeks, StrategyRequest};
+use crate::utils::OhlcvCandle;
+use chrono::NaiveDate;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A strategy held in a [`Portfolio`].
+///
+/// The strategy is stored as its type and option positions, the same data as
+/// a [`StrategyRequest`], so strategies of any type can be held side by side,
+/// serialised and rebuilt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioStrategy {
+    /// Identifier of the strategy within the portfolio.
+    pub id: String,
+    /// Display name of the strategy.
+    pub name: String,
+    /// Type of the strategy.
+    pub strategy_type: StrategyType,
+    /// Option positions of the strategy.
+    pub positions: Vec<Position>,
+}
+
+impl PortfolioStrategy {
+    /// Captures `strategy` under the identifier `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::InvalidStrategy`] when the strategy has no
+    /// positions, and propagates errors reading its positions.
+    pub fn from_strategy<S: Strategable>(id: &str, strategy: &S) -> Result<Self, PortfolioError> {
+        let positions: Vec<Position> = strategy.get_positions()?.into_iter().cloned().collect();
+        if positions.is_empty() {
+            return Err(PortfolioError::invalid_strategy(&format!(
+                "strategy '{id}' has no positions"
+            )));
+        }
+        Ok(PortfolioStrategy {
+            id: id.to_string(),
+            name: strategy.get_title(),
+            strategy_type: strategy.type_name(),
+            positions,
+        })
+    }
+
+    /// Request that rebuilds the concrete strategy.
+    #[must_use]
+    pub fn to_request(&self) -> StrategyRequest {
+        StrategyRequest::new(self.strategy_type.clone(), self.positions.clone())
+    }
+
+    /// Symbol of the underlying of the strategy.
+    #[must_use]
+    pub fn symbol(&self) -> Option<&str> {
+        self.positions
+            .first()
+            .map(|position| position.option.underlying_symbol.as_str())
+    }
+}
+
+/// Unrealized P&L of a [`Portfolio`] at its current prices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioPnL {
+    /// P&L of every strategy, by identifier.
+    pub by_strategy: BTreeMap<String, Decimal>,
+    /// P&L of the standalone legs.
+    pub legs: Decimal,
+    /// Total P&L.
+    pub total: Decimal,
+}
+
+/// Greek exposures of a [`Portfolio`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioExposure {
+    /// Net Greeks of every underlying, in that underlying's units.
+    pub by_underlying: BTreeMap<String, PortfolioGreeks>,
+    /// Net delta, gamma and vega of every underlying in money terms.
+    pub dollar: BTreeMap<String, DollarGreeks>,
+    /// Symbol of the reference underlying, when the portfolio has one.
+    pub reference: Option<String>,
+    /// Delta and gamma beta-weighted to the reference underlying, when the
+    /// portfolio has one.
+    pub beta_weighted: Option<BetaWeightedGreeks>,
+}
+
+/// Margin of a [`Portfolio`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioMargin {
+    /// Reg-T requirement of every strategy, by identifier.
+    pub by_strategy: BTreeMap<String, MarginRequirement>,
+    /// Reg-T requirement of the standalone option legs, by underlying, with
+    /// the spot legs on the same underlying as cover.
+    pub option_legs: BTreeMap<String, MarginRequirement>,
+    /// Margin posted for futures and perpetual legs.
+    pub linear_margin: Decimal,
+    /// Total buying-power reduction plus linear margin.
+    pub total: Decimal,
+}
+
+/// Contracts expiring on one date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpirationRung {
+    /// Expiration date.
+    pub date: NaiveDate,
+    /// Long option and futures contracts expiring.
+    pub long_quantity: Positive,
+    /// Short option and futures contracts expiring.
+    pub short_quantity: Positive,
+    /// Underlyings with contracts expiring.
+    pub symbols: BTreeSet<String>,
+    /// Identifiers of the strategies with positions expiring.
+    pub strategies: BTreeSet<String>,
+}
+
+/// Aggregate of strategies and legs across underlyings.
+///
+/// A portfolio holds any number of strategies, keyed by identifier, and
+/// standalone legs (spot, futures, perpetuals and single options). Prices are
+/// looked up by underlying symbol in `underlying_prices`, falling back to the
+/// underlying price stored in the options on that symbol. Betas default to 1
+/// and are used to weight delta and gamma to `reference_symbol`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Portfolio {
+    /// Name of the portfolio.
+    pub name: String,
+    /// Held strategies.
+    pub strategies: Vec<PortfolioStrategy>,
+    /// Standalone legs.
+    pub legs: Vec<Leg>,
+    /// Current price of every underlying.
+    pub underlying_prices: BTreeMap<String, Positive>,
+    /// Beta of every underlying against the reference.
+    pub betas: BTreeMap<String, Decimal>,
+    /// Underlying that Greeks are beta-weighted to.
+    pub reference_symbol: Option<String>,
+}
+
+impl Portfolio {
+    /// Creates an empty portfolio.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Portfolio {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the price of an underlying.
+    #[must_use]
+    pub fn with_price(mut self, symbol: &str, price: Positive) -> Self {
+        self.set_price(symbol, price);
+        self
+    }
+
+    /// Sets the beta of an underlying against the reference.
+    #[must_use]
+    pub fn with_beta(mut self, symbol: &str, beta: Decimal) -> Self {
+        self.betas.insert(symbol.to_string(), beta);
+        self
+    }
+
+    /// Sets the reference underlying for beta-weighted Greeks.
+    #[must_use]
+    pub fn with_reference(mut self, symbol: &str) -> Self {
+        self.reference_symbol = Some(symbol.to_string());
+        self
+    }
+
+    /// Estimates the beta of an underlying against the reference from candle
+    /// histories and stores it, see [`estimate_beta`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::Greeks`] when the beta cannot be estimated.
+    pub fn estimate_beta(
+        &mut self,
+        symbol: &str,
+        asset: &[OhlcvCandle],
+        reference: &[OhlcvCandle],
+    ) -> Result<Decimal, PortfolioError> {
+        let beta = estimate_beta(asset, reference)?;
+        self.betas.insert(symbol.to_string(), beta);
+        Ok(beta)
+    }
+
+    /// Updates the price of an underlying.
+    pub fn set_price(&mut self, symbol: &str, price: Positive) {
+        self.underlying_prices.insert(symbol.to_string(), price);
+    }
+
+    /// Adds `strategy` under the identifier `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::DuplicateStrategy`] when `id` is taken, and
+    /// the errors of [`PortfolioStrategy::from_strategy`].
+    pub fn add_strategy<S: Strategable>(
+        &mut self,
+        id: &str,
+        strategy: &S,
+    ) -> Result<(), PortfolioError> {
+        if self.get_strategy(id).is_some() {
+            return Err(PortfolioError::DuplicateStrategy { id: id.to_string() });
+        }
+        self.strategies
+            .push(PortfolioStrategy::from_strategy(id, strategy)?);
+        Ok(())
+    }
+
+    /// Removes the strategy `id` and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::StrategyNotFound`] when no strategy has `id`.
+    pub fn remove_strategy(&mut self, id: &str) -> Result<PortfolioStrategy, PortfolioError> {
+        let index = self.strategy_index(id)?;
+        Ok(self.strategies.remove(index))
+    }
+
+    /// Rolls the strategy `id` into `replacement`, keeping its identifier,
+    /// and returns the strategy that was closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::StrategyNotFound`] when no strategy has `id`,
+    /// and the errors of [`PortfolioStrategy::from_strategy`].
+    pub fn roll_strategy<S: Strategable>(
+        &mut self,
+        id: &str,
+        replacement: &S,
+    ) -> Result<PortfolioStrategy, PortfolioError> {
+        let index = self.strategy_index(id)?;
+        let rolled = PortfolioStrategy::from_strategy(id, replacement)?;
+        let slot = self
+            .strategies
+            .get_mut(index)
+            .ok_or_else(|| PortfolioError::StrategyNotFound { id: id.to_string() })?;
+        Ok(std::mem::replace(slot, rolled))
+    }
+
+    /// The strategy with identifier `id`.
+    #[must_use]
+    pub fn get_strategy(&self, id: &str) -> Option<&PortfolioStrategy> {
+        self.strategies.iter().find(|strategy| strategy.id == id)
+    }
+
+    /// Adds a standalone leg.
+    pub fn add_leg(&mut self, leg: Leg) {
+        self.legs.push(leg);
+    }
+
+    /// Removes the standalone leg at `index` and returns it.
+    pub fn remove_leg(&mut self, index: usize) -> Option<Leg> {
+        (index < self.legs.len()).then(|| self.legs.remove(index))
+    }
+
+    /// Symbols of every underlying held.
+    #[must_use]
+    pub fn symbols(&self) -> BTreeSet<String> {
+        self.strategies
+            .iter()
+            .flat_map(|strategy| strategy.positions.iter())
+            .map(|position| position.option.underlying_symbol.clone())
+            .chain(self.legs.iter().map(|leg| leg.get_symbol().to_string()))
+            .collect()
+    }
+
+    /// Current price of `symbol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::MissingPrice`] when the price is not set and
+    /// no option on `symbol` is held.
+    pub fn price(&self, symbol: &str) -> Result<Positive, PortfolioError> {
+        if let Some(price) = self.underlying_prices.get(symbol) {
+            return Ok(*price);
+        }
+        self.strategies
+            .iter()
+            .flat_map(|strategy| strategy.positions.iter())
+            .chain(self.legs.iter().filter_map(Leg::as_option))
+            .find(|position| position.option.underlying_symbol == symbol)
+            .map(|position| position.option.underlying_price)
+            .ok_or_else(|| PortfolioError::MissingPrice {
+                symbol: symbol.to_string(),
+            })
+    }
+
+    /// Unrealized P&L of every strategy and leg at current prices, with
+    /// options marked at their implied volatility by the default engine for
+    /// their option type (see [`PricingEngine::for_option_type`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::MissingPrice`] when an underlying has no
+    /// price, and propagates pricing and position errors.
+    pub fn unrealized_pnl(&self) -> Result<PortfolioPnL, PortfolioError> {
+        self.pnl_under(None)
+    }
+
+    /// Unrealized P&L as [`Portfolio::unrealized_pnl`], with every option
+    /// marked by `engine`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Portfolio::unrealized_pnl`], and
+    /// [`PricingError::UnsupportedOptionType`](crate::error::PricingError::UnsupportedOptionType)
+    /// when `engine` cannot price a held option.
+    pub fn unrealized_pnl_with_engine(
+        &self,
+        engine: &PricingEngine,
+    ) -> Result<PortfolioPnL, PortfolioError> {
+        self.pnl_under(Some(engine))
+    }
+
+    fn pnl_under(&self, engine: Option<&PricingEngine>) -> Result<PortfolioPnL, PortfolioError> {
+        let by_strategy = self
+            .strategies
+            .iter()
+            .map(|strategy| {
+                let pnl = strategy
+                    .positions
+                    .iter()
+                    .map(|position| self.position_pnl(position, engine))
+                    .sum::<Result<Decimal, PortfolioError>>()?;
+                Ok((strategy.id.clone(), pnl))
+            })
+            .collect::<Result<BTreeMap<String, Decimal>, PortfolioError>>()?;
+        let legs = self
+            .legs
+            .iter()
+            .map(|leg| match leg.as_option() {
+                Some(position) => self.position_pnl(position, engine),
+                None => Ok(leg.pnl_at_price(self.price(leg.get_symbol())?)),
+            })
+            .sum::<Result<Decimal, PortfolioError>>()?;
+        let total = by_strategy.values().sum::<Decimal>() + legs;
+        Ok(PortfolioPnL {
+            by_strategy,
+            legs,
+            total,
+        })
+    }
+
+    /// Total unrealized P&L under each joint price scenario, for instance
+    /// the correlated moves of
+    /// [`crate::simulation::MultiAssetSimulator::scenarios`].
+    ///
+    /// A scenario overrides the prices of the underlyings it lists; the
+    /// others keep their current price. Options are repriced instantly, with
+    /// no time decay.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Portfolio::unrealized_pnl`].
+    pub fn scenario_pnl(
+        &self,
+        scenarios: &[BTreeMap<String, Positive>],
+    ) -> Result<Vec<Decimal>, PortfolioError> {
+        self.scenario_pnl_under(scenarios, None)
+    }
+
+    /// Scenario P&L as [`Portfolio::scenario_pnl`], with every option
+    /// marked by `engine`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Portfolio::unrealized_pnl_with_engine`].
+    pub fn scenario_pnl_with_engine(
+        &self,
+        scenarios: &[BTreeMap<String, Positive>],
+        engine: &PricingEngine,
+    ) -> Result<Vec<Decimal>, PortfolioError> {
+        self.scenario_pnl_under(scenarios, Some(engine))
+    }
+
+    fn scenario_pnl_under(
+        &self,
+        scenarios: &[BTreeMap<String, Positive>],
+        engine: Option<&PricingEngine>,
+    ) -> Result<Vec<Decimal>, PortfolioError> {
+        let mut shocked = self.clone();
+        scenarios
+            .iter()
+            .map(|scenario| {
+                shocked
+                    .underlying_prices
+                    .clone_from(&self.underlying_prices);
+                shocked.underlying_prices.extend(
+                    scenario
+                        .iter()
+                        .map(|(symbol, price)| (symbol.clone(), *price)),
+                );
+                Ok(shocked.pnl_under(engine)?.total)
+            })
+            .collect()
+    }
+
+    /// Net Greeks of every underlying at current prices, and delta and gamma
+    /// beta-weighted to the reference underlying. Option Greeks come from the
+    /// default engine for each option type, so they are the sensitivities of
+    /// the marks of [`Portfolio::unrealized_pnl`].
+    ///
+    /// An underlying with beta `β` and price `S` contributes
+    /// `Δ·β·S/S_ref` to the weighted delta and `Γ·(β·S/S_ref)²` to the
+    /// weighted gamma.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::MissingPrice`] when an underlying or the
+    /// reference has no price, and propagates Greeks errors.
+    pub fn greeks(&self) -> Result<PortfolioExposure, PortfolioError> {
+        self.exposure_under(None)
+    }
+
+    /// Exposure as [`Portfolio::greeks`], with option Greeks computed under
+    /// `engine`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Portfolio::greeks`], including the errors of
+    /// [`delta_with_engine`] when `engine` cannot price a held option.
+    pub fn greeks_with_engine(
+        &self,
+        engine: &PricingEngine,
+    ) -> Result<PortfolioExposure, PortfolioError> {
+        self.exposure_under(Some(engine))
+    }
+
+    fn exposure_under(
+        &self,
+        engine: Option<&PricingEngine>,
+    ) -> Result<PortfolioExposure, PortfolioError> {
+        let mut by_underlying: BTreeMap<String, PortfolioGreeks> = BTreeMap::new();
+        for position in self.option_positions() {
+            let option = self.marked(&position.option)?;
+            let default = PricingEngine::for_option_type(&option.option_type);
+            let engine = engine.unwrap_or(&default);
+            let greeks = by_underlying
+                .entry(option.underlying_symbol.clone())
+                .or_default();
+            greeks.delta += delta_with_engine(&option, engine)?;
+            greeks.gamma += signed(&option, gamma_with_engine(&option, engine)?);
+            greeks.theta += signed(&option, theta_with_engine(&option, engine)?);
+            greeks.vega += signed(&option, vega_with_engine(&option, engine)?);
+            greeks.rho += signed(&option, rho_with_engine(&option, engine)?);
+        }
+        for leg in self.legs.iter().filter(|leg| leg.is_linear()) {
+            by_underlying
+                .entry(leg.get_symbol().to_string())
+                .or_default()
+                .delta += leg.delta()?;
+        }
+
+        let dollar = by_underlying
+            .iter()
+            .map(|(symbol, greeks)| {
+                Ok((
+                    symbol.clone(),
+                    DollarGreeks::from_greeks(
+                        self.price(symbol)?,
+                        greeks.delta,
+                        greeks.gamma,
+                        greeks.vega,
+                    ),
+                ))
+            })
+            .collect::<Result<BTreeMap<String, DollarGreeks>, PortfolioError>>()?;
+
+        let beta_weighted = match &self.reference_symbol {
+            Some(reference) => {
+                let reference_price = self.price(reference)?;
+                let mut weighted = BetaWeightedGreeks::default();
+                for (symbol, greeks) in &dollar {
+                    let beta = self.betas.get(symbol).copied().unwrap_or(Decimal::ONE);
+                    weighted += greeks.beta_weighted(&BetaWeighting::new(beta, reference_price));
+                }
+                Some(weighted)
+            }
+            None => None,
+        };
+        Ok(PortfolioExposure {
+            by_underlying,
+            dollar,
+            reference: self.reference_symbol.clone(),
+            beta_weighted,
+        })
+    }
+
+    /// Margin of every strategy and leg at current prices.
+    ///
+    /// Strategies and standalone options are margined with `model`; futures
+    /// and perpetuals contribute the margin they post.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::MissingPrice`] when an underlying has no
+    /// price, and propagates margin errors.
+    pub fn margin(&self, model: &RegTMargin) -> Result<PortfolioMargin, PortfolioError> {
+        let by_strategy = self
+            .strategies
+            .iter()
+            .map(|strategy| {
+                let positions = self.marked_positions(strategy.positions.iter())?;
+                let requirement =
+                    model.strategy_type_margin(&strategy.strategy_type, &positions)?;
+                Ok((strategy.id.clone(), requirement))
+            })
+            .collect::<Result<BTreeMap<String, MarginRequirement>, PortfolioError>>()?;
+
+        let mut option_legs = BTreeMap::new();
+        let option_symbols: BTreeSet<&str> = self
+            .legs
+            .iter()
+            .filter_map(Leg::as_option)
+            .map(|position| position.option.underlying_symbol.as_str())
+            .collect();
+        for symbol in option_symbols {
+            let positions = self.marked_positions(
+                self.legs
+                    .iter()
+                    .filter_map(Leg::as_option)
+                    .filter(|position| position.option.underlying_symbol == symbol),
+            )?;
+            let stock: Vec<_> = self
+                .legs
+                .iter()
+                .filter_map(Leg::as_spot)
+                .filter(|spot| spot.symbol == symbol)
+                .cloned()
+                .collect();
+            option_legs.insert(
+                symbol.to_string(),
+                model.positions_margin(&positions, &stock)?,
+            );
+        }
+
+        let linear_margin: Decimal = self
+            .legs
+            .iter()
+            .map(|leg| match leg {
+                Leg::Future(future) => future.total_margin_required().to_dec(),
+                Leg::Perpetual(perpetual) => perpetual.margin.to_dec(),
+                Leg::Option(_) | Leg::Spot(_) => Decimal::ZERO,
+            })
+            .sum();
+        let total = by_strategy
+            .values()
+            .chain(option_legs.values())
+            .map(|requirement| requirement.buying_power_reduction)
+            .sum::<Decimal>()
+            + linear_margin;
+        Ok(PortfolioMargin {
+            by_strategy,
+            option_legs,
+            linear_margin,
+            total,
+        })
+    }
+
+    /// Option and futures contracts grouped by expiration date, earliest
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors resolving an expiration date.
+    pub fn expiration_ladder(&self) -> Result<Vec<ExpirationRung>, PortfolioError> {
+        let mut rungs: BTreeMap<NaiveDate, ExpirationRung> = BTreeMap::new();
+        let mut add = |date: NaiveDate,
+                       long: bool,
+                       quantity: Positive,
+                       symbol: &str,
+                       strategy: Option<&str>| {
+            let rung = rungs.entry(date).or_insert_with(|| ExpirationRung {
+                date,
+                long_quantity: Positive::ZERO,
+                short_quantity: Positive::ZERO,
+                symbols: BTreeSet::new(),
+                strategies: BTreeSet::new(),
+            });
+            if long {
+                rung.long_quantity += quantity;
+            } else {
+                rung.short_quantity += quantity;
+            }
+            rung.symbols.insert(symbol.to_string());
+            if let Some(id) = strategy {
+                rung.strategies.insert(id.to_string());
+            }
+        };
+
+        for strategy in &self.strategies {
+            for position in &strategy.positions {
+                let option = &position.option;
+                add(
+                    option.expiration_date.get_date()?.date_naive(),
+                    option.is_long(),
+                    option.quantity,
+                    &option.underlying_symbol,
+                    Some(&strategy.id),
+                );
+            }
+        }
+        for leg in &self.legs {
+            match leg {
+                Leg::Option(position) => {
+                    let option = &position.option;
+                    add(
+                        option.expiration_date.get_date()?.date_naive(),
+                        option.is_long(),
+                        option.quantity,
+                        &option.underlying_symbol,
+                        None,
+                    );
+                }
+                Leg::Future(future) => add(
+                    future.expiration_date.get_date()?.date_naive(),
+                    future.side == crate::model::types::Side::Long,
+                    future.quantity,
+                    &future.symbol,
+                    None,
+                ),
+                Leg::Spot(_) | Leg::Perpetual(_) => {}
+            }
+        }
+        Ok(rungs.into_values().collect())
+    }
+
+    /// Serialises the portfolio to pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::Json`] when serialisation fails.
+    pub fn to_json(&self) -> Result<String, PortfolioError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parses a portfolio from JSON produced by [`Portfolio::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::Json`] when the JSON is malformed.
+    pub fn from_json(json: &str) -> Result<Self, PortfolioError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    fn strategy_index(&self, id: &str) -> Result<usize, PortfolioError> {
+        self.strategies
+            .iter()
+            .position(|strategy| strategy.id == id)
+            .ok_or_else(|| PortfolioError::StrategyNotFound { id: id.to_string() })
+    }
+
+    fn option_positions(&self) -> impl Iterator<Item = &Position> {
+        self.strategies
+            .iter()
+            .flat_map(|strategy| strategy.positions.iter())
+            .chain(self.legs.iter().filter_map(Leg::as_option))
+    }
+
+    /// `option` with the underlying at its current price.
+    fn marked(&self, option: &Options) -> Result<Options, PortfolioError> {
+        let mut marked = option.clone();
+        marked.underlying_price = self.price(&option.underlying_symbol)?;
+        Ok(marked)
+    }
+
+    fn marked_positions<'a>(
+        &self,
+        positions: impl Iterator<Item = &'a Position>,
+    ) -> Result<Vec<Position>, PortfolioError> {
+        positions
+            .map(|position| {
+                let mut marked = position.clone();
+                marked.option = self.marked(&position.option)?;
+                Ok(marked)
+            })
+            .collect()
+    }
+
+    /// Unrealized P&L of `position` marked by `engine`, or by the default
+    /// engine for its option type.
+    fn position_pnl(
+        &self,
+        position: &Position,
+        engine: Option<&PricingEngine>,
+    ) -> Result<Decimal, PortfolioError> {
+        let option = self.marked(&position.option)?;
+        let default = PricingEngine::for_option_type(&option.option_type);
+        let mark = price_option(&option, engine.unwrap_or(&default))?;
+        Ok(position.unrealized_pnl(mark)?)
+    }
+}
+
+/// Option Greeks other than delta are reported unsigned; short positions
+/// carry the opposite sign.
+fn signed(option: &Options, value: Decimal) -> Decimal {
+    if option.is_short() { -value } else { value }
+}
+
+#[cfg(test)]
+mod tests_portfolio {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::leg::{FuturePosition, SpotPosition};
+    use crate::model::types::OptionType;
+    use crate::strategies::base::Positionable;
+    use crate::strategies::{ShortPut, ShortStrangle};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn short_put(symbol: &str, strike: f64, price: f64) -> ShortPut {
+        ShortPut::new(
+            symbol.to_string(),
+            pos_or_panic!(strike),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            pos_or_panic!(price),
+            dec!(0.05),
+            Positive::ZERO,
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    fn short_strangle() -> ShortStrangle {
+        ShortStrangle::new(
+            "SPX".to_string(),
+            pos_or_panic!(5000.0),
+            pos_or_panic!(5200.0),
+            pos_or_panic!(4800.0),
+            ExpirationDate::Days(pos_or_panic!(45.0)),
+            pos_or_panic!(0.18),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(40.0),
+            pos_or_panic!(45.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    fn portfolio() -> Portfolio {
+        let mut portfolio = Portfolio::new("book")
+            .with_price("SPX", pos_or_panic!(5000.0))
+            .with_price("AAPL", pos_or_panic!(100.0))
+            .with_beta("AAPL", dec!(1.2))
+            .with_reference("SPX");
+        portfolio
+            .add_strategy("spx-strangle", &short_strangle())
+            .unwrap();
+        portfolio
+            .add_strategy("aapl-put", &short_put("AAPL", 95.0, 100.0))
+            .unwrap();
+        portfolio.add_leg(Leg::spot(SpotPosition::long(
+            "AAPL".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(90.0),
+        )));
+        portfolio
+    }
+
+    #[test]
+    fn test_add_remove_and_roll() {
+        let mut portfolio = portfolio();
+        assert_eq!(portfolio.strategies.len(), 2);
+        assert!(matches!(
+            portfolio.add_strategy("aapl-put", &short_put("AAPL", 90.0, 100.0)),
+            Err(PortfolioError::DuplicateStrategy { .. })
+        ));
+
+        let closed = portfolio
+            .roll_strategy("aapl-put", &short_put("AAPL", 90.0, 100.0))
+            .unwrap();
+        assert_eq!(closed.positions[0].option.strike_price, pos_or_panic!(95.0));
+        let rolled = portfolio.get_strategy("aapl-put").unwrap();
+        assert_eq!(rolled.positions[0].option.strike_price, pos_or_panic!(90.0));
+        assert_eq!(rolled.strategy_type, StrategyType::ShortPut);
+        let strangle = portfolio.get_strategy("spx-strangle").unwrap();
+        assert!(strangle.to_request().get_strategy().is_ok());
+
+        assert!(portfolio.remove_strategy("aapl-put").is_ok());
+        assert!(matches!(
+            portfolio.remove_strategy("aapl-put"),
+            Err(PortfolioError::StrategyNotFound { .. })
+        ));
+        assert!(portfolio.remove_leg(0).is_some());
+        assert!(portfolio.remove_leg(0).is_none());
+        assert_eq!(portfolio.symbols(), BTreeSet::from(["SPX".to_string()]));
+    }
+
+    #[test]
+    fn test_unrealized_pnl() {
+        let mut portfolio = portfolio();
+        let pnl = portfolio.unrealized_pnl().unwrap();
+        // 100 shares bought at 90 and marked at 100.
+        assert_eq!(pnl.legs, dec!(1000));
+        assert_eq!(
+            pnl.total,
+            pnl.by_strategy.values().sum::<Decimal>() + pnl.legs
+        );
+
+        portfolio.set_price("AAPL", pos_or_panic!(80.0));
+        let stressed = portfolio.unrealized_pnl().unwrap();
+        assert_eq!(stressed.legs, dec!(-1000));
+        assert!(stressed.by_strategy["aapl-put"] < pnl.by_strategy["aapl-put"]);
+    }
+
+    #[test]
+    fn test_scenario_pnl_applies_joint_moves() {
+        let portfolio = portfolio();
+        let base = portfolio.unrealized_pnl().unwrap().total;
+        let scenarios = vec![
+            BTreeMap::new(),
+            BTreeMap::from([("AAPL".to_string(), pos_or_panic!(80.0))]),
+            BTreeMap::from([
+                ("AAPL".to_string(), pos_or_panic!(80.0)),
+                ("SPX".to_string(), pos_or_panic!(4500.0)),
+            ]),
+        ];
+        let pnl = portfolio.scenario_pnl(&scenarios).unwrap();
+        assert_eq!(pnl[0], base);
+        assert!(pnl[1] < base);
+        assert!(pnl[2] < pnl[1]);
+        // Scenarios do not leak into the portfolio or into each other.
+        assert_eq!(portfolio.price("AAPL").unwrap(), Positive::HUNDRED);
+        assert_eq!(portfolio.scenario_pnl(&scenarios[..1]).unwrap()[0], base);
+    }
+
+    #[test]
+    fn test_american_positions_are_marked_by_engine() {
+        let mut position = short_put("AAPL", 95.0, 100.0).get_positions().unwrap()[0].clone();
+        position.option.option_type = OptionType::American;
+        let mut portfolio = Portfolio::new("book").with_price("AAPL", Positive::HUNDRED);
+        portfolio.add_leg(Leg::option(position.clone()));
+        portfolio.add_leg(Leg::spot(SpotPosition::long(
+            "AAPL".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(90.0),
+        )));
+
+        let pnl = portfolio.unrealized_pnl().unwrap();
+        let baw = price_option(&position.option, &PricingEngine::BaroneAdesiWhaley).unwrap();
+        assert_eq!(pnl.legs, position.unrealized_pnl(baw).unwrap() + dec!(1000));
+
+        let lattice = PricingEngine::Binomial {
+            steps: crate::constants::DEFAULT_BINOMIAL_STEPS,
+        };
+        let lattice_pnl = portfolio.unrealized_pnl_with_engine(&lattice).unwrap();
+        assert!((lattice_pnl.legs - pnl.legs).abs() < dec!(0.1));
+        assert!(
+            portfolio
+                .unrealized_pnl_with_engine(&PricingEngine::ClosedFormExotic)
+                .is_err()
+        );
+        assert_eq!(
+            portfolio
+                .scenario_pnl_with_engine(&[BTreeMap::new()], &lattice)
+                .unwrap()[0],
+            lattice_pnl.total
+        );
+
+        let exposure = portfolio.greeks().unwrap();
+        let lattice_exposure = portfolio.greeks_with_engine(&lattice).unwrap();
+        let (aapl, lattice_aapl) = (
+            &exposure.by_underlying["AAPL"],
+            &lattice_exposure.by_underlying["AAPL"],
+        );
+        assert!(aapl.delta > dec!(100));
+        assert!(aapl.gamma < Decimal::ZERO);
+        assert!((aapl.delta - lattice_aapl.delta).abs() < dec!(0.05));
+    }
+
+    #[test]
+    fn test_greeks_beta_weighted() {
+        let portfolio = portfolio();
+        let exposure = portfolio.greeks().unwrap();
+        let aapl = &exposure.by_underlying["AAPL"];
+        let spx = &exposure.by_underlying["SPX"];
+        // Short put: positive delta on top of 100 shares, short gamma.
+        assert!(aapl.delta > dec!(100));
+        assert!(aapl.gamma < Decimal::ZERO);
+        assert!(spx.theta > Decimal::ZERO);
+        assert!(spx.vega < Decimal::ZERO);
+
+        assert_eq!(exposure.dollar["SPX"].dollar_delta, spx.delta * dec!(5000));
+        assert_eq!(exposure.reference.as_deref(), Some("SPX"));
+        let weighted = exposure.beta_weighted.unwrap();
+        let expected = spx.delta + aapl.delta * dec!(1.2) * dec!(100) / dec!(5000);
+        assert!((weighted.delta - expected).abs() < dec!(0.0000001));
+
+        let unweighted = Portfolio {
+            reference_symbol: None,
+            ..portfolio
+        };
+        assert!(unweighted.greeks().unwrap().beta_weighted.is_none());
+    }
+
+    #[test]
+    fn test_margin_and_missing_price() {
+        let mut portfolio = portfolio();
+        portfolio.add_leg(Leg::future(FuturePosition::long(
+            "ES".to_string(),
+            Positive::ONE,
+            pos_or_panic!(5000.0),
+            ExpirationDate::Days(pos_or_panic!(60.0)),
+            pos_or_panic!(50.0),
+            pos_or_panic!(12000.0),
+        )));
+        assert!(matches!(
+            portfolio.unrealized_pnl(),
+            Err(PortfolioError::MissingPrice { .. })
+        ));
+        portfolio.set_price("ES", pos_or_panic!(5010.0));
+
+        let margin = portfolio.margin(&RegTMargin::default()).unwrap();
+        assert_eq!(margin.by_strategy.len(), 2);
+        assert!(margin.option_legs.is_empty());
+        assert_eq!(margin.linear_margin, dec!(12000));
+        let requirements: Decimal = margin
+            .by_strategy
+            .values()
+            .map(|requirement| requirement.buying_power_reduction)
+            .sum();
+        assert_eq!(margin.total, requirements + dec!(12000));
+    }
+
+    #[test]
+    fn test_expiration_ladder() {
+        let ladder = portfolio().expiration_ladder().unwrap();
+        assert_eq!(ladder.len(), 2);
+        assert!(ladder[0].date < ladder[1].date);
+        assert_eq!(ladder[0].short_quantity, Positive::ONE);
+        assert_eq!(ladder[1].short_quantity, Positive::TWO);
+        assert_eq!(
+            ladder[1].strategies,
+            BTreeSet::from(["spx-strangle".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let portfolio = portfolio();
+        let json = portfolio.to_json().unwrap();
+        let restored = Portfolio::from_json(&json).unwrap();
+        assert_eq!(restored, portfolio);
+        assert!(matches!(
+            Portfolio::from_json("{"),
+            Err(PortfolioError::Json { .. })
+        ));
+    }
+}
@@ -36,9 +36,13 @@ pub use crate::strategies::{
     // Specific strategy implementations (commonly used)
     bear_call_spread::BearCallSpread,
     bear_put_spread::BearPutSpread,
+    box_spread::BoxSpread,
     bull_call_spread::BullCallSpread,
     bull_put_spread::BullPutSpread,
+    call_back_spread::CallBackSpread,
+    call_broken_wing_butterfly::CallBrokenWingButterfly,
     call_butterfly::CallButterfly,
+    call_condor::CallCondor,
     collar::Collar,
     covered_call::CoveredCall,
     custom::CustomStrategy,
@@ -46,21 +50,38 @@ pub use crate::strategies::{
         AdjustmentAction, AdjustmentConfig, AdjustmentError, AdjustmentOptimizer, AdjustmentPlan,
         AdjustmentTarget, DeltaNeutrality, PortfolioGreeks,
     },
+    diagonal_spread::DiagonalSpread,
+    double_calendar_spread::DoubleCalendarSpread,
     iron_butterfly::IronButterfly,
     iron_condor::IronCondor,
+    jade_lizard::JadeLizard,
     long_butterfly_spread::LongButterflySpread,
+    long_calendar_spread::LongCalendarSpread,
     long_call::LongCall,
     long_put::LongPut,
     long_straddle::LongStraddle,
     long_strangle::LongStrangle,
+    multi_objective::{
+        MultiObjectiveOptimizer, Objective, OptimizationConstraints, RankedCandidate,
+    },
     poor_mans_covered_call::PoorMansCoveredCall,
     probabilities::ProbabilityAnalysis,
     protective_put::ProtectivePut,
+    put_back_spread::PutBackSpread,
+    put_broken_wing_butterfly::PutBrokenWingButterfly,
+    put_condor::PutCondor,
+    ratio_call_spread::RatioCallSpread,
+    ratio_put_spread::RatioPutSpread,
+    reverse_jade_lizard::ReverseJadeLizard,
+    risk_reversal::RiskReversal,
     short_butterfly_spread::ShortButterflySpread,
+    short_calendar_spread::ShortCalendarSpread,
     short_call::ShortCall,
     short_put::ShortPut,
     short_straddle::ShortStraddle,
     short_strangle::ShortStrangle,
+    strap::Strap,
+    strip::Strip,
     utils::FindOptimalSide,
 };
 
@@ -124,8 +145,8 @@ pub use std::path::Path;
 
 // Simulation types and functions
 pub use crate::simulation::{
-    ExitPolicy, Simulate, SimulationStats, WalkParams, WalkPath, WalkType, WalkTypeAble,
-    WalkTypeAbleClone, check_exit_policy, expanding_window_vols, generator_positive,
+    BootstrapMethod, ExitPolicy, Regime, Simulate, SimulationStats, WalkParams, WalkPath, WalkType,
+    WalkTypeAble, WalkTypeAbleClone, check_exit_policy, expanding_window_vols, generator_positive,
     randomwalk::RandomWalk,
     simulator::Simulator,
     steps::{Step, Xstep, Ystep},
@@ -79,8 +79,7 @@ fn geometric_asian_price(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -142,8 +141,7 @@ fn arithmetic_asian_price(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
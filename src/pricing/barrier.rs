@@ -269,6 +269,9 @@ mod tests {
             option_style: style,
             dividend_yield: pos_or_panic!(0.04),
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -90,8 +90,7 @@ fn cash_or_nothing_price(option: &Options, payout: Decimal) -> Result<Decimal, P
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -152,8 +151,7 @@ fn asset_or_nothing_price(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -6,8 +6,11 @@
 
 use crate::error::PricingError;
 use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::pricing::dividends::DividendSchedule;
 use crate::pricing::payoff::{Payoff, PayoffInfo};
 use crate::pricing::utils::*;
+use crate::pricing::yield_curve::YieldCurve;
+use crate::utils::DayCountBasis;
 use crate::{d2f, f2d};
 use positive::Positive;
 use rust_decimal::{Decimal, MathematicalOps};
@@ -42,6 +45,11 @@ pub struct BinomialPricingParams<'a> {
     /// The risk-free interest rate used in the pricing model.
     pub int_rate: Decimal,
 
+    /// Continuous dividend yield of the underlying (the foreign rate for a
+    /// currency). The lattice drifts at `int_rate - dividend_yield` and
+    /// discounts at `int_rate`.
+    pub dividend_yield: Positive,
+
     /// The strike price of the option, represented as a positive value.
     pub strike: Positive,
 
@@ -82,6 +90,7 @@ pub struct BinomialPricingParams<'a> {
 ///     - `asset`: Current price of the underlying asset.
 ///     - `volatility`: Annualized volatility of the underlying asset.
 ///     - `int_rate`: Annualized risk-free interest rate.
+///     - `dividend_yield`: Continuous dividend yield of the underlying.
 ///     - `strike`: Strike price of the option.
 ///     - `expiry`: Time to expiration in years.
 ///     - `no_steps`: Number of steps in the binomial tree.
@@ -119,6 +128,65 @@ pub struct BinomialPricingParams<'a> {
     side = ?*params.side,
 ))]
 pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingError> {
+    backward_induction(params, |_| Decimal::ZERO)
+}
+
+/// Prices an option on an underlying paying discrete cash dividends with a
+/// dividend-adjusted binomial tree.
+///
+/// The tree recombines on the escrowed spot `S* = S - PV(dividends)`, where
+/// the present value covers the dividends going ex before `params.expiry`.
+/// At every exercise decision the intrinsic value is taken on the cum-dividend
+/// spot, i.e. the tree node plus the value of the dividends still to come, so
+/// early exercise of calls just before an ex-dividend date is captured.
+///
+/// # Arguments
+///
+/// * `params` - Pricing parameters, with `asset` the cum-dividend spot and
+///   `int_rate` the zero rate to expiration.
+/// * `dividends` - Cash dividends of the underlying.
+/// * `day_count` - Basis measuring the ex-dividend dates from the valuation
+///   date, which should be the one `params.expiry` was measured with.
+/// * `curve` - Curve used to discount the dividends.
+///
+/// # Errors
+///
+/// Returns [`PricingError::ExpirationDate`] when an ex-dividend date cannot
+/// be resolved, [`PricingError::MethodError`] when the dividends are worth
+/// more than the spot, and otherwise the errors of [`price_binomial`].
+pub fn price_binomial_with_dividends(
+    params: BinomialPricingParams,
+    dividends: &DividendSchedule,
+    day_count: &DayCountBasis,
+    curve: &YieldCurve,
+) -> Result<Decimal, PricingError> {
+    let expiry = params.expiry;
+    let dividends = dividends.times(day_count)?;
+    let escrowed = params.asset.to_dec() - dividends.present_value(Positive::ZERO, expiry, curve);
+    if escrowed <= Decimal::ZERO {
+        return Err(PricingError::method_error(
+            "binomial",
+            "present value of dividends exceeds the underlying price",
+        ));
+    }
+    let params = BinomialPricingParams {
+        asset: Positive::new_decimal(escrowed)?,
+        ..params
+    };
+    backward_induction(params, |time| {
+        Positive::new_decimal(time)
+            .map(|from| dividends.present_value(from, expiry, curve))
+            .unwrap_or(Decimal::ZERO)
+    })
+}
+
+/// Backward induction on the tree of `params.asset`, where `income(t)` is the
+/// value at time `t` of the cash the holder of the underlying still receives
+/// before expiry and is added to the node spot when exercising.
+fn backward_induction(
+    params: BinomialPricingParams,
+    income: impl Fn(Decimal) -> Decimal,
+) -> Result<Decimal, PricingError> {
     let mut info = PayoffInfo {
         spot: params.asset,
         strike: params.strike,
@@ -141,7 +209,7 @@ pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingE
     let dt = (params.expiry / Positive::new(no_steps_raw as f64)?).to_dec();
     let u = calculate_up_factor(params.volatility, dt)?;
     let d = calculate_down_factor(params.volatility, dt)?;
-    let p = calculate_probability(params.int_rate, dt, d, u)?;
+    let p = calculate_probability(params.int_rate - params.dividend_yield.to_dec(), dt, d, u)?;
     let discount_factor = calculate_discount_factor(params.int_rate, dt)?;
 
     let mut prices: Vec<Decimal> = (0..=no_steps_raw)
@@ -153,7 +221,8 @@ pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingE
             let option_value = option_node_value(p, prices[i + 1], prices[i], discount_factor)?;
             match params.option_type {
                 OptionType::American => {
-                    let spot = params.asset * u.powi(i as i64) * d.powi((step - i) as i64);
+                    let spot = params.asset * u.powi(i as i64) * d.powi((step - i) as i64)
+                        + income(dt * Decimal::from(step as u32));
                     info.spot = spot;
                     let intrinsic_value = f2d!(params.option_type.payoff(&info));
                     prices[i] = option_value.max(intrinsic_value);
@@ -166,7 +235,8 @@ pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingE
                         .iter()
                         .any(|t| (time_at_step - t.to_dec()).abs() < dt / Decimal::TWO);
                     if is_exercise_date {
-                        let spot = params.asset * u.powi(i as i64) * d.powi((step - i) as i64);
+                        let spot = params.asset * u.powi(i as i64) * d.powi((step - i) as i64)
+                            + income(time_at_step);
                         info.spot = spot;
                         let intrinsic_value = f2d!(params.option_type.payoff(&info));
                         prices[i] = option_value.max(intrinsic_value);
@@ -228,6 +298,7 @@ pub fn price_binomial(params: BinomialPricingParams) -> Result<Decimal, PricingE
 ///             asset: Positive::HUNDRED,
 ///             volatility: pos_or_panic!(0.2),
 ///             int_rate: dec!(0.05),
+///             dividend_yield: Positive::ZERO,
 ///             strike: Positive::HUNDRED,
 ///             expiry: Positive::ONE,
 ///             no_steps: nz!(1000),
@@ -263,7 +334,12 @@ pub fn generate_binomial_tree(params: &BinomialPricingParams) -> BinomialTreeRes
     let dt = (params.expiry / f2d!(no_steps_raw as f64)).to_dec();
     let up_factor = calculate_up_factor(params.volatility, dt)?;
     let down_factor = calculate_down_factor(params.volatility, dt)?;
-    let probability = calculate_probability(params.int_rate, dt, down_factor, up_factor)?;
+    let probability = calculate_probability(
+        params.int_rate - params.dividend_yield.to_dec(),
+        dt,
+        down_factor,
+        up_factor,
+    )?;
     let discount_factor = calculate_discount_factor(params.int_rate, dt)?;
 
     let mut asset_tree = vec![vec![Decimal::ZERO; no_steps_raw + 1]; no_steps_raw + 1];
@@ -335,8 +411,11 @@ pub fn generate_binomial_tree(params: &BinomialPricingParams) -> BinomialTreeRes
 #[cfg(test)]
 mod tests_price_binomial {
     use super::*;
+    use crate::Options;
     use crate::assert_decimal_eq;
     use crate::model::types::OptionType;
+    use crate::model::utils::create_sample_option_with_days;
+    use crate::pricing::black_scholes;
     use rust_decimal_macros::dec;
 
     const EPSILON: Decimal = dec!(1e-6);
@@ -347,6 +426,7 @@ mod tests_price_binomial {
             asset: Positive::HUNDRED,
             strike: Positive::HUNDRED,
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             volatility: pos_or_panic!(0.2),
             expiry: Positive::ONE,
             no_steps: crate::nz!(3),
@@ -365,6 +445,7 @@ mod tests_price_binomial {
             asset: Positive::HUNDRED,
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: Positive::HUNDRED,
             expiry: Positive::ONE,
             no_steps: crate::nz!(1000),
@@ -383,6 +464,7 @@ mod tests_price_binomial {
             asset: pos_or_panic!(50.0),
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: pos_or_panic!(52.0),
             expiry: Positive::ONE,
             no_steps: crate::nz!(1),
@@ -401,6 +483,7 @@ mod tests_price_binomial {
             asset: Positive::HUNDRED,
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: Positive::HUNDRED,
             expiry: Positive::ONE,
             no_steps: crate::nz!(1000),
@@ -429,6 +512,7 @@ mod tests_price_binomial {
             asset,
             volatility: Positive::ZERO,
             int_rate,
+            dividend_yield: Positive::ZERO,
             strike,
             expiry,
             no_steps: crate::nz!(1000),
@@ -451,6 +535,7 @@ mod tests_price_binomial {
             asset: pos_or_panic!(150.0),
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: Positive::HUNDRED,
             expiry: Positive::ONE,
             no_steps: crate::nz!(1000),
@@ -469,6 +554,7 @@ mod tests_price_binomial {
             asset: pos_or_panic!(50.0),
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: Positive::HUNDRED,
             expiry: Positive::ONE,
             no_steps: crate::nz!(1000),
@@ -487,6 +573,7 @@ mod tests_price_binomial {
             asset: Positive::HUNDRED,
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: Positive::HUNDRED,
             expiry: Positive::ZERO,
             no_steps: crate::nz!(1000),
@@ -498,6 +585,36 @@ mod tests_price_binomial {
         let price = price_binomial(params).unwrap();
         assert_decimal_eq!(price, Decimal::ZERO, EPSILON);
     }
+
+    #[test]
+    fn test_dividend_yield_drifts_the_lattice() {
+        let option = Options {
+            dividend_yield: pos_or_panic!(0.03),
+            ..create_sample_option_with_days(
+                OptionStyle::Call,
+                Side::Long,
+                Positive::HUNDRED,
+                Positive::ONE,
+                Positive::HUNDRED,
+                pos_or_panic!(0.2),
+                pos_or_panic!(365.0),
+            )
+        };
+        let lattice = price_binomial(BinomialPricingParams {
+            asset: option.underlying_price,
+            volatility: option.implied_volatility,
+            int_rate: option.risk_free_rate,
+            dividend_yield: option.dividend_yield,
+            strike: option.strike_price,
+            expiry: option.time_to_expiration().unwrap(),
+            no_steps: crate::nz!(500),
+            option_type: &OptionType::European,
+            option_style: &OptionStyle::Call,
+            side: &Side::Long,
+        })
+        .unwrap();
+        assert_decimal_eq!(lattice, black_scholes(&option).unwrap(), dec!(0.01));
+    }
 }
 
 #[cfg(test)]
@@ -515,6 +632,7 @@ mod tests_generate_binomial_tree {
             asset: Positive::HUNDRED,
             strike: Positive::HUNDRED,
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             volatility: pos_or_panic!(0.2),
             expiry: Positive::ONE,
             no_steps: crate::nz!(3),
@@ -547,6 +665,7 @@ mod tests_generate_binomial_tree {
             asset: Positive::HUNDRED,
             strike: Positive::HUNDRED,
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             volatility: pos_or_panic!(0.2),
             expiry: Positive::ONE,
             no_steps: crate::nz!(3),
@@ -570,6 +689,7 @@ mod tests_generate_binomial_tree {
             strike: pos_or_panic!(30.0),
             expiry: Positive::ONE,
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             volatility: pos_or_panic!(0.17),
             no_steps: crate::nz!(1),
             option_type: &OptionType::European,
@@ -593,6 +713,7 @@ mod tests_generate_binomial_tree {
             strike: pos_or_panic!(30.0),
             expiry: Positive::ONE,
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             volatility: pos_or_panic!(0.17),
             no_steps: crate::nz!(2),
             option_type: &OptionType::European,
@@ -626,6 +747,7 @@ mod tests_generate_binomial_tree {
             strike: pos_or_panic!(110.0),
             expiry: pos_or_panic!(3.0), // Assuming each time step is 1 unit of time
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             volatility: pos_or_panic!(0.09531018), // Calculated to match the 10% up/down movement
             no_steps: crate::nz!(3),
             option_type: &OptionType::European,
@@ -666,6 +788,7 @@ mod tests_generate_binomial_tree {
             asset: pos_or_panic!(50.0),
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: pos_or_panic!(52.0),
             expiry: Positive::TWO,
             no_steps: crate::nz!(2),
@@ -697,6 +820,7 @@ mod tests_generate_binomial_tree {
             asset: pos_or_panic!(50.0),
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: pos_or_panic!(52.0),
             expiry: Positive::TWO,
             no_steps: crate::nz!(2),
@@ -738,6 +862,7 @@ mod tests_bermuda_option {
             asset: pos_or_panic!(50.0),
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: pos_or_panic!(52.0),
             expiry: Positive::ONE,
             no_steps: crate::nz!(100),
@@ -788,6 +913,7 @@ mod tests_bermuda_option {
             asset: Positive::HUNDRED,
             volatility: pos_or_panic!(0.3),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: pos_or_panic!(105.0),
             expiry: Positive::ONE,
             no_steps: crate::nz!(50),
@@ -810,6 +936,7 @@ mod tests_bermuda_option {
             asset: pos_or_panic!(50.0),
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: pos_or_panic!(52.0),
             expiry: Positive::ONE,
             no_steps: crate::nz!(52),
@@ -852,6 +979,7 @@ mod tests_bermuda_option {
             asset: Positive::HUNDRED,
             volatility: pos_or_panic!(0.2),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: Positive::HUNDRED,
             expiry: Positive::ONE,
             no_steps: crate::nz!(50),
@@ -883,6 +1011,7 @@ mod tests_bermuda_option {
             asset: Positive::HUNDRED,
             volatility: pos_or_panic!(0.25),
             int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
             strike: pos_or_panic!(95.0),
             expiry: Positive::ONE,
             no_steps: crate::nz!(100),
@@ -898,3 +1027,65 @@ mod tests_bermuda_option {
         );
     }
 }
+
+#[cfg(test)]
+mod tests_price_binomial_with_dividends {
+    use super::*;
+    use crate::pricing::DiscreteDividend;
+    use rust_decimal_macros::dec;
+
+    fn params(option_type: &OptionType) -> BinomialPricingParams<'_> {
+        BinomialPricingParams {
+            asset: Positive::HUNDRED,
+            volatility: pos_or_panic!(0.2),
+            int_rate: dec!(0.05),
+            dividend_yield: Positive::ZERO,
+            strike: Positive::HUNDRED,
+            expiry: Positive::ONE,
+            no_steps: crate::nz!(200),
+            option_type,
+            option_style: &OptionStyle::Call,
+            side: &Side::Long,
+        }
+    }
+
+    #[test]
+    fn test_empty_schedule_matches_plain_tree() {
+        let curve = YieldCurve::flat(dec!(0.05));
+        let plain = price_binomial(params(&OptionType::American)).unwrap();
+        let adjusted = price_binomial_with_dividends(
+            params(&OptionType::American),
+            &DividendSchedule::default(),
+            &DayCountBasis::default(),
+            &curve,
+        )
+        .unwrap();
+        assert_eq!(plain, adjusted);
+    }
+
+    #[test]
+    fn test_american_call_exercised_before_large_dividend() {
+        let curve = YieldCurve::flat(dec!(0.05));
+        let dividends = DividendSchedule::new(vec![DiscreteDividend::in_days(
+            pos_or_panic!(182.5),
+            pos_or_panic!(8.0),
+        )]);
+        let european = price_binomial_with_dividends(
+            params(&OptionType::European),
+            &dividends,
+            &DayCountBasis::default(),
+            &curve,
+        )
+        .unwrap();
+        let american = price_binomial_with_dividends(
+            params(&OptionType::American),
+            &dividends,
+            &DayCountBasis::default(),
+            &curve,
+        )
+        .unwrap();
+        // Without dividends an American call is never exercised early.
+        assert!(american > european + dec!(0.1), "{american} vs {european}");
+        assert!(european < price_binomial(params(&OptionType::European)).unwrap());
+    }
+}
@@ -93,6 +93,9 @@ use tracing::{instrument, trace};
 ///     option_style: OptionStyle::Call,
 ///     dividend_yield: pos_or_panic!(0.0),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 /// let price = black_76(&option)?;
 /// # Ok::<(), optionstratlib::error::PricingError>(())
@@ -72,6 +72,9 @@ use tracing::{instrument, trace};
     side = ?option.side,
 ))]
 pub fn black_scholes(option: &Options) -> Result<Decimal, PricingError> {
+    if let Some(adjusted) = option.escrowed()? {
+        return black_scholes(&adjusted);
+    }
     let (d1, d2, expiry_time) = calculate_d1_d2_and_time(option)?;
     match option.option_type {
         OptionType::European => calculate_european_option_price(option, d1, d2, expiry_time),
@@ -406,6 +409,9 @@ mod tests_black_scholes {
             quantity: Positive::ONE,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -424,6 +430,9 @@ mod tests_black_scholes {
             dividend_yield: Positive::ZERO,
 
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -441,6 +450,9 @@ mod tests_black_scholes {
             quantity: Positive::ZERO,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -1177,3 +1189,94 @@ mod tests_black_scholes_bis {
         assert_decimal_eq!(price, dec!(2.49), dec!(0.01));
     }
 }
+
+#[cfg(test)]
+mod tests_black_scholes_term_structure {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::assert_decimal_eq;
+    use crate::pricing::{DiscreteDividend, DividendSchedule, YieldCurve};
+    use positive::{Positive, pos_or_panic};
+    use rust_decimal_macros::dec;
+
+    fn call() -> Options {
+        Options::new(
+            OptionType::European,
+            Side::Long,
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.25),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.03),
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_black_scholes_flat_curve_matches_scalar_rate() {
+        let option = call();
+        let curved = option
+            .clone()
+            .with_yield_curve(YieldCurve::flat(dec!(0.03)));
+        assert_decimal_eq!(
+            black_scholes(&curved).unwrap(),
+            black_scholes(&option).unwrap(),
+            dec!(1e-8)
+        );
+    }
+
+    #[test]
+    fn test_black_scholes_escrows_cash_dividends() {
+        let dividends = DividendSchedule::new(vec![
+            DiscreteDividend::in_days(pos_or_panic!(91.25), Positive::TWO),
+            DiscreteDividend::in_days(pos_or_panic!(273.75), Positive::TWO),
+            // Goes ex after expiry, so it does not affect the price.
+            DiscreteDividend::in_days(pos_or_panic!(547.5), Positive::TEN),
+        ]);
+        let option = call().with_dividends(dividends);
+        let pv = dec!(2) * dec!(-0.0075).exp() + dec!(2) * dec!(-0.0225).exp();
+        assert_decimal_eq!(option.dividends_present_value().unwrap(), pv, dec!(1e-8));
+
+        let escrowed = Options {
+            underlying_price: Positive::new_decimal(dec!(100) - pv).unwrap(),
+            ..call()
+        };
+        let price = black_scholes(&option).unwrap();
+        assert_decimal_eq!(price, black_scholes(&escrowed).unwrap(), dec!(1e-8));
+        assert!(price < black_scholes(&call()).unwrap());
+    }
+
+    #[test]
+    fn test_dated_dividends_against_dated_expiry() {
+        use crate::utils::DayCountBasis;
+        use chrono::{Duration, Utc};
+
+        let now = Utc::now();
+        let option = Options {
+            expiration_date: ExpirationDate::DateTime(now + Duration::days(180)),
+            ..call()
+        }
+        .with_day_count(DayCountBasis::Actual360)
+        .with_dividends(DividendSchedule::new(vec![
+            DiscreteDividend::new(now - Duration::days(10), Positive::TEN),
+            DiscreteDividend::new(now + Duration::days(90), Positive::TWO),
+            DiscreteDividend::new(now + Duration::days(200), Positive::TEN),
+        ]));
+        // Only the dividend between now and expiry counts, 90/360 years out.
+        let pv = dec!(2) * (dec!(-0.03) * dec!(0.25)).exp();
+        assert_decimal_eq!(option.dividends_present_value().unwrap(), pv, dec!(1e-6));
+    }
+
+    #[test]
+    fn test_black_scholes_rejects_dividends_above_spot() {
+        let option = call().with_dividends(DividendSchedule::new(vec![DiscreteDividend::in_days(
+            pos_or_panic!(182.5),
+            pos_or_panic!(150.0),
+        )]));
+        assert!(black_scholes(&option).is_err());
+    }
+}
@@ -69,8 +69,7 @@ fn simple_chooser_price(option: &Options, choice_date_days: f64) -> Result<Decim
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t_big = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     // Convert choice_date from days to years
@@ -207,8 +206,7 @@ fn price_at_choice_equals_expiry(option: &Options) -> Result<Decimal, PricingErr
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
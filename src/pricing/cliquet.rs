@@ -90,8 +90,7 @@ fn price_cliquet(option: &Options, reset_dates: &[f64]) -> Result<Decimal, Prici
 
     // Total expiration in years
     let t_total = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     // Convert reset dates from days to years
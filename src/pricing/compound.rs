@@ -245,8 +245,7 @@ fn price_compound(
     let q = compound.dividend_yield.to_dec();
     let sigma = compound.implied_volatility;
     let t1 = compound
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t1 == Positive::ZERO {
@@ -0,0 +1,238 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Discrete cash dividends.
+//!
+//! A [`DividendSchedule`] lists the cash dividends an underlying pays. The
+//! pricing engines use the escrowed-dividend model: the spot is split into
+//! the present value of the dividends paid before expiry, which is known,
+//! and a risky part that follows the usual lognormal dynamics.
+//!
+//! Ex-dividend dates are [`ExpirationDate`]s: either a number of days from
+//! the valuation date or an absolute instant. Pricers resolve them with
+//! [`DayCountBasis::years_to`] under the option's basis, the same call that
+//! measures the time to expiry, so dividends and expiry always share one
+//! valuation date. Dividends that have already gone ex are ignored.
+
+use crate::pricing::yield_curve::YieldCurve;
+use crate::utils::DayCountBasis;
+use chrono::{DateTime, Utc};
+use expiration_date::ExpirationDate;
+use expiration_date::error::ExpirationDateError;
+use positive::Positive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A cash dividend paid by the underlying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DiscreteDividend {
+    /// Ex-dividend date, in days from the valuation date or as an instant.
+    pub ex_date: ExpirationDate,
+    /// Cash amount by which the spot drops on the ex-dividend date.
+    pub amount: Positive,
+}
+
+impl DiscreteDividend {
+    /// Creates a dividend of `amount` going ex on `ex_date`.
+    #[must_use]
+    pub fn new(ex_date: DateTime<Utc>, amount: Positive) -> Self {
+        Self {
+            ex_date: ExpirationDate::DateTime(ex_date),
+            amount,
+        }
+    }
+
+    /// Creates a dividend of `amount` going ex `days` calendar days after the
+    /// valuation date, like an option expiring in `ExpirationDate::Days`.
+    #[must_use]
+    pub fn in_days(days: Positive, amount: Positive) -> Self {
+        Self {
+            ex_date: ExpirationDate::Days(days),
+            amount,
+        }
+    }
+
+    /// Years from the valuation date to the ex-dividend date under
+    /// `day_count`, zero once the dividend has gone ex.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpirationDateError`] when the ex-dividend date cannot be
+    /// resolved.
+    pub fn years_to(&self, day_count: &DayCountBasis) -> Result<Positive, ExpirationDateError> {
+        day_count.years_to(&self.ex_date)
+    }
+}
+
+/// Cash dividends of an underlying, ordered by ex-dividend date.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DividendSchedule {
+    dividends: Vec<DiscreteDividend>,
+}
+
+impl DividendSchedule {
+    /// Creates a schedule from dividends in any order.
+    #[must_use]
+    pub fn new(dividends: Vec<DiscreteDividend>) -> Self {
+        let mut schedule = DividendSchedule { dividends };
+        schedule.dividends.sort_by_key(|dividend| dividend.ex_date);
+        schedule
+    }
+
+    /// Adds a dividend.
+    #[must_use]
+    pub fn with_dividend(self, dividend: DiscreteDividend) -> Self {
+        let mut dividends = self.dividends;
+        dividends.push(dividend);
+        Self::new(dividends)
+    }
+
+    /// The dividends, ordered by ex-dividend date.
+    #[must_use]
+    pub fn dividends(&self) -> &[DiscreteDividend] {
+        &self.dividends
+    }
+
+    /// Returns `true` when the schedule has no dividends.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.dividends.is_empty()
+    }
+
+    /// Value at time `from` of the dividends going ex after `from` and no
+    /// later than `to`, discounted on `curve`. Times are years from the
+    /// valuation date under `day_count`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpirationDateError`] when an ex-dividend date cannot be
+    /// resolved.
+    pub fn present_value(
+        &self,
+        day_count: &DayCountBasis,
+        from: Positive,
+        to: Positive,
+        curve: &YieldCurve,
+    ) -> Result<Decimal, ExpirationDateError> {
+        Ok(self.times(day_count)?.present_value(from, to, curve))
+    }
+
+    /// The dividends still to go ex, as year fractions from the valuation
+    /// date under `day_count`.
+    pub(crate) fn times(
+        &self,
+        day_count: &DayCountBasis,
+    ) -> Result<DividendTimes, ExpirationDateError> {
+        let mut times = Vec::with_capacity(self.dividends.len());
+        for dividend in &self.dividends {
+            let time = dividend.years_to(day_count)?;
+            if time > Positive::ZERO {
+                times.push((time, dividend.amount));
+            }
+        }
+        Ok(DividendTimes(times))
+    }
+}
+
+/// A [`DividendSchedule`] resolved to `(years from the valuation date,
+/// amount)` pairs, so that a pricer stepping through time measures every
+/// dividend from the same instant.
+pub(crate) struct DividendTimes(Vec<(Positive, Positive)>);
+
+impl DividendTimes {
+    /// Value at time `from` of the dividends going ex after `from` and no
+    /// later than `to`, discounted on `curve`.
+    pub(crate) fn present_value(
+        &self,
+        from: Positive,
+        to: Positive,
+        curve: &YieldCurve,
+    ) -> Decimal {
+        let start = curve.discount_factor(from);
+        self.0
+            .iter()
+            .filter(|(time, _)| *time > from && *time <= to)
+            .map(|(time, amount)| amount.to_dec() * curve.discount_factor(*time) / start)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests_dividends {
+    use super::*;
+    use chrono::Duration;
+    use positive::pos_or_panic;
+    use rust_decimal::MathematicalOps;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_schedule_present_value() {
+        let basis = DayCountBasis::Actual365Fixed;
+        let schedule = DividendSchedule::new(vec![
+            DiscreteDividend::in_days(pos_or_panic!(273.75), Positive::ONE),
+            DiscreteDividend::in_days(pos_or_panic!(91.25), Positive::TWO),
+        ]);
+        assert_eq!(schedule.dividends()[0].amount, Positive::TWO);
+
+        let curve = YieldCurve::flat(dec!(0.04));
+        let all = schedule
+            .present_value(&basis, Positive::ZERO, Positive::ONE, &curve)
+            .unwrap();
+        let expected = dec!(2) * dec!(-0.01).exp() + dec!(-0.03).exp();
+        assert!((all - expected).abs() < dec!(0.0000001));
+
+        // Only the second dividend remains after 0.5 years, valued at 0.5.
+        let remaining = schedule
+            .present_value(&basis, pos_or_panic!(0.5), Positive::ONE, &curve)
+            .unwrap();
+        assert!((remaining - dec!(-0.01).exp()).abs() < dec!(0.0000001));
+        assert_eq!(
+            schedule
+                .present_value(&basis, Positive::ZERO, pos_or_panic!(0.1), &curve)
+                .unwrap(),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_ex_dates_are_measured_under_the_day_count() {
+        let dividend = DiscreteDividend::in_days(pos_or_panic!(90.0), Positive::ONE);
+        let act365 = dividend.years_to(&DayCountBasis::Actual365Fixed).unwrap();
+        let act360 = dividend.years_to(&DayCountBasis::Actual360).unwrap();
+        assert!((act365.to_dec() - dec!(90) / dec!(365)).abs() < dec!(0.000001));
+        assert!((act360.to_dec() - dec!(0.25)).abs() < dec!(0.000001));
+
+        // A dividend that has gone ex no longer counts.
+        let paid = DiscreteDividend::new(Utc::now() - Duration::days(1), Positive::TEN);
+        assert_eq!(
+            paid.years_to(&DayCountBasis::Actual365Fixed).unwrap(),
+            Positive::ZERO
+        );
+        let schedule = DividendSchedule::new(vec![paid, dividend]);
+        let pv = schedule
+            .present_value(
+                &DayCountBasis::Actual365Fixed,
+                Positive::ZERO,
+                Positive::ONE,
+                &YieldCurve::flat(Decimal::ZERO),
+            )
+            .unwrap();
+        assert_eq!(pv, Decimal::ONE);
+    }
+
+    #[test]
+    fn test_relative_ex_dates_share_the_expiry_reference() {
+        for basis in [DayCountBasis::Actual365Fixed, DayCountBasis::Actual360] {
+            let dividend = DiscreteDividend::in_days(pos_or_panic!(45.0), Positive::ONE);
+            let expiry = ExpirationDate::Days(pos_or_panic!(45.0));
+            assert_eq!(
+                dividend.years_to(&basis).unwrap(),
+                basis.years_to(&expiry).unwrap()
+            );
+        }
+    }
+}
@@ -82,7 +82,7 @@ pub fn exchange_black_scholes(option: &Options) -> Result<Decimal, PricingError>
     let s2 = second_asset_price;
     let q1 = Decimal::from(option.dividend_yield);
     let sigma1 = Decimal::from(option.implied_volatility);
-    let t = Decimal::from(option.expiration_date.get_years()?);
+    let t = Decimal::from(option.years_to_expiry()?);
 
     let price = margrabe_formula(
         s1,
@@ -0,0 +1,1043 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Finite-Difference Pricing
+//!
+//! Solves the Black–Scholes PDE
+//!
+//! ```text
+//! ∂V/∂t + ½ σ² S² ∂²V/∂S² + (r - q) S ∂V/∂S - r V = 0
+//! ```
+//!
+//! backwards from expiry on a uniform spot grid with the Crank–Nicolson
+//! scheme. The first time steps are replaced by fully implicit half steps
+//! (Rannacher smoothing) so that the kink or jump of the payoff does not
+//! produce the spurious oscillations Crank–Nicolson is known for.
+//!
+//! * **Early exercise**: American options solve the linear complementarity
+//!   problem `V ≥ payoff` at every step with projected SOR (PSOR); Bermuda
+//!   options project onto the payoff at their exercise dates.
+//! * **Barriers**: knock-out barriers are absorbing Dirichlet boundaries
+//!   worth the rebate; knock-in options are priced through in/out parity
+//!   with a second, barrier-bounded solve.
+//! * **Discrete dividends**: the spot drops by the cash amount at every
+//!   ex-dividend date, imposed as the jump condition `V(S, t⁻) = V(S - D, t⁺)`.
+//!
+//! The grid is built so that both the spot and any barrier sit exactly on a
+//! node, which makes the grid delta, gamma and theta direct finite
+//! differences of the solution. The engine is meant as a reference against
+//! which the closed forms of [`crate::pricing::barrier`] and
+//! [`crate::pricing::american`] can be validated.
+
+use crate::Options;
+use crate::error::PricingError;
+use crate::model::decimal::finite_decimal;
+use crate::model::types::{BarrierType, OptionStyle, OptionType, Side};
+pub use crate::pricing::dividends::DiscreteDividend;
+use crate::pricing::payoff::{Payoff, PayoffInfo};
+use num_traits::ToPrimitive;
+use positive::Positive;
+use positive::constants::DAYS_IN_A_YEAR;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::num::NonZeroUsize;
+use std::ops::Sub;
+use tracing::{debug, instrument};
+
+/// Floor applied to `σ√T` when sizing the grid, so that low-volatility or
+/// short-dated options still get a grid wide enough to hold the strike.
+const MIN_GRID_STD_DEV: f64 = 0.05;
+
+/// Number of sample points used to cell-average the terminal payoff.
+const CELL_SAMPLES: usize = 16;
+
+/// Configuration of the finite-difference engine.
+///
+/// The defaults use a 200 × 200 grid spanning five standard deviations,
+/// two Rannacher steps and an over-relaxation factor of 1.2 for PSOR.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiniteDifferenceConfig {
+    /// Number of spot intervals of the grid (at least 4).
+    pub space_steps: NonZeroUsize,
+    /// Number of time steps between today and expiry.
+    pub time_steps: NonZeroUsize,
+    /// Number of initial Crank–Nicolson steps replaced by two fully implicit
+    /// half steps each.
+    pub rannacher_steps: usize,
+    /// Half-width of the grid in standard deviations of `ln S_T`.
+    pub std_devs: Positive,
+    /// PSOR over-relaxation factor, in `(0, 2)`.
+    pub psor_omega: Decimal,
+    /// PSOR convergence tolerance on the largest update of a sweep.
+    pub psor_tolerance: Decimal,
+    /// Maximum number of PSOR sweeps per time step.
+    pub psor_max_iterations: u32,
+    /// Cash dividends paid before expiry.
+    pub dividends: Vec<DiscreteDividend>,
+}
+
+impl Default for FiniteDifferenceConfig {
+    fn default() -> Self {
+        Self {
+            space_steps: NonZeroUsize::MIN.saturating_add(199),
+            time_steps: NonZeroUsize::MIN.saturating_add(199),
+            rannacher_steps: 2,
+            std_devs: Positive::FIVE,
+            psor_omega: dec!(1.2),
+            psor_tolerance: dec!(1e-10),
+            psor_max_iterations: 10_000,
+            dividends: Vec::new(),
+        }
+    }
+}
+
+impl FiniteDifferenceConfig {
+    /// Sets the number of spot intervals.
+    #[must_use]
+    pub fn with_space_steps(mut self, space_steps: NonZeroUsize) -> Self {
+        self.space_steps = space_steps;
+        self
+    }
+
+    /// Sets the number of time steps.
+    #[must_use]
+    pub fn with_time_steps(mut self, time_steps: NonZeroUsize) -> Self {
+        self.time_steps = time_steps;
+        self
+    }
+
+    /// Sets the number of Rannacher start-up steps.
+    #[must_use]
+    pub fn with_rannacher_steps(mut self, rannacher_steps: usize) -> Self {
+        self.rannacher_steps = rannacher_steps;
+        self
+    }
+
+    /// Sets the grid half-width in standard deviations.
+    #[must_use]
+    pub fn with_std_devs(mut self, std_devs: Positive) -> Self {
+        self.std_devs = std_devs;
+        self
+    }
+
+    /// Sets the PSOR over-relaxation factor.
+    #[must_use]
+    pub fn with_psor_omega(mut self, psor_omega: Decimal) -> Self {
+        self.psor_omega = psor_omega;
+        self
+    }
+
+    /// Adds a cash dividend.
+    #[must_use]
+    pub fn with_dividend(mut self, dividend: DiscreteDividend) -> Self {
+        self.dividends.push(dividend);
+        self
+    }
+}
+
+/// Price and grid Greeks returned by [`finite_difference_price`].
+///
+/// Values are per unit and carry the side sign like
+/// [`crate::pricing::black_scholes`]; theta is per calendar day, as in
+/// [`crate::greeks::theta`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FiniteDifferenceResult {
+    /// Option price.
+    pub price: Decimal,
+    /// First derivative of the price with respect to the spot.
+    pub delta: Decimal,
+    /// Second derivative of the price with respect to the spot.
+    pub gamma: Decimal,
+    /// Price change per calendar day.
+    pub theta: Decimal,
+}
+
+/// Prices an option by Crank–Nicolson finite differences on the
+/// Black–Scholes PDE and returns the grid delta, gamma and theta.
+///
+/// Supports European, American and Bermuda exercise of vanilla calls and
+/// puts, continuously monitored barrier options (with rebate, European
+/// exercise) and binary options. The continuous `dividend_yield` enters the
+/// drift; cash dividends are taken from `config.dividends` and the option's
+/// dividend schedule, and the rate is the option's zero rate to expiry.
+///
+/// # Errors
+///
+/// * `PricingError::UnsupportedOptionType` for any other option type.
+/// * `PricingError::InvalidEngine` when the grid has fewer than four spot
+///   intervals or the PSOR factor lies outside `(0, 2)`.
+/// * `PricingError::MethodError` when the tridiagonal system is singular or
+///   PSOR does not converge, `PricingError::NonFinite` when the solution is
+///   not finite, and `PricingError::ExpirationDate` when the expiry or an
+///   ex-dividend date cannot be converted to a year fraction.
+#[instrument(skip(option, config), fields(
+    space_steps = config.space_steps.get(),
+    time_steps = config.time_steps.get(),
+    option_type = %option.option_type,
+    style = ?option.option_style,
+))]
+pub fn finite_difference_price(
+    option: &Options,
+    config: &FiniteDifferenceConfig,
+) -> Result<FiniteDifferenceResult, PricingError> {
+    if !matches!(
+        option.option_type,
+        OptionType::European
+            | OptionType::American
+            | OptionType::Bermuda { .. }
+            | OptionType::Barrier { .. }
+            | OptionType::Binary { .. }
+    ) {
+        return Err(PricingError::unsupported_option_type(
+            &option.option_type.to_string(),
+            "Finite difference",
+        ));
+    }
+    if config.space_steps.get() < 4 {
+        return Err(PricingError::invalid_engine(
+            "finite-difference grid needs at least 4 spot intervals",
+        ));
+    }
+    let omega = config.psor_omega.to_f64().unwrap_or(0.0);
+    if omega <= 0.0 || omega >= 2.0 {
+        return Err(PricingError::invalid_engine(&format!(
+            "PSOR over-relaxation factor must lie in (0, 2), got {}",
+            config.psor_omega
+        )));
+    }
+
+    let market = Market::new(option, config)?;
+    let solution = if market.expiry <= 0.0 {
+        Solution::constant(market.terminal_payoff(option, market.spot)?)
+    } else {
+        solve_option(option, &market, config)?
+    };
+    debug!(price = solution.price, "finite-difference solve complete");
+
+    let sign = match option.side {
+        Side::Long => 1.0,
+        Side::Short => -1.0,
+    };
+    let finite = |value: f64, context: &'static str| {
+        finite_decimal(sign * value).ok_or_else(|| PricingError::non_finite(context, value))
+    };
+    Ok(FiniteDifferenceResult {
+        price: finite(solution.price, "pricing::finite_difference::price")?,
+        delta: finite(solution.delta, "pricing::finite_difference::delta")?,
+        gamma: finite(solution.gamma, "pricing::finite_difference::gamma")?,
+        theta: finite(
+            solution.theta / DAYS_IN_A_YEAR.to_f64(),
+            "pricing::finite_difference::theta",
+        )?,
+    })
+}
+
+/// Market inputs of the PDE in `f64`.
+struct Market {
+    spot: f64,
+    strike: f64,
+    rate: f64,
+    carry: f64,
+    sigma: f64,
+    expiry: f64,
+    /// `(ex-date, amount)` of the dividends strictly before expiry.
+    dividends: Vec<(f64, f64)>,
+    omega: f64,
+    tolerance: f64,
+    max_iterations: u32,
+}
+
+impl Market {
+    fn new(option: &Options, config: &FiniteDifferenceConfig) -> Result<Self, PricingError> {
+        let expiry = option.time_to_expiration()?.to_f64();
+        let mut dividends = Vec::new();
+        for dividend in config.dividends.iter().chain(option.dividends.dividends()) {
+            let time = dividend.years_to(&option.day_count)?.to_f64();
+            if time > 0.0 && time < expiry {
+                dividends.push((time, dividend.amount.to_f64()));
+            }
+        }
+        Ok(Self {
+            spot: option.underlying_price.to_f64(),
+            strike: option.strike_price.to_f64(),
+            rate: option.term_risk_free_rate()?.to_f64().unwrap_or(0.0),
+            carry: option.dividend_yield.to_f64(),
+            sigma: option.implied_volatility.to_f64(),
+            expiry,
+            dividends,
+            omega: config.psor_omega.to_f64().unwrap_or(1.0),
+            tolerance: config.psor_tolerance.to_f64().unwrap_or(1e-10),
+            max_iterations: config.psor_max_iterations,
+        })
+    }
+
+    fn intrinsic(&self, style: OptionStyle, spot: f64) -> f64 {
+        match style {
+            OptionStyle::Call => (spot - self.strike).max(0.0),
+            OptionStyle::Put => (self.strike - spot).max(0.0),
+        }
+    }
+
+    /// Payoff at expiry; barrier conditions are imposed by the grid.
+    fn terminal_payoff(&self, option: &Options, spot: f64) -> Result<f64, PricingError> {
+        match option.option_type {
+            OptionType::Binary { .. } => Ok(option.option_type.payoff(&PayoffInfo {
+                spot: Positive::new(spot)?,
+                strike: option.strike_price,
+                style: option.option_style,
+                side: Side::Long,
+                ..PayoffInfo::default()
+            })),
+            _ => Ok(self.intrinsic(option.option_style, spot)),
+        }
+    }
+}
+
+/// Price and Greeks of a single solve, in `f64` and per year.
+#[derive(Debug, Clone, Copy)]
+struct Solution {
+    price: f64,
+    delta: f64,
+    gamma: f64,
+    theta: f64,
+}
+
+impl Solution {
+    fn constant(price: f64) -> Self {
+        Self {
+            price,
+            delta: 0.0,
+            gamma: 0.0,
+            theta: 0.0,
+        }
+    }
+}
+
+impl Sub for Solution {
+    type Output = Solution;
+
+    fn sub(self, other: Solution) -> Solution {
+        Solution {
+            price: self.price - other.price,
+            delta: self.delta - other.delta,
+            gamma: self.gamma - other.gamma,
+            theta: self.theta - other.theta,
+        }
+    }
+}
+
+fn solve_option(
+    option: &Options,
+    market: &Market,
+    config: &FiniteDifferenceConfig,
+) -> Result<Solution, PricingError> {
+    let exercise = match &option.option_type {
+        OptionType::American => Exercise::American,
+        OptionType::Bermuda { exercise_dates } => {
+            Exercise::Bermuda(exercise_dates.iter().map(|d| d.to_f64()).collect())
+        }
+        _ => Exercise::European,
+    };
+    let width =
+        config.std_devs.to_f64() * (market.sigma * market.expiry.sqrt()).max(MIN_GRID_STD_DEV);
+    let far = market.spot.max(market.strike) * width.exp();
+    let m = config.space_steps.get();
+
+    let OptionType::Barrier {
+        barrier_type,
+        barrier_level,
+        rebate,
+    } = &option.option_type
+    else {
+        let grid = Grid::open(market.spot, far, m);
+        let terminal = grid.cell_average(|s| market.terminal_payoff(option, s))?;
+        return grid.solve(
+            option,
+            market,
+            config,
+            &exercise,
+            terminal,
+            Edge::Pde,
+            Edge::Linear,
+        );
+    };
+
+    let level = barrier_level.to_f64();
+    let rebate = rebate.map_or(0.0, |r| r.to_f64());
+    let (down, knock_out) = match barrier_type {
+        BarrierType::DownAndOut => (true, true),
+        BarrierType::DownAndIn => (true, false),
+        BarrierType::UpAndOut => (false, true),
+        BarrierType::UpAndIn => (false, false),
+        // `BarrierType` is `#[non_exhaustive]`.
+        _ => {
+            return Err(PricingError::unsupported_option_type(
+                &option.option_type.to_string(),
+                "Finite difference",
+            ));
+        }
+    };
+    let breached = if down {
+        market.spot <= level
+    } else {
+        market.spot >= level
+    };
+    let vanilla = || {
+        let grid = Grid::open(market.spot, far, m);
+        let terminal = grid.cell_average(|s| Ok(market.intrinsic(option.option_style, s)))?;
+        grid.solve(
+            option,
+            market,
+            config,
+            &exercise,
+            terminal,
+            Edge::Pde,
+            Edge::Linear,
+        )
+    };
+    // The knock-out leg, with `shift` subtracted from the terminal payoff and
+    // `boundary` paid on the barrier.
+    let knock_out_leg = |shift: f64, boundary: f64| {
+        let (grid, lower, upper) = if down {
+            let grid = Grid::down_barrier(market.spot, level, far.max(2.0 * market.spot), m);
+            (grid, Edge::Fixed(boundary), Edge::Linear)
+        } else {
+            let grid = Grid::up_barrier(market.spot, level, m);
+            let lower = if grid.lower_at_zero() {
+                Edge::Pde
+            } else {
+                Edge::Linear
+            };
+            (grid, lower, Edge::Fixed(boundary))
+        };
+        let terminal =
+            grid.cell_average(|s| Ok(market.intrinsic(option.option_style, s) - shift))?;
+        grid.solve(option, market, config, &exercise, terminal, lower, upper)
+    };
+
+    match (knock_out, breached) {
+        (true, true) => Ok(Solution::constant(rebate)),
+        (true, false) => knock_out_leg(0.0, rebate),
+        (false, true) => vanilla(),
+        // In/out parity with the rebate paid at expiry when never knocked in:
+        // KI = V - E[e^{-rT} (payoff - R) 1{not hit}].
+        (false, false) => Ok(vanilla()? - knock_out_leg(rebate, 0.0)?),
+    }
+}
+
+enum Exercise {
+    European,
+    American,
+    Bermuda(Vec<f64>),
+}
+
+/// Boundary condition at one end of the spot grid.
+#[derive(Debug, Clone, Copy)]
+enum Edge {
+    /// The PDE itself, valid only at `S = 0` where it degenerates to
+    /// `∂V/∂t = r V`.
+    Pde,
+    /// Absorbing boundary with a fixed value (barrier rebate).
+    Fixed(f64),
+    /// Zero gamma: the value is extrapolated linearly.
+    Linear,
+}
+
+/// Uniform spot grid with the spot on node `spot_index`.
+struct Grid {
+    nodes: Vec<f64>,
+    spot_index: usize,
+    ds: f64,
+}
+
+impl Grid {
+    fn build(lower: f64, ds: f64, m: usize, spot_index: usize) -> Self {
+        Self {
+            nodes: (0..=m).map(|i| lower + i as f64 * ds).collect(),
+            spot_index,
+            ds,
+        }
+    }
+
+    /// Grid on `[0, ≈far]`.
+    fn open(spot: f64, far: f64, m: usize) -> Self {
+        let j = ((m as f64 * spot / far).round() as usize).clamp(1, m - 1);
+        Self::build(0.0, spot / j as f64, m, j)
+    }
+
+    /// Grid on `[barrier, ≈far]` for a down barrier below the spot.
+    fn down_barrier(spot: f64, barrier: f64, far: f64, m: usize) -> Self {
+        let j = ((m as f64 * (spot - barrier) / (far - barrier)).round() as usize).clamp(1, m - 1);
+        Self::build(barrier, (spot - barrier) / j as f64, m, j)
+    }
+
+    /// Grid on `[≥ 0, barrier]` for an up barrier above the spot.
+    fn up_barrier(spot: f64, barrier: f64, m: usize) -> Self {
+        let j = ((m as f64 * spot / barrier).floor() as usize).clamp(1, m - 1);
+        let ds = (barrier - spot) / (m - j) as f64;
+        Self::build((spot - j as f64 * ds).max(0.0), ds, m, j)
+    }
+
+    fn lower_at_zero(&self) -> bool {
+        self.nodes.first().is_some_and(|&s| s <= 1e-12 * self.ds)
+    }
+
+    fn map<F>(&self, f: F) -> Result<Vec<f64>, PricingError>
+    where
+        F: Fn(f64) -> Result<f64, PricingError>,
+    {
+        self.nodes.iter().map(|&s| f(s)).collect()
+    }
+
+    /// Averages `f` over the cell `[s - ds/2, s + ds/2]` of every node, which
+    /// removes the O(ds) error of sampling a kinked or discontinuous payoff
+    /// exactly at its strike.
+    fn cell_average<F>(&self, f: F) -> Result<Vec<f64>, PricingError>
+    where
+        F: Fn(f64) -> Result<f64, PricingError>,
+    {
+        self.map(|s| {
+            let mut sum = 0.0;
+            for k in 0..CELL_SAMPLES {
+                let offset = (k as f64 + 0.5) / CELL_SAMPLES as f64 - 0.5;
+                sum += f((s + offset * self.ds).max(0.0))?;
+            }
+            Ok(sum / CELL_SAMPLES as f64)
+        })
+    }
+
+    /// Value at `spot` by linear interpolation, clamped to the grid range.
+    fn interpolate(&self, values: &[f64], spot: f64) -> f64 {
+        let lower = self.nodes.first().copied().unwrap_or(0.0);
+        let last = values.len().saturating_sub(1);
+        let x = ((spot - lower) / self.ds).clamp(0.0, last as f64);
+        let i = (x.floor() as usize).min(last.saturating_sub(1));
+        let w = x - i as f64;
+        let left = values.get(i).copied().unwrap_or(0.0);
+        let right = values.get(i + 1).copied().unwrap_or(left);
+        left + w * (right - left)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn solve(
+        &self,
+        option: &Options,
+        market: &Market,
+        config: &FiniteDifferenceConfig,
+        exercise: &Exercise,
+        terminal: Vec<f64>,
+        lower: Edge,
+        upper: Edge,
+    ) -> Result<Solution, PricingError> {
+        let obstacle = self.map(|s| Ok(market.intrinsic(option.option_style, s)))?;
+        let mut values = terminal;
+        apply_edges(&mut values, lower, upper);
+
+        let n = config.time_steps.get();
+        let dt = market.expiry / n as f64;
+        let smoothing = config.rannacher_steps.min(n);
+        let schedule = (0..smoothing)
+            .flat_map(|_| [(1.0, 0.5 * dt), (1.0, 0.5 * dt)])
+            .chain((smoothing..n).map(|_| (0.5, dt)));
+
+        let mut tau = 0.0;
+        let mut previous = values.clone();
+        let mut last_dt = dt;
+        for (theta, step) in schedule {
+            previous.clone_from(&values);
+            values = self.step(
+                market, &values, theta, step, lower, upper, exercise, &obstacle,
+            )?;
+            let (from, to) = (tau, tau + step);
+            tau = to;
+            last_dt = step;
+
+            for &(ex_date, amount) in &market.dividends {
+                let ex_tau = market.expiry - ex_date;
+                if ex_tau > from && ex_tau <= to {
+                    let after = values.clone();
+                    for (value, &s) in values.iter_mut().zip(&self.nodes) {
+                        *value = self.interpolate(&after, (s - amount).max(0.0));
+                    }
+                    apply_edges(&mut values, lower, upper);
+                    if !matches!(exercise, Exercise::European) {
+                        project(&mut values, &obstacle);
+                    }
+                }
+            }
+            if let Exercise::Bermuda(dates) = exercise {
+                let now = market.expiry - tau;
+                if dates.iter().any(|&d| (d - now).abs() <= 0.5 * step) {
+                    project(&mut values, &obstacle);
+                }
+            }
+        }
+
+        let j = self.spot_index;
+        let value = |v: &[f64], i: usize| v.get(i).copied().unwrap_or(0.0);
+        let (down, mid, up) = (
+            value(&values, j - 1),
+            value(&values, j),
+            value(&values, j + 1),
+        );
+        Ok(Solution {
+            price: mid,
+            delta: (up - down) / (2.0 * self.ds),
+            gamma: (up - 2.0 * mid + down) / (self.ds * self.ds),
+            theta: (value(&previous, j) - mid) / last_dt,
+        })
+    }
+
+    /// Advances the solution by one θ-scheme step of length `dt`.
+    #[allow(clippy::too_many_arguments)]
+    fn step(
+        &self,
+        market: &Market,
+        values: &[f64],
+        theta: f64,
+        dt: f64,
+        lower: Edge,
+        upper: Edge,
+        exercise: &Exercise,
+        obstacle: &[f64],
+    ) -> Result<Vec<f64>, PricingError> {
+        let last = self.nodes.len() - 1;
+        let first = usize::from(!matches!(lower, Edge::Pde));
+        let size = last - first;
+        let (mut sub, mut diag, mut sup, mut rhs) = (
+            Vec::with_capacity(size),
+            Vec::with_capacity(size),
+            Vec::with_capacity(size),
+            Vec::with_capacity(size),
+        );
+        let variance = market.sigma * market.sigma;
+        let drift = market.rate - market.carry;
+        for i in first..last {
+            let s = self.nodes.get(i).copied().unwrap_or(0.0);
+            let diffusion = 0.5 * variance * s * s / (self.ds * self.ds);
+            let convection = 0.5 * drift * s / self.ds;
+            let (a, b, c) = (
+                diffusion - convection,
+                -2.0 * diffusion - market.rate,
+                diffusion + convection,
+            );
+            let left = if i == 0 {
+                0.0
+            } else {
+                values.get(i - 1).copied().unwrap_or(0.0)
+            };
+            let centre = values.get(i).copied().unwrap_or(0.0);
+            let right = values.get(i + 1).copied().unwrap_or(0.0);
+            rhs.push(centre + (1.0 - theta) * dt * (a * left + b * centre + c * right));
+            sub.push(-theta * dt * a);
+            diag.push(1.0 - theta * dt * b);
+            sup.push(-theta * dt * c);
+        }
+
+        // Fold the boundary conditions into the first and last rows.
+        match (
+            lower,
+            sub.first_mut(),
+            diag.first_mut(),
+            sup.first_mut(),
+            rhs.first_mut(),
+        ) {
+            (Edge::Fixed(g), Some(l), _, _, Some(r)) => {
+                *r -= *l * g;
+                *l = 0.0;
+            }
+            (Edge::Linear, Some(l), Some(d), Some(u), _) => {
+                *d += 2.0 * *l;
+                *u -= *l;
+                *l = 0.0;
+            }
+            _ => {}
+        }
+        match (
+            upper,
+            sub.last_mut(),
+            diag.last_mut(),
+            sup.last_mut(),
+            rhs.last_mut(),
+        ) {
+            (Edge::Fixed(h), _, _, Some(u), Some(r)) => {
+                *r -= *u * h;
+                *u = 0.0;
+            }
+            (Edge::Linear, Some(l), Some(d), Some(u), _) => {
+                *l -= *u;
+                *d += 2.0 * *u;
+                *u = 0.0;
+            }
+            _ => {}
+        }
+
+        let interior = match exercise {
+            Exercise::American => {
+                let floor = obstacle.get(first..last).unwrap_or_default();
+                let start = values.get(first..last).unwrap_or_default();
+                psor(&sub, &diag, &sup, &rhs, floor, start, market)?
+            }
+            _ => thomas(&sub, &diag, &sup, &rhs).ok_or_else(|| {
+                PricingError::method_error("finite_difference_price", "singular tridiagonal system")
+            })?,
+        };
+
+        let mut next = Vec::with_capacity(self.nodes.len());
+        if first == 1 {
+            next.push(0.0);
+        }
+        next.extend(interior);
+        next.push(0.0);
+        apply_edges(&mut next, lower, upper);
+        if matches!(exercise, Exercise::American) {
+            project(&mut next, obstacle);
+        }
+        Ok(next)
+    }
+}
+
+/// Writes the boundary values implied by `lower` and `upper` into `values`.
+fn apply_edges(values: &mut [f64], lower: Edge, upper: Edge) {
+    match lower {
+        Edge::Fixed(g) => {
+            if let Some(v) = values.first_mut() {
+                *v = g;
+            }
+        }
+        Edge::Linear => {
+            if let [v0, v1, v2, ..] = values {
+                *v0 = 2.0 * *v1 - *v2;
+            }
+        }
+        Edge::Pde => {}
+    }
+    match upper {
+        Edge::Fixed(h) => {
+            if let Some(v) = values.last_mut() {
+                *v = h;
+            }
+        }
+        Edge::Linear => {
+            if let [.., v2, v1, v0] = values {
+                *v0 = 2.0 * *v1 - *v2;
+            }
+        }
+        Edge::Pde => {}
+    }
+}
+
+/// Imposes the early-exercise constraint `V ≥ payoff`.
+fn project(values: &mut [f64], obstacle: &[f64]) {
+    for (v, &floor) in values.iter_mut().zip(obstacle) {
+        *v = v.max(floor);
+    }
+}
+
+/// Thomas algorithm for a tridiagonal system; `None` when a pivot vanishes.
+fn thomas(sub: &[f64], diag: &[f64], sup: &[f64], rhs: &[f64]) -> Option<Vec<f64>> {
+    let mut c_prime = Vec::with_capacity(diag.len());
+    let mut d_prime = Vec::with_capacity(diag.len());
+    let (mut c_prev, mut d_prev) = (0.0, 0.0);
+    for (((&l, &d), &u), &r) in sub.iter().zip(diag).zip(sup).zip(rhs) {
+        let pivot = d - l * c_prev;
+        if pivot.abs() < f64::MIN_POSITIVE {
+            return None;
+        }
+        c_prev = u / pivot;
+        d_prev = (r - l * d_prev) / pivot;
+        c_prime.push(c_prev);
+        d_prime.push(d_prev);
+    }
+    let mut solution = vec![0.0; diag.len()];
+    let mut next = 0.0;
+    for ((x, &c), &d) in solution.iter_mut().zip(&c_prime).zip(&d_prime).rev() {
+        next = d - c * next;
+        *x = next;
+    }
+    solution.iter().all(|x| x.is_finite()).then_some(solution)
+}
+
+/// Projected successive over-relaxation for the linear complementarity
+/// problem `A x ≥ b, x ≥ floor, (A x - b)ᵀ (x - floor) = 0`.
+fn psor(
+    sub: &[f64],
+    diag: &[f64],
+    sup: &[f64],
+    rhs: &[f64],
+    floor: &[f64],
+    start: &[f64],
+    market: &Market,
+) -> Result<Vec<f64>, PricingError> {
+    let mut x: Vec<f64> = start.iter().zip(floor).map(|(&v, &f)| v.max(f)).collect();
+    let n = x.len();
+    for _ in 0..market.max_iterations {
+        let mut largest = 0.0f64;
+        for i in 0..n {
+            let left = if i == 0 {
+                0.0
+            } else {
+                x.get(i - 1).copied().unwrap_or(0.0)
+            };
+            let right = x.get(i + 1).copied().unwrap_or(0.0);
+            let (Some(&l), Some(&d), Some(&u), Some(&r), Some(&f)) = (
+                sub.get(i),
+                diag.get(i),
+                sup.get(i),
+                rhs.get(i),
+                floor.get(i),
+            ) else {
+                continue;
+            };
+            let Some(current) = x.get_mut(i) else {
+                continue;
+            };
+            let gauss_seidel = (r - l * left - u * right) / d;
+            let updated = (*current + market.omega * (gauss_seidel - *current)).max(f);
+            largest = largest.max((updated - *current).abs());
+            *current = updated;
+        }
+        if largest <= market.tolerance {
+            return Ok(x);
+        }
+    }
+    Err(PricingError::method_error(
+        "finite_difference_price",
+        &format!(
+            "PSOR did not converge within {} iterations",
+            market.max_iterations
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::greeks::{delta, gamma, theta};
+    use crate::model::types::BinaryType;
+    use crate::model::utils::create_sample_option_with_days;
+    use crate::pricing::binomial_model::{BinomialPricingParams, price_binomial};
+    use crate::pricing::{barrier_black_scholes, binary_black_scholes, black_scholes};
+    use positive::pos_or_panic;
+
+    fn option(option_type: OptionType, style: OptionStyle) -> Options {
+        Options {
+            option_type,
+            dividend_yield: pos_or_panic!(0.02),
+            ..create_sample_option_with_days(
+                style,
+                Side::Long,
+                Positive::HUNDRED,
+                Positive::ONE,
+                Positive::HUNDRED,
+                pos_or_panic!(0.25),
+                pos_or_panic!(182.5),
+            )
+        }
+    }
+
+    fn price(option: &Options) -> Decimal {
+        finite_difference_price(option, &FiniteDifferenceConfig::default())
+            .unwrap()
+            .price
+    }
+
+    #[test]
+    fn test_finite_difference_european_matches_black_scholes() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            // `greeks::delta` applies e^{-qT} to N(d1) with a d1 that omits q,
+            // so the Greeks are compared without a dividend yield.
+            let mut european = option(OptionType::European, style);
+            european.dividend_yield = Positive::ZERO;
+            let result =
+                finite_difference_price(&european, &FiniteDifferenceConfig::default()).unwrap();
+            let reference = black_scholes(&european).unwrap();
+            assert!(
+                (result.price - reference).abs() < dec!(0.01),
+                "{result:?} vs {reference}"
+            );
+            assert!((result.delta - delta(&european).unwrap()).abs() < dec!(0.002));
+            assert!((result.gamma - gamma(&european).unwrap()).abs() < dec!(0.0005));
+            assert!((result.theta - theta(&european).unwrap()).abs() < dec!(0.002));
+        }
+    }
+
+    #[test]
+    fn test_finite_difference_american_put_matches_lattice() {
+        let american = option(OptionType::American, OptionStyle::Put);
+        let lattice = price_binomial(BinomialPricingParams {
+            asset: american.underlying_price,
+            volatility: american.implied_volatility,
+            int_rate: american.risk_free_rate,
+            dividend_yield: american.dividend_yield,
+            strike: american.strike_price,
+            expiry: american.time_to_expiration().unwrap(),
+            no_steps: NonZeroUsize::new(500).unwrap(),
+            option_type: &american.option_type,
+            option_style: &american.option_style,
+            side: &Side::Long,
+        })
+        .unwrap();
+        assert!((price(&american) - lattice).abs() < dec!(0.02));
+        assert!(
+            price(&american)
+                > black_scholes(&option(OptionType::European, OptionStyle::Put)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_finite_difference_bermuda_between_european_and_american() {
+        let bermuda = option(
+            OptionType::Bermuda {
+                exercise_dates: vec![pos_or_panic!(0.1), pos_or_panic!(0.25), pos_or_panic!(0.4)],
+            },
+            OptionStyle::Put,
+        );
+        let european = price(&option(OptionType::European, OptionStyle::Put));
+        let american = price(&option(OptionType::American, OptionStyle::Put));
+        let bermudan = price(&bermuda);
+        assert!(
+            european < bermudan && bermudan < american,
+            "{european} {bermudan} {american}"
+        );
+    }
+
+    #[test]
+    fn test_finite_difference_barriers_match_closed_form() {
+        // Only the down barrier call formulas are exercised here; those are
+        // the variants with a strike above the barrier.
+        let cases = [
+            (BarrierType::DownAndOut, 85.0),
+            (BarrierType::DownAndIn, 85.0),
+        ];
+        for (barrier_type, level) in cases {
+            let barrier = option(
+                OptionType::Barrier {
+                    barrier_type,
+                    barrier_level: pos_or_panic!(level),
+                    rebate: None,
+                },
+                OptionStyle::Call,
+            );
+            let fd = price(&barrier);
+            let closed_form = barrier_black_scholes(&barrier).unwrap();
+            assert!(
+                (fd - closed_form).abs() < dec!(0.02),
+                "{barrier_type:?}: {fd} vs {closed_form}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_finite_difference_up_barrier_put_parity() {
+        let make = |barrier_type| {
+            option(
+                OptionType::Barrier {
+                    barrier_type,
+                    barrier_level: pos_or_panic!(120.0),
+                    rebate: None,
+                },
+                OptionStyle::Put,
+            )
+        };
+        let vanilla = price(&option(OptionType::European, OptionStyle::Put));
+        let knock_out = price(&make(BarrierType::UpAndOut));
+        let knock_in = price(&make(BarrierType::UpAndIn));
+        assert!(knock_out > Decimal::ZERO && knock_out < vanilla);
+        assert!(knock_in > Decimal::ZERO && knock_in < knock_out);
+        assert!((knock_out + knock_in - vanilla).abs() < dec!(0.005));
+    }
+
+    #[test]
+    fn test_finite_difference_in_out_parity_and_rebate() {
+        let make = |barrier_type, rebate| {
+            option(
+                OptionType::Barrier {
+                    barrier_type,
+                    barrier_level: pos_or_panic!(90.0),
+                    rebate,
+                },
+                OptionStyle::Call,
+            )
+        };
+        let vanilla = price(&option(OptionType::European, OptionStyle::Call));
+        let knock_in = price(&make(BarrierType::DownAndIn, None));
+        let knock_out = price(&make(BarrierType::DownAndOut, None));
+        assert!((knock_in + knock_out - vanilla).abs() < dec!(0.005));
+
+        let with_rebate = price(&make(BarrierType::DownAndOut, Some(pos_or_panic!(3.0))));
+        assert!(with_rebate > knock_out);
+        let breached = option(
+            OptionType::Barrier {
+                barrier_type: BarrierType::DownAndOut,
+                barrier_level: pos_or_panic!(110.0),
+                rebate: Some(pos_or_panic!(3.0)),
+            },
+            OptionStyle::Call,
+        );
+        assert_eq!(price(&breached), dec!(3));
+    }
+
+    #[test]
+    fn test_finite_difference_binary_with_rannacher_smoothing() {
+        let binary = option(
+            OptionType::Binary {
+                binary_type: BinaryType::CashOrNothing,
+            },
+            OptionStyle::Call,
+        );
+        let reference = binary_black_scholes(&binary).unwrap();
+        let smoothed =
+            finite_difference_price(&binary, &FiniteDifferenceConfig::default()).unwrap();
+        assert!(
+            (smoothed.price - reference).abs() < dec!(0.002),
+            "{smoothed:?} vs {reference}"
+        );
+        assert!(smoothed.gamma.abs() < dec!(0.01));
+    }
+
+    #[test]
+    fn test_finite_difference_discrete_dividend() {
+        let dividend = DiscreteDividend::in_days(pos_or_panic!(164.25), pos_or_panic!(8.0));
+        let config = FiniteDifferenceConfig::default().with_dividend(dividend);
+        let mut call = option(OptionType::European, OptionStyle::Call);
+        call.dividend_yield = Positive::ZERO;
+        let plain = price(&call);
+        let paying = finite_difference_price(&call, &config).unwrap().price;
+        assert!(paying < plain);
+
+        // A large dividend just before expiry makes early exercise of a call
+        // optimal, so the American call is worth more than the European one.
+        let mut american = call.clone();
+        american.option_type = OptionType::American;
+        let early = finite_difference_price(&american, &config).unwrap().price;
+        assert!(early > paying + dec!(0.5), "{early} vs {paying}");
+    }
+
+    #[test]
+    fn test_finite_difference_short_side_and_validation() {
+        let mut short = option(OptionType::European, OptionStyle::Call);
+        short.side = Side::Short;
+        let long = price(&option(OptionType::European, OptionStyle::Call));
+        assert_eq!(price(&short), -long);
+
+        let asian = option(
+            OptionType::Asian {
+                averaging_type: crate::model::types::AsianAveragingType::Arithmetic,
+            },
+            OptionStyle::Call,
+        );
+        assert!(matches!(
+            finite_difference_price(&asian, &FiniteDifferenceConfig::default()),
+            Err(PricingError::UnsupportedOptionType { .. })
+        ));
+        let bad = FiniteDifferenceConfig::default().with_psor_omega(dec!(2.5));
+        assert!(matches!(
+            finite_difference_price(&short, &bad),
+            Err(PricingError::InvalidEngine { .. })
+        ));
+    }
+}
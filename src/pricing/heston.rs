@@ -0,0 +1,720 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Heston Semi-Analytic Pricing
+//!
+//! Prices European options under the Heston (1993) stochastic volatility
+//! model
+//!
+//! ```text
+//! dS = (r - q) S dt + √v S dW₁
+//! dv = κ (θ - v) dt + ξ √v dW₂,      d⟨W₁, W₂⟩ = ρ dt
+//! ```
+//!
+//! with the single-integral representation of Lewis (2001):
+//!
+//! ```text
+//! C = S e^{-qT} - √(S K) e^{-(r+q)T/2} / π ∫₀^∞ Re[e^{iuk} φ(u - i/2)] / (u² + ¼) du
+//! ```
+//!
+//! where `k = ln(S/K) + (r - q)T` and `φ` is the characteristic function of
+//! `ln(S_T / F_T)`. The characteristic function uses the "little Heston trap"
+//! formulation of Albrecher et al. (2007), which stays on the principal
+//! branch of the complex logarithm for long maturities. Puts follow from
+//! put–call parity.
+//!
+//! The integral is evaluated with composite 16-point Gauss–Legendre
+//! quadrature on panels sized from the average variance over the option's
+//! life, until the tail contribution becomes negligible.
+
+use crate::ExpirationDate;
+use crate::Options;
+use crate::curves::{Curve, Point2D};
+use crate::error::{PricingError, VolatilityError};
+use crate::model::decimal::finite_decimal;
+use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::volatility::{
+    IvSolverConfig, VolatilityModel, VolatilitySmile, solve_implied_volatility,
+};
+use num_traits::ToPrimitive;
+use positive::Positive;
+use positive::constants::DAYS_IN_A_YEAR;
+use rust_decimal::Decimal;
+use std::collections::BTreeSet;
+use std::f64::consts::PI;
+use std::ops::{Add, Div, Mul, Sub};
+use tracing::{debug, instrument};
+
+/// Number of Gauss–Legendre nodes per integration panel.
+const GAUSS_NODES: usize = 16;
+
+/// Maximum number of integration panels before the integral is declared
+/// divergent.
+const MAX_PANELS: usize = 4_000;
+
+/// Number of consecutive negligible panels that terminate the integration.
+const TAIL_PANELS: usize = 3;
+
+/// Absolute tolerance on a panel contribution to the Lewis integral.
+const TAIL_TOLERANCE: f64 = 1e-14;
+
+/// Parameters of the Heston stochastic volatility model.
+///
+/// Variances (`v0`, `theta`) are annualised variances, not volatilities:
+/// a 20 % volatility corresponds to `0.04`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HestonParams {
+    /// Initial variance `v₀`.
+    pub v0: Positive,
+    /// Mean-reversion speed `κ` of the variance.
+    pub kappa: Positive,
+    /// Long-run variance `θ`.
+    pub theta: Positive,
+    /// Volatility of variance `ξ`.
+    pub xi: Positive,
+    /// Correlation `ρ` between the spot and variance shocks.
+    pub rho: Decimal,
+}
+
+impl HestonParams {
+    /// Creates a validated parameter set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidEngine` when `v0`, `kappa`, `theta` or
+    /// `xi` is zero, or when `rho` lies outside `[-1, 1]`.
+    pub fn new(
+        v0: Positive,
+        kappa: Positive,
+        theta: Positive,
+        xi: Positive,
+        rho: Decimal,
+    ) -> Result<Self, PricingError> {
+        let params = Self {
+            v0,
+            kappa,
+            theta,
+            xi,
+            rho,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Returns `true` when the Feller condition `2κθ ≥ ξ²` holds, i.e. the
+    /// variance process never reaches zero.
+    #[must_use]
+    pub fn feller_satisfied(&self) -> bool {
+        2.0 * self.kappa.to_f64() * self.theta.to_f64() >= self.xi.to_f64().powi(2)
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        for (name, value) in [
+            ("v0", self.v0),
+            ("kappa", self.kappa),
+            ("theta", self.theta),
+            ("xi", self.xi),
+        ] {
+            if value == Positive::ZERO {
+                return Err(PricingError::invalid_engine(&format!(
+                    "Heston parameter {name} must be strictly positive"
+                )));
+            }
+        }
+        if self.rho < Decimal::NEGATIVE_ONE || self.rho > Decimal::ONE {
+            return Err(PricingError::invalid_engine(&format!(
+                "Heston correlation rho {} must lie in [-1, 1]",
+                self.rho
+            )));
+        }
+        Ok(())
+    }
+
+    /// Average variance `(1/T) ∫ E[v_t] dt` over `[0, T]`.
+    fn average_variance(&self, expiry: f64) -> f64 {
+        let v0 = self.v0.to_f64();
+        let theta = self.theta.to_f64();
+        let kt = self.kappa.to_f64() * expiry;
+        if kt < 1e-8 {
+            return v0;
+        }
+        theta + (v0 - theta) * (1.0 - (-kt).exp()) / kt
+    }
+}
+
+/// Prices a European option under the Heston model.
+///
+/// The option's `implied_volatility` is ignored: the variance dynamics are
+/// taken from `params`. The result is per unit of underlying and carries the
+/// sign of `option.side`, like [`crate::pricing::black_scholes`]. A yield
+/// curve or cash dividends are handled by pricing at the zero rate to expiry
+/// on the escrowed spot `S - PV(dividends)`.
+///
+/// # Errors
+///
+/// * `PricingError::UnsupportedOptionType` for non-European options.
+/// * `PricingError::InvalidEngine` when `params` is invalid.
+/// * `PricingError::MethodError` when the dividends are worth more than the spot.
+/// * `PricingError::MethodError` when the Lewis integral does not converge,
+///   and `PricingError::NonFinite` when the price is not finite.
+/// * `PricingError::ExpirationDate` when the expiry cannot be converted to a
+///   year fraction.
+#[instrument(skip(option, params), fields(
+    strike = %option.strike_price,
+    style = ?option.option_style,
+))]
+pub fn heston_price(option: &Options, params: &HestonParams) -> Result<Decimal, PricingError> {
+    if !matches!(option.option_type, OptionType::European) {
+        return Err(PricingError::unsupported_option_type(
+            &option.option_type.to_string(),
+            "Heston",
+        ));
+    }
+    params.validate()?;
+    let escrowed = option.escrowed()?;
+    let option = escrowed.as_ref().unwrap_or(option);
+    let market = HestonMarket {
+        spot: option.underlying_price.to_f64(),
+        expiry: option.time_to_expiration()?.to_f64(),
+        rate: option.risk_free_rate.to_f64().unwrap_or(0.0),
+        dividend: option.dividend_yield.to_f64(),
+    };
+    let price = market.price(option.strike_price.to_f64(), &option.option_style, params)?;
+    debug!(price, "Heston price");
+    let signed = match option.side {
+        Side::Long => price,
+        Side::Short => -price,
+    };
+    finite_decimal(signed).ok_or_else(|| PricingError::non_finite("pricing::heston::price", signed))
+}
+
+/// Market inputs of the Heston kernel in `f64`.
+#[derive(Debug, Clone, Copy)]
+struct HestonMarket {
+    spot: f64,
+    expiry: f64,
+    rate: f64,
+    dividend: f64,
+}
+
+impl HestonMarket {
+    /// Price of a long option with the given strike.
+    fn price(
+        &self,
+        strike: f64,
+        style: &OptionStyle,
+        params: &HestonParams,
+    ) -> Result<f64, PricingError> {
+        let spot_leg = self.spot * (-self.dividend * self.expiry).exp();
+        let strike_leg = strike * (-self.rate * self.expiry).exp();
+        if self.expiry <= 0.0 {
+            let intrinsic = match style {
+                OptionStyle::Call => self.spot - strike,
+                OptionStyle::Put => strike - self.spot,
+            };
+            return Ok(intrinsic.max(0.0));
+        }
+        let call = spot_leg
+            - (self.spot * strike).sqrt()
+                * (-(self.rate + self.dividend) * self.expiry / 2.0).exp()
+                / PI
+                * self.lewis_integral(strike, params)?;
+        let price = match style {
+            OptionStyle::Call => call,
+            OptionStyle::Put => call - spot_leg + strike_leg,
+        };
+        // Quadrature noise can push deep out-of-the-money prices a hair
+        // below zero.
+        Ok(price.max(0.0))
+    }
+
+    fn lewis_integral(&self, strike: f64, params: &HestonParams) -> Result<f64, PricingError> {
+        let k = (self.spot / strike).ln() + (self.rate - self.dividend) * self.expiry;
+        let cf = CharacteristicFunction::new(params, self.expiry);
+        let integrand = |u: f64| {
+            let shifted = Complex::new(u, -0.5);
+            let phase = Complex::new(0.0, u * k).exp();
+            (phase * cf.eval(shifted)).re / (u * u + 0.25)
+        };
+
+        // Panels start at unit width, because the poles of 1 / (u² + ¼) at
+        // ±i/2 limit the accuracy near the origin, and then grow with their
+        // distance from it, capped by the decay scale of the characteristic
+        // function and by the oscillation period of e^{iuk}.
+        let total_variance = params.average_variance(self.expiry) * self.expiry;
+        let decay_scale = 0.5 / total_variance.max(1e-12).sqrt();
+        let oscillation_scale = if k.abs() > 0.0 {
+            8.0 / k.abs()
+        } else {
+            f64::INFINITY
+        };
+        let (nodes, weights) = gauss_legendre(GAUSS_NODES);
+        let mut integral = 0.0;
+        let mut quiet = 0;
+        let mut left = 0.0_f64;
+        for _ in 0..MAX_PANELS {
+            let width = (left / 2.0)
+                .min(decay_scale)
+                .min(oscillation_scale)
+                .max(1.0);
+            let half = width / 2.0;
+            let centre = left + half;
+            let contribution: f64 = nodes
+                .iter()
+                .zip(&weights)
+                .map(|(&x, &w)| w * integrand(centre + half * x))
+                .sum::<f64>()
+                * half;
+            if !contribution.is_finite() {
+                return Err(PricingError::non_finite(
+                    "pricing::heston::integrand",
+                    contribution,
+                ));
+            }
+            integral += contribution;
+            left += width;
+            if contribution.abs() < TAIL_TOLERANCE {
+                quiet += 1;
+                if quiet == TAIL_PANELS {
+                    return Ok(integral);
+                }
+            } else {
+                quiet = 0;
+            }
+        }
+        Err(PricingError::method_error(
+            "Heston",
+            &format!("Lewis integral did not converge within {MAX_PANELS} panels"),
+        ))
+    }
+}
+
+/// Characteristic function of `ln(S_T / F_T)` under Heston.
+struct CharacteristicFunction {
+    v0: f64,
+    kappa: f64,
+    theta: f64,
+    xi: f64,
+    rho: f64,
+    expiry: f64,
+}
+
+impl CharacteristicFunction {
+    fn new(params: &HestonParams, expiry: f64) -> Self {
+        Self {
+            v0: params.v0.to_f64(),
+            kappa: params.kappa.to_f64(),
+            theta: params.theta.to_f64(),
+            xi: params.xi.to_f64(),
+            rho: params.rho.to_f64().unwrap_or(0.0),
+            expiry,
+        }
+    }
+
+    fn eval(&self, u: Complex) -> Complex {
+        let i = Complex::new(0.0, 1.0);
+        let xi2 = self.xi * self.xi;
+        let iu = i * u;
+        let beta = Complex::real(self.kappa) - iu * (self.rho * self.xi);
+        let d = (beta * beta + (iu + u * u) * xi2).sqrt();
+        let minus = beta - d;
+        let g = minus / (beta + d);
+        let decay = (d * -self.expiry).exp();
+        let one = Complex::real(1.0);
+        let log_term = ((one - g * decay) / (one - g)).ln();
+        let c = (minus * self.expiry - log_term * 2.0) * (self.kappa * self.theta / xi2);
+        let dd = minus / xi2 * ((one - decay) / (one - g * decay));
+        (c + dd * self.v0).exp()
+    }
+}
+
+/// Minimal complex arithmetic for the characteristic function.
+#[derive(Debug, Clone, Copy)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    fn real(re: f64) -> Self {
+        Self { re, im: 0.0 }
+    }
+
+    fn exp(self) -> Self {
+        let scale = self.re.exp();
+        Self::new(scale * self.im.cos(), scale * self.im.sin())
+    }
+
+    /// Principal branch of the logarithm.
+    fn ln(self) -> Self {
+        Self::new(self.re.hypot(self.im).ln(), self.im.atan2(self.re))
+    }
+
+    /// Principal square root (non-negative real part).
+    fn sqrt(self) -> Self {
+        let modulus = self.re.hypot(self.im);
+        let re = ((modulus + self.re) / 2.0).sqrt();
+        let im = ((modulus - self.re) / 2.0).sqrt();
+        Self::new(re, if self.im < 0.0 { -im } else { im })
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.re * rhs, self.im * rhs)
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let denominator = rhs.re * rhs.re + rhs.im * rhs.im;
+        Self::new(
+            (self.re * rhs.re + self.im * rhs.im) / denominator,
+            (self.im * rhs.re - self.re * rhs.im) / denominator,
+        )
+    }
+}
+
+impl Div<f64> for Complex {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Self::new(self.re / rhs, self.im / rhs)
+    }
+}
+
+/// Nodes and weights of the `n`-point Gauss–Legendre rule on `[-1, 1]`.
+fn gauss_legendre(n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut nodes = Vec::with_capacity(n);
+    let mut weights = Vec::with_capacity(n);
+    for i in 0..n {
+        // Chebyshev initial guess refined by Newton on P_n.
+        let mut x = (PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        let mut derivative = 1.0;
+        for _ in 0..100 {
+            let (mut p0, mut p1) = (1.0, x);
+            for k in 2..=n {
+                let kf = k as f64;
+                let p2 = ((2.0 * kf - 1.0) * x * p1 - (kf - 1.0) * p0) / kf;
+                p0 = p1;
+                p1 = p2;
+            }
+            derivative = n as f64 * (x * p1 - p0) / (x * x - 1.0);
+            let step = p1 / derivative;
+            x -= step;
+            if step.abs() < 1e-15 {
+                break;
+            }
+        }
+        nodes.push(x);
+        weights.push(2.0 / ((1.0 - x * x) * derivative * derivative));
+    }
+    (nodes, weights)
+}
+
+/// Implied volatility smile generated by a Heston parameter set.
+///
+/// Every strike is priced with the Lewis integral and inverted to a
+/// Black–Scholes volatility, using the out-of-the-money side of the forward
+/// for numerical stability.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HestonSmile {
+    /// Spot price of the underlying.
+    pub spot: Positive,
+    /// Time to expiry in years.
+    pub time_to_expiry: Positive,
+    /// Continuously compounded risk-free rate.
+    pub risk_free_rate: Decimal,
+    /// Continuous dividend yield.
+    pub dividend_yield: Positive,
+    /// Heston model parameters.
+    pub params: HestonParams,
+    /// Strikes at which [`VolatilitySmile::smile`] samples the model.
+    pub strikes: Vec<Positive>,
+}
+
+impl HestonSmile {
+    /// Creates a smile for the given market and strikes.
+    #[must_use]
+    pub fn new(
+        spot: Positive,
+        time_to_expiry: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        params: HestonParams,
+        strikes: Vec<Positive>,
+    ) -> Self {
+        Self {
+            spot,
+            time_to_expiry,
+            risk_free_rate,
+            dividend_yield,
+            params,
+            strikes,
+        }
+    }
+}
+
+impl VolatilityModel for HestonSmile {
+    fn volatility_at(&self, strike: Positive) -> Result<Positive, VolatilityError> {
+        let market = HestonMarket {
+            spot: self.spot.to_f64(),
+            expiry: self.time_to_expiry.to_f64(),
+            rate: self.risk_free_rate.to_f64().unwrap_or(0.0),
+            dividend: self.dividend_yield.to_f64(),
+        };
+        let forward = market.spot * ((market.rate - market.dividend) * market.expiry).exp();
+        let style = if strike.to_f64() < forward {
+            OptionStyle::Put
+        } else {
+            OptionStyle::Call
+        };
+        let price = market
+            .price(strike.to_f64(), &style, &self.params)
+            .map_err(|e| VolatilityError::NumericalFailure {
+                reason: format!("Heston price at strike {strike}: {e}"),
+            })?;
+        let price = Positive::new(price)?;
+        let option = Options::new(
+            OptionType::European,
+            Side::Long,
+            "HESTON".to_string(),
+            strike,
+            ExpirationDate::Days(self.time_to_expiry * DAYS_IN_A_YEAR),
+            self.params.v0.sqrt(),
+            Positive::ONE,
+            self.spot,
+            self.risk_free_rate,
+            style,
+            self.dividend_yield,
+            None,
+        );
+        Ok(solve_implied_volatility(&option, price, &IvSolverConfig::default())?.volatility)
+    }
+}
+
+impl VolatilitySmile for HestonSmile {
+    /// Samples the Heston implied volatility at every strike of the smile.
+    ///
+    /// Strikes whose price cannot be inverted (for example far wings priced
+    /// at their no-arbitrage bound) are skipped.
+    fn smile(&self) -> Curve {
+        let points: BTreeSet<Point2D> = self
+            .strikes
+            .iter()
+            .filter_map(|&strike| match self.volatility_at(strike) {
+                Ok(vol) => Some(Point2D::new(strike.to_dec(), vol.to_dec())),
+                Err(e) => {
+                    debug!("Heston smile skipped strike {}: {}", strike, e);
+                    None
+                }
+            })
+            .collect();
+        Curve::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::utils::create_sample_option_with_days;
+    use crate::pricing::{DiscreteDividend, DividendSchedule, YieldCurve, black_scholes};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn option(style: OptionStyle, strike: f64, days: f64, rate: Decimal) -> Options {
+        Options {
+            risk_free_rate: rate,
+            dividend_yield: Positive::ZERO,
+            ..create_sample_option_with_days(
+                style,
+                Side::Long,
+                Positive::HUNDRED,
+                Positive::ONE,
+                pos_or_panic!(strike),
+                pos_or_panic!(0.2),
+                pos_or_panic!(days),
+            )
+        }
+    }
+
+    fn fang_oosterlee() -> HestonParams {
+        HestonParams::new(
+            pos_or_panic!(0.0175),
+            pos_or_panic!(1.5768),
+            pos_or_panic!(0.0398),
+            pos_or_panic!(0.5751),
+            dec!(-0.5711),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_gauss_legendre_integrates_polynomials_exactly() {
+        let (nodes, weights) = gauss_legendre(GAUSS_NODES);
+        let integral: f64 = nodes
+            .iter()
+            .zip(&weights)
+            .map(|(x, w)| w * x.powi(30))
+            .sum();
+        assert!((integral - 2.0 / 31.0).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_heston_price_matches_reference_value() {
+        // Fang & Oosterlee (2008), COS method reference: 5.785155450.
+        let call = option(OptionStyle::Call, 100.0, 365.0, Decimal::ZERO);
+        let price = heston_price(&call, &fang_oosterlee()).unwrap();
+        assert!(
+            (price - dec!(5.785155450)).abs() < dec!(0.000001),
+            "{price}"
+        );
+    }
+
+    #[test]
+    fn test_heston_put_call_parity() {
+        let params = fang_oosterlee();
+        for strike in [80.0, 100.0, 125.0] {
+            let call = heston_price(
+                &option(OptionStyle::Call, strike, 180.0, dec!(0.05)),
+                &params,
+            )
+            .unwrap();
+            let put = heston_price(
+                &option(OptionStyle::Put, strike, 180.0, dec!(0.05)),
+                &params,
+            )
+            .unwrap();
+            let forward_value = 100.0 - strike * (-0.05f64 * 180.0 / 365.0).exp();
+            let parity = (call - put).to_f64().unwrap() - forward_value;
+            assert!(parity.abs() < 1e-8, "strike {strike}: {parity}");
+        }
+    }
+
+    #[test]
+    fn test_heston_collapses_to_black_scholes_without_vol_of_vol() {
+        let params = HestonParams::new(
+            pos_or_panic!(0.04),
+            Positive::TWO,
+            pos_or_panic!(0.04),
+            pos_or_panic!(0.001),
+            Decimal::ZERO,
+        )
+        .unwrap();
+        for (style, strike) in [(OptionStyle::Call, 90.0), (OptionStyle::Put, 110.0)] {
+            let contract = option(style, strike, 90.0, dec!(0.03));
+            let heston = heston_price(&contract, &params).unwrap();
+            let bs = black_scholes(&contract).unwrap();
+            assert!((heston - bs).abs() < dec!(0.00001), "{heston} vs {bs}");
+        }
+    }
+
+    #[test]
+    fn test_heston_short_side_and_validation() {
+        let mut contract = option(OptionStyle::Call, 100.0, 365.0, Decimal::ZERO);
+        let long = heston_price(&contract, &fang_oosterlee()).unwrap();
+        contract.side = Side::Short;
+        assert_eq!(heston_price(&contract, &fang_oosterlee()).unwrap(), -long);
+
+        contract.option_type = OptionType::American;
+        assert!(matches!(
+            heston_price(&contract, &fang_oosterlee()),
+            Err(PricingError::UnsupportedOptionType { .. })
+        ));
+        assert!(
+            HestonParams::new(
+                pos_or_panic!(0.04),
+                Positive::ONE,
+                pos_or_panic!(0.04),
+                pos_or_panic!(0.3),
+                dec!(-1.5),
+            )
+            .is_err()
+        );
+        assert!(fang_oosterlee().feller_satisfied() == (2.0 * 1.5768 * 0.0398 >= 0.5751 * 0.5751));
+    }
+
+    #[test]
+    fn test_heston_prices_curve_and_cash_dividends_on_escrowed_spot() {
+        let params = fang_oosterlee();
+        let contract = option(OptionStyle::Put, 100.0, 180.0, dec!(0.05))
+            .with_yield_curve(YieldCurve::flat(dec!(0.02)))
+            .with_dividends(DividendSchedule::new(vec![DiscreteDividend::in_days(
+                pos_or_panic!(60.0),
+                Positive::TWO,
+            )]));
+        let restated = Options {
+            underlying_price: Positive::new_decimal(
+                dec!(100) - contract.dividends_present_value().unwrap(),
+            )
+            .unwrap(),
+            ..option(OptionStyle::Put, 100.0, 180.0, dec!(0.02))
+        };
+        let price = heston_price(&contract, &params).unwrap();
+        assert!((price - heston_price(&restated, &params).unwrap()).abs() < dec!(1e-9));
+        assert!(
+            price
+                > heston_price(&option(OptionStyle::Put, 100.0, 180.0, dec!(0.05)), &params)
+                    .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_heston_smile_is_skewed_by_negative_correlation() {
+        let strikes: Vec<Positive> = [80.0, 90.0, 100.0, 110.0, 120.0]
+            .iter()
+            .map(|&k| pos_or_panic!(k))
+            .collect();
+        let smile = HestonSmile::new(
+            Positive::HUNDRED,
+            Positive::ONE,
+            Decimal::ZERO,
+            Positive::ZERO,
+            fang_oosterlee(),
+            strikes,
+        );
+        let curve = smile.smile();
+        assert_eq!(curve.points.len(), 5);
+        let vols: Vec<Decimal> = curve.points.iter().map(|p| p.y).collect();
+        assert!(vols.windows(2).all(|w| w[0] > w[1]), "{vols:?}");
+
+        // The smile reprices the model: BS at the smile vol equals Heston.
+        let atm = smile.volatility_at(Positive::HUNDRED).unwrap();
+        let mut contract = option(OptionStyle::Call, 100.0, 365.0, Decimal::ZERO);
+        contract.implied_volatility = atm;
+        let bs = black_scholes(&contract).unwrap();
+        assert!((bs - dec!(5.785155450)).abs() < dec!(0.0001), "{bs}");
+    }
+}
@@ -80,8 +80,7 @@ fn floating_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
@@ -204,8 +203,7 @@ fn fixed_strike_lookback(option: &Options) -> Result<Decimal, PricingError> {
     let q = option.dividend_yield.to_dec();
     let sigma = option.implied_volatility;
     let t = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?;
 
     if t == Positive::ZERO {
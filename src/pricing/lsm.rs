@@ -0,0 +1,774 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Longstaff–Schwartz Least-Squares Monte Carlo
+//!
+//! Prices American and Bermudan options on simulated price paths by
+//! backward induction: at every exercise date the discounted future cash
+//! flows of the in-the-money paths are regressed on a polynomial basis of the
+//! current spot, and a path is exercised when its intrinsic value beats the
+//! fitted continuation value.
+//!
+//! Paths can come from any source. [`longstaff_schwartz_simulator`] reads them
+//! from a [`Simulator`], so early exercise is priced under the same
+//! `WalkType` models (Heston, GARCH, jump diffusion, ...) used for scenario
+//! analysis; the paths are expected to be risk-neutral.
+//!
+//! The in-sample estimator is slightly low-biased because the exercise rule
+//! is fitted on the same paths it is evaluated on.
+
+use crate::Options;
+use crate::error::PricingError;
+use crate::model::decimal::{d_add, d_sub, finite_decimal};
+use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::pricing::monte_carlo::MonteCarloResult;
+use crate::simulation::simulator::Simulator;
+use num_traits::ToPrimitive;
+use positive::Positive;
+use positive::constants::DAYS_IN_A_YEAR;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::num::NonZeroUsize;
+use tracing::{debug, instrument};
+
+/// Relative tolerance used to match the first simulated price with the
+/// option's underlying price.
+const SPOT_TOLERANCE: f64 = 1e-6;
+
+/// Polynomial family used to regress continuation values.
+///
+/// Every family is evaluated on the moneyness `x = S / K`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum LsmBasis {
+    /// `1, x, x², …`
+    #[default]
+    Monomial,
+    /// Weighted Laguerre polynomials `e^{-x/2} L_n(x)`, the basis of the
+    /// original Longstaff–Schwartz paper (the constant term is unweighted).
+    Laguerre,
+    /// Probabilists' Hermite polynomials `He_n(x - 1)`, centred at the money.
+    Hermite,
+}
+
+impl LsmBasis {
+    /// Writes the `degree + 1` basis functions at `x` into `out`.
+    fn evaluate(self, x: f64, out: &mut [f64]) {
+        let mut iter = out.iter_mut();
+        let Some(first) = iter.next() else {
+            return;
+        };
+        *first = 1.0;
+        match self {
+            LsmBasis::Monomial => {
+                let mut power = 1.0;
+                for slot in iter {
+                    power *= x;
+                    *slot = power;
+                }
+            }
+            LsmBasis::Laguerre => {
+                // L_0 = 1, L_1 = 1 - x, (n + 1) L_{n+1} = (2n + 1 - x) L_n - n L_{n-1}
+                let weight = (-0.5 * x).exp();
+                let (mut previous, mut current) = (1.0, 1.0 - x);
+                for (n, slot) in iter.enumerate() {
+                    *slot = weight * current;
+                    let n = (n + 1) as f64;
+                    let next = ((2.0 * n + 1.0 - x) * current - n * previous) / (n + 1.0);
+                    previous = current;
+                    current = next;
+                }
+            }
+            LsmBasis::Hermite => {
+                // He_0 = 1, He_1 = y, He_{n+1} = y He_n - n He_{n-1}
+                let y = x - 1.0;
+                let (mut previous, mut current) = (1.0, y);
+                for (n, slot) in iter.enumerate() {
+                    *slot = current;
+                    let next = y * current - (n + 1) as f64 * previous;
+                    previous = current;
+                    current = next;
+                }
+            }
+        }
+    }
+}
+
+/// Configuration of the Longstaff–Schwartz regression.
+///
+/// The defaults regress on a cubic monomial basis over in-the-money paths
+/// only and report a 95 % confidence interval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LsmConfig {
+    /// Polynomial family of the regression.
+    pub basis: LsmBasis,
+    /// Highest polynomial degree; the regression has `degree + 1` terms.
+    pub degree: NonZeroUsize,
+    /// Regress only on paths that are in the money at the exercise date.
+    pub in_the_money_only: bool,
+    /// Two-sided confidence level of the reported interval, in `(0, 1)`.
+    pub confidence_level: Decimal,
+}
+
+impl Default for LsmConfig {
+    fn default() -> Self {
+        Self {
+            basis: LsmBasis::Monomial,
+            degree: NonZeroUsize::MIN.saturating_add(2),
+            in_the_money_only: true,
+            confidence_level: dec!(0.95),
+        }
+    }
+}
+
+impl LsmConfig {
+    /// Sets the polynomial family.
+    #[must_use]
+    pub fn with_basis(mut self, basis: LsmBasis) -> Self {
+        self.basis = basis;
+        self
+    }
+
+    /// Sets the highest polynomial degree.
+    #[must_use]
+    pub fn with_degree(mut self, degree: NonZeroUsize) -> Self {
+        self.degree = degree;
+        self
+    }
+
+    /// Restricts (or not) the regression to in-the-money paths.
+    #[must_use]
+    pub fn with_in_the_money_only(mut self, in_the_money_only: bool) -> Self {
+        self.in_the_money_only = in_the_money_only;
+        self
+    }
+
+    /// Sets the two-sided confidence level of the reported interval.
+    #[must_use]
+    pub fn with_confidence_level(mut self, confidence_level: Decimal) -> Self {
+        self.confidence_level = confidence_level;
+        self
+    }
+}
+
+/// Prices a European, American or Bermudan option by Longstaff–Schwartz
+/// regression on explicit price paths.
+///
+/// `times` holds the year fractions from today of every observation, starting
+/// at `0` and ending at the option's expiry; `paths[i][k]` is the price of
+/// path `i` at `times[k]`. American options may be exercised at every
+/// observation, Bermudan options at the observations within half a step of
+/// one of their `exercise_dates` (year fractions, as in the binomial
+/// lattice) and at expiry, European options at expiry only.
+///
+/// Cash flows are discounted on the option's yield curve, or at
+/// `risk_free_rate` without one; the paths are assumed to be risk-neutral,
+/// so the dividend yield only enters through them. Cash dividends must be
+/// simulated in the paths too and are rejected on the option. The
+/// result is per unit and carries the side sign like
+/// [`crate::pricing::black_scholes`]; the standard error is computed from the
+/// per-path discounted cash flows.
+///
+/// # Errors
+///
+/// * `PricingError::UnsupportedOptionType` for option types other than
+///   European, American and Bermuda.
+/// * `PricingError::MethodError` when the option carries cash dividends,
+///   when the grid or the paths are malformed
+///   (fewer than two observations, non-increasing times, a grid that does
+///   not end at expiry, ragged paths, or a first price that differs from
+///   the option's underlying price).
+/// * `PricingError::InvalidEngine` when the confidence level lies outside
+///   `(0, 1)`, and `PricingError::NonFinite` when a statistic is not finite.
+#[instrument(skip(option, times, paths, config), fields(
+    paths = paths.len(),
+    observations = times.len(),
+    option_type = %option.option_type,
+    style = ?option.option_style,
+))]
+pub fn longstaff_schwartz(
+    option: &Options,
+    times: &[Positive],
+    paths: &[Vec<Positive>],
+    config: &LsmConfig,
+) -> Result<MonteCarloResult, PricingError> {
+    let times: Vec<f64> = times.iter().map(|t| t.to_f64()).collect();
+    let paths: Vec<Vec<f64>> = paths
+        .iter()
+        .map(|path| path.iter().map(|s| s.to_f64()).collect())
+        .collect();
+    price(option, &times, &paths, config)
+}
+
+/// Prices a European, American or Bermudan option by Longstaff–Schwartz
+/// regression on the random walks of `simulator`.
+///
+/// Every walk must have the same number of steps. The observation times are
+/// the elapsed days between the first step and each later step (read from
+/// the steps' expiration dates) converted to years; observations past the
+/// option's expiry are ignored. See [`longstaff_schwartz`] for the exercise
+/// rules and the returned statistics.
+///
+/// # Errors
+///
+/// Returns `PricingError::SimulationError` when a step cannot be read as a
+/// positive price or a day count, `PricingError::MethodError` when the
+/// simulator is empty or its walks are ragged, and every error of
+/// [`longstaff_schwartz`].
+pub fn longstaff_schwartz_simulator(
+    option: &Options,
+    simulator: &Simulator<Positive, Positive>,
+    config: &LsmConfig,
+) -> Result<MonteCarloResult, PricingError> {
+    let walks = simulator.get_random_walks();
+    let first = walks.first().ok_or_else(|| {
+        PricingError::method_error("longstaff_schwartz", "simulator has no random walks")
+    })?;
+    let days = first
+        .get_steps()
+        .iter()
+        .map(|step| step.get_x_step().days_left().map(|d| d.to_f64()))
+        .collect::<Result<Vec<f64>, _>>()
+        .map_err(|e| PricingError::simulation_error(&e.to_string()))?;
+    let start = days.first().copied().unwrap_or(0.0);
+    let expiry = option.time_to_expiration()?.to_f64();
+    let step_tolerance = 1e-9;
+    let times: Vec<f64> = days
+        .iter()
+        .map(|d| (start - d) / DAYS_IN_A_YEAR.to_f64())
+        .take_while(|&t| t <= expiry + step_tolerance)
+        .collect();
+
+    let paths = walks
+        .iter()
+        .map(|walk| {
+            walk.get_steps()
+                .iter()
+                .take(times.len())
+                .map(|step| step.get_positive_value().map(|s| s.to_f64()))
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|e| PricingError::simulation_error(&e.to_string()))
+        })
+        .collect::<Result<Vec<Vec<f64>>, PricingError>>()?;
+    price(option, &times, &paths, config)
+}
+
+fn price(
+    option: &Options,
+    times: &[f64],
+    paths: &[Vec<f64>],
+    config: &LsmConfig,
+) -> Result<MonteCarloResult, PricingError> {
+    if !matches!(
+        option.option_type,
+        OptionType::European | OptionType::American | OptionType::Bermuda { .. }
+    ) {
+        return Err(PricingError::unsupported_option_type(
+            &option.option_type.to_string(),
+            "Longstaff-Schwartz",
+        ));
+    }
+    if !option.dividends.is_empty() {
+        return Err(PricingError::method_error(
+            "longstaff_schwartz",
+            "cash dividends must be simulated in the paths, not set on the option",
+        ));
+    }
+    if config.confidence_level <= Decimal::ZERO || config.confidence_level >= Decimal::ONE {
+        return Err(PricingError::invalid_engine(&format!(
+            "confidence level must lie in (0, 1), got {}",
+            config.confidence_level
+        )));
+    }
+    let expiry = option.time_to_expiration()?.to_f64();
+    let grid = validate_grid(times, expiry)?;
+    validate_paths(option, paths, times.len())?;
+
+    let strike = option.strike_price.to_f64();
+    let curve = option.discount_curve();
+    let discount: Vec<f64> = grid
+        .iter()
+        .map(|&t| {
+            curve
+                .discount_factor(Positive::new(t).unwrap_or(Positive::ZERO))
+                .to_f64()
+                .unwrap_or(0.0)
+        })
+        .collect();
+    let intrinsic = |spot: f64| match option.option_style {
+        OptionStyle::Call => (spot - strike).max(0.0),
+        OptionStyle::Put => (strike - spot).max(0.0),
+    };
+    let exercisable = exercise_flags(&option.option_type, &grid);
+
+    // (cash flow, discount factor of the cash flow) per path, initialised at
+    // expiry.
+    let expiry_discount = discount.last().copied().unwrap_or(1.0);
+    let mut cash_flows: Vec<(f64, f64)> = paths
+        .iter()
+        .map(|path| {
+            (
+                intrinsic(path.last().copied().unwrap_or(0.0)),
+                expiry_discount,
+            )
+        })
+        .collect();
+
+    let terms = config.degree.get() + 1;
+    let mut basis = vec![0.0; terms];
+    let mut exercised_dates = 0usize;
+    for (k, (&t, &can_exercise)) in grid.iter().zip(&exercisable).enumerate().rev() {
+        if k == 0 || !can_exercise || t >= expiry {
+            continue;
+        }
+        let step_discount = discount.get(k).copied().unwrap_or(1.0);
+        let mut gram = vec![vec![0.0; terms]; terms];
+        let mut moment = vec![0.0; terms];
+        let mut regressed = 0usize;
+        for (path, &(cash, flow_discount)) in paths.iter().zip(&cash_flows) {
+            let spot = path.get(k).copied().unwrap_or(0.0);
+            if config.in_the_money_only && intrinsic(spot) <= 0.0 {
+                continue;
+            }
+            config.basis.evaluate(spot / strike, &mut basis);
+            let y = cash * flow_discount / step_discount;
+            for ((row, &bi), m) in gram.iter_mut().zip(&basis).zip(moment.iter_mut()) {
+                *m += bi * y;
+                for (g, &bj) in row.iter_mut().zip(&basis) {
+                    *g += bi * bj;
+                }
+            }
+            regressed += 1;
+        }
+        if regressed <= terms {
+            continue;
+        }
+        let Some(coefficients) = solve(gram, moment) else {
+            debug!(step = k, "singular LSM regression; exercise skipped");
+            continue;
+        };
+        let mut any = false;
+        for (path, flow) in paths.iter().zip(cash_flows.iter_mut()) {
+            let spot = path.get(k).copied().unwrap_or(0.0);
+            let exercise = intrinsic(spot);
+            if exercise <= 0.0 {
+                continue;
+            }
+            config.basis.evaluate(spot / strike, &mut basis);
+            let continuation: f64 = basis.iter().zip(&coefficients).map(|(b, c)| b * c).sum();
+            if exercise >= continuation {
+                *flow = (exercise, step_discount);
+                any = true;
+            }
+        }
+        exercised_dates += usize::from(any);
+    }
+    debug!(exercised_dates, "LSM backward induction complete");
+
+    let discounted: Vec<f64> = cash_flows
+        .iter()
+        .map(|&(cash, flow_discount)| cash * flow_discount)
+        .collect();
+    let n = discounted.len() as f64;
+    let mean = discounted.iter().sum::<f64>() / n;
+    let variance = if discounted.len() > 1 {
+        discounted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+    } else {
+        0.0
+    };
+    let spot_now = option.underlying_price.to_f64();
+    let immediate = match exercisable.first() {
+        Some(true) if !matches!(option.option_type, OptionType::European) => intrinsic(spot_now),
+        _ => 0.0,
+    };
+    let (estimate, standard_error) = if immediate > mean {
+        (immediate, 0.0)
+    } else {
+        (mean, (variance / n).sqrt())
+    };
+
+    let level = config.confidence_level.to_f64().unwrap_or(0.95);
+    let quantile = Normal::standard().inverse_cdf(0.5 * (1.0 + level));
+    let finite = |value: f64, context: &'static str| {
+        finite_decimal(value).ok_or_else(|| PricingError::non_finite(context, value))
+    };
+    let price = finite(estimate, "pricing::lsm::price")?;
+    let half_width = finite(quantile * standard_error, "pricing::lsm::ci")?;
+    let lower = d_sub(price, half_width, "pricing::lsm::lower")?;
+    let upper = d_add(price, half_width, "pricing::lsm::upper")?;
+    let (price, confidence_interval) = match option.side {
+        Side::Long => (price, (lower, upper)),
+        Side::Short => (-price, (-upper, -lower)),
+    };
+    Ok(MonteCarloResult {
+        price,
+        standard_error: finite(standard_error, "pricing::lsm::se")?,
+        confidence_interval,
+        confidence_level: config.confidence_level,
+        paths: paths.len(),
+        control_variate_beta: None,
+    })
+}
+
+/// Checks the observation grid and snaps its last point onto `expiry`.
+fn validate_grid(times: &[f64], expiry: f64) -> Result<Vec<f64>, PricingError> {
+    let invalid = |reason: &str| PricingError::method_error("longstaff_schwartz", reason);
+    if times.len() < 2 {
+        return Err(invalid("at least two observation times are required"));
+    }
+    if times.first().is_none_or(|&t| t.abs() > 1e-12) {
+        return Err(invalid("the first observation time must be 0"));
+    }
+    if times.windows(2).any(|w| matches!(w, [a, b] if b <= a)) {
+        return Err(invalid("observation times must be strictly increasing"));
+    }
+    let (last, previous) = match times {
+        [.., previous, last] => (*last, *previous),
+        _ => return Err(invalid("at least two observation times are required")),
+    };
+    if (last - expiry).abs() > 0.5 * (last - previous) {
+        return Err(invalid(&format!(
+            "observation grid ends at {last:.6} years but the option expires at {expiry:.6}"
+        )));
+    }
+    let mut grid = times.to_vec();
+    if let Some(slot) = grid.last_mut() {
+        *slot = expiry;
+    }
+    Ok(grid)
+}
+
+fn validate_paths(option: &Options, paths: &[Vec<f64>], len: usize) -> Result<(), PricingError> {
+    let invalid = |reason: &str| PricingError::method_error("longstaff_schwartz", reason);
+    if paths.is_empty() {
+        return Err(invalid("at least one path is required"));
+    }
+    if paths.iter().any(|path| path.len() != len) {
+        return Err(invalid(
+            "every path must have one price per observation time",
+        ));
+    }
+    let spot = option.underlying_price.to_f64();
+    let mismatched = paths.iter().any(|path| {
+        path.first()
+            .is_none_or(|&s0| (s0 - spot).abs() > SPOT_TOLERANCE * spot.max(1.0))
+    });
+    if mismatched {
+        return Err(invalid("paths must start at the option's underlying price"));
+    }
+    Ok(())
+}
+
+/// Marks the observations at which early exercise is allowed.
+fn exercise_flags(option_type: &OptionType, grid: &[f64]) -> Vec<bool> {
+    match option_type {
+        OptionType::American => vec![true; grid.len()],
+        OptionType::Bermuda { exercise_dates } => {
+            let mut flags = Vec::with_capacity(grid.len());
+            let mut previous = grid.first().copied().unwrap_or(0.0);
+            for (k, &t) in grid.iter().enumerate() {
+                let next = grid.get(k + 1).copied().unwrap_or(t);
+                let half_step = 0.5 * (t - previous).max(next - t);
+                flags.push(
+                    exercise_dates
+                        .iter()
+                        .any(|d| (d.to_f64() - t).abs() <= half_step),
+                );
+                previous = t;
+            }
+            flags
+        }
+        _ => vec![false; grid.len()],
+    }
+}
+
+/// Solves the symmetric system `a · x = b` by Gaussian elimination with
+/// partial pivoting. Returns `None` when the system is singular.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| {
+            let vi = a.get(i).and_then(|r| r.get(col)).map_or(0.0, |v| v.abs());
+            let vj = a.get(j).and_then(|r| r.get(col)).map_or(0.0, |v| v.abs());
+            vi.total_cmp(&vj)
+        })?;
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = upper.last()?;
+        let diagonal = *pivot_row.get(col)?;
+        if diagonal.abs() < 1e-12 {
+            return None;
+        }
+        let pivot_rhs = *b.get(col)?;
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row.get(col)? / diagonal;
+            for (x, &p) in row.iter_mut().zip(pivot_row).skip(col) {
+                *x -= factor * p;
+            }
+            *b.get_mut(col + 1 + offset)? -= factor * pivot_rhs;
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let coefficients = a.get(row)?;
+        let tail: f64 = coefficients
+            .iter()
+            .zip(&x)
+            .skip(row + 1)
+            .map(|(c, v)| c * v)
+            .sum();
+        let value = (b.get(row)? - tail) / coefficients.get(row)?;
+        *x.get_mut(row)? = value;
+    }
+    x.iter().all(|v| v.is_finite()).then_some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::utils::create_sample_option_with_days;
+    use crate::pricing::binomial_model::{BinomialPricingParams, price_binomial};
+    use crate::pricing::{DiscreteDividend, DividendSchedule, YieldCurve, black_scholes};
+    use crate::simulation::steps::{Step, Xstep, Ystep};
+    use crate::simulation::{WalkParams, WalkType, WalkTypeAble, generator_positive};
+    use crate::utils::{TimeFrame, deterministic_rng};
+    use positive::pos_or_panic;
+    use rand_distr::{Distribution, StandardNormal};
+
+    fn option(option_type: OptionType, spot: f64, days: f64) -> Options {
+        Options {
+            option_type,
+            risk_free_rate: dec!(0.06),
+            dividend_yield: Positive::ZERO,
+            ..create_sample_option_with_days(
+                OptionStyle::Put,
+                Side::Long,
+                pos_or_panic!(spot),
+                Positive::ONE,
+                pos_or_panic!(40.0),
+                pos_or_panic!(0.2),
+                pos_or_panic!(days),
+            )
+        }
+    }
+
+    /// Antithetic risk-neutral GBM paths on an even grid over one year.
+    fn gbm_paths(spot: f64, steps: usize, pairs: usize) -> (Vec<Positive>, Vec<Vec<Positive>>) {
+        let (rate, sigma, dt) = (0.06, 0.2, 1.0 / steps as f64);
+        let mut rng = deterministic_rng(2026);
+        let times = (0..=steps)
+            .map(|k| Positive::new(k as f64 * dt).unwrap())
+            .collect();
+        let mut paths = Vec::with_capacity(2 * pairs);
+        for _ in 0..pairs {
+            let shocks: Vec<f64> = (0..steps)
+                .map(|_| StandardNormal.sample(&mut rng))
+                .collect();
+            for sign in [1.0, -1.0] {
+                let mut s = spot;
+                let mut path = vec![Positive::new(s).unwrap()];
+                for z in &shocks {
+                    s *= ((rate - 0.5 * sigma * sigma) * dt + sigma * dt.sqrt() * sign * z).exp();
+                    path.push(Positive::new(s).unwrap());
+                }
+                paths.push(path);
+            }
+        }
+        (times, paths)
+    }
+
+    fn lattice(option: &Options) -> f64 {
+        price_binomial(BinomialPricingParams {
+            asset: option.underlying_price,
+            volatility: option.implied_volatility,
+            int_rate: option.risk_free_rate,
+            dividend_yield: option.dividend_yield,
+            strike: option.strike_price,
+            expiry: option.time_to_expiration().unwrap(),
+            no_steps: NonZeroUsize::new(200).unwrap(),
+            option_type: &option.option_type,
+            option_style: &option.option_style,
+            side: &Side::Long,
+        })
+        .unwrap()
+        .to_f64()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_lsm_american_put_matches_longstaff_schwartz_table() {
+        // Longstaff & Schwartz (2001), table 1: S = 36, σ = 0.2, T = 1 → 4.478.
+        let american = option(OptionType::American, 36.0, 365.0);
+        let (times, paths) = gbm_paths(36.0, 50, 5_000);
+        let result = longstaff_schwartz(&american, &times, &paths, &LsmConfig::default()).unwrap();
+        let price = result.price.to_f64().unwrap();
+        assert!((price - 4.478).abs() < 0.06, "LSM price {price}");
+        assert!((price - lattice(&american)).abs() < 0.06);
+        assert!(result.standard_error < dec!(0.05), "{result:?}");
+        assert_eq!(result.paths, 10_000);
+    }
+
+    #[test]
+    fn test_lsm_bases_agree() {
+        let american = option(OptionType::American, 40.0, 365.0);
+        let (times, paths) = gbm_paths(40.0, 50, 5_000);
+        let prices: Vec<f64> = [LsmBasis::Monomial, LsmBasis::Laguerre, LsmBasis::Hermite]
+            .into_iter()
+            .map(|basis| {
+                let config = LsmConfig::default().with_basis(basis);
+                longstaff_schwartz(&american, &times, &paths, &config)
+                    .unwrap()
+                    .price
+                    .to_f64()
+                    .unwrap()
+            })
+            .collect();
+        for price in &prices {
+            assert!((price - 2.314).abs() < 0.06, "{prices:?}");
+        }
+    }
+
+    #[test]
+    fn test_lsm_bermuda_between_european_and_american() {
+        let (times, paths) = gbm_paths(36.0, 52, 5_000);
+        let config = LsmConfig::default();
+        let value = |option_type: OptionType| {
+            longstaff_schwartz(&option(option_type, 36.0, 365.0), &times, &paths, &config)
+                .unwrap()
+                .price
+        };
+        let european = value(OptionType::European);
+        let bermuda = value(OptionType::Bermuda {
+            exercise_dates: vec![pos_or_panic!(0.25), pos_or_panic!(0.5), pos_or_panic!(0.75)],
+        });
+        let american = value(OptionType::American);
+        assert!(european < bermuda && bermuda < american);
+
+        let reference = black_scholes(&option(OptionType::European, 36.0, 365.0)).unwrap();
+        assert!(
+            (european - reference).abs() < dec!(0.05),
+            "{european} vs {reference}"
+        );
+    }
+
+    #[test]
+    fn test_lsm_deep_in_the_money_exercises_immediately() {
+        let american = option(OptionType::American, 20.0, 365.0);
+        let (times, paths) = gbm_paths(20.0, 10, 200);
+        let result = longstaff_schwartz(&american, &times, &paths, &LsmConfig::default()).unwrap();
+        assert_eq!(result.price, dec!(20));
+        assert_eq!(result.standard_error, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_lsm_short_side_is_negated() {
+        let mut american = option(OptionType::American, 36.0, 365.0);
+        let (times, paths) = gbm_paths(36.0, 20, 500);
+        let long = longstaff_schwartz(&american, &times, &paths, &LsmConfig::default()).unwrap();
+        american.side = Side::Short;
+        let short = longstaff_schwartz(&american, &times, &paths, &LsmConfig::default()).unwrap();
+        assert_eq!(short.price, -long.price);
+        assert_eq!(short.confidence_interval.1, -long.confidence_interval.0);
+    }
+
+    #[test]
+    fn test_lsm_rejects_malformed_inputs() {
+        let american = option(OptionType::American, 36.0, 365.0);
+        let (times, paths) = gbm_paths(36.0, 10, 10);
+        let config = LsmConfig::default();
+
+        let short_grid = &times[..5];
+        assert!(matches!(
+            longstaff_schwartz(&american, short_grid, &paths, &config),
+            Err(PricingError::MethodError { .. })
+        ));
+        let wrong_spot = option(OptionType::American, 30.0, 365.0);
+        assert!(longstaff_schwartz(&wrong_spot, &times, &paths, &config).is_err());
+        let asian = option(
+            OptionType::Asian {
+                averaging_type: crate::model::types::AsianAveragingType::Arithmetic,
+            },
+            36.0,
+            365.0,
+        );
+        assert!(matches!(
+            longstaff_schwartz(&asian, &times, &paths, &config),
+            Err(PricingError::UnsupportedOptionType { .. })
+        ));
+        let bad_level = config.with_confidence_level(dec!(1.5));
+        assert!(longstaff_schwartz(&american, &times, &paths, &bad_level).is_err());
+    }
+
+    #[test]
+    fn test_lsm_discounts_on_the_yield_curve() {
+        let (times, paths) = gbm_paths(36.0, 20, 500);
+        let config = LsmConfig::default();
+        let american = option(OptionType::American, 36.0, 365.0);
+        let curved = Options {
+            risk_free_rate: Decimal::ZERO,
+            ..american.clone()
+        }
+        .with_yield_curve(YieldCurve::flat(dec!(0.06)));
+        let flat = longstaff_schwartz(&american, &times, &paths, &config).unwrap();
+        let on_curve = longstaff_schwartz(&curved, &times, &paths, &config).unwrap();
+        assert!((flat.price - on_curve.price).abs() < dec!(1e-9));
+
+        let with_dividend =
+            american.with_dividends(DividendSchedule::new(vec![DiscreteDividend::in_days(
+                pos_or_panic!(90.0),
+                Positive::ONE,
+            )]));
+        assert!(matches!(
+            longstaff_schwartz(&with_dividend, &times, &paths, &config),
+            Err(PricingError::MethodError { .. })
+        ));
+    }
+
+    #[derive(Clone)]
+    struct ReplayWalker;
+    impl WalkTypeAble<Positive, Positive> for ReplayWalker {}
+
+    #[test]
+    fn test_lsm_simulator_matches_explicit_paths() {
+        let prices: Vec<Positive> = [36.0, 35.0, 33.5, 34.0, 32.0]
+            .into_iter()
+            .map(|p| pos_or_panic!(p))
+            .collect();
+        let walk_params = WalkParams {
+            size: prices.len(),
+            init_step: Step {
+                x: Xstep::new(
+                    Positive::ONE,
+                    TimeFrame::Day,
+                    ExpirationDate::Days(pos_or_panic!(30.0)),
+                ),
+                y: Ystep::new(0, prices[0]),
+            },
+            walker: Box::new(ReplayWalker),
+            walk_type: WalkType::Historical {
+                timeframe: TimeFrame::Day,
+                prices: prices.clone(),
+                symbol: None,
+            },
+        };
+        let simulator =
+            Simulator::new("lsm".to_string(), 3, &walk_params, generator_positive).unwrap();
+        let american = option(OptionType::American, 36.0, 4.0);
+        let config = LsmConfig::default().with_degree(NonZeroUsize::MIN);
+
+        let from_simulator = longstaff_schwartz_simulator(&american, &simulator, &config).unwrap();
+        let times: Vec<Positive> = (0..prices.len())
+            .map(|d| Positive::new(d as f64 / DAYS_IN_A_YEAR.to_f64()).unwrap())
+            .collect();
+        let explicit = longstaff_schwartz(&american, &times, &vec![prices; 3], &config).unwrap();
+        assert_eq!(from_simulator, explicit);
+        // Identical paths make the regression singular, so the put is held to expiry.
+        assert!(from_simulator.price > dec!(7.99) && from_simulator.price < dec!(8));
+    }
+}
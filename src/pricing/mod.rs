@@ -106,6 +106,9 @@
 //!             option_style: OptionStyle::Call,
 //!             dividend_yield: pos_or_panic!(0.01),
 //!             exotic_params: None,
+//!             yield_curve: None,
+//!             dividends: Default::default(),
+//!             day_count: Default::default(),
 //!         };
 //! let price = telegraph(&option, optionstratlib::nz!(1000), Some(dec!(0.5)), Some(dec!(0.3)));
 //! ```
@@ -136,6 +139,9 @@
 //!             option_style: OptionStyle::Call,
 //!             dividend_yield: pos_or_panic!(0.01),
 //!             exotic_params: None,
+//!             yield_curve: None,
+//!             dividends: Default::default(),
+//!             day_count: Default::default(),
 //!         };
 //! // Compare prices across different models
 //! let bs_price = black_scholes(&option);
@@ -185,6 +191,29 @@ pub mod american;
 /// Binomial Tree model for option pricing.
 pub mod binomial_model;
 
+/// Crank–Nicolson / PSOR finite-difference solver for the Black–Scholes PDE
+/// with early exercise, barriers and discrete dividends.
+pub mod finite_difference;
+
+/// Discrete cash-dividend schedules honoured by the Black–Scholes, binomial,
+/// Monte Carlo and finite-difference pricers.
+pub mod dividends;
+
+/// Interest-rate term structure bootstrapped from deposits, swaps and
+/// treasuries with log-linear discount-factor interpolation.
+pub mod yield_curve;
+
+/// Heston stochastic-volatility pricing through the Lewis characteristic
+/// function integral, and the implied volatility smile it generates.
+pub mod heston;
+
+/// European pricing off a SABR implied volatility smile.
+pub mod sabr;
+
+/// Longstaff–Schwartz least-squares Monte Carlo for American and Bermudan
+/// exercise on simulated paths.
+pub mod lsm;
+
 /// Barrier option pricing using analytical extensions.
 pub mod barrier;
 
@@ -281,6 +310,9 @@ pub mod monte_carlo;
 /// of options at expiration or exercise.
 pub(crate) mod payoff;
 
+/// Sobol low-discrepancy sequence backing the quasi-Monte Carlo pricer.
+pub(crate) mod sobol;
+
 /// Telegraph process model for asset price movement.
 ///
 /// Implements a telegraph process model which can be used as an alternative to
@@ -329,6 +361,9 @@ pub(crate) mod utils;
 ///     option_style: OptionStyle::Call,
 ///     dividend_yield: pos_or_panic!(0.01),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 ///
 /// let engine = PricingEngine::ClosedFormBS;
@@ -341,21 +376,34 @@ pub use american::barone_adesi_whaley;
 pub use asian::asian_black_scholes;
 pub use barrier::barrier_black_scholes;
 pub use binary::binary_black_scholes;
-pub use binomial_model::{BinomialPricingParams, generate_binomial_tree, price_binomial};
+pub use binomial_model::{
+    BinomialPricingParams, generate_binomial_tree, price_binomial, price_binomial_with_dividends,
+};
 pub use black_76::{Black76, black_76};
 pub use black_scholes_model::{BlackScholes, black_scholes};
 pub use chooser::chooser_black_scholes;
 pub use cliquet::cliquet_black_scholes;
 pub use compound::compound_black_scholes;
+pub use dividends::{DiscreteDividend, DividendSchedule};
 pub use exchange::exchange_black_scholes;
+pub use finite_difference::{
+    FiniteDifferenceConfig, FiniteDifferenceResult, finite_difference_price,
+};
 pub use garman_kohlhagen::{GarmanKohlhagen, garman_kohlhagen};
+pub use heston::{HestonParams, HestonSmile, heston_price};
 pub use lookback::lookback_black_scholes;
-pub use monte_carlo::monte_carlo_option_pricing;
+pub use lsm::{LsmBasis, LsmConfig, longstaff_schwartz, longstaff_schwartz_simulator};
+pub use monte_carlo::{
+    MonteCarloConfig, MonteCarloResult, SOBOL_REPLICATES, SamplingScheme,
+    monte_carlo_option_pricing, monte_carlo_price,
+};
 pub use payoff::{Payoff, PayoffInfo, Profit};
 pub use power::power_black_scholes;
 pub use quanto::quanto_black_scholes;
 pub use rainbow::rainbow_black_scholes;
+pub use sabr::{forward_price, sabr_price};
 pub use spread::spread_black_scholes;
 pub use telegraph::{TelegraphProcess, telegraph};
 pub use unified::{Priceable, PricingEngine, price_option};
 pub use utils::{probability_keep_under_strike, simulate_returns};
+pub use yield_curve::{CurveInstrument, YieldCurve};
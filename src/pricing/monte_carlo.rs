@@ -1,12 +1,23 @@
 use crate::Options;
+use crate::constants::{DEFAULT_MC_PATHS, DEFAULT_MC_STEPS};
 use crate::error::PricingError;
-use crate::model::decimal::{d_div, d_mul, d_sub, finite_decimal};
+use crate::model::decimal::{d_add, d_div, d_mul, d_sub, finite_decimal};
+use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::pricing::black_scholes_model::black_scholes;
+use crate::pricing::payoff::{Payoff, PayoffInfo};
+use crate::pricing::sobol::{SOBOL_MAX_DIMENSIONS, Sobol};
 use crate::pricing::utils::wiener_increment;
+use crate::utils::others::{DETERMINISTIC_RNG_DEFAULT_SEED, deterministic_rng};
 use num_traits::{FromPrimitive, ToPrimitive};
 use positive::Positive;
+use rand::RngExt;
+use rand_distr::{Distribution, StandardNormal};
+use rayon::prelude::*;
 use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use statrs::distribution::{ContinuousCDF, Normal, StudentsT};
 use std::num::NonZeroUsize;
-use tracing::instrument;
+use tracing::{debug, instrument};
 
 /// This function performs Monte Carlo simulation to price an option.
 ///
@@ -57,7 +68,7 @@ pub fn monte_carlo_option_pricing(
 ) -> Result<Decimal, PricingError> {
     let steps_raw = steps.get();
     let simulations_raw = simulations.get();
-    let dt = option.expiration_date.get_years()? / steps_raw as f64;
+    let dt = option.years_to_expiry()? / steps_raw as f64;
     let mut payoff_sum = 0.0;
 
     for _ in 0..simulations_raw {
@@ -99,7 +110,7 @@ pub fn monte_carlo_option_pricing(
             rate_f64,
         ));
     }
-    let years = option.expiration_date.get_years()?.to_f64();
+    let years = option.years_to_expiry()?.to_f64();
     if !years.is_finite() {
         return Err(PricingError::non_finite(
             "pricing::monte_carlo::years",
@@ -153,7 +164,7 @@ pub fn monte_carlo_option_pricing(
 /// 5. Return the discounted average payoff as the estimated option price.
 ///
 /// # Errors
-/// - Propagates any error from `option.expiration_date.get_years()?` (e.g.,
+/// - Propagates any error from `option.years_to_expiry()?` (e.g.,
 ///   invalid expiration date).
 /// - Returns `PricingError::method_error` when `num_simulations` cannot be
 ///   represented as a `Decimal` (effectively unreachable for valid `usize`
@@ -176,7 +187,7 @@ pub fn price_option_monte_carlo(
 
     // Calculate total discount factor (risk-free rate adjusted for dividends)
     let effective_rate = option.risk_free_rate - option.dividend_yield;
-    let discount_factor = (-effective_rate * option.expiration_date.get_years()?).exp();
+    let discount_factor = (-effective_rate * option.years_to_expiry()?).exp();
 
     // Calculate payoff for each final price and sum them
     let total_payoff: Decimal = final_prices
@@ -202,6 +213,607 @@ pub fn price_option_monte_carlo(
     Ok(Positive::new_decimal(avg_payoff.abs()).unwrap_or(Positive::ZERO))
 }
 
+/// Number of independently shifted Sobol replicates used to estimate the
+/// standard error of the quasi-Monte Carlo estimator.
+pub const SOBOL_REPLICATES: usize = 16;
+
+/// Paths simulated per pseudo-random chunk. Each chunk owns an RNG seeded
+/// from `(seed, chunk index)`, so results do not depend on the rayon pool.
+const PATHS_PER_CHUNK: usize = 4_096;
+
+/// Source of the Gaussian increments driving each simulated path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum SamplingScheme {
+    /// Pseudo-random normals drawn from a seeded [`rand::rngs::StdRng`].
+    #[default]
+    PseudoRandom,
+    /// Randomised quasi-Monte Carlo: digitally shifted Sobol points mapped
+    /// through the inverse normal CDF, one dimension per time step. Limited
+    /// to as many steps as the embedded direction table has dimensions (21).
+    Sobol,
+}
+
+/// Configuration of the variance-reduced Monte Carlo pricer
+/// [`monte_carlo_price`].
+///
+/// The defaults simulate [`DEFAULT_MC_PATHS`] pseudo-random paths of
+/// [`DEFAULT_MC_STEPS`] steps with antithetic and control variates enabled
+/// and a 95 % confidence interval. European payoffs are exact with a single
+/// step; path-dependent payoffs are monitored at every step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarloConfig {
+    /// Number of simulated paths, antithetic twins included.
+    pub paths: NonZeroUsize,
+    /// Number of monitoring steps per path.
+    pub steps: NonZeroUsize,
+    /// Seed of the deterministic random stream.
+    pub seed: u64,
+    /// Pair every path with its mirror image `-Z`.
+    pub antithetic: bool,
+    /// Use the discounted vanilla payoff, whose expectation is the
+    /// Black–Scholes price, as a control variate.
+    pub control_variate: bool,
+    /// Source of the Gaussian increments.
+    pub sampling: SamplingScheme,
+    /// Two-sided confidence level of the reported interval, in `(0, 1)`.
+    pub confidence_level: Decimal,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        Self {
+            paths: DEFAULT_MC_PATHS,
+            steps: DEFAULT_MC_STEPS,
+            seed: DETERMINISTIC_RNG_DEFAULT_SEED,
+            antithetic: true,
+            control_variate: true,
+            sampling: SamplingScheme::PseudoRandom,
+            confidence_level: dec!(0.95),
+        }
+    }
+}
+
+impl MonteCarloConfig {
+    /// Sets the number of simulated paths.
+    #[must_use]
+    pub fn with_paths(mut self, paths: NonZeroUsize) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Sets the number of monitoring steps per path.
+    #[must_use]
+    pub fn with_steps(mut self, steps: NonZeroUsize) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Sets the seed of the deterministic random stream.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Enables or disables antithetic variates.
+    #[must_use]
+    pub fn with_antithetic(mut self, antithetic: bool) -> Self {
+        self.antithetic = antithetic;
+        self
+    }
+
+    /// Enables or disables the Black–Scholes control variate.
+    #[must_use]
+    pub fn with_control_variate(mut self, control_variate: bool) -> Self {
+        self.control_variate = control_variate;
+        self
+    }
+
+    /// Sets the sampling scheme.
+    #[must_use]
+    pub fn with_sampling(mut self, sampling: SamplingScheme) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Sets the two-sided confidence level of the reported interval.
+    #[must_use]
+    pub fn with_confidence_level(mut self, confidence_level: Decimal) -> Self {
+        self.confidence_level = confidence_level;
+        self
+    }
+}
+
+/// Outcome of [`monte_carlo_price`].
+///
+/// `price` and both interval bounds are per unit and carry the side sign,
+/// like [`crate::pricing::black_scholes`]; `standard_error` is always
+/// non-negative.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarloResult {
+    /// Estimated option price.
+    pub price: Decimal,
+    /// Standard error of the estimate.
+    pub standard_error: Decimal,
+    /// Lower and upper bounds of the confidence interval.
+    pub confidence_interval: (Decimal, Decimal),
+    /// Confidence level of `confidence_interval`.
+    pub confidence_level: Decimal,
+    /// Number of simulated paths, antithetic twins included.
+    pub paths: usize,
+    /// Estimated control-variate coefficient, when the control was applied.
+    pub control_variate_beta: Option<Decimal>,
+}
+
+/// Prices an option by variance-reduced Monte Carlo simulation under
+/// geometric Brownian motion with drift `r - q`.
+///
+/// Paths are simulated with the exact log-normal transition, so a single step
+/// prices European payoffs without discretisation bias. Path-dependent
+/// payoffs (Asian, barrier, lookback) are monitored discretely at each of the
+/// `config.steps` dates and evaluated through [`OptionType::payoff`].
+///
+/// Variance reduction:
+/// * **Antithetic variates** pair each path with its mirror `-Z` and treat
+///   the pair average as one sample.
+/// * **Control variate** regresses the payoff on the discounted vanilla
+///   terminal payoff, whose expectation is the Black–Scholes price, and
+///   removes the fitted component (`Y - β (X - E[X])`).
+/// * **Sobol sampling** replaces pseudo-random normals with
+///   [`SOBOL_REPLICATES`] digitally shifted Sobol sequences; the standard
+///   error comes from the spread of the replicate estimates and the interval
+///   uses Student-t quantiles.
+///
+/// Pseudo-random paths are split into fixed-size chunks simulated in
+/// parallel with rayon, each with its own RNG derived from `config.seed`
+/// through [`deterministic_rng`], so the same configuration always returns
+/// bit-identical results regardless of the thread count.
+///
+/// # Errors
+///
+/// * `PricingError::UnsupportedOptionType` for early-exercise and
+///   multi-asset option types (American, Bermuda, Compound, Chooser,
+///   Cliquet, Rainbow, Spread, Exchange).
+/// * `PricingError::InvalidEngine` when the confidence level lies outside
+///   `(0, 1)` or Sobol sampling is requested with more steps than
+///   supported dimensions.
+/// * `PricingError::ExpirationDate` when the expiry cannot be converted to
+///   a year fraction, and `PricingError::NonFinite` when a simulated spot or
+///   an estimator statistic is not finite.
+#[instrument(skip(option, config), fields(
+    paths = config.paths.get(),
+    steps = config.steps.get(),
+    sampling = ?config.sampling,
+    option_type = %option.option_type,
+    style = ?option.option_style,
+    side = ?option.side,
+))]
+pub fn monte_carlo_price(
+    option: &Options,
+    config: &MonteCarloConfig,
+) -> Result<MonteCarloResult, PricingError> {
+    if matches!(
+        option.option_type,
+        OptionType::American
+            | OptionType::Bermuda { .. }
+            | OptionType::Compound { .. }
+            | OptionType::Chooser { .. }
+            | OptionType::Cliquet { .. }
+            | OptionType::Rainbow { .. }
+            | OptionType::Spread { .. }
+            | OptionType::Exchange { .. }
+    ) {
+        return Err(PricingError::unsupported_option_type(
+            &option.option_type.to_string(),
+            "Monte Carlo",
+        ));
+    }
+    if config.confidence_level <= Decimal::ZERO || config.confidence_level >= Decimal::ONE {
+        return Err(PricingError::invalid_engine(&format!(
+            "confidence level must lie in (0, 1), got {}",
+            config.confidence_level
+        )));
+    }
+
+    let model = PathModel::new(option, config.steps.get())?;
+    let sign = match option.side {
+        Side::Long => Decimal::ONE,
+        Side::Short => Decimal::NEGATIVE_ONE,
+    };
+    let control_mean = if config.control_variate {
+        control_expectation(option)
+    } else {
+        None
+    };
+
+    let samples_per_path = if config.antithetic { 2 } else { 1 };
+    let units = config.paths.get().div_ceil(samples_per_path);
+    let estimate = match config.sampling {
+        SamplingScheme::PseudoRandom => {
+            simulate_pseudo_random(&model, config, units)?.estimate(control_mean)
+        }
+        SamplingScheme::Sobol => simulate_sobol(&model, config, units, control_mean)?,
+    };
+    let standard_error = estimate.standard_error;
+    let level = config.confidence_level.to_f64().unwrap_or(0.95);
+    let quantile = estimate.quantile(level)?;
+
+    let price = finite(estimate.mean, "pricing::monte_carlo_price::price")?;
+    let half_width = finite(quantile * standard_error, "pricing::monte_carlo_price::ci")?;
+    let standard_error = finite(standard_error, "pricing::monte_carlo_price::se")?;
+    let (lower, upper) = (
+        d_sub(price, half_width, "pricing::monte_carlo_price::lower")?,
+        d_add(price, half_width, "pricing::monte_carlo_price::upper")?,
+    );
+    let confidence_interval = match option.side {
+        Side::Long => (lower, upper),
+        Side::Short => (-upper, -lower),
+    };
+    Ok(MonteCarloResult {
+        price: price * sign,
+        standard_error,
+        confidence_interval,
+        confidence_level: config.confidence_level,
+        paths: units * samples_per_path,
+        control_variate_beta: estimate.beta.and_then(finite_decimal),
+    })
+}
+
+fn finite(value: f64, context: &'static str) -> Result<Decimal, PricingError> {
+    finite_decimal(value).ok_or_else(|| PricingError::non_finite(context, value))
+}
+
+/// Black–Scholes price of the long European vanilla with the same terms,
+/// i.e. the expectation of the control variate.
+fn control_expectation(option: &Options) -> Option<f64> {
+    let vanilla = Options {
+        option_type: OptionType::European,
+        side: Side::Long,
+        ..option.clone()
+    };
+    match black_scholes(&vanilla).map(|price| price.to_f64()) {
+        Ok(Some(price)) if price.is_finite() => Some(price),
+        outcome => {
+            debug!(
+                ?outcome,
+                "control variate disabled: no Black-Scholes reference"
+            );
+            None
+        }
+    }
+}
+
+/// Discretised GBM dynamics and payoff description of one option.
+struct PathModel {
+    log_spot: f64,
+    spot: f64,
+    strike: f64,
+    /// Log-drift of each step, following the forward rates of the curve.
+    drift: Vec<f64>,
+    /// Value at the end of each step of the cash dividends still to be paid,
+    /// added back to the escrowed spot on monitored paths.
+    income: Vec<f64>,
+    diffusion: f64,
+    discount: f64,
+    option: Options,
+    monitors_path: bool,
+    steps: usize,
+}
+
+impl PathModel {
+    fn new(option: &Options, steps: usize) -> Result<Self, PricingError> {
+        let expiry = option.years_to_expiry()?;
+        let years = expiry.to_f64();
+        let curve = option.discount_curve();
+        let spot = option.underlying_price.to_f64();
+        let escrowed = (option.underlying_price.to_dec() - option.dividends_present_value()?)
+            .to_f64()
+            .unwrap_or(0.0);
+        if escrowed <= 0.0 {
+            return Err(PricingError::method_error(
+                "monte_carlo",
+                "present value of dividends exceeds the underlying price",
+            ));
+        }
+        let dividend = option.dividend_yield.to_f64();
+        let sigma = option.implied_volatility.to_f64();
+        let dt = years / steps as f64;
+        let drift = curve
+            .step_forward_rates(expiry, steps)
+            .into_iter()
+            .map(|rate| (rate - dividend - 0.5 * sigma * sigma) * dt)
+            .collect();
+        let dividends = option.dividends.times(&option.day_count)?;
+        let income = (1..=steps)
+            .map(|step| {
+                let time = Positive::new(dt * step as f64).unwrap_or(expiry);
+                dividends
+                    .present_value(time, expiry, &curve)
+                    .to_f64()
+                    .unwrap_or(0.0)
+            })
+            .collect();
+        Ok(Self {
+            log_spot: escrowed.ln(),
+            spot,
+            strike: option.strike_price.to_f64(),
+            drift,
+            income,
+            diffusion: sigma * dt.sqrt(),
+            discount: curve.discount_factor(expiry).to_f64().unwrap_or(0.0),
+            option: Options {
+                side: Side::Long,
+                ..option.clone()
+            },
+            monitors_path: !matches!(option.option_type, OptionType::European),
+            steps,
+        })
+    }
+
+    /// Returns the discounted payoff and the discounted vanilla control for
+    /// the path driven by `normals` scaled by `direction` (`±1`).
+    fn sample(
+        &self,
+        normals: &[f64],
+        direction: f64,
+        path: &mut Vec<f64>,
+    ) -> Result<(f64, f64), PricingError> {
+        path.clear();
+        let mut log_spot = self.log_spot;
+        let (mut low, mut high) = (self.spot, self.spot);
+        for ((&z, &drift), &income) in normals.iter().zip(&self.drift).zip(&self.income) {
+            log_spot += drift + self.diffusion * direction * z;
+            let spot = log_spot.exp() + income;
+            low = low.min(spot);
+            high = high.max(spot);
+            if self.monitors_path {
+                path.push(spot);
+            }
+        }
+        let terminal = log_spot.exp();
+        if !terminal.is_finite() {
+            return Err(PricingError::non_finite(
+                "pricing::monte_carlo::terminal_spot",
+                terminal,
+            ));
+        }
+        let control = match self.option.option_style {
+            OptionStyle::Call => (terminal - self.strike).max(0.0),
+            OptionStyle::Put => (self.strike - terminal).max(0.0),
+        };
+        let payoff = if self.monitors_path {
+            self.option.option_type.payoff(&PayoffInfo {
+                spot: Positive::new(terminal)?,
+                strike: self.option.strike_price,
+                style: self.option.option_style,
+                side: Side::Long,
+                spot_prices: Some(path.clone()),
+                spot_min: Some(low),
+                spot_max: Some(high),
+            })
+        } else {
+            control
+        };
+        Ok((self.discount * payoff, self.discount * control))
+    }
+
+    /// Evaluates one sampling unit: a single path, or the average of an
+    /// antithetic pair.
+    fn unit(
+        &self,
+        normals: &[f64],
+        antithetic: bool,
+        path: &mut Vec<f64>,
+    ) -> Result<(f64, f64), PricingError> {
+        let (y, x) = self.sample(normals, 1.0, path)?;
+        if !antithetic {
+            return Ok((y, x));
+        }
+        let (y_mirror, x_mirror) = self.sample(normals, -1.0, path)?;
+        Ok((0.5 * (y + y_mirror), 0.5 * (x + x_mirror)))
+    }
+}
+
+/// Running first and second moments of the payoff `y` and control `x`.
+#[derive(Debug, Clone, Copy, Default)]
+struct Moments {
+    n: f64,
+    sum_y: f64,
+    sum_x: f64,
+    sum_yy: f64,
+    sum_xx: f64,
+    sum_xy: f64,
+}
+
+impl Moments {
+    fn push(&mut self, (y, x): (f64, f64)) {
+        self.n += 1.0;
+        self.sum_y += y;
+        self.sum_x += x;
+        self.sum_yy += y * y;
+        self.sum_xx += x * x;
+        self.sum_xy += x * y;
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        self.n += other.n;
+        self.sum_y += other.sum_y;
+        self.sum_x += other.sum_x;
+        self.sum_yy += other.sum_yy;
+        self.sum_xx += other.sum_xx;
+        self.sum_xy += other.sum_xy;
+        self
+    }
+
+    fn mean_y(&self) -> f64 {
+        self.sum_y / self.n
+    }
+
+    fn mean_x(&self) -> f64 {
+        self.sum_x / self.n
+    }
+
+    /// Sample covariances `(c_yy, c_xx, c_xy)`.
+    fn covariances(&self) -> (f64, f64, f64) {
+        let dof = (self.n - 1.0).max(1.0);
+        let (my, mx) = (self.mean_y(), self.mean_x());
+        (
+            (self.sum_yy - self.n * my * my) / dof,
+            (self.sum_xx - self.n * mx * mx) / dof,
+            (self.sum_xy - self.n * mx * my) / dof,
+        )
+    }
+
+    /// Optimal control-variate coefficient `Cov(x, y) / Var(x)`.
+    fn beta(&self, control_mean: Option<f64>) -> Option<f64> {
+        control_mean?;
+        let (_, c_xx, c_xy) = self.covariances();
+        (c_xx > f64::EPSILON).then(|| c_xy / c_xx)
+    }
+
+    /// Point estimate and its standard error from i.i.d. units.
+    fn estimate(&self, control_mean: Option<f64>) -> Estimate {
+        let beta = self.beta(control_mean);
+        let (c_yy, _, c_xy) = self.covariances();
+        let (mean, variance) = match (beta, control_mean) {
+            (Some(b), Some(mu)) => (self.mean_y() - b * (self.mean_x() - mu), c_yy - b * c_xy),
+            _ => (self.mean_y(), c_yy),
+        };
+        Estimate {
+            mean,
+            standard_error: (variance.max(0.0) / self.n).sqrt(),
+            beta,
+            replicates: None,
+        }
+    }
+}
+
+/// Point estimate together with what is needed to quote its error.
+struct Estimate {
+    mean: f64,
+    standard_error: f64,
+    beta: Option<f64>,
+    /// Number of independent replicates for randomised QMC.
+    replicates: Option<usize>,
+}
+
+impl Estimate {
+    /// Two-sided quantile for `level`: normal for i.i.d. paths, Student-t
+    /// with `R - 1` degrees of freedom for `R` QMC replicates.
+    fn quantile(&self, level: f64) -> Result<f64, PricingError> {
+        let p = 0.5 * (1.0 + level);
+        let quantile = match self.replicates {
+            Some(r) if r > 1 => StudentsT::new(0.0, 1.0, (r - 1) as f64)
+                .map_err(|e| PricingError::method_error("monte_carlo_price", &e.to_string()))?
+                .inverse_cdf(p),
+            _ => Normal::standard().inverse_cdf(p),
+        };
+        if quantile.is_finite() {
+            Ok(quantile)
+        } else {
+            Err(PricingError::non_finite(
+                "pricing::monte_carlo::quantile",
+                quantile,
+            ))
+        }
+    }
+}
+
+fn simulate_pseudo_random(
+    model: &PathModel,
+    config: &MonteCarloConfig,
+    units: usize,
+) -> Result<Moments, PricingError> {
+    let chunks = units.div_ceil(PATHS_PER_CHUNK);
+    (0..chunks)
+        .into_par_iter()
+        .map(|chunk| {
+            let mut rng = deterministic_rng(chunk_seed(config.seed, chunk));
+            let mut normals = vec![0.0; model.steps];
+            let mut path = Vec::with_capacity(model.steps);
+            let mut moments = Moments::default();
+            let len = PATHS_PER_CHUNK.min(units - chunk * PATHS_PER_CHUNK);
+            for _ in 0..len {
+                for z in normals.iter_mut() {
+                    *z = StandardNormal.sample(&mut rng);
+                }
+                moments.push(model.unit(&normals, config.antithetic, &mut path)?);
+            }
+            Ok(moments)
+        })
+        .try_reduce(Moments::default, |a, b| Ok(a.merge(b)))
+}
+
+fn simulate_sobol(
+    model: &PathModel,
+    config: &MonteCarloConfig,
+    units: usize,
+    control_mean: Option<f64>,
+) -> Result<Estimate, PricingError> {
+    let sobol = Sobol::new(model.steps).ok_or_else(|| {
+        PricingError::invalid_engine(&format!(
+            "Sobol sampling supports at most {SOBOL_MAX_DIMENSIONS} steps, got {}",
+            model.steps
+        ))
+    })?;
+    let per_replicate = units.div_ceil(SOBOL_REPLICATES);
+    let mut rng = deterministic_rng(config.seed);
+    let shifts: Vec<Vec<u32>> = (0..SOBOL_REPLICATES)
+        .map(|_| (0..model.steps).map(|_| rng.random::<u32>()).collect())
+        .collect();
+    let standard = Normal::standard();
+    let replicates = shifts
+        .par_iter()
+        .map(|shift| {
+            let mut uniforms = vec![0.0; model.steps];
+            let mut normals = vec![0.0; model.steps];
+            let mut path = Vec::with_capacity(model.steps);
+            let mut moments = Moments::default();
+            for index in 0..per_replicate as u64 {
+                sobol.point_into(index, shift, &mut uniforms);
+                for (z, &u) in normals.iter_mut().zip(&uniforms) {
+                    *z = standard.inverse_cdf(u);
+                }
+                moments.push(model.unit(&normals, config.antithetic, &mut path)?);
+            }
+            Ok(moments)
+        })
+        .collect::<Result<Vec<Moments>, PricingError>>()?;
+
+    // The control coefficient is fitted on the pooled sample and shared by
+    // every replicate, which keeps the replicate estimates exchangeable.
+    let pooled = replicates
+        .iter()
+        .fold(Moments::default(), |acc, m| acc.merge(*m));
+    let beta = pooled.beta(control_mean);
+    let estimates: Vec<f64> = replicates
+        .iter()
+        .map(|m| match (beta, control_mean) {
+            (Some(b), Some(mu)) => m.mean_y() - b * (m.mean_x() - mu),
+            _ => m.mean_y(),
+        })
+        .collect();
+    let r = estimates.len() as f64;
+    let mean = estimates.iter().sum::<f64>() / r;
+    let variance = estimates.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / (r - 1.0);
+    Ok(Estimate {
+        mean,
+        standard_error: (variance / r).sqrt(),
+        beta,
+        replicates: Some(estimates.len()),
+    })
+}
+
+/// Derives the seed of pseudo-random chunk `chunk` with a SplitMix64 step,
+/// so neighbouring chunks get decorrelated streams.
+pub(crate) fn chunk_seed(seed: u64, chunk: usize) -> u64 {
+    let mut z = seed.wrapping_add((chunk as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,6 +839,9 @@ mod tests {
             option_style: OptionStyle::Call,
             dividend_yield: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -607,3 +1222,239 @@ mod tests_price_option_monte_carlo {
     //             "Expected close to {}, got {}", expected.0, result.unwrap().0);
     // }
 }
+
+#[cfg(test)]
+mod tests_monte_carlo_price {
+    use super::*;
+    use crate::model::types::{BarrierType, OptionStyle, OptionType, Side};
+    use crate::model::utils::create_sample_option_with_days;
+    use crate::pricing::barrier_black_scholes;
+    use positive::pos_or_panic;
+
+    fn nz(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    fn option(option_type: OptionType, style: OptionStyle, side: Side) -> Options {
+        Options {
+            option_type,
+            ..create_sample_option_with_days(
+                style,
+                side,
+                Positive::HUNDRED,
+                Positive::ONE,
+                Positive::HUNDRED,
+                pos_or_panic!(0.25),
+                pos_or_panic!(182.5),
+            )
+        }
+    }
+
+    fn european_config() -> MonteCarloConfig {
+        MonteCarloConfig::default()
+            .with_paths(nz(40_000))
+            .with_steps(nz(1))
+            .with_seed(7)
+    }
+
+    #[test]
+    fn test_monte_carlo_price_european_brackets_black_scholes() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            let opt = option(OptionType::European, style, Side::Long);
+            let reference = black_scholes(&opt).unwrap();
+            let plain = european_config().with_control_variate(false);
+            let result = monte_carlo_price(&opt, &plain).unwrap();
+            let (lower, upper) = result.confidence_interval;
+            assert!(
+                lower <= reference && reference <= upper,
+                "{result:?} vs {reference}"
+            );
+            assert!((result.price - reference).abs() < dec!(4) * result.standard_error);
+            assert_eq!(result.paths, 40_000);
+        }
+    }
+
+    #[test]
+    fn test_monte_carlo_price_variance_reduction_shrinks_standard_error() {
+        let opt = option(OptionType::European, OptionStyle::Call, Side::Long);
+        let base = european_config()
+            .with_antithetic(false)
+            .with_control_variate(false);
+        let crude = monte_carlo_price(&opt, &base).unwrap();
+        let antithetic = monte_carlo_price(&opt, &base.clone().with_antithetic(true)).unwrap();
+        let controlled = monte_carlo_price(&opt, &base.with_control_variate(true)).unwrap();
+        assert!(antithetic.standard_error < crude.standard_error);
+        // The control is the payoff itself for a European, so the residual
+        // variance collapses.
+        assert!(controlled.standard_error < crude.standard_error / dec!(100));
+        assert!(controlled.control_variate_beta.is_some());
+        assert!(crude.control_variate_beta.is_none());
+    }
+
+    #[test]
+    fn test_monte_carlo_price_same_seed_is_reproducible() {
+        let opt = option(OptionType::European, OptionStyle::Put, Side::Long);
+        let config = european_config().with_control_variate(false);
+        let first = monte_carlo_price(&opt, &config).unwrap();
+        let second = monte_carlo_price(&opt, &config).unwrap();
+        let other = monte_carlo_price(&opt, &config.with_seed(8)).unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first.price, other.price);
+    }
+
+    #[test]
+    fn test_monte_carlo_price_sobol_beats_pseudo_random() {
+        let opt = option(OptionType::European, OptionStyle::Call, Side::Long);
+        let reference = black_scholes(&opt).unwrap();
+        let config = european_config()
+            .with_paths(nz(16_384))
+            .with_antithetic(false)
+            .with_control_variate(false);
+        let pseudo = monte_carlo_price(&opt, &config).unwrap();
+        let sobol = monte_carlo_price(&opt, &config.with_sampling(SamplingScheme::Sobol)).unwrap();
+        assert!(sobol.standard_error < pseudo.standard_error / dec!(5));
+        assert!((sobol.price - reference).abs() < dec!(0.05));
+    }
+
+    #[test]
+    fn test_monte_carlo_price_sobol_rejects_too_many_steps() {
+        let opt = option(OptionType::European, OptionStyle::Call, Side::Long);
+        let config = european_config()
+            .with_steps(nz(SOBOL_MAX_DIMENSIONS + 1))
+            .with_sampling(SamplingScheme::Sobol);
+        assert!(matches!(
+            monte_carlo_price(&opt, &config),
+            Err(PricingError::InvalidEngine { .. })
+        ));
+    }
+
+    #[test]
+    fn test_monte_carlo_price_short_side_flips_sign_and_interval() {
+        let long = option(OptionType::European, OptionStyle::Call, Side::Long);
+        let short = option(OptionType::European, OptionStyle::Call, Side::Short);
+        let config = european_config();
+        let l = monte_carlo_price(&long, &config).unwrap();
+        let s = monte_carlo_price(&short, &config).unwrap();
+        assert_eq!(s.price, -l.price);
+        assert_eq!(s.confidence_interval.0, -l.confidence_interval.1);
+        assert_eq!(s.standard_error, l.standard_error);
+    }
+
+    #[test]
+    fn test_monte_carlo_price_barrier_close_to_closed_form() {
+        let opt = option(
+            OptionType::Barrier {
+                barrier_type: BarrierType::DownAndOut,
+                barrier_level: pos_or_panic!(85.0),
+                rebate: None,
+            },
+            OptionStyle::Call,
+            Side::Long,
+        );
+        let closed_form = barrier_black_scholes(&opt).unwrap();
+        let config = MonteCarloConfig::default()
+            .with_paths(nz(20_000))
+            .with_steps(nz(252))
+            .with_seed(11);
+        let result = monte_carlo_price(&opt, &config).unwrap();
+        // Discrete monitoring knocks out less often than the continuous
+        // closed form, so the simulated price sits slightly above it.
+        assert!(result.price >= closed_form - dec!(3) * result.standard_error);
+        assert!(
+            (result.price - closed_form).abs() < dec!(0.5),
+            "{result:?} vs {closed_form}"
+        );
+    }
+
+    #[test]
+    fn test_monte_carlo_price_asian_below_european() {
+        let asian = option(
+            OptionType::Asian {
+                averaging_type: crate::model::types::AsianAveragingType::Arithmetic,
+            },
+            OptionStyle::Call,
+            Side::Long,
+        );
+        let european = option(OptionType::European, OptionStyle::Call, Side::Long);
+        let config = MonteCarloConfig::default()
+            .with_paths(nz(10_000))
+            .with_steps(nz(21));
+        let averaged = monte_carlo_price(&asian, &config).unwrap();
+        let vanilla = black_scholes(&european).unwrap();
+        assert!(averaged.price > Decimal::ZERO && averaged.price < vanilla);
+        let sobol =
+            monte_carlo_price(&asian, &config.with_sampling(SamplingScheme::Sobol)).unwrap();
+        assert!((sobol.price - averaged.price).abs() < dec!(0.2));
+    }
+
+    #[test]
+    fn test_monte_carlo_price_rejects_early_exercise_and_bad_level() {
+        let american = option(OptionType::American, OptionStyle::Put, Side::Long);
+        assert!(matches!(
+            monte_carlo_price(&american, &MonteCarloConfig::default()),
+            Err(PricingError::UnsupportedOptionType { .. })
+        ));
+        let european = option(OptionType::European, OptionStyle::Put, Side::Long);
+        let config = european_config().with_confidence_level(Decimal::ONE);
+        assert!(matches!(
+            monte_carlo_price(&european, &config),
+            Err(PricingError::InvalidEngine { .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests_monte_carlo_term_structure {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::pricing::{CurveInstrument, DiscreteDividend, DividendSchedule, YieldCurve};
+    use positive::pos_or_panic;
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn test_monte_carlo_price_honours_curve_and_dividends() {
+        let curve = YieldCurve::bootstrap(&[
+            CurveInstrument::Deposit {
+                tenor: pos_or_panic!(0.25),
+                rate: dec!(0.02),
+            },
+            CurveInstrument::Swap {
+                tenor: Positive::ONE,
+                rate: dec!(0.04),
+                frequency: NonZeroU32::MIN,
+            },
+        ])
+        .unwrap();
+        let option = Options::new(
+            OptionType::European,
+            Side::Long,
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.25),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            OptionStyle::Put,
+            Positive::ZERO,
+            None,
+        )
+        .with_yield_curve(curve)
+        .with_dividends(DividendSchedule::new(vec![DiscreteDividend::in_days(
+            pos_or_panic!(146.0),
+            pos_or_panic!(3.0),
+        )]));
+        let reference = black_scholes(&option).unwrap();
+        let config = MonteCarloConfig::default()
+            .with_paths(NonZeroUsize::new(40_000).unwrap())
+            .with_steps(NonZeroUsize::new(12).unwrap())
+            .with_seed(11)
+            .with_control_variate(false);
+        let result = monte_carlo_price(&option, &config).unwrap();
+        assert!(
+            (result.price - reference).abs() < dec!(4) * result.standard_error,
+            "{result:?} vs {reference}"
+        );
+    }
+}
@@ -80,7 +80,7 @@ pub fn quanto_black_scholes(option: &Options) -> Result<Decimal, PricingError> {
     let r_d = option.risk_free_rate;
     let q = Decimal::from(option.dividend_yield);
     let sigma_s = Decimal::from(option.implied_volatility);
-    let t = Decimal::from(option.expiration_date.get_years()?);
+    let t = Decimal::from(option.years_to_expiry()?);
 
     if t <= dec!(0.0) {
         let intrinsic = match option.option_style {
@@ -118,8 +118,7 @@ fn price_two_asset_rainbow(
 
     let r = option.risk_free_rate;
     let t = option
-        .expiration_date
-        .get_years()
+        .years_to_expiry()
         .map_err(|e| PricingError::other(&e.to_string()))?
         .to_dec();
 
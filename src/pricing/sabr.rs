@@ -0,0 +1,163 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # SABR Pricing
+//!
+//! Prices European options off a SABR smile: the forward
+//! `F = S e^{(r - q)T}` and the option's strike give the Hagan implied
+//! volatility of [`crate::volatility::sabr_implied_volatility`], which is
+//! then fed to the Black–Scholes–Merton closed form. Quoting and pricing
+//! therefore share one set of SABR parameters.
+
+use crate::Options;
+use crate::error::PricingError;
+use crate::model::types::OptionType;
+use crate::pricing::black_scholes_model::black_scholes;
+use crate::volatility::{SabrParams, sabr_implied_volatility};
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+use tracing::{debug, instrument};
+
+/// Forward price `S e^{(r - q)T}` of the option's underlying.
+///
+/// With a yield curve or cash dividends, `r` is the zero rate to expiry and
+/// `S` the escrowed spot `S - PV(dividends)`.
+///
+/// # Errors
+///
+/// Returns `PricingError::ExpirationDate` when the expiry cannot be
+/// converted to a year fraction, `PricingError::NonFinite` when the forward
+/// overflows, `PricingError::Positive` if it is not representable and
+/// `PricingError::MethodError` when the dividends are worth more than the spot.
+pub fn forward_price(option: &Options) -> Result<Positive, PricingError> {
+    let escrowed = option.escrowed()?;
+    let option = escrowed.as_ref().unwrap_or(option);
+    let expiry = option.time_to_expiration()?.to_f64();
+    let carry = option.risk_free_rate.to_f64().unwrap_or(0.0) - option.dividend_yield.to_f64();
+    let forward = option.underlying_price.to_f64() * (carry * expiry).exp();
+    if !forward.is_finite() {
+        return Err(PricingError::non_finite("pricing::sabr::forward", forward));
+    }
+    Ok(Positive::new(forward)?)
+}
+
+/// Prices a European option with the Black–Scholes formula at the SABR
+/// implied volatility of its strike.
+///
+/// The option's own `implied_volatility` is ignored. The result is per unit
+/// of underlying and carries the sign of `option.side`.
+///
+/// # Errors
+///
+/// * `PricingError::UnsupportedOptionType` for non-European options.
+/// * `PricingError::MethodError` when `params` is invalid or the Hagan
+///   expansion breaks down at the option's strike.
+/// * Any error of [`black_scholes`] or [`forward_price`].
+#[instrument(skip(option, params), fields(
+    strike = %option.strike_price,
+    style = ?option.option_style,
+))]
+pub fn sabr_price(option: &Options, params: &SabrParams) -> Result<Decimal, PricingError> {
+    if !matches!(option.option_type, OptionType::European) {
+        return Err(PricingError::unsupported_option_type(
+            &option.option_type.to_string(),
+            "SABR",
+        ));
+    }
+    let forward = forward_price(option)?;
+    let expiry = option.time_to_expiration()?;
+    let volatility = sabr_implied_volatility(forward, option.strike_price, expiry, params)
+        .map_err(|e| PricingError::method_error("SABR", &e.to_string()))?;
+    debug!(%forward, %volatility, "SABR volatility");
+    let mut priced = option.clone();
+    priced.implied_volatility = volatility;
+    black_scholes(&priced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::types::{OptionStyle, Side};
+    use crate::model::utils::create_sample_option_with_days;
+    use crate::pricing::{DiscreteDividend, DividendSchedule, YieldCurve};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn option(style: OptionStyle, strike: f64) -> Options {
+        Options {
+            risk_free_rate: dec!(0.04),
+            ..create_sample_option_with_days(
+                style,
+                Side::Long,
+                Positive::HUNDRED,
+                Positive::ONE,
+                pos_or_panic!(strike),
+                pos_or_panic!(0.9),
+                pos_or_panic!(182.5),
+            )
+        }
+    }
+
+    #[test]
+    fn test_sabr_price_uses_smile_volatility() {
+        let params = SabrParams::new(
+            pos_or_panic!(0.25),
+            Decimal::ONE,
+            dec!(-0.3),
+            pos_or_panic!(0.5),
+        )
+        .unwrap();
+        for (style, strike) in [(OptionStyle::Put, 85.0), (OptionStyle::Call, 115.0)] {
+            let contract = option(style, strike);
+            let forward = forward_price(&contract).unwrap();
+            assert!((forward.to_f64() - 100.0 * (0.03f64 * 0.5).exp()).abs() < 1e-10);
+            let vol = sabr_implied_volatility(
+                forward,
+                contract.strike_price,
+                contract.time_to_expiration().unwrap(),
+                &params,
+            )
+            .unwrap();
+            let mut reference = contract.clone();
+            reference.implied_volatility = vol;
+            assert_eq!(
+                sabr_price(&contract, &params).unwrap(),
+                black_scholes(&reference).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_sabr_forward_uses_term_rate_and_escrowed_spot() {
+        let contract = option(OptionStyle::Call, 100.0)
+            .with_yield_curve(YieldCurve::flat(dec!(0.02)))
+            .with_dividends(DividendSchedule::new(vec![DiscreteDividend::in_days(
+                pos_or_panic!(91.25),
+                Positive::TWO,
+            )]));
+        let escrowed = 100.0 - 2.0 * (-0.02f64 * 0.25).exp();
+        let forward = forward_price(&contract).unwrap();
+        assert!((forward.to_f64() - escrowed * (0.01f64 * 0.5).exp()).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_sabr_price_rejects_non_european() {
+        let params = SabrParams::new(
+            pos_or_panic!(0.25),
+            Decimal::ONE,
+            Decimal::ZERO,
+            Positive::ONE,
+        )
+        .unwrap();
+        let mut contract = option(OptionStyle::Call, 100.0);
+        contract.option_type = OptionType::American;
+        assert!(matches!(
+            sabr_price(&contract, &params),
+            Err(PricingError::UnsupportedOptionType { .. })
+        ));
+    }
+}
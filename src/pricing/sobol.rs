@@ -0,0 +1,185 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Sobol low-discrepancy sequence used by the quasi-Monte Carlo pricer.
+//!
+//! Direction numbers follow Joe & Kuo (2008, `new-joe-kuo-6.21201`) for
+//! dimensions 2..=21; dimension 1 is the van der Corput sequence in base 2.
+//! Points are computed directly from the Gray code of their index, so any
+//! point can be generated without iterating over its predecessors, and a
+//! per-replicate digital shift (XOR with a random 32-bit word) turns the
+//! sequence into an unbiased randomised QMC estimator.
+
+/// Number of bits per coordinate.
+const BITS: usize = 32;
+
+/// `(degree s, coefficient a, initial direction numbers m_1..m_s)` for
+/// dimensions 2..=21.
+const JOE_KUO: [(usize, u32, &[u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+/// Maximum number of dimensions supported by the embedded direction table.
+pub(crate) const SOBOL_MAX_DIMENSIONS: usize = JOE_KUO.len() + 1;
+
+/// Sobol generator over a fixed number of dimensions.
+#[derive(Debug, Clone)]
+pub(crate) struct Sobol {
+    directions: Vec<[u32; BITS]>,
+}
+
+impl Sobol {
+    /// Builds a generator for `dimensions` coordinates, or `None` when the
+    /// request is zero or exceeds [`SOBOL_MAX_DIMENSIONS`].
+    pub(crate) fn new(dimensions: usize) -> Option<Self> {
+        if dimensions == 0 || dimensions > SOBOL_MAX_DIMENSIONS {
+            return None;
+        }
+        let mut directions = Vec::with_capacity(dimensions);
+        directions.push(van_der_corput_directions());
+        for &(degree, coefficient, initial) in JOE_KUO.iter().take(dimensions - 1) {
+            directions.push(joe_kuo_directions(degree, coefficient, initial));
+        }
+        Some(Self { directions })
+    }
+
+    /// Writes point `index` of the sequence, digitally shifted by `shift`,
+    /// into `out` as uniforms in the open interval `(0, 1)`.
+    ///
+    /// Coordinates beyond `min(out.len(), shift.len(), dimensions)` are left
+    /// untouched.
+    pub(crate) fn point_into(&self, index: u64, shift: &[u32], out: &mut [f64]) {
+        let gray = index ^ (index >> 1);
+        for ((directions, &mask), slot) in self.directions.iter().zip(shift).zip(out.iter_mut()) {
+            let mut x = 0u32;
+            for (bit, &v) in directions.iter().enumerate() {
+                if (gray >> bit) & 1 == 1 {
+                    x ^= v;
+                }
+            }
+            // Centre the point inside its 2^-32 cell so that neither 0 nor 1
+            // is ever returned to the inverse normal CDF.
+            *slot = ((x ^ mask) as f64 + 0.5) / 4_294_967_296.0;
+        }
+    }
+}
+
+fn van_der_corput_directions() -> [u32; BITS] {
+    let mut v = [0u32; BITS];
+    for (k, slot) in v.iter_mut().enumerate() {
+        *slot = 1u32 << (BITS - 1 - k);
+    }
+    v
+}
+
+fn joe_kuo_directions(degree: usize, coefficient: u32, initial: &[u32]) -> [u32; BITS] {
+    let mut v = [0u32; BITS];
+    for (k, (slot, &m)) in v.iter_mut().zip(initial).enumerate() {
+        *slot = m << (BITS - 1 - k);
+    }
+    for k in degree..BITS {
+        let (head, tail) = v.split_at_mut(k);
+        let Some(slot) = tail.first_mut() else {
+            break;
+        };
+        let Some(&lagged) = head.get(k - degree) else {
+            break;
+        };
+        let mut value = lagged ^ (lagged >> degree);
+        for i in 1..degree {
+            if (coefficient >> (degree - 1 - i)) & 1 == 1
+                && let Some(&previous) = head.get(k - i)
+            {
+                value ^= previous;
+            }
+        }
+        *slot = value;
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sobol_rejects_unsupported_dimensions() {
+        assert!(Sobol::new(0).is_none());
+        assert!(Sobol::new(SOBOL_MAX_DIMENSIONS + 1).is_none());
+        assert!(Sobol::new(SOBOL_MAX_DIMENSIONS).is_some());
+    }
+
+    #[test]
+    fn test_sobol_first_points_match_reference() {
+        let sobol = Sobol::new(3).unwrap();
+        let shift = [0u32; 3];
+        let mut point = [0.0; 3];
+        let half_cell = 0.5 / 4_294_967_296.0;
+        let expected = [[0.5, 0.5, 0.5], [0.75, 0.25, 0.25], [0.25, 0.75, 0.75]];
+        for (index, reference) in expected.iter().enumerate() {
+            sobol.point_into(index as u64 + 1, &shift, &mut point);
+            for (x, r) in point.iter().zip(reference) {
+                assert!((x - r - half_cell).abs() < 1e-12, "{x} vs {r}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_sobol_points_stratify_every_dimension() {
+        let sobol = Sobol::new(SOBOL_MAX_DIMENSIONS).unwrap();
+        let shift = vec![0u32; SOBOL_MAX_DIMENSIONS];
+        let mut point = vec![0.0; SOBOL_MAX_DIMENSIONS];
+        let n = 256usize;
+        let mut counts = vec![[0usize; 16]; SOBOL_MAX_DIMENSIONS];
+        for index in 0..n {
+            sobol.point_into(index as u64, &shift, &mut point);
+            for (dim, &x) in point.iter().enumerate() {
+                assert!(x > 0.0 && x < 1.0);
+                counts[dim][(x * 16.0) as usize] += 1;
+            }
+        }
+        for (dim, buckets) in counts.iter().enumerate() {
+            assert!(
+                buckets.iter().all(|&c| c == n / 16),
+                "dimension {dim} is not stratified: {buckets:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sobol_digital_shift_preserves_stratification() {
+        let sobol = Sobol::new(2).unwrap();
+        let shift = [0xDEAD_BEEF, 0x1234_5678];
+        let mut point = [0.0; 2];
+        let mut counts = [[0usize; 8]; 2];
+        for index in 0..64u64 {
+            sobol.point_into(index, &shift, &mut point);
+            for (dim, &x) in point.iter().enumerate() {
+                counts[dim][(x * 8.0) as usize] += 1;
+            }
+        }
+        assert!(counts.iter().flatten().all(|&c| c == 8));
+    }
+}
@@ -80,7 +80,7 @@ pub fn spread_black_scholes(option: &Options) -> Result<Decimal, PricingError> {
     let r = option.risk_free_rate;
     let q1 = Decimal::from(option.dividend_yield);
     let sigma1 = Decimal::from(option.implied_volatility);
-    let t = Decimal::from(option.expiration_date.get_years()?);
+    let t = Decimal::from(option.years_to_expiry()?);
 
     let price = if k.abs() < dec!(0.0001) {
         margrabe_formula(
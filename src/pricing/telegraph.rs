@@ -511,6 +511,9 @@ mod tests_telegraph_process_basis {
             expiration_date: Default::default(),
             quantity: Positive::ONE,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
 
         let _price = telegraph(&option, crate::nz!(1000), Some(dec!(0.7)), Some(dec!(0.5)));
@@ -543,6 +546,9 @@ mod tests_telegraph_process_extended {
             expiration_date: Default::default(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
@@ -1,10 +1,24 @@
 use crate::Options;
+use crate::constants::DEFAULT_BINOMIAL_STEPS;
 use crate::error::{PricingError, PricingResult};
+use crate::model::types::{OptionType, Side};
+use crate::pricing::american::barone_adesi_whaley;
+use crate::pricing::binomial_model::{
+    BinomialPricingParams, price_binomial, price_binomial_with_dividends,
+};
 use crate::pricing::black_76::black_76;
 use crate::pricing::black_scholes_model::black_scholes;
+use crate::pricing::finite_difference::{FiniteDifferenceConfig, finite_difference_price};
 use crate::pricing::garman_kohlhagen::garman_kohlhagen;
+use crate::pricing::heston::{HestonParams, heston_price};
+use crate::pricing::lsm::{LsmConfig, longstaff_schwartz_simulator};
+use crate::pricing::monte_carlo::{MonteCarloConfig, monte_carlo_price};
+use crate::pricing::sabr::sabr_price;
+use crate::pricing::telegraph::telegraph;
 use crate::simulation::simulator::Simulator;
+use crate::volatility::SabrParams;
 use positive::Positive;
+use std::num::NonZeroUsize;
 
 /// Pricing engine selector for option pricing.
 ///
@@ -13,6 +27,18 @@ use positive::Positive;
 /// - `ClosedFormBlack76`: Uses the Black-76 closed-form formula
 /// - `MonteCarlo`: Uses Monte Carlo simulation with a configured simulator
 /// - `ClosedFormGK`: Uses the Garman-Kohlhagen closed-form formula for FX options
+/// - `Binomial`: Uses a Cox-Ross-Rubinstein lattice (European, American, Bermuda)
+/// - `BaroneAdesiWhaley`: Uses the BAW quadratic approximation for American options
+/// - `Telegraph`: Uses a telegraph-process simulation for European options
+/// - `ClosedFormExotic`: Uses the exotic closed forms (barrier, Asian, lookback, ...)
+/// - `MonteCarloGbm`: Uses the seeded, variance-reduced GBM Monte Carlo pricer
+/// - `LongstaffSchwartz`: Uses least-squares Monte Carlo on simulated paths
+/// - `FiniteDifference`: Uses a Crank–Nicolson / PSOR grid on the Black–Scholes PDE
+/// - `Heston`: Uses the Heston semi-analytic (Lewis) formula for European options
+/// - `Sabr`: Uses Black–Scholes at the SABR (Hagan) implied volatility
+///
+/// Use [`PricingEngine::supports`] to check whether an engine can price a
+/// given [`OptionType`] before dispatching.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum PricingEngine {
@@ -45,6 +71,182 @@ pub enum PricingEngine {
     /// `dividend_yield`). Structurally identical to Black–Scholes–Merton
     /// with `q = r_f`.
     ClosedFormGK,
+
+    /// Cox–Ross–Rubinstein binomial lattice.
+    ///
+    /// Prices European, American and Bermuda exercise in O(steps²). The
+    /// lattice drifts at the rate net of `dividend_yield`. See
+    /// [`crate::constants::DEFAULT_BINOMIAL_STEPS`] for a sensible default.
+    Binomial {
+        /// Number of time steps in the lattice
+        steps: NonZeroUsize,
+    },
+
+    /// Barone-Adesi–Whaley quadratic approximation for American options.
+    ///
+    /// O(1) early-exercise pricing with continuous dividend yield. Options
+    /// on a yield curve or with cash dividends are priced at the term rate
+    /// and escrowed spot, as under Black-Scholes.
+    BaroneAdesiWhaley,
+
+    /// Telegraph-process simulation for European options.
+    ///
+    /// Volatility switches between two regimes whose transition rates are
+    /// estimated from the option's implied volatility. The estimate is a
+    /// single simulated path, so prices are random.
+    Telegraph {
+        /// Number of time steps in the simulated path
+        steps: NonZeroUsize,
+    },
+
+    /// Closed-form pricing for exotic option types.
+    ///
+    /// Routes Asian, barrier, binary, lookback, compound, chooser, cliquet,
+    /// rainbow, spread, quanto, exchange and power options to their
+    /// dedicated kernels. Vanilla exercise styles are rejected.
+    ClosedFormExotic,
+
+    /// Seeded, variance-reduced Monte Carlo under geometric Brownian motion.
+    ///
+    /// Prices European and path-dependent payoffs (Asian, barrier, binary,
+    /// lookback, quanto, power) with [`monte_carlo_price`]. Identical
+    /// configurations return identical prices, so bump-and-reprice Greeks
+    /// use common random numbers.
+    MonteCarloGbm {
+        /// Paths, steps, seed and variance-reduction settings
+        config: MonteCarloConfig,
+    },
+
+    /// Longstaff–Schwartz least-squares Monte Carlo on simulated paths.
+    ///
+    /// Prices European, American and Bermuda exercise on the random walks of
+    /// a `Simulator`, so early exercise can be valued under any `WalkType`
+    /// model. The walks must start at the option's underlying price and be
+    /// simulated under the risk-neutral measure.
+    LongstaffSchwartz {
+        /// The simulator holding the risk-neutral price paths
+        simulator: Simulator<Positive, Positive>,
+        /// Regression basis, degree and confidence settings
+        config: LsmConfig,
+    },
+
+    /// Crank–Nicolson finite differences on the Black–Scholes PDE.
+    ///
+    /// Prices European, American (PSOR) and Bermuda exercise, continuously
+    /// monitored barriers and binary options, with optional cash dividends.
+    /// Serves as the reference engine for the closed-form exotics.
+    FiniteDifference {
+        /// Grid, smoothing, PSOR and dividend settings
+        config: FiniteDifferenceConfig,
+    },
+
+    /// Heston stochastic-volatility pricing for European options.
+    ///
+    /// Integrates the Heston characteristic function with the Lewis (2001)
+    /// formula. The option's `implied_volatility` is ignored in favour of
+    /// the model's variance dynamics.
+    Heston {
+        /// Initial variance, mean reversion, long-run variance, vol of vol
+        /// and correlation
+        params: HestonParams,
+    },
+
+    /// Black–Scholes pricing at the SABR implied volatility of the strike.
+    ///
+    /// The option's `implied_volatility` is replaced by Hagan's SABR
+    /// volatility for the forward `S e^{(r - q)T}`, so a whole chain can be
+    /// priced off one set of quoted SABR parameters.
+    Sabr {
+        /// Alpha, beta, rho and nu of the SABR model
+        params: SabrParams,
+    },
+}
+
+impl PricingEngine {
+    /// Returns a short human-readable name of the engine, used in error messages.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            PricingEngine::ClosedFormBS => "Black-Scholes",
+            PricingEngine::ClosedFormBlack76 => "Black-76",
+            PricingEngine::MonteCarlo { .. } => "Monte Carlo",
+            PricingEngine::ClosedFormGK => "Garman-Kohlhagen",
+            PricingEngine::Binomial { .. } => "Binomial",
+            PricingEngine::BaroneAdesiWhaley => "Barone-Adesi-Whaley",
+            PricingEngine::Telegraph { .. } => "Telegraph",
+            PricingEngine::ClosedFormExotic => "Closed-form exotic",
+            PricingEngine::MonteCarloGbm { .. } => "Monte Carlo (GBM)",
+            PricingEngine::LongstaffSchwartz { .. } => "Longstaff-Schwartz",
+            PricingEngine::FiniteDifference { .. } => "Finite difference",
+            PricingEngine::Heston { .. } => "Heston",
+            PricingEngine::Sabr { .. } => "SABR",
+        }
+    }
+
+    /// Returns `true` when the engine can price options of `option_type`.
+    ///
+    /// [`price_option`] returns [`PricingError::UnsupportedOptionType`] for
+    /// every combination for which this method returns `false`. The
+    /// closed-form engines have no early-exercise formula of their own: as in
+    /// [`crate::greeks::greeks_with_engine`], American exercise is priced with
+    /// Barone-Adesi–Whaley (zero cost of carry under Black-76, `q = r_f`
+    /// under Garman–Kohlhagen) and Bermuda exercise with the binomial lattice.
+    #[must_use]
+    pub fn supports(&self, option_type: &OptionType) -> bool {
+        let vanilla = matches!(
+            option_type,
+            OptionType::European | OptionType::American | OptionType::Bermuda { .. }
+        );
+        match self {
+            PricingEngine::ClosedFormBS => true,
+            PricingEngine::ClosedFormBlack76 => {
+                matches!(option_type, OptionType::European | OptionType::American)
+            }
+            PricingEngine::ClosedFormGK => vanilla,
+            PricingEngine::Telegraph { .. }
+            | PricingEngine::Heston { .. }
+            | PricingEngine::Sabr { .. } => matches!(option_type, OptionType::European),
+            PricingEngine::MonteCarlo { .. } => true,
+            PricingEngine::Binomial { .. } | PricingEngine::LongstaffSchwartz { .. } => vanilla,
+            PricingEngine::BaroneAdesiWhaley => matches!(option_type, OptionType::American),
+            PricingEngine::ClosedFormExotic => !vanilla,
+            PricingEngine::FiniteDifference { .. } => {
+                vanilla
+                    || matches!(
+                        option_type,
+                        OptionType::Barrier { .. } | OptionType::Binary { .. }
+                    )
+            }
+            PricingEngine::MonteCarloGbm { .. } => matches!(
+                option_type,
+                OptionType::European
+                    | OptionType::Asian { .. }
+                    | OptionType::Barrier { .. }
+                    | OptionType::Binary { .. }
+                    | OptionType::Lookback { .. }
+                    | OptionType::Quanto { .. }
+                    | OptionType::Power { .. }
+            ),
+        }
+    }
+
+    /// The default engine for options of `option_type`.
+    ///
+    /// Black-Scholes for European exercise, Barone-Adesi–Whaley for American,
+    /// the binomial lattice with [`DEFAULT_BINOMIAL_STEPS`] for Bermuda and
+    /// the closed-form exotic formulas for everything else. The returned
+    /// engine always [`supports`](Self::supports) `option_type`.
+    #[must_use]
+    pub fn for_option_type(option_type: &OptionType) -> Self {
+        match option_type {
+            OptionType::European => PricingEngine::ClosedFormBS,
+            OptionType::American => PricingEngine::BaroneAdesiWhaley,
+            OptionType::Bermuda { .. } => PricingEngine::Binomial {
+                steps: DEFAULT_BINOMIAL_STEPS,
+            },
+            _ => PricingEngine::ClosedFormExotic,
+        }
+    }
 }
 
 /// Prices an option using the specified pricing engine.
@@ -83,6 +285,9 @@ pub enum PricingEngine {
 ///     option_style: OptionStyle::Call,
 ///     dividend_yield: pos_or_panic!(0.01),
 ///     exotic_params: None,
+///     yield_curve: None,
+///     dividends: Default::default(),
+///     day_count: Default::default(),
 /// };
 /// let engine = PricingEngine::ClosedFormBS;
 /// let price = price_option(&option, &engine)?;
@@ -96,20 +301,65 @@ pub enum PricingEngine {
 /// variants. From the closed-form engines (Black–Scholes, Black-76,
 /// Garman–Kohlhagen) you may receive [`PricingError::ExpirationDate`],
 /// [`PricingError::Greeks`] (for example zero-volatility or non-finite
-/// intermediate values bubbled up from `d1`/`d2`), and (Black-76 and
-/// Garman–Kohlhagen) [`PricingError::UnsupportedOptionType`] for
-/// non-European inputs. From the binomial lattice you may receive
-/// [`PricingError::BinomialNodeMissing`] or [`PricingError::SqrtFailure`].
+/// intermediate values bubbled up from `d1`/`d2`), the errors of the
+/// Barone-Adesi–Whaley and binomial engines for American and Bermuda
+/// exercise, and (Black-76 and Garman–Kohlhagen)
+/// [`PricingError::UnsupportedOptionType`] for the types rejected by
+/// [`PricingEngine::supports`]. The binomial, Barone-Adesi–Whaley, telegraph and
+/// closed-form exotic engines return [`PricingError::UnsupportedOptionType`]
+/// for any option type rejected by [`PricingEngine::supports`]. From the
+/// binomial lattice you may receive [`PricingError::BinomialNodeMissing`] or
+/// [`PricingError::SqrtFailure`]; Barone-Adesi–Whaley surfaces
+/// `PricingError::MethodError` when the critical price does not converge.
 /// The Monte Carlo engine surfaces failures as
-/// [`PricingError::SimulationError`], and exotic engines surface their
+/// [`PricingError::SimulationError`]; the GBM Monte Carlo engine returns
+/// [`PricingError::UnsupportedOptionType`] for unsupported types and
+/// [`PricingError::InvalidEngine`] for an inconsistent configuration, and
+/// the Longstaff–Schwartz engine returns [`PricingError::MethodError`] for
+/// malformed paths. The finite-difference engine returns
+/// [`PricingError::InvalidEngine`] for an invalid grid and
+/// [`PricingError::MethodError`] when PSOR does not converge. The Heston
+/// engine returns [`PricingError::InvalidEngine`] for invalid parameters and
+/// [`PricingError::MethodError`] when its integral does not converge; the
+/// SABR engine returns [`PricingError::MethodError`] when the Hagan
+/// expansion fails at the option's strike. Exotic
+/// engines surface their
 /// own variants (barrier, binary, compound, chooser, cliquet, lookback,
 /// telegraph).
 pub fn price_option(option: &Options, engine: &PricingEngine) -> PricingResult<Positive> {
     match engine {
+        PricingEngine::ClosedFormBS | PricingEngine::ClosedFormGK
+            if matches!(option.option_type, OptionType::American) =>
+        {
+            price_option(option, &PricingEngine::BaroneAdesiWhaley)
+        }
+        PricingEngine::ClosedFormBS | PricingEngine::ClosedFormGK
+            if matches!(option.option_type, OptionType::Bermuda { .. }) =>
+        {
+            price_option(
+                option,
+                &PricingEngine::Binomial {
+                    steps: DEFAULT_BINOMIAL_STEPS,
+                },
+            )
+        }
         PricingEngine::ClosedFormBS => {
             let price_decimal = black_scholes(option)?;
             Ok(Positive::new_decimal(price_decimal.abs())?)
         }
+        PricingEngine::ClosedFormBlack76 if matches!(option.option_type, OptionType::American) => {
+            // Futures carry no cost: BAW with the dividend yield set to the rate.
+            let price_decimal = barone_adesi_whaley(
+                option.underlying_price,
+                option.strike_price,
+                option.time_to_expiration()?,
+                option.risk_free_rate,
+                Positive::new_decimal(option.risk_free_rate)?,
+                option.implied_volatility,
+                &option.option_style,
+            )?;
+            Ok(Positive::new_decimal(price_decimal.abs())?)
+        }
         PricingEngine::ClosedFormBlack76 => {
             let price_decimal = black_76(option)?;
             Ok(Positive::new_decimal(price_decimal.abs())?)
@@ -121,6 +371,96 @@ pub fn price_option(option: &Options, engine: &PricingEngine) -> PricingResult<P
             let price_decimal = garman_kohlhagen(option)?;
             Ok(Positive::new_decimal(price_decimal.abs())?)
         }
+        PricingEngine::Binomial { steps } => {
+            ensure_supported(option, engine)?;
+            let params = BinomialPricingParams {
+                asset: option.underlying_price,
+                volatility: option.implied_volatility,
+                int_rate: option.term_risk_free_rate()?,
+                dividend_yield: option.dividend_yield,
+                strike: option.strike_price,
+                expiry: option.time_to_expiration()?,
+                no_steps: *steps,
+                option_type: &option.option_type,
+                option_style: &option.option_style,
+                side: &Side::Long,
+            };
+            let price_decimal = if option.dividends.is_empty() {
+                price_binomial(params)?
+            } else {
+                price_binomial_with_dividends(
+                    params,
+                    &option.dividends,
+                    &option.day_count,
+                    &option.discount_curve(),
+                )?
+            };
+            Ok(Positive::new_decimal(price_decimal.abs())?)
+        }
+        PricingEngine::BaroneAdesiWhaley => {
+            ensure_supported(option, engine)?;
+            // Term rate and escrowed spot for options on a curve or with cash
+            // dividends, as for Black-Scholes.
+            let escrowed = option.escrowed()?;
+            let option = escrowed.as_ref().unwrap_or(option);
+            let price_decimal = barone_adesi_whaley(
+                option.underlying_price,
+                option.strike_price,
+                option.time_to_expiration()?,
+                option.risk_free_rate,
+                option.dividend_yield,
+                option.implied_volatility,
+                &option.option_style,
+            )?;
+            Ok(Positive::new_decimal(price_decimal.abs())?)
+        }
+        PricingEngine::Telegraph { steps } => {
+            ensure_supported(option, engine)?;
+            let price_decimal = telegraph(option, *steps, None, None)?;
+            Ok(Positive::new_decimal(price_decimal.abs())?)
+        }
+        PricingEngine::ClosedFormExotic => {
+            ensure_supported(option, engine)?;
+            let price_decimal = black_scholes(option)?;
+            Ok(Positive::new_decimal(price_decimal.abs())?)
+        }
+        PricingEngine::MonteCarloGbm { config } => {
+            ensure_supported(option, engine)?;
+            let result = monte_carlo_price(option, config)?;
+            Ok(Positive::new_decimal(result.price.abs())?)
+        }
+        PricingEngine::LongstaffSchwartz { simulator, config } => {
+            ensure_supported(option, engine)?;
+            let result = longstaff_schwartz_simulator(option, simulator, config)?;
+            Ok(Positive::new_decimal(result.price.abs())?)
+        }
+        PricingEngine::FiniteDifference { config } => {
+            ensure_supported(option, engine)?;
+            let result = finite_difference_price(option, config)?;
+            Ok(Positive::new_decimal(result.price.abs())?)
+        }
+        PricingEngine::Heston { params } => {
+            ensure_supported(option, engine)?;
+            let price_decimal = heston_price(option, params)?;
+            Ok(Positive::new_decimal(price_decimal.abs())?)
+        }
+        PricingEngine::Sabr { params } => {
+            ensure_supported(option, engine)?;
+            let price_decimal = sabr_price(option, params)?;
+            Ok(Positive::new_decimal(price_decimal.abs())?)
+        }
+    }
+}
+
+/// Rejects option types the engine cannot price with a typed error.
+fn ensure_supported(option: &Options, engine: &PricingEngine) -> PricingResult<()> {
+    if engine.supports(&option.option_type) {
+        Ok(())
+    } else {
+        Err(PricingError::unsupported_option_type(
+            &option.option_type.to_string(),
+            engine.name(),
+        ))
     }
 }
 
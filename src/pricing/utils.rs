@@ -159,7 +159,8 @@ pub(crate) fn calculate_down_factor(
 ///
 /// # Arguments
 ///
-/// * `int_rate` - The interest rate as a floating-point number.
+/// * `int_rate` - The drift rate of the underlying: the interest rate net
+///   of any continuous dividend yield.
 /// * `dt` - The time interval as a floating-point number.
 /// * `down_factor` - The down factor as a floating-point number.
 /// * `up_factor` - The up factor as a floating-point number.
@@ -326,7 +327,7 @@ pub(crate) fn calculate_option_price(
 /// The function takes into account the future asset price, the interest rate, the expiry time,
 /// the type of option (call or put), and the style of the option (European or American).
 ///
-/// It adjusts the future asset price with the provided interest rate and expiry time,
+/// It adjusts the future asset price with the interest rate net of the dividend yield,
 /// calculates the payoff, discounts it by the interest rate, and then adjusts for the side
 /// of the trade (long or short).
 ///
@@ -341,7 +342,8 @@ pub(crate) fn calculate_discounted_payoff(
     params: BinomialPricingParams,
 ) -> Result<Decimal, PricingError> {
     let info = PayoffInfo {
-        spot: params.asset * (params.int_rate * params.expiry).exp(),
+        spot: params.asset
+            * ((params.int_rate - params.dividend_yield.to_dec()) * params.expiry).exp(),
         strike: params.strike,
         style: *params.option_style,
         side: *params.side,
@@ -441,7 +443,7 @@ pub fn probability_keep_under_strike(
         Some(strike) => strike,
         None => option.strike_price,
     };
-    let years = option.expiration_date.get_years()?;
+    let years = option.years_to_expiry()?;
     let d2_val = d2(
         option.underlying_price,
         strike_price,
@@ -688,6 +690,9 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ONE,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
         let strike = spos!(100.0);
         let probability = probability_keep_under_strike(option, strike).unwrap();
@@ -710,6 +715,9 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
         let strike = None;
         let probability = probability_keep_under_strike(option, strike).unwrap();
@@ -737,6 +745,9 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
         let strike = None;
         assert!(
@@ -760,6 +771,9 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
         let strike = None;
         let probability = probability_keep_under_strike(option, strike).unwrap();
@@ -784,6 +798,9 @@ mod tests_probability_keep_under_strike {
             underlying_symbol: "".to_string(),
             quantity: Positive::ZERO,
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         };
         let strike = None;
         let probability = probability_keep_under_strike(option, strike).unwrap();
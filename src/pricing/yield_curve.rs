@@ -0,0 +1,445 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Interest-rate term structure.
+//!
+//! A [`YieldCurve`] holds discount factors at pillar times and interpolates
+//! them log-linearly, i.e. with piecewise-constant continuously compounded
+//! forward rates. Beyond the last pillar the last forward rate is held flat.
+//!
+//! Curves are bootstrapped from [`CurveInstrument`] quotes in order of
+//! maturity: each instrument fixes the discount factor at its tenor so that
+//! it reprices exactly, given the pillars already solved.
+
+use crate::error::PricingError;
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use utoipa::ToSchema;
+
+/// Bisection iterations used to solve each pillar.
+const BOOTSTRAP_ITERATIONS: usize = 100;
+
+/// Bounds of the log discount factor searched by the bootstrap.
+const MIN_LOG_DISCOUNT: Decimal = dec!(-10);
+const MAX_LOG_DISCOUNT: Decimal = dec!(1);
+
+/// A market quote used to bootstrap a [`YieldCurve`].
+///
+/// Tenors are year fractions from today and rates are annualised decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum CurveInstrument {
+    /// Money-market deposit with simple interest, repricing when
+    /// `DF(T)·(1 + r·T) = 1`.
+    Deposit {
+        /// Maturity in years.
+        tenor: Positive,
+        /// Simple deposit rate.
+        rate: Decimal,
+    },
+    /// Par interest-rate swap whose fixed leg pays `rate` `frequency` times a
+    /// year, repricing when `rate·Σ αᵢ·DF(tᵢ) + DF(T) = 1`.
+    Swap {
+        /// Maturity in years.
+        tenor: Positive,
+        /// Par fixed rate.
+        rate: Decimal,
+        /// Fixed payments per year.
+        #[schema(value_type = u32)]
+        frequency: NonZeroU32,
+    },
+    /// Treasury bill or bond paying `coupon` `frequency` times a year, quoted
+    /// at `price` per unit of face value. A bill has a zero coupon.
+    Treasury {
+        /// Maturity in years.
+        tenor: Positive,
+        /// Annual coupon rate.
+        coupon: Decimal,
+        /// Coupon payments per year.
+        #[schema(value_type = u32)]
+        frequency: NonZeroU32,
+        /// Clean price per unit of face value.
+        price: Positive,
+    },
+}
+
+impl CurveInstrument {
+    /// Maturity of the instrument in years.
+    #[must_use]
+    pub fn tenor(&self) -> Positive {
+        match self {
+            CurveInstrument::Deposit { tenor, .. }
+            | CurveInstrument::Swap { tenor, .. }
+            | CurveInstrument::Treasury { tenor, .. } => *tenor,
+        }
+    }
+
+    /// Model value minus market value per unit notional on `curve`.
+    fn mispricing(&self, curve: &YieldCurve) -> Decimal {
+        match self {
+            CurveInstrument::Deposit { tenor, rate } => {
+                curve.discount_factor(*tenor) * (Decimal::ONE + rate * tenor.to_dec())
+                    - Decimal::ONE
+            }
+            CurveInstrument::Swap {
+                tenor,
+                rate,
+                frequency,
+            } => {
+                let annuity = annuity(curve, *tenor, *frequency);
+                rate * annuity + curve.discount_factor(*tenor) - Decimal::ONE
+            }
+            CurveInstrument::Treasury {
+                tenor,
+                coupon,
+                frequency,
+                price,
+            } => {
+                let annuity = annuity(curve, *tenor, *frequency);
+                coupon * annuity + curve.discount_factor(*tenor) - price.to_dec()
+            }
+        }
+    }
+}
+
+/// `Σ αᵢ·DF(tᵢ)` over a schedule rolled back from `tenor` every
+/// `1/frequency` years, with a short first period when needed.
+fn annuity(curve: &YieldCurve, tenor: Positive, frequency: NonZeroU32) -> Decimal {
+    let period = Decimal::ONE / Decimal::from(frequency.get());
+    let mut annuity = Decimal::ZERO;
+    let mut end = tenor.to_dec();
+    while end > Decimal::ZERO {
+        let start = (end - period).max(Decimal::ZERO);
+        let payment = Positive::new_decimal(end).unwrap_or(Positive::ZERO);
+        annuity += (end - start) * curve.discount_factor(payment);
+        end = start;
+    }
+    annuity
+}
+
+/// Discount curve with log-linear interpolation of discount factors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct YieldCurve {
+    /// Pillar times in years, increasing, with their log discount factors.
+    pillars: Vec<(Positive, Decimal)>,
+}
+
+impl YieldCurve {
+    /// A curve with the same continuously compounded `rate` at every tenor.
+    #[must_use]
+    pub fn flat(rate: Decimal) -> Self {
+        YieldCurve {
+            pillars: vec![(Positive::ONE, -rate)],
+        }
+    }
+
+    /// Builds a curve from continuously compounded zero rates at pillar
+    /// times.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PricingError::MethodError`] when no rates are given or two
+    /// pillars share a time, including time zero.
+    pub fn from_zero_rates(zero_rates: &[(Positive, Decimal)]) -> Result<Self, PricingError> {
+        let mut pillars: Vec<(Positive, Decimal)> = zero_rates
+            .iter()
+            .map(|(time, rate)| (*time, -rate * time.to_dec()))
+            .collect();
+        pillars.sort_by_key(|(time, _)| *time);
+        Self::validate(&pillars)?;
+        Ok(YieldCurve { pillars })
+    }
+
+    /// Bootstraps a curve from deposit, swap and treasury quotes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PricingError::MethodError`] when no instruments are given,
+    /// two instruments share a tenor, a tenor is zero, or no discount factor
+    /// reprices an instrument.
+    pub fn bootstrap(instruments: &[CurveInstrument]) -> Result<Self, PricingError> {
+        let mut instruments = instruments.to_vec();
+        instruments.sort_by_key(CurveInstrument::tenor);
+        Self::validate(
+            &instruments
+                .iter()
+                .map(|instrument| (instrument.tenor(), Decimal::ZERO))
+                .collect::<Vec<_>>(),
+        )?;
+
+        let mut curve = YieldCurve {
+            pillars: Vec::with_capacity(instruments.len()),
+        };
+        for instrument in &instruments {
+            curve.pillars.push((instrument.tenor(), Decimal::ZERO));
+            let mispricing = |curve: &mut YieldCurve, log_discount: Decimal| {
+                if let Some(pillar) = curve.pillars.last_mut() {
+                    pillar.1 = log_discount;
+                }
+                instrument.mispricing(curve)
+            };
+            let (mut low, mut high) = (MIN_LOG_DISCOUNT, MAX_LOG_DISCOUNT);
+            if mispricing(&mut curve, low) > Decimal::ZERO
+                || mispricing(&mut curve, high) < Decimal::ZERO
+            {
+                return Err(PricingError::method_error(
+                    "yield_curve::bootstrap",
+                    &format!(
+                        "no discount factor reprices the instrument at tenor {}",
+                        instrument.tenor()
+                    ),
+                ));
+            }
+            for _ in 0..BOOTSTRAP_ITERATIONS {
+                let middle = (low + high) / Decimal::TWO;
+                if mispricing(&mut curve, middle) > Decimal::ZERO {
+                    high = middle;
+                } else {
+                    low = middle;
+                }
+            }
+            mispricing(&mut curve, (low + high) / Decimal::TWO);
+        }
+        Ok(curve)
+    }
+
+    fn validate(pillars: &[(Positive, Decimal)]) -> Result<(), PricingError> {
+        if pillars.is_empty() {
+            return Err(PricingError::method_error(
+                "yield_curve",
+                "a curve needs at least one pillar",
+            ));
+        }
+        let mut previous = Positive::ZERO;
+        for (time, _) in pillars {
+            if *time <= previous {
+                return Err(PricingError::method_error(
+                    "yield_curve",
+                    &format!("pillar times must be positive and distinct, got {time}"),
+                ));
+            }
+            previous = *time;
+        }
+        Ok(())
+    }
+
+    /// Pillar times in years with their discount factors.
+    #[must_use]
+    pub fn pillars(&self) -> Vec<(Positive, Decimal)> {
+        self.pillars
+            .iter()
+            .map(|(time, log_discount)| (*time, log_discount.exp()))
+            .collect()
+    }
+
+    /// Natural log of the discount factor at `time` years.
+    fn log_discount(&self, time: Positive) -> Decimal {
+        let time = time.to_dec();
+        let mut previous = (Decimal::ZERO, Decimal::ZERO);
+        for (pillar_time, log_discount) in &self.pillars {
+            let pillar_time = pillar_time.to_dec();
+            if time <= pillar_time {
+                let weight = (time - previous.0) / (pillar_time - previous.0);
+                return previous.1 + weight * (log_discount - previous.1);
+            }
+            previous = (pillar_time, *log_discount);
+        }
+        // Extrapolate with the forward rate of the last segment.
+        let last_forward = match self.pillars.len() {
+            0 => Decimal::ZERO,
+            1 => -previous.1 / previous.0,
+            n => self
+                .pillars
+                .get(n - 2)
+                .map(|(time, log_discount)| {
+                    (log_discount - previous.1) / (previous.0 - time.to_dec())
+                })
+                .unwrap_or(Decimal::ZERO),
+        };
+        previous.1 - last_forward * (time - previous.0)
+    }
+
+    /// Discount factor for a cash flow at `time` years.
+    #[must_use]
+    pub fn discount_factor(&self, time: Positive) -> Decimal {
+        self.log_discount(time).exp()
+    }
+
+    /// Continuously compounded zero rate to `time` years. At time zero this
+    /// is the forward rate of the first segment.
+    #[must_use]
+    pub fn zero_rate(&self, time: Positive) -> Decimal {
+        if time == Positive::ZERO {
+            return match self.pillars.first() {
+                Some((first, log_discount)) => -log_discount / first.to_dec(),
+                None => Decimal::ZERO,
+            };
+        }
+        -self.log_discount(time) / time.to_dec()
+    }
+
+    /// Continuously compounded forward rate between `start` and `end` years.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PricingError::MethodError`] when `end` is not after `start`.
+    pub fn forward_rate(&self, start: Positive, end: Positive) -> Result<Decimal, PricingError> {
+        if end <= start {
+            return Err(PricingError::method_error(
+                "yield_curve::forward_rate",
+                &format!("end {end} must be after start {start}"),
+            ));
+        }
+        Ok((self.log_discount(start) - self.log_discount(end)) / (end - start).to_dec())
+    }
+
+    /// The curve with every zero and forward rate moved by `shift`.
+    #[must_use]
+    pub fn parallel_shift(&self, shift: Decimal) -> Self {
+        YieldCurve {
+            pillars: self
+                .pillars
+                .iter()
+                .map(|(time, log_discount)| (*time, log_discount - shift * time.to_dec()))
+                .collect(),
+        }
+    }
+
+    /// Forward rates over `steps` equal periods up to `time` years, as
+    /// `f64` for simulation kernels.
+    pub(crate) fn step_forward_rates(&self, time: Positive, steps: usize) -> Vec<f64> {
+        let dt = time.to_dec() / Decimal::from(steps.max(1));
+        (0..steps)
+            .map(|step| {
+                let start = Positive::new_decimal(dt * Decimal::from(step)).unwrap_or_default();
+                let end = Positive::new_decimal(dt * Decimal::from(step + 1)).unwrap_or_default();
+                self.forward_rate(start, end)
+                    .map(|rate| rate.to_f64().unwrap_or(0.0))
+                    .unwrap_or_else(|_| self.zero_rate(start).to_f64().unwrap_or(0.0))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests_yield_curve {
+    use super::*;
+    use positive::pos_or_panic;
+
+    fn close(a: Decimal, b: Decimal) -> bool {
+        (a - b).abs() < dec!(0.0000001)
+    }
+
+    #[test]
+    fn test_flat_curve() {
+        let curve = YieldCurve::flat(dec!(0.05));
+        assert!(close(curve.zero_rate(pos_or_panic!(0.5)), dec!(0.05)));
+        assert!(close(curve.zero_rate(pos_or_panic!(10.0)), dec!(0.05)));
+        assert!(close(curve.zero_rate(Positive::ZERO), dec!(0.05)));
+        assert!(close(
+            curve.discount_factor(Positive::TWO),
+            dec!(-0.1).exp()
+        ));
+        assert!(close(
+            curve
+                .forward_rate(Positive::ONE, pos_or_panic!(3.0))
+                .unwrap(),
+            dec!(0.05)
+        ));
+    }
+
+    #[test]
+    fn test_log_linear_interpolation() {
+        let curve = YieldCurve::from_zero_rates(&[
+            (Positive::ONE, dec!(0.02)),
+            (Positive::TWO, dec!(0.04)),
+        ])
+        .unwrap();
+        // ln DF(1.5) is halfway between -0.02 and -0.08.
+        assert!(close(
+            curve.discount_factor(pos_or_panic!(1.5)),
+            dec!(-0.05).exp()
+        ));
+        // Flat 6% forward beyond the last pillar.
+        assert!(close(
+            curve.forward_rate(Positive::TWO, Positive::FIVE).unwrap(),
+            dec!(0.06)
+        ));
+        assert!(curve.forward_rate(Positive::TWO, Positive::ONE).is_err());
+    }
+
+    #[test]
+    fn test_parallel_shift() {
+        let curve = YieldCurve::from_zero_rates(&[
+            (Positive::ONE, dec!(0.02)),
+            (Positive::TWO, dec!(0.04)),
+        ])
+        .unwrap()
+        .parallel_shift(dec!(0.01));
+        assert!(close(curve.zero_rate(Positive::ZERO), dec!(0.03)));
+        assert!(close(
+            curve.zero_rate(pos_or_panic!(1.5)),
+            dec!(0.0433333333)
+        ));
+        assert!(close(curve.zero_rate(Positive::TWO), dec!(0.05)));
+        assert!(close(
+            curve.forward_rate(Positive::TWO, Positive::FIVE).unwrap(),
+            dec!(0.07)
+        ));
+    }
+
+    #[test]
+    fn test_bootstrap_reprices_instruments() {
+        let instruments = [
+            CurveInstrument::Deposit {
+                tenor: pos_or_panic!(0.25),
+                rate: dec!(0.03),
+            },
+            CurveInstrument::Treasury {
+                tenor: Positive::ONE,
+                coupon: Decimal::ZERO,
+                frequency: NonZeroU32::MIN,
+                price: pos_or_panic!(0.965),
+            },
+            CurveInstrument::Swap {
+                tenor: Positive::TWO,
+                rate: dec!(0.038),
+                frequency: NonZeroU32::MIN,
+            },
+            CurveInstrument::Treasury {
+                tenor: Positive::FIVE,
+                coupon: dec!(0.04),
+                frequency: NonZeroU32::MIN.saturating_add(1),
+                price: pos_or_panic!(0.99),
+            },
+        ];
+        let curve = YieldCurve::bootstrap(&instruments).unwrap();
+        assert_eq!(curve.pillars().len(), 4);
+        for instrument in &instruments {
+            assert!(instrument.mispricing(&curve).abs() < dec!(0.000000001));
+        }
+        assert!(close(
+            curve.discount_factor(pos_or_panic!(0.25)),
+            Decimal::ONE / dec!(1.0075)
+        ));
+        let rate = curve.zero_rate(Positive::TWO);
+        assert!(rate > dec!(0.03) && rate < dec!(0.045));
+    }
+
+    #[test]
+    fn test_bootstrap_errors() {
+        assert!(YieldCurve::bootstrap(&[]).is_err());
+        let deposit = CurveInstrument::Deposit {
+            tenor: Positive::ONE,
+            rate: dec!(0.03),
+        };
+        assert!(YieldCurve::bootstrap(&[deposit, deposit]).is_err());
+        assert!(YieldCurve::from_zero_rates(&[(Positive::ZERO, dec!(0.01))]).is_err());
+    }
+}
@@ -122,6 +122,9 @@ use positive::pos_or_panic;
 //!         option_style: OptionStyle::Call,
 //!         dividend_yield: pos_or_panic!(0.01),
 //!         exotic_params: None,
+//!         yield_curve: None,
+//!         dividends: Default::default(),
+//!         day_count: Default::default(),
 //!     };
 //!     // Create multiple positions
 //!     let positions = vec![
@@ -156,6 +159,153 @@ use positive::pos_or_panic;
 //! }
 //! ```
 //!
+//! ### Portfolio Margin
+//!
+//! `PortfolioSPANMargin` margins a set of legs (options, futures, spot) on one
+//! underlying together, using the standard 16-scenario risk array with time
+//! decay and extreme moves, intra-commodity spread and delivery month charges,
+//! and the short option minimum:
+//!
+//! ```rust
+//! use optionstratlib::model::leg::{FuturePosition, Leg};
+//! use optionstratlib::risk::PortfolioSPANMargin;
+//! use optionstratlib::ExpirationDate;
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal_macros::dec;
+//!
+//! fn main() -> Result<(), optionstratlib::error::PricingError> {
+//!     let legs = vec![
+//!         Leg::future(FuturePosition::long(
+//!             "ESZ6".to_string(),
+//!             Positive::ONE,
+//!             pos_or_panic!(5000.0),
+//!             ExpirationDate::Days(pos_or_panic!(60.0)),
+//!             pos_or_panic!(50.0),
+//!             pos_or_panic!(12000.0),
+//!         )),
+//!         Leg::future(FuturePosition::short(
+//!             "ESH7".to_string(),
+//!             Positive::ONE,
+//!             pos_or_panic!(5050.0),
+//!             ExpirationDate::Days(pos_or_panic!(150.0)),
+//!             pos_or_panic!(50.0),
+//!             pos_or_panic!(12000.0),
+//!         )),
+//!     ];
+//!     let span = PortfolioSPANMargin::default().with_intra_spread_charge(dec!(4.0));
+//!     let report = span.calculate_margin(&legs, pos_or_panic!(5000.0))?;
+//!     for scenario in &report.scenarios {
+//!         println!("{}: {}", scenario.number, scenario.loss);
+//!     }
+//!     assert_eq!(report.total_margin, report.intra_commodity_charge);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Strategy-Based Margin
+//!
+//! `RegTMargin` applies the CBOE strategy-based rules used for retail
+//! accounts: naked short options, spreads, straddles and stock-covered
+//! positions, reporting the buying-power reduction:
+//!
+//! ```rust
+//! use optionstratlib::ExpirationDate;
+//! use optionstratlib::risk::{MarginTreatment, RegTMargin};
+//! use optionstratlib::strategies::ShortPut;
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal_macros::dec;
+//!
+//! fn main() -> Result<(), optionstratlib::error::StrategyError> {
+//!     let short_put = ShortPut::new(
+//!         "AAPL".to_string(),
+//!         pos_or_panic!(95.0),
+//!         ExpirationDate::Days(pos_or_panic!(30.0)),
+//!         pos_or_panic!(0.2),
+//!         Positive::ONE,
+//!         Positive::HUNDRED,
+//!         dec!(0.05),
+//!         Positive::ZERO,
+//!         pos_or_panic!(2.0),
+//!         Positive::ZERO,
+//!         Positive::ZERO,
+//!     )?;
+//!     let margin = RegTMargin::default().strategy_margin(&short_put)?;
+//!     assert_eq!(margin.treatment, MarginTreatment::Naked);
+//!     assert_eq!(margin.buying_power_reduction, dec!(15.0));
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Value at Risk
+//!
+//! `ValueAtRisk` computes VaR and Expected Shortfall of a set of legs with
+//! parametric delta-gamma, historical simulation over `OhlcvCandle` closes
+//! and Monte Carlo over a `WalkType` model, each filling a
+//! `RiskMetricsSimulation`:
+//!
+//! ```rust
+//! use optionstratlib::model::leg::{Leg, SpotPosition};
+//! use optionstratlib::risk::ValueAtRisk;
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal_macros::dec;
+//!
+//! fn main() -> Result<(), optionstratlib::error::SimulationError> {
+//!     let legs = vec![Leg::spot(SpotPosition::long(
+//!         "AAPL".to_string(),
+//!         Positive::TEN,
+//!         Positive::HUNDRED,
+//!     ))];
+//!     let var = ValueAtRisk::default()
+//!         .with_confidence(dec!(0.99))
+//!         .with_horizon_days(pos_or_panic!(10.0));
+//!     let result = var.delta_gamma(&legs, Positive::HUNDRED, pos_or_panic!(0.25))?;
+//!     assert!(result.expected_shortfall >= result.value_at_risk);
+//!     println!("99% 10-day VaR: {}", result.value_at_risk);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Stress Scenarios
+//!
+//! `ScenarioEngine` revalues a portfolio under named scenarios combining a
+//! spot shock, a parallel, tilted or skewed volatility surface shift, a rate
+//! shift and a time roll. Replays of historical crashes are built in:
+//!
+//! ```rust
+//! use optionstratlib::ExpirationDate;
+//! use optionstratlib::risk::{ScenarioEngine, StressScenario, VolatilityShift};
+//! use optionstratlib::strategies::ShortPut;
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal_macros::dec;
+//!
+//! fn main() -> Result<(), optionstratlib::error::Error> {
+//!     let short_put = ShortPut::new(
+//!         "SPX".to_string(),
+//!         pos_or_panic!(95.0),
+//!         ExpirationDate::Days(pos_or_panic!(30.0)),
+//!         pos_or_panic!(0.2),
+//!         Positive::ONE,
+//!         Positive::HUNDRED,
+//!         dec!(0.05),
+//!         Positive::ZERO,
+//!         pos_or_panic!(2.0),
+//!         Positive::ZERO,
+//!         Positive::ZERO,
+//!     )?;
+//!     let engine = ScenarioEngine::default()
+//!         .with_scenario(
+//!             StressScenario::new("Down 10%, vol up 8")
+//!                 .with_spot_shock(dec!(-0.10))
+//!                 .with_volatility_shift(VolatilityShift::parallel(dec!(0.08)).with_skew(dec!(0.2))),
+//!         )
+//!         .with_historical_replays();
+//!     for result in engine.run_strategy(&short_put)? {
+//!         println!("{:<20} {:>10.2}", result.scenario, result.pnl);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
 //! ## Implementation Details
 //!
 //! ### Risk Array Calculation
@@ -188,7 +338,16 @@ use positive::pos_or_panic;
 //! - Results are conservative estimates of potential losses
 
 mod model;
+mod portfolio_span;
+mod reg_t;
+pub(crate) mod revaluation;
+mod scenario;
 mod span;
+mod var;
 
 pub use model::{RiskCategory, RiskMetricsSimulation};
+pub use portfolio_span::{PortfolioSPANMargin, SPANMarginReport, SPANScenario};
+pub use reg_t::{MarginRequirement, MarginTreatment, RegTMargin};
+pub use scenario::{ScenarioEngine, ScenarioResult, StressScenario, VolatilityShift};
 pub use span::SPANMargin;
+pub use var::{VaRMethod, VaRMetrics, VaRReport, VaRResult, ValueAtRisk};
@@ -0,0 +1,492 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+use crate::error::PricingError;
+use crate::greeks::Greeks;
+use crate::model::ExpirationDate;
+use crate::model::leg::{Leg, LegAble};
+use crate::model::option::Options;
+use crate::risk::revaluation::Revaluation;
+use chrono::Datelike;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::BTreeMap;
+
+/// Price moves of the thirteen ordinary scenarios that are scanned with both
+/// volatility up and volatility down, as fractions of the price scan range.
+const PRICE_MOVES: [Decimal; 7] = [
+    Decimal::ZERO,
+    dec!(0.3333333333333333333333333333),
+    dec!(-0.3333333333333333333333333333),
+    dec!(0.6666666666666666666666666667),
+    dec!(-0.6666666666666666666666666667),
+    Decimal::ONE,
+    Decimal::NEGATIVE_ONE,
+];
+
+/// Portfolio SPAN margin calculator for a combined commodity.
+///
+/// Unlike [`SPANMargin`](super::SPANMargin), which margins a single option
+/// position, this calculator margins a set of [`Leg`]s on the same underlying
+/// together, so offsetting legs reduce the requirement the way a clearing
+/// house does. The requirement is built from:
+///
+/// - the scanning risk, the worst loss of the standard 16-scenario risk
+///   array: price unchanged and up/down one, two and three thirds of the
+///   price scan range, each with volatility up and down, plus two extreme
+///   moves of `extreme_move_multiple` times the range of which only
+///   `extreme_cover_fraction` of the loss counts. Options are revalued
+///   `look_ahead_days` closer to expiry, so time decay is part of every
+///   scenario;
+/// - the intra-commodity spread charge, `intra_spread_charge` per unit of
+///   delta that offsets between contract months, since the scan assumes
+///   all months move together;
+/// - the delivery month charge, `delivery_month_charge` per unit of net
+///   delta in legs expiring within `delivery_window_days`;
+/// - the short option minimum, `short_option_minimum` times the underlying
+///   price per short option, which floors the total.
+///
+/// The volatility scan range is relative to each option's implied
+/// volatility. Perpetual and spot legs count in the scan but belong to no
+/// contract month. All fields are exchange-specific; the charges default to
+/// zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioSPANMargin {
+    /// Price scan range as a fraction of the underlying price
+    pub price_scan_range: Decimal,
+    /// Volatility scan range as a fraction of each leg's implied volatility
+    pub volatility_scan_range: Decimal,
+    /// Size of the extreme moves in multiples of the price scan range
+    pub extreme_move_multiple: Decimal,
+    /// Fraction of the extreme-move loss included in the scanning risk
+    pub extreme_cover_fraction: Decimal,
+    /// Days of time decay applied to options in every scenario
+    pub look_ahead_days: Positive,
+    /// Charge per unit of delta spread between contract months
+    pub intra_spread_charge: Decimal,
+    /// Charge per unit of net delta in the delivery month
+    pub delivery_month_charge: Decimal,
+    /// Legs expiring within this many days are in the delivery month
+    pub delivery_window_days: Positive,
+    /// Minimum charge per short option as a fraction of the underlying price
+    pub short_option_minimum: Decimal,
+}
+
+impl Default for PortfolioSPANMargin {
+    fn default() -> Self {
+        PortfolioSPANMargin {
+            price_scan_range: dec!(0.06),
+            volatility_scan_range: dec!(0.10),
+            extreme_move_multiple: dec!(3),
+            extreme_cover_fraction: dec!(0.35),
+            look_ahead_days: Positive::ONE,
+            intra_spread_charge: Decimal::ZERO,
+            delivery_month_charge: Decimal::ZERO,
+            delivery_window_days: Positive::ZERO,
+            short_option_minimum: Decimal::ZERO,
+        }
+    }
+}
+
+/// One scenario of the SPAN risk array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SPANScenario {
+    /// Scenario number, 1 to 16 in the standard SPAN order
+    pub number: usize,
+    /// Underlying move as a fraction of the current price
+    pub price_move: Decimal,
+    /// Volatility move as a fraction of each implied volatility
+    pub volatility_move: Decimal,
+    /// Fraction of the loss that counts toward the scanning risk
+    pub cover_fraction: Decimal,
+    /// Underlying price in the scenario
+    pub underlying_price: Positive,
+    /// Covered portfolio loss, negative when the scenario is a gain
+    pub loss: Decimal,
+}
+
+/// Portfolio SPAN requirement with its components.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SPANMarginReport {
+    /// The 16 scenarios of the risk array
+    pub scenarios: Vec<SPANScenario>,
+    /// Worst covered scenario loss, floored at zero
+    pub scanning_risk: Decimal,
+    /// Charge for delta spread between contract months
+    pub intra_commodity_charge: Decimal,
+    /// Charge for delta in the delivery month
+    pub delivery_month_charge: Decimal,
+    /// Floor for portfolios with short options
+    pub short_option_minimum: Decimal,
+    /// Margin requirement: the larger of the risk charges and the short
+    /// option minimum
+    pub total_margin: Decimal,
+}
+
+impl SPANMarginReport {
+    /// The scenario that sets the scanning risk, if any scenario is a loss.
+    #[must_use]
+    pub fn active_scenario(&self) -> Option<&SPANScenario> {
+        self.scenarios
+            .iter()
+            .filter(|scenario| scenario.loss > Decimal::ZERO)
+            .max_by(|a, b| a.loss.cmp(&b.loss))
+    }
+}
+
+impl PortfolioSPANMargin {
+    /// Sets the price scan range.
+    pub fn with_price_scan_range(mut self, price_scan_range: Decimal) -> Self {
+        self.price_scan_range = price_scan_range;
+        self
+    }
+
+    /// Sets the volatility scan range.
+    pub fn with_volatility_scan_range(mut self, volatility_scan_range: Decimal) -> Self {
+        self.volatility_scan_range = volatility_scan_range;
+        self
+    }
+
+    /// Sets the extreme move multiple and its cover fraction.
+    pub fn with_extreme_move(mut self, multiple: Decimal, cover_fraction: Decimal) -> Self {
+        self.extreme_move_multiple = multiple;
+        self.extreme_cover_fraction = cover_fraction;
+        self
+    }
+
+    /// Sets the time decay horizon of the scenarios.
+    pub fn with_look_ahead_days(mut self, look_ahead_days: Positive) -> Self {
+        self.look_ahead_days = look_ahead_days;
+        self
+    }
+
+    /// Sets the intra-commodity spread charge per unit of delta.
+    pub fn with_intra_spread_charge(mut self, intra_spread_charge: Decimal) -> Self {
+        self.intra_spread_charge = intra_spread_charge;
+        self
+    }
+
+    /// Sets the delivery month charge and the window that defines the
+    /// delivery month.
+    pub fn with_delivery_month_charge(mut self, charge: Decimal, window_days: Positive) -> Self {
+        self.delivery_month_charge = charge;
+        self.delivery_window_days = window_days;
+        self
+    }
+
+    /// Sets the short option minimum.
+    pub fn with_short_option_minimum(mut self, short_option_minimum: Decimal) -> Self {
+        self.short_option_minimum = short_option_minimum;
+        self
+    }
+
+    /// Calculates the portfolio SPAN requirement of `legs`, all on an
+    /// underlying trading at `underlying_price`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::MethodError` when the scan ranges would move
+    /// the price or volatility to zero or below, and propagates the pricing,
+    /// Greeks and expiration errors of the option legs.
+    pub fn calculate_margin(
+        &self,
+        legs: &[Leg],
+        underlying_price: Positive,
+    ) -> Result<SPANMarginReport, PricingError> {
+        self.validate()?;
+        let revaluation = Revaluation::new(legs, underlying_price)?;
+        let scenarios = self.risk_array(&revaluation, underlying_price)?;
+        let scanning_risk = scenarios
+            .iter()
+            .map(|scenario| scenario.loss)
+            .fold(Decimal::ZERO, Decimal::max);
+
+        let month_deltas = self.month_deltas(legs, revaluation.options())?;
+        let long_delta: Decimal = month_deltas
+            .iter()
+            .map(|(_, delta)| (*delta).max(Decimal::ZERO))
+            .sum();
+        let short_delta: Decimal = month_deltas
+            .iter()
+            .map(|(_, delta)| (-delta).max(Decimal::ZERO))
+            .sum();
+        let intra_commodity_charge = self.intra_spread_charge * long_delta.min(short_delta);
+        let delivery_delta: Decimal = month_deltas
+            .iter()
+            .filter(|(in_delivery, _)| *in_delivery)
+            .map(|(_, delta)| *delta)
+            .sum();
+        let delivery_month_charge = self.delivery_month_charge * delivery_delta.abs();
+
+        let short_option_minimum: Decimal = revaluation
+            .options()
+            .iter()
+            .filter(|option| option.is_short())
+            .map(|option| self.short_option_minimum * underlying_price * option.quantity)
+            .sum();
+
+        let total_margin = (scanning_risk + intra_commodity_charge + delivery_month_charge)
+            .max(short_option_minimum);
+        Ok(SPANMarginReport {
+            scenarios,
+            scanning_risk,
+            intra_commodity_charge,
+            delivery_month_charge,
+            short_option_minimum,
+            total_margin,
+        })
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        let largest_move = self.price_scan_range * self.extreme_move_multiple.max(Decimal::ONE);
+        if self.price_scan_range <= Decimal::ZERO || largest_move >= Decimal::ONE {
+            return Err(PricingError::method_error(
+                "SPAN",
+                "price scan range must be positive and keep every scenario price above zero",
+            ));
+        }
+        if self.volatility_scan_range < Decimal::ZERO || self.volatility_scan_range >= Decimal::ONE
+        {
+            return Err(PricingError::method_error(
+                "SPAN",
+                "volatility scan range must be in [0, 1)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Builds the 16 scenarios in the standard SPAN order.
+    fn risk_array(
+        &self,
+        revaluation: &Revaluation<'_>,
+        underlying_price: Positive,
+    ) -> Result<Vec<SPANScenario>, PricingError> {
+        let mut moves: Vec<(Decimal, Decimal, Decimal)> = PRICE_MOVES
+            .iter()
+            .flat_map(|&price| {
+                [
+                    (price, self.volatility_scan_range),
+                    (price, -self.volatility_scan_range),
+                ]
+            })
+            .map(|(price, volatility)| (price * self.price_scan_range, volatility, Decimal::ONE))
+            .collect();
+        let extreme = self.price_scan_range * self.extreme_move_multiple;
+        moves.push((extreme, Decimal::ZERO, self.extreme_cover_fraction));
+        moves.push((-extreme, Decimal::ZERO, self.extreme_cover_fraction));
+
+        moves
+            .into_iter()
+            .enumerate()
+            .map(|(index, (price_move, volatility_move, cover_fraction))| {
+                let scenario_price = underlying_price * (Decimal::ONE + price_move);
+                let change =
+                    revaluation.change(scenario_price, volatility_move, self.look_ahead_days)?;
+                Ok(SPANScenario {
+                    number: index + 1,
+                    price_move,
+                    volatility_move,
+                    cover_fraction,
+                    underlying_price: scenario_price,
+                    loss: -change * cover_fraction,
+                })
+            })
+            .collect()
+    }
+
+    /// Net delta of every contract month, flagged when the month is the
+    /// delivery month. Legs without expiration belong to no month.
+    fn month_deltas(
+        &self,
+        legs: &[Leg],
+        options: &[Options],
+    ) -> Result<Vec<(bool, Decimal)>, PricingError> {
+        let mut months: BTreeMap<(i32, u32), (bool, Decimal)> = BTreeMap::new();
+        let mut add = |expiration: &ExpirationDate, delta: Decimal| -> Result<(), PricingError> {
+            let date = expiration.get_date()?;
+            let in_delivery = expiration.get_days()? <= self.delivery_window_days;
+            let entry = months
+                .entry((date.year(), date.month()))
+                .or_insert((false, Decimal::ZERO));
+            entry.0 |= in_delivery;
+            entry.1 += delta;
+            Ok(())
+        };
+        for option in options {
+            add(&option.expiration_date, option.delta()?)?;
+        }
+        for future in legs.iter().filter_map(Leg::as_future) {
+            add(&future.expiration_date, future.delta()?)?;
+        }
+        Ok(months.into_values().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests_portfolio_span {
+    use super::*;
+    use crate::model::leg::{FuturePosition, SpotPosition};
+    use crate::model::position::Position;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::model::utils::create_sample_option;
+    use chrono::Utc;
+    use positive::pos_or_panic;
+
+    fn option_leg(style: OptionStyle, side: Side, strike: Positive) -> Leg {
+        let option = create_sample_option(
+            style,
+            side,
+            Positive::HUNDRED,
+            Positive::ONE,
+            strike,
+            pos_or_panic!(0.2),
+        );
+        Leg::option(Position::new(
+            option,
+            pos_or_panic!(2.0),
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        ))
+    }
+
+    fn future_leg(side: Side, days: f64) -> Leg {
+        let expiration = ExpirationDate::Days(pos_or_panic!(days));
+        let future = match side {
+            Side::Long => FuturePosition::long(
+                "FUT".to_string(),
+                Positive::ONE,
+                Positive::HUNDRED,
+                expiration,
+                Positive::TEN,
+                Positive::HUNDRED,
+            ),
+            Side::Short => FuturePosition::short(
+                "FUT".to_string(),
+                Positive::ONE,
+                Positive::HUNDRED,
+                expiration,
+                Positive::TEN,
+                Positive::HUNDRED,
+            ),
+        };
+        Leg::future(future)
+    }
+
+    #[test]
+    fn test_sixteen_scenarios() {
+        let span = PortfolioSPANMargin::default();
+        let report = span
+            .calculate_margin(&[future_leg(Side::Long, 90.0)], Positive::HUNDRED)
+            .unwrap();
+        assert_eq!(report.scenarios.len(), 16);
+        assert!(
+            report
+                .scenarios
+                .iter()
+                .enumerate()
+                .all(|(i, s)| s.number == i + 1)
+        );
+        assert_eq!(report.scenarios[14].price_move, dec!(0.18));
+        assert_eq!(report.scenarios[14].cover_fraction, dec!(0.35));
+        assert_eq!(report.scenarios[15].price_move, dec!(-0.18));
+        // 10 units down 18%, 35% covered, beats the full 6% move
+        assert_eq!(report.scanning_risk, dec!(63.0));
+        assert_eq!(report.active_scenario().unwrap().number, 16);
+        assert_eq!(report.total_margin, report.scanning_risk);
+    }
+
+    #[test]
+    fn test_calendar_spread_is_charged_intra_commodity() {
+        let span = PortfolioSPANMargin::default().with_intra_spread_charge(dec!(1.5));
+        let legs = [future_leg(Side::Long, 40.0), future_leg(Side::Short, 130.0)];
+        let report = span.calculate_margin(&legs, Positive::HUNDRED).unwrap();
+        assert_eq!(report.scanning_risk, Decimal::ZERO);
+        assert_eq!(report.intra_commodity_charge, dec!(15.0));
+        assert_eq!(report.total_margin, dec!(15.0));
+    }
+
+    #[test]
+    fn test_delivery_month_charge() {
+        let span = PortfolioSPANMargin::default()
+            .with_delivery_month_charge(dec!(0.5), pos_or_panic!(10.0));
+        let near = span
+            .calculate_margin(&[future_leg(Side::Short, 5.0)], Positive::HUNDRED)
+            .unwrap();
+        assert_eq!(near.delivery_month_charge, dec!(5.0));
+        let far = span
+            .calculate_margin(&[future_leg(Side::Short, 60.0)], Positive::HUNDRED)
+            .unwrap();
+        assert_eq!(far.delivery_month_charge, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_portfolio_offsets_reduce_margin() {
+        let span = PortfolioSPANMargin::default();
+        let short_call = option_leg(OptionStyle::Call, Side::Short, Positive::HUNDRED);
+        let long_call = option_leg(OptionStyle::Call, Side::Long, pos_or_panic!(105.0));
+        let alone = span
+            .calculate_margin(std::slice::from_ref(&short_call), Positive::HUNDRED)
+            .unwrap();
+        let spread = span
+            .calculate_margin(&[short_call, long_call], Positive::HUNDRED)
+            .unwrap();
+        assert!(alone.scanning_risk > Decimal::ZERO);
+        assert!(spread.scanning_risk < alone.scanning_risk);
+        assert!(spread.scanning_risk <= dec!(5.0));
+    }
+
+    #[test]
+    fn test_short_option_minimum_floors_requirement() {
+        let span = PortfolioSPANMargin::default().with_short_option_minimum(dec!(0.2));
+        let legs = [
+            option_leg(OptionStyle::Put, Side::Short, pos_or_panic!(60.0)),
+            Leg::spot(SpotPosition::long(
+                "SPOT".to_string(),
+                Positive::ONE,
+                Positive::HUNDRED,
+            )),
+        ];
+        let report = span.calculate_margin(&legs, Positive::HUNDRED).unwrap();
+        assert_eq!(report.short_option_minimum, dec!(20.0));
+        assert_eq!(report.total_margin, dec!(20.0));
+    }
+
+    #[test]
+    fn test_short_american_put_is_margined() {
+        let span = PortfolioSPANMargin::default();
+        let mut american = option_leg(OptionStyle::Put, Side::Short, Positive::HUNDRED);
+        if let Leg::Option(position) = &mut american {
+            position.option.option_type = OptionType::American;
+        }
+        let european = option_leg(OptionStyle::Put, Side::Short, Positive::HUNDRED);
+        let american = span
+            .calculate_margin(&[american], Positive::HUNDRED)
+            .unwrap();
+        let european = span
+            .calculate_margin(&[european], Positive::HUNDRED)
+            .unwrap();
+        assert_eq!(american.scenarios.len(), 16);
+        assert!(american.scanning_risk > Decimal::ZERO);
+        // The early-exercise premium grows as the put moves into the money,
+        // so the short American put loses more on the down moves
+        assert!(american.scanning_risk > european.scanning_risk);
+        assert!(american.active_scenario().unwrap().price_move < Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_invalid_scan_range() {
+        let span = PortfolioSPANMargin::default().with_price_scan_range(dec!(0.4));
+        assert!(span.calculate_margin(&[], Positive::HUNDRED).is_err());
+        let report = PortfolioSPANMargin::default()
+            .calculate_margin(&[], Positive::HUNDRED)
+            .unwrap();
+        assert_eq!(report.total_margin, Decimal::ZERO);
+        assert!(report.active_scenario().is_none());
+    }
+}
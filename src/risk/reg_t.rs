@@ -0,0 +1,553 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+use crate::error::StrategyError;
+use crate::model::leg::SpotPosition;
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, Side};
+use crate::strategies::base::{Strategable, StrategyType};
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Strategy-based (Reg-T) margin calculator following the CBOE margin rules
+/// for retail accounts.
+///
+/// Short options are first paired with long options of the same style that
+/// expire no earlier, and each pair is margined as a spread: the amount by
+/// which the long strike is worse than the short strike, zero for debit
+/// spreads. Short calls left over are covered by long stock and short puts by
+/// short stock, at `shares_per_contract` shares per option unit. Anything
+/// still uncovered is naked and requires the option's premium plus
+/// `naked_underlying_rate` of the underlying less the out-of-the-money
+/// amount, and at least `naked_minimum_rate` of the underlying (calls) or
+/// strike (puts). When both calls and puts carry short exposure only the
+/// larger side is charged, plus the premium of the other side's naked
+/// shorts, as for short straddles, strangles and iron condors.
+///
+/// Long options are paid in full. Stock is margined at `stock_initial_rate`
+/// initially and `stock_maintenance_rate` (long) or
+/// `short_stock_maintenance_rate` (short) for maintenance. Premiums are the
+/// positions' recorded premiums, in the same per-unit terms as the rest of
+/// the library.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegTMargin {
+    /// Fraction of the underlying charged on a naked short option
+    pub naked_underlying_rate: Decimal,
+    /// Minimum naked charge as a fraction of the underlying (calls) or strike (puts)
+    pub naked_minimum_rate: Decimal,
+    /// Initial margin on stock as a fraction of its market value
+    pub stock_initial_rate: Decimal,
+    /// Maintenance margin on long stock as a fraction of its market value
+    pub stock_maintenance_rate: Decimal,
+    /// Maintenance margin on short stock as a fraction of its market value
+    pub short_stock_maintenance_rate: Decimal,
+    /// Shares of stock that cover one option unit
+    pub shares_per_contract: Positive,
+}
+
+impl Default for RegTMargin {
+    fn default() -> Self {
+        RegTMargin {
+            naked_underlying_rate: dec!(0.20),
+            naked_minimum_rate: dec!(0.10),
+            stock_initial_rate: dec!(0.50),
+            stock_maintenance_rate: dec!(0.25),
+            short_stock_maintenance_rate: dec!(0.30),
+            shares_per_contract: Positive::HUNDRED,
+        }
+    }
+}
+
+/// The rule that set a [`MarginRequirement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarginTreatment {
+    /// No short options: every option is paid in full
+    Debit,
+    /// Every short option is paired with a long option
+    DefinedRisk,
+    /// At least one short option is covered by stock and none is naked
+    Covered,
+    /// Short options on one side are naked
+    Naked,
+    /// Calls and puts both carry naked shorts
+    StraddleOrStrangle,
+}
+
+/// Reg-T margin requirement of a set of positions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginRequirement {
+    /// Rule that set the option requirement
+    pub treatment: MarginTreatment,
+    /// Margin required to open the positions, excluding premiums paid
+    pub initial_margin: Decimal,
+    /// Margin required to keep the positions open
+    pub maintenance_margin: Decimal,
+    /// Premium received less premium paid, negative for a net debit
+    pub net_premium: Decimal,
+    /// Buying power consumed: initial margin plus debits less credits
+    pub buying_power_reduction: Decimal,
+}
+
+impl MarginRequirement {
+    /// Return on margin of `pnl`, or `None` when no buying power is used.
+    #[must_use]
+    pub fn return_on_margin(&self, pnl: Decimal) -> Option<Decimal> {
+        (self.buying_power_reduction > Decimal::ZERO).then(|| pnl / self.buying_power_reduction)
+    }
+}
+
+/// An option leg being paired, in per-unit terms.
+#[derive(Debug, Clone, Copy)]
+struct OptionLeg {
+    strike: Decimal,
+    years: Decimal,
+    quantity: Decimal,
+    premium: Decimal,
+}
+
+/// Requirement of the calls or the puts of a portfolio.
+#[derive(Debug, Default)]
+struct SideMargin {
+    requirement: Decimal,
+    naked_premium: Decimal,
+    has_short: bool,
+    has_covered: bool,
+    has_naked: bool,
+}
+
+impl RegTMargin {
+    /// Sets the naked option rates, e.g. `0.15` and `0.10` for broad-based
+    /// index options.
+    pub fn with_naked_rates(mut self, underlying_rate: Decimal, minimum_rate: Decimal) -> Self {
+        self.naked_underlying_rate = underlying_rate;
+        self.naked_minimum_rate = minimum_rate;
+        self
+    }
+
+    /// Sets the stock margin rates.
+    pub fn with_stock_rates(
+        mut self,
+        initial_rate: Decimal,
+        maintenance_rate: Decimal,
+        short_maintenance_rate: Decimal,
+    ) -> Self {
+        self.stock_initial_rate = initial_rate;
+        self.stock_maintenance_rate = maintenance_rate;
+        self.short_stock_maintenance_rate = short_maintenance_rate;
+        self
+    }
+
+    /// Sets the number of shares that cover one option unit.
+    pub fn with_shares_per_contract(mut self, shares_per_contract: Positive) -> Self {
+        self.shares_per_contract = shares_per_contract;
+        self
+    }
+
+    /// Margin requirement of a strategy.
+    ///
+    /// Strategies whose [`StrategyType`] implies stock (covered call,
+    /// protective put and collar) hold it outside their option positions, so
+    /// the stock that matches their option quantity is assumed at the
+    /// underlying price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the strategy has no
+    /// positions, and propagates position and expiration errors.
+    pub fn strategy_margin<S: Strategable>(
+        &self,
+        strategy: &S,
+    ) -> Result<MarginRequirement, StrategyError> {
+        let positions: Vec<Position> = strategy.get_positions()?.into_iter().cloned().collect();
+        self.strategy_type_margin(&strategy.type_name(), &positions)
+    }
+
+    /// Margin requirement of the option positions of a strategy of type
+    /// `kind`, with the stock implied by the type assumed as in
+    /// [`RegTMargin::strategy_margin`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when `positions` is empty, and
+    /// propagates expiration errors of the options.
+    pub fn strategy_type_margin(
+        &self,
+        kind: &StrategyType,
+        positions: &[Position],
+    ) -> Result<MarginRequirement, StrategyError> {
+        let stock_style = match kind {
+            StrategyType::CoveredCall | StrategyType::Collar => Some(OptionStyle::Call),
+            StrategyType::ProtectivePut => Some(OptionStyle::Put),
+            _ => None,
+        };
+        let stock: Vec<SpotPosition> = positions
+            .iter()
+            .filter(|position| Some(position.option.option_style) == stock_style)
+            .map(|position| {
+                SpotPosition::long(
+                    position.option.underlying_symbol.clone(),
+                    position.option.quantity * self.shares_per_contract,
+                    position.option.underlying_price,
+                )
+            })
+            .collect();
+        self.positions_margin(positions, &stock)
+    }
+
+    /// Margin requirement of option positions and stock on the same
+    /// underlying, valued at the options' underlying price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when `positions` is empty, and
+    /// propagates expiration errors of the options.
+    pub fn positions_margin(
+        &self,
+        positions: &[Position],
+        stock: &[SpotPosition],
+    ) -> Result<MarginRequirement, StrategyError> {
+        let underlying_price = positions
+            .first()
+            .map(|position| position.option.underlying_price.to_dec())
+            .ok_or_else(|| {
+                StrategyError::invalid_parameters("RegTMargin", "no option positions")
+            })?;
+        let shares = |side: Side| -> Decimal {
+            stock
+                .iter()
+                .filter(|spot| spot.side == side)
+                .map(|spot| spot.quantity.to_dec())
+                .sum()
+        };
+        let (long_shares, short_shares) = (shares(Side::Long), shares(Side::Short));
+
+        let calls = self.side_margin(
+            positions,
+            OptionStyle::Call,
+            underlying_price,
+            long_shares / self.shares_per_contract.to_dec(),
+        )?;
+        let puts = self.side_margin(
+            positions,
+            OptionStyle::Put,
+            underlying_price,
+            short_shares / self.shares_per_contract.to_dec(),
+        )?;
+        let (high, low) = if calls.requirement >= puts.requirement {
+            (&calls, &puts)
+        } else {
+            (&puts, &calls)
+        };
+        let option_margin = high.requirement + low.naked_premium;
+
+        let long_value = long_shares * underlying_price;
+        let short_value = short_shares * underlying_price;
+        let initial_margin = option_margin + self.stock_initial_rate * (long_value + short_value);
+        let maintenance_margin = option_margin
+            + self.stock_maintenance_rate * long_value
+            + self.short_stock_maintenance_rate * short_value;
+
+        let net_premium: Decimal = positions
+            .iter()
+            .map(|position| {
+                let flow = position.premium.to_dec() * position.option.quantity.to_dec();
+                if position.is_short() { flow } else { -flow }
+            })
+            .sum();
+        let buying_power_reduction = (initial_margin - net_premium).max(Decimal::ZERO);
+
+        let treatment = match (calls.has_naked, puts.has_naked) {
+            (true, true) => MarginTreatment::StraddleOrStrangle,
+            (true, false) | (false, true) => MarginTreatment::Naked,
+            _ if calls.has_covered || puts.has_covered => MarginTreatment::Covered,
+            _ if calls.has_short || puts.has_short => MarginTreatment::DefinedRisk,
+            _ => MarginTreatment::Debit,
+        };
+        Ok(MarginRequirement {
+            treatment,
+            initial_margin,
+            maintenance_margin,
+            net_premium,
+            buying_power_reduction,
+        })
+    }
+
+    /// Pairs the shorts of one style with longs, then stock, and charges
+    /// the remainder as naked.
+    fn side_margin(
+        &self,
+        positions: &[Position],
+        style: OptionStyle,
+        underlying_price: Decimal,
+        stock_cover: Decimal,
+    ) -> Result<SideMargin, StrategyError> {
+        let mut shorts: Vec<OptionLeg> = Vec::new();
+        let mut longs: Vec<OptionLeg> = Vec::new();
+        for position in positions
+            .iter()
+            .filter(|position| position.option.option_style == style)
+        {
+            let leg = OptionLeg {
+                strike: position.option.strike_price.to_dec(),
+                years: position.option.time_to_expiration()?.to_dec(),
+                quantity: position.option.quantity.to_dec(),
+                premium: position.premium.to_dec(),
+            };
+            if position.is_short() {
+                shorts.push(leg);
+            } else {
+                longs.push(leg);
+            }
+        }
+        // Loss per unit of a short covered by a long, zero for debit spreads.
+        let spread = |short: &OptionLeg, long: &OptionLeg| match style {
+            OptionStyle::Call => (long.strike - short.strike).max(Decimal::ZERO),
+            OptionStyle::Put => (short.strike - long.strike).max(Decimal::ZERO),
+        };
+        // Lower call strikes (higher put strikes) pick their covers first.
+        match style {
+            OptionStyle::Call => shorts.sort_by_key(|short| short.strike),
+            OptionStyle::Put => shorts.sort_by_key(|short| std::cmp::Reverse(short.strike)),
+        }
+
+        let mut margin = SideMargin {
+            has_short: !shorts.is_empty(),
+            ..SideMargin::default()
+        };
+        let mut stock_cover = stock_cover;
+        for short in &mut shorts {
+            while short.quantity > Decimal::ZERO {
+                let Some(long) = longs
+                    .iter_mut()
+                    .filter(|long| long.quantity > Decimal::ZERO && long.years >= short.years)
+                    .min_by_key(|long| spread(short, long))
+                else {
+                    break;
+                };
+                let paired = short.quantity.min(long.quantity);
+                margin.requirement += spread(short, long) * paired;
+                short.quantity -= paired;
+                long.quantity -= paired;
+            }
+
+            let covered = short.quantity.min(stock_cover);
+            if covered > Decimal::ZERO {
+                margin.has_covered = true;
+                stock_cover -= covered;
+                short.quantity -= covered;
+            }
+
+            if short.quantity > Decimal::ZERO {
+                let (out_of_the_money, minimum_base) = match style {
+                    OptionStyle::Call => ((short.strike - underlying_price), underlying_price),
+                    OptionStyle::Put => ((underlying_price - short.strike), short.strike),
+                };
+                let per_unit = (self.naked_underlying_rate * underlying_price
+                    - out_of_the_money.max(Decimal::ZERO))
+                .max(self.naked_minimum_rate * minimum_base);
+                margin.requirement += (per_unit + short.premium) * short.quantity;
+                margin.naked_premium += short.premium * short.quantity;
+                margin.has_naked = true;
+            }
+        }
+        Ok(margin)
+    }
+}
+
+#[cfg(test)]
+mod tests_reg_t {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::strategies::{
+        BullCallSpread, BullPutSpread, CoveredCall, IronCondor, ShortPut, ShortStrangle,
+    };
+    use positive::pos_or_panic;
+
+    fn days() -> ExpirationDate {
+        ExpirationDate::Days(pos_or_panic!(30.0))
+    }
+
+    #[test]
+    fn test_naked_short_put() {
+        let strategy = ShortPut::new(
+            "SPY".to_string(),
+            pos_or_panic!(95.0),
+            days(),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            Positive::ZERO,
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let margin = RegTMargin::default().strategy_margin(&strategy).unwrap();
+        assert_eq!(margin.treatment, MarginTreatment::Naked);
+        // 20% of 100 less 5 OTM beats 10% of the 95 strike, plus the premium
+        assert_eq!(margin.initial_margin, dec!(17.0));
+        assert_eq!(margin.net_premium, dec!(2.0));
+        assert_eq!(margin.buying_power_reduction, dec!(15.0));
+        assert_eq!(margin.return_on_margin(dec!(1.5)), Some(dec!(0.1)));
+    }
+
+    #[test]
+    fn test_far_otm_naked_put_uses_minimum() {
+        let strategy = ShortPut::new(
+            "SPY".to_string(),
+            pos_or_panic!(70.0),
+            days(),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            Positive::ZERO,
+            pos_or_panic!(0.1),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let margin = RegTMargin::default().strategy_margin(&strategy).unwrap();
+        assert_eq!(margin.initial_margin, dec!(7.1));
+    }
+
+    #[test]
+    fn test_credit_and_debit_spreads() {
+        let credit = BullPutSpread::new(
+            "SPY".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(90.0),
+            pos_or_panic!(95.0),
+            days(),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(0.5),
+            pos_or_panic!(1.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let margin = RegTMargin::default().strategy_margin(&credit).unwrap();
+        assert_eq!(margin.treatment, MarginTreatment::DefinedRisk);
+        assert_eq!(margin.initial_margin, dec!(5.0));
+        assert_eq!(margin.buying_power_reduction, dec!(4.0));
+
+        let debit = BullCallSpread::new(
+            "SPY".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            days(),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(6.5),
+            pos_or_panic!(1.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let margin = RegTMargin::default().strategy_margin(&debit).unwrap();
+        assert_eq!(margin.initial_margin, Decimal::ZERO);
+        assert_eq!(margin.net_premium, dec!(-5.0));
+        assert_eq!(margin.buying_power_reduction, dec!(5.0));
+    }
+
+    #[test]
+    fn test_iron_condor_charges_one_side() {
+        let condor = IronCondor::new(
+            "SPY".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(105.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(110.0),
+            pos_or_panic!(85.0),
+            days(),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.5),
+            pos_or_panic!(1.5),
+            pos_or_panic!(0.5),
+            pos_or_panic!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let margin = RegTMargin::default().strategy_margin(&condor).unwrap();
+        assert_eq!(margin.treatment, MarginTreatment::DefinedRisk);
+        assert_eq!(margin.initial_margin, dec!(10.0));
+        assert_eq!(margin.buying_power_reduction, dec!(8.0));
+    }
+
+    #[test]
+    fn test_short_strangle_adds_other_side_premium() {
+        let strangle = ShortStrangle::new(
+            "SPY".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(110.0),
+            pos_or_panic!(95.0),
+            days(),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.0),
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let margin = RegTMargin::default().strategy_margin(&strangle).unwrap();
+        assert_eq!(margin.treatment, MarginTreatment::StraddleOrStrangle);
+        // put side 20 - 5 + 2 = 17 beats call side 20 - 10 + 1 = 11; plus the call premium
+        assert_eq!(margin.initial_margin, dec!(18.0));
+        assert_eq!(margin.buying_power_reduction, dec!(15.0));
+    }
+
+    #[test]
+    fn test_covered_call_margins_the_stock() {
+        let covered = CoveredCall::new(
+            "SPY".to_string(),
+            Positive::HUNDRED,
+            pos_or_panic!(105.0),
+            days(),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::HUNDRED,
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let margin = RegTMargin::default().strategy_margin(&covered).unwrap();
+        assert_eq!(margin.treatment, MarginTreatment::Covered);
+        assert_eq!(margin.initial_margin, dec!(5000.0));
+        assert_eq!(margin.maintenance_margin, dec!(2500.0));
+        assert_eq!(margin.buying_power_reduction, dec!(4998.0));
+    }
+
+    #[test]
+    fn test_empty_positions_error() {
+        assert!(RegTMargin::default().positions_margin(&[], &[]).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+use crate::error::PricingError;
+use crate::model::ExpirationDate;
+use crate::model::leg::{Leg, LegAble};
+use crate::model::option::Options;
+use crate::model::types::Side;
+use crate::pricing::unified::{PricingEngine, price_option};
+use positive::Positive;
+use rust_decimal::Decimal;
+
+/// Legs on one underlying prepared for full revaluation under scenarios.
+///
+/// Option legs are repriced with the default engine for their option type
+/// (see [`PricingEngine::for_option_type`]) at the scenario price and
+/// volatility, closer to expiry by the elapsed days, and at intrinsic value
+/// once expired. Linear legs move with their P&L at the scenario price.
+pub(crate) struct Revaluation<'a> {
+    legs: &'a [Leg],
+    options: Vec<Options>,
+    base_price: Positive,
+    base_value: Decimal,
+}
+
+impl<'a> Revaluation<'a> {
+    /// Values `legs` with the underlying at `base_price`.
+    pub(crate) fn new(legs: &'a [Leg], base_price: Positive) -> Result<Self, PricingError> {
+        let options: Vec<Options> = legs
+            .iter()
+            .filter_map(Leg::as_option)
+            .map(|position| {
+                let mut option = position.option.clone();
+                option.underlying_price = base_price;
+                option
+            })
+            .collect();
+        let base_value = options
+            .iter()
+            .map(option_value)
+            .sum::<Result<Decimal, _>>()?;
+        Ok(Revaluation {
+            legs,
+            options,
+            base_price,
+            base_value,
+        })
+    }
+
+    /// The option legs, repriced at the base price.
+    pub(crate) fn options(&self) -> &[Options] {
+        &self.options
+    }
+
+    /// Change in portfolio value when the underlying moves to `price`, every
+    /// implied volatility moves by the fraction `volatility_move` and
+    /// `elapsed_days` pass.
+    pub(crate) fn change(
+        &self,
+        price: Positive,
+        volatility_move: Decimal,
+        elapsed_days: Positive,
+    ) -> Result<Decimal, PricingError> {
+        self.change_with(price, elapsed_days, |scenario| {
+            scenario.implied_volatility *= Decimal::ONE + volatility_move;
+            Ok(())
+        })
+    }
+
+    /// Change in portfolio value when the underlying moves to `price` and
+    /// `elapsed_days` pass, with `adjust` applied to every option before
+    /// repricing. `adjust` sees the option at the base price and its current
+    /// expiry.
+    pub(crate) fn change_with(
+        &self,
+        price: Positive,
+        elapsed_days: Positive,
+        adjust: impl Fn(&mut Options) -> Result<(), PricingError>,
+    ) -> Result<Decimal, PricingError> {
+        let linear_change: Decimal = self
+            .legs
+            .iter()
+            .filter(|leg| leg.is_linear())
+            .map(|leg| leg.pnl_at_price(price) - leg.pnl_at_price(self.base_price))
+            .sum();
+        let option_value = self
+            .options
+            .iter()
+            .map(|option| {
+                let mut scenario = option.clone();
+                adjust(&mut scenario)?;
+                option_value(&aged(&scenario, price, elapsed_days)?)
+            })
+            .sum::<Result<Decimal, _>>()?;
+        Ok(linear_change + option_value - self.base_value)
+    }
+}
+
+/// `option` with the underlying at `price` and `elapsed_days` closer to
+/// expiry, floored at expiry.
+pub(crate) fn aged(
+    option: &Options,
+    price: Positive,
+    elapsed_days: Positive,
+) -> Result<Options, PricingError> {
+    let mut aged = option.clone();
+    aged.underlying_price = price;
+    let days = option.expiration_date.get_days()?;
+    aged.expiration_date = ExpirationDate::Days(if days > elapsed_days {
+        days - elapsed_days
+    } else {
+        Positive::ZERO
+    });
+    Ok(aged)
+}
+
+/// Signed value of an option position, at intrinsic value once expired.
+///
+/// Live options are priced with [`PricingEngine::for_option_type`], so
+/// American and Bermuda legs carry their early-exercise premium and exotic
+/// legs use their closed-form payoffs.
+pub(crate) fn option_value(option: &Options) -> Result<Decimal, PricingError> {
+    if option.expiration_date.get_days()? == Positive::ZERO {
+        return Ok(option.intrinsic_value(option.underlying_price)?);
+    }
+    let engine = PricingEngine::for_option_type(&option.option_type);
+    let value = price_option(option, &engine)?.to_dec() * option.quantity;
+    Ok(match option.side {
+        Side::Long => value,
+        Side::Short => -value,
+    })
+}
@@ -0,0 +1,481 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+use crate::error::PricingError;
+use crate::model::leg::Leg;
+use crate::model::option::Options;
+use crate::risk::revaluation::Revaluation;
+use crate::strategies::base::Strategies;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::MathematicalOps;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Lowest implied volatility a shifted surface can reach.
+const MIN_VOLATILITY: Decimal = dec!(0.01);
+
+/// Shift of the implied volatility surface, in absolute volatility points.
+///
+/// The shift for an option with strike `K` and `T` years to expiry, with the
+/// underlying at `S` before the shock, is
+/// `parallel + tilt·(1 − T) + skew·ln(S/K)`:
+///
+/// - `parallel` moves every point of the surface.
+/// - `tilt` pivots the term structure around one year; a positive tilt lifts
+///   short tenors and lowers long ones.
+/// - `skew` steepens the smile; a positive skew lifts downside strikes and
+///   lowers upside strikes.
+///
+/// Shifted volatilities are floored at 1%.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct VolatilityShift {
+    /// Shift applied to every option.
+    pub parallel: Decimal,
+    /// Term-structure tilt per year of distance from a one-year tenor.
+    pub tilt: Decimal,
+    /// Skew steepening per unit of log-moneyness `ln(S/K)`.
+    pub skew: Decimal,
+}
+
+impl VolatilityShift {
+    /// A parallel shift of the whole surface.
+    #[must_use]
+    pub fn parallel(shift: Decimal) -> Self {
+        VolatilityShift {
+            parallel: shift,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the term-structure tilt.
+    #[must_use]
+    pub fn with_tilt(mut self, tilt: Decimal) -> Self {
+        self.tilt = tilt;
+        self
+    }
+
+    /// Sets the skew steepening.
+    #[must_use]
+    pub fn with_skew(mut self, skew: Decimal) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    /// Shift for `option`, with the underlying at `underlying_price` before
+    /// the shock.
+    fn shift_for(
+        &self,
+        option: &Options,
+        underlying_price: Positive,
+    ) -> Result<Decimal, PricingError> {
+        let years = option.years_to_expiry()?.to_dec();
+        let moneyness = (underlying_price / option.strike_price).to_dec().ln();
+        Ok(self.parallel + self.tilt * (Decimal::ONE - years) + self.skew * moneyness)
+    }
+}
+
+/// A named market scenario: spot shock, volatility surface shift, rate shift
+/// and time roll, applied together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StressScenario {
+    /// Scenario name as shown in reports.
+    pub name: String,
+    /// Relative move of the underlying, e.g. `-0.20` for a 20% fall.
+    pub spot_shock: Decimal,
+    /// Shift of the implied volatility surface.
+    pub volatility_shift: VolatilityShift,
+    /// Absolute shift of the risk-free rate, applied as a parallel shift to
+    /// the yield curve of options that carry one.
+    pub rate_shift: Decimal,
+    /// Days rolled forward, reducing the time to expiry of every option.
+    pub time_roll_days: Positive,
+}
+
+impl StressScenario {
+    /// Creates a scenario with no shocks.
+    pub fn new(name: &str) -> Self {
+        StressScenario {
+            name: name.to_string(),
+            spot_shock: Decimal::ZERO,
+            volatility_shift: VolatilityShift::default(),
+            rate_shift: Decimal::ZERO,
+            time_roll_days: Positive::ZERO,
+        }
+    }
+
+    /// Sets the relative spot shock.
+    #[must_use]
+    pub fn with_spot_shock(mut self, spot_shock: Decimal) -> Self {
+        self.spot_shock = spot_shock;
+        self
+    }
+
+    /// Sets the volatility surface shift.
+    #[must_use]
+    pub fn with_volatility_shift(mut self, volatility_shift: VolatilityShift) -> Self {
+        self.volatility_shift = volatility_shift;
+        self
+    }
+
+    /// Sets the risk-free rate shift.
+    #[must_use]
+    pub fn with_rate_shift(mut self, rate_shift: Decimal) -> Self {
+        self.rate_shift = rate_shift;
+        self
+    }
+
+    /// Sets the time roll in days.
+    #[must_use]
+    pub fn with_time_roll(mut self, time_roll_days: Positive) -> Self {
+        self.time_roll_days = time_roll_days;
+        self
+    }
+
+    /// Built-in replays of historical crashes: Black Monday 1987, the 2008
+    /// Lehman collapse, the 2020 Covid crash and the February 2018
+    /// "Volmageddon" volatility spike.
+    ///
+    /// Shocks are approximate peak-to-trough moves of the S&P 500 and of its
+    /// implied volatility over each episode.
+    pub fn historical_replays() -> Vec<StressScenario> {
+        HISTORICAL_REPLAYS
+            .iter()
+            .map(|replay| StressScenario {
+                name: replay.name.to_string(),
+                spot_shock: replay.spot_shock,
+                volatility_shift: VolatilityShift {
+                    parallel: replay.parallel,
+                    tilt: replay.tilt,
+                    skew: replay.skew,
+                },
+                rate_shift: replay.rate_shift,
+                // The table only holds non-negative day counts, so the
+                // fallback is unreachable.
+                time_roll_days: Positive::new_decimal(replay.time_roll_days)
+                    .unwrap_or(Positive::ZERO),
+            })
+            .collect()
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        if self.spot_shock <= -Decimal::ONE {
+            return Err(PricingError::other(&format!(
+                "scenario '{}' has a spot shock of {}, which must be above -1",
+                self.name, self.spot_shock
+            )));
+        }
+        Ok(())
+    }
+}
+
+struct HistoricalReplay {
+    name: &'static str,
+    spot_shock: Decimal,
+    parallel: Decimal,
+    tilt: Decimal,
+    skew: Decimal,
+    rate_shift: Decimal,
+    time_roll_days: Decimal,
+}
+
+const HISTORICAL_REPLAYS: [HistoricalReplay; 4] = [
+    HistoricalReplay {
+        name: "Black Monday 1987",
+        spot_shock: dec!(-0.205),
+        parallel: dec!(0.45),
+        tilt: dec!(0.20),
+        skew: dec!(0.10),
+        rate_shift: dec!(-0.005),
+        time_roll_days: Decimal::ONE,
+    },
+    HistoricalReplay {
+        name: "Lehman 2008",
+        spot_shock: dec!(-0.28),
+        parallel: dec!(0.45),
+        tilt: dec!(0.10),
+        skew: dec!(0.05),
+        rate_shift: dec!(-0.01),
+        time_roll_days: dec!(28),
+    },
+    HistoricalReplay {
+        name: "Covid 2020",
+        spot_shock: dec!(-0.34),
+        parallel: dec!(0.50),
+        tilt: dec!(0.15),
+        skew: dec!(0.05),
+        rate_shift: dec!(-0.015),
+        time_roll_days: dec!(33),
+    },
+    HistoricalReplay {
+        name: "Volmageddon 2018",
+        spot_shock: dec!(-0.041),
+        parallel: dec!(0.20),
+        tilt: dec!(0.10),
+        skew: dec!(0.02),
+        rate_shift: Decimal::ZERO,
+        time_roll_days: Decimal::ONE,
+    },
+];
+
+/// Outcome of one scenario.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    /// Name of the scenario.
+    pub scenario: String,
+    /// Underlying price after the spot shock.
+    pub underlying_price: Positive,
+    /// Change in portfolio value under the scenario.
+    pub pnl: Decimal,
+}
+
+/// Runs a set of [`StressScenario`]s against a portfolio with full
+/// revaluation.
+///
+/// Options are repriced with the default engine for their option type (see
+/// [`crate::pricing::PricingEngine::for_option_type`]) after applying the
+/// volatility surface shift at their pre-shock strike and tenor, the rate
+/// shift and the time roll; linear legs move with the shocked underlying.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioEngine {
+    /// Scenarios run in order.
+    pub scenarios: Vec<StressScenario>,
+}
+
+impl ScenarioEngine {
+    /// Creates an engine running `scenarios`.
+    pub fn new(scenarios: Vec<StressScenario>) -> Self {
+        ScenarioEngine { scenarios }
+    }
+
+    /// Adds a scenario.
+    #[must_use]
+    pub fn with_scenario(mut self, scenario: StressScenario) -> Self {
+        self.scenarios.push(scenario);
+        self
+    }
+
+    /// Adds the built-in historical replays.
+    #[must_use]
+    pub fn with_historical_replays(mut self) -> Self {
+        self.scenarios.extend(StressScenario::historical_replays());
+        self
+    }
+
+    /// Runs every scenario on legs on one underlying at `underlying_price`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PricingError`] when a scenario is invalid or an option
+    /// cannot be repriced.
+    pub fn run(
+        &self,
+        legs: &[Leg],
+        underlying_price: Positive,
+    ) -> Result<Vec<ScenarioResult>, PricingError> {
+        let revaluation = Revaluation::new(legs, underlying_price)?;
+        self.scenarios
+            .iter()
+            .map(|scenario| {
+                scenario.validate()?;
+                let shocked_price = underlying_price * (Decimal::ONE + scenario.spot_shock);
+                let pnl =
+                    revaluation.change_with(shocked_price, scenario.time_roll_days, |option| {
+                        let shift = scenario
+                            .volatility_shift
+                            .shift_for(option, underlying_price)?;
+                        let volatility =
+                            (option.implied_volatility.to_dec() + shift).max(MIN_VOLATILITY);
+                        option.implied_volatility = Positive::new_decimal(volatility)?;
+                        option.risk_free_rate += scenario.rate_shift;
+                        if let Some(curve) = &mut option.yield_curve {
+                            *curve = curve.parallel_shift(scenario.rate_shift);
+                        }
+                        Ok(())
+                    })?;
+                Ok(ScenarioResult {
+                    scenario: scenario.name.clone(),
+                    underlying_price: shocked_price,
+                    pnl,
+                })
+            })
+            .collect()
+    }
+
+    /// Runs every scenario on the positions of `strategy` at its underlying
+    /// price.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PricingError`] when the positions cannot be read, a
+    /// scenario is invalid or an option cannot be repriced.
+    pub fn run_strategy<S: Strategies>(
+        &self,
+        strategy: &S,
+    ) -> Result<Vec<ScenarioResult>, PricingError> {
+        let legs: Vec<Leg> = strategy
+            .get_positions()?
+            .into_iter()
+            .map(|position| Leg::option(position.clone()))
+            .collect();
+        self.run(&legs, *strategy.get_underlying_price())
+    }
+}
+
+#[cfg(test)]
+mod tests_scenario {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::leg::SpotPosition;
+    use crate::model::position::Position;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::pricing::YieldCurve;
+    use crate::risk::revaluation::option_value;
+    use crate::strategies::ShortPut;
+    use crate::strategies::base::Positionable;
+    use chrono::Utc;
+    use positive::pos_or_panic;
+
+    fn short_put() -> ShortPut {
+        ShortPut::new(
+            "SPX".to_string(),
+            pos_or_panic!(95.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            Positive::ZERO,
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_spot_shock_on_linear_leg() {
+        let legs = vec![Leg::spot(SpotPosition::long(
+            "SPX".to_string(),
+            Positive::TEN,
+            Positive::HUNDRED,
+        ))];
+        let engine = ScenarioEngine::default()
+            .with_scenario(StressScenario::new("down 10%").with_spot_shock(dec!(-0.10)));
+        let results = engine.run(&legs, Positive::HUNDRED).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].underlying_price, pos_or_panic!(90.0));
+        assert_eq!(results[0].pnl, dec!(-100));
+    }
+
+    #[test]
+    fn test_no_shock_is_flat() {
+        let engine = ScenarioEngine::new(vec![StressScenario::new("base")]);
+        let results = engine.run_strategy(&short_put()).unwrap();
+        assert!(results[0].pnl.abs() < dec!(0.0000001));
+    }
+
+    #[test]
+    fn test_volatility_shift_components() {
+        let strategy = short_put();
+        let parallel = ScenarioEngine::default()
+            .with_scenario(
+                StressScenario::new("vol up")
+                    .with_volatility_shift(VolatilityShift::parallel(dec!(0.10))),
+            )
+            .with_scenario(
+                StressScenario::new("skew")
+                    .with_volatility_shift(VolatilityShift::default().with_skew(dec!(1.0))),
+            )
+            .with_scenario(
+                StressScenario::new("rates and time")
+                    .with_rate_shift(dec!(0.02))
+                    .with_time_roll(pos_or_panic!(10.0)),
+            );
+        let results = parallel.run_strategy(&strategy).unwrap();
+        // Short options lose when volatility rises, including through a
+        // steeper skew on a downside strike, and gain from time decay.
+        assert!(results[0].pnl < Decimal::ZERO);
+        assert!(results[1].pnl < Decimal::ZERO);
+        assert!(results[2].pnl > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_rate_shift_moves_the_yield_curve() {
+        let option = Options::new(
+            OptionType::European,
+            Side::Long,
+            "SPX".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(365.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            Decimal::ZERO,
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        )
+        .with_yield_curve(YieldCurve::flat(dec!(0.05)));
+        let legs = vec![Leg::option(Position::new(
+            option.clone(),
+            Positive::TEN,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        ))];
+        let engine = ScenarioEngine::default()
+            .with_scenario(StressScenario::new("rates up").with_rate_shift(dec!(0.02)));
+        let results = engine.run(&legs, Positive::HUNDRED).unwrap();
+
+        let shifted = option
+            .clone()
+            .with_yield_curve(YieldCurve::flat(dec!(0.07)));
+        let expected = option_value(&shifted).unwrap() - option_value(&option).unwrap();
+        assert!(expected > dec!(0.5));
+        assert!((results[0].pnl - expected).abs() < dec!(0.0000001));
+    }
+
+    #[test]
+    fn test_short_american_put_is_repriced() {
+        let strategy = short_put();
+        let legs: Vec<Leg> = strategy
+            .get_positions()
+            .unwrap()
+            .into_iter()
+            .map(|position| {
+                let mut position = position.clone();
+                position.option.option_type = OptionType::American;
+                Leg::option(position)
+            })
+            .collect();
+        let engine = ScenarioEngine::default()
+            .with_scenario(StressScenario::new("down 10%").with_spot_shock(dec!(-0.10)));
+        let american = engine.run(&legs, Positive::HUNDRED).unwrap();
+        let european = engine.run_strategy(&strategy).unwrap();
+        assert!(american[0].pnl < european[0].pnl);
+    }
+
+    #[test]
+    fn test_historical_replays() {
+        let engine = ScenarioEngine::default().with_historical_replays();
+        assert_eq!(engine.scenarios.len(), 4);
+        assert_eq!(engine.scenarios[0].name, "Black Monday 1987");
+        let results = engine.run_strategy(&short_put()).unwrap();
+        for result in &results {
+            assert!(result.pnl < Decimal::ZERO, "{}", result.scenario);
+        }
+    }
+
+    #[test]
+    fn test_invalid_spot_shock() {
+        let engine = ScenarioEngine::new(vec![
+            StressScenario::new("wipeout").with_spot_shock(dec!(-1.0)),
+        ]);
+        assert!(engine.run_strategy(&short_put()).is_err());
+    }
+}
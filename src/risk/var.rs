@@ -0,0 +1,615 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+use crate::error::{PricingError, SimulationError};
+use crate::greeks::Greeks;
+use crate::model::leg::{Leg, LegAble};
+use crate::risk::revaluation::Revaluation;
+use crate::simulation::WalkParams;
+use crate::utils::OhlcvCandle;
+use positive::Positive;
+use positive::constants::DAYS_IN_A_YEAR;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, MathematicalOps, ToPrimitive};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal};
+
+/// Method used to build the P&L distribution of a [`VaRResult`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VaRMethod {
+    /// Quadratic delta-gamma approximation under normal price moves.
+    DeltaGamma,
+    /// Full revaluation under historical returns of the underlying.
+    Historical,
+    /// Full revaluation under prices simulated with a `WalkType` model.
+    MonteCarlo,
+}
+
+/// Value-at-Risk and Expected Shortfall of a portfolio under one method.
+///
+/// `value_at_risk` and `expected_shortfall` are loss magnitudes at the
+/// configured confidence, floored at zero. `pnl` holds the scenario P&L of
+/// the whole portfolio over the horizon, sorted from worst to best.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaRResult {
+    /// Method that produced the P&L distribution.
+    pub method: VaRMethod,
+    /// Confidence level of `value_at_risk` and `expected_shortfall`.
+    pub confidence: Decimal,
+    /// Horizon in days.
+    pub horizon_days: Positive,
+    /// Loss not exceeded with probability `confidence`.
+    pub value_at_risk: Decimal,
+    /// Average loss beyond `value_at_risk`.
+    pub expected_shortfall: Decimal,
+    /// Scenario P&L, sorted ascending.
+    pub pnl: Vec<Decimal>,
+    /// Fixed-level tail losses and summary statistics of the same
+    /// distribution.
+    pub metrics: VaRMetrics,
+}
+
+/// Summary statistics of a scenario P&L distribution.
+///
+/// Loss fields are positive magnitudes floored at zero. The ratios are taken
+/// against the total cost of the legs, so they are zero for a portfolio that
+/// cost nothing to put on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaRMetrics {
+    /// VaR at 95% confidence.
+    pub var_95: Decimal,
+    /// VaR at 99% confidence.
+    pub var_99: Decimal,
+    /// Expected Shortfall at 95% confidence.
+    pub es_95: Decimal,
+    /// Share of scenarios losing more than half the total leg cost.
+    pub severe_loss_probability: Positive,
+    /// Worst scenario loss as a fraction of the total leg cost.
+    pub worst_loss_ratio: Positive,
+    /// Mean scenario P&L.
+    pub mean_pnl: Decimal,
+    /// Standard deviation of the scenario P&L.
+    pub pnl_std_dev: Decimal,
+}
+
+/// The three VaR methods run on the same portfolio, side by side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaRReport {
+    /// Parametric delta-gamma result.
+    pub delta_gamma: VaRResult,
+    /// Historical simulation result.
+    pub historical: VaRResult,
+    /// Monte Carlo result.
+    pub monte_carlo: VaRResult,
+}
+
+/// Value-at-Risk and Expected Shortfall engine for portfolios of legs on
+/// one underlying.
+///
+/// Three methods are available:
+///
+/// - **Delta-gamma**: the portfolio P&L is approximated by
+///   `Δ·dS + ½·Γ·dS²` with `dS = S·σ·√(h/365)·z` evaluated over a grid of
+///   `simulations` standard normal quantiles.
+/// - **Historical**: overlapping `h`-day log returns of the candle closes are
+///   applied to the current price and every leg is fully revalued.
+/// - **Monte Carlo**: `simulations` paths are drawn from the walk parameters
+///   and every leg is fully revalued at the final price of each path.
+///
+/// Full revaluation prices options with the default engine for their option
+/// type (see [`crate::pricing::PricingEngine::for_option_type`]) at the
+/// scenario price with `horizon_days` less to expiry; linear legs use their P&L at price.
+///
+/// Every result also carries [`VaRMetrics`] of its distribution: VaR and
+/// Expected Shortfall at the fixed 95% and 99% levels, loss ratios against
+/// the total leg cost and the mean and deviation of the P&L.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueAtRisk {
+    /// Confidence level, strictly between 0 and 1.
+    pub confidence: Decimal,
+    /// Risk horizon in days.
+    pub horizon_days: Positive,
+    /// Number of scenarios for the delta-gamma grid and Monte Carlo paths.
+    pub simulations: usize,
+}
+
+impl Default for ValueAtRisk {
+    fn default() -> Self {
+        ValueAtRisk {
+            confidence: dec!(0.95),
+            horizon_days: Positive::ONE,
+            simulations: 10_000,
+        }
+    }
+}
+
+impl ValueAtRisk {
+    /// Sets the confidence level.
+    #[must_use]
+    pub fn with_confidence(mut self, confidence: Decimal) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    /// Sets the risk horizon in days.
+    #[must_use]
+    pub fn with_horizon_days(mut self, horizon_days: Positive) -> Self {
+        self.horizon_days = horizon_days;
+        self
+    }
+
+    /// Sets the number of scenarios.
+    #[must_use]
+    pub fn with_simulations(mut self, simulations: usize) -> Self {
+        self.simulations = simulations;
+        self
+    }
+
+    /// Parametric delta-gamma VaR with the underlying at `underlying_price`
+    /// and annualized `volatility`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] for an invalid
+    /// configuration, or a pricing error when a Greek cannot be computed.
+    pub fn delta_gamma(
+        &self,
+        legs: &[Leg],
+        underlying_price: Positive,
+        volatility: Positive,
+    ) -> Result<VaRResult, SimulationError> {
+        self.validate()?;
+        let revaluation = Revaluation::new(legs, underlying_price)?;
+        let mut delta = Decimal::ZERO;
+        let mut gamma = Decimal::ZERO;
+        for option in revaluation.options() {
+            delta += option.delta().map_err(PricingError::from)?;
+            // Option gamma is reported unsigned; short legs carry negative gamma.
+            let option_gamma = option.gamma().map_err(PricingError::from)?;
+            gamma += if option.is_short() {
+                -option_gamma
+            } else {
+                option_gamma
+            };
+        }
+        for leg in legs.iter().filter(|leg| leg.is_linear()) {
+            delta += leg.delta().map_err(PricingError::from)?;
+        }
+
+        let scale = underlying_price.to_dec()
+            * volatility.to_dec()
+            * (self.horizon_days / DAYS_IN_A_YEAR)
+                .to_dec()
+                .sqrt()
+                .unwrap_or_default();
+        let normal = Normal::standard();
+        let count = self.simulations as f64;
+        let pnl = (0..self.simulations)
+            .map(|i| {
+                let z = normal.inverse_cdf((i as f64 + 0.5) / count);
+                let z = Decimal::from_f64(z).ok_or_else(|| {
+                    SimulationError::invalid_parameters("normal quantile is not finite")
+                })?;
+                let price_move = scale * z;
+                Ok(delta * price_move + dec!(0.5) * gamma * price_move * price_move)
+            })
+            .collect::<Result<Vec<Decimal>, SimulationError>>()?;
+        Ok(self.result(VaRMethod::DeltaGamma, legs, pnl))
+    }
+
+    /// Historical-simulation VaR with full revaluation, using the closes of
+    /// daily `candles` in chronological order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] for an invalid
+    /// configuration or when there are not enough candles for one horizon
+    /// return, a positive error for non-positive closes, or a pricing error
+    /// when a leg cannot be revalued.
+    pub fn historical(
+        &self,
+        legs: &[Leg],
+        underlying_price: Positive,
+        candles: &[OhlcvCandle],
+    ) -> Result<VaRResult, SimulationError> {
+        self.validate()?;
+        let steps = self
+            .horizon_days
+            .to_dec()
+            .round()
+            .to_usize()
+            .unwrap_or(1)
+            .max(1);
+        if candles.len() <= steps {
+            return Err(SimulationError::invalid_parameters(&format!(
+                "historical VaR needs more than {steps} candles, got {}",
+                candles.len()
+            )));
+        }
+        let closes = candles
+            .iter()
+            .map(|candle| Positive::new_decimal(candle.close))
+            .collect::<Result<Vec<Positive>, _>>()?;
+
+        let revaluation = Revaluation::new(legs, underlying_price)?;
+        let pnl = closes
+            .iter()
+            .zip(closes.iter().skip(steps))
+            .map(|(start, end)| {
+                let scenario_price = underlying_price * (*end / *start);
+                Ok(revaluation.change(scenario_price, Decimal::ZERO, self.horizon_days)?)
+            })
+            .collect::<Result<Vec<Decimal>, SimulationError>>()?;
+        Ok(self.result(VaRMethod::Historical, legs, pnl))
+    }
+
+    /// Monte Carlo VaR with full revaluation at the final price of
+    /// `simulations` paths drawn from `walk`. The walk starts at the current
+    /// underlying price and should span the risk horizon.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] for an invalid
+    /// configuration, any error raised by the walker, or a pricing error when
+    /// a leg cannot be revalued.
+    pub fn monte_carlo(
+        &self,
+        legs: &[Leg],
+        walk: &WalkParams<Positive, Positive>,
+    ) -> Result<VaRResult, SimulationError> {
+        self.validate()?;
+        let underlying_price = walk.ystep_as_positive()?;
+        let revaluation = Revaluation::new(legs, underlying_price)?;
+        let pnl = (0..self.simulations)
+            .map(|_| {
+                let path = walk.walker.generate(walk)?;
+                let final_price = path.last().copied().ok_or_else(|| {
+                    SimulationError::walk_error("Monte Carlo VaR walk produced no prices")
+                })?;
+                Ok(revaluation.change(final_price, Decimal::ZERO, self.horizon_days)?)
+            })
+            .collect::<Result<Vec<Decimal>, SimulationError>>()?;
+        Ok(self.result(VaRMethod::MonteCarlo, legs, pnl))
+    }
+
+    /// Runs the three methods on the same legs for side-by-side reporting.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error raised by any of the methods.
+    pub fn report(
+        &self,
+        legs: &[Leg],
+        underlying_price: Positive,
+        volatility: Positive,
+        candles: &[OhlcvCandle],
+        walk: &WalkParams<Positive, Positive>,
+    ) -> Result<VaRReport, SimulationError> {
+        Ok(VaRReport {
+            delta_gamma: self.delta_gamma(legs, underlying_price, volatility)?,
+            historical: self.historical(legs, underlying_price, candles)?,
+            monte_carlo: self.monte_carlo(legs, walk)?,
+        })
+    }
+
+    fn validate(&self) -> Result<(), SimulationError> {
+        if self.confidence <= Decimal::ZERO || self.confidence >= Decimal::ONE {
+            return Err(SimulationError::invalid_parameters(
+                "VaR confidence must be strictly between 0 and 1",
+            ));
+        }
+        if self.simulations == 0 {
+            return Err(SimulationError::invalid_parameters(
+                "VaR needs at least one simulation",
+            ));
+        }
+        Ok(())
+    }
+
+    fn result(&self, method: VaRMethod, legs: &[Leg], mut pnl: Vec<Decimal>) -> VaRResult {
+        pnl.sort();
+        let (value_at_risk, expected_shortfall) = tail_loss(&pnl, self.confidence);
+        let investment: Decimal = legs.iter().map(|leg| leg.total_cost().to_dec()).sum();
+        VaRResult {
+            method,
+            confidence: self.confidence,
+            horizon_days: self.horizon_days,
+            value_at_risk,
+            expected_shortfall,
+            metrics: metrics(&pnl, investment),
+            pnl,
+        }
+    }
+}
+
+/// VaR and Expected Shortfall of an ascending P&L sample as positive loss
+/// magnitudes.
+fn tail_loss(sorted_pnl: &[Decimal], confidence: Decimal) -> (Decimal, Decimal) {
+    let count = Decimal::from(sorted_pnl.len());
+    let index = ((Decimal::ONE - confidence) * count)
+        .ceil()
+        .to_usize()
+        .unwrap_or(0)
+        .saturating_sub(1)
+        .min(sorted_pnl.len().saturating_sub(1));
+    let Some(quantile) = sorted_pnl.get(index) else {
+        return (Decimal::ZERO, Decimal::ZERO);
+    };
+    let tail = sorted_pnl.get(..=index).unwrap_or_default();
+    let tail_mean = tail.iter().sum::<Decimal>() / Decimal::from(tail.len());
+    (
+        (-*quantile).max(Decimal::ZERO),
+        (-tail_mean).max(Decimal::ZERO),
+    )
+}
+
+fn metrics(sorted_pnl: &[Decimal], investment: Decimal) -> VaRMetrics {
+    let (var_95, es_95) = tail_loss(sorted_pnl, dec!(0.95));
+    let (var_99, _) = tail_loss(sorted_pnl, dec!(0.99));
+    let count = Decimal::from(sorted_pnl.len().max(1));
+    let mean = sorted_pnl.iter().sum::<Decimal>() / count;
+    let variance = sorted_pnl
+        .iter()
+        .map(|pnl| (*pnl - mean) * (*pnl - mean))
+        .sum::<Decimal>()
+        / count;
+    let std_dev = variance.sqrt().unwrap_or_default();
+
+    let (severe_loss_probability, worst_loss_ratio) = if investment > Decimal::ZERO {
+        let severe = sorted_pnl
+            .iter()
+            .filter(|pnl| -**pnl > investment * dec!(0.5))
+            .count();
+        let worst_loss = sorted_pnl
+            .first()
+            .map_or(Decimal::ZERO, |pnl| (-*pnl).max(Decimal::ZERO));
+        (Decimal::from(severe) / count, worst_loss / investment)
+    } else {
+        (Decimal::ZERO, Decimal::ZERO)
+    };
+
+    VaRMetrics {
+        var_95,
+        var_99,
+        es_95,
+        severe_loss_probability: Positive::new_decimal(severe_loss_probability)
+            .unwrap_or(Positive::ZERO),
+        worst_loss_ratio: Positive::new_decimal(worst_loss_ratio).unwrap_or(Positive::ZERO),
+        mean_pnl: mean,
+        pnl_std_dev: std_dev,
+    }
+}
+
+#[cfg(test)]
+mod tests_var {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::model::leg::{FuturePosition, SpotPosition};
+    use crate::model::option::Options;
+    use crate::model::position::Position;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::simulation::WalkType;
+    use crate::simulation::steps::Step;
+    use crate::simulation::walk_test_support::RampWalker;
+    use crate::utils::TimeFrame;
+    use chrono::{NaiveDate, Utc};
+    use positive::pos_or_panic;
+
+    fn spot_leg() -> Leg {
+        Leg::spot(SpotPosition::long(
+            "TEST".to_string(),
+            Positive::TEN,
+            Positive::HUNDRED,
+        ))
+    }
+
+    fn short_call_leg() -> Leg {
+        short_option_leg(
+            OptionType::European,
+            OptionStyle::Call,
+            pos_or_panic!(105.0),
+        )
+    }
+
+    fn short_option_leg(option_type: OptionType, style: OptionStyle, strike: Positive) -> Leg {
+        let option = Options::new(
+            option_type,
+            Side::Short,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::ONE,
+            Positive::HUNDRED,
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Leg::option(Position::new(
+            option,
+            pos_or_panic!(1.5),
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        ))
+    }
+
+    fn candles(closes: &[f64]) -> Vec<OhlcvCandle> {
+        closes
+            .iter()
+            .map(|close| {
+                let close = Decimal::from_f64(*close).unwrap();
+                OhlcvCandle {
+                    date: NaiveDate::from_ymd_opt(2026, 1, 2).unwrap(),
+                    time: "00:00:00".to_string(),
+                    open: close,
+                    high: close,
+                    low: close,
+                    close,
+                    volume: 0,
+                }
+            })
+            .collect()
+    }
+
+    fn walk(delta: Positive) -> WalkParams<Positive, Positive> {
+        WalkParams {
+            size: 2,
+            init_step: Step::new(
+                Positive::ONE,
+                TimeFrame::Day,
+                ExpirationDate::Days(pos_or_panic!(30.0)),
+                Positive::HUNDRED,
+            ),
+            walk_type: WalkType::GeometricBrownian {
+                dt: pos_or_panic!(1.0 / 365.0),
+                drift: Decimal::ZERO,
+                volatility: pos_or_panic!(0.2),
+            },
+            walker: Box::new(RampWalker { delta }),
+        }
+    }
+
+    #[test]
+    fn test_delta_gamma_linear_matches_normal_var() {
+        let var = ValueAtRisk::default().with_horizon_days(pos_or_panic!(365.0));
+        let result = var
+            .delta_gamma(&[spot_leg()], Positive::HUNDRED, pos_or_panic!(0.2))
+            .unwrap();
+        // 10 units × 100 × 20% × 1.645
+        assert_eq!(result.method, VaRMethod::DeltaGamma);
+        assert!((result.value_at_risk - dec!(328.97)).abs() < dec!(0.5));
+        assert!(result.expected_shortfall > result.value_at_risk);
+        assert!(result.metrics.var_99 > result.metrics.var_95);
+    }
+
+    #[test]
+    fn test_delta_gamma_short_gamma_skews_losses() {
+        let var = ValueAtRisk::default();
+        let result = var
+            .delta_gamma(&[short_call_leg()], Positive::HUNDRED, pos_or_panic!(0.2))
+            .unwrap();
+        let worst = -*result.pnl.first().unwrap();
+        let best = *result.pnl.last().unwrap();
+        assert!(worst > best);
+    }
+
+    #[test]
+    fn test_historical_full_revaluation() {
+        let var = ValueAtRisk::default().with_confidence(dec!(0.75));
+        let history = candles(&[100.0, 110.0, 99.0, 99.0, 108.9]);
+        let result = var
+            .historical(&[spot_leg()], Positive::HUNDRED, &history)
+            .unwrap();
+        // Returns +10%, -10%, 0%, +10% on 10 units at 100.
+        assert_eq!(result.pnl.len(), 4);
+        assert_eq!(result.pnl.first().unwrap().round_dp(6), dec!(-100));
+        assert_eq!(result.value_at_risk.round_dp(6), dec!(100));
+        assert_eq!(result.expected_shortfall.round_dp(6), dec!(100));
+        // Sorted P&L -100, 0, 100, 100 on a leg costing 1000.
+        assert_eq!(result.metrics.mean_pnl.round_dp(6), dec!(25));
+        assert_eq!(result.metrics.pnl_std_dev.round_dp(4), dec!(82.9156));
+        assert_eq!(
+            result.metrics.worst_loss_ratio.round_to(6),
+            pos_or_panic!(0.1)
+        );
+        assert_eq!(result.metrics.severe_loss_probability, Positive::ZERO);
+    }
+
+    #[test]
+    fn test_american_leg_is_fully_revalued() {
+        let strike = Positive::HUNDRED;
+        let american = [short_option_leg(
+            OptionType::American,
+            OptionStyle::Put,
+            strike,
+        )];
+        let european = [short_option_leg(
+            OptionType::European,
+            OptionStyle::Put,
+            strike,
+        )];
+        let var = ValueAtRisk::default().with_simulations(200);
+        let volatility = pos_or_panic!(0.2);
+        let history = candles(&[100.0, 110.0, 99.0, 99.0, 108.9]);
+
+        let delta_gamma = var
+            .delta_gamma(&american, Positive::HUNDRED, volatility)
+            .unwrap();
+        let historical = var
+            .historical(&american, Positive::HUNDRED, &history)
+            .unwrap();
+        let monte_carlo = var.monte_carlo(&american, &walk(Positive::TWO)).unwrap();
+        assert!(delta_gamma.value_at_risk > Decimal::ZERO);
+        assert_eq!(historical.pnl.len(), 4);
+        assert!(!monte_carlo.pnl.is_empty());
+
+        // The early-exercise premium makes the short American put lose more
+        // than its European twin on the -10% day.
+        let european = var
+            .historical(&european, Positive::HUNDRED, &history)
+            .unwrap();
+        assert!(historical.value_at_risk > european.value_at_risk);
+    }
+
+    #[test]
+    fn test_historical_needs_enough_candles() {
+        let var = ValueAtRisk::default().with_horizon_days(pos_or_panic!(5.0));
+        let history = candles(&[100.0, 101.0, 102.0]);
+        assert!(
+            var.historical(&[spot_leg()], Positive::HUNDRED, &history)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_monte_carlo_revalues_final_price() {
+        let var = ValueAtRisk::default().with_simulations(20);
+        let future = Leg::future(FuturePosition::short(
+            "TEST".to_string(),
+            Positive::ONE,
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(60.0)),
+            Positive::TEN,
+            pos_or_panic!(100.0),
+        ));
+        let result = var.monte_carlo(&[future], &walk(Positive::TWO)).unwrap();
+        assert_eq!(result.method, VaRMethod::MonteCarlo);
+        assert_eq!(result.pnl.len(), 20);
+        // Every path ends at 102: a short future of size 10 loses 20.
+        assert_eq!(result.value_at_risk, dec!(20));
+        assert_eq!(result.metrics.es_95, dec!(20));
+    }
+
+    #[test]
+    fn test_report_and_invalid_confidence() {
+        let legs = [spot_leg(), short_call_leg()];
+        let history = candles(&[100.0, 101.0, 99.5, 100.5, 98.0, 99.0]);
+        let report = ValueAtRisk::default()
+            .with_simulations(50)
+            .report(
+                &legs,
+                Positive::HUNDRED,
+                pos_or_panic!(0.2),
+                &history,
+                &walk(Positive::ONE),
+            )
+            .unwrap();
+        assert_eq!(report.delta_gamma.method, VaRMethod::DeltaGamma);
+        assert_eq!(report.historical.method, VaRMethod::Historical);
+        assert_eq!(report.monte_carlo.method, VaRMethod::MonteCarlo);
+
+        let invalid = ValueAtRisk::default().with_confidence(Decimal::ONE);
+        assert!(
+            invalid
+                .delta_gamma(&legs, Positive::HUNDRED, pos_or_panic!(0.2))
+                .is_err()
+        );
+    }
+}
@@ -206,7 +206,7 @@ mod tests_generator_optionseries {
     use crate::error::SimulationError;
     use crate::series::{OptionSeries, OptionSeriesBuildParams};
     use crate::simulation::steps::{Step, Xstep, Ystep};
-    use crate::simulation::{WalkParams, WalkType, WalkTypeAble};
+    use crate::simulation::{BootstrapMethod, Regime, WalkParams, WalkType, WalkTypeAble};
     use crate::utils::TimeFrame;
     use crate::utils::time::convert_time_frame;
     use rust_decimal_macros::dec;
@@ -484,6 +484,26 @@ mod tests_generator_optionseries {
                 vol_speed: pos_or_panic!(0.1),
                 vol_mean: pos_or_panic!(0.2),
             },
+            WalkType::RegimeSwitching {
+                dt: pos_or_panic!(0.01),
+                regimes: vec![
+                    Regime::new(dec!(0.05), volatility),
+                    Regime::new(dec!(-0.2), pos_or_panic!(0.5)),
+                ],
+                transition_matrix: vec![vec![dec!(0.9), dec!(0.1)], vec![dec!(0.3), dec!(0.7)]],
+                initial_regime: 0,
+            },
+            WalkType::BlockBootstrap {
+                timeframe: TimeFrame::Day,
+                prices: vec![
+                    Positive::HUNDRED,
+                    pos_or_panic!(101.0),
+                    pos_or_panic!(99.0),
+                    pos_or_panic!(102.0),
+                ],
+                block_length: 2,
+                method: BootstrapMethod::Circular,
+            },
         ];
 
         // Make sure each walk type is handled by checking that the function runs
@@ -558,6 +578,60 @@ mod tests_generator_optionseries {
         );
     }
 
+    #[test]
+    fn test_generator_optionseries_regime_switching_and_bootstrap() {
+        let initial_series = create_test_option_series();
+        let walk_types = [
+            WalkType::RegimeSwitching {
+                dt: pos_or_panic!(0.004),
+                regimes: vec![
+                    Regime::new(dec!(0.1), pos_or_panic!(0.15)),
+                    Regime::new(dec!(-0.3), pos_or_panic!(0.4)),
+                    Regime::new(dec!(0.0), pos_or_panic!(0.8)),
+                ],
+                transition_matrix: vec![
+                    vec![dec!(0.9), dec!(0.08), dec!(0.02)],
+                    vec![dec!(0.1), dec!(0.8), dec!(0.1)],
+                    vec![dec!(0.0), dec!(0.5), dec!(0.5)],
+                ],
+                initial_regime: 0,
+            },
+            WalkType::BlockBootstrap {
+                timeframe: TimeFrame::Day,
+                prices: vec![
+                    Positive::HUNDRED,
+                    pos_or_panic!(102.0),
+                    pos_or_panic!(98.0),
+                    pos_or_panic!(105.0),
+                ],
+                block_length: 2,
+                method: BootstrapMethod::Stationary,
+            },
+        ];
+
+        for walk_type in walk_types {
+            let walk_params = WalkParams {
+                size: 5,
+                init_step: Step {
+                    x: Xstep::new(
+                        Positive::ONE,
+                        TimeFrame::Day,
+                        ExpirationDate::Days(pos_or_panic!(30.0)),
+                    ),
+                    y: Ystep::new(0, initial_series.clone()),
+                },
+                walk_type,
+                walker: Box::new(TestWalker {}),
+            };
+
+            let steps = match generator_optionseries(&walk_params) {
+                Ok(steps) => steps,
+                Err(e) => panic!("generator_optionseries failed: {e}"),
+            };
+            assert_eq!(steps.len(), 5);
+        }
+    }
+
     #[test]
     fn test_create_series_from_step() {
         // Test the create_series_from_step function directly
@@ -1,6 +1,7 @@
 use crate::ExpirationDate;
 use crate::chains::OptionChain;
 use crate::error::ChainError;
+use crate::pricing::YieldCurve;
 use crate::series::params::OptionSeriesBuildParams;
 use crate::utils::Len;
 use positive::Positive;
@@ -31,6 +32,10 @@ pub struct OptionSeries {
 
     /// The annual dividend yield of the underlying asset.
     pub dividend_yield: Option<Positive>,
+
+    /// Yield curves attached to individual expirations with
+    /// [`OptionSeries::attach_yield_curve`].
+    pub yield_curves: BTreeMap<ExpirationDate, YieldCurve>,
 }
 
 impl OptionSeries {
@@ -56,9 +61,52 @@ impl OptionSeries {
             chains: BTreeMap::new(),
             risk_free_rate: None,
             dividend_yield: None,
+            yield_curves: BTreeMap::new(),
         }
     }
 
+    /// Attaches a yield curve to the chain expiring at `expiration`.
+    ///
+    /// The risk-free rate of the chain and of each of its options is set to
+    /// the zero rate of the curve at that expiration, so pricing off the chain
+    /// discounts on the term structure rather than on a single series rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChainError::InvalidParameters`] when the series holds no
+    /// chain for `expiration`, and a [`ChainError`] when the expiration
+    /// cannot be converted to a year fraction.
+    pub fn attach_yield_curve(
+        &mut self,
+        expiration: ExpirationDate,
+        curve: YieldCurve,
+    ) -> Result<(), ChainError> {
+        let rate = curve.zero_rate(expiration.get_years()?);
+        let chain = self.chains.get_mut(&expiration).ok_or_else(|| {
+            ChainError::invalid_parameters(
+                "expiration",
+                &format!("no chain expiring at {expiration}"),
+            )
+        })?;
+        chain.risk_free_rate = Some(rate);
+        chain.options = std::mem::take(&mut chain.options)
+            .into_iter()
+            .map(|mut option| {
+                option.risk_free_rate = Some(rate);
+                option
+            })
+            .collect();
+        self.yield_curves.insert(expiration, curve);
+        Ok(())
+    }
+
+    /// Returns the yield curve attached to the chain expiring at
+    /// `expiration`, if any.
+    #[must_use]
+    pub fn yield_curve(&self, expiration: &ExpirationDate) -> Option<&YieldCurve> {
+        self.yield_curves.get(expiration)
+    }
+
     /// Retrieves the nearest expiring option chain from the collection of option chains.
     ///
     /// # Returns
@@ -186,6 +234,7 @@ impl OptionSeries {
             chains,
             risk_free_rate: price_params.risk_free_rate,
             dividend_yield: price_params.dividend_yield,
+            yield_curves: BTreeMap::new(),
         })
     }
 
@@ -257,7 +306,7 @@ impl Serialize for OptionSeries {
         S: Serializer,
     {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("OptionSeries", 5)?;
+        let mut state = serializer.serialize_struct("OptionSeries", 6)?;
 
         state.serialize_field("symbol", &self.symbol)?;
         state.serialize_field("underlying_price", &self.underlying_price)?;
@@ -281,6 +330,15 @@ impl Serialize for OptionSeries {
             state.serialize_field("dividend_yield", yield_val)?;
         }
 
+        if !self.yield_curves.is_empty() {
+            let mut curves_map: BTreeMap<String, &YieldCurve> = BTreeMap::new();
+            for (date, curve) in &self.yield_curves {
+                let key = date.get_date_string().map_err(serde::ser::Error::custom)?;
+                curves_map.insert(key, curve);
+            }
+            state.serialize_field("yield_curves", &curves_map)?;
+        }
+
         state.end()
     }
 }
@@ -300,6 +358,7 @@ impl<'de> Deserialize<'de> for OptionSeries {
             Chains,
             RiskFreeRate,
             DividendYield,
+            YieldCurves,
         }
 
         // Create a visitor to handle the deserialization
@@ -321,6 +380,7 @@ impl<'de> Deserialize<'de> for OptionSeries {
                 let mut string_chains: Option<BTreeMap<String, OptionChain>> = None;
                 let mut risk_free_rate = None;
                 let mut dividend_yield = None;
+                let mut string_curves: Option<BTreeMap<String, YieldCurve>> = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -354,6 +414,12 @@ impl<'de> Deserialize<'de> for OptionSeries {
                             }
                             dividend_yield = Some(map.next_value()?);
                         }
+                        Field::YieldCurves => {
+                            if string_curves.is_some() {
+                                return Err(de::Error::duplicate_field("yield_curves"));
+                            }
+                            string_curves = Some(map.next_value()?);
+                        }
                     }
                 }
 
@@ -371,12 +437,20 @@ impl<'de> Deserialize<'de> for OptionSeries {
                     chains.insert(expiration_date, chain);
                 }
 
+                let mut yield_curves = BTreeMap::new();
+                for (date_str, curve) in string_curves.unwrap_or_default() {
+                    let expiration_date = ExpirationDate::from_string_to_days(&date_str)
+                        .map_err(|e| de::Error::custom(format!("Invalid date format: {e}")))?;
+                    yield_curves.insert(expiration_date, curve);
+                }
+
                 Ok(OptionSeries {
                     symbol,
                     underlying_price,
                     chains,
                     risk_free_rate,
                     dividend_yield,
+                    yield_curves,
                 })
             }
         }
@@ -388,6 +462,7 @@ impl<'de> Deserialize<'de> for OptionSeries {
             "chains",
             "risk_free_rate",
             "dividend_yield",
+            "yield_curves",
         ];
 
         // Use our visitor to deserialize
@@ -833,4 +908,53 @@ mod tests_option_series {
             assert_eq!(cloned_expirations, original_expirations);
         }
     }
+
+    mod tests_yield_curves {
+        use super::*;
+        use crate::pricing::YieldCurve;
+
+        #[test]
+        fn test_attach_yield_curve_reprices_chain_rates() {
+            let mut series = create_test_series();
+            let expiration = ExpirationDate::Days(pos_or_panic!(30.0));
+            let curve = YieldCurve::from_zero_rates(&[
+                (pos_or_panic!(0.01), dec!(0.02)),
+                (Positive::ONE, dec!(0.04)),
+            ])
+            .unwrap();
+            let rate = curve.zero_rate(expiration.get_years().unwrap());
+
+            series
+                .attach_yield_curve(expiration, curve.clone())
+                .unwrap();
+
+            let chain = &series.chains[&expiration];
+            assert_eq!(chain.risk_free_rate, Some(rate));
+            assert!(
+                chain
+                    .options
+                    .iter()
+                    .all(|option| option.risk_free_rate == Some(rate))
+            );
+            assert_eq!(series.yield_curve(&expiration), Some(&curve));
+            // Other expirations keep the series rate.
+            let weekly = ExpirationDate::Days(pos_or_panic!(7.0));
+            assert_eq!(series.chains[&weekly].risk_free_rate, Some(dec!(0.05)));
+            assert!(series.yield_curve(&weekly).is_none());
+
+            let json = serde_json::to_string(&series).unwrap();
+            let restored: OptionSeries = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.yield_curves.len(), 1);
+        }
+
+        #[test]
+        fn test_attach_yield_curve_unknown_expiration() {
+            let mut series = create_test_series();
+            let result = series.attach_yield_curve(
+                ExpirationDate::Days(pos_or_panic!(90.0)),
+                YieldCurve::flat(dec!(0.03)),
+            );
+            assert!(result.is_err());
+        }
+    }
 }
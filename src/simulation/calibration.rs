@@ -0,0 +1,1254 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Calibration of [`WalkType`] models to historical prices.
+//!
+//! A [`WalkCalibrator`] is built from closing prices or OHLCV candles sampled
+//! at a fixed [`TimeFrame`] and fits one [`WalkModel`] at a time:
+//!
+//! | Model | Estimator |
+//! |-------|-----------|
+//! | Geometric Brownian | Gaussian MLE of the log returns (sample moments) |
+//! | Log returns | AR(1) regression of the log returns |
+//! | Mean reverting | Ornstein–Uhlenbeck regression of the price levels |
+//! | Jump diffusion | Merton MLE of the log returns (Poisson mixture) |
+//! | GARCH | GARCH(1,1) Gaussian MLE of the log returns |
+//! | Heston | QMLE of the Euler variance dynamics on a realised variance proxy |
+//! | Telegraph | Run-length estimator on high/low volatility regimes |
+//!
+//! Every fit reports [`FitStatistics`]. Log-likelihoods are densities of the
+//! observed *prices* (log-return models carry the `1 / S` Jacobian), so AIC
+//! and BIC can be compared across models fitted to the same data, which is
+//! what [`WalkCalibrator::best_fit`] does.
+//!
+//! Returned parameters follow the conventions of the walk kernels: rates and
+//! volatilities are annualised with the calibrator's `dt`, and the
+//! price-level models (mean reverting, jump diffusion) are expressed in price
+//! units at the last observed price.
+
+use crate::error::SimulationError;
+use crate::model::decimal::finite_decimal;
+use crate::pricing::telegraph::estimate_telegraph_parameters;
+use crate::simulation::WalkType;
+use crate::utils::{OhlcvCandle, TimeFrame};
+use crate::volatility::optimize::nelder_mead;
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::f64::consts::{LN_2, PI};
+
+/// Minimum number of prices accepted by a [`WalkCalibrator`].
+pub const MIN_CALIBRATION_PRICES: usize = 30;
+
+/// Number of Poisson terms kept in the Merton return density.
+const JUMP_TERMS: usize = 10;
+
+/// RiskMetrics decay of the EWMA filter that turns per-step variance
+/// estimates into the Heston variance proxy.
+const VARIANCE_PROXY_DECAY: f64 = 0.94;
+
+/// Convergence tolerance of the likelihood maximisations.
+const OPTIMIZER_TOLERANCE: f64 = 1e-10;
+
+/// Iteration cap of the likelihood maximisations.
+const OPTIMIZER_MAX_ITERATIONS: usize = 4000;
+
+/// Probability bound applied before inverting the normal CDF.
+const PIT_CLAMP: f64 = 1e-12;
+
+/// Walk models that can be calibrated to historical prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WalkModel {
+    /// [`WalkType::GeometricBrownian`].
+    GeometricBrownian,
+    /// [`WalkType::LogReturns`] with an AR(1) autocorrelation.
+    LogReturns,
+    /// [`WalkType::MeanReverting`].
+    MeanReverting,
+    /// [`WalkType::JumpDiffusion`].
+    JumpDiffusion,
+    /// [`WalkType::Garch`].
+    Garch,
+    /// [`WalkType::Heston`].
+    Heston,
+    /// [`WalkType::Telegraph`].
+    Telegraph,
+}
+
+impl WalkModel {
+    /// Every calibratable model.
+    pub const ALL: [WalkModel; 7] = [
+        WalkModel::GeometricBrownian,
+        WalkModel::LogReturns,
+        WalkModel::MeanReverting,
+        WalkModel::JumpDiffusion,
+        WalkModel::Garch,
+        WalkModel::Heston,
+        WalkModel::Telegraph,
+    ];
+
+    /// Number of free parameters estimated for the model.
+    #[must_use]
+    pub fn parameters(self) -> usize {
+        match self {
+            WalkModel::GeometricBrownian => 2,
+            WalkModel::LogReturns | WalkModel::MeanReverting => 3,
+            WalkModel::Garch => 4,
+            WalkModel::JumpDiffusion | WalkModel::Heston => 5,
+            WalkModel::Telegraph => 6,
+        }
+    }
+}
+
+/// Goodness of fit of a walk calibration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FitStatistics {
+    /// Log-likelihood of the observed prices under the fitted model.
+    pub log_likelihood: Decimal,
+    /// Akaike information criterion, `2k − 2 ln L`.
+    pub aic: Decimal,
+    /// Bayesian information criterion, `k ln n − 2 ln L`.
+    pub bic: Decimal,
+    /// Kolmogorov–Smirnov distance between the probability integral
+    /// transforms of the observations and the uniform distribution.
+    pub ks_statistic: Decimal,
+    /// Lag-one autocorrelation of the normal scores of the residuals.
+    pub residual_autocorrelation: Decimal,
+    /// Number of observations entering the likelihood.
+    pub observations: usize,
+    /// Number of estimated parameters.
+    pub parameters: usize,
+}
+
+impl FitStatistics {
+    /// Builds the statistics from per-observation log densities and
+    /// conditional CDF values.
+    fn from_fit(fit: &Fit, parameters: usize) -> Result<Self, SimulationError> {
+        let observations = fit.log_densities.len();
+        let n = observations as f64;
+        let k = parameters as f64;
+        let log_likelihood: f64 = fit.log_densities.iter().sum();
+
+        let mut pit = fit.pit.clone();
+        pit.sort_by(f64::total_cmp);
+        let count = pit.len() as f64;
+        let ks = pit
+            .iter()
+            .enumerate()
+            .map(|(i, &u)| ((i + 1) as f64 / count - u).max(u - i as f64 / count))
+            .fold(0.0, f64::max);
+
+        let normal = Normal::standard();
+        let scores: Vec<f64> = fit
+            .pit
+            .iter()
+            .map(|&u| normal.inverse_cdf(u.clamp(PIT_CLAMP, 1.0 - PIT_CLAMP)))
+            .collect();
+
+        Ok(Self {
+            log_likelihood: decimal(log_likelihood, "simulation::calibration::log_likelihood")?,
+            aic: decimal(
+                2.0 * k - 2.0 * log_likelihood,
+                "simulation::calibration::aic",
+            )?,
+            bic: decimal(
+                k * n.ln() - 2.0 * log_likelihood,
+                "simulation::calibration::bic",
+            )?,
+            ks_statistic: decimal(ks, "simulation::calibration::ks_statistic")?,
+            residual_autocorrelation: decimal(
+                lag_one_autocorrelation(&scores),
+                "simulation::calibration::residual_autocorrelation",
+            )?,
+            observations,
+            parameters,
+        })
+    }
+}
+
+/// A fitted walk together with its goodness of fit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalkCalibration {
+    /// Calibrated model.
+    pub model: WalkModel,
+    /// Walk parameters ready to drive a simulation.
+    pub walk_type: WalkType,
+    /// Goodness of fit of the calibration.
+    pub fit: FitStatistics,
+}
+
+/// Per-observation output of a model fit.
+struct Fit {
+    walk_type: WalkType,
+    /// Log density of each observed price.
+    log_densities: Vec<f64>,
+    /// Conditional CDF of each observation (probability integral transform).
+    pit: Vec<f64>,
+}
+
+/// Calibrates [`WalkType`] models to a historical price series.
+#[derive(Debug, Clone)]
+pub struct WalkCalibrator {
+    dt: Positive,
+    prices: Vec<f64>,
+    log_prices: Vec<f64>,
+    returns: Vec<f64>,
+    /// Per-step variance estimate of each return, from the candle ranges
+    /// when available and from the squared returns otherwise.
+    step_variances: Vec<f64>,
+}
+
+impl WalkCalibrator {
+    /// Creates a calibrator over `prices` sampled once per `timeframe`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InsufficientHistoricalData`] for fewer than
+    /// [`MIN_CALIBRATION_PRICES`] prices and
+    /// [`SimulationError::InvalidParameters`] when a price is zero.
+    pub fn from_prices(prices: &[Positive], timeframe: TimeFrame) -> Result<Self, SimulationError> {
+        let closes: Vec<f64> = prices.iter().map(|p| p.to_f64()).collect();
+        Self::build(closes, None, timeframe)
+    }
+
+    /// Creates a calibrator over OHLCV candles (for instance from
+    /// [`crate::utils::read_ohlcv_from_zip`]) of the given `timeframe`.
+    ///
+    /// Closing prices drive every model; the Heston variance proxy uses the
+    /// Garman–Klass range estimator of each candle instead of squared
+    /// returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InsufficientHistoricalData`] for fewer than
+    /// [`MIN_CALIBRATION_PRICES`] candles and
+    /// [`SimulationError::InvalidParameters`] when a candle has a
+    /// non-positive price or a high below its low.
+    pub fn from_candles(
+        candles: &[OhlcvCandle],
+        timeframe: TimeFrame,
+    ) -> Result<Self, SimulationError> {
+        let mut closes = Vec::with_capacity(candles.len());
+        let mut ranges = Vec::with_capacity(candles.len());
+        for candle in candles {
+            let [open, high, low, close] = [candle.open, candle.high, candle.low, candle.close]
+                .map(|value| value.to_f64().unwrap_or(0.0));
+            if open <= 0.0 || low <= 0.0 || close <= 0.0 || high < low {
+                return Err(SimulationError::invalid_parameters(&format!(
+                    "invalid candle on {} {}: open {}, high {}, low {}, close {}",
+                    candle.date, candle.time, candle.open, candle.high, candle.low, candle.close
+                )));
+            }
+            let range = (high / low).ln();
+            let body = (close / open).ln();
+            ranges.push((0.5 * range * range - (2.0 * LN_2 - 1.0) * body * body).max(0.0));
+            closes.push(close);
+        }
+        // The return from close i to close i + 1 is realised during candle i + 1.
+        Self::build(
+            closes,
+            Some(ranges.into_iter().skip(1).collect()),
+            timeframe,
+        )
+    }
+
+    fn build(
+        prices: Vec<f64>,
+        ranges: Option<Vec<f64>>,
+        timeframe: TimeFrame,
+    ) -> Result<Self, SimulationError> {
+        if prices.len() < MIN_CALIBRATION_PRICES {
+            return Err(SimulationError::InsufficientHistoricalData {
+                required: MIN_CALIBRATION_PRICES,
+                found: prices.len(),
+            });
+        }
+        if prices.iter().any(|&p| p <= 0.0 || !p.is_finite()) {
+            return Err(SimulationError::invalid_parameters(
+                "calibration prices must be strictly positive",
+            ));
+        }
+        let log_prices: Vec<f64> = prices.iter().map(|p| p.ln()).collect();
+        let returns: Vec<f64> = log_prices
+            .iter()
+            .zip(log_prices.iter().skip(1))
+            .map(|(from, to)| to - from)
+            .collect();
+        let step_variances = ranges.unwrap_or_else(|| returns.iter().map(|r| r * r).collect());
+        Ok(Self {
+            dt: Positive::ONE / timeframe.periods_per_year(),
+            prices,
+            log_prices,
+            returns,
+            step_variances,
+        })
+    }
+
+    /// Time step between observations, in years.
+    #[must_use]
+    pub fn dt(&self) -> Positive {
+        self.dt
+    }
+
+    /// Number of prices in the series.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    /// Returns `true` when the series holds no prices.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.prices.is_empty()
+    }
+
+    /// Fits `model` to the series.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] when the data cannot
+    /// support the model (no price variation, no mean reversion for the
+    /// mean-reverting and Heston fits) and
+    /// [`SimulationError::NonFinite`] when an estimate is not finite.
+    pub fn calibrate(&self, model: WalkModel) -> Result<WalkCalibration, SimulationError> {
+        let fit = match model {
+            WalkModel::GeometricBrownian => self.fit_geometric_brownian(),
+            WalkModel::LogReturns => self.fit_log_returns(),
+            WalkModel::MeanReverting => self.fit_mean_reverting(),
+            WalkModel::JumpDiffusion => self.fit_jump_diffusion(),
+            WalkModel::Garch => self.fit_garch(),
+            WalkModel::Heston => self.fit_heston(),
+            WalkModel::Telegraph => self.fit_telegraph(),
+        }?;
+        Ok(WalkCalibration {
+            model,
+            fit: FitStatistics::from_fit(&fit, model.parameters())?,
+            walk_type: fit.walk_type,
+        })
+    }
+
+    /// Fits every model in `models` and returns the one with the lowest AIC.
+    ///
+    /// Models the data cannot support are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] when `models` is empty,
+    /// or the last calibration error when no model could be fitted.
+    pub fn best_fit(&self, models: &[WalkModel]) -> Result<WalkCalibration, SimulationError> {
+        let mut best: Option<WalkCalibration> = None;
+        let mut last_error =
+            SimulationError::invalid_parameters("no walk model given for calibration");
+        for &model in models {
+            match self.calibrate(model) {
+                Ok(calibration) => {
+                    if best
+                        .as_ref()
+                        .is_none_or(|current| calibration.fit.aic < current.fit.aic)
+                    {
+                        best = Some(calibration);
+                    }
+                }
+                Err(error) => last_error = error,
+            }
+        }
+        best.ok_or(last_error)
+    }
+
+    fn dt_f64(&self) -> f64 {
+        self.dt.to_f64()
+    }
+
+    fn last_price(&self) -> f64 {
+        self.prices.last().copied().unwrap_or(0.0)
+    }
+
+    /// Log prices at which each return is observed, i.e. the Jacobian terms
+    /// converting return densities into price densities.
+    fn return_jacobians(&self) -> impl Iterator<Item = &f64> {
+        self.log_prices.iter().skip(1)
+    }
+
+    fn fit_geometric_brownian(&self) -> Result<Fit, SimulationError> {
+        let dt = self.dt_f64();
+        let (mean, variance) = moments(&self.returns);
+        if variance <= 0.0 {
+            return Err(SimulationError::invalid_parameters(
+                "geometric Brownian calibration needs varying prices",
+            ));
+        }
+        let (log_densities, pit) = self
+            .returns
+            .iter()
+            .zip(self.return_jacobians())
+            .map(|(&r, &jacobian)| {
+                let (density, cdf) = gaussian(r, mean, variance);
+                (density - jacobian, cdf)
+            })
+            .unzip();
+        Ok(Fit {
+            walk_type: WalkType::GeometricBrownian {
+                dt: self.dt,
+                drift: decimal(mean / dt, "simulation::calibration::gbm::drift")?,
+                volatility: Positive::new((variance / dt).sqrt())?,
+            },
+            log_densities,
+            pit,
+        })
+    }
+
+    fn fit_log_returns(&self) -> Result<Fit, SimulationError> {
+        let dt = self.dt_f64();
+        let previous = self.returns.iter().copied();
+        let current = self.returns.iter().skip(1).copied();
+        let (intercept, slope, variance) = regression(previous.clone().zip(current.clone()));
+        if variance <= 0.0 {
+            return Err(SimulationError::invalid_parameters(
+                "log-returns calibration needs varying returns",
+            ));
+        }
+        let autocorrelation = slope.clamp(-1.0, 1.0);
+        let (log_densities, pit) = previous
+            .zip(current)
+            .zip(self.return_jacobians().skip(1))
+            .map(|((prev, r), &jacobian)| {
+                let (density, cdf) = gaussian(r, intercept + autocorrelation * prev, variance);
+                (density - jacobian, cdf)
+            })
+            .unzip();
+        Ok(Fit {
+            walk_type: WalkType::LogReturns {
+                dt: self.dt,
+                expected_return: decimal(
+                    intercept / dt,
+                    "simulation::calibration::log_returns::expected_return",
+                )?,
+                volatility: Positive::new((variance / dt).sqrt())?,
+                autocorrelation: Some(decimal(
+                    autocorrelation,
+                    "simulation::calibration::log_returns::autocorrelation",
+                )?),
+            },
+            log_densities,
+            pit,
+        })
+    }
+
+    /// Ornstein–Uhlenbeck regression `x₊ = a + b·x + ε` of the Euler scheme
+    /// `x₊ = x + κ(θ − x)dt + σ√dt·Z` used by the mean-reverting kernel.
+    fn fit_mean_reverting(&self) -> Result<Fit, SimulationError> {
+        let dt = self.dt_f64();
+        let pairs = self
+            .prices
+            .iter()
+            .copied()
+            .zip(self.prices.iter().skip(1).copied());
+        let (intercept, slope, variance) = regression(pairs.clone());
+        if slope >= 1.0 || variance <= 0.0 {
+            return Err(SimulationError::invalid_parameters(
+                "prices show no mean reversion",
+            ));
+        }
+        let speed = (1.0 - slope) / dt;
+        let mean = intercept / (1.0 - slope);
+        if mean <= 0.0 {
+            return Err(SimulationError::invalid_parameters(
+                "mean-reverting calibration produced a non-positive mean level",
+            ));
+        }
+        let sigma_abs = (variance / dt).sqrt();
+        let (log_densities, pit) = pairs
+            .map(|(x, next)| gaussian(next, intercept + slope * x, variance))
+            .unzip();
+        Ok(Fit {
+            walk_type: WalkType::MeanReverting {
+                dt: self.dt,
+                // The kernel scales its volatility by the mean level.
+                volatility: Positive::new(sigma_abs / mean)?,
+                speed: Positive::new(speed)?,
+                mean: Positive::new(mean)?,
+            },
+            log_densities,
+            pit,
+        })
+    }
+
+    /// Merton MLE: each log return is `c + s·Z + Σⱼ Jⱼ` with a Poisson(q)
+    /// number of `N(m, δ²)` jumps, maximised over `(c, ln s, ln q, m, ln δ)`.
+    fn fit_jump_diffusion(&self) -> Result<Fit, SimulationError> {
+        let dt = self.dt_f64();
+        let (mean, variance) = moments(&self.returns);
+        if variance <= 0.0 {
+            return Err(SimulationError::invalid_parameters(
+                "jump-diffusion calibration needs varying prices",
+            ));
+        }
+        let sd = variance.sqrt();
+        let (outliers, regular): (Vec<f64>, Vec<f64>) = self
+            .returns
+            .iter()
+            .partition(|r| (*r - mean).abs() > 3.0 * sd);
+        let n = self.returns.len() as f64;
+        let (jump_mean, jump_variance) = if outliers.len() > 1 {
+            moments(&outliers)
+        } else {
+            (0.0, 9.0 * variance)
+        };
+        let (_, regular_variance) = moments(&regular);
+        let start = [
+            mean,
+            regular_variance.max(0.25 * variance).sqrt().ln(),
+            (outliers.len().max(1) as f64 / n).ln(),
+            jump_mean,
+            jump_variance.max(variance).sqrt().ln(),
+        ];
+        let steps = [0.1 * sd, 0.2, 0.5, sd, 0.2];
+
+        let negative_log_likelihood = |p: &[f64]| -> f64 {
+            let Some(params) = JumpParams::from_slice(p) else {
+                return f64::INFINITY;
+            };
+            -self
+                .returns
+                .iter()
+                .map(|&r| params.density(r).0.ln())
+                .sum::<f64>()
+        };
+        let minimum = nelder_mead(
+            negative_log_likelihood,
+            &start,
+            &steps,
+            OPTIMIZER_TOLERANCE,
+            OPTIMIZER_MAX_ITERATIONS,
+        );
+        let params = JumpParams::from_slice(&minimum.point).ok_or_else(|| {
+            SimulationError::non_finite("simulation::calibration::jump_diffusion", minimum.value)
+        })?;
+
+        let (log_densities, pit) = self
+            .returns
+            .iter()
+            .zip(self.return_jacobians())
+            .map(|(&r, &jacobian)| {
+                let (density, cdf) = params.density(r);
+                (density.ln() - jacobian, cdf)
+            })
+            .unzip();
+
+        // The kernel applies arithmetic jumps, so the log-normal jump factor
+        // e^J − 1 is converted to price units at the last observed price.
+        let price = self.last_price();
+        let jump_variance = params.jump_sd * params.jump_sd;
+        let jump_factor_mean = (params.jump_mean + 0.5 * jump_variance).exp() - 1.0;
+        let jump_factor_sd =
+            ((2.0 * params.jump_mean + jump_variance).exp() * (jump_variance.exp() - 1.0)).sqrt();
+        Ok(Fit {
+            walk_type: WalkType::JumpDiffusion {
+                dt: self.dt,
+                drift: decimal(
+                    price * (params.drift + 0.5 * params.sd * params.sd) / dt,
+                    "simulation::calibration::jump_diffusion::drift",
+                )?,
+                volatility: Positive::new(params.sd / dt.sqrt())?,
+                intensity: Positive::new(params.intensity / dt)?,
+                jump_mean: decimal(
+                    price * jump_factor_mean,
+                    "simulation::calibration::jump_diffusion::jump_mean",
+                )?,
+                jump_volatility: Positive::new(price * jump_factor_sd)?,
+            },
+            log_densities,
+            pit,
+        })
+    }
+
+    /// GARCH(1,1) Gaussian MLE of `r = c + ε`, `h₊ = ω + α·ε² + β·h`, with
+    /// the recursion started at the sample variance.
+    fn fit_garch(&self) -> Result<Fit, SimulationError> {
+        let dt = self.dt_f64();
+        let (mean, variance) = moments(&self.returns);
+        if variance <= 0.0 {
+            return Err(SimulationError::invalid_parameters(
+                "GARCH calibration needs varying prices",
+            ));
+        }
+        let start = [mean, (0.05 * variance).ln(), 0.08, 0.87];
+        let steps = [0.1 * variance.sqrt(), 0.5, 0.03, 0.03];
+
+        let negative_log_likelihood = |p: &[f64]| -> f64 {
+            let Some(params) = GarchParams::from_slice(p) else {
+                return f64::INFINITY;
+            };
+            -params
+                .filter(&self.returns, variance)
+                .map(|(r, h)| gaussian(r, params.mean, h).0)
+                .sum::<f64>()
+        };
+        let minimum = nelder_mead(
+            negative_log_likelihood,
+            &start,
+            &steps,
+            OPTIMIZER_TOLERANCE,
+            OPTIMIZER_MAX_ITERATIONS,
+        );
+        let params = GarchParams::from_slice(&minimum.point).ok_or_else(|| {
+            SimulationError::non_finite("simulation::calibration::garch", minimum.value)
+        })?;
+
+        let (log_densities, pit) = params
+            .filter(&self.returns, variance)
+            .zip(self.return_jacobians())
+            .map(|((r, h), &jacobian)| {
+                let (density, cdf) = gaussian(r, params.mean, h);
+                (density - jacobian, cdf)
+            })
+            .unzip();
+        let long_run_variance = params.omega / (1.0 - params.alpha - params.beta);
+        Ok(Fit {
+            walk_type: WalkType::Garch {
+                dt: self.dt,
+                drift: decimal(params.mean / dt, "simulation::calibration::garch::drift")?,
+                volatility: Positive::new((long_run_variance / dt).sqrt())?,
+                alpha: Positive::new(params.alpha)?,
+                beta: Positive::new(params.beta)?,
+            },
+            log_densities,
+            pit,
+        })
+    }
+
+    /// Heston QMLE: the latent variance is replaced by an EWMA filter of the
+    /// per-step variance estimates, and the Euler transition
+    /// `(v₊ − v)/√v = κθ·dt/√v − κ·dt·√v + ξ√dt·η` is fitted by least
+    /// squares. A variance shock only shows in the realised variance of the
+    /// following step, so `ρ` is the correlation of each standardised return
+    /// shock with the next innovation of the proxy.
+    fn fit_heston(&self) -> Result<Fit, SimulationError> {
+        let dt = self.dt_f64();
+        let initial = self.step_variances.iter().sum::<f64>() / self.step_variances.len() as f64;
+        if initial <= 0.0 {
+            return Err(SimulationError::invalid_parameters(
+                "Heston calibration needs varying prices",
+            ));
+        }
+        // Annualised variance known before each return, plus the one after
+        // the last return.
+        let mut variances = Vec::with_capacity(self.step_variances.len() + 1);
+        let mut filtered = initial;
+        variances.push(filtered / dt);
+        for &step_variance in &self.step_variances {
+            filtered =
+                VARIANCE_PROXY_DECAY * filtered + (1.0 - VARIANCE_PROXY_DECAY) * step_variance;
+            variances.push(filtered / dt);
+        }
+        let transitions: Vec<(f64, f64)> = variances
+            .iter()
+            .copied()
+            .zip(variances.iter().skip(1).copied())
+            .collect();
+
+        // Normal equations of the two-regressor least squares without intercept.
+        let (mut s11, mut s12, mut s22, mut s1y, mut s2y) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for &(v, next) in &transitions {
+            let root = v.sqrt();
+            let (x1, x2, y) = (dt / root, -dt * root, (next - v) / root);
+            s11 += x1 * x1;
+            s12 += x1 * x2;
+            s22 += x2 * x2;
+            s1y += x1 * y;
+            s2y += x2 * y;
+        }
+        let determinant = s11 * s22 - s12 * s12;
+        let kappa_theta = (s1y * s22 - s2y * s12) / determinant;
+        let kappa = (s2y * s11 - s1y * s12) / determinant;
+        if !(kappa > 0.0 && kappa_theta > 0.0) {
+            return Err(SimulationError::invalid_parameters(
+                "variance proxy shows no mean reversion",
+            ));
+        }
+        let theta = kappa_theta / kappa;
+
+        let precision: f64 = transitions.iter().map(|(v, _)| 1.0 / v).sum();
+        let mean = self
+            .returns
+            .iter()
+            .zip(&transitions)
+            .map(|(r, (v, _))| r / v)
+            .sum::<f64>()
+            / precision;
+
+        let (return_shocks, variance_shocks): (Vec<f64>, Vec<f64>) = self
+            .returns
+            .iter()
+            .zip(&transitions)
+            .map(|(&r, &(v, next))| {
+                let root = v.sqrt();
+                let eta = (next - v) / root - kappa_theta * dt / root + kappa * dt * root;
+                ((r - mean) / (v * dt).sqrt(), eta)
+            })
+            .unzip();
+        let xi = (variance_shocks.iter().map(|e| e * e).sum::<f64>()
+            / variance_shocks.len() as f64
+            / dt)
+            .sqrt();
+        let leading = return_shocks
+            .get(..return_shocks.len().saturating_sub(1))
+            .unwrap_or_default();
+        let next_innovations = variance_shocks.get(1..).unwrap_or_default();
+        let rho = correlation(leading, next_innovations).clamp(-1.0, 1.0);
+
+        let (log_densities, pit) = self
+            .returns
+            .iter()
+            .zip(&transitions)
+            .zip(self.return_jacobians())
+            .map(|((&r, &(v, _)), &jacobian)| {
+                let (density, cdf) = gaussian(r, mean, v * dt);
+                (density - jacobian, cdf)
+            })
+            .unzip();
+        Ok(Fit {
+            walk_type: WalkType::Heston {
+                dt: self.dt,
+                drift: decimal(mean / dt, "simulation::calibration::heston::drift")?,
+                volatility: Positive::new(variances.last().copied().unwrap_or(theta).sqrt())?,
+                kappa: Positive::new(kappa)?,
+                theta: Positive::new(theta)?,
+                xi: Positive::new(xi)?,
+                rho: decimal(rho, "simulation::calibration::heston::rho")?,
+            },
+            log_densities,
+            pit,
+        })
+    }
+
+    /// Classifies each return as high (+1) or low (−1) volatility by its
+    /// absolute deviation against the median, estimates the switching rates
+    /// from the regime durations and the regime volatilities from the
+    /// returns of each regime.
+    fn fit_telegraph(&self) -> Result<Fit, SimulationError> {
+        let dt = self.dt_f64();
+        let (mean, variance) = moments(&self.returns);
+        if variance <= 0.0 {
+            return Err(SimulationError::invalid_parameters(
+                "telegraph calibration needs varying prices",
+            ));
+        }
+        let deviations: Vec<f64> = self.returns.iter().map(|r| (r - mean).abs()).collect();
+        let mut sorted = deviations.clone();
+        sorted.sort_by(f64::total_cmp);
+        let median = sorted.get(sorted.len() / 2).copied().unwrap_or(0.0);
+        let high: Vec<bool> = deviations.iter().map(|&d| d > median).collect();
+
+        let signal = deviations
+            .iter()
+            .map(|&d| decimal(d - median, "simulation::calibration::telegraph::signal"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (lambda_up, lambda_down) = estimate_telegraph_parameters(&signal, Decimal::ZERO)?;
+
+        let regime_variance = |state: bool| {
+            let squares: Vec<f64> = self
+                .returns
+                .iter()
+                .zip(&high)
+                .filter(|(_, h)| **h == state)
+                .map(|(r, _)| (r - mean) * (r - mean))
+                .collect();
+            squares.iter().sum::<f64>() / squares.len().max(1) as f64
+        };
+        let (up_variance, down_variance) = (regime_variance(true), regime_variance(false));
+        if down_variance <= 0.0 {
+            return Err(SimulationError::invalid_parameters(
+                "telegraph calibration needs varying returns in both regimes",
+            ));
+        }
+
+        let (log_densities, pit) = self
+            .returns
+            .iter()
+            .zip(&high)
+            .zip(self.return_jacobians())
+            .map(|((&r, &h), &jacobian)| {
+                let state_variance = if h { up_variance } else { down_variance };
+                let (density, cdf) = gaussian(r, mean, state_variance);
+                (density - jacobian, cdf)
+            })
+            .unzip();
+        let dt_decimal = self.dt.to_dec();
+        Ok(Fit {
+            walk_type: WalkType::Telegraph {
+                dt: self.dt,
+                drift: decimal(mean / dt, "simulation::calibration::telegraph::drift")?,
+                volatility: Positive::new((variance / dt).sqrt())?,
+                lambda_up: Positive::new_decimal(lambda_up / dt_decimal)?,
+                lambda_down: Positive::new_decimal(lambda_down / dt_decimal)?,
+                vol_multiplier_up: Some(Positive::new((up_variance / variance).sqrt())?),
+                vol_multiplier_down: Some(Positive::new((down_variance / variance).sqrt())?),
+            },
+            log_densities,
+            pit,
+        })
+    }
+}
+
+/// Per-step Merton parameters.
+struct JumpParams {
+    drift: f64,
+    sd: f64,
+    intensity: f64,
+    jump_mean: f64,
+    jump_sd: f64,
+}
+
+impl JumpParams {
+    /// Decodes `(c, ln s, ln q, m, ln δ)`, rejecting more than one expected
+    /// jump per step.
+    fn from_slice(p: &[f64]) -> Option<Self> {
+        let &[drift, log_sd, log_intensity, jump_mean, log_jump_sd] = p else {
+            return None;
+        };
+        let params = Self {
+            drift,
+            sd: log_sd.exp(),
+            intensity: log_intensity.exp(),
+            jump_mean,
+            jump_sd: log_jump_sd.exp(),
+        };
+        (params.intensity <= 1.0 && params.sd > 0.0 && params.jump_sd > 0.0).then_some(params)
+    }
+
+    /// Density and CDF of a log return under the truncated Poisson mixture.
+    fn density(&self, r: f64) -> (f64, f64) {
+        let mut weight = (-self.intensity).exp();
+        let (mut density, mut cdf) = (0.0, 0.0);
+        for jumps in 0..JUMP_TERMS {
+            let k = jumps as f64;
+            if jumps > 0 {
+                weight *= self.intensity / k;
+            }
+            let variance = self.sd * self.sd + k * self.jump_sd * self.jump_sd;
+            let (log_density, term_cdf) = gaussian(r, self.drift + k * self.jump_mean, variance);
+            density += weight * log_density.exp();
+            cdf += weight * term_cdf;
+        }
+        (density.max(f64::MIN_POSITIVE), cdf)
+    }
+}
+
+/// Per-step GARCH(1,1) parameters.
+#[derive(Clone, Copy)]
+struct GarchParams {
+    mean: f64,
+    omega: f64,
+    alpha: f64,
+    beta: f64,
+}
+
+impl GarchParams {
+    /// Decodes `(c, ln ω, α, β)`, rejecting non-stationary persistence.
+    fn from_slice(p: &[f64]) -> Option<Self> {
+        let &[mean, log_omega, alpha, beta] = p else {
+            return None;
+        };
+        (alpha >= 0.0 && beta >= 0.0 && alpha + beta < 1.0).then(|| Self {
+            mean,
+            omega: log_omega.exp(),
+            alpha,
+            beta,
+        })
+    }
+
+    /// Pairs each return with its conditional variance.
+    fn filter<'a>(self, returns: &'a [f64], initial: f64) -> impl Iterator<Item = (f64, f64)> + 'a {
+        returns.iter().scan(initial, move |h, &r| {
+            let current = *h;
+            let shock = r - self.mean;
+            *h = self.omega + self.alpha * shock * shock + self.beta * current;
+            Some((r, current))
+        })
+    }
+}
+
+/// Log density and CDF of `N(mean, variance)` at `x`.
+fn gaussian(x: f64, mean: f64, variance: f64) -> (f64, f64) {
+    let deviation = x - mean;
+    let log_density = -0.5 * ((2.0 * PI * variance).ln() + deviation * deviation / variance);
+    let cdf = Normal::standard().cdf(deviation / variance.sqrt());
+    (log_density, cdf)
+}
+
+/// Sample mean and maximum-likelihood variance.
+fn moments(values: &[f64]) -> (f64, f64) {
+    let n = values.len().max(1) as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n;
+    (mean, variance)
+}
+
+/// Least squares `y = a + b·x + ε`, returning `(a, b, Var ε)`.
+fn regression(pairs: impl Iterator<Item = (f64, f64)> + Clone) -> (f64, f64, f64) {
+    let n = pairs.clone().count().max(1) as f64;
+    let (sum_x, sum_y) = pairs
+        .clone()
+        .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+    let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+    let (sxx, sxy) = pairs.clone().fold((0.0, 0.0), |(sxx, sxy), (x, y)| {
+        (
+            sxx + (x - mean_x) * (x - mean_x),
+            sxy + (x - mean_x) * (y - mean_y),
+        )
+    });
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    let intercept = mean_y - slope * mean_x;
+    let variance = pairs
+        .map(|(x, y)| {
+            let residual = y - intercept - slope * x;
+            residual * residual
+        })
+        .sum::<f64>()
+        / n;
+    (intercept, slope, variance)
+}
+
+/// Pearson correlation of two equally long samples.
+fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let (mean_a, var_a) = moments(a);
+    let (mean_b, var_b) = moments(b);
+    let covariance = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| (x - mean_a) * (y - mean_b))
+        .sum::<f64>()
+        / a.len().max(1) as f64;
+    if var_a > 0.0 && var_b > 0.0 {
+        covariance / (var_a * var_b).sqrt()
+    } else {
+        0.0
+    }
+}
+
+fn lag_one_autocorrelation(values: &[f64]) -> f64 {
+    let lagged: Vec<f64> = values.iter().skip(1).copied().collect();
+    let leading = values.get(..lagged.len()).unwrap_or_default();
+    correlation(leading, &lagged)
+}
+
+fn decimal(value: f64, context: &'static str) -> Result<Decimal, SimulationError> {
+    finite_decimal(value).ok_or_else(|| SimulationError::non_finite(context, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::deterministic_rng;
+    use chrono::NaiveDate;
+    use positive::pos_or_panic;
+    use rand::RngExt;
+    use rand::rngs::StdRng;
+    use rand_distr::{Distribution, StandardNormal};
+    use rust_decimal_macros::dec;
+
+    const STEPS: usize = 2500;
+
+    fn normal(rng: &mut StdRng) -> f64 {
+        StandardNormal.sample(rng)
+    }
+
+    fn to_prices(path: &[f64]) -> Vec<Positive> {
+        path.iter().map(|&p| pos_or_panic!(p)).collect()
+    }
+
+    fn log_path(returns: impl Iterator<Item = f64>) -> Vec<Positive> {
+        let mut price: f64 = 100.0;
+        let mut path = vec![price];
+        for r in returns {
+            price *= r.exp();
+            path.push(price);
+        }
+        to_prices(&path)
+    }
+
+    fn calibrator(prices: &[Positive]) -> WalkCalibrator {
+        WalkCalibrator::from_prices(prices, TimeFrame::Day).unwrap()
+    }
+
+    fn dt() -> f64 {
+        1.0 / 252.0
+    }
+
+    #[test]
+    fn test_geometric_brownian_recovers_volatility() {
+        let mut rng = deterministic_rng(7);
+        let sd = 0.25 * dt().sqrt();
+        let prices = log_path((0..STEPS).map(|_| sd * normal(&mut rng)));
+        let calibration = calibrator(&prices)
+            .calibrate(WalkModel::GeometricBrownian)
+            .unwrap();
+        let WalkType::GeometricBrownian { volatility, .. } = calibration.walk_type else {
+            panic!("unexpected walk type");
+        };
+        assert!((volatility.to_f64() - 0.25).abs() < 0.015);
+        assert_eq!(calibration.fit.observations, STEPS);
+        assert!(calibration.fit.ks_statistic < dec!(0.05));
+        assert!(calibration.fit.residual_autocorrelation.abs() < dec!(0.06));
+    }
+
+    #[test]
+    fn test_log_returns_recovers_autocorrelation() {
+        let mut rng = deterministic_rng(11);
+        let sd = 0.2 * dt().sqrt();
+        let mut previous = 0.0;
+        let prices = log_path((0..STEPS).map(|_| {
+            previous = 0.3 * previous + sd * normal(&mut rng);
+            previous
+        }));
+        let calibration = calibrator(&prices)
+            .calibrate(WalkModel::LogReturns)
+            .unwrap();
+        let WalkType::LogReturns {
+            autocorrelation,
+            volatility,
+            ..
+        } = calibration.walk_type
+        else {
+            panic!("unexpected walk type");
+        };
+        let autocorrelation = autocorrelation.unwrap().to_f64().unwrap();
+        assert!((autocorrelation - 0.3).abs() < 0.05);
+        assert!((volatility.to_f64() - 0.2).abs() < 0.015);
+    }
+
+    fn ou_path(rng: &mut StdRng) -> Vec<Positive> {
+        let (speed, mean, sigma) = (5.0, 100.0, 20.0);
+        let mut x: f64 = 90.0;
+        let mut path = vec![x];
+        for _ in 0..STEPS {
+            x += speed * (mean - x) * dt() + sigma * dt().sqrt() * normal(rng);
+            path.push(x);
+        }
+        to_prices(&path)
+    }
+
+    #[test]
+    fn test_mean_reverting_regression_recovers_level_and_speed() {
+        let prices = ou_path(&mut deterministic_rng(3));
+        let calibration = calibrator(&prices)
+            .calibrate(WalkModel::MeanReverting)
+            .unwrap();
+        let WalkType::MeanReverting {
+            volatility,
+            speed,
+            mean,
+            ..
+        } = calibration.walk_type
+        else {
+            panic!("unexpected walk type");
+        };
+        assert!((mean.to_f64() - 100.0).abs() < 2.0);
+        assert!(speed.to_f64() > 2.5 && speed.to_f64() < 9.0);
+        assert!((volatility.to_f64() - 0.2).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_best_fit_prefers_mean_reversion_on_ou_data() {
+        let prices = ou_path(&mut deterministic_rng(5));
+        let best = calibrator(&prices)
+            .best_fit(&[WalkModel::GeometricBrownian, WalkModel::MeanReverting])
+            .unwrap();
+        assert_eq!(best.model, WalkModel::MeanReverting);
+    }
+
+    #[test]
+    fn test_mean_reverting_rejects_trending_prices() {
+        let prices: Vec<Positive> = (0..100)
+            .map(|i| pos_or_panic!(100.0 * 1.01f64.powi(i)))
+            .collect();
+        let result = calibrator(&prices).calibrate(WalkModel::MeanReverting);
+        assert!(matches!(
+            result,
+            Err(SimulationError::InvalidParameters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_garch_mle_recovers_persistence() {
+        let mut rng = deterministic_rng(17);
+        let (omega, alpha, beta): (f64, f64, f64) = (2e-6, 0.1, 0.85);
+        let mut variance = omega / (1.0 - alpha - beta);
+        let prices = log_path((0..STEPS * 2).map(|_| {
+            let shock = variance.sqrt() * normal(&mut rng);
+            variance = omega + alpha * shock * shock + beta * variance;
+            shock
+        }));
+        let calibration = calibrator(&prices).calibrate(WalkModel::Garch).unwrap();
+        let WalkType::Garch {
+            alpha: fitted_alpha,
+            beta: fitted_beta,
+            volatility,
+            ..
+        } = calibration.walk_type
+        else {
+            panic!("unexpected walk type");
+        };
+        assert!((fitted_alpha.to_f64() - alpha).abs() < 0.05);
+        assert!((fitted_beta.to_f64() - beta).abs() < 0.08);
+        let long_run = (omega / (1.0 - alpha - beta) * 252.0).sqrt();
+        assert!((volatility.to_f64() - long_run).abs() < 0.1 * long_run);
+
+        let gbm = calibrator(&prices)
+            .calibrate(WalkModel::GeometricBrownian)
+            .unwrap();
+        assert!(calibration.fit.aic < gbm.fit.aic);
+    }
+
+    #[test]
+    fn test_jump_diffusion_mle_detects_jumps() {
+        let mut rng = deterministic_rng(23);
+        let sd = 0.15 * dt().sqrt();
+        let prices = log_path((0..STEPS).map(|_| {
+            let jump = if rng.random::<f64>() < 0.02 {
+                -0.05 + 0.02 * normal(&mut rng)
+            } else {
+                0.0
+            };
+            sd * normal(&mut rng) + jump
+        }));
+        let calibrator = calibrator(&prices);
+        let calibration = calibrator.calibrate(WalkModel::JumpDiffusion).unwrap();
+        let WalkType::JumpDiffusion {
+            volatility,
+            intensity,
+            jump_mean,
+            ..
+        } = calibration.walk_type
+        else {
+            panic!("unexpected walk type");
+        };
+        assert!((volatility.to_f64() - 0.15).abs() < 0.03);
+        // 0.02 jumps per day is about five per year.
+        assert!(intensity.to_f64() > 2.5 && intensity.to_f64() < 10.0);
+        assert!(jump_mean < Decimal::ZERO);
+
+        let gbm = calibrator.calibrate(WalkModel::GeometricBrownian).unwrap();
+        assert!(calibration.fit.aic < gbm.fit.aic);
+        assert!(calibration.fit.ks_statistic < gbm.fit.ks_statistic);
+    }
+
+    #[test]
+    fn test_heston_qmle_recovers_leverage_sign() {
+        let mut rng = deterministic_rng(29);
+        let (kappa, theta, xi, rho) = (3.0, 0.04, 0.4, -0.7);
+        let mut variance: f64 = theta;
+        let prices = log_path((0..STEPS * 2).map(|_| {
+            let z1 = normal(&mut rng);
+            let z2 = rho * z1 + (1.0 - rho * rho).sqrt() * normal(&mut rng);
+            let r = (variance * dt()).sqrt() * z1;
+            variance =
+                (variance + kappa * (theta - variance) * dt() + xi * (variance * dt()).sqrt() * z2)
+                    .max(1e-6);
+            r
+        }));
+        let calibration = calibrator(&prices).calibrate(WalkModel::Heston).unwrap();
+        let WalkType::Heston {
+            theta: fitted_theta,
+            rho: fitted_rho,
+            kappa: fitted_kappa,
+            xi: fitted_xi,
+            ..
+        } = calibration.walk_type
+        else {
+            panic!("unexpected walk type");
+        };
+        assert!(fitted_rho < Decimal::ZERO);
+        assert!(fitted_theta.to_f64() > 0.02 && fitted_theta.to_f64() < 0.08);
+        assert!(fitted_kappa > Positive::ZERO);
+        assert!(fitted_xi > Positive::ZERO);
+    }
+
+    #[test]
+    fn test_telegraph_separates_volatility_regimes() {
+        let mut rng = deterministic_rng(31);
+        let mut high = false;
+        let prices = log_path((0..STEPS).map(|_| {
+            if rng.random::<f64>() < 0.05 {
+                high = !high;
+            }
+            let vol = if high { 0.4 } else { 0.1 };
+            vol * dt().sqrt() * normal(&mut rng)
+        }));
+        let calibration = calibrator(&prices).calibrate(WalkModel::Telegraph).unwrap();
+        let WalkType::Telegraph {
+            vol_multiplier_up,
+            vol_multiplier_down,
+            lambda_up,
+            lambda_down,
+            ..
+        } = calibration.walk_type
+        else {
+            panic!("unexpected walk type");
+        };
+        assert!(vol_multiplier_up.unwrap() > Positive::ONE);
+        assert!(vol_multiplier_down.unwrap() < Positive::ONE);
+        assert!(lambda_up > Positive::ZERO && lambda_down > Positive::ZERO);
+    }
+
+    #[test]
+    fn test_from_candles_uses_ranges() {
+        let mut rng = deterministic_rng(37);
+        let date = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+        let mut close = 100.0;
+        let candles: Vec<OhlcvCandle> = (0..200)
+            .map(|i| {
+                let open: f64 = close;
+                close = open * (0.01 * normal(&mut rng)).exp();
+                let high = open.max(close) * 1.004;
+                let low = open.min(close) * 0.996;
+                OhlcvCandle {
+                    date: date + chrono::Days::new(i),
+                    time: "16:00:00".to_string(),
+                    open: finite_decimal(open).unwrap(),
+                    high: finite_decimal(high).unwrap(),
+                    low: finite_decimal(low).unwrap(),
+                    close: finite_decimal(close).unwrap(),
+                    volume: 1_000,
+                }
+            })
+            .collect();
+        let calibrator = WalkCalibrator::from_candles(&candles, TimeFrame::Day).unwrap();
+        assert_eq!(calibrator.len(), 200);
+        assert_eq!(calibrator.dt(), Positive::ONE / pos_or_panic!(252.0));
+        let calibration = calibrator.calibrate(WalkModel::GeometricBrownian).unwrap();
+        assert_eq!(calibration.fit.observations, 199);
+        assert!(calibration.fit.bic > calibration.fit.aic);
+
+        let mut broken = candles.clone();
+        broken[3].low = dec!(0.0);
+        assert!(WalkCalibrator::from_candles(&broken, TimeFrame::Day).is_err());
+    }
+
+    #[test]
+    fn test_insufficient_history_is_rejected() {
+        let prices = vec![Positive::HUNDRED; MIN_CALIBRATION_PRICES - 1];
+        let result = WalkCalibrator::from_prices(&prices, TimeFrame::Day);
+        assert!(matches!(
+            result,
+            Err(SimulationError::InsufficientHistoricalData { found, .. })
+                if found == MIN_CALIBRATION_PRICES - 1
+        ));
+    }
+
+    #[test]
+    fn test_constant_prices_cannot_be_calibrated() {
+        let prices = vec![Positive::HUNDRED; MIN_CALIBRATION_PRICES];
+        let calibrator = calibrator(&prices);
+        for model in WalkModel::ALL {
+            assert!(calibrator.calibrate(model).is_err(), "{model:?}");
+        }
+        assert!(calibrator.best_fit(&WalkModel::ALL).is_err());
+        assert!(calibrator.best_fit(&[]).is_err());
+    }
+}
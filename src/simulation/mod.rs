@@ -130,6 +130,14 @@ mod params;
 pub mod exit;
 mod stats;
 
+/// Parameter estimation of [`WalkType`] models from historical prices and
+/// OHLCV candles, with likelihood-based goodness-of-fit statistics.
+pub mod calibration;
+
+/// Correlated simulation of several underlyings on a common time grid, for
+/// multi-asset Monte Carlo pricing and joint portfolio scenarios.
+pub mod multi_asset;
+
 /// Generic walk driver shared by every step generator.
 ///
 /// Contains [`walk_steps`], the single implementation of the
@@ -142,11 +150,16 @@ mod walk_driver;
 #[cfg(test)]
 pub(crate) mod walk_test_support;
 
+pub use calibration::{
+    FitStatistics, MIN_CALIBRATION_PRICES, WalkCalibration, WalkCalibrator, WalkModel,
+};
 pub use exit::{ExitPolicy, check_exit_policy};
-pub use model::{WalkPath, WalkType};
+pub use model::{BootstrapMethod, Regime, WalkPath, WalkType};
+pub use multi_asset::{AssetWalk, CorrelationMatrix, MultiAssetPath, MultiAssetSimulator};
 pub use params::WalkParams;
 pub use stats::SimulationStats;
 pub use traits::{
-    Simulate, WalkTypeAble, WalkTypeAbleClone, custom_walk, garch_walk, heston_walk, telegraph_walk,
+    Simulate, WalkTypeAble, WalkTypeAbleClone, custom_walk, garch_walk, heston_walk,
+    regime_switching_walk, telegraph_walk,
 };
 pub use walk_driver::{expanding_window_vols, generator_positive, walk_steps, walk_steps_par};
@@ -1,6 +1,8 @@
+use crate::error::SimulationError;
 use crate::utils::TimeFrame;
 use positive::Positive;
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use utoipa::ToSchema;
@@ -134,6 +136,20 @@ pub enum WalkType {
         vol_multiplier_down: Option<Positive>,
     },
 
+    /// Markov regime-switching process (geometric walk whose drift and
+    /// volatility follow a discrete-time Markov chain over several regimes)
+    RegimeSwitching {
+        /// Time step size (fraction of year: daily=1/365, weekly=1/52, etc.)
+        dt: Positive,
+        /// Drift and volatility of each regime (at least two)
+        regimes: Vec<Regime>,
+        /// Per-step transition probabilities: row `i` holds the probabilities
+        /// of moving from regime `i` to each regime, and must sum to one
+        transition_matrix: Vec<Vec<Decimal>>,
+        /// Index of the regime the walk starts in
+        initial_regime: usize,
+    },
+
     /// Represents historical price data for a given timeframe.
     ///
     /// This encapsulates the historical price data, including the timeframe
@@ -161,8 +177,133 @@ pub enum WalkType {
         /// This field can be utilized in scenarios where a symbol (e.g., stock ticker, identifier) may or may not be required.
         symbol: Option<String>,
     },
+
+    /// Block bootstrap of historical log returns.
+    ///
+    /// Instead of replaying the history, the walk starts from the initial
+    /// step and resamples blocks of consecutive log returns of `prices`, so
+    /// every path keeps the short-range dependence of the data while
+    /// producing a different sequence of moves.
+    BlockBootstrap {
+        /// The timeframe of the historical data.
+        timeframe: TimeFrame,
+        /// Historical prices whose log returns are resampled (at least two).
+        prices: Vec<Positive>,
+        /// Block length: fixed for [`BootstrapMethod::Circular`], mean of the
+        /// geometric block length for [`BootstrapMethod::Stationary`].
+        block_length: usize,
+        /// Block sampling scheme.
+        method: BootstrapMethod,
+    },
 }
 
+/// Drift and volatility of one regime of a [`WalkType::RegimeSwitching`] walk.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Regime {
+    /// Drift parameter (expected return while the regime is active)
+    pub drift: Decimal,
+    /// Volatility parameter (annualized standard deviation while the regime is active)
+    pub volatility: Positive,
+}
+
+impl Regime {
+    /// Creates a regime with the given drift and annualized volatility.
+    #[must_use]
+    pub fn new(drift: Decimal, volatility: Positive) -> Self {
+        Self { drift, volatility }
+    }
+}
+
+/// Block sampling scheme of a [`WalkType::BlockBootstrap`] walk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum BootstrapMethod {
+    /// Politis–Romano stationary bootstrap: each step starts a new block at a
+    /// random return with probability `1 / block_length`, so block lengths
+    /// are geometric with mean `block_length`.
+    #[default]
+    Stationary,
+    /// Circular block bootstrap: blocks of exactly `block_length` returns
+    /// starting at random positions, wrapping around the end of the history.
+    Circular,
+}
+
+impl Display for BootstrapMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BootstrapMethod::Stationary => write!(f, "Stationary"),
+            BootstrapMethod::Circular => write!(f, "Circular"),
+        }
+    }
+}
+
+/// Validates the regimes and transition matrix of a
+/// [`WalkType::RegimeSwitching`] walk.
+///
+/// # Errors
+///
+/// Returns [`SimulationError::InvalidParameters`] when there are fewer than
+/// two regimes, the matrix is not square with one row per regime, a
+/// probability lies outside `[0, 1]`, a row does not sum to one, or
+/// `initial_regime` is out of range.
+pub(crate) fn validate_regime_switching(
+    regimes: &[Regime],
+    transition_matrix: &[Vec<Decimal>],
+    initial_regime: usize,
+) -> Result<(), SimulationError> {
+    let n = regimes.len();
+    if n < 2 {
+        return Err(SimulationError::invalid_parameters(
+            "regime switching needs at least two regimes",
+        ));
+    }
+    if transition_matrix.len() != n || transition_matrix.iter().any(|row| row.len() != n) {
+        return Err(SimulationError::invalid_parameters(&format!(
+            "transition matrix must be {n}x{n}, one row and column per regime"
+        )));
+    }
+    for (i, row) in transition_matrix.iter().enumerate() {
+        if row.iter().any(|p| *p < Decimal::ZERO || *p > Decimal::ONE) {
+            return Err(SimulationError::invalid_parameters(&format!(
+                "transition probabilities of regime {i} must lie in [0, 1]"
+            )));
+        }
+        let total: Decimal = row.iter().sum();
+        if (total - Decimal::ONE).abs() > REGIME_ROW_TOLERANCE {
+            return Err(SimulationError::invalid_parameters(&format!(
+                "transition probabilities of regime {i} sum to {total}, expected 1"
+            )));
+        }
+    }
+    if initial_regime >= n {
+        return Err(SimulationError::invalid_parameters(&format!(
+            "initial regime {initial_regime} out of range for {n} regimes"
+        )));
+    }
+    Ok(())
+}
+
+/// Regime reached from a transition `row` for the uniform draw `u`.
+///
+/// Walks the cumulative probabilities of the row; rounding that leaves `u`
+/// past the last cumulative value falls back to the last reachable regime.
+pub(crate) fn next_regime(row: &[f64], u: f64) -> usize {
+    let mut cumulative = 0.0;
+    let mut last_reachable = 0;
+    for (regime, probability) in row.iter().enumerate() {
+        if *probability > 0.0 {
+            cumulative += probability;
+            last_reachable = regime;
+            if u < cumulative {
+                return regime;
+            }
+        }
+    }
+    last_reachable
+}
+
+/// Tolerance on the row sums of a regime transition matrix.
+const REGIME_ROW_TOLERANCE: Decimal = dec!(1e-10);
+
 /// A simulated walk that exposes the per-step volatility path alongside the
 /// price path.
 ///
@@ -188,10 +329,13 @@ impl WalkType {
     ///
     /// For the nine synthetic walk types this is the annualized `volatility`
     /// field of the variant (the initial volatility for stochastic-volatility
-    /// models such as `Garch`, `Heston`, `Custom` and `Telegraph`).
+    /// models such as `Garch`, `Heston`, `Custom` and `Telegraph`). For
+    /// [`WalkType::RegimeSwitching`] it is the volatility of the initial
+    /// regime.
     ///
-    /// Returns `None` for [`WalkType::Historical`], whose volatility is not a
-    /// parameter but must be estimated from the provided price history.
+    /// Returns `None` for [`WalkType::Historical`] and
+    /// [`WalkType::BlockBootstrap`], whose volatility is not a parameter but
+    /// must be estimated from the provided price history.
     #[must_use]
     pub fn volatility(&self) -> Option<Positive> {
         match self {
@@ -204,7 +348,12 @@ impl WalkType {
             | WalkType::Heston { volatility, .. }
             | WalkType::Custom { volatility, .. }
             | WalkType::Telegraph { volatility, .. } => Some(*volatility),
-            WalkType::Historical { .. } => None,
+            WalkType::RegimeSwitching {
+                regimes,
+                initial_regime,
+                ..
+            } => regimes.get(*initial_regime).map(|regime| regime.volatility),
+            WalkType::Historical { .. } | WalkType::BlockBootstrap { .. } => None,
         }
     }
 }
@@ -302,6 +451,15 @@ impl Display for WalkType {
                 f,
                 "Telegraph {{ dt: {dt}, drift: {drift}, volatility: {volatility}, lambda_up: {lambda_up}, lambda_down: {lambda_down}, vol_multiplier_up: {vol_multiplier_up:?}, vol_multiplier_down: {vol_multiplier_down:?} }}"
             ),
+            WalkType::RegimeSwitching {
+                dt,
+                regimes,
+                transition_matrix,
+                initial_regime,
+            } => write!(
+                f,
+                "RegimeSwitching {{ dt: {dt}, regimes: {regimes:?}, transition_matrix: {transition_matrix:?}, initial_regime: {initial_regime} }}"
+            ),
             WalkType::Historical {
                 timeframe,
                 prices,
@@ -310,6 +468,15 @@ impl Display for WalkType {
                 f,
                 "Historical {{ timeframe: {timeframe}, prices: {prices:?}, symbol: {symbol:?} }}"
             ),
+            WalkType::BlockBootstrap {
+                timeframe,
+                prices,
+                block_length,
+                method,
+            } => write!(
+                f,
+                "BlockBootstrap {{ timeframe: {timeframe}, prices: {prices:?}, block_length: {block_length}, method: {method} }}"
+            ),
         }
     }
 }
@@ -744,6 +911,81 @@ mod tests_walk_type {
         assert!(display.contains("vol_multiplier_down: Some(0.8)"));
     }
 
+    fn two_regimes() -> WalkType {
+        WalkType::RegimeSwitching {
+            dt: pos_or_panic!(0.01),
+            regimes: vec![
+                Regime::new(dec!(0.05), pos_or_panic!(0.15)),
+                Regime::new(dec!(-0.1), pos_or_panic!(0.45)),
+            ],
+            transition_matrix: vec![vec![dec!(0.95), dec!(0.05)], vec![dec!(0.2), dec!(0.8)]],
+            initial_regime: 1,
+        }
+    }
+
+    #[test]
+    fn test_display_regime_switching_and_bootstrap() {
+        let display = format!("{}", two_regimes());
+        assert!(display.contains("RegimeSwitching"));
+        assert!(display.contains("initial_regime: 1"));
+        assert!(display.contains("0.95"));
+
+        let walk = WalkType::BlockBootstrap {
+            timeframe: TimeFrame::Day,
+            prices: vec![Positive::HUNDRED, pos_or_panic!(101.0)],
+            block_length: 5,
+            method: BootstrapMethod::Circular,
+        };
+        let display = format!("{walk}");
+        assert!(display.contains("BlockBootstrap"));
+        assert!(display.contains("block_length: 5"));
+        assert!(display.contains("method: Circular"));
+    }
+
+    #[test]
+    fn test_volatility_of_regime_switching_and_bootstrap() {
+        assert_eq!(two_regimes().volatility(), Some(pos_or_panic!(0.45)));
+        let walk = WalkType::BlockBootstrap {
+            timeframe: TimeFrame::Day,
+            prices: vec![Positive::HUNDRED, pos_or_panic!(101.0)],
+            block_length: 5,
+            method: BootstrapMethod::default(),
+        };
+        assert_eq!(walk.volatility(), None);
+    }
+
+    #[test]
+    fn test_validate_regime_switching() {
+        let regimes = [
+            Regime::new(Decimal::ZERO, pos_or_panic!(0.1)),
+            Regime::new(Decimal::ZERO, pos_or_panic!(0.3)),
+        ];
+        let valid = vec![vec![dec!(0.9), dec!(0.1)], vec![dec!(0.5), dec!(0.5)]];
+        assert!(validate_regime_switching(&regimes, &valid, 0).is_ok());
+        assert!(validate_regime_switching(&regimes, &valid, 2).is_err());
+        assert!(validate_regime_switching(&regimes[..1], &[vec![Decimal::ONE]], 0).is_err());
+        let not_square = vec![vec![dec!(0.9), dec!(0.1)]];
+        assert!(validate_regime_switching(&regimes, &not_square, 0).is_err());
+        let bad_row = vec![vec![dec!(0.9), dec!(0.2)], vec![dec!(0.5), dec!(0.5)]];
+        assert!(validate_regime_switching(&regimes, &bad_row, 0).is_err());
+        let negative = vec![vec![dec!(1.1), dec!(-0.1)], vec![dec!(0.5), dec!(0.5)]];
+        assert!(matches!(
+            validate_regime_switching(&regimes, &negative, 0),
+            Err(SimulationError::InvalidParameters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_next_regime_follows_cumulative_probabilities() {
+        let row = [0.2, 0.0, 0.8];
+        assert_eq!(next_regime(&row, 0.1), 0);
+        assert_eq!(next_regime(&row, 0.2), 2);
+        assert_eq!(next_regime(&row, 0.99), 2);
+        // Rounding past the last cumulative value never lands on an
+        // unreachable regime.
+        assert_eq!(next_regime(&[0.5, 0.499_999, 0.0], 0.999_999_5), 1);
+    }
+
     #[test]
     fn test_log_returns_without_autocorrelation() {
         let walk = WalkType::LogReturns {
@@ -1103,6 +1345,37 @@ mod tests_serialize {
         assert_eq!(walk_type, deserialized);
     }
 
+    #[test]
+    fn test_regime_switching_and_bootstrap_serialization() {
+        let walk_types = [
+            WalkType::RegimeSwitching {
+                dt: pos_or_panic!(0.004),
+                regimes: vec![
+                    Regime::new(dec!(0.08), pos_or_panic!(0.12)),
+                    Regime::new(dec!(0.0), pos_or_panic!(0.25)),
+                    Regime::new(dec!(-0.3), pos_or_panic!(0.6)),
+                ],
+                transition_matrix: vec![
+                    vec![dec!(0.97), dec!(0.02), dec!(0.01)],
+                    vec![dec!(0.05), dec!(0.9), dec!(0.05)],
+                    vec![dec!(0.1), dec!(0.2), dec!(0.7)],
+                ],
+                initial_regime: 0,
+            },
+            WalkType::BlockBootstrap {
+                timeframe: TimeFrame::Day,
+                prices: vec![Positive::HUNDRED, pos_or_panic!(101.5), pos_or_panic!(99.8)],
+                block_length: 10,
+                method: BootstrapMethod::Stationary,
+            },
+        ];
+        for walk_type in walk_types {
+            let json = to_string(&walk_type).unwrap();
+            let deserialized: WalkType = from_str(&json).unwrap();
+            assert_eq!(walk_type, deserialized);
+        }
+    }
+
     #[test]
     fn test_deserialize_from_json_string() {
         let json = r#"{
@@ -0,0 +1,1415 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Correlated multi-asset path simulation.
+//!
+//! A [`MultiAssetSimulator`] advances several underlyings together, each with
+//! its own [`WalkType`], on a common time grid. At every step a vector of
+//! independent standard normals is multiplied by the Cholesky factor of a
+//! [`CorrelationMatrix`], and each asset's walk consumes its correlated
+//! component as the shock of its price equation. Auxiliary randomness (jump
+//! arrivals and sizes, Heston and custom volatility shocks, telegraph regime
+//! switches) stays asset-specific; the Heston variance shock is correlated
+//! with the asset's own price shock through its `rho`.
+//!
+//! The aligned paths price two-asset payoffs
+//! ([`OptionType::Rainbow`], [`OptionType::Spread`], [`OptionType::Exchange`])
+//! by Monte Carlo, which cross-checks the closed forms of
+//! [`crate::pricing::rainbow_black_scholes`],
+//! [`crate::pricing::spread_black_scholes`] and
+//! [`crate::pricing::exchange_black_scholes`], and produce joint price
+//! scenarios for [`crate::portfolio::Portfolio::scenario_pnl`].
+
+use crate::Options;
+use crate::error::{PricingError, SimulationError};
+use crate::model::decimal::finite_decimal;
+use crate::model::types::{OptionStyle, OptionType, RainbowType, Side};
+use crate::pricing::monte_carlo::chunk_seed;
+use crate::pricing::{Payoff, PayoffInfo};
+use crate::simulation::WalkType;
+use crate::simulation::model::{next_regime, validate_regime_switching};
+use crate::utils::deterministic_rng;
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rand::RngExt;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, StandardNormal};
+use rayon::prelude::*;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+/// Smallest squared Cholesky pivot accepted as positive.
+const PIVOT_TOLERANCE: f64 = 1e-12;
+
+/// Largest asymmetry `|ρᵢⱼ − ρⱼᵢ|` tolerated in a correlation matrix.
+const SYMMETRY_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 10);
+
+/// Validated correlation matrix together with its Cholesky factor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationMatrix {
+    matrix: Vec<Vec<Decimal>>,
+    /// Rows of the lower-triangular factor `L` with `L·Lᵀ = matrix`.
+    lower: Vec<Vec<f64>>,
+}
+
+impl CorrelationMatrix {
+    /// Validates `matrix` and computes its Cholesky factor.
+    ///
+    /// # Errors
+    ///
+    /// * [`SimulationError::InvalidParameters`] when the matrix is empty,
+    ///   not square, not symmetric, has a diagonal entry other than one, or
+    ///   is not positive definite.
+    /// * [`SimulationError::InvalidCorrelation`] when an entry lies outside
+    ///   `[-1, 1]`.
+    pub fn new(matrix: Vec<Vec<Decimal>>) -> Result<Self, SimulationError> {
+        let dimension = matrix.len();
+        if dimension == 0 {
+            return Err(SimulationError::invalid_parameters(
+                "correlation matrix must not be empty",
+            ));
+        }
+        for (i, row) in matrix.iter().enumerate() {
+            if row.len() != dimension {
+                return Err(SimulationError::invalid_parameters(&format!(
+                    "correlation matrix must be square: row {i} has {} entries, expected {dimension}",
+                    row.len()
+                )));
+            }
+            for (j, &rho) in row.iter().enumerate() {
+                if !(-Decimal::ONE..=Decimal::ONE).contains(&rho) {
+                    return Err(SimulationError::InvalidCorrelation { rho });
+                }
+                if i == j && rho != Decimal::ONE {
+                    return Err(SimulationError::invalid_parameters(&format!(
+                        "correlation matrix diagonal must be one, found {rho} at ({i}, {i})"
+                    )));
+                }
+                let transposed = matrix.get(j).and_then(|r| r.get(i)).copied();
+                if transposed.is_some_and(|t| (t - rho).abs() > SYMMETRY_TOLERANCE) {
+                    return Err(SimulationError::invalid_parameters(&format!(
+                        "correlation matrix must be symmetric: entries ({i}, {j}) and ({j}, {i}) differ"
+                    )));
+                }
+            }
+        }
+
+        let mut lower: Vec<Vec<f64>> = Vec::with_capacity(dimension);
+        for (i, row) in matrix.iter().enumerate() {
+            let mut factor_row = Vec::with_capacity(i + 1);
+            for (previous, &rho) in lower.iter().zip(row) {
+                let dot: f64 = factor_row.iter().zip(previous).map(|(a, b)| a * b).sum();
+                let pivot = previous.last().copied().unwrap_or(1.0);
+                factor_row.push((rho.to_f64().unwrap_or(0.0) - dot) / pivot);
+            }
+            let squared = 1.0 - factor_row.iter().map(|l| l * l).sum::<f64>();
+            if squared <= PIVOT_TOLERANCE {
+                return Err(SimulationError::invalid_parameters(&format!(
+                    "correlation matrix is not positive definite (pivot {squared:e} at row {i})"
+                )));
+            }
+            factor_row.push(squared.sqrt());
+            lower.push(factor_row);
+        }
+        Ok(Self { matrix, lower })
+    }
+
+    /// Uncorrelated assets.
+    #[must_use]
+    pub fn identity(dimension: usize) -> Self {
+        let unit = |i: usize, j: usize| if i == j { 1.0 } else { 0.0 };
+        Self {
+            matrix: (0..dimension)
+                .map(|i| {
+                    (0..dimension)
+                        .map(|j| if i == j { Decimal::ONE } else { Decimal::ZERO })
+                        .collect()
+                })
+                .collect(),
+            lower: (0..dimension)
+                .map(|i| (0..=i).map(|j| unit(i, j)).collect())
+                .collect(),
+        }
+    }
+
+    /// Two assets with correlation `rho`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CorrelationMatrix::new`]; `|rho| = 1` is rejected as not
+    /// positive definite.
+    pub fn two_assets(rho: Decimal) -> Result<Self, SimulationError> {
+        Self::new(vec![vec![Decimal::ONE, rho], vec![rho, Decimal::ONE]])
+    }
+
+    /// Number of assets.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.matrix.len()
+    }
+
+    /// Correlation between assets `i` and `j`.
+    #[must_use]
+    pub fn get(&self, i: usize, j: usize) -> Option<Decimal> {
+        self.matrix.get(i).and_then(|row| row.get(j)).copied()
+    }
+
+    /// Rows of the correlation matrix.
+    #[must_use]
+    pub fn matrix(&self) -> &[Vec<Decimal>] {
+        &self.matrix
+    }
+
+    /// Rows of the lower-triangular Cholesky factor; row `i` holds `i + 1`
+    /// entries.
+    #[must_use]
+    pub fn cholesky_factor(&self) -> &[Vec<f64>] {
+        &self.lower
+    }
+
+    /// Maps independent standard normals to correlated ones, `L·z`.
+    ///
+    /// Missing entries of `independent` count as zero.
+    #[must_use]
+    pub fn correlate(&self, independent: &[f64]) -> Vec<f64> {
+        self.lower
+            .iter()
+            .map(|row| row.iter().zip(independent).map(|(l, z)| l * z).sum())
+            .collect()
+    }
+}
+
+/// One underlying of a [`MultiAssetSimulator`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetWalk {
+    /// Symbol of the underlying, used as key of the price scenarios.
+    pub symbol: String,
+    /// Price at the start of every path.
+    pub initial_price: Positive,
+    /// Dynamics of the underlying.
+    pub walk_type: WalkType,
+}
+
+impl AssetWalk {
+    /// Creates an asset walk.
+    #[must_use]
+    pub fn new(symbol: &str, initial_price: Positive, walk_type: WalkType) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            initial_price,
+            walk_type,
+        }
+    }
+}
+
+/// Aligned prices of every asset along one simulated path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiAssetPath {
+    /// One row per asset, each holding `steps + 1` prices starting at the
+    /// initial price.
+    pub prices: Vec<Vec<Positive>>,
+}
+
+impl MultiAssetPath {
+    /// Prices of asset `index` along the path.
+    #[must_use]
+    pub fn asset(&self, index: usize) -> Option<&[Positive]> {
+        self.prices.get(index).map(Vec::as_slice)
+    }
+
+    /// Number of steps of the path.
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.prices
+            .first()
+            .map_or(0, |row| row.len().saturating_sub(1))
+    }
+
+    /// Prices of every asset after `step` steps.
+    #[must_use]
+    pub fn prices_at(&self, step: usize) -> Option<Vec<Positive>> {
+        self.prices
+            .iter()
+            .map(|row| row.get(step).copied())
+            .collect()
+    }
+
+    /// Prices of every asset at the end of the path.
+    #[must_use]
+    pub fn terminal_prices(&self) -> Vec<Positive> {
+        self.prices
+            .iter()
+            .filter_map(|row| row.last().copied())
+            .collect()
+    }
+}
+
+/// Monte Carlo simulator of correlated underlyings.
+///
+/// Paths are simulated eagerly by [`MultiAssetSimulator::new`]. Path `p`
+/// draws from an RNG seeded with `(seed, p)`, so results are reproducible
+/// and independent of the rayon thread count.
+#[derive(Debug, Clone)]
+pub struct MultiAssetSimulator {
+    assets: Vec<AssetWalk>,
+    correlation: CorrelationMatrix,
+    paths: Vec<MultiAssetPath>,
+}
+
+impl MultiAssetSimulator {
+    /// Simulates `paths` paths of `steps` steps of the correlated `assets`.
+    ///
+    /// Every walk must share the same `dt` so that the paths stay aligned in
+    /// time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] when there are no
+    /// assets, paths or steps, when the correlation dimension does not match
+    /// the number of assets, when the walks use different `dt`, for invalid
+    /// [`WalkType::RegimeSwitching`] regimes, and for
+    /// [`WalkType::Historical`] and [`WalkType::BlockBootstrap`] walks, which
+    /// follow observed prices and cannot be correlated. Walk parameter errors of the kernels
+    /// ([`SimulationError::GarchStationarity`],
+    /// [`SimulationError::InvalidCorrelation`],
+    /// [`SimulationError::InvalidAutocorrelation`]) are propagated.
+    pub fn new(
+        assets: Vec<AssetWalk>,
+        correlation: CorrelationMatrix,
+        paths: usize,
+        steps: usize,
+        seed: u64,
+    ) -> Result<Self, SimulationError> {
+        if assets.is_empty() || paths == 0 || steps == 0 {
+            return Err(SimulationError::invalid_parameters(
+                "multi-asset simulation needs at least one asset, path and step",
+            ));
+        }
+        if correlation.dimension() != assets.len() {
+            return Err(SimulationError::invalid_parameters(&format!(
+                "correlation matrix of dimension {} for {} assets",
+                correlation.dimension(),
+                assets.len()
+            )));
+        }
+        let mut dts = assets.iter().map(|asset| walk_dt(&asset.walk_type));
+        let first_dt = dts.next().flatten();
+        if dts.any(|dt| dt != first_dt) {
+            return Err(SimulationError::invalid_parameters(
+                "correlated walks must share the same dt",
+            ));
+        }
+
+        let simulated = (0..paths)
+            .into_par_iter()
+            .map(|path| {
+                let mut rng = deterministic_rng(chunk_seed(seed, path));
+                simulate_path(&assets, &correlation, steps, &mut rng)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            assets,
+            correlation,
+            paths: simulated,
+        })
+    }
+
+    /// Simulates the risk-neutral dynamics of the underlyings of `option`.
+    ///
+    /// Each asset follows geometric Brownian motion with drift `r − q` over
+    /// `steps` equal steps to expiry. Two-asset options read the second
+    /// asset and the correlation from their exotic parameters, with the
+    /// defaults of the matching closed-form pricers; any other option type
+    /// simulates its single underlying.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] when a required
+    /// exotic parameter is missing, and the errors of
+    /// [`CorrelationMatrix::two_assets`] and [`MultiAssetSimulator::new`].
+    pub fn for_option(
+        option: &Options,
+        paths: usize,
+        steps: usize,
+        seed: u64,
+    ) -> Result<Self, SimulationError> {
+        let t = option.years_to_expiry()?;
+        let rate = option.term_risk_free_rate()?;
+        let steps_dec = Decimal::from(steps.max(1));
+        let dt = t / steps_dec;
+        let gbm = |volatility: Positive, dividend: Positive| WalkType::GeometricBrownian {
+            dt,
+            drift: rate
+                - dividend.to_dec()
+                - volatility.to_dec() * volatility.to_dec() / Decimal::TWO,
+            volatility,
+        };
+        let first = AssetWalk::new(
+            &option.underlying_symbol,
+            option.underlying_price,
+            gbm(option.implied_volatility, option.dividend_yield),
+        );
+        let second_symbol = format!("{}-2", option.underlying_symbol);
+        let params = option.exotic_params.as_ref();
+        let missing = |name: &str| {
+            SimulationError::invalid_parameters(&format!(
+                "{} requires exotic parameter {name}",
+                option.option_type
+            ))
+        };
+
+        let second = match &option.option_type {
+            OptionType::Rainbow { .. } => {
+                let params = params.ok_or_else(|| missing("rainbow_second_asset_price"))?;
+                let price = params
+                    .rainbow_second_asset_price
+                    .ok_or_else(|| missing("rainbow_second_asset_price"))?;
+                let volatility = params
+                    .rainbow_second_asset_volatility
+                    .ok_or_else(|| missing("rainbow_second_asset_volatility"))?;
+                let dividend = params
+                    .rainbow_second_asset_dividend
+                    .unwrap_or(option.dividend_yield);
+                let rho = params.rainbow_correlation.unwrap_or(Decimal::new(5, 1));
+                Some((price, volatility, dividend, rho))
+            }
+            OptionType::Spread { second_asset } => {
+                let params = params.ok_or_else(|| missing("spread_second_asset_volatility"))?;
+                let volatility = params
+                    .spread_second_asset_volatility
+                    .ok_or_else(|| missing("spread_second_asset_volatility"))?;
+                let rho = params
+                    .spread_correlation
+                    .ok_or_else(|| missing("spread_correlation"))?;
+                let dividend = params
+                    .spread_second_asset_dividend
+                    .unwrap_or(Positive::ZERO);
+                Some((*second_asset, volatility, dividend, rho))
+            }
+            OptionType::Exchange { second_asset } => {
+                let params = params.ok_or_else(|| missing("exchange_second_asset_volatility"))?;
+                let volatility = params
+                    .exchange_second_asset_volatility
+                    .ok_or_else(|| missing("exchange_second_asset_volatility"))?;
+                let rho = params
+                    .exchange_correlation
+                    .ok_or_else(|| missing("exchange_correlation"))?;
+                let dividend = params
+                    .exchange_second_asset_dividend
+                    .unwrap_or(Positive::ZERO);
+                Some((*second_asset, volatility, dividend, rho))
+            }
+            _ => None,
+        };
+
+        match second {
+            Some((price, volatility, dividend, rho)) => Self::new(
+                vec![
+                    first,
+                    AssetWalk::new(&second_symbol, price, gbm(volatility, dividend)),
+                ],
+                CorrelationMatrix::two_assets(rho)?,
+                paths,
+                steps,
+                seed,
+            ),
+            None => Self::new(
+                vec![first],
+                CorrelationMatrix::identity(1),
+                paths,
+                steps,
+                seed,
+            ),
+        }
+    }
+
+    /// Simulated underlyings.
+    #[must_use]
+    pub fn assets(&self) -> &[AssetWalk] {
+        &self.assets
+    }
+
+    /// Correlation of the price shocks.
+    #[must_use]
+    pub fn correlation(&self) -> &CorrelationMatrix {
+        &self.correlation
+    }
+
+    /// Every simulated path.
+    #[must_use]
+    pub fn get_paths(&self) -> &[MultiAssetPath] {
+        &self.paths
+    }
+
+    /// Path `index`.
+    #[must_use]
+    pub fn get_path(&self, index: usize) -> Option<&MultiAssetPath> {
+        self.paths.get(index)
+    }
+
+    /// Number of simulated paths.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Returns `true` when no path was simulated.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Terminal prices of every asset, one vector per path.
+    #[must_use]
+    pub fn get_last_prices(&self) -> Vec<Vec<Positive>> {
+        self.paths
+            .iter()
+            .map(MultiAssetPath::terminal_prices)
+            .collect()
+    }
+
+    /// Joint price scenarios after `step` steps, one per path, keyed by
+    /// asset symbol as in [`crate::portfolio::Portfolio::underlying_prices`].
+    /// Paths shorter than `step` yield no scenario.
+    #[must_use]
+    pub fn scenarios(&self, step: usize) -> Vec<BTreeMap<String, Positive>> {
+        self.paths
+            .iter()
+            .filter_map(|path| path.prices_at(step))
+            .map(|prices| {
+                self.assets
+                    .iter()
+                    .map(|asset| asset.symbol.clone())
+                    .zip(prices)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Sample correlation of the log returns of assets `first` and `second`,
+    /// pooled over every step of every path.
+    ///
+    /// Returns `None` for unknown assets or degenerate samples.
+    #[must_use]
+    pub fn realized_correlation(&self, first: usize, second: usize) -> Option<Decimal> {
+        let log_returns = |row: &[Positive]| -> Vec<f64> {
+            row.iter()
+                .zip(row.iter().skip(1))
+                .map(|(from, to)| (to.to_f64() / from.to_f64()).ln())
+                .collect()
+        };
+        let (mut xs, mut ys) = (Vec::new(), Vec::new());
+        for path in &self.paths {
+            xs.extend(log_returns(path.asset(first)?));
+            ys.extend(log_returns(path.asset(second)?));
+        }
+        let n = xs.len() as f64;
+        let mean_x = xs.iter().sum::<f64>() / n;
+        let mean_y = ys.iter().sum::<f64>() / n;
+        let (mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0);
+        for (x, y) in xs.iter().zip(&ys) {
+            sxx += (x - mean_x) * (x - mean_x);
+            syy += (y - mean_y) * (y - mean_y);
+            sxy += (x - mean_x) * (y - mean_y);
+        }
+        finite_decimal(sxy / (sxx * syy).sqrt())
+    }
+
+    /// Prices `option` as the discounted mean payoff over the simulated
+    /// paths.
+    ///
+    /// Rainbow options use the first `num_assets` assets, spread and
+    /// exchange options the first two (`S₁ − S₂`); any other option type is
+    /// evaluated on the path of the first asset through
+    /// [`OptionType::payoff`]. Like the closed-form pricers the result is per
+    /// unit and carries the side sign. Paths should be simulated under the
+    /// risk-neutral measure, for instance by
+    /// [`MultiAssetSimulator::for_option`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PricingError::MethodError`] when the option needs more
+    /// assets than were simulated, [`PricingError::UnsupportedOptionType`]
+    /// for an unknown rainbow type, and propagates expiry, rate and
+    /// non-finite errors.
+    pub fn get_mc_option_price(&self, option: &Options) -> Result<Decimal, PricingError> {
+        let required = match &option.option_type {
+            OptionType::Rainbow { num_assets, .. } => *num_assets,
+            OptionType::Spread { .. } | OptionType::Exchange { .. } => 2,
+            _ => 1,
+        };
+        if required > self.assets.len() {
+            return Err(PricingError::method_error(
+                "multi-asset Monte Carlo",
+                &format!(
+                    "{} needs {required} assets, {} simulated",
+                    option.option_type,
+                    self.assets.len()
+                ),
+            ));
+        }
+        if self.paths.is_empty() {
+            return Ok(Decimal::ZERO);
+        }
+
+        let strike = option.strike_price.to_f64();
+        let call_put = |underlying: f64| match option.option_style {
+            OptionStyle::Call => (underlying - strike).max(0.0),
+            OptionStyle::Put => (strike - underlying).max(0.0),
+        };
+        let mut total = 0.0;
+        for path in &self.paths {
+            let terminal: Vec<f64> = path
+                .terminal_prices()
+                .iter()
+                .map(Positive::to_f64)
+                .collect();
+            let first = terminal.first().copied().unwrap_or(0.0);
+            let second = terminal.get(1).copied().unwrap_or(0.0);
+            total += match &option.option_type {
+                OptionType::Rainbow { rainbow_type, .. } => {
+                    let basket = terminal.iter().take(required).copied();
+                    let underlying = match rainbow_type {
+                        RainbowType::BestOf => basket.fold(f64::MIN, f64::max),
+                        RainbowType::WorstOf => basket.fold(f64::MAX, f64::min),
+                        _ => {
+                            return Err(PricingError::unsupported_option_type(
+                                &option.option_type.to_string(),
+                                "multi-asset Monte Carlo",
+                            ));
+                        }
+                    };
+                    call_put(underlying)
+                }
+                OptionType::Spread { .. } => call_put(first - second),
+                OptionType::Exchange { .. } => match option.option_style {
+                    OptionStyle::Call => (first - second).max(0.0),
+                    OptionStyle::Put => (second - first).max(0.0),
+                },
+                other => {
+                    let spots: Vec<f64> = path
+                        .asset(0)
+                        .unwrap_or_default()
+                        .iter()
+                        .map(Positive::to_f64)
+                        .collect();
+                    other.payoff(&PayoffInfo {
+                        spot: Positive::new(first).unwrap_or(Positive::ZERO),
+                        strike: option.strike_price,
+                        style: option.option_style,
+                        side: Side::Long,
+                        spot_min: spots.iter().copied().reduce(f64::min),
+                        spot_max: spots.iter().copied().reduce(f64::max),
+                        spot_prices: Some(spots),
+                    })
+                }
+            };
+        }
+
+        let t = option.years_to_expiry()?.to_f64();
+        let rate = option.term_risk_free_rate()?.to_f64().unwrap_or(0.0);
+        let sign = match option.side {
+            Side::Long => 1.0,
+            Side::Short => -1.0,
+        };
+        let price = sign * (-rate * t).exp() * total / self.paths.len() as f64;
+        finite_decimal(price)
+            .ok_or_else(|| PricingError::non_finite("simulation::multi_asset::price", price))
+    }
+}
+
+/// Time step of a walk, `None` for walks driven by historical prices.
+fn walk_dt(walk_type: &WalkType) -> Option<Positive> {
+    match walk_type {
+        WalkType::Brownian { dt, .. }
+        | WalkType::GeometricBrownian { dt, .. }
+        | WalkType::LogReturns { dt, .. }
+        | WalkType::MeanReverting { dt, .. }
+        | WalkType::JumpDiffusion { dt, .. }
+        | WalkType::Garch { dt, .. }
+        | WalkType::Heston { dt, .. }
+        | WalkType::Custom { dt, .. }
+        | WalkType::Telegraph { dt, .. }
+        | WalkType::RegimeSwitching { dt, .. } => Some(*dt),
+        WalkType::Historical { .. } | WalkType::BlockBootstrap { .. } => None,
+    }
+}
+
+fn simulate_path(
+    assets: &[AssetWalk],
+    correlation: &CorrelationMatrix,
+    steps: usize,
+    rng: &mut StdRng,
+) -> Result<MultiAssetPath, SimulationError> {
+    let mut steppers = assets
+        .iter()
+        .map(|asset| Stepper::new(asset, rng))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut prices: Vec<Vec<Positive>> = assets
+        .iter()
+        .map(|asset| {
+            let mut row = Vec::with_capacity(steps + 1);
+            row.push(asset.initial_price);
+            row
+        })
+        .collect();
+    let mut independent = vec![0.0; assets.len()];
+    for _ in 0..steps {
+        for z in independent.iter_mut() {
+            *z = StandardNormal.sample(rng);
+        }
+        let shocks = correlation.correlate(&independent);
+        for ((stepper, row), shock) in steppers.iter_mut().zip(prices.iter_mut()).zip(shocks) {
+            let price = stepper.advance(shock, rng).max(0.0);
+            stepper.price = price;
+            row.push(Positive::new(price).unwrap_or(Positive::ZERO));
+        }
+    }
+    Ok(MultiAssetPath { prices })
+}
+
+/// Single-step form of the built-in walk kernels, driven by an external
+/// price shock.
+struct Stepper {
+    price: f64,
+    dt: f64,
+    sqrt_dt: f64,
+    dynamics: Dynamics,
+}
+
+enum Dynamics {
+    Brownian {
+        drift: f64,
+        sigma_abs: f64,
+    },
+    GeometricBrownian {
+        drift: f64,
+        volatility: f64,
+    },
+    LogReturns {
+        drift: f64,
+        volatility: f64,
+        autocorrelation: f64,
+        previous: f64,
+    },
+    MeanReverting {
+        speed: f64,
+        mean: f64,
+        sigma_abs: f64,
+    },
+    JumpDiffusion {
+        drift: f64,
+        volatility: f64,
+        intensity: f64,
+        jump_mean: f64,
+        jump_volatility: f64,
+    },
+    Garch {
+        drift: f64,
+        omega: f64,
+        alpha: f64,
+        beta: f64,
+        variance: f64,
+        previous_shock: f64,
+    },
+    Heston {
+        drift: f64,
+        variance: f64,
+        kappa: f64,
+        theta: f64,
+        xi: f64,
+        rho: f64,
+    },
+    Custom {
+        drift: f64,
+        volatility: f64,
+        vov: f64,
+        speed: f64,
+        mean: f64,
+    },
+    Telegraph {
+        drift: f64,
+        volatility: f64,
+        lambda_up: f64,
+        lambda_down: f64,
+        multiplier_up: f64,
+        multiplier_down: f64,
+        up: bool,
+    },
+    RegimeSwitching {
+        /// `(drift, volatility)` of each regime.
+        regimes: Vec<(f64, f64)>,
+        transitions: Vec<Vec<f64>>,
+        regime: usize,
+    },
+}
+
+fn f(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+impl Stepper {
+    fn new(asset: &AssetWalk, rng: &mut StdRng) -> Result<Self, SimulationError> {
+        let price = asset.initial_price.to_f64();
+        let dt = walk_dt(&asset.walk_type).ok_or_else(|| {
+            SimulationError::invalid_parameters(
+                "historical and bootstrap walks follow observed prices and cannot be correlated",
+            )
+        })?;
+        let dynamics = match &asset.walk_type {
+            WalkType::Brownian {
+                drift, volatility, ..
+            } => Dynamics::Brownian {
+                drift: f(*drift),
+                sigma_abs: volatility.to_f64() * price,
+            },
+            WalkType::GeometricBrownian {
+                drift, volatility, ..
+            } => Dynamics::GeometricBrownian {
+                drift: f(*drift),
+                volatility: volatility.to_f64(),
+            },
+            WalkType::LogReturns {
+                expected_return,
+                volatility,
+                autocorrelation,
+                ..
+            } => {
+                let autocorrelation = autocorrelation.unwrap_or(Decimal::ZERO);
+                if !(-Decimal::ONE..=Decimal::ONE).contains(&autocorrelation) {
+                    return Err(SimulationError::InvalidAutocorrelation {
+                        value: autocorrelation,
+                    });
+                }
+                Dynamics::LogReturns {
+                    drift: f(*expected_return),
+                    volatility: volatility.to_f64(),
+                    autocorrelation: f(autocorrelation),
+                    previous: 0.0,
+                }
+            }
+            WalkType::MeanReverting {
+                volatility,
+                speed,
+                mean,
+                ..
+            } => Dynamics::MeanReverting {
+                speed: speed.to_f64(),
+                mean: mean.to_f64(),
+                sigma_abs: volatility.to_f64() * mean.to_f64(),
+            },
+            WalkType::JumpDiffusion {
+                drift,
+                volatility,
+                intensity,
+                jump_mean,
+                jump_volatility,
+                ..
+            } => Dynamics::JumpDiffusion {
+                drift: f(*drift),
+                volatility: volatility.to_f64(),
+                intensity: intensity.to_f64(),
+                jump_mean: f(*jump_mean),
+                jump_volatility: jump_volatility.to_f64(),
+            },
+            WalkType::Garch {
+                drift,
+                volatility,
+                alpha,
+                beta,
+                ..
+            } => {
+                if *alpha + *beta >= Positive::ONE {
+                    return Err(SimulationError::GarchStationarity {
+                        alpha: *alpha,
+                        beta: *beta,
+                    });
+                }
+                let variance = volatility.to_f64() * volatility.to_f64();
+                Dynamics::Garch {
+                    drift: f(*drift),
+                    omega: variance * (1.0 - alpha.to_f64() - beta.to_f64()),
+                    alpha: alpha.to_f64(),
+                    beta: beta.to_f64(),
+                    variance,
+                    previous_shock: 0.0,
+                }
+            }
+            WalkType::Heston {
+                drift,
+                volatility,
+                kappa,
+                theta,
+                xi,
+                rho,
+                ..
+            } => {
+                if !(-Decimal::ONE..=Decimal::ONE).contains(rho) {
+                    return Err(SimulationError::InvalidCorrelation { rho: *rho });
+                }
+                Dynamics::Heston {
+                    drift: f(*drift),
+                    variance: volatility.to_f64() * volatility.to_f64(),
+                    kappa: kappa.to_f64(),
+                    theta: theta.to_f64(),
+                    xi: xi.to_f64(),
+                    rho: f(*rho),
+                }
+            }
+            WalkType::Custom {
+                drift,
+                volatility,
+                vov,
+                vol_speed,
+                vol_mean,
+                ..
+            } => Dynamics::Custom {
+                drift: f(*drift),
+                volatility: volatility.to_f64(),
+                vov: vov.to_f64(),
+                speed: vol_speed.to_f64(),
+                mean: vol_mean.to_f64(),
+            },
+            WalkType::Telegraph {
+                drift,
+                volatility,
+                lambda_up,
+                lambda_down,
+                vol_multiplier_up,
+                vol_multiplier_down,
+                ..
+            } => Dynamics::Telegraph {
+                drift: f(*drift),
+                volatility: volatility.to_f64(),
+                lambda_up: lambda_up.to_f64(),
+                lambda_down: lambda_down.to_f64(),
+                multiplier_up: vol_multiplier_up.map_or(1.0, |m| m.to_f64()),
+                multiplier_down: vol_multiplier_down.map_or(1.0, |m| m.to_f64()),
+                up: rng.random::<f64>() < 0.5,
+            },
+            WalkType::RegimeSwitching {
+                regimes,
+                transition_matrix,
+                initial_regime,
+                ..
+            } => {
+                validate_regime_switching(regimes, transition_matrix, *initial_regime)?;
+                Dynamics::RegimeSwitching {
+                    regimes: regimes
+                        .iter()
+                        .map(|regime| (f(regime.drift), regime.volatility.to_f64()))
+                        .collect(),
+                    transitions: transition_matrix
+                        .iter()
+                        .map(|row| row.iter().copied().map(f).collect())
+                        .collect(),
+                    regime: *initial_regime,
+                }
+            }
+            WalkType::Historical { .. } | WalkType::BlockBootstrap { .. } => {
+                unreachable!("rejected by walk_dt")
+            }
+        };
+        Ok(Self {
+            price,
+            dt: dt.to_f64(),
+            sqrt_dt: dt.to_f64().sqrt(),
+            dynamics,
+        })
+    }
+
+    /// Next price given the correlated price shock `z`.
+    fn advance(&mut self, z: f64, rng: &mut StdRng) -> f64 {
+        let (x, dt, sqrt_dt) = (self.price, self.dt, self.sqrt_dt);
+        match &mut self.dynamics {
+            Dynamics::Brownian { drift, sigma_abs } => x + *drift * dt + *sigma_abs * sqrt_dt * z,
+            Dynamics::GeometricBrownian { drift, volatility } => {
+                x * (*drift * dt + *volatility * sqrt_dt * z).exp()
+            }
+            Dynamics::LogReturns {
+                drift,
+                volatility,
+                autocorrelation,
+                previous,
+            } => {
+                let log_return =
+                    *drift * dt + *volatility * sqrt_dt * z + *autocorrelation * *previous;
+                *previous = log_return;
+                x * log_return.exp()
+            }
+            Dynamics::MeanReverting {
+                speed,
+                mean,
+                sigma_abs,
+            } => x + *speed * (*mean - x) * dt + *sigma_abs * sqrt_dt * z,
+            Dynamics::JumpDiffusion {
+                drift,
+                volatility,
+                intensity,
+                jump_mean,
+                jump_volatility,
+            } => {
+                let jump = if rng.random::<f64>() < 1.0 - (-*intensity * dt).exp() {
+                    let size: f64 = StandardNormal.sample(rng);
+                    *jump_mean + *jump_volatility * size
+                } else {
+                    0.0
+                };
+                x + *drift * dt + *volatility * x * sqrt_dt * z + jump
+            }
+            Dynamics::Garch {
+                drift,
+                omega,
+                alpha,
+                beta,
+                variance,
+                previous_shock,
+            } => {
+                *variance = *omega + *alpha * *previous_shock * *previous_shock + *beta * *variance;
+                let shock = z * variance.sqrt() * sqrt_dt;
+                *previous_shock = shock;
+                x * (*drift * dt + shock).exp()
+            }
+            Dynamics::Heston {
+                drift,
+                variance,
+                kappa,
+                theta,
+                xi,
+                rho,
+            } => {
+                let independent: f64 = StandardNormal.sample(rng);
+                let variance_shock = *rho * z + (1.0 - *rho * *rho).sqrt() * independent;
+                let next = (*variance
+                    + *kappa * (*theta - *variance) * dt
+                    + *xi * variance.sqrt() * variance_shock * sqrt_dt)
+                    .max(0.0);
+                let average = 0.5 * (*variance + next);
+                *variance = next;
+                x * (*drift * dt + average.sqrt() * z * sqrt_dt).exp()
+            }
+            Dynamics::Custom {
+                drift,
+                volatility,
+                vov,
+                speed,
+                mean,
+            } => {
+                let price = x + *drift * dt + z * *volatility * x * sqrt_dt;
+                let vol_shock: f64 = StandardNormal.sample(rng);
+                *volatility = (*volatility
+                    + *speed * (*mean - *volatility) * dt
+                    + *vov * sqrt_dt * vol_shock)
+                    .max(0.0);
+                price
+            }
+            Dynamics::Telegraph {
+                drift,
+                volatility,
+                lambda_up,
+                lambda_down,
+                multiplier_up,
+                multiplier_down,
+                up,
+            } => {
+                let lambda = if *up { *lambda_down } else { *lambda_up };
+                if rng.random::<f64>() < 1.0 - (-lambda * dt).exp() {
+                    *up = !*up;
+                }
+                let multiplier = if *up {
+                    *multiplier_up
+                } else {
+                    *multiplier_down
+                };
+                x * (*drift * dt + *volatility * multiplier * sqrt_dt * z).exp()
+            }
+            Dynamics::RegimeSwitching {
+                regimes,
+                transitions,
+                regime,
+            } => {
+                if let Some(row) = transitions.get(*regime) {
+                    *regime = next_regime(row, rng.random::<f64>());
+                }
+                let (drift, volatility) = regimes.get(*regime).copied().unwrap_or((0.0, 0.0));
+                x * (drift * dt + volatility * sqrt_dt * z).exp()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::option::ExoticParams;
+    use crate::pricing::exchange_black_scholes;
+    use crate::{ExpirationDate, OptionStyle};
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn two_asset_option(option_type: OptionType, exotic_params: ExoticParams) -> Options {
+        Options {
+            option_type,
+            side: Side::Long,
+            underlying_symbol: "AAA".to_string(),
+            strike_price: Positive::HUNDRED,
+            expiration_date: ExpirationDate::Days(pos_or_panic!(182.5)),
+            implied_volatility: pos_or_panic!(0.25),
+            quantity: Positive::ONE,
+            underlying_price: Positive::HUNDRED,
+            risk_free_rate: dec!(0.04),
+            option_style: OptionStyle::Call,
+            dividend_yield: Positive::ZERO,
+            exotic_params: Some(exotic_params),
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
+        }
+    }
+
+    fn assert_close(simulated: Decimal, closed_form: Decimal, tolerance: Decimal) {
+        assert!(
+            (simulated - closed_form).abs() <= tolerance,
+            "simulated {simulated} vs closed form {closed_form}"
+        );
+    }
+
+    #[test]
+    fn test_correlation_matrix_validation() {
+        assert!(CorrelationMatrix::new(vec![]).is_err());
+        assert!(CorrelationMatrix::new(vec![vec![Decimal::ONE, dec!(0.2)]]).is_err());
+        assert!(matches!(
+            CorrelationMatrix::two_assets(dec!(1.2)),
+            Err(SimulationError::InvalidCorrelation { .. })
+        ));
+        assert!(CorrelationMatrix::two_assets(Decimal::ONE).is_err());
+        assert!(
+            CorrelationMatrix::new(vec![
+                vec![Decimal::ONE, dec!(0.3)],
+                vec![dec!(0.4), Decimal::ONE],
+            ])
+            .is_err()
+        );
+        assert!(
+            CorrelationMatrix::new(vec![
+                vec![dec!(0.9), dec!(0.3)],
+                vec![dec!(0.3), Decimal::ONE],
+            ])
+            .is_err()
+        );
+        let not_positive_definite = CorrelationMatrix::new(vec![
+            vec![Decimal::ONE, dec!(0.9), dec!(0.9)],
+            vec![dec!(0.9), Decimal::ONE, dec!(-0.9)],
+            vec![dec!(0.9), dec!(-0.9), Decimal::ONE],
+        ]);
+        assert!(matches!(
+            not_positive_definite,
+            Err(SimulationError::InvalidParameters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cholesky_factor_reproduces_matrix() {
+        let matrix = CorrelationMatrix::new(vec![
+            vec![Decimal::ONE, dec!(0.5), dec!(-0.2)],
+            vec![dec!(0.5), Decimal::ONE, dec!(0.3)],
+            vec![dec!(-0.2), dec!(0.3), Decimal::ONE],
+        ])
+        .unwrap();
+        let lower = matrix.cholesky_factor();
+        for (i, row_i) in lower.iter().enumerate() {
+            for (j, row_j) in lower.iter().enumerate() {
+                let product: f64 = row_i.iter().zip(row_j).map(|(a, b)| a * b).sum();
+                let expected = matrix.get(i, j).unwrap().to_f64().unwrap();
+                assert!((product - expected).abs() < 1e-12);
+            }
+        }
+        assert_eq!(
+            CorrelationMatrix::identity(3).correlate(&[1.0, 2.0, 3.0]),
+            vec![1.0, 2.0, 3.0]
+        );
+    }
+
+    #[test]
+    fn test_paths_are_aligned_and_correlated() {
+        let dt = pos_or_panic!(1.0 / 252.0);
+        let assets = vec![
+            AssetWalk::new(
+                "AAA",
+                Positive::HUNDRED,
+                WalkType::GeometricBrownian {
+                    dt,
+                    drift: Decimal::ZERO,
+                    volatility: pos_or_panic!(0.2),
+                },
+            ),
+            AssetWalk::new(
+                "BBB",
+                pos_or_panic!(50.0),
+                WalkType::Heston {
+                    dt,
+                    drift: Decimal::ZERO,
+                    volatility: pos_or_panic!(0.3),
+                    kappa: Positive::TWO,
+                    theta: pos_or_panic!(0.09),
+                    xi: pos_or_panic!(0.2),
+                    rho: dec!(-0.5),
+                },
+            ),
+        ];
+        let simulator = MultiAssetSimulator::new(
+            assets,
+            CorrelationMatrix::two_assets(dec!(0.7)).unwrap(),
+            400,
+            50,
+            42,
+        )
+        .unwrap();
+        assert_eq!(simulator.len(), 400);
+        let path = simulator.get_path(0).unwrap();
+        assert_eq!(path.steps(), 50);
+        assert_eq!(path.asset(1).unwrap()[0], pos_or_panic!(50.0));
+        let realized = simulator.realized_correlation(0, 1).unwrap();
+        assert!((realized - dec!(0.7)).abs() < dec!(0.03), "{realized}");
+
+        let scenarios = simulator.scenarios(50);
+        assert_eq!(scenarios.len(), 400);
+        assert!(scenarios[0].contains_key("AAA") && scenarios[0].contains_key("BBB"));
+        assert!(simulator.scenarios(51).is_empty());
+    }
+
+    #[test]
+    fn test_simulation_is_reproducible() {
+        let walk = WalkType::JumpDiffusion {
+            dt: pos_or_panic!(0.01),
+            drift: Decimal::ZERO,
+            volatility: pos_or_panic!(0.2),
+            intensity: Positive::TWO,
+            jump_mean: dec!(-1.0),
+            jump_volatility: Positive::ONE,
+        };
+        let build = |seed| {
+            MultiAssetSimulator::new(
+                vec![AssetWalk::new("AAA", Positive::HUNDRED, walk.clone())],
+                CorrelationMatrix::identity(1),
+                8,
+                20,
+                seed,
+            )
+            .unwrap()
+        };
+        assert_eq!(build(1).get_paths(), build(1).get_paths());
+        assert_ne!(build(1).get_paths(), build(2).get_paths());
+    }
+
+    #[test]
+    fn test_invalid_simulations_are_rejected() {
+        let gbm = |dt: f64| WalkType::GeometricBrownian {
+            dt: pos_or_panic!(dt),
+            drift: Decimal::ZERO,
+            volatility: pos_or_panic!(0.2),
+        };
+        let correlation = CorrelationMatrix::identity(2);
+        let mismatched_dt = vec![
+            AssetWalk::new("AAA", Positive::HUNDRED, gbm(0.01)),
+            AssetWalk::new("BBB", Positive::HUNDRED, gbm(0.02)),
+        ];
+        assert!(MultiAssetSimulator::new(mismatched_dt, correlation.clone(), 10, 10, 1).is_err());
+
+        let single = vec![AssetWalk::new("AAA", Positive::HUNDRED, gbm(0.01))];
+        assert!(MultiAssetSimulator::new(single, correlation.clone(), 10, 10, 1).is_err());
+
+        let historical = vec![
+            AssetWalk::new("AAA", Positive::HUNDRED, gbm(0.01)),
+            AssetWalk::new(
+                "BBB",
+                Positive::HUNDRED,
+                WalkType::Historical {
+                    timeframe: crate::utils::TimeFrame::Day,
+                    prices: vec![Positive::HUNDRED; 20],
+                    symbol: None,
+                },
+            ),
+        ];
+        assert!(MultiAssetSimulator::new(historical, correlation.clone(), 10, 10, 1).is_err());
+
+        let bootstrap = vec![
+            AssetWalk::new("AAA", Positive::HUNDRED, gbm(0.01)),
+            AssetWalk::new(
+                "BBB",
+                Positive::HUNDRED,
+                WalkType::BlockBootstrap {
+                    timeframe: crate::utils::TimeFrame::Day,
+                    prices: vec![Positive::HUNDRED; 20],
+                    block_length: 5,
+                    method: crate::simulation::BootstrapMethod::Circular,
+                },
+            ),
+        ];
+        assert!(MultiAssetSimulator::new(bootstrap, correlation, 10, 10, 1).is_err());
+    }
+
+    #[test]
+    fn test_regime_switching_asset_follows_its_chain() {
+        let regime = |drift| crate::simulation::Regime::new(drift, Positive::ZERO);
+        let dt = pos_or_panic!(0.01);
+        let switching = WalkType::RegimeSwitching {
+            dt,
+            regimes: vec![regime(Decimal::ONE), regime(-Decimal::ONE)],
+            transition_matrix: vec![
+                vec![Decimal::ZERO, Decimal::ONE],
+                vec![Decimal::ONE, Decimal::ZERO],
+            ],
+            initial_regime: 0,
+        };
+        let simulator = MultiAssetSimulator::new(
+            vec![
+                AssetWalk::new("AAA", Positive::HUNDRED, switching),
+                AssetWalk::new(
+                    "BBB",
+                    Positive::HUNDRED,
+                    WalkType::GeometricBrownian {
+                        dt,
+                        drift: Decimal::ZERO,
+                        volatility: pos_or_panic!(0.2),
+                    },
+                ),
+            ],
+            CorrelationMatrix::two_assets(dec!(0.5)).unwrap(),
+            3,
+            4,
+            5,
+        )
+        .unwrap();
+        // Volatility-free regimes alternating between drifts -1 and +1.
+        let down = 100.0 * (-0.01f64).exp();
+        for path in simulator.get_paths() {
+            let prices: Vec<f64> = path.asset(0).unwrap().iter().map(|p| p.to_f64()).collect();
+            for (price, expected) in prices.iter().zip([100.0, down, 100.0, down, 100.0]) {
+                assert!((price - expected).abs() < 1e-9, "{prices:?}");
+            }
+        }
+
+        let invalid = WalkType::RegimeSwitching {
+            dt,
+            regimes: vec![regime(Decimal::ONE)],
+            transition_matrix: vec![vec![Decimal::ONE]],
+            initial_regime: 0,
+        };
+        assert!(
+            MultiAssetSimulator::new(
+                vec![AssetWalk::new("AAA", Positive::HUNDRED, invalid)],
+                CorrelationMatrix::identity(1),
+                1,
+                1,
+                1,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rainbow_simulation_satisfies_best_worst_parity() {
+        let params = ExoticParams {
+            rainbow_second_asset_price: Some(pos_or_panic!(95.0)),
+            rainbow_second_asset_volatility: Some(pos_or_panic!(0.3)),
+            rainbow_correlation: Some(dec!(0.4)),
+            ..Default::default()
+        };
+        let rainbow = |rainbow_type| {
+            let option = two_asset_option(
+                OptionType::Rainbow {
+                    num_assets: 2,
+                    rainbow_type,
+                },
+                params.clone(),
+            );
+            let simulator = MultiAssetSimulator::for_option(&option, 40_000, 1, 7).unwrap();
+            simulator.get_mc_option_price(&option).unwrap()
+        };
+        let best = rainbow(RainbowType::BestOf);
+        let worst = rainbow(RainbowType::WorstOf);
+        assert!(best > worst && worst > Decimal::ZERO);
+
+        // max(S1, S2) + min(S1, S2) = S1 + S2, so the two calls sum to two vanillas.
+        let mut first = two_asset_option(OptionType::European, ExoticParams::default());
+        first.exotic_params = None;
+        let mut second = first.clone();
+        second.underlying_price = pos_or_panic!(95.0);
+        second.implied_volatility = pos_or_panic!(0.3);
+        let vanillas = crate::pricing::black_scholes(&first).unwrap()
+            + crate::pricing::black_scholes(&second).unwrap();
+        assert_close(best + worst, vanillas, dec!(0.3));
+    }
+
+    #[test]
+    fn test_exchange_and_spread_simulation_match_closed_forms() {
+        let exchange = two_asset_option(
+            OptionType::Exchange {
+                second_asset: pos_or_panic!(95.0),
+            },
+            ExoticParams {
+                exchange_second_asset_volatility: Some(pos_or_panic!(0.3)),
+                exchange_correlation: Some(dec!(0.5)),
+                ..Default::default()
+            },
+        );
+        let simulator = MultiAssetSimulator::for_option(&exchange, 40_000, 1, 11).unwrap();
+        let margrabe = exchange_black_scholes(&exchange).unwrap();
+        assert_close(
+            simulator.get_mc_option_price(&exchange).unwrap(),
+            margrabe,
+            dec!(0.2),
+        );
+
+        let spread_params = ExoticParams {
+            spread_second_asset_volatility: Some(pos_or_panic!(0.3)),
+            spread_correlation: Some(dec!(0.5)),
+            ..Default::default()
+        };
+        let spread_type = OptionType::Spread {
+            second_asset: pos_or_panic!(95.0),
+        };
+        // A zero-strike spread call is an exchange option.
+        let mut zero_strike = two_asset_option(spread_type.clone(), spread_params.clone());
+        zero_strike.strike_price = Positive::ZERO;
+        let simulator = MultiAssetSimulator::for_option(&zero_strike, 40_000, 1, 13).unwrap();
+        assert_close(
+            simulator.get_mc_option_price(&zero_strike).unwrap(),
+            margrabe,
+            dec!(0.2),
+        );
+
+        let mut spread = two_asset_option(spread_type, spread_params);
+        spread.strike_price = pos_or_panic!(5.0);
+        spread.side = Side::Short;
+        let simulator = MultiAssetSimulator::for_option(&spread, 40_000, 1, 13).unwrap();
+        let simulated = simulator.get_mc_option_price(&spread).unwrap();
+        assert!(simulated < Decimal::ZERO && -simulated < margrabe);
+        // Reference value from a one-million-path simulation of the same spread.
+        assert_close(simulated, dec!(-7.63), dec!(0.25));
+    }
+
+    #[test]
+    fn test_single_asset_option_uses_first_path() {
+        let mut option = two_asset_option(OptionType::European, ExoticParams::default());
+        option.exotic_params = None;
+        let simulator = MultiAssetSimulator::for_option(&option, 40_000, 1, 17).unwrap();
+        assert_eq!(simulator.assets().len(), 1);
+        let simulated = simulator.get_mc_option_price(&option).unwrap();
+        let closed_form = crate::pricing::black_scholes(&option).unwrap();
+        assert_close(simulated, closed_form, dec!(0.2));
+
+        let spread = two_asset_option(
+            OptionType::Spread {
+                second_asset: Positive::HUNDRED,
+            },
+            ExoticParams::default(),
+        );
+        assert!(simulator.get_mc_option_price(&spread).is_err());
+        assert!(MultiAssetSimulator::for_option(&spread, 10, 1, 1).is_err());
+    }
+}
@@ -7,12 +7,16 @@
 use crate::backtesting::results::SimulationStatsResult;
 use crate::error::SimulationError;
 use crate::model::decimal::{decimal_normal_sample, finite_decimal};
-use crate::simulation::model::WalkPath;
+use crate::simulation::model::{
+    BootstrapMethod, Regime, WalkPath, next_regime, validate_regime_switching,
+};
 use crate::simulation::simulator::Simulator;
 use crate::simulation::{ExitPolicy, WalkParams, WalkType};
+use crate::utils::others::calculate_log_returns;
 use crate::volatility::generate_ou_process;
 use num_traits::ToPrimitive;
 use positive::Positive;
+use rand::{Rng, RngExt};
 use rust_decimal::{Decimal, MathematicalOps};
 use std::convert::TryInto;
 use std::fmt::{Debug, Display};
@@ -334,6 +338,104 @@ where
     }
 }
 
+/// Built-in Markov regime-switching walk kernel: simulates the price path
+/// together with the volatility of the regime active at each step.
+///
+/// This is the shared implementation behind the default
+/// [`WalkTypeAble::regime_switching`] and
+/// [`WalkTypeAble::regime_switching_with_vol`] methods, exposed publicly so
+/// custom walkers overriding one of them can compose or wrap the built-in
+/// dynamics instead of reimplementing them.
+///
+/// # Errors
+///
+/// Same as [`WalkTypeAble::regime_switching`].
+pub fn regime_switching_walk<X, Y>(params: &WalkParams<X, Y>) -> Result<WalkPath, SimulationError>
+where
+    X: Copy + TryInto<Positive> + AddAssign + Display,
+    Y: TryInto<Positive> + Display + Clone,
+{
+    match &params.walk_type {
+        WalkType::RegimeSwitching {
+            dt,
+            regimes,
+            transition_matrix,
+            initial_regime,
+        } => {
+            validate_regime_switching(regimes, transition_matrix, *initial_regime)?;
+            let transitions: Vec<Vec<f64>> = transition_matrix
+                .iter()
+                .map(|row| row.iter().map(|p| p.to_f64().unwrap_or(0.0)).collect())
+                .collect();
+
+            let mut values = Vec::with_capacity(params.size);
+            let mut vols = Vec::with_capacity(params.size);
+            let mut price = params.ystep_as_positive()?.to_dec();
+            let mut regime = *initial_regime;
+            values.push(Positive::new_decimal(price).unwrap_or(Positive::ZERO));
+            vols.push(regimes[regime].volatility);
+
+            let mut rng = rand::rng();
+            let sqrt_dt = dt.sqrt().to_dec();
+            for _ in 1..params.size {
+                // Move the Markov chain, then diffuse with the new regime
+                regime = next_regime(&transitions[regime], rng.random::<f64>());
+                let Regime { drift, volatility } = regimes[regime];
+
+                let z = decimal_normal_sample();
+                let price_change = drift * dt.to_dec() + volatility.to_dec() * sqrt_dt * z;
+                price *= price_change.exp();
+
+                values.push(Positive::new_decimal(price).unwrap_or(Positive::ZERO));
+                // Volatility of the regime that generated this step.
+                vols.push(volatility);
+            }
+
+            Ok(WalkPath {
+                prices: values,
+                vols: Some(vols),
+            })
+        }
+        _ => Err(SimulationError::InvalidWalkType {
+            expected: "RegimeSwitching",
+        }),
+    }
+}
+
+/// Draws `count` log returns from `returns` by block bootstrap.
+///
+/// Circular blocks have exactly `block_length` returns; stationary blocks
+/// end after each return with probability `1 / block_length`. Both wrap
+/// around the end of the history. `returns` must not be empty and
+/// `block_length` must be positive.
+fn bootstrap_returns<R: Rng + ?Sized>(
+    returns: &[Decimal],
+    block_length: usize,
+    method: BootstrapMethod,
+    count: usize,
+    rng: &mut R,
+) -> Vec<Decimal> {
+    let n = returns.len();
+    let restart_probability = 1.0 / block_length as f64;
+    let mut sampled = Vec::with_capacity(count);
+    let mut index = rng.random_range(0..n);
+    for drawn in 0..count {
+        if drawn > 0 {
+            let new_block = match method {
+                BootstrapMethod::Circular => drawn % block_length == 0,
+                BootstrapMethod::Stationary => rng.random::<f64>() < restart_probability,
+            };
+            index = if new_block {
+                rng.random_range(0..n)
+            } else {
+                (index + 1) % n
+            };
+        }
+        sampled.push(returns[index]);
+    }
+    sampled
+}
+
 /// Object-safe helper trait that exposes a `Clone`-compatible operation for
 /// [`WalkTypeAble`] trait objects.
 ///
@@ -395,6 +497,8 @@ where
 /// - GARCH (Generalized Autoregressive Conditional Heteroskedasticity)
 /// - Heston stochastic volatility model
 /// - Custom stochastic process with mean-reverting volatility
+/// - Telegraph and Markov regime-switching processes
+/// - Historical replay and block bootstrap of historical returns
 ///
 /// # Object safety and cloning
 ///
@@ -453,7 +557,9 @@ where
             WalkType::Heston { .. } => self.heston(params),
             WalkType::Custom { .. } => self.custom(params),
             WalkType::Telegraph { .. } => self.telegraph(params),
+            WalkType::RegimeSwitching { .. } => self.regime_switching(params),
             WalkType::Historical { .. } => self.historical(params),
+            WalkType::BlockBootstrap { .. } => self.block_bootstrap(params),
         }
     }
 
@@ -461,19 +567,21 @@ where
     /// volatility path for walk types whose volatility varies over time.
     ///
     /// For the stochastic-volatility variants (`Garch`, `Heston`, `Custom`,
-    /// `Telegraph`) the returned [`WalkPath::vols`] carries the ANNUALIZED
-    /// volatility prevailing at each step, aligned index-by-index with
-    /// [`WalkPath::prices`]. For every other variant `vols` is `None` — the
-    /// constant volatility is available via [`WalkType::volatility`], and
-    /// `Historical` per-step estimates are the caller's concern (see
+    /// `Telegraph`, `RegimeSwitching`) the returned [`WalkPath::vols`]
+    /// carries the ANNUALIZED volatility prevailing at each step, aligned
+    /// index-by-index with [`WalkPath::prices`]. For every other variant
+    /// `vols` is `None` — the constant volatility is available via
+    /// [`WalkType::volatility`], and `Historical` / `BlockBootstrap`
+    /// per-step estimates are the caller's concern (see
     /// `simulation::walk_steps`).
     ///
     /// # Note for implementors
     ///
-    /// The price-path methods (`garch`, `heston`, `custom`, `telegraph`)
-    /// and their `*_with_vol` siblings are BOTH backed by the same built-in
-    /// kernels ([`garch_walk`], [`heston_walk`], [`custom_walk`],
-    /// [`telegraph_walk`]) but are independent override points: overriding
+    /// The price-path methods (`garch`, `heston`, `custom`, `telegraph`,
+    /// `regime_switching`) and their `*_with_vol` siblings are BOTH backed by
+    /// the same built-in kernels ([`garch_walk`], [`heston_walk`],
+    /// [`custom_walk`], [`telegraph_walk`], [`regime_switching_walk`]) but
+    /// are independent override points: overriding
     /// one never changes the other's default. Consumers that need the vol
     /// path (the walk generators) call `generate_with_vol`, so a walker
     /// overriding a price-path method MUST override the matching
@@ -490,6 +598,7 @@ where
             WalkType::Heston { .. } => self.heston_with_vol(params),
             WalkType::Custom { .. } => self.custom_with_vol(params),
             WalkType::Telegraph { .. } => self.telegraph_with_vol(params),
+            WalkType::RegimeSwitching { .. } => self.regime_switching_with_vol(params),
             _ => Ok(WalkPath {
                 prices: self.generate(params)?,
                 vols: None,
@@ -1042,6 +1151,130 @@ where
             }),
         }
     }
+
+    /// Generates a Markov regime-switching process.
+    ///
+    /// At every step the active regime moves according to the transition
+    /// matrix, and the price then follows a geometric step with the drift and
+    /// volatility of the new regime. Unlike the two-state Telegraph process,
+    /// any number of regimes (two or more) is supported, each with its own
+    /// drift.
+    ///
+    /// # Parameters
+    ///
+    /// * `params` - Walk parameters including initial value, time step, the
+    ///   regimes, the per-step transition matrix and the initial regime.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Positive>, SimulationError>` - A vector of positive values representing
+    ///   the generated regime-switching path, or an error if parameters are invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidWalkType`] when
+    /// `params.walk_type` is not a [`WalkType::RegimeSwitching`] variant,
+    /// [`SimulationError::InvalidParameters`] when there are fewer than two
+    /// regimes, the transition matrix is not a square row-stochastic matrix
+    /// matching the regimes, or the initial regime is out of range, and
+    /// [`SimulationError::PositiveError`] when the initial `y` value violates
+    /// the `Positive` invariant.
+    fn regime_switching(
+        &self,
+        params: &WalkParams<X, Y>,
+    ) -> Result<Vec<Positive>, SimulationError> {
+        // Standalone: this method never routes through
+        // `regime_switching_with_vol`, so overriding either method cannot
+        // change the other's default.
+        Ok(regime_switching_walk(params)?.prices)
+    }
+
+    /// Markov regime-switching walk that also exposes the regime volatility
+    /// path.
+    ///
+    /// Same dynamics as [`WalkTypeAble::regime_switching`]; `vols[i]` is the
+    /// ANNUALIZED volatility of the regime that generated `prices[i]`
+    /// (`vols[0]` is the volatility of the initial regime).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`WalkTypeAble::regime_switching`].
+    fn regime_switching_with_vol(
+        &self,
+        params: &WalkParams<X, Y>,
+    ) -> Result<WalkPath, SimulationError> {
+        regime_switching_walk(params)
+    }
+
+    /// Generates a walk by block bootstrap of historical log returns.
+    ///
+    /// The walk starts from the initial step and applies log returns of the
+    /// historical `prices` resampled in blocks of consecutive observations,
+    /// either with fixed-length circular blocks or with the geometric block
+    /// lengths of the stationary bootstrap. Every call draws a new path, so
+    /// a simulator built on this walk type explores many reorderings of one
+    /// history instead of replaying it.
+    ///
+    /// # Parameters
+    ///
+    /// * `params` - Walk parameters including initial value, the historical
+    ///   prices, the block length and the bootstrap method.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Positive>, SimulationError>` - A vector of `params.size`
+    ///   positive values starting at the initial value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidWalkType`] when
+    /// `params.walk_type` is not a [`WalkType::BlockBootstrap`] variant,
+    /// [`SimulationError::InsufficientHistoricalData`] when fewer than two
+    /// prices are provided, [`SimulationError::InvalidParameters`] when
+    /// `block_length` is zero, and propagates the errors of computing the
+    /// log returns and of `params.ystep_as_positive()`.
+    fn block_bootstrap(&self, params: &WalkParams<X, Y>) -> Result<Vec<Positive>, SimulationError> {
+        match &params.walk_type {
+            WalkType::BlockBootstrap {
+                prices,
+                block_length,
+                method,
+                ..
+            } => {
+                if prices.len() < 2 {
+                    return Err(SimulationError::InsufficientHistoricalData {
+                        required: 2,
+                        found: prices.len(),
+                    });
+                }
+                if *block_length == 0 {
+                    return Err(SimulationError::invalid_parameters(
+                        "bootstrap block length must be positive",
+                    ));
+                }
+                let log_returns = calculate_log_returns(prices)?;
+                let sampled = bootstrap_returns(
+                    &log_returns,
+                    *block_length,
+                    *method,
+                    params.size.saturating_sub(1),
+                    &mut rand::rng(),
+                );
+
+                let mut current_value: Positive = params.ystep_as_positive()?;
+                let mut values = Vec::with_capacity(params.size);
+                values.push(current_value);
+                for log_return in sampled {
+                    current_value *= log_return.exp();
+                    values.push(current_value);
+                }
+                Ok(values)
+            }
+            _ => Err(SimulationError::InvalidWalkType {
+                expected: "BlockBootstrap",
+            }),
+        }
+    }
 }
 
 impl<X, Y> Debug for Box<dyn WalkTypeAble<X, Y>> {
@@ -1130,7 +1363,7 @@ mod tests_walk_type_able {
     use crate::simulation::params::WalkParams;
     use crate::simulation::steps::Step;
     use crate::simulation::traits::WalkTypeAble;
-    use crate::utils::TimeFrame;
+    use crate::utils::{TimeFrame, deterministic_rng};
     use positive::pos_or_panic;
     use rust_decimal::Decimal;
     use std::fmt::Display;
@@ -1372,6 +1605,213 @@ mod tests_walk_type_able {
         Ok(())
     }
 
+    fn alternating_regimes() -> WalkType {
+        WalkType::RegimeSwitching {
+            dt: pos_or_panic!(0.01),
+            regimes: vec![
+                Regime {
+                    drift: Decimal::ZERO,
+                    volatility: pos_or_panic!(0.1),
+                },
+                Regime {
+                    drift: Decimal::ZERO,
+                    volatility: pos_or_panic!(0.4),
+                },
+            ],
+            transition_matrix: vec![
+                vec![Decimal::ZERO, Decimal::ONE],
+                vec![Decimal::ONE, Decimal::ZERO],
+            ],
+            initial_regime: 0,
+        }
+    }
+
+    #[test]
+    fn test_regime_switching_walk() -> Result<(), SimulationError> {
+        let params = create_test_params(6, 10.0, 100.0, alternating_regimes());
+
+        let walker = TestWalker {};
+        let result = walker.regime_switching(&params)?;
+        assert_eq!(result.len(), 6);
+        assert_eq!(result[0], Positive::HUNDRED);
+
+        // A deterministic chain flips regime on every step.
+        let path = walker.generate_with_vol(&params)?;
+        let vols = path
+            .vols
+            .expect("regime switching exposes its volatility path");
+        let expected: Vec<Positive> = [0.1, 0.4, 0.1, 0.4, 0.1, 0.4]
+            .iter()
+            .map(|v| pos_or_panic!(*v))
+            .collect();
+        assert_eq!(vols, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_regime_switching_rejects_invalid_matrix() {
+        let WalkType::RegimeSwitching {
+            dt,
+            regimes,
+            initial_regime,
+            ..
+        } = alternating_regimes()
+        else {
+            unreachable!()
+        };
+        let params = create_test_params(
+            5,
+            10.0,
+            100.0,
+            WalkType::RegimeSwitching {
+                dt,
+                regimes,
+                transition_matrix: vec![
+                    vec![Decimal::new(5, 1), Decimal::new(4, 1)],
+                    vec![Decimal::ONE, Decimal::ZERO],
+                ],
+                initial_regime,
+            },
+        );
+        let walker = TestWalker {};
+        assert!(matches!(
+            walker.regime_switching(&params),
+            Err(SimulationError::InvalidParameters { .. })
+        ));
+        assert!(matches!(
+            walker.telegraph(&params),
+            Err(SimulationError::InvalidWalkType { .. })
+        ));
+    }
+
+    fn bootstrap_history() -> Vec<Positive> {
+        [100.0, 101.0, 103.0, 102.0, 106.0, 105.0]
+            .iter()
+            .map(|p| pos_or_panic!(*p))
+            .collect()
+    }
+
+    fn path_log_returns(path: &[Positive]) -> Vec<Decimal> {
+        path.windows(2)
+            .map(|pair| (pair[1].to_dec() / pair[0].to_dec()).ln())
+            .collect()
+    }
+
+    fn history_index(returns: &[Decimal], value: Decimal) -> usize {
+        returns
+            .iter()
+            .position(|r| (*r - value).abs() < Decimal::new(1, 9))
+            .expect("bootstrapped return must come from the history")
+    }
+
+    #[test]
+    fn test_block_bootstrap_circular_single_block_is_rotation() -> Result<(), SimulationError> {
+        let prices = bootstrap_history();
+        let history = calculate_log_returns(&prices)?;
+        let params = create_test_params(
+            6,
+            10.0,
+            50.0,
+            WalkType::BlockBootstrap {
+                timeframe: TimeFrame::Day,
+                prices,
+                block_length: 5,
+                method: BootstrapMethod::Circular,
+            },
+        );
+
+        let walker = TestWalker {};
+        let result = walker.block_bootstrap(&params)?;
+        assert_eq!(result.len(), 6);
+        assert_eq!(result[0], pos_or_panic!(50.0));
+
+        // One block covering the whole history: consecutive returns wrap around.
+        let sampled = path_log_returns(&result);
+        let start = history_index(&history, sampled[0]);
+        for (offset, value) in sampled.iter().enumerate() {
+            assert_eq!(history_index(&history, *value), (start + offset) % 5);
+        }
+        // Same multiset of moves as the history, so the same end-to-end return.
+        let growth = result[5].to_dec() / result[0].to_dec();
+        assert!((growth - Decimal::new(105, 2)).abs() < Decimal::new(1, 9));
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_bootstrap_stationary_resamples_history() -> Result<(), SimulationError> {
+        let prices = bootstrap_history();
+        let history = calculate_log_returns(&prices)?;
+        let params = create_test_params(
+            40,
+            10.0,
+            100.0,
+            WalkType::BlockBootstrap {
+                timeframe: TimeFrame::Day,
+                prices,
+                block_length: 3,
+                method: BootstrapMethod::Stationary,
+            },
+        );
+        let walker = TestWalker {};
+        let result = walker.generate(&params)?;
+        assert_eq!(result.len(), 40);
+        for value in path_log_returns(&result) {
+            history_index(&history, value);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_bootstrap_block_lengths() {
+        let returns: Vec<Decimal> = (0..50).map(Decimal::from).collect();
+        let breaks = |sampled: &[Decimal]| {
+            sampled
+                .windows(2)
+                .filter(|pair| pair[1] != (pair[0] + Decimal::ONE) % Decimal::from(50))
+                .count()
+        };
+
+        let mut rng = deterministic_rng(42);
+        let stationary =
+            bootstrap_returns(&returns, 4, BootstrapMethod::Stationary, 20_000, &mut rng);
+        let mean_block = 20_000.0 / (breaks(&stationary) + 1) as f64;
+        assert!((mean_block - 4.0).abs() < 0.25, "{mean_block}");
+
+        let circular = bootstrap_returns(&returns, 3, BootstrapMethod::Circular, 3_000, &mut rng);
+        for (block, chunk) in circular.chunks(3).enumerate() {
+            assert_eq!(breaks(chunk), 0, "block {block} is not contiguous");
+        }
+    }
+
+    #[test]
+    fn test_block_bootstrap_rejects_invalid_history() {
+        let walker = TestWalker {};
+        let bootstrap = |prices: Vec<Positive>, block_length| {
+            create_test_params(
+                5,
+                10.0,
+                100.0,
+                WalkType::BlockBootstrap {
+                    timeframe: TimeFrame::Day,
+                    prices,
+                    block_length,
+                    method: BootstrapMethod::Stationary,
+                },
+            )
+        };
+        assert!(matches!(
+            walker.block_bootstrap(&bootstrap(vec![Positive::HUNDRED], 2)),
+            Err(SimulationError::InsufficientHistoricalData {
+                required: 2,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            walker.block_bootstrap(&bootstrap(bootstrap_history(), 0)),
+            Err(SimulationError::InvalidParameters { .. })
+        ));
+    }
+
     #[test]
     fn test_with_different_types() -> Result<(), SimulationError> {
         #[derive(Debug, Copy, Clone, PartialEq)]
@@ -28,10 +28,11 @@ use tracing::debug;
 
 /// Annualized volatility driving the walk.
 ///
-/// For the nine synthetic walk types this is the variant's `volatility`
-/// parameter (see [`WalkType::volatility`]). For [`WalkType::Historical`] it
-/// is the constant volatility estimated from the log returns of the full
-/// price history, annualized from the history's timeframe.
+/// For the synthetic walk types this is the variant's `volatility`
+/// parameter (see [`WalkType::volatility`]). For [`WalkType::Historical`] and
+/// [`WalkType::BlockBootstrap`] it is the constant volatility estimated from
+/// the log returns of the full price history, annualized from the history's
+/// timeframe.
 ///
 /// # Errors
 ///
@@ -47,6 +48,9 @@ where
     match &walk_params.walk_type {
         WalkType::Historical {
             timeframe, prices, ..
+        }
+        | WalkType::BlockBootstrap {
+            timeframe, prices, ..
         } => {
             let log_returns: Vec<Decimal> = calculate_log_returns(prices)?;
             let constant_volatility = constant_volatility(&log_returns)?;
@@ -63,9 +67,9 @@ where
 /// `prices[..=i]`.
 ///
 /// This is the estimator [`walk_steps`] and [`walk_steps_par`] use for
-/// [`WalkType::Historical`], whose volatility is not a model parameter and has
-/// to be estimated from the series ([`WalkType::volatility`] returns `None` for
-/// it). It is public so that a consumer pricing a historical walk outside the
+/// [`WalkType::Historical`] and [`WalkType::BlockBootstrap`], whose volatility
+/// is not a model parameter and has to be estimated from the series
+/// ([`WalkType::volatility`] returns `None` for them). It is public so that a consumer pricing a historical walk outside the
 /// driver — generating the path with
 /// [`crate::simulation::WalkTypeAble::generate_with_vol`] and building its own
 /// chains, say — uses the same numbers rather than a second copy of the
@@ -255,13 +259,13 @@ where
     }
 
     // Per-step volatilities: from the walker when the model simulates a vol
-    // path (Garch/Heston/Custom/Telegraph); for Historical walks, an
-    // expanding-window estimate over the walked prices that uses no future
-    // data. Otherwise a single constant from the walk type.
+    // path (Garch/Heston/Custom/Telegraph/RegimeSwitching); for Historical
+    // and BlockBootstrap walks, an expanding-window estimate over the walked
+    // prices that uses no future data. Otherwise a single constant from the walk type.
     let step_vols: Option<Vec<Positive>> = match path.vols {
         Some(vols) => Some(vols),
         None => match &walk_params.walk_type {
-            WalkType::Historical { timeframe, .. } => {
+            WalkType::Historical { timeframe, .. } | WalkType::BlockBootstrap { timeframe, .. } => {
                 expanding_window_vols(&y_steps, *timeframe).map_err(E::from)?
             }
             _ => None,
@@ -363,7 +367,7 @@ where
     let step_vols: Option<Vec<Positive>> = match path.vols {
         Some(vols) => Some(vols),
         None => match &walk_params.walk_type {
-            WalkType::Historical { timeframe, .. } => {
+            WalkType::Historical { timeframe, .. } | WalkType::BlockBootstrap { timeframe, .. } => {
                 expanding_window_vols(&y_steps, *timeframe).map_err(E::from)?
             }
             _ => None,
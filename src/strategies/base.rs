@@ -177,6 +177,42 @@ pub enum StrategyType {
     PoorMansCoveredCall,
     /// Call Butterfly strategy.
     CallButterfly,
+    /// Long Calendar Spread strategy.
+    LongCalendarSpread,
+    /// Short Calendar Spread strategy.
+    ShortCalendarSpread,
+    /// Diagonal Spread strategy.
+    DiagonalSpread,
+    /// Double Calendar Spread strategy.
+    DoubleCalendarSpread,
+    /// Ratio Call Spread strategy.
+    RatioCallSpread,
+    /// Ratio Put Spread strategy.
+    RatioPutSpread,
+    /// Call Back Spread strategy.
+    CallBackSpread,
+    /// Put Back Spread strategy.
+    PutBackSpread,
+    /// Call Broken Wing Butterfly strategy.
+    CallBrokenWingButterfly,
+    /// Put Broken Wing Butterfly strategy.
+    PutBrokenWingButterfly,
+    /// Jade Lizard strategy.
+    JadeLizard,
+    /// Reverse Jade Lizard strategy.
+    ReverseJadeLizard,
+    /// Strap strategy.
+    Strap,
+    /// Strip strategy.
+    Strip,
+    /// Call Condor strategy.
+    CallCondor,
+    /// Put Condor strategy.
+    PutCondor,
+    /// Box Spread strategy.
+    BoxSpread,
+    /// Risk Reversal strategy.
+    RiskReversal,
     /// Custom strategy.
     Custom,
 }
@@ -207,6 +243,24 @@ impl FromStr for StrategyType {
             "ShortPut" => Ok(StrategyType::ShortPut),
             "PoorMansCoveredCall" => Ok(StrategyType::PoorMansCoveredCall),
             "CallButterfly" => Ok(StrategyType::CallButterfly),
+            "LongCalendarSpread" => Ok(StrategyType::LongCalendarSpread),
+            "ShortCalendarSpread" => Ok(StrategyType::ShortCalendarSpread),
+            "DiagonalSpread" => Ok(StrategyType::DiagonalSpread),
+            "DoubleCalendarSpread" => Ok(StrategyType::DoubleCalendarSpread),
+            "RatioCallSpread" => Ok(StrategyType::RatioCallSpread),
+            "RatioPutSpread" => Ok(StrategyType::RatioPutSpread),
+            "CallBackSpread" => Ok(StrategyType::CallBackSpread),
+            "PutBackSpread" => Ok(StrategyType::PutBackSpread),
+            "CallBrokenWingButterfly" => Ok(StrategyType::CallBrokenWingButterfly),
+            "PutBrokenWingButterfly" => Ok(StrategyType::PutBrokenWingButterfly),
+            "JadeLizard" => Ok(StrategyType::JadeLizard),
+            "ReverseJadeLizard" => Ok(StrategyType::ReverseJadeLizard),
+            "Strap" => Ok(StrategyType::Strap),
+            "Strip" => Ok(StrategyType::Strip),
+            "CallCondor" => Ok(StrategyType::CallCondor),
+            "PutCondor" => Ok(StrategyType::PutCondor),
+            "BoxSpread" => Ok(StrategyType::BoxSpread),
+            "RiskReversal" => Ok(StrategyType::RiskReversal),
             "Custom" => Ok(StrategyType::Custom),
             _ => Err(()),
         }
@@ -0,0 +1,690 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Box Spread combines a bull call spread with a bear put spread on the same two strikes: it buys
+//! a call and sells a put at the lower strike (a synthetic long) and sells a call and buys a put at
+//! the upper strike (a synthetic short).
+//!
+//! The strategy has four components:
+//! 1. **Long lower-strike call** and **short lower-strike put**: Together a synthetic long underlying
+//!    bought at the lower strike.
+//! 2. **Short upper-strike call** and **long upper-strike put**: Together a synthetic short underlying
+//!    sold at the upper strike.
+//!
+//! At expiration the box is always worth the distance between the strikes, whatever the underlying
+//! does, so its profit is fixed when the trade is opened. A box bought for less than the discounted
+//! width is an arbitrage; one bought for more is a guaranteed loss, which is how boxes are used to
+//! lend or borrow at an implied rate.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::piecewise::{self, LegFactory};
+use crate::{
+    ExpirationDate, Options,
+    chains::{StrategyLegs, chain::OptionChain, utils::OptionDataGroup},
+    error::{
+        GreeksError, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+
+pub(super) const BOX_SPREAD_DESCRIPTION: &str = "A Box Spread buys a bull call spread and a bear put spread on the same two \
+    strikes. It pays the strike width at expiration regardless of the underlying, so its profit or \
+    loss is fixed at entry.";
+
+/// # BoxSpread
+///
+/// Represents a Box Spread: a long call and a short put at the lower strike, and a short call and a
+/// long put at the upper strike.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::BoxSpread`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The underlying prices at expiration at which the strategy neither makes nor loses money.
+/// * `long_call`: The long call at the lower strike.
+/// * `short_put`: The short put at the lower strike.
+/// * `short_call`: The short call at the upper strike.
+/// * `long_put`: The long put at the upper strike.
+///
+/// ## Risk and Reward
+/// The payoff at expiration is the strike width times the quantity at every price, so the profit is
+/// that amount less the net debit and fees. When the debit exceeds the width the strategy has a
+/// fixed loss and no profit instead.
+///
+/// ## Break-Even Points
+/// A box has no break-even points: its result is the same at every underlying price.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct BoxSpread {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a BoxSpread strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Prices at expiration where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The long call at the lower strike
+    pub(super) long_call: Position,
+    /// The short put at the lower strike
+    pub(super) short_put: Position,
+    /// The short call at the upper strike
+    pub(super) short_call: Position,
+    /// The long put at the upper strike
+    pub(super) long_put: Position,
+}
+
+impl BoxSpread {
+    /// # Creates a new Box Spread strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `lower_strike`: Strike price of the long call and short put
+    /// * `upper_strike`: Strike price of the short call and long put
+    /// * `expiration`: Expiration date shared by all the options
+    /// * `implied_volatility`: Implied volatility used to price the options
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts for every leg of the strategy
+    /// * `premium_long_call`: Premium paid for the long call
+    /// * `premium_short_put`: Premium received for the short put
+    /// * `premium_short_call`: Premium received for the short call
+    /// * `premium_long_put`: Premium paid for the long put
+    /// * `open_fee`: Transaction fee per contract for opening each position
+    /// * `close_fee`: Transaction fee per contract for closing each position
+    ///
+    /// ## Returns
+    /// A fully initialized `BoxSpread` with its break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the parameters are invalid: the long call and
+    /// short put must share the lower strike and the short call and long put the upper one.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        lower_strike: Positive,
+        upper_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_long_call: Positive,
+        premium_short_put: Positive,
+        premium_short_call: Positive,
+        premium_long_put: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let factory = LegFactory {
+            symbol: underlying_symbol,
+            underlying_price,
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            open_fee,
+            close_fee,
+        };
+        let mut strategy = BoxSpread {
+            long_call: factory.position(
+                OptionStyle::Call,
+                Side::Long,
+                lower_strike,
+                quantity,
+                premium_long_call,
+            ),
+            short_put: factory.position(
+                OptionStyle::Put,
+                Side::Short,
+                lower_strike,
+                quantity,
+                premium_short_put,
+            ),
+            short_call: factory.position(
+                OptionStyle::Call,
+                Side::Short,
+                upper_strike,
+                quantity,
+                premium_short_call,
+            ),
+            long_put: factory.position(
+                OptionStyle::Put,
+                Side::Long,
+                upper_strike,
+                quantity,
+                premium_long_put,
+            ),
+            ..BoxSpread::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Box Spread new",
+                "the long call and short put must share the lower strike and the short call and long put the upper one",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    fn legs(&self) -> [&Position; 4] {
+        [
+            &self.long_call,
+            &self.short_put,
+            &self.short_call,
+            &self.long_put,
+        ]
+    }
+
+    fn legs_mut(&mut self) -> [&mut Position; 4] {
+        [
+            &mut self.long_call,
+            &mut self.short_put,
+            &mut self.short_call,
+            &mut self.long_put,
+        ]
+    }
+}
+
+impl StrategyConstructor for BoxSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        let [long_call, short_put, short_call, long_put] = piecewise::match_legs(
+            vec_positions,
+            [
+                (OptionStyle::Call, Side::Long),
+                (OptionStyle::Put, Side::Short),
+                (OptionStyle::Call, Side::Short),
+                (OptionStyle::Put, Side::Long),
+            ],
+            "Box Spread get_strategy",
+        )?;
+        let mut strategy = BoxSpread {
+            long_call,
+            short_put,
+            short_call,
+            long_put,
+            ..BoxSpread::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Box Spread get_strategy",
+                "the long call and short put must share the lower strike and the short call and long put the upper one",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for BoxSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = piecewise::break_even_points(&self.legs())?;
+        Ok(())
+    }
+}
+
+impl Validable for BoxSpread {
+    fn validate(&self) -> bool {
+        self.legs().iter().all(|leg| leg.validate())
+            && self.long_call.option.strike_price == self.short_put.option.strike_price
+            && self.short_call.option.strike_price == self.long_put.option.strike_price
+            && self.long_call.option.strike_price < self.short_call.option.strike_price
+    }
+}
+
+impl Positionable for BoxSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Call, Side::Long) => self.long_call = position.clone(),
+            (OptionStyle::Put, Side::Short) => self.short_put = position.clone(),
+            (OptionStyle::Call, Side::Short) => self.short_call = position.clone(),
+            (OptionStyle::Put, Side::Long) => self.long_put = position.clone(),
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(self.legs().to_vec())
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        piecewise::find_legs(self.legs_mut().into(), option_style, side, strike)
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for BoxSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for BoxSpread {
+    fn get_title(&self) -> String {
+        let legs: Vec<String> = self.legs().iter().map(|leg| leg.get_title()).collect();
+        format!("{:?} Strategy: \n\t{}", self.kind, legs.join("\n\t"))
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        piecewise::basic_types(&self.legs())
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::implied_volatilities(&self.legs())
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::quantities(&self.legs())
+    }
+    fn one_option(&self) -> &Options {
+        self.long_call.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.long_call.one_option_mut()
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        for leg in self.legs_mut() {
+            leg.option.expiration_date = expiration_date;
+        }
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.underlying_price = *price;
+        })
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.implied_volatility = *volatility;
+        })
+    }
+}
+
+impl Strategies for BoxSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_profit(&self.legs())
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_loss(&self.legs())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_area(&self.legs())
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_ratio(&self.legs())
+    }
+}
+
+impl Optimizable for BoxSpread {
+    type Strategy = BoxSpread;
+
+    /// Combinations of two increasing strikes of `option_chain` that pass the
+    /// `side` filter.
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        option_chain
+            .get_double_iter()
+            .filter(move |(lower, upper)| {
+                piecewise::quotes_in_side(option_chain, &[lower, upper], &side)
+            })
+            .map(|(lower, upper)| OptionDataGroup::Two(lower, upper))
+    }
+
+    /// Searches every combination of two increasing strikes of `option_chain` for
+    /// the strategy that scores best under `criteria` and replaces `self`
+    /// with it.
+    ///
+    /// Long legs are bought at the ask and short legs sold at the bid, and the
+    /// expiration, quantity, rate and fees of `self` are kept.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let candidates = self
+            .filter_combinations(option_chain, side)
+            .filter_map(|group| group.strategy_legs())
+            .map(|legs| self.create_strategy(option_chain, &legs));
+        if let Some(strategy) = piecewise::select_best(candidates, &criteria) {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `BoxSpread` from two quotes in increasing strike order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs have the wrong
+    /// shape, a needed bid or ask is missing, or the resulting strategy is
+    /// invalid.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (lower, upper) = match legs {
+            StrategyLegs::TwoLegs {
+                first: lower,
+                second: upper,
+            } => (lower, upper),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "BoxSpread requires exactly two legs (TwoLegs)",
+                ));
+            }
+        };
+        BoxSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            lower.strike_price,
+            upper.strike_price,
+            self.long_call.option.expiration_date,
+            lower.implied_volatility,
+            self.long_call.option.risk_free_rate,
+            self.long_call.option.dividend_yield,
+            self.long_call.option.quantity,
+            piecewise::premium(lower, OptionStyle::Call, Side::Long)?,
+            piecewise::premium(lower, OptionStyle::Put, Side::Short)?,
+            piecewise::premium(upper, OptionStyle::Call, Side::Short)?,
+            piecewise::premium(upper, OptionStyle::Put, Side::Long)?,
+            self.long_call.open_fee,
+            self.long_call.close_fee,
+        )
+    }
+}
+
+impl Profit for BoxSpread {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        piecewise::profit_at(&self.legs(), price)
+    }
+}
+
+impl ProbabilityAnalysis for BoxSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, true)
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, false)
+    }
+}
+
+impl Greeks for BoxSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self.legs().into_iter().map(|leg| &leg.option).collect())
+    }
+}
+
+impl DeltaNeutrality for BoxSpread {}
+
+impl PnLCalculator for BoxSpread {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl(
+            &self.legs(),
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl_at_expiration(&self.legs(), underlying_price)
+    }
+}
+
+test_strategy_traits!(BoxSpread, test_box_spread_implementations);
+
+#[cfg(test)]
+mod tests_box_spread {
+    use super::*;
+    use crate::assert_decimal_eq;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> BoxSpread {
+        BoxSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.5),
+            pos_or_panic!(2.0),
+            pos_or_panic!(2.5),
+            pos_or_panic!(6.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_box_spread_has_no_break_even_points() {
+        let strategy = create_strategy();
+        assert!(strategy.validate());
+        assert!(strategy.break_even_points.is_empty());
+        let at_low = strategy.calculate_profit_at(&pos_or_panic!(50.0)).unwrap();
+        let at_high = strategy.calculate_profit_at(&pos_or_panic!(150.0)).unwrap();
+        assert_eq!(at_low, at_high);
+    }
+
+    #[test]
+    fn test_box_spread_max_profit_and_loss() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.get_max_profit().unwrap().to_dec(),
+            dec!(0.5),
+            dec!(1e-9)
+        );
+        assert!(strategy.get_max_loss().is_err());
+    }
+
+    #[test]
+    fn test_box_spread_profit_at_expiration() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap(),
+            dec!(0.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(85.0)).unwrap(),
+            dec!(0.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(115.0)).unwrap(),
+            dec!(0.5),
+            dec!(1e-9)
+        );
+        let pnl = strategy
+            .calculate_pnl_at_expiration(&pos_or_panic!(100.0))
+            .unwrap();
+        assert_eq!(
+            pnl.total_pnl(),
+            Some(strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_box_spread_rejects_invalid_parameters() {
+        let result = BoxSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(105.0),
+            pos_or_panic!(95.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(7.5),
+            pos_or_panic!(2.0),
+            pos_or_panic!(2.5),
+            pos_or_panic!(6.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_box_spread_get_strategy_from_positions() {
+        let strategy = create_strategy();
+        let positions = vec![
+            strategy.long_put.clone(),
+            strategy.short_call.clone(),
+            strategy.short_put.clone(),
+            strategy.long_call.clone(),
+        ];
+        let rebuilt = BoxSpread::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.break_even_points, strategy.break_even_points);
+        assert_eq!(rebuilt.get_positions().unwrap().len(), 4);
+
+        let mut wrong = positions.clone();
+        wrong[0].option.side = match wrong[0].option.side {
+            Side::Long => Side::Short,
+            Side::Short => Side::Long,
+        };
+        assert!(BoxSpread::get_strategy(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_box_spread_find_optimal() {
+        let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+            "SPY".to_string(),
+            None,
+            6,
+            spos!(2.5),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(100.0))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("SPY".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        ))
+        .unwrap();
+        let mut strategy = create_strategy();
+        strategy.find_optimal(&chain, FindOptimalSide::All, OptimizationCriteria::Area);
+        assert!(strategy.validate());
+        for leg in strategy.get_positions().unwrap() {
+            assert!(
+                chain
+                    .options
+                    .iter()
+                    .any(|quote| quote.strike_price == leg.option.strike_price)
+            );
+        }
+    }
+}
@@ -9,9 +9,13 @@ use crate::model::Position;
 use crate::strategies::base::StrategyType;
 use crate::strategies::custom::CustomStrategy;
 use crate::strategies::{
-    BearCallSpread, BearPutSpread, BullCallSpread, BullPutSpread, CallButterfly, IronButterfly,
-    IronCondor, LongButterflySpread, LongStraddle, LongStrangle, PoorMansCoveredCall,
-    ShortButterflySpread, ShortStraddle, ShortStrangle, Strategable, StrategyConstructor,
+    BearCallSpread, BearPutSpread, BoxSpread, BullCallSpread, BullPutSpread, CallBackSpread,
+    CallBrokenWingButterfly, CallButterfly, CallCondor, DiagonalSpread, DoubleCalendarSpread,
+    IronButterfly, IronCondor, JadeLizard, LongButterflySpread, LongCalendarSpread, LongStraddle,
+    LongStrangle, PoorMansCoveredCall, PutBackSpread, PutBrokenWingButterfly, PutCondor,
+    RatioCallSpread, RatioPutSpread, ReverseJadeLizard, RiskReversal, ShortButterflySpread,
+    ShortCalendarSpread, ShortStraddle, ShortStrangle, Strap, Strategable, StrategyConstructor,
+    Strip,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -129,6 +133,48 @@ impl StrategyRequest {
             StrategyType::CallButterfly => {
                 Ok(Box::new(CallButterfly::get_strategy(&self.positions)?))
             }
+            StrategyType::LongCalendarSpread => {
+                Ok(Box::new(LongCalendarSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::ShortCalendarSpread => Ok(Box::new(ShortCalendarSpread::get_strategy(
+                &self.positions,
+            )?)),
+            StrategyType::DiagonalSpread => {
+                Ok(Box::new(DiagonalSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::DoubleCalendarSpread => Ok(Box::new(DoubleCalendarSpread::get_strategy(
+                &self.positions,
+            )?)),
+            StrategyType::RatioCallSpread => {
+                Ok(Box::new(RatioCallSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::RatioPutSpread => {
+                Ok(Box::new(RatioPutSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::CallBackSpread => {
+                Ok(Box::new(CallBackSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::PutBackSpread => {
+                Ok(Box::new(PutBackSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::CallBrokenWingButterfly => Ok(Box::new(
+                CallBrokenWingButterfly::get_strategy(&self.positions)?,
+            )),
+            StrategyType::PutBrokenWingButterfly => Ok(Box::new(
+                PutBrokenWingButterfly::get_strategy(&self.positions)?,
+            )),
+            StrategyType::JadeLizard => Ok(Box::new(JadeLizard::get_strategy(&self.positions)?)),
+            StrategyType::ReverseJadeLizard => {
+                Ok(Box::new(ReverseJadeLizard::get_strategy(&self.positions)?))
+            }
+            StrategyType::Strap => Ok(Box::new(Strap::get_strategy(&self.positions)?)),
+            StrategyType::Strip => Ok(Box::new(Strip::get_strategy(&self.positions)?)),
+            StrategyType::CallCondor => Ok(Box::new(CallCondor::get_strategy(&self.positions)?)),
+            StrategyType::PutCondor => Ok(Box::new(PutCondor::get_strategy(&self.positions)?)),
+            StrategyType::BoxSpread => Ok(Box::new(BoxSpread::get_strategy(&self.positions)?)),
+            StrategyType::RiskReversal => {
+                Ok(Box::new(RiskReversal::get_strategy(&self.positions)?))
+            }
             StrategyType::Custom => Ok(Box::new(CustomStrategy::get_strategy(&self.positions)?)),
         }
     }
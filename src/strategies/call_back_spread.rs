@@ -0,0 +1,668 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Call Back Spread (call ratio backspread) sells calls at a lower strike and buys a larger
+//! number of calls at a higher strike. The premium of the short calls pays for part or all of the
+//! long calls, leaving a position that is net long calls above the upper strike.
+//!
+//! The strategy has two components:
+//! 1. **Short lower-strike calls**: Finance the trade and cap the gain between the strikes.
+//! 2. **Long higher-strike calls**: More contracts than the short leg, providing unlimited upside.
+//!
+//! The worst outcome is the underlying finishing at the upper strike, where the short calls are in
+//! the money and the long calls expire worthless. Below the lower strike the result is the net
+//! premium, a small gain when the spread is opened for a credit.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::piecewise::{self, LegFactory};
+use crate::{
+    ExpirationDate, Options,
+    chains::{StrategyLegs, chain::OptionChain, utils::OptionDataGroup},
+    error::{
+        GreeksError, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+
+pub(super) const CALL_BACK_SPREAD_DESCRIPTION: &str = "A Call Back Spread sells calls at a lower strike and buys more calls \
+    at a higher strike. It has unlimited upside, limited risk that peaks at the upper strike \
+    and keeps the net credit if the underlying falls.";
+
+/// # CallBackSpread
+///
+/// Represents a Call Back Spread: short calls at the lower strike and a larger number of long
+/// calls at the upper strike.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::CallBackSpread`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The underlying prices at expiration at which the strategy neither makes nor loses money.
+/// * `short_call`: The short calls at the lower strike.
+/// * `long_call`: The long calls at the upper strike.
+///
+/// ## Risk and Reward
+/// The profit is unlimited above the upper strike. The maximum loss is the width between the
+/// strikes times the short quantity less the net credit (or plus the net debit), reached with the
+/// underlying at the upper strike.
+///
+/// ## Break-Even Points
+/// There is an upper break-even point above the long strike and, when the spread is opened for a
+/// credit, a lower one between the strikes.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct CallBackSpread {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a CallBackSpread strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Prices at expiration where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The short calls at the lower strike
+    pub(super) short_call: Position,
+    /// The long calls at the upper strike
+    pub(super) long_call: Position,
+}
+
+impl CallBackSpread {
+    /// # Creates a new Call Back Spread strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `lower_strike`: Strike price of the short calls
+    /// * `upper_strike`: Strike price of the long calls
+    /// * `expiration`: Expiration date shared by all the options
+    /// * `implied_volatility`: Implied volatility used to price the options
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts of the short calls at the lower strike
+    /// * `ratio`: Number of long calls bought per short call sold
+    /// * `premium_short_call`: Premium received for each short call
+    /// * `premium_long_call`: Premium paid for each long call
+    /// * `open_fee`: Transaction fee per contract for opening each position
+    /// * `close_fee`: Transaction fee per contract for closing each position
+    ///
+    /// ## Returns
+    /// A fully initialized `CallBackSpread` with its break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the parameters are invalid: the short strike must
+    /// be below the long strike and the long calls must outnumber the short calls.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        lower_strike: Positive,
+        upper_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        ratio: Positive,
+        premium_short_call: Positive,
+        premium_long_call: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let factory = LegFactory {
+            symbol: underlying_symbol,
+            underlying_price,
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            open_fee,
+            close_fee,
+        };
+        let mut strategy = CallBackSpread {
+            short_call: factory.position(
+                OptionStyle::Call,
+                Side::Short,
+                lower_strike,
+                quantity,
+                premium_short_call,
+            ),
+            long_call: factory.position(
+                OptionStyle::Call,
+                Side::Long,
+                upper_strike,
+                quantity * ratio,
+                premium_long_call,
+            ),
+            ..CallBackSpread::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Call Back Spread new",
+                "the short strike must be below the long strike and the long calls must outnumber the short calls",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    /// Number of long calls bought per short call sold, or zero for an empty strategy.
+    pub fn ratio(&self) -> Positive {
+        let base = self.short_call.option.quantity;
+        if base == Positive::ZERO {
+            Positive::ZERO
+        } else {
+            self.long_call.option.quantity / base
+        }
+    }
+
+    fn legs(&self) -> [&Position; 2] {
+        [&self.short_call, &self.long_call]
+    }
+
+    fn legs_mut(&mut self) -> [&mut Position; 2] {
+        [&mut self.short_call, &mut self.long_call]
+    }
+}
+
+impl StrategyConstructor for CallBackSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        let [short_call, long_call] = piecewise::match_legs(
+            vec_positions,
+            [
+                (OptionStyle::Call, Side::Short),
+                (OptionStyle::Call, Side::Long),
+            ],
+            "Call Back Spread get_strategy",
+        )?;
+        let mut strategy = CallBackSpread {
+            short_call,
+            long_call,
+            ..CallBackSpread::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Call Back Spread get_strategy",
+                "the short strike must be below the long strike and the long calls must outnumber the short calls",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for CallBackSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = piecewise::break_even_points(&self.legs())?;
+        Ok(())
+    }
+}
+
+impl Validable for CallBackSpread {
+    fn validate(&self) -> bool {
+        self.legs().iter().all(|leg| leg.validate())
+            && self.short_call.option.strike_price < self.long_call.option.strike_price
+            && self.long_call.option.quantity > self.short_call.option.quantity
+    }
+}
+
+impl Positionable for CallBackSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Call, Side::Short) => self.short_call = position.clone(),
+            (OptionStyle::Call, Side::Long) => self.long_call = position.clone(),
+            _ => {
+                return Err(PositionError::invalid_position_type(
+                    position.option.side,
+                    "Put is not part of a Call Back Spread".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(self.legs().to_vec())
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        piecewise::find_legs(self.legs_mut().into(), option_style, side, strike)
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for CallBackSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for CallBackSpread {
+    fn get_title(&self) -> String {
+        let legs: Vec<String> = self.legs().iter().map(|leg| leg.get_title()).collect();
+        format!("{:?} Strategy: \n\t{}", self.kind, legs.join("\n\t"))
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        piecewise::basic_types(&self.legs())
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::implied_volatilities(&self.legs())
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::quantities(&self.legs())
+    }
+    fn one_option(&self) -> &Options {
+        self.short_call.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.short_call.one_option_mut()
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        for leg in self.legs_mut() {
+            leg.option.expiration_date = expiration_date;
+        }
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.underlying_price = *price;
+        })
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.implied_volatility = *volatility;
+        })
+    }
+}
+
+impl Strategies for CallBackSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_profit(&self.legs())
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_loss(&self.legs())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_area(&self.legs())
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_ratio(&self.legs())
+    }
+}
+
+impl Optimizable for CallBackSpread {
+    type Strategy = CallBackSpread;
+
+    /// Combinations of two increasing strikes of `option_chain` that pass the
+    /// `side` filter.
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        option_chain
+            .get_double_iter()
+            .filter(move |(lower, upper)| {
+                piecewise::quotes_in_side(option_chain, &[lower, upper], &side)
+            })
+            .map(|(lower, upper)| OptionDataGroup::Two(lower, upper))
+    }
+
+    /// Searches every combination of two increasing strikes of `option_chain` for
+    /// the strategy that scores best under `criteria` and replaces `self`
+    /// with it.
+    ///
+    /// Long legs are bought at the ask and short legs sold at the bid, and the
+    /// expiration, quantity, ratio, rate and fees of `self` are kept.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let candidates = self
+            .filter_combinations(option_chain, side)
+            .filter_map(|group| group.strategy_legs())
+            .map(|legs| self.create_strategy(option_chain, &legs));
+        if let Some(strategy) = piecewise::select_best(candidates, &criteria) {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `CallBackSpread` from two quotes in increasing strike order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs have the wrong
+    /// shape, a needed bid or ask is missing, or the resulting strategy is
+    /// invalid.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (lower, upper) = match legs {
+            StrategyLegs::TwoLegs {
+                first: lower,
+                second: upper,
+            } => (lower, upper),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "CallBackSpread requires exactly two legs (TwoLegs)",
+                ));
+            }
+        };
+        CallBackSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            lower.strike_price,
+            upper.strike_price,
+            self.short_call.option.expiration_date,
+            lower.implied_volatility,
+            self.short_call.option.risk_free_rate,
+            self.short_call.option.dividend_yield,
+            self.short_call.option.quantity,
+            self.ratio(),
+            piecewise::premium(lower, OptionStyle::Call, Side::Short)?,
+            piecewise::premium(upper, OptionStyle::Call, Side::Long)?,
+            self.short_call.open_fee,
+            self.short_call.close_fee,
+        )
+    }
+}
+
+impl Profit for CallBackSpread {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        piecewise::profit_at(&self.legs(), price)
+    }
+}
+
+impl ProbabilityAnalysis for CallBackSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, true)
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, false)
+    }
+}
+
+impl Greeks for CallBackSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self.legs().into_iter().map(|leg| &leg.option).collect())
+    }
+}
+
+impl DeltaNeutrality for CallBackSpread {}
+
+impl PnLCalculator for CallBackSpread {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl(
+            &self.legs(),
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl_at_expiration(&self.legs(), underlying_price)
+    }
+}
+
+test_strategy_traits!(CallBackSpread, test_call_back_spread_implementations);
+
+#[cfg(test)]
+mod tests_call_back_spread {
+    use super::*;
+    use crate::assert_decimal_eq;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> CallBackSpread {
+        CallBackSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.0),
+            pos_or_panic!(7.0),
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_call_back_spread_break_even_points() {
+        let strategy = create_strategy();
+        assert!(strategy.validate());
+        assert_eq!(strategy.break_even_points.len(), 2);
+        assert_decimal_eq!(
+            strategy.break_even_points[0].to_dec(),
+            dec!(98.0),
+            dec!(0.01)
+        );
+        assert_decimal_eq!(
+            strategy.break_even_points[1].to_dec(),
+            dec!(112.0),
+            dec!(0.01)
+        );
+        for point in &strategy.break_even_points {
+            let pnl = strategy.calculate_profit_at(point).unwrap();
+            assert!(pnl.abs() < dec!(0.05), "P&L {pnl} at break-even {point}");
+        }
+    }
+
+    #[test]
+    fn test_call_back_spread_max_profit_and_loss() {
+        let strategy = create_strategy();
+        assert_eq!(strategy.get_max_profit().unwrap(), Positive::MAX);
+        assert_decimal_eq!(
+            strategy.get_max_loss().unwrap().to_dec(),
+            dec!(7.0),
+            dec!(1e-9)
+        );
+    }
+
+    #[test]
+    fn test_call_back_spread_profit_at_expiration() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap(),
+            dec!(-2.0),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(85.0)).unwrap(),
+            dec!(3.0),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(115.0)).unwrap(),
+            dec!(3.0),
+            dec!(1e-9)
+        );
+        let pnl = strategy
+            .calculate_pnl_at_expiration(&pos_or_panic!(100.0))
+            .unwrap();
+        assert_eq!(
+            pnl.total_pnl(),
+            Some(strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_call_back_spread_rejects_invalid_parameters() {
+        let result = CallBackSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.0),
+            pos_or_panic!(7.0),
+            pos_or_panic!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_call_back_spread_get_strategy_from_positions() {
+        let strategy = create_strategy();
+        let positions = vec![strategy.long_call.clone(), strategy.short_call.clone()];
+        let rebuilt = CallBackSpread::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.break_even_points, strategy.break_even_points);
+        assert_eq!(rebuilt.get_positions().unwrap().len(), 2);
+
+        let mut wrong = positions.clone();
+        wrong[0].option.side = match wrong[0].option.side {
+            Side::Long => Side::Short,
+            Side::Short => Side::Long,
+        };
+        assert!(CallBackSpread::get_strategy(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_call_back_spread_find_optimal() {
+        let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+            "SPY".to_string(),
+            None,
+            6,
+            spos!(2.5),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(100.0))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("SPY".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        ))
+        .unwrap();
+        let mut strategy = create_strategy();
+        strategy.find_optimal(&chain, FindOptimalSide::All, OptimizationCriteria::Area);
+        assert!(strategy.validate());
+        for leg in strategy.get_positions().unwrap() {
+            assert!(
+                chain
+                    .options
+                    .iter()
+                    .any(|quote| quote.strike_price == leg.option.strike_price)
+            );
+        }
+    }
+}
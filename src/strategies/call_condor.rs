@@ -0,0 +1,741 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Call Condor (long condor spread with calls) buys a call at the lowest strike, sells calls at
+//! the two middle strikes and buys a call at the highest strike. It is the all-call counterpart of
+//! the iron condor and profits when the underlying stays between the two short strikes.
+//!
+//! The strategy has four components:
+//! 1. **Long lowest-strike call**: Starts the profit zone.
+//! 2. **Short lower-middle call**: Caps the gain of the long lower call.
+//! 3. **Short upper-middle call**: Finances the trade and ends the flat profit zone.
+//! 4. **Long highest-strike call**: Limits the loss of the upper short call.
+//!
+//! Both the maximum profit and the maximum loss are limited and known when the trade is opened.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::piecewise::{self, LegFactory};
+use crate::{
+    ExpirationDate, Options,
+    chains::{StrategyLegs, chain::OptionChain, utils::OptionDataGroup},
+    error::{
+        GreeksError, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+
+pub(super) const CALL_CONDOR_DESCRIPTION: &str = "A Call Condor buys calls at the outer strikes and sells calls at the two \
+    inner strikes for a net debit. It profits when the underlying finishes between the inner \
+    strikes and has limited risk on both sides.";
+
+/// # CallCondor
+///
+/// Represents a Call Condor: long calls at the lowest and highest strikes and short calls at the two
+/// middle strikes.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::CallCondor`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The underlying prices at expiration at which the strategy neither makes nor loses money.
+/// * `long_call_low`: The long call at the lowest strike.
+/// * `short_call_low`: The short call at the lower middle strike.
+/// * `short_call_high`: The short call at the upper middle strike.
+/// * `long_call_high`: The long call at the highest strike.
+///
+/// ## Risk and Reward
+/// The maximum profit is the lower spread width less the net debit, kept with the underlying
+/// between the short strikes. The maximum loss is the net debit below the lowest strike, or the net
+/// debit plus any difference between the two spread widths above the highest one.
+///
+/// ## Break-Even Points
+/// There are two break-even points, one inside each of the outer spreads.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct CallCondor {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a CallCondor strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Prices at expiration where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The long call at the lowest strike
+    pub(super) long_call_low: Position,
+    /// The short call at the lower middle strike
+    pub(super) short_call_low: Position,
+    /// The short call at the upper middle strike
+    pub(super) short_call_high: Position,
+    /// The long call at the highest strike
+    pub(super) long_call_high: Position,
+}
+
+impl CallCondor {
+    /// # Creates a new Call Condor strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `lowest_strike`: Strike price of the lower long call
+    /// * `lower_middle_strike`: Strike price of the lower short call
+    /// * `upper_middle_strike`: Strike price of the upper short call
+    /// * `highest_strike`: Strike price of the upper long call
+    /// * `expiration`: Expiration date shared by all the options
+    /// * `implied_volatility`: Implied volatility used to price the options
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts for every leg of the strategy
+    /// * `premium_long_call_low`: Premium paid for the lower long call
+    /// * `premium_short_call_low`: Premium received for the lower short call
+    /// * `premium_short_call_high`: Premium received for the upper short call
+    /// * `premium_long_call_high`: Premium paid for the upper long call
+    /// * `open_fee`: Transaction fee per contract for opening each position
+    /// * `close_fee`: Transaction fee per contract for closing each position
+    ///
+    /// ## Returns
+    /// A fully initialized `CallCondor` with its break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the parameters are invalid: the strikes must be
+    /// strictly increasing from the lower long call to the upper long call.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        lowest_strike: Positive,
+        lower_middle_strike: Positive,
+        upper_middle_strike: Positive,
+        highest_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_long_call_low: Positive,
+        premium_short_call_low: Positive,
+        premium_short_call_high: Positive,
+        premium_long_call_high: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let factory = LegFactory {
+            symbol: underlying_symbol,
+            underlying_price,
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            open_fee,
+            close_fee,
+        };
+        let mut strategy = CallCondor {
+            long_call_low: factory.position(
+                OptionStyle::Call,
+                Side::Long,
+                lowest_strike,
+                quantity,
+                premium_long_call_low,
+            ),
+            short_call_low: factory.position(
+                OptionStyle::Call,
+                Side::Short,
+                lower_middle_strike,
+                quantity,
+                premium_short_call_low,
+            ),
+            short_call_high: factory.position(
+                OptionStyle::Call,
+                Side::Short,
+                upper_middle_strike,
+                quantity,
+                premium_short_call_high,
+            ),
+            long_call_high: factory.position(
+                OptionStyle::Call,
+                Side::Long,
+                highest_strike,
+                quantity,
+                premium_long_call_high,
+            ),
+            ..CallCondor::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Call Condor new",
+                "the strikes must be strictly increasing from the lower long call to the upper long call",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    fn legs(&self) -> [&Position; 4] {
+        [
+            &self.long_call_low,
+            &self.short_call_low,
+            &self.short_call_high,
+            &self.long_call_high,
+        ]
+    }
+
+    fn legs_mut(&mut self) -> [&mut Position; 4] {
+        [
+            &mut self.long_call_low,
+            &mut self.short_call_low,
+            &mut self.short_call_high,
+            &mut self.long_call_high,
+        ]
+    }
+}
+
+impl StrategyConstructor for CallCondor {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        let [
+            long_call_low,
+            short_call_low,
+            short_call_high,
+            long_call_high,
+        ] = piecewise::match_legs(
+            vec_positions,
+            [
+                (OptionStyle::Call, Side::Long),
+                (OptionStyle::Call, Side::Short),
+                (OptionStyle::Call, Side::Short),
+                (OptionStyle::Call, Side::Long),
+            ],
+            "Call Condor get_strategy",
+        )?;
+        let mut strategy = CallCondor {
+            long_call_low,
+            short_call_low,
+            short_call_high,
+            long_call_high,
+            ..CallCondor::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Call Condor get_strategy",
+                "the strikes must be strictly increasing from the lower long call to the upper long call",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for CallCondor {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = piecewise::break_even_points(&self.legs())?;
+        Ok(())
+    }
+}
+
+impl Validable for CallCondor {
+    fn validate(&self) -> bool {
+        self.legs().iter().all(|leg| leg.validate())
+            && self.long_call_low.option.strike_price < self.short_call_low.option.strike_price
+            && self.short_call_low.option.strike_price < self.short_call_high.option.strike_price
+            && self.short_call_high.option.strike_price < self.long_call_high.option.strike_price
+    }
+}
+
+impl Positionable for CallCondor {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Call, Side::Long) => {
+                if position.option.strike_price < self.short_call_low.option.strike_price {
+                    self.long_call_low = position.clone();
+                } else {
+                    self.long_call_high = position.clone();
+                }
+            }
+            (OptionStyle::Call, Side::Short) => {
+                if position.option.strike_price < self.short_call_high.option.strike_price {
+                    self.short_call_low = position.clone();
+                } else {
+                    self.short_call_high = position.clone();
+                }
+            }
+            _ => {
+                return Err(PositionError::invalid_position_type(
+                    position.option.side,
+                    "Put is not part of a Call Condor".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(self.legs().to_vec())
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        piecewise::find_legs(self.legs_mut().into(), option_style, side, strike)
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for CallCondor {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for CallCondor {
+    fn get_title(&self) -> String {
+        let legs: Vec<String> = self.legs().iter().map(|leg| leg.get_title()).collect();
+        format!("{:?} Strategy: \n\t{}", self.kind, legs.join("\n\t"))
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        piecewise::basic_types(&self.legs())
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::implied_volatilities(&self.legs())
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::quantities(&self.legs())
+    }
+    fn one_option(&self) -> &Options {
+        self.long_call_low.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.long_call_low.one_option_mut()
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        for leg in self.legs_mut() {
+            leg.option.expiration_date = expiration_date;
+        }
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.underlying_price = *price;
+        })
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.implied_volatility = *volatility;
+        })
+    }
+}
+
+impl Strategies for CallCondor {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_profit(&self.legs())
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_loss(&self.legs())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_area(&self.legs())
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_ratio(&self.legs())
+    }
+}
+
+impl Optimizable for CallCondor {
+    type Strategy = CallCondor;
+
+    /// Combinations of four increasing strikes of `option_chain` that pass the
+    /// `side` filter.
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        option_chain
+            .get_quad_iter()
+            .filter(move |(lowest, lower_middle, upper_middle, highest)| {
+                piecewise::quotes_in_side(
+                    option_chain,
+                    &[lowest, lower_middle, upper_middle, highest],
+                    &side,
+                )
+            })
+            .map(|(lowest, lower_middle, upper_middle, highest)| {
+                OptionDataGroup::Four(lowest, lower_middle, upper_middle, highest)
+            })
+    }
+
+    /// Searches every combination of four increasing strikes of `option_chain` for
+    /// the strategy that scores best under `criteria` and replaces `self`
+    /// with it.
+    ///
+    /// Long legs are bought at the ask and short legs sold at the bid, and the
+    /// expiration, quantity, rate and fees of `self` are kept.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let candidates = self
+            .filter_combinations(option_chain, side)
+            .filter_map(|group| group.strategy_legs())
+            .map(|legs| self.create_strategy(option_chain, &legs));
+        if let Some(strategy) = piecewise::select_best(candidates, &criteria) {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `CallCondor` from four quotes in increasing strike order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs have the wrong
+    /// shape, a needed bid or ask is missing, or the resulting strategy is
+    /// invalid.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (lowest, lower_middle, upper_middle, highest) = match legs {
+            StrategyLegs::FourLegs {
+                first: lowest,
+                second: lower_middle,
+                third: upper_middle,
+                fourth: highest,
+            } => (lowest, lower_middle, upper_middle, highest),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "CallCondor requires exactly four legs (FourLegs)",
+                ));
+            }
+        };
+        CallCondor::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            lowest.strike_price,
+            lower_middle.strike_price,
+            upper_middle.strike_price,
+            highest.strike_price,
+            self.long_call_low.option.expiration_date,
+            lowest.implied_volatility,
+            self.long_call_low.option.risk_free_rate,
+            self.long_call_low.option.dividend_yield,
+            self.long_call_low.option.quantity,
+            piecewise::premium(lowest, OptionStyle::Call, Side::Long)?,
+            piecewise::premium(lower_middle, OptionStyle::Call, Side::Short)?,
+            piecewise::premium(upper_middle, OptionStyle::Call, Side::Short)?,
+            piecewise::premium(highest, OptionStyle::Call, Side::Long)?,
+            self.long_call_low.open_fee,
+            self.long_call_low.close_fee,
+        )
+    }
+}
+
+impl Profit for CallCondor {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        piecewise::profit_at(&self.legs(), price)
+    }
+}
+
+impl ProbabilityAnalysis for CallCondor {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, true)
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, false)
+    }
+}
+
+impl Greeks for CallCondor {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self.legs().into_iter().map(|leg| &leg.option).collect())
+    }
+}
+
+impl DeltaNeutrality for CallCondor {}
+
+impl PnLCalculator for CallCondor {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl(
+            &self.legs(),
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl_at_expiration(&self.legs(), underlying_price)
+    }
+}
+
+test_strategy_traits!(CallCondor, test_call_condor_implementations);
+
+#[cfg(test)]
+mod tests_call_condor {
+    use super::*;
+    use crate::assert_decimal_eq;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> CallCondor {
+        CallCondor::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(90.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            pos_or_panic!(110.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(11.0),
+            pos_or_panic!(7.0),
+            pos_or_panic!(2.0),
+            pos_or_panic!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_call_condor_break_even_points() {
+        let strategy = create_strategy();
+        assert!(strategy.validate());
+        assert_eq!(strategy.break_even_points.len(), 2);
+        assert_decimal_eq!(
+            strategy.break_even_points[0].to_dec(),
+            dec!(92.5),
+            dec!(0.01)
+        );
+        assert_decimal_eq!(
+            strategy.break_even_points[1].to_dec(),
+            dec!(107.5),
+            dec!(0.01)
+        );
+        for point in &strategy.break_even_points {
+            let pnl = strategy.calculate_profit_at(point).unwrap();
+            assert!(pnl.abs() < dec!(0.05), "P&L {pnl} at break-even {point}");
+        }
+    }
+
+    #[test]
+    fn test_call_condor_max_profit_and_loss() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.get_max_profit().unwrap().to_dec(),
+            dec!(2.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.get_max_loss().unwrap().to_dec(),
+            dec!(2.5),
+            dec!(1e-9)
+        );
+    }
+
+    #[test]
+    fn test_call_condor_profit_at_expiration() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap(),
+            dec!(2.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(80.0)).unwrap(),
+            dec!(-2.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(120.0)).unwrap(),
+            dec!(-2.5),
+            dec!(1e-9)
+        );
+        let pnl = strategy
+            .calculate_pnl_at_expiration(&pos_or_panic!(100.0))
+            .unwrap();
+        assert_eq!(
+            pnl.total_pnl(),
+            Some(strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_call_condor_rejects_invalid_parameters() {
+        let result = CallCondor::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(90.0),
+            pos_or_panic!(105.0),
+            pos_or_panic!(110.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(11.0),
+            pos_or_panic!(7.0),
+            pos_or_panic!(2.0),
+            pos_or_panic!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_call_condor_get_strategy_from_positions() {
+        let strategy = create_strategy();
+        let positions = vec![
+            strategy.long_call_high.clone(),
+            strategy.short_call_high.clone(),
+            strategy.short_call_low.clone(),
+            strategy.long_call_low.clone(),
+        ];
+        let rebuilt = CallCondor::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.break_even_points, strategy.break_even_points);
+        assert_eq!(rebuilt.get_positions().unwrap().len(), 4);
+
+        let mut wrong = positions.clone();
+        wrong[0].option.side = match wrong[0].option.side {
+            Side::Long => Side::Short,
+            Side::Short => Side::Long,
+        };
+        assert!(CallCondor::get_strategy(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_call_condor_find_optimal() {
+        let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+            "SPY".to_string(),
+            None,
+            6,
+            spos!(2.5),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(100.0))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("SPY".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        ))
+        .unwrap();
+        let mut strategy = create_strategy();
+        strategy.find_optimal(&chain, FindOptimalSide::All, OptimizationCriteria::Area);
+        assert!(strategy.validate());
+        for leg in strategy.get_positions().unwrap() {
+            assert!(
+                chain
+                    .options
+                    .iter()
+                    .any(|quote| quote.strike_price == leg.option.strike_price)
+            );
+        }
+    }
+}
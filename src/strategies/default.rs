@@ -1,14 +1,36 @@
 use crate::model::Position;
 use crate::strategies::base::StrategyType;
+use crate::strategies::box_spread::BOX_SPREAD_DESCRIPTION;
+use crate::strategies::call_back_spread::CALL_BACK_SPREAD_DESCRIPTION;
+use crate::strategies::call_broken_wing_butterfly::CALL_BROKEN_WING_BUTTERFLY_DESCRIPTION;
+use crate::strategies::call_condor::CALL_CONDOR_DESCRIPTION;
+use crate::strategies::diagonal_spread::DIAGONAL_SPREAD_DESCRIPTION;
+use crate::strategies::double_calendar_spread::DOUBLE_CALENDAR_SPREAD_DESCRIPTION;
+use crate::strategies::jade_lizard::JADE_LIZARD_DESCRIPTION;
+use crate::strategies::long_calendar_spread::LONG_CALENDAR_SPREAD_DESCRIPTION;
 use crate::strategies::long_call::LONG_CALL_DESCRIPTION;
 use crate::strategies::long_put::LONG_PUT_DESCRIPTION;
 use crate::strategies::poor_mans_covered_call::PMCC_DESCRIPTION;
+use crate::strategies::put_back_spread::PUT_BACK_SPREAD_DESCRIPTION;
+use crate::strategies::put_broken_wing_butterfly::PUT_BROKEN_WING_BUTTERFLY_DESCRIPTION;
+use crate::strategies::put_condor::PUT_CONDOR_DESCRIPTION;
+use crate::strategies::ratio_call_spread::RATIO_CALL_SPREAD_DESCRIPTION;
+use crate::strategies::ratio_put_spread::RATIO_PUT_SPREAD_DESCRIPTION;
+use crate::strategies::reverse_jade_lizard::REVERSE_JADE_LIZARD_DESCRIPTION;
+use crate::strategies::risk_reversal::RISK_REVERSAL_DESCRIPTION;
+use crate::strategies::short_calendar_spread::SHORT_CALENDAR_SPREAD_DESCRIPTION;
 use crate::strategies::short_call::SHORT_CALL_DESCRIPTION;
 use crate::strategies::short_put::SHORT_PUT_DESCRIPTION;
+use crate::strategies::strap::STRAP_DESCRIPTION;
+use crate::strategies::strip::STRIP_DESCRIPTION;
 use crate::strategies::{
-    BearCallSpread, BearPutSpread, BullCallSpread, BullPutSpread, CallButterfly, IronButterfly,
-    IronCondor, LongButterflySpread, LongCall, LongPut, LongStraddle, LongStrangle,
-    PoorMansCoveredCall, ShortButterflySpread, ShortCall, ShortPut, ShortStraddle, ShortStrangle,
+    BearCallSpread, BearPutSpread, BoxSpread, BullCallSpread, BullPutSpread, CallBackSpread,
+    CallBrokenWingButterfly, CallButterfly, CallCondor, DiagonalSpread, DoubleCalendarSpread,
+    IronButterfly, IronCondor, JadeLizard, LongButterflySpread, LongCalendarSpread, LongCall,
+    LongPut, LongStraddle, LongStrangle, PoorMansCoveredCall, PutBackSpread,
+    PutBrokenWingButterfly, PutCondor, RatioCallSpread, RatioPutSpread, ReverseJadeLizard,
+    RiskReversal, ShortButterflySpread, ShortCalendarSpread, ShortCall, ShortPut, ShortStraddle,
+    ShortStrangle, Strap, Strip,
 };
 
 impl Default for BullCallSpread {
@@ -179,6 +201,56 @@ impl Default for PoorMansCoveredCall {
         }
     }
 }
+impl Default for LongCalendarSpread {
+    fn default() -> Self {
+        LongCalendarSpread {
+            name: "Long Calendar Spread".to_string(),
+            kind: StrategyType::LongCalendarSpread,
+            description: LONG_CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg: Position::default(),
+            far_leg: Position::default(),
+        }
+    }
+}
+impl Default for ShortCalendarSpread {
+    fn default() -> Self {
+        ShortCalendarSpread {
+            name: "Short Calendar Spread".to_string(),
+            kind: StrategyType::ShortCalendarSpread,
+            description: SHORT_CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg: Position::default(),
+            far_leg: Position::default(),
+        }
+    }
+}
+impl Default for DiagonalSpread {
+    fn default() -> Self {
+        DiagonalSpread {
+            name: "Diagonal Spread".to_string(),
+            kind: StrategyType::DiagonalSpread,
+            description: DIAGONAL_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg: Position::default(),
+            far_leg: Position::default(),
+        }
+    }
+}
+impl Default for DoubleCalendarSpread {
+    fn default() -> Self {
+        DoubleCalendarSpread {
+            name: "Double Calendar Spread".to_string(),
+            kind: StrategyType::DoubleCalendarSpread,
+            description: DOUBLE_CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_put: Position::default(),
+            far_put: Position::default(),
+            near_call: Position::default(),
+            far_call: Position::default(),
+        }
+    }
+}
 impl Default for CallButterfly {
     fn default() -> Self {
         CallButterfly {
@@ -236,3 +308,181 @@ impl Default for ShortPut {
         }
     }
 }
+impl Default for RatioCallSpread {
+    fn default() -> Self {
+        RatioCallSpread {
+            name: "Ratio Call Spread".to_string(),
+            kind: StrategyType::RatioCallSpread,
+            description: RATIO_CALL_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_call: Position::default(),
+            short_call: Position::default(),
+        }
+    }
+}
+impl Default for RatioPutSpread {
+    fn default() -> Self {
+        RatioPutSpread {
+            name: "Ratio Put Spread".to_string(),
+            kind: StrategyType::RatioPutSpread,
+            description: RATIO_PUT_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            short_put: Position::default(),
+            long_put: Position::default(),
+        }
+    }
+}
+impl Default for CallBackSpread {
+    fn default() -> Self {
+        CallBackSpread {
+            name: "Call Back Spread".to_string(),
+            kind: StrategyType::CallBackSpread,
+            description: CALL_BACK_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            short_call: Position::default(),
+            long_call: Position::default(),
+        }
+    }
+}
+impl Default for PutBackSpread {
+    fn default() -> Self {
+        PutBackSpread {
+            name: "Put Back Spread".to_string(),
+            kind: StrategyType::PutBackSpread,
+            description: PUT_BACK_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_put: Position::default(),
+            short_put: Position::default(),
+        }
+    }
+}
+impl Default for CallBrokenWingButterfly {
+    fn default() -> Self {
+        CallBrokenWingButterfly {
+            name: "Call Broken Wing Butterfly".to_string(),
+            kind: StrategyType::CallBrokenWingButterfly,
+            description: CALL_BROKEN_WING_BUTTERFLY_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_call_low: Position::default(),
+            short_call: Position::default(),
+            long_call_high: Position::default(),
+        }
+    }
+}
+impl Default for PutBrokenWingButterfly {
+    fn default() -> Self {
+        PutBrokenWingButterfly {
+            name: "Put Broken Wing Butterfly".to_string(),
+            kind: StrategyType::PutBrokenWingButterfly,
+            description: PUT_BROKEN_WING_BUTTERFLY_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_put_low: Position::default(),
+            short_put: Position::default(),
+            long_put_high: Position::default(),
+        }
+    }
+}
+impl Default for JadeLizard {
+    fn default() -> Self {
+        JadeLizard {
+            name: "Jade Lizard".to_string(),
+            kind: StrategyType::JadeLizard,
+            description: JADE_LIZARD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            short_put: Position::default(),
+            short_call: Position::default(),
+            long_call: Position::default(),
+        }
+    }
+}
+impl Default for ReverseJadeLizard {
+    fn default() -> Self {
+        ReverseJadeLizard {
+            name: "Reverse Jade Lizard".to_string(),
+            kind: StrategyType::ReverseJadeLizard,
+            description: REVERSE_JADE_LIZARD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_put: Position::default(),
+            short_put: Position::default(),
+            short_call: Position::default(),
+        }
+    }
+}
+impl Default for Strap {
+    fn default() -> Self {
+        Strap {
+            name: "Strap".to_string(),
+            kind: StrategyType::Strap,
+            description: STRAP_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_call: Position::default(),
+            long_put: Position::default(),
+        }
+    }
+}
+impl Default for Strip {
+    fn default() -> Self {
+        Strip {
+            name: "Strip".to_string(),
+            kind: StrategyType::Strip,
+            description: STRIP_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_call: Position::default(),
+            long_put: Position::default(),
+        }
+    }
+}
+impl Default for CallCondor {
+    fn default() -> Self {
+        CallCondor {
+            name: "Call Condor".to_string(),
+            kind: StrategyType::CallCondor,
+            description: CALL_CONDOR_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_call_low: Position::default(),
+            short_call_low: Position::default(),
+            short_call_high: Position::default(),
+            long_call_high: Position::default(),
+        }
+    }
+}
+impl Default for PutCondor {
+    fn default() -> Self {
+        PutCondor {
+            name: "Put Condor".to_string(),
+            kind: StrategyType::PutCondor,
+            description: PUT_CONDOR_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_put_low: Position::default(),
+            short_put_low: Position::default(),
+            short_put_high: Position::default(),
+            long_put_high: Position::default(),
+        }
+    }
+}
+impl Default for BoxSpread {
+    fn default() -> Self {
+        BoxSpread {
+            name: "Box Spread".to_string(),
+            kind: StrategyType::BoxSpread,
+            description: BOX_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_call: Position::default(),
+            short_put: Position::default(),
+            short_call: Position::default(),
+            long_put: Position::default(),
+        }
+    }
+}
+impl Default for RiskReversal {
+    fn default() -> Self {
+        RiskReversal {
+            name: "Risk Reversal".to_string(),
+            kind: StrategyType::RiskReversal,
+            description: RISK_REVERSAL_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            short_put: Position::default(),
+            long_call: Position::default(),
+        }
+    }
+}
@@ -0,0 +1,842 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Delta hedging simulator.
+//!
+//! [`DeltaHedgeSimulator`] replays a book of option positions along price
+//! paths, from a [`Simulator`] or historical candles, and re-hedges it
+//! according to a [`HedgePolicy`] with the underlying or with at-the-money
+//! options from a chain regenerated at the current price. Every trade pays
+//! commissions and slippage per unit. The resulting [`HedgeReport`] shows the
+//! hedged P&L distribution, tracking error and turnover of the policy, so
+//! re-hedge bands can be compared on the same paths.
+//!
+//! Positions are valued with the default engine for their option type
+//! (Barone-Adesi–Whaley for American exercise) at each step's price, closer to
+//! expiry by the elapsed time, and at intrinsic value once expired. Implied
+//! volatilities are held constant and cash earns no interest.
+
+use crate::chains::OptionChain;
+use crate::chains::utils::OptionChainBuildParams;
+use crate::error::{PricingError, SimulationError};
+use crate::greeks::Greeks;
+use crate::model::Position;
+use crate::model::option::Options;
+use crate::model::types::{OptionStyle, Side};
+use crate::risk::revaluation::{aged, option_value};
+use crate::simulation::simulator::Simulator;
+use crate::utils::OhlcvCandle;
+use chrono::NaiveTime;
+use positive::Positive;
+use positive::constants::DAYS_IN_A_YEAR;
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Rule deciding when, and to what delta, the book is re-hedged.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HedgePolicy {
+    /// Re-hedge to delta neutral every `steps` steps.
+    FixedInterval {
+        /// Number of steps between re-hedges.
+        steps: usize,
+    },
+    /// Re-hedge to delta neutral when the net delta leaves `±band`.
+    DeltaBand {
+        /// Tolerated net delta, in units of the underlying.
+        band: Positive,
+    },
+    /// Whalley–Wilmott no-transaction band
+    /// `H = (3·k·Γ² / (2·λ))^(1/3)`, with `k` the cost per unit traded and
+    /// `λ` the risk aversion. The net delta is brought back to the nearest
+    /// band edge rather than to zero.
+    WhalleyWilmott {
+        /// Risk aversion `λ`; larger values give narrower bands.
+        risk_aversion: Positive,
+    },
+    /// Re-hedge to delta neutral when the net delta leaves
+    /// `±multiplier·|Γ|·S·σ·√Δt`, i.e. `multiplier` standard deviations of
+    /// the delta drift over one step.
+    GammaScaled {
+        /// Number of one-step standard deviations tolerated.
+        multiplier: Positive,
+    },
+}
+
+impl HedgePolicy {
+    /// Net delta the book is re-hedged to, or `None` when no re-hedge is due.
+    fn target(
+        &self,
+        net_delta: Decimal,
+        steps_since_hedge: Option<usize>,
+        band: impl Fn() -> Decimal,
+    ) -> Option<Decimal> {
+        match self {
+            HedgePolicy::FixedInterval { steps } => match steps_since_hedge {
+                Some(elapsed) if elapsed < *steps => None,
+                _ => Some(Decimal::ZERO),
+            },
+            HedgePolicy::WhalleyWilmott { .. } => {
+                let band = band();
+                (net_delta.abs() > band).then(|| {
+                    band * if net_delta.is_sign_negative() {
+                        -Decimal::ONE
+                    } else {
+                        Decimal::ONE
+                    }
+                })
+            }
+            HedgePolicy::DeltaBand { .. } | HedgePolicy::GammaScaled { .. } => {
+                (net_delta.abs() > band()).then_some(Decimal::ZERO)
+            }
+        }
+    }
+}
+
+/// Instrument used to neutralise the book's delta.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum HedgeInstrument {
+    /// Shares of the underlying.
+    #[default]
+    Underlying,
+    /// At-the-money option of `style`, bought or sold at the quotes of a
+    /// chain regenerated from `chain` at the current price. The previous
+    /// hedge option is closed at model value on every re-hedge.
+    AtmOption {
+        /// Parameters of the regenerated chain.
+        chain: Box<OptionChainBuildParams>,
+        /// Style of the hedge option.
+        style: OptionStyle,
+    },
+}
+
+/// Costs charged per unit (share or option) traded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct HedgeCosts {
+    /// Commission per unit traded.
+    pub commission_per_unit: Positive,
+    /// Slippage per unit traded.
+    pub slippage_per_unit: Positive,
+}
+
+impl HedgeCosts {
+    /// Creates a cost model.
+    #[must_use]
+    pub fn new(commission_per_unit: Positive, slippage_per_unit: Positive) -> Self {
+        HedgeCosts {
+            commission_per_unit,
+            slippage_per_unit,
+        }
+    }
+
+    fn per_unit(&self) -> Decimal {
+        (self.commission_per_unit + self.slippage_per_unit).to_dec()
+    }
+}
+
+/// Outcome of hedging the book along one path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HedgePathResult {
+    /// Hedged P&L: book plus hedge P&L, net of costs.
+    pub pnl: Decimal,
+    /// P&L of the unhedged book.
+    pub book_pnl: Decimal,
+    /// P&L of the hedge trades before costs.
+    pub hedge_pnl: Decimal,
+    /// Commissions and slippage paid.
+    pub costs: Decimal,
+    /// Standard deviation of the step-by-step hedged P&L.
+    pub tracking_error: Decimal,
+    /// Traded value, summed over all hedge trades.
+    pub turnover: Decimal,
+    /// Units traded, summed over all hedge trades.
+    pub traded_units: Decimal,
+    /// Number of re-hedges, including the initial hedge.
+    pub rebalances: usize,
+}
+
+/// Hedging outcomes of one policy over many paths.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HedgeReport {
+    /// Policy that produced the report.
+    pub policy: HedgePolicy,
+    /// Outcome of every path, in input order.
+    pub paths: Vec<HedgePathResult>,
+    /// Hedged P&L of every path, sorted ascending.
+    pub pnl_distribution: Vec<Decimal>,
+    /// Mean hedged P&L.
+    pub mean_pnl: Decimal,
+    /// Standard deviation of the hedged P&L across paths.
+    pub pnl_std_dev: Decimal,
+    /// Mean tracking error.
+    pub mean_tracking_error: Decimal,
+    /// Mean turnover.
+    pub mean_turnover: Decimal,
+    /// Mean costs.
+    pub mean_costs: Decimal,
+    /// Mean number of re-hedges.
+    pub mean_rebalances: Decimal,
+}
+
+impl HedgeReport {
+    fn new(policy: HedgePolicy, paths: Vec<HedgePathResult>) -> Self {
+        let mut pnl_distribution: Vec<Decimal> = paths.iter().map(|path| path.pnl).collect();
+        pnl_distribution.sort();
+        let mean_of = |field: fn(&HedgePathResult) -> Decimal| {
+            mean(&paths.iter().map(field).collect::<Vec<Decimal>>())
+        };
+        HedgeReport {
+            policy,
+            mean_pnl: mean(&pnl_distribution),
+            pnl_std_dev: std_dev(&pnl_distribution),
+            mean_tracking_error: mean_of(|path| path.tracking_error),
+            mean_turnover: mean_of(|path| path.turnover),
+            mean_costs: mean_of(|path| path.costs),
+            mean_rebalances: mean_of(|path| Decimal::from(path.rebalances)),
+            pnl_distribution,
+            paths,
+        }
+    }
+}
+
+/// Replays a hedging policy through time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltaHedgeSimulator {
+    /// When to re-hedge.
+    pub policy: HedgePolicy,
+    /// What to hedge with.
+    pub instrument: HedgeInstrument,
+    /// Costs per unit traded.
+    pub costs: HedgeCosts,
+}
+
+impl DeltaHedgeSimulator {
+    /// Creates a simulator hedging with the underlying at no cost.
+    #[must_use]
+    pub fn new(policy: HedgePolicy) -> Self {
+        DeltaHedgeSimulator {
+            policy,
+            instrument: HedgeInstrument::default(),
+            costs: HedgeCosts::default(),
+        }
+    }
+
+    /// Sets the hedge instrument.
+    #[must_use]
+    pub fn with_instrument(mut self, instrument: HedgeInstrument) -> Self {
+        self.instrument = instrument;
+        self
+    }
+
+    /// Sets the transaction costs.
+    #[must_use]
+    pub fn with_costs(mut self, costs: HedgeCosts) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    /// Hedges `book` along every random walk of `simulator`.
+    ///
+    /// The elapsed time of each step is taken from the walk's remaining days
+    /// to expiration, so the walk should end no later than the book.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] for an empty book or a
+    /// walk shorter than two steps, and propagates pricing, Greeks and chain
+    /// errors.
+    pub fn run_simulator(
+        &self,
+        book: &[Position],
+        simulator: &Simulator<Positive, Positive>,
+    ) -> Result<HedgeReport, SimulationError> {
+        let paths = simulator
+            .get_random_walks()
+            .into_iter()
+            .map(|walk| {
+                let steps = walk.get_steps();
+                let start = match steps.first() {
+                    Some(step) => step.get_x_step().days_left()?,
+                    None => Positive::ZERO,
+                };
+                let mut prices = Vec::with_capacity(steps.len());
+                let mut elapsed = Vec::with_capacity(steps.len());
+                for step in steps {
+                    prices.push(step.get_positive_value()?);
+                    let days_left = step.get_x_step().days_left()?;
+                    elapsed.push(if start > days_left {
+                        start - days_left
+                    } else {
+                        Positive::ZERO
+                    });
+                }
+                self.run_path(book, &prices, &elapsed)
+            })
+            .collect::<Result<Vec<HedgePathResult>, SimulationError>>()?;
+        Ok(HedgeReport::new(self.policy, paths))
+    }
+
+    /// Hedges `book` along the closes of historical `candles`, in
+    /// chronological order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] for an empty book,
+    /// fewer than two candles, or a candle time not in `HH:MM:SS` or `HH:MM`
+    /// format, and propagates pricing, Greeks and chain errors.
+    pub fn run_candles(
+        &self,
+        book: &[Position],
+        candles: &[OhlcvCandle],
+    ) -> Result<HedgePathResult, SimulationError> {
+        let timestamps = candles
+            .iter()
+            .map(|candle| {
+                let time = NaiveTime::parse_from_str(&candle.time, "%H:%M:%S")
+                    .or_else(|_| NaiveTime::parse_from_str(&candle.time, "%H:%M"))
+                    .map_err(|e| {
+                        SimulationError::invalid_parameters(&format!(
+                            "invalid candle time '{}': {e}",
+                            candle.time
+                        ))
+                    })?;
+                Ok(candle.date.and_time(time))
+            })
+            .collect::<Result<Vec<_>, SimulationError>>()?;
+        let start = timestamps.first().copied().unwrap_or_default();
+        let elapsed = timestamps
+            .iter()
+            .map(|timestamp| {
+                let days = Decimal::from((*timestamp - start).num_seconds()) / dec!(86400);
+                Ok(Positive::new_decimal(days)?)
+            })
+            .collect::<Result<Vec<Positive>, SimulationError>>()?;
+        let prices = candles
+            .iter()
+            .map(|candle| Ok(Positive::new_decimal(candle.close)?))
+            .collect::<Result<Vec<Positive>, SimulationError>>()?;
+        self.run_path(book, &prices, &elapsed)
+    }
+
+    /// Runs every policy on the same walks, keeping this simulator's
+    /// instrument and costs, to compare re-hedge bands.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error raised by [`Self::run_simulator`].
+    pub fn compare_policies(
+        &self,
+        book: &[Position],
+        simulator: &Simulator<Positive, Positive>,
+        policies: &[HedgePolicy],
+    ) -> Result<Vec<HedgeReport>, SimulationError> {
+        policies
+            .iter()
+            .map(|policy| {
+                DeltaHedgeSimulator {
+                    policy: *policy,
+                    ..self.clone()
+                }
+                .run_simulator(book, simulator)
+            })
+            .collect()
+    }
+
+    /// Hedges `book` along `prices`, where `elapsed[i]` is the number of
+    /// days between the first price and `prices[i]`.
+    ///
+    /// The book is hedged on every step but the last, where it is only
+    /// marked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::InvalidParameters`] for an empty book, fewer
+    /// than two prices, or mismatched lengths, and propagates pricing, Greeks
+    /// and chain errors.
+    pub fn run_path(
+        &self,
+        book: &[Position],
+        prices: &[Positive],
+        elapsed: &[Positive],
+    ) -> Result<HedgePathResult, SimulationError> {
+        if book.is_empty() {
+            return Err(SimulationError::invalid_parameters(
+                "delta hedging needs at least one position",
+            ));
+        }
+        if prices.len() < 2 || prices.len() != elapsed.len() {
+            return Err(SimulationError::invalid_parameters(
+                "delta hedging needs at least two prices with one elapsed time each",
+            ));
+        }
+        let volatility = mean(
+            &book
+                .iter()
+                .map(|position| position.option.implied_volatility.to_dec())
+                .collect::<Vec<Decimal>>(),
+        );
+
+        let mut hedge = HedgeBook::default();
+        let mut base_value = Decimal::ZERO;
+        let mut book_value = Decimal::ZERO;
+        let mut previous_pnl = Decimal::ZERO;
+        let mut increments = Vec::with_capacity(prices.len());
+        let mut steps_since_hedge: Option<usize> = None;
+        let mut previous_elapsed = Positive::ZERO;
+
+        for (step, (price, elapsed)) in prices.iter().zip(elapsed).enumerate() {
+            let options = book
+                .iter()
+                .map(|position| aged(&position.option, *price, *elapsed))
+                .collect::<Result<Vec<Options>, PricingError>>()?;
+            book_value = options
+                .iter()
+                .map(option_value)
+                .sum::<Result<Decimal, PricingError>>()?;
+            if step == 0 {
+                base_value = book_value;
+            }
+
+            if step + 1 < prices.len() {
+                let (book_delta, book_gamma) = delta_gamma(&options)?;
+                let net_delta = book_delta + hedge.delta(*price, *elapsed)?;
+                let step_years = (*elapsed - previous_elapsed).to_dec() / DAYS_IN_A_YEAR.to_dec();
+                let band = || match self.policy {
+                    HedgePolicy::DeltaBand { band } => band.to_dec(),
+                    HedgePolicy::WhalleyWilmott { risk_aversion } => {
+                        let width = dec!(1.5) * self.costs.per_unit() * book_gamma * book_gamma
+                            / risk_aversion.to_dec();
+                        if width.is_zero() {
+                            Decimal::ZERO
+                        } else {
+                            width.powd(Decimal::ONE / dec!(3))
+                        }
+                    }
+                    HedgePolicy::GammaScaled { multiplier } => {
+                        multiplier.to_dec()
+                            * book_gamma.abs()
+                            * price.to_dec()
+                            * volatility
+                            * step_years.sqrt().unwrap_or(Decimal::ZERO)
+                    }
+                    HedgePolicy::FixedInterval { .. } => Decimal::ZERO,
+                };
+                if let Some(target) = self.policy.target(net_delta, steps_since_hedge, band) {
+                    self.rehedge(&mut hedge, target - book_delta, *price, *elapsed)?;
+                    steps_since_hedge = Some(0);
+                }
+            }
+
+            let pnl = book_value - base_value + hedge.value(*price, *elapsed)? - hedge.costs;
+            if step > 0 {
+                increments.push(pnl - previous_pnl);
+            }
+            previous_pnl = pnl;
+            previous_elapsed = *elapsed;
+            steps_since_hedge = steps_since_hedge.map(|steps| steps + 1);
+        }
+
+        let book_pnl = book_value - base_value;
+        Ok(HedgePathResult {
+            pnl: previous_pnl,
+            book_pnl,
+            hedge_pnl: previous_pnl - book_pnl + hedge.costs,
+            costs: hedge.costs,
+            tracking_error: std_dev(&increments),
+            turnover: hedge.turnover,
+            traded_units: hedge.traded_units,
+            rebalances: hedge.rebalances,
+        })
+    }
+
+    /// Moves the hedge delta to `hedge_delta`.
+    fn rehedge(
+        &self,
+        hedge: &mut HedgeBook,
+        hedge_delta: Decimal,
+        price: Positive,
+        elapsed: Positive,
+    ) -> Result<(), SimulationError> {
+        hedge.rebalances += 1;
+        match &self.instrument {
+            HedgeInstrument::Underlying => {
+                let shares = hedge_delta - hedge.shares;
+                hedge.shares = hedge_delta;
+                hedge.trade(shares, price.to_dec(), &self.costs);
+            }
+            HedgeInstrument::AtmOption { chain, style } => {
+                if let Some((option, entered)) = hedge.option.take() {
+                    let value = option_value(&aged(&option, price, elapsed - entered)?)?;
+                    let units = option.quantity.to_dec();
+                    hedge.cash += value;
+                    hedge.traded_units += units;
+                    hedge.turnover += value.abs();
+                    hedge.costs += units * self.costs.per_unit();
+                }
+                let mut params = chain.as_ref().clone();
+                params.set_underlying_price(Some(Box::new(price)));
+                let chain = OptionChain::build_chain(&params)?;
+                let atm = chain.atm_option_data()?;
+                let unit = atm.get_position(Side::Long, *style, None, None, None)?;
+                let mut unit_option = unit.option.clone();
+                unit_option.quantity = Positive::ONE;
+                let unit_delta = unit_option.delta().map_err(PricingError::from)?;
+                if unit_delta.is_zero() {
+                    return Ok(());
+                }
+                let quantity = hedge_delta / unit_delta;
+                if quantity.is_zero() {
+                    return Ok(());
+                }
+                let side = if quantity > Decimal::ZERO {
+                    Side::Long
+                } else {
+                    Side::Short
+                };
+                let position = atm.get_position(side, *style, None, None, None)?;
+                let mut option = position.option;
+                option.quantity = Positive::new_decimal(quantity.abs())?;
+                hedge.trade(quantity, position.premium.to_dec(), &self.costs);
+                hedge.option = Some((option, elapsed));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Hedge positions and cash accumulated along one path.
+#[derive(Default)]
+struct HedgeBook {
+    shares: Decimal,
+    /// Hedge option and the elapsed days when it was opened.
+    option: Option<(Options, Positive)>,
+    cash: Decimal,
+    costs: Decimal,
+    turnover: Decimal,
+    traded_units: Decimal,
+    rebalances: usize,
+}
+
+impl HedgeBook {
+    /// Buys `quantity` units (sells when negative) at `price`.
+    fn trade(&mut self, quantity: Decimal, price: Decimal, costs: &HedgeCosts) {
+        let units = quantity.abs();
+        self.cash -= quantity * price;
+        self.costs += units * costs.per_unit();
+        self.turnover += units * price;
+        self.traded_units += units;
+    }
+
+    fn delta(&self, price: Positive, elapsed: Positive) -> Result<Decimal, SimulationError> {
+        let option_delta = match &self.option {
+            Some((option, entered)) => delta_gamma(&[aged(option, price, elapsed - *entered)?])?.0,
+            None => Decimal::ZERO,
+        };
+        Ok(self.shares + option_delta)
+    }
+
+    /// Value of the hedge positions plus cash, before costs.
+    fn value(&self, price: Positive, elapsed: Positive) -> Result<Decimal, SimulationError> {
+        let option_value = match &self.option {
+            Some((option, entered)) => option_value(&aged(option, price, elapsed - *entered)?)?,
+            None => Decimal::ZERO,
+        };
+        Ok(self.shares * price.to_dec() + option_value + self.cash)
+    }
+}
+
+/// Delta and side-signed gamma of `options`, ignoring expired ones.
+fn delta_gamma(options: &[Options]) -> Result<(Decimal, Decimal), PricingError> {
+    let mut delta = Decimal::ZERO;
+    let mut gamma = Decimal::ZERO;
+    for option in options {
+        if option.expiration_date.get_days()? == Positive::ZERO {
+            continue;
+        }
+        delta += option.delta()?;
+        let option_gamma = option.gamma()?;
+        gamma += if option.is_short() {
+            -option_gamma
+        } else {
+            option_gamma
+        };
+    }
+    Ok((delta, gamma))
+}
+
+fn mean(values: &[Decimal]) -> Decimal {
+    if values.is_empty() {
+        return Decimal::ZERO;
+    }
+    values.iter().sum::<Decimal>() / Decimal::from(values.len())
+}
+
+fn std_dev(values: &[Decimal]) -> Decimal {
+    if values.len() < 2 {
+        return Decimal::ZERO;
+    }
+    let mean = mean(values);
+    let variance = values
+        .iter()
+        .map(|value| (*value - mean) * (*value - mean))
+        .sum::<Decimal>()
+        / Decimal::from(values.len());
+    variance.sqrt().unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests_hedging {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::OptionDataPriceParams;
+    use crate::model::types::OptionType;
+    use crate::pricing::{PricingEngine, price_option};
+    use crate::simulation::steps::Step;
+    use crate::simulation::walk_test_support::RampWalker;
+    use crate::simulation::{WalkParams, WalkType};
+    use crate::utils::TimeFrame;
+    use chrono::{NaiveDate, Utc};
+    use positive::{pos_or_panic, spos};
+
+    fn short_call() -> Position {
+        let option = Options::new(
+            OptionType::European,
+            Side::Short,
+            "TEST".to_string(),
+            Positive::HUNDRED,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            Positive::TEN,
+            Positive::HUNDRED,
+            dec!(0.05),
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            pos_or_panic!(2.5),
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    fn path(start: f64, step: f64, len: usize) -> (Vec<Positive>, Vec<Positive>) {
+        let prices = (0..len)
+            .map(|i| pos_or_panic!(start + step * i as f64))
+            .collect();
+        let elapsed = (0..len).map(|i| pos_or_panic!(i as f64)).collect();
+        (prices, elapsed)
+    }
+
+    #[test]
+    fn test_daily_hedge_reduces_book_pnl() {
+        let (prices, elapsed) = path(100.0, 0.5, 20);
+        let result = DeltaHedgeSimulator::new(HedgePolicy::FixedInterval { steps: 1 })
+            .run_path(&[short_call()], &prices, &elapsed)
+            .unwrap();
+        // The short call loses on a rally; the long share hedge offsets it.
+        assert!(result.book_pnl < dec!(-30));
+        assert!(result.hedge_pnl > Decimal::ZERO);
+        assert!(result.pnl.abs() < result.book_pnl.abs() / dec!(3));
+        assert_eq!(result.rebalances, 19);
+        assert_eq!(result.costs, Decimal::ZERO);
+        assert_eq!(result.pnl, result.book_pnl + result.hedge_pnl);
+    }
+
+    #[test]
+    fn test_bands_trade_less_and_pay_costs() {
+        let (prices, elapsed) = path(100.0, 0.5, 20);
+        let costs = HedgeCosts::new(pos_or_panic!(0.01), pos_or_panic!(0.04));
+        let daily = DeltaHedgeSimulator::new(HedgePolicy::FixedInterval { steps: 1 })
+            .with_costs(costs)
+            .run_path(&[short_call()], &prices, &elapsed)
+            .unwrap();
+        let banded = DeltaHedgeSimulator::new(HedgePolicy::DeltaBand {
+            band: pos_or_panic!(1.0),
+        })
+        .with_costs(costs)
+        .run_path(&[short_call()], &prices, &elapsed)
+        .unwrap();
+        assert!(banded.rebalances < daily.rebalances);
+        assert!(banded.traded_units < daily.traded_units);
+        assert_eq!(daily.costs, daily.traded_units * dec!(0.05));
+        assert_eq!(daily.pnl, daily.book_pnl + daily.hedge_pnl - daily.costs);
+        assert!(daily.turnover > daily.traded_units * dec!(99));
+    }
+
+    #[test]
+    fn test_whalley_wilmott_and_gamma_scaled_bands() {
+        let (prices, elapsed) = path(100.0, 0.5, 20);
+        let free = DeltaHedgeSimulator::new(HedgePolicy::WhalleyWilmott {
+            risk_aversion: Positive::ONE,
+        })
+        .run_path(&[short_call()], &prices, &elapsed)
+        .unwrap();
+        // Without costs the no-transaction band collapses to zero.
+        assert_eq!(free.rebalances, 19);
+
+        let costly = DeltaHedgeSimulator::new(HedgePolicy::WhalleyWilmott {
+            risk_aversion: pos_or_panic!(0.01),
+        })
+        .with_costs(HedgeCosts::new(Positive::ONE, Positive::ZERO))
+        .run_path(&[short_call()], &prices, &elapsed)
+        .unwrap();
+        // Trading back to the band edge, not to zero, trades fewer shares.
+        assert!(costly.traded_units < free.traded_units);
+
+        let scaled = DeltaHedgeSimulator::new(HedgePolicy::GammaScaled {
+            multiplier: pos_or_panic!(3.0),
+        })
+        .run_path(&[short_call()], &prices, &elapsed)
+        .unwrap();
+        assert!(scaled.rebalances > 0);
+        assert!(scaled.rebalances < free.rebalances);
+    }
+
+    #[test]
+    fn test_american_book_is_valued_with_early_exercise() {
+        let mut put = short_call();
+        put.option.option_type = OptionType::American;
+        put.option.option_style = OptionStyle::Put;
+        let (prices, elapsed) = path(100.0, -5.0, 5);
+        let result = DeltaHedgeSimulator::new(HedgePolicy::FixedInterval { steps: 1 })
+            .run_path(&[put.clone()], &prices, &elapsed)
+            .unwrap();
+
+        let book_pnl = |option: &Options| {
+            let value = |step: usize| {
+                let aged = aged(option, prices[step], elapsed[step]).unwrap();
+                price_option(&aged, &PricingEngine::for_option_type(&aged.option_type))
+                    .unwrap()
+                    .to_dec()
+                    * aged.quantity
+            };
+            value(0) - value(4)
+        };
+        assert!((result.book_pnl - book_pnl(&put.option)).abs() < dec!(1e-9));
+        // Valued as European, the put would end below its intrinsic value.
+        let mut european = put.option.clone();
+        european.option_type = OptionType::European;
+        assert!(result.book_pnl < book_pnl(&european) - dec!(0.1));
+    }
+
+    #[test]
+    fn test_hedge_with_atm_options() {
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(Positive::HUNDRED)),
+            Some(ExpirationDate::Days(pos_or_panic!(60.0))),
+            Some(dec!(0.05)),
+            spos!(0.0),
+            Some("TEST".to_string()),
+        );
+        let chain = OptionChainBuildParams::new(
+            "TEST".to_string(),
+            None,
+            5,
+            spos!(5.0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos_or_panic!(0.02),
+            2,
+            price_params,
+            pos_or_panic!(0.2),
+        );
+        let (prices, elapsed) = path(100.0, 0.5, 10);
+        let result = DeltaHedgeSimulator::new(HedgePolicy::FixedInterval { steps: 3 })
+            .with_instrument(HedgeInstrument::AtmOption {
+                chain: Box::new(chain),
+                style: OptionStyle::Call,
+            })
+            .run_path(&[short_call()], &prices, &elapsed)
+            .unwrap();
+        assert_eq!(result.rebalances, 3);
+        assert!(result.hedge_pnl > Decimal::ZERO);
+        assert!(result.pnl.abs() < result.book_pnl.abs());
+    }
+
+    #[test]
+    fn test_run_simulator_and_compare_policies() {
+        let walk = WalkParams {
+            size: 10,
+            init_step: Step::new(
+                Positive::ONE,
+                TimeFrame::Day,
+                ExpirationDate::Days(pos_or_panic!(30.0)),
+                Positive::HUNDRED,
+            ),
+            walk_type: WalkType::GeometricBrownian {
+                dt: pos_or_panic!(1.0 / 365.0),
+                drift: Decimal::ZERO,
+                volatility: pos_or_panic!(0.2),
+            },
+            walker: Box::new(RampWalker {
+                delta: Positive::ONE,
+            }),
+        };
+        let simulator = Simulator::new(
+            "hedge".to_string(),
+            3,
+            &walk,
+            crate::simulation::generator_positive,
+        )
+        .unwrap();
+        let reports = DeltaHedgeSimulator::new(HedgePolicy::FixedInterval { steps: 1 })
+            .compare_policies(
+                &[short_call()],
+                &simulator,
+                &[
+                    HedgePolicy::FixedInterval { steps: 1 },
+                    HedgePolicy::DeltaBand {
+                        band: pos_or_panic!(2.0),
+                    },
+                ],
+            )
+            .unwrap();
+        assert_eq!(reports.len(), 2);
+        let daily = &reports[0];
+        assert_eq!(daily.paths.len(), 3);
+        assert_eq!(daily.pnl_distribution.len(), 3);
+        // Identical deterministic walks give identical outcomes.
+        assert_eq!(daily.pnl_std_dev, Decimal::ZERO);
+        assert_eq!(daily.mean_rebalances, dec!(9));
+        assert!(reports[1].mean_rebalances < daily.mean_rebalances);
+    }
+
+    #[test]
+    fn test_run_candles_and_errors() {
+        let candles: Vec<OhlcvCandle> = (0..5)
+            .map(|day| {
+                let close = Decimal::from(100 + day);
+                OhlcvCandle {
+                    date: NaiveDate::from_ymd_opt(2026, 3, 2 + day).unwrap(),
+                    time: "16:00".to_string(),
+                    open: close,
+                    high: close,
+                    low: close,
+                    close,
+                    volume: 0,
+                }
+            })
+            .collect();
+        let simulator = DeltaHedgeSimulator::new(HedgePolicy::FixedInterval { steps: 1 });
+        let result = simulator.run_candles(&[short_call()], &candles).unwrap();
+        assert_eq!(result.rebalances, 4);
+
+        assert!(simulator.run_candles(&[], &candles).is_err());
+        assert!(
+            simulator
+                .run_candles(&[short_call()], &candles[..1])
+                .is_err()
+        );
+        let mut bad = candles.clone();
+        bad[1].time = "4pm".to_string();
+        assert!(simulator.run_candles(&[short_call()], &bad).is_err());
+    }
+}
@@ -29,6 +29,14 @@
 //! - **AdjustmentTarget**: Target Greeks for optimization.
 //! - **AdjustmentOptimizer**: Optimizer for finding best adjustment plans.
 //!
+//! ## Hedging Simulation
+//!
+//! - **DeltaHedgeSimulator**: Replays a re-hedging policy along simulated or
+//!   historical price paths, with transaction costs, and reports the hedged
+//!   P&L distribution, tracking error and turnover.
+//! - **HedgePolicy**: Fixed interval, delta band, Whalley–Wilmott band and
+//!   gamma-scaled band re-hedging rules.
+//!
 //! ## Overview
 //!
 //! Delta neutrality is a core concept in options trading, where traders aim to balance
@@ -43,11 +51,15 @@
 //! - Portfolio-level Greeks aggregation and optimization.
 //!
 pub mod adjustment;
+pub mod hedging;
 mod model;
 pub mod optimizer;
 pub mod portfolio;
 
 pub use adjustment::{AdjustmentAction, AdjustmentConfig, AdjustmentError, AdjustmentPlan};
+pub use hedging::{
+    DeltaHedgeSimulator, HedgeCosts, HedgeInstrument, HedgePathResult, HedgePolicy, HedgeReport,
+};
 pub use model::{
     DELTA_THRESHOLD, DeltaAdjustment, DeltaInfo, DeltaNeutralResponse, DeltaNeutrality,
     DeltaPositionInfo,
@@ -1619,6 +1619,9 @@ mod tests_generate_delta_adjustments {
             option_style,
             dividend_yield: pos_or_panic!(0.01),
             exotic_params: None,
+            yield_curve: None,
+            dividends: Default::default(),
+            day_count: Default::default(),
         }
     }
 
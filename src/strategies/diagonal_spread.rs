@@ -0,0 +1,887 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Diagonal Spread sells a near-term option and buys a longer-dated option of the same style at
+//! a different strike. It combines the time-decay edge of a calendar spread with the directional
+//! bias of a vertical spread: a call diagonal with the long strike below the short strike (the
+//! classic Poor Man's Covered Call) is bullish, and a put diagonal with the long strike above the
+//! short strike is bearish.
+//!
+//! The strategy has two components:
+//! 1. **Short near-term option**: Collects premium that decays quickly as the front expiry approaches.
+//! 2. **Long far-term option**: Carries the directional exposure and keeps most of its time value
+//!    when the front option expires.
+//!
+//! The profit and loss profile is measured at the front expiry: the short leg settles at intrinsic
+//! value and the long leg is valued with Black–Scholes at its own implied volatility and remaining
+//! time.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::horizon;
+use crate::{
+    ExpirationDate, Options,
+    chains::{StrategyLegs, chain::OptionChain},
+    error::{
+        GreeksError, OperationErrorKind, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, OptionType, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    series::OptionSeries,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use chrono::Utc;
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+use utoipa::ToSchema;
+
+pub(super) const DIAGONAL_SPREAD_DESCRIPTION: &str = "A Diagonal Spread sells a near-term option \
+    and buys a longer-dated option of the same style at a different strike. \
+    It earns the faster time decay of the front-month option while the strike offset \
+    gives the position a directional bias.";
+
+/// # DiagonalSpread
+///
+/// Represents a Diagonal Spread: a short near-term option and a long far-term option of the same
+/// style at different strikes.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::DiagonalSpread`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The prices at the front expiry at which the strategy neither makes nor loses money.
+/// * `near_leg`: The short near-term option.
+/// * `far_leg`: The long far-term option.
+///
+/// ## Risk and Reward
+/// The maximum profit is reached with the underlying near the short strike at the front expiry.
+/// The maximum loss is usually on the side of the strike offset away from the long strike, where
+/// the far leg loses its value, and is measured on the front-expiry profile.
+///
+/// ## Break-Even Points
+/// Up to two break-even points, found numerically on the front-expiry profile.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct DiagonalSpread {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a DiagonalSpread strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Front-expiry prices where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The near-term option that is sold
+    pub(super) near_leg: Position,
+    /// The far-term option that is bought
+    pub(super) far_leg: Position,
+}
+
+impl DiagonalSpread {
+    /// # Creates a new Diagonal Spread strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `near_strike`: Strike price of the short near-term option
+    /// * `far_strike`: Strike price of the long far-term option
+    /// * `option_style`: Style (call or put) shared by both legs
+    /// * `near_expiration`: Expiration date of the short near-term option
+    /// * `far_expiration`: Expiration date of the long far-term option
+    /// * `near_implied_volatility`: Implied volatility of the near-term option
+    /// * `far_implied_volatility`: Implied volatility of the far-term option
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts for both legs of the strategy
+    /// * `premium_near`: Premium received for the near-term option
+    /// * `premium_far`: Premium paid for the far-term option
+    /// * `open_fee_near`: Transaction fee for opening the near-term position
+    /// * `close_fee_near`: Transaction fee for closing the near-term position
+    /// * `open_fee_far`: Transaction fee for opening the far-term position
+    /// * `close_fee_far`: Transaction fee for closing the far-term position
+    ///
+    /// ## Returns
+    /// A fully initialized `DiagonalSpread` with its front-expiry break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the near expiration is not
+    /// strictly earlier than the far one or the strikes are equal, and propagates pricing errors from
+    /// the break-even calculation.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        near_strike: Positive,
+        far_strike: Positive,
+        option_style: OptionStyle,
+        near_expiration: ExpirationDate,
+        far_expiration: ExpirationDate,
+        near_implied_volatility: Positive,
+        far_implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_near: Positive,
+        premium_far: Positive,
+        open_fee_near: Positive,
+        close_fee_near: Positive,
+        open_fee_far: Positive,
+        close_fee_far: Positive,
+    ) -> Result<Self, StrategyError> {
+        let mut strategy = DiagonalSpread::default();
+
+        let near_option = Options::new(
+            OptionType::European,
+            Side::Short,
+            underlying_symbol.clone(),
+            near_strike,
+            near_expiration,
+            near_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let near_leg = Position::new(
+            near_option,
+            premium_near,
+            Utc::now(),
+            open_fee_near,
+            close_fee_near,
+            None,
+            None,
+        );
+        strategy.add_position(&near_leg)?;
+
+        let far_option = Options::new(
+            OptionType::European,
+            Side::Long,
+            underlying_symbol,
+            far_strike,
+            far_expiration,
+            far_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let far_leg = Position::new(
+            far_option,
+            premium_far,
+            Utc::now(),
+            open_fee_far,
+            close_fee_far,
+            None,
+            None,
+        );
+        strategy.add_position(&far_leg)?;
+
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Diagonal Spread new",
+                "near expiration must be earlier than far expiration and strikes must differ",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    /// Searches every pair of expirations in `series` (near before far) and
+    /// every pair of distinct strikes, one from each chain, for the diagonal
+    /// that scores best under `criteria`, and replaces `self` with it.
+    ///
+    /// The near leg is sold at the bid and the far leg bought at the ask of
+    /// their own chains, and each leg keeps the implied volatility quoted
+    /// for its expiry. The style, quantity, rate and fees of `self` are kept.
+    pub fn find_optimal_in_series(
+        &mut self,
+        series: &OptionSeries,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let mut best: Option<DiagonalSpread> = None;
+
+        for ((near_expiration, near_chain), (far_expiration, far_chain)) in
+            horizon::expiry_pairs(series)
+        {
+            let mut template = self.clone();
+            template.near_leg.option.expiration_date = *near_expiration;
+            template.far_leg.option.expiration_date = *far_expiration;
+
+            for near_quote in near_chain.options.iter() {
+                if !horizon::strike_in_side(near_chain, near_quote, &side) {
+                    continue;
+                }
+                for far_quote in far_chain.options.iter() {
+                    if far_quote.strike_price == near_quote.strike_price
+                        || !horizon::strike_in_side(far_chain, far_quote, &side)
+                    {
+                        continue;
+                    }
+                    let legs = StrategyLegs::TwoLegs {
+                        first: near_quote,
+                        second: far_quote,
+                    };
+                    let strategy = match template.create_strategy(near_chain, &legs) {
+                        Ok(s) => s,
+                        Err(e) => {
+                            debug!(error = %e, "skipping invalid diagonal combination");
+                            continue;
+                        }
+                    };
+                    if let Some(value) = horizon::score(&strategy, &criteria)
+                        && value > best_value
+                    {
+                        best_value = value;
+                        best = Some(strategy);
+                    }
+                }
+            }
+        }
+
+        if let Some(strategy) = best {
+            *self = strategy;
+        }
+    }
+}
+
+impl StrategyConstructor for DiagonalSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        let [first, second] = vec_positions else {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Diagonal Spread get_strategy".to_string(),
+                    reason: "Must have exactly 2 options".to_string(),
+                },
+            ));
+        };
+        let (near_leg, far_leg) = match (first.option.side, second.option.side) {
+            (Side::Short, Side::Long) => (first, second),
+            (Side::Long, Side::Short) => (second, first),
+            _ => {
+                return Err(StrategyError::OperationError(
+                    OperationErrorKind::InvalidParameters {
+                        operation: "Diagonal Spread get_strategy".to_string(),
+                        reason: "Diagonal Spread requires one short and one long option"
+                            .to_string(),
+                    },
+                ));
+            }
+        };
+        if near_leg.option.option_style != far_leg.option.option_style {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Diagonal Spread get_strategy".to_string(),
+                    reason: "Both options must share the same style".to_string(),
+                },
+            ));
+        }
+
+        let mut strategy = DiagonalSpread {
+            name: "Diagonal Spread".to_string(),
+            kind: StrategyType::DiagonalSpread,
+            description: DIAGONAL_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg: near_leg.clone(),
+            far_leg: far_leg.clone(),
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Diagonal Spread get_strategy".to_string(),
+                    reason:
+                        "The short option must expire before the long option at a different strike"
+                            .to_string(),
+                },
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for DiagonalSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = horizon::front_expiry_break_even_points(&self.get_positions()?)?;
+        Ok(())
+    }
+}
+
+impl Validable for DiagonalSpread {
+    fn validate(&self) -> bool {
+        let near = &self.near_leg.option;
+        let far = &self.far_leg.option;
+        let ordered = match (
+            near.expiration_date.get_days(),
+            far.expiration_date.get_days(),
+        ) {
+            (Ok(near_days), Ok(far_days)) => near_days < far_days,
+            _ => false,
+        };
+        self.near_leg.validate()
+            && self.far_leg.validate()
+            && near.side == Side::Short
+            && far.side == Side::Long
+            && near.option_style == far.option_style
+            && near.strike_price != far.strike_price
+            && ordered
+    }
+}
+
+impl Positionable for DiagonalSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match position.option.side {
+            Side::Short => self.near_leg = position.clone(),
+            Side::Long => self.far_leg = position.clone(),
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(vec![&self.near_leg, &self.far_leg])
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        let leg = match side {
+            Side::Short => &mut self.near_leg,
+            Side::Long => &mut self.far_leg,
+        };
+        if leg.option.option_style == *option_style && leg.option.strike_price == *strike {
+            Ok(vec![leg])
+        } else {
+            Err(PositionError::invalid_position_type(
+                *side,
+                "Strike not found in positions".to_string(),
+            ))
+        }
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for DiagonalSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for DiagonalSpread {
+    fn get_title(&self) -> String {
+        format!(
+            "{:?} Strategy: \n\t{}\n\t{}",
+            self.kind,
+            self.near_leg.get_title(),
+            self.far_leg.get_title()
+        )
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            })
+            .collect()
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.implied_volatility,
+                )
+            })
+            .collect()
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.quantity,
+                )
+            })
+            .collect()
+    }
+    fn one_option(&self) -> &Options {
+        self.near_leg.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.near_leg.one_option_mut()
+    }
+    /// Moves the front expiry to `expiration_date` and shifts the far leg by
+    /// the same amount, keeping the gap between the two expirations.
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        self.far_leg.option.expiration_date = horizon::rolled_expiration(
+            &self.far_leg.option.expiration_date,
+            &self.near_leg.option.expiration_date,
+            &expiration_date,
+        )?;
+        self.near_leg.option.expiration_date = expiration_date;
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.underlying_price = *price;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        Ok(())
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.implied_volatility = *volatility;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        Ok(())
+    }
+}
+
+impl Strategies for DiagonalSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        horizon::front_expiry_max_profit(&self.get_positions()?)
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        horizon::front_expiry_max_loss(&self.get_positions()?)
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        horizon::front_expiry_profit_area(&self.get_positions()?)
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        horizon::front_expiry_profit_ratio(&self.get_positions()?)
+    }
+}
+
+impl Optimizable for DiagonalSpread {
+    type Strategy = DiagonalSpread;
+
+    /// Searches pairs of distinct strikes of `option_chain` for the best
+    /// diagonal at the current near and far expirations.
+    ///
+    /// The chain is taken as the front month: the near leg is sold at its
+    /// bid, while the far leg is priced with Black–Scholes at the far
+    /// expiry and the chain's implied volatility for its strike. Use
+    /// [`DiagonalSpread::find_optimal_in_series`] to search quoted
+    /// expiries instead.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let mut best: Option<DiagonalSpread> = None;
+
+        let far_quotes: Vec<_> = option_chain
+            .options
+            .iter()
+            .filter(|quote| horizon::strike_in_side(option_chain, quote, &side))
+            .filter_map(|quote| {
+                horizon::repriced_quote(
+                    quote,
+                    &self.far_leg.option,
+                    option_chain.underlying_price,
+                    self.far_leg.option.expiration_date,
+                )
+                .inspect_err(|e| debug!(error = %e, "skipping strike without a far-expiry price"))
+                .ok()
+            })
+            .collect();
+
+        for near_quote in option_chain.options.iter() {
+            if !horizon::strike_in_side(option_chain, near_quote, &side) {
+                continue;
+            }
+            for far_quote in far_quotes.iter() {
+                if far_quote.strike_price == near_quote.strike_price {
+                    continue;
+                }
+                let legs = StrategyLegs::TwoLegs {
+                    first: near_quote,
+                    second: far_quote,
+                };
+                let strategy = match self.create_strategy(option_chain, &legs) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        debug!(error = %e, "skipping invalid diagonal combination");
+                        continue;
+                    }
+                };
+                if let Some(value) = horizon::score(&strategy, &criteria)
+                    && value > best_value
+                {
+                    best_value = value;
+                    best = Some(strategy);
+                }
+            }
+        }
+
+        if let Some(strategy) = best {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `DiagonalSpread` from a near quote (`first`) and a far
+    /// quote (`second`) at different strikes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs are not
+    /// `TwoLegs`, the strikes are equal, or the near bid or far ask is missing.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (near, far) = match legs {
+            StrategyLegs::TwoLegs { first, second } => (first, second),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "DiagonalSpread requires exactly two legs (TwoLegs)",
+                ));
+            }
+        };
+        if near.strike_price == far.strike_price {
+            return Err(StrategyError::invalid_parameters(
+                "create_strategy",
+                "diagonal legs must have different strikes",
+            ));
+        }
+        let style = self.near_leg.option.option_style;
+        let premium_near = horizon::leg_premium(near, style, Side::Short).ok_or_else(|| {
+            StrategyError::operation_not_supported("create_strategy", "missing bid for near leg")
+        })?;
+        let premium_far = horizon::leg_premium(far, style, Side::Long).ok_or_else(|| {
+            StrategyError::operation_not_supported("create_strategy", "missing ask for far leg")
+        })?;
+
+        DiagonalSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            near.strike_price,
+            far.strike_price,
+            style,
+            self.near_leg.option.expiration_date,
+            self.far_leg.option.expiration_date,
+            near.implied_volatility,
+            far.implied_volatility,
+            self.near_leg.option.risk_free_rate,
+            self.near_leg.option.dividend_yield,
+            self.near_leg.option.quantity,
+            premium_near,
+            premium_far,
+            self.near_leg.open_fee,
+            self.near_leg.close_fee,
+            self.far_leg.open_fee,
+            self.far_leg.close_fee,
+        )
+    }
+}
+
+impl Profit for DiagonalSpread {
+    /// Profit at the front expiry, with the far leg valued at its remaining
+    /// time value.
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        horizon::profit_at_front_expiry(&[&self.near_leg, &self.far_leg], price)
+    }
+}
+
+impl ProbabilityAnalysis for DiagonalSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        horizon::front_expiry_ranges(
+            &[&self.near_leg, &self.far_leg],
+            &self.break_even_points,
+            true,
+        )
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        horizon::front_expiry_ranges(
+            &[&self.near_leg, &self.far_leg],
+            &self.break_even_points,
+            false,
+        )
+    }
+}
+
+impl Greeks for DiagonalSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.near_leg.option, &self.far_leg.option])
+    }
+}
+
+impl DeltaNeutrality for DiagonalSpread {}
+
+impl PnLCalculator for DiagonalSpread {
+    /// P&L with `expiration_date` left on the near leg; the far leg is aged
+    /// by the same elapsed time.
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        horizon::aged_pnl(
+            &[&self.near_leg, &self.far_leg],
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    /// Realized P&L of closing the spread at the front expiry, with the far
+    /// leg sold back at its own implied volatility.
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        horizon::front_expiry_pnl(&[&self.near_leg, &self.far_leg], underlying_price)
+    }
+}
+
+test_strategy_traits!(DiagonalSpread, test_diagonal_spread_implementations);
+
+#[cfg(test)]
+mod tests_diagonal_spread {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::series::OptionSeriesBuildParams;
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy(near_strike: f64, far_strike: f64) -> DiagonalSpread {
+        DiagonalSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            Positive::new(near_strike).unwrap(),
+            Positive::new(far_strike).unwrap(),
+            OptionStyle::Call,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(120.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            dec!(0.03),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.0),
+            pos_or_panic!(9.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diagonal_call_is_bullish() {
+        let strategy = create_strategy(105.0, 95.0);
+        assert!(strategy.validate());
+        let down = strategy.calculate_profit_at(&pos_or_panic!(85.0)).unwrap();
+        let at_short = strategy.calculate_profit_at(&pos_or_panic!(105.0)).unwrap();
+        assert!(down < Decimal::ZERO);
+        assert!(at_short > Decimal::ZERO);
+        assert!(strategy.delta().unwrap() > Decimal::ZERO);
+
+        let max_profit = strategy.get_max_profit().unwrap();
+        assert!((max_profit.to_dec() - at_short).abs() < dec!(0.2));
+    }
+
+    #[test]
+    fn test_diagonal_front_expiry_values() {
+        // Short 105 call for 30 days, long 95 call for 120 days: at the front
+        // expiry P&L(S) = C(S, 95, 90/365, 0.2, 3%) - 9.5 + 1.0 - max(S - 105, 0).
+        let strategy = create_strategy(105.0, 95.0);
+        for (price, expected) in [
+            (90.0, dec!(-6.605148)),
+            (105.0, dec!(2.901842)),
+            (115.0, dec!(2.288284)),
+        ] {
+            let profit = strategy
+                .calculate_profit_at(&Positive::new(price).unwrap())
+                .unwrap();
+            assert!(
+                (profit - expected).abs() < dec!(0.0001),
+                "{profit} vs {expected} at {price}"
+            );
+        }
+        // Above the short strike the long call's extra intrinsic value and
+        // carry keep the spread in profit, so there is one break-even.
+        assert_eq!(strategy.break_even_points.len(), 1);
+        assert!((strategy.break_even_points[0].to_dec() - dec!(101.5078)).abs() < dec!(0.01));
+    }
+
+    #[test]
+    fn test_diagonal_break_even_points_are_zero_crossings() {
+        let strategy = create_strategy(105.0, 95.0);
+        assert!(!strategy.break_even_points.is_empty());
+        for point in &strategy.break_even_points {
+            let pnl = strategy.calculate_profit_at(point).unwrap();
+            assert!(pnl.abs() < dec!(0.05), "P&L {pnl} at break-even {point}");
+        }
+    }
+
+    #[test]
+    fn test_diagonal_rejects_equal_strikes() {
+        let result = DiagonalSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(100.0),
+            pos_or_panic!(100.0),
+            OptionStyle::Call,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(120.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            dec!(0.03),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.0),
+            pos_or_panic!(9.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_diagonal_find_optimal_in_series_uses_distinct_strikes() {
+        let chain_params = OptionChainBuildParams::new(
+            "SPY".to_string(),
+            None,
+            4,
+            spos!(2.5),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(100.0))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.03)),
+                spos!(0.0),
+                Some("SPY".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        let params = OptionSeriesBuildParams::new(
+            chain_params,
+            vec![pos_or_panic!(30.0), pos_or_panic!(90.0)],
+        );
+        let series = OptionSeries::build_series(&params).unwrap();
+
+        let mut strategy = create_strategy(105.0, 95.0);
+        strategy.find_optimal_in_series(&series, FindOptimalSide::All, OptimizationCriteria::Area);
+        assert!(strategy.validate());
+        assert_ne!(
+            strategy.near_leg.option.strike_price,
+            strategy.far_leg.option.strike_price
+        );
+        assert!(strategy.get_profit_area().unwrap() > Decimal::ZERO);
+    }
+}
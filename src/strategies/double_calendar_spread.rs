@@ -0,0 +1,941 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Double Calendar Spread combines a put calendar at a lower strike with a call calendar at a
+//! higher strike, both sharing the same near and far expirations. Each calendar sells the
+//! near-term option and buys the far-term option, so the position is opened for a net debit and
+//! profits from time decay while the underlying stays between (or close to) the two strikes at
+//! the front expiry.
+//!
+//! The strategy has four components:
+//! 1. **Short near-term put** and **long far-term put** at the lower strike.
+//! 2. **Short near-term call** and **long far-term call** at the higher strike.
+//!
+//! Compared with a single calendar, the two strikes widen the profitable region around the spot at
+//! the cost of a larger debit. The profit and loss profile is measured at the front expiry, with
+//! both far legs valued with Black–Scholes at their own implied volatility and remaining time. Like
+//! the single calendar, the position is long vega.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::horizon;
+use crate::{
+    ExpirationDate, Options,
+    chains::{OptionData, StrategyLegs, chain::OptionChain},
+    error::{
+        GreeksError, OperationErrorKind, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, OptionType, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    series::OptionSeries,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use chrono::Utc;
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+use utoipa::ToSchema;
+
+pub(super) const DOUBLE_CALENDAR_SPREAD_DESCRIPTION: &str = "A Double Calendar Spread combines a put calendar \
+    at a lower strike with a call calendar at a higher strike, selling the near-term options and \
+    buying the far-term options. It profits from time decay while the underlying stays between \
+    the two strikes at the front expiry.";
+
+/// # DoubleCalendarSpread
+///
+/// Represents a Double Calendar Spread: a put calendar at a lower strike and a call calendar at a
+/// higher strike with common near and far expirations.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::DoubleCalendarSpread`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The prices at the front expiry at which the strategy neither makes nor loses money.
+/// * `near_put`: The short near-term put at the lower strike.
+/// * `far_put`: The long far-term put at the lower strike.
+/// * `near_call`: The short near-term call at the higher strike.
+/// * `far_call`: The long far-term call at the higher strike.
+///
+/// ## Risk and Reward
+/// The maximum loss is close to the net debit paid and occurs when the underlying moves far outside
+/// the strikes. The maximum profit is reached near one of the strikes at the front expiry and is
+/// measured on the front-expiry profile.
+///
+/// ## Break-Even Points
+/// There are usually two break-even points, below the put strike and above the call strike, found
+/// numerically on the front-expiry profile.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct DoubleCalendarSpread {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a DoubleCalendarSpread strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Front-expiry prices where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The near-term put that is sold
+    pub(super) near_put: Position,
+    /// The far-term put that is bought
+    pub(super) far_put: Position,
+    /// The near-term call that is sold
+    pub(super) near_call: Position,
+    /// The far-term call that is bought
+    pub(super) far_call: Position,
+}
+
+impl DoubleCalendarSpread {
+    /// # Creates a new Double Calendar Spread strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `put_strike`: Strike price of the put calendar
+    /// * `call_strike`: Strike price of the call calendar
+    /// * `near_expiration`: Expiration date of the short near-term options
+    /// * `far_expiration`: Expiration date of the long far-term options
+    /// * `near_implied_volatility`: Implied volatility of the near-term options
+    /// * `far_implied_volatility`: Implied volatility of the far-term options
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts for every leg of the strategy
+    /// * `premium_near_put`: Premium received for the near-term put
+    /// * `premium_far_put`: Premium paid for the far-term put
+    /// * `premium_near_call`: Premium received for the near-term call
+    /// * `premium_far_call`: Premium paid for the far-term call
+    /// * `open_fee`: Transaction fee for opening each position
+    /// * `close_fee`: Transaction fee for closing each position
+    ///
+    /// ## Returns
+    /// A fully initialized `DoubleCalendarSpread` with its front-expiry break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the near expiration is not
+    /// strictly earlier than the far one or the put strike is above the call
+    /// strike, and propagates pricing errors from the break-even calculation.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        put_strike: Positive,
+        call_strike: Positive,
+        near_expiration: ExpirationDate,
+        far_expiration: ExpirationDate,
+        near_implied_volatility: Positive,
+        far_implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_near_put: Positive,
+        premium_far_put: Positive,
+        premium_near_call: Positive,
+        premium_far_call: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let mut strategy = DoubleCalendarSpread::default();
+
+        let legs = [
+            (
+                OptionStyle::Put,
+                Side::Short,
+                put_strike,
+                near_expiration,
+                near_implied_volatility,
+                premium_near_put,
+            ),
+            (
+                OptionStyle::Put,
+                Side::Long,
+                put_strike,
+                far_expiration,
+                far_implied_volatility,
+                premium_far_put,
+            ),
+            (
+                OptionStyle::Call,
+                Side::Short,
+                call_strike,
+                near_expiration,
+                near_implied_volatility,
+                premium_near_call,
+            ),
+            (
+                OptionStyle::Call,
+                Side::Long,
+                call_strike,
+                far_expiration,
+                far_implied_volatility,
+                premium_far_call,
+            ),
+        ];
+        for (option_style, side, strike, expiration, implied_volatility, premium) in legs {
+            let option = Options::new(
+                OptionType::European,
+                side,
+                underlying_symbol.clone(),
+                strike,
+                expiration,
+                implied_volatility,
+                quantity,
+                underlying_price,
+                risk_free_rate,
+                option_style,
+                dividend_yield,
+                None,
+            );
+            let position =
+                Position::new(option, premium, Utc::now(), open_fee, close_fee, None, None);
+            strategy.add_position(&position)?;
+        }
+
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Double Calendar Spread new",
+                "near expiration must be earlier than far expiration and the put strike must not exceed the call strike",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    /// Searches every pair of expirations in `series` (near before far) and
+    /// every put/call strike pair quoted in both chains for the double
+    /// calendar that scores best under `criteria`, and replaces `self` with
+    /// it.
+    ///
+    /// Near legs are sold at the bid and far legs bought at the ask of their
+    /// own chains, and each leg keeps the implied volatility quoted for its
+    /// expiry. The quantity, rate and fees of `self` are kept.
+    pub fn find_optimal_in_series(
+        &mut self,
+        series: &OptionSeries,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let mut best: Option<DoubleCalendarSpread> = None;
+
+        for ((near_expiration, near_chain), (far_expiration, far_chain)) in
+            horizon::expiry_pairs(series)
+        {
+            let mut template = self.clone();
+            for leg in [&mut template.near_put, &mut template.near_call] {
+                leg.option.expiration_date = *near_expiration;
+            }
+            for leg in [&mut template.far_put, &mut template.far_call] {
+                leg.option.expiration_date = *far_expiration;
+            }
+
+            let pairs: Vec<(&OptionData, &OptionData)> = near_chain
+                .options
+                .iter()
+                .filter_map(|near| {
+                    horizon::quote_at(far_chain, near.strike_price).map(|far| (near, far))
+                })
+                .collect();
+            if let Some((strategy, value)) =
+                template.best_strike_pair(near_chain, &pairs, &side, &criteria)
+                && value > best_value
+            {
+                best_value = value;
+                best = Some(strategy);
+            }
+        }
+
+        if let Some(strategy) = best {
+            *self = strategy;
+        }
+    }
+
+    /// Best-scoring double calendar over `(near, far)` quote pairs, taking a
+    /// put pair at a lower strike than the call pair.
+    fn best_strike_pair(
+        &self,
+        chain: &OptionChain,
+        pairs: &[(&OptionData, &OptionData)],
+        side: &FindOptimalSide,
+        criteria: &OptimizationCriteria,
+    ) -> Option<(DoubleCalendarSpread, Decimal)> {
+        let (put_side, call_side) = match side {
+            FindOptimalSide::Center => (FindOptimalSide::Lower, FindOptimalSide::Upper),
+            other => (*other, *other),
+        };
+        let mut best: Option<(DoubleCalendarSpread, Decimal)> = None;
+
+        for (near_put, far_put) in pairs {
+            if !horizon::strike_in_side(chain, near_put, &put_side) {
+                continue;
+            }
+            for (near_call, far_call) in pairs {
+                if near_call.strike_price <= near_put.strike_price
+                    || !horizon::strike_in_side(chain, near_call, &call_side)
+                {
+                    continue;
+                }
+                let legs = StrategyLegs::FourLegs {
+                    first: near_put,
+                    second: far_put,
+                    third: near_call,
+                    fourth: far_call,
+                };
+                let strategy = match self.create_strategy(chain, &legs) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        debug!(error = %e, "skipping invalid double calendar combination");
+                        continue;
+                    }
+                };
+                if let Some(value) = horizon::score(&strategy, criteria)
+                    && best
+                        .as_ref()
+                        .is_none_or(|(_, best_value)| value > *best_value)
+                {
+                    best = Some((strategy, value));
+                }
+            }
+        }
+        best
+    }
+
+    fn legs(&self) -> [&Position; 4] {
+        [
+            &self.near_put,
+            &self.far_put,
+            &self.near_call,
+            &self.far_call,
+        ]
+    }
+}
+
+impl StrategyConstructor for DoubleCalendarSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 4 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Double Calendar Spread get_strategy".to_string(),
+                    reason: "Must have exactly 4 options".to_string(),
+                },
+            ));
+        }
+
+        let mut strategy = DoubleCalendarSpread::default();
+        let mut seen = HashSet::new();
+        for position in vec_positions {
+            if !seen.insert((position.option.option_style, position.option.side)) {
+                return Err(StrategyError::OperationError(
+                    OperationErrorKind::InvalidParameters {
+                        operation: "Double Calendar Spread get_strategy".to_string(),
+                        reason: "Requires one short and one long option of each style".to_string(),
+                    },
+                ));
+            }
+            strategy.add_position(position)?;
+        }
+
+        if !strategy.validate() {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Double Calendar Spread get_strategy".to_string(),
+                    reason:
+                        "Short options must share the near expiry, long options the far expiry, \
+                        and the put strike must not exceed the call strike"
+                            .to_string(),
+                },
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for DoubleCalendarSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = horizon::front_expiry_break_even_points(&self.legs())?;
+        Ok(())
+    }
+}
+
+impl Validable for DoubleCalendarSpread {
+    fn validate(&self) -> bool {
+        let days = |position: &Position| position.option.expiration_date.get_days().ok();
+        let expiries_valid = match (
+            days(&self.near_put),
+            days(&self.near_call),
+            days(&self.far_put),
+            days(&self.far_call),
+        ) {
+            (Some(near_put), Some(near_call), Some(far_put), Some(far_call)) => {
+                near_put == near_call && far_put == far_call && near_put < far_put
+            }
+            _ => false,
+        };
+        let put_strike = self.near_put.option.strike_price;
+        let call_strike = self.near_call.option.strike_price;
+        self.legs().iter().all(|leg| leg.validate())
+            && self.near_put.option.option_style == OptionStyle::Put
+            && self.near_call.option.option_style == OptionStyle::Call
+            && self.far_put.option.strike_price == put_strike
+            && self.far_call.option.strike_price == call_strike
+            && put_strike <= call_strike
+            && expiries_valid
+    }
+}
+
+impl Positionable for DoubleCalendarSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Put, Side::Short) => self.near_put = position.clone(),
+            (OptionStyle::Put, Side::Long) => self.far_put = position.clone(),
+            (OptionStyle::Call, Side::Short) => self.near_call = position.clone(),
+            (OptionStyle::Call, Side::Long) => self.far_call = position.clone(),
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(self.legs().to_vec())
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        let leg = match (option_style, side) {
+            (OptionStyle::Put, Side::Short) => &mut self.near_put,
+            (OptionStyle::Put, Side::Long) => &mut self.far_put,
+            (OptionStyle::Call, Side::Short) => &mut self.near_call,
+            (OptionStyle::Call, Side::Long) => &mut self.far_call,
+        };
+        if leg.option.strike_price == *strike {
+            Ok(vec![leg])
+        } else {
+            Err(PositionError::invalid_position_type(
+                *side,
+                "Strike not found in positions".to_string(),
+            ))
+        }
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for DoubleCalendarSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for DoubleCalendarSpread {
+    fn get_title(&self) -> String {
+        let leg_titles: Vec<String> = self.legs().iter().map(|leg| leg.get_title()).collect();
+        format!("{:?} Strategy: \n\t{}", self.kind, leg_titles.join("\n\t"))
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        self.legs()
+            .into_iter()
+            .map(|leg| OptionBasicType {
+                option_style: &leg.option.option_style,
+                side: &leg.option.side,
+                strike_price: &leg.option.strike_price,
+                expiration_date: &leg.option.expiration_date,
+            })
+            .collect()
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        self.legs()
+            .into_iter()
+            .map(|leg| {
+                (
+                    OptionBasicType {
+                        option_style: &leg.option.option_style,
+                        side: &leg.option.side,
+                        strike_price: &leg.option.strike_price,
+                        expiration_date: &leg.option.expiration_date,
+                    },
+                    &leg.option.implied_volatility,
+                )
+            })
+            .collect()
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        self.legs()
+            .into_iter()
+            .map(|leg| {
+                (
+                    OptionBasicType {
+                        option_style: &leg.option.option_style,
+                        side: &leg.option.side,
+                        strike_price: &leg.option.strike_price,
+                        expiration_date: &leg.option.expiration_date,
+                    },
+                    &leg.option.quantity,
+                )
+            })
+            .collect()
+    }
+    fn one_option(&self) -> &Options {
+        self.near_call.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.near_call.one_option_mut()
+    }
+    /// Moves the front expiry to `expiration_date` and shifts the far legs
+    /// by the same amount, keeping the gap between the two expirations.
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        let front = self.near_call.option.expiration_date;
+        for leg in [&mut self.far_put, &mut self.far_call] {
+            leg.option.expiration_date =
+                horizon::rolled_expiration(&leg.option.expiration_date, &front, &expiration_date)?;
+        }
+        for leg in [&mut self.near_put, &mut self.near_call] {
+            leg.option.expiration_date = expiration_date;
+        }
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        for leg in [
+            &mut self.near_put,
+            &mut self.far_put,
+            &mut self.near_call,
+            &mut self.far_call,
+        ] {
+            leg.option.underlying_price = *price;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        Ok(())
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        for leg in [
+            &mut self.near_put,
+            &mut self.far_put,
+            &mut self.near_call,
+            &mut self.far_call,
+        ] {
+            leg.option.implied_volatility = *volatility;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        Ok(())
+    }
+}
+
+impl Strategies for DoubleCalendarSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        horizon::front_expiry_max_profit(&self.legs())
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        horizon::front_expiry_max_loss(&self.legs())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        horizon::front_expiry_profit_area(&self.legs())
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        horizon::front_expiry_profit_ratio(&self.legs())
+    }
+}
+
+impl Optimizable for DoubleCalendarSpread {
+    type Strategy = DoubleCalendarSpread;
+
+    /// Searches put/call strike pairs of `option_chain` for the best double
+    /// calendar at the current near and far expirations.
+    ///
+    /// The chain is taken as the front month: near legs are sold at the bid,
+    /// while far legs are priced with Black–Scholes at the far expiry and
+    /// the chain's implied volatility for each strike. Use
+    /// [`DoubleCalendarSpread::find_optimal_in_series`] to search quoted
+    /// expiries instead.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let far_expiration = self.far_call.option.expiration_date;
+        let mut far_quotes = Vec::new();
+        for quote in option_chain.options.iter() {
+            let repriced = horizon::repriced_quote(
+                quote,
+                &self.far_put.option,
+                option_chain.underlying_price,
+                far_expiration,
+            )
+            .and_then(|put| {
+                horizon::repriced_quote(
+                    &put,
+                    &self.far_call.option,
+                    option_chain.underlying_price,
+                    far_expiration,
+                )
+            });
+            match repriced {
+                Ok(far) => far_quotes.push((quote, far)),
+                Err(e) => debug!(error = %e, "skipping strike without a far-expiry price"),
+            }
+        }
+        let pairs: Vec<(&OptionData, &OptionData)> =
+            far_quotes.iter().map(|(near, far)| (*near, far)).collect();
+
+        if let Some((strategy, _)) = self.best_strike_pair(option_chain, &pairs, &side, &criteria) {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `DoubleCalendarSpread` from near and far put quotes
+    /// (`first`, `second`) and near and far call quotes (`third`, `fourth`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs are not
+    /// `FourLegs`, a calendar's strikes differ, or a required bid or ask is
+    /// missing.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (near_put, far_put, near_call, far_call) = match legs {
+            StrategyLegs::FourLegs {
+                first,
+                second,
+                third,
+                fourth,
+            } => (first, second, third, fourth),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "DoubleCalendarSpread requires exactly four legs (FourLegs)",
+                ));
+            }
+        };
+        if near_put.strike_price != far_put.strike_price
+            || near_call.strike_price != far_call.strike_price
+        {
+            return Err(StrategyError::invalid_parameters(
+                "create_strategy",
+                "each calendar's legs must share the same strike",
+            ));
+        }
+        let premium = |quote: &OptionData, style: OptionStyle, side: Side| {
+            horizon::leg_premium(quote, style, side).ok_or_else(|| {
+                StrategyError::operation_not_supported(
+                    "create_strategy",
+                    &format!("missing {side:?} {style:?} quote at {}", quote.strike_price),
+                )
+            })
+        };
+
+        DoubleCalendarSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            near_put.strike_price,
+            near_call.strike_price,
+            self.near_call.option.expiration_date,
+            self.far_call.option.expiration_date,
+            near_call.implied_volatility,
+            far_call.implied_volatility,
+            self.near_call.option.risk_free_rate,
+            self.near_call.option.dividend_yield,
+            self.near_call.option.quantity,
+            premium(near_put, OptionStyle::Put, Side::Short)?,
+            premium(far_put, OptionStyle::Put, Side::Long)?,
+            premium(near_call, OptionStyle::Call, Side::Short)?,
+            premium(far_call, OptionStyle::Call, Side::Long)?,
+            self.near_call.open_fee,
+            self.near_call.close_fee,
+        )
+    }
+}
+
+impl Profit for DoubleCalendarSpread {
+    /// Profit at the front expiry, with the far legs valued at their
+    /// remaining time value.
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        horizon::profit_at_front_expiry(&self.legs(), price)
+    }
+}
+
+impl ProbabilityAnalysis for DoubleCalendarSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        horizon::front_expiry_ranges(&self.legs(), &self.break_even_points, true)
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        horizon::front_expiry_ranges(&self.legs(), &self.break_even_points, false)
+    }
+}
+
+impl Greeks for DoubleCalendarSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self.legs().into_iter().map(|leg| &leg.option).collect())
+    }
+}
+
+impl DeltaNeutrality for DoubleCalendarSpread {}
+
+impl PnLCalculator for DoubleCalendarSpread {
+    /// P&L with `expiration_date` left on the near legs; the far legs are
+    /// aged by the same elapsed time.
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        horizon::aged_pnl(
+            &self.legs(),
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    /// Realized P&L of closing the spread at the front expiry, with the far
+    /// legs sold back at their own implied volatility.
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        horizon::front_expiry_pnl(&self.legs(), underlying_price)
+    }
+}
+
+test_strategy_traits!(
+    DoubleCalendarSpread,
+    test_double_calendar_spread_implementations
+);
+
+#[cfg(test)]
+mod tests_double_calendar_spread {
+    use super::*;
+    use crate::strategies::LongCalendarSpread;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> DoubleCalendarSpread {
+        DoubleCalendarSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(90.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            dec!(0.03),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(0.8),
+            pos_or_panic!(2.0),
+            pos_or_panic!(0.9),
+            pos_or_panic!(2.3),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    fn calendar(style: OptionStyle, strike: f64, near: f64, far: f64) -> LongCalendarSpread {
+        LongCalendarSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            Positive::new(strike).unwrap(),
+            style,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(90.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            dec!(0.03),
+            Positive::ZERO,
+            Positive::ONE,
+            Positive::new(near).unwrap(),
+            Positive::new(far).unwrap(),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_double_calendar_equals_sum_of_calendars() {
+        let strategy = create_strategy();
+        let put_calendar = calendar(OptionStyle::Put, 95.0, 0.8, 2.0);
+        let call_calendar = calendar(OptionStyle::Call, 105.0, 0.9, 2.3);
+        for price in [80.0, 95.0, 100.0, 105.0, 125.0] {
+            let price = Positive::new(price).unwrap();
+            let expected = put_calendar.calculate_profit_at(&price).unwrap()
+                + call_calendar.calculate_profit_at(&price).unwrap();
+            let profit = strategy.calculate_profit_at(&price).unwrap();
+            assert!((profit - expected).abs() < dec!(1e-9));
+        }
+    }
+
+    #[test]
+    fn test_double_calendar_front_expiry_values() {
+        // Both far legs have 60 days left at 20% when the near legs expire:
+        // P&L(S) = P(S, 95) - 2.0 + 0.8 - max(95 - S, 0)
+        //        + C(S, 105) - 2.3 + 0.9 - max(S - 105, 0).
+        let strategy = create_strategy();
+        for (price, expected) in [
+            (90.0, dec!(-1.717154)),
+            (100.0, dec!(0.075329)),
+            (110.0, dec!(-0.599319)),
+        ] {
+            let profit = strategy
+                .calculate_profit_at(&Positive::new(price).unwrap())
+                .unwrap();
+            assert!(
+                (profit - expected).abs() < dec!(0.0001),
+                "{profit} vs {expected} at {price}"
+            );
+        }
+        let expected = [dec!(93.855), dec!(108.1311)];
+        for (point, expected) in strategy.break_even_points.iter().zip(expected) {
+            assert!((point.to_dec() - expected).abs() < dec!(0.01), "{point}");
+        }
+    }
+
+    #[test]
+    fn test_double_calendar_profits_between_strikes() {
+        let strategy = create_strategy();
+        assert!(strategy.validate());
+        assert_eq!(strategy.break_even_points.len(), 2);
+        assert!(strategy.break_even_points[0] < pos_or_panic!(95.0));
+        assert!(strategy.break_even_points[1] > pos_or_panic!(105.0));
+        assert!(strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap() > Decimal::ZERO);
+        assert_eq!(strategy.get_profit_ranges().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_double_calendar_get_strategy_round_trip() {
+        let strategy = create_strategy();
+        let positions: Vec<Position> = strategy.legs().into_iter().rev().cloned().collect();
+        let rebuilt = DoubleCalendarSpread::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.break_even_points, strategy.break_even_points);
+
+        let mut duplicated = positions.clone();
+        duplicated[0] = duplicated[1].clone();
+        assert!(DoubleCalendarSpread::get_strategy(&duplicated).is_err());
+    }
+
+    #[test]
+    fn test_double_calendar_find_optimal_center_brackets_spot() {
+        let chain =
+            OptionChain::load_from_json("examples/Chains/SP500-18-oct-2024-5781.88.json").unwrap();
+        let mut strategy = DoubleCalendarSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            pos_or_panic!(5750.0),
+            pos_or_panic!(5800.0),
+            ExpirationDate::Days(pos_or_panic!(2.0)),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.17),
+            pos_or_panic!(0.17),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(10.0),
+            pos_or_panic!(60.0),
+            pos_or_panic!(10.0),
+            pos_or_panic!(60.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let mut filtered = chain.clone();
+        filtered.options.retain(|o| {
+            o.strike_price >= pos_or_panic!(5750.0) && o.strike_price <= pos_or_panic!(5815.0)
+        });
+        strategy.find_optimal(
+            &filtered,
+            FindOptimalSide::Center,
+            OptimizationCriteria::Ratio,
+        );
+        assert!(strategy.validate());
+        assert!(strategy.near_put.option.strike_price <= chain.underlying_price);
+        assert!(strategy.near_call.option.strike_price >= chain.underlying_price);
+    }
+}
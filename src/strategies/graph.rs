@@ -7,10 +7,13 @@
 use crate::pricing::Profit;
 use crate::strategies::base::BreakEvenable;
 use crate::strategies::{
-    BasicAble, BearCallSpread, BearPutSpread, BullCallSpread, BullPutSpread, CallButterfly,
-    IronButterfly, IronCondor, LongButterflySpread, LongCall, LongPut, LongStraddle, LongStrangle,
-    PoorMansCoveredCall, ShortButterflySpread, ShortCall, ShortPut, ShortStraddle, ShortStrangle,
-    Strategies,
+    BasicAble, BearCallSpread, BearPutSpread, BoxSpread, BullCallSpread, BullPutSpread,
+    CallBackSpread, CallBrokenWingButterfly, CallButterfly, CallCondor, DiagonalSpread,
+    DoubleCalendarSpread, IronButterfly, IronCondor, JadeLizard, LongButterflySpread,
+    LongCalendarSpread, LongCall, LongPut, LongStraddle, LongStrangle, PoorMansCoveredCall,
+    PutBackSpread, PutBrokenWingButterfly, PutCondor, RatioCallSpread, RatioPutSpread,
+    ReverseJadeLizard, RiskReversal, ShortButterflySpread, ShortCalendarSpread, ShortCall,
+    ShortPut, ShortStraddle, ShortStrangle, Strap, Strategies, Strip,
 };
 use crate::visualization::{
     ColorScheme, Graph, GraphConfig, GraphData, Label2D, LineStyle, Series2D, TraceMode, VisPoint2D,
@@ -394,6 +397,24 @@ impl_graph_for_payoff_strategy!(
     ShortPut,
     PoorMansCoveredCall,
     CallButterfly,
+    LongCalendarSpread,
+    ShortCalendarSpread,
+    DiagonalSpread,
+    DoubleCalendarSpread,
+    RatioCallSpread,
+    RatioPutSpread,
+    CallBackSpread,
+    PutBackSpread,
+    CallBrokenWingButterfly,
+    PutBrokenWingButterfly,
+    JadeLizard,
+    ReverseJadeLizard,
+    Strap,
+    Strip,
+    CallCondor,
+    PutCondor,
+    BoxSpread,
+    RiskReversal,
     crate::strategies::custom::CustomStrategy,
     crate::strategies::covered_call::CoveredCall,
     crate::strategies::collar::Collar,
@@ -0,0 +1,530 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Valuation of multi-expiry strategies at their front expiry.
+//!
+//! Calendar and diagonal spreads hold legs with different expirations, so
+//! their payoff at the front expiry is not a sum of intrinsic values: the
+//! legs that are still alive keep their remaining time value. The helpers
+//! here settle the expiring legs at intrinsic value and price the remaining
+//! legs with Black–Scholes at their own implied volatility and remaining
+//! time, which is the P&L profile used for break-evens, maximum profit and
+//! loss, probability ranges and optimisation.
+
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::error::probability::ProbabilityError;
+use crate::error::{PricingError, StrategyError};
+use crate::model::ProfitLossRange;
+use crate::model::decimal::finite_decimal;
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, Side};
+use crate::model::utils::mean_and_std;
+use crate::pnl::PnLCalculator;
+use crate::pnl::utils::PnL;
+use crate::series::OptionSeries;
+use crate::strategies::Strategies;
+use crate::strategies::probabilities::utils::VolatilityAdjustment;
+use crate::strategies::utils::{FindOptimalSide, OptimizationCriteria};
+use crate::{ExpirationDate, Options};
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+
+/// Number of points on which the front-expiry profile is sampled.
+const PROFILE_POINTS: usize = 201;
+
+/// Minimum half-width of the sampled price range, as a log-return.
+const MIN_PROFILE_WIDTH: f64 = 0.25;
+
+/// Standard deviations of the front-expiry move covered by the profile.
+const PROFILE_DEVIATIONS: f64 = 4.0;
+
+/// Bisection steps used to refine each break-even point.
+const BREAK_EVEN_ITERATIONS: usize = 60;
+
+/// Days between the valuation date and the expiry of `option`.
+fn days_to_expiry(option: &Options) -> Result<f64, PricingError> {
+    Ok(option.expiration_date.get_days()?.to_f64())
+}
+
+/// Expiration of the leg that expires first.
+///
+/// # Errors
+///
+/// Returns `PricingError::ExpirationDate` when an expiry cannot be
+/// converted to days and `PricingError::MethodError` for an empty strategy.
+pub(crate) fn front_expiration(positions: &[&Position]) -> Result<ExpirationDate, PricingError> {
+    let mut front: Option<(f64, ExpirationDate)> = None;
+    for position in positions {
+        let days = days_to_expiry(&position.option)?;
+        if front.is_none_or(|(d, _)| days < d) {
+            front = Some((days, position.option.expiration_date));
+        }
+    }
+    front
+        .map(|(_, expiration)| expiration)
+        .ok_or_else(|| PricingError::method_error("front expiry", "strategy has no legs"))
+}
+
+/// The leg's option with `elapsed` days removed from its life, or `None`
+/// once it has expired.
+fn aged_option(option: &Options, elapsed: f64) -> Result<Option<Options>, PricingError> {
+    let remaining = days_to_expiry(option)? - elapsed;
+    if remaining <= f64::EPSILON {
+        return Ok(None);
+    }
+    let mut aged = option.clone();
+    aged.expiration_date = ExpirationDate::Days(Positive::new(remaining)?);
+    Ok(Some(aged))
+}
+
+/// P&L of one leg at the front expiry with the underlying at `price`.
+fn leg_profit(
+    position: &Position,
+    price: &Positive,
+    front_days: f64,
+) -> Result<Decimal, PricingError> {
+    match aged_option(&position.option, front_days)? {
+        None => position.pnl_at_expiration(&Some(price)),
+        Some(mut option) => {
+            option.underlying_price = *price;
+            let value = option.calculate_price_black_scholes()? * option.quantity.to_dec();
+            Ok(value - position.total_cost()?.to_dec() + position.premium_received()?.to_dec())
+        }
+    }
+}
+
+/// P&L of the strategy at the front expiry with the underlying at `price`.
+///
+/// # Errors
+///
+/// Propagates expiry conversion and Black–Scholes pricing errors.
+pub(crate) fn profit_at_front_expiry(
+    positions: &[&Position],
+    price: &Positive,
+) -> Result<Decimal, PricingError> {
+    let front_days = front_expiration(positions)?.get_days()?.to_f64();
+    positions
+        .iter()
+        .map(|position| leg_profit(position, price, front_days))
+        .sum()
+}
+
+/// Price range on which the front-expiry profile is sampled: the strikes
+/// and the spot widened by `PROFILE_DEVIATIONS` standard deviations of the
+/// move to the front expiry.
+fn profile_range(positions: &[&Position]) -> Result<(f64, f64), PricingError> {
+    let front = front_expiration(positions)?;
+    let years = front.get_years()?.to_f64();
+    let (mut low, mut high, mut vol) = (f64::INFINITY, 0.0f64, 0.0f64);
+    for position in positions {
+        let option = &position.option;
+        for price in [
+            option.strike_price.to_f64(),
+            option.underlying_price.to_f64(),
+        ] {
+            low = low.min(price);
+            high = high.max(price);
+        }
+        vol = vol.max(option.implied_volatility.to_f64());
+    }
+    let width = (PROFILE_DEVIATIONS * vol * years.sqrt()).max(MIN_PROFILE_WIDTH);
+    Ok((low * (-width).exp(), high * width.exp()))
+}
+
+/// `(price, P&L)` samples of the front-expiry profile.
+///
+/// # Errors
+///
+/// Propagates the errors of [`profit_at_front_expiry`].
+pub(crate) fn front_expiry_profile(
+    positions: &[&Position],
+) -> Result<Vec<(f64, f64)>, PricingError> {
+    let (low, high) = profile_range(positions)?;
+    let step = (high - low) / (PROFILE_POINTS - 1) as f64;
+    // The strikes are where the expiring legs kink the profile, so the
+    // extremes of a calendar sit on them rather than between grid points.
+    let mut prices: Vec<f64> = (0..PROFILE_POINTS)
+        .map(|i| low + step * i as f64)
+        .chain(
+            positions
+                .iter()
+                .map(|position| position.option.strike_price.to_f64()),
+        )
+        .collect();
+    prices.sort_by(f64::total_cmp);
+    prices.dedup();
+    prices
+        .into_iter()
+        .map(|price| {
+            let pnl = profit_at_front_expiry(positions, &Positive::new(price)?)?;
+            Ok((price, pnl.to_f64().unwrap_or(0.0)))
+        })
+        .collect()
+}
+
+/// Underlying prices at which the front-expiry P&L crosses zero, refined
+/// by bisection and rounded to two decimals.
+///
+/// # Errors
+///
+/// Propagates the errors of [`profit_at_front_expiry`].
+pub(crate) fn front_expiry_break_even_points(
+    positions: &[&Position],
+) -> Result<Vec<Positive>, PricingError> {
+    let profile = front_expiry_profile(positions)?;
+    let pnl_at = |price: f64| -> Result<f64, PricingError> {
+        Ok(profit_at_front_expiry(positions, &Positive::new(price)?)?
+            .to_f64()
+            .unwrap_or(0.0))
+    };
+    let mut points = Vec::new();
+    for pair in profile.windows(2) {
+        let &[(mut lo, lo_pnl), (mut hi, hi_pnl)] = pair else {
+            continue;
+        };
+        if lo_pnl.signum() == hi_pnl.signum() || hi_pnl == 0.0 {
+            continue;
+        }
+        let lo_sign = lo_pnl.signum();
+        for _ in 0..BREAK_EVEN_ITERATIONS {
+            let mid = 0.5 * (lo + hi);
+            if pnl_at(mid)?.signum() == lo_sign {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        points.push(Positive::new(0.5 * (lo + hi))?.round_to(2));
+    }
+    Ok(points)
+}
+
+/// Largest front-expiry profit on the sampled profile.
+///
+/// # Errors
+///
+/// Returns `StrategyError::ProfitLossError` when the profile never turns a
+/// profit.
+pub(crate) fn front_expiry_max_profit(positions: &[&Position]) -> Result<Positive, StrategyError> {
+    let best = front_expiry_profile(positions)?
+        .into_iter()
+        .map(|(_, pnl)| pnl)
+        .fold(f64::NEG_INFINITY, f64::max);
+    if best <= 0.0 {
+        return Err(StrategyError::ProfitLossError(
+            crate::error::strategies::ProfitLossErrorKind::MaxProfitError {
+                reason: "Max profit is negative".to_string(),
+            },
+        ));
+    }
+    Ok(Positive::new(best)?)
+}
+
+/// Largest front-expiry loss on the sampled profile, as a positive amount.
+///
+/// # Errors
+///
+/// Returns `StrategyError::ProfitLossError` when the profile never turns a
+/// loss.
+pub(crate) fn front_expiry_max_loss(positions: &[&Position]) -> Result<Positive, StrategyError> {
+    let worst = front_expiry_profile(positions)?
+        .into_iter()
+        .map(|(_, pnl)| pnl)
+        .fold(f64::INFINITY, f64::min);
+    if worst >= 0.0 {
+        return Err(StrategyError::ProfitLossError(
+            crate::error::strategies::ProfitLossErrorKind::MaxLossError {
+                reason: "Max loss must be negative".to_string(),
+            },
+        ));
+    }
+    Ok(Positive::new(-worst)?)
+}
+
+/// Area under the positive part of the front-expiry profile, per unit of
+/// underlying price (trapezoidal rule).
+///
+/// # Errors
+///
+/// Returns `StrategyError::NumericConversion` if the area is not finite.
+pub(crate) fn front_expiry_profit_area(positions: &[&Position]) -> Result<Decimal, StrategyError> {
+    let profile = front_expiry_profile(positions)?;
+    let area: f64 = profile
+        .windows(2)
+        .filter_map(|pair| match pair {
+            [(x0, y0), (x1, y1)] => Some(0.5 * (y0.max(0.0) + y1.max(0.0)) * (x1 - x0)),
+            _ => None,
+        })
+        .sum();
+    let spot = positions
+        .first()
+        .map_or(1.0, |p| p.option.underlying_price.to_f64());
+    let result = area / spot;
+    finite_decimal(result).ok_or_else(|| StrategyError::numeric_conversion(result))
+}
+
+/// Maximum profit over maximum loss, in percent (zero when either is
+/// unavailable).
+///
+/// # Errors
+///
+/// Returns `StrategyError::NumericConversion` if the ratio is not finite.
+pub(crate) fn front_expiry_profit_ratio(positions: &[&Position]) -> Result<Decimal, StrategyError> {
+    let result = match (
+        front_expiry_max_profit(positions),
+        front_expiry_max_loss(positions),
+    ) {
+        (Ok(profit), Ok(loss)) => (profit / loss).to_f64() * 100.0,
+        _ => 0.0,
+    };
+    finite_decimal(result).ok_or_else(|| StrategyError::numeric_conversion(result))
+}
+
+/// Profit (`profit = true`) or loss price ranges at the front expiry
+/// between consecutive break-even points, with the probability of the
+/// underlying finishing in each.
+///
+/// # Errors
+///
+/// Propagates range construction and probability errors.
+pub(crate) fn front_expiry_ranges(
+    positions: &[&Position],
+    break_even_points: &[Positive],
+    profit: bool,
+) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+    let Some(first) = positions.first() else {
+        return Ok(Vec::new());
+    };
+    let expiration = front_expiration(positions)?;
+    let (mean_volatility, std_dev) = mean_and_std(
+        positions
+            .iter()
+            .map(|p| p.option.implied_volatility)
+            .collect(),
+    );
+    let mut bounds: Vec<Option<Positive>> = vec![None];
+    bounds.extend(break_even_points.iter().copied().map(Some));
+    bounds.push(None);
+
+    let mut ranges = Vec::new();
+    for pair in bounds.windows(2) {
+        let [lower, upper] = pair else { continue };
+        let probe = match (lower, upper) {
+            (Some(l), Some(u)) => (*l + *u) / Positive::TWO,
+            (Some(l), None) => *l * Positive::TWO,
+            (None, Some(u)) => *u / Positive::TWO,
+            (None, None) => first.option.underlying_price,
+        };
+        let is_profit = profit_at_front_expiry(positions, &probe)? > Decimal::ZERO;
+        if is_profit != profit {
+            continue;
+        }
+        let mut range = ProfitLossRange::new(*lower, *upper, Positive::ZERO)?;
+        range.calculate_probability(
+            &first.option.underlying_price,
+            Some(VolatilityAdjustment {
+                base_volatility: mean_volatility,
+                std_dev_adjustment: std_dev,
+            }),
+            None,
+            &expiration,
+            Some(first.option.risk_free_rate),
+        )?;
+        ranges.push(range);
+    }
+    Ok(ranges)
+}
+
+/// P&L of closing every leg at the front expiry with the underlying at
+/// `price`: expired legs settle at intrinsic value and the others are sold
+/// back at their Black–Scholes value. The result is fully realized and
+/// matches [`profit_at_front_expiry`].
+///
+/// # Errors
+///
+/// Propagates expiry conversion and pricing errors.
+pub(crate) fn front_expiry_pnl(
+    positions: &[&Position],
+    price: &Positive,
+) -> Result<PnL, PricingError> {
+    let front = front_expiration(positions)?;
+    let front_days = front.get_days()?.to_f64();
+    let mut total: Option<PnL> = None;
+    for position in positions {
+        let pnl = PnL::new(
+            Some(leg_profit(position, price, front_days)?),
+            Some(Decimal::ZERO),
+            position.total_cost()?,
+            position.premium_received()?,
+            front.get_date()?,
+        );
+        total = Some(match total {
+            None => pnl,
+            Some(sum) => sum + pnl,
+        });
+    }
+    total.ok_or_else(|| PricingError::method_error("front expiry P&L", "strategy has no legs"))
+}
+
+/// P&L when `expiration_date` is the time left on the front leg: every leg
+/// is aged by the same elapsed time, expired legs settle at intrinsic value
+/// and the others are marked to Black–Scholes at `implied_volatility`.
+///
+/// # Errors
+///
+/// Propagates expiry conversion and pricing errors.
+pub(crate) fn aged_pnl(
+    positions: &[&Position],
+    market_price: &Positive,
+    expiration_date: ExpirationDate,
+    implied_volatility: &Positive,
+) -> Result<PnL, PricingError> {
+    let front_days = front_expiration(positions)?.get_days()?.to_f64();
+    let elapsed = (front_days - expiration_date.get_days()?.to_f64()).max(0.0);
+    let mut total: Option<PnL> = None;
+    for position in positions {
+        let pnl = match aged_option(&position.option, elapsed)? {
+            None => position.calculate_pnl_at_expiration(market_price)?,
+            Some(aged) => {
+                position.calculate_pnl(market_price, aged.expiration_date, implied_volatility)?
+            }
+        };
+        total = Some(match total {
+            None => pnl,
+            Some(sum) => sum + pnl,
+        });
+    }
+    total.ok_or_else(|| PricingError::method_error("aged P&L", "strategy has no legs"))
+}
+
+/// A chain of a series together with its expiration.
+pub(crate) type DatedChain<'a> = (&'a ExpirationDate, &'a OptionChain);
+
+/// Pairs of `(near, far)` chains of a series with strictly increasing
+/// expiries.
+pub(crate) fn expiry_pairs(series: &OptionSeries) -> Vec<(DatedChain<'_>, DatedChain<'_>)> {
+    let chains: Vec<DatedChain<'_>> = series.chains.iter().collect();
+    let mut pairs = Vec::new();
+    for (i, near) in chains.iter().enumerate() {
+        for far in chains.iter().skip(i + 1) {
+            pairs.push((*near, *far));
+        }
+    }
+    pairs
+}
+
+/// The quotes of `chain` at exactly `strike`.
+pub(crate) fn quote_at(chain: &OptionChain, strike: Positive) -> Option<&OptionData> {
+    chain.options.iter().find(|o| o.strike_price == strike)
+}
+
+/// The quote at which a leg of `style` trades on `side`: longs pay the ask,
+/// shorts receive the bid.
+pub(crate) fn leg_premium(quote: &OptionData, style: OptionStyle, side: Side) -> Option<Positive> {
+    match (style, side) {
+        (OptionStyle::Call, Side::Long) => quote.call_ask,
+        (OptionStyle::Call, Side::Short) => quote.call_bid,
+        (OptionStyle::Put, Side::Long) => quote.put_ask,
+        (OptionStyle::Put, Side::Short) => quote.put_bid,
+    }
+}
+
+/// `quote` with its bid and ask for `template`'s style replaced by the
+/// Black–Scholes value at `expiration`, priced at the quote's implied
+/// volatility. Used to project a single-expiry chain onto a later expiry.
+///
+/// # Errors
+///
+/// Propagates Black–Scholes pricing errors.
+pub(crate) fn repriced_quote(
+    quote: &OptionData,
+    template: &Options,
+    underlying_price: Positive,
+    expiration: ExpirationDate,
+) -> Result<OptionData, PricingError> {
+    let mut option = template.clone();
+    option.side = Side::Long;
+    option.quantity = Positive::ONE;
+    option.strike_price = quote.strike_price;
+    option.implied_volatility = quote.implied_volatility;
+    option.underlying_price = underlying_price;
+    option.expiration_date = expiration;
+    let price = Positive::new_decimal(option.calculate_price_black_scholes()?.max(Decimal::ZERO))?;
+    let mut repriced = quote.clone();
+    match template.option_style {
+        OptionStyle::Call => {
+            repriced.call_bid = Some(price);
+            repriced.call_ask = Some(price);
+        }
+        OptionStyle::Put => {
+            repriced.put_bid = Some(price);
+            repriced.put_ask = Some(price);
+        }
+    }
+    Ok(repriced)
+}
+
+/// Expiry of a later leg after the front expiry moves from `front` to
+/// `new_front`, keeping the gap between the two.
+///
+/// # Errors
+///
+/// Returns `PricingError::ExpirationDate` when an expiry cannot be
+/// converted to days.
+pub(crate) fn rolled_expiration(
+    leg: &ExpirationDate,
+    front: &ExpirationDate,
+    new_front: &ExpirationDate,
+) -> Result<ExpirationDate, PricingError> {
+    let gap = leg.get_days()? - front.get_days()?;
+    Ok(ExpirationDate::Days(new_front.get_days()? + gap))
+}
+
+/// Whether `strike` passes the optimiser's `side` filter, with
+/// [`FindOptimalSide::Center`] meaning the strike closest to the spot.
+pub(crate) fn strike_in_side(
+    chain: &OptionChain,
+    quote: &OptionData,
+    side: &FindOptimalSide,
+) -> bool {
+    let spot = chain.underlying_price;
+    match side {
+        FindOptimalSide::Upper => quote.strike_price >= spot,
+        FindOptimalSide::Lower => quote.strike_price <= spot,
+        FindOptimalSide::Range(start, end) => {
+            quote.strike_price >= *start && quote.strike_price <= *end
+        }
+        FindOptimalSide::Center => chain
+            .atm_strike()
+            .is_ok_and(|atm| *atm == quote.strike_price),
+        FindOptimalSide::DeltaRange(min, max) => {
+            let (delta_call, delta_put) = quote.current_deltas();
+            delta_call.is_some_and(|d| d >= *min && d <= *max)
+                || delta_put.is_some_and(|d| d >= *min && d <= *max)
+        }
+        FindOptimalSide::All | FindOptimalSide::Deltable(_) => true,
+    }
+}
+
+/// Value of `strategy` under `criteria`, or `None` if it cannot be scored.
+pub(crate) fn score<S: Strategies>(
+    strategy: &S,
+    criteria: &OptimizationCriteria,
+) -> Option<Decimal> {
+    let metric = match criteria {
+        OptimizationCriteria::Ratio => strategy.get_profit_ratio(),
+        OptimizationCriteria::Area => strategy.get_profit_area(),
+    };
+    match metric {
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::warn!(error = %e, "skipping candidate with unscorable metric");
+            None
+        }
+    }
+}
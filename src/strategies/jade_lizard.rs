@@ -0,0 +1,684 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Jade Lizard combines a short put with a short call spread: it sells a put below the market,
+//! sells a call above it and buys a further out-of-the-money call. Collecting a total credit at least
+//! as large as the width of the call spread removes all upside risk.
+//!
+//! The strategy has three components:
+//! 1. **Short lower-strike put**: Collects most of the premium and carries the downside risk.
+//! 2. **Short middle-strike call**: Adds premium and caps the profit on a rally.
+//! 3. **Long upper-strike call**: Limits the loss of the short call.
+//!
+//! The position is neutral to bullish and profits from time decay while the underlying stays between
+//! the short strikes.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::piecewise::{self, LegFactory};
+use crate::{
+    ExpirationDate, Options,
+    chains::{StrategyLegs, chain::OptionChain, utils::OptionDataGroup},
+    error::{
+        GreeksError, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+
+pub(super) const JADE_LIZARD_DESCRIPTION: &str = "A Jade Lizard sells an out-of-the-money put and an out-of-the-money call \
+    spread for a net credit. When the credit covers the call spread width it has no upside \
+    risk; the downside risk is that of the short put.";
+
+/// # JadeLizard
+///
+/// Represents a Jade Lizard: a short put at the lower strike, a short call at the middle strike and
+/// a long call at the upper strike.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::JadeLizard`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The underlying prices at expiration at which the strategy neither makes nor loses money.
+/// * `short_put`: The short put at the lower strike.
+/// * `short_call`: The short call at the middle strike.
+/// * `long_call`: The long call at the upper strike.
+///
+/// ## Risk and Reward
+/// The maximum profit is the net credit, kept with the underlying between the short strikes. Above
+/// the long call the result is the credit less the call spread width, and the maximum loss is reached
+/// with the underlying at zero, where the short put is assigned at its strike.
+///
+/// ## Break-Even Points
+/// There is a break-even point below the short put and, only when the credit is smaller than the
+/// call spread width, another one inside the call spread.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct JadeLizard {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a JadeLizard strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Prices at expiration where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The short put at the lower strike
+    pub(super) short_put: Position,
+    /// The short call at the middle strike
+    pub(super) short_call: Position,
+    /// The long call at the upper strike
+    pub(super) long_call: Position,
+}
+
+impl JadeLizard {
+    /// # Creates a new Jade Lizard strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `put_strike`: Strike price of the short put
+    /// * `short_call_strike`: Strike price of the short call
+    /// * `long_call_strike`: Strike price of the long call
+    /// * `expiration`: Expiration date shared by all the options
+    /// * `implied_volatility`: Implied volatility used to price the options
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts for every leg of the strategy
+    /// * `premium_short_put`: Premium received for the short put
+    /// * `premium_short_call`: Premium received for the short call
+    /// * `premium_long_call`: Premium paid for the long call
+    /// * `open_fee`: Transaction fee per contract for opening each position
+    /// * `close_fee`: Transaction fee per contract for closing each position
+    ///
+    /// ## Returns
+    /// A fully initialized `JadeLizard` with its break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the parameters are invalid: the put strike must
+    /// be below the short call strike, which must be below the long call strike.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        put_strike: Positive,
+        short_call_strike: Positive,
+        long_call_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_short_put: Positive,
+        premium_short_call: Positive,
+        premium_long_call: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let factory = LegFactory {
+            symbol: underlying_symbol,
+            underlying_price,
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            open_fee,
+            close_fee,
+        };
+        let mut strategy = JadeLizard {
+            short_put: factory.position(
+                OptionStyle::Put,
+                Side::Short,
+                put_strike,
+                quantity,
+                premium_short_put,
+            ),
+            short_call: factory.position(
+                OptionStyle::Call,
+                Side::Short,
+                short_call_strike,
+                quantity,
+                premium_short_call,
+            ),
+            long_call: factory.position(
+                OptionStyle::Call,
+                Side::Long,
+                long_call_strike,
+                quantity,
+                premium_long_call,
+            ),
+            ..JadeLizard::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Jade Lizard new",
+                "the put strike must be below the short call strike, which must be below the long call strike",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    fn legs(&self) -> [&Position; 3] {
+        [&self.short_put, &self.short_call, &self.long_call]
+    }
+
+    fn legs_mut(&mut self) -> [&mut Position; 3] {
+        [
+            &mut self.short_put,
+            &mut self.short_call,
+            &mut self.long_call,
+        ]
+    }
+}
+
+impl StrategyConstructor for JadeLizard {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        let [short_put, short_call, long_call] = piecewise::match_legs(
+            vec_positions,
+            [
+                (OptionStyle::Put, Side::Short),
+                (OptionStyle::Call, Side::Short),
+                (OptionStyle::Call, Side::Long),
+            ],
+            "Jade Lizard get_strategy",
+        )?;
+        let mut strategy = JadeLizard {
+            short_put,
+            short_call,
+            long_call,
+            ..JadeLizard::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Jade Lizard get_strategy",
+                "the put strike must be below the short call strike, which must be below the long call strike",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for JadeLizard {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = piecewise::break_even_points(&self.legs())?;
+        Ok(())
+    }
+}
+
+impl Validable for JadeLizard {
+    fn validate(&self) -> bool {
+        self.legs().iter().all(|leg| leg.validate())
+            && self.short_put.option.strike_price < self.short_call.option.strike_price
+            && self.short_call.option.strike_price < self.long_call.option.strike_price
+    }
+}
+
+impl Positionable for JadeLizard {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Put, Side::Short) => self.short_put = position.clone(),
+            (OptionStyle::Call, Side::Short) => self.short_call = position.clone(),
+            (OptionStyle::Call, Side::Long) => self.long_call = position.clone(),
+            _ => {
+                return Err(PositionError::invalid_position_type(
+                    position.option.side,
+                    "Position is not part of a Jade Lizard".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(self.legs().to_vec())
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        piecewise::find_legs(self.legs_mut().into(), option_style, side, strike)
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for JadeLizard {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for JadeLizard {
+    fn get_title(&self) -> String {
+        let legs: Vec<String> = self.legs().iter().map(|leg| leg.get_title()).collect();
+        format!("{:?} Strategy: \n\t{}", self.kind, legs.join("\n\t"))
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        piecewise::basic_types(&self.legs())
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::implied_volatilities(&self.legs())
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::quantities(&self.legs())
+    }
+    fn one_option(&self) -> &Options {
+        self.short_put.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.short_put.one_option_mut()
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        for leg in self.legs_mut() {
+            leg.option.expiration_date = expiration_date;
+        }
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.underlying_price = *price;
+        })
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.implied_volatility = *volatility;
+        })
+    }
+}
+
+impl Strategies for JadeLizard {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_profit(&self.legs())
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_loss(&self.legs())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_area(&self.legs())
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_ratio(&self.legs())
+    }
+}
+
+impl Optimizable for JadeLizard {
+    type Strategy = JadeLizard;
+
+    /// Combinations of three increasing strikes of `option_chain` that pass the
+    /// `side` filter.
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        option_chain
+            .get_triple_iter()
+            .filter(move |(lower, middle, upper)| {
+                piecewise::quotes_in_side(option_chain, &[lower, middle, upper], &side)
+            })
+            .map(|(lower, middle, upper)| OptionDataGroup::Three(lower, middle, upper))
+    }
+
+    /// Searches every combination of three increasing strikes of `option_chain` for
+    /// the strategy that scores best under `criteria` and replaces `self`
+    /// with it.
+    ///
+    /// Long legs are bought at the ask and short legs sold at the bid, and the
+    /// expiration, quantity, rate and fees of `self` are kept.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let candidates = self
+            .filter_combinations(option_chain, side)
+            .filter_map(|group| group.strategy_legs())
+            .map(|legs| self.create_strategy(option_chain, &legs));
+        if let Some(strategy) = piecewise::select_best(candidates, &criteria) {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `JadeLizard` from three quotes in increasing strike order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs have the wrong
+    /// shape, a needed bid or ask is missing, or the resulting strategy is
+    /// invalid.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (lower, middle, upper) = match legs {
+            StrategyLegs::ThreeLegs {
+                first: lower,
+                second: middle,
+                third: upper,
+            } => (lower, middle, upper),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "JadeLizard requires exactly three legs (ThreeLegs)",
+                ));
+            }
+        };
+        JadeLizard::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            lower.strike_price,
+            middle.strike_price,
+            upper.strike_price,
+            self.short_put.option.expiration_date,
+            lower.implied_volatility,
+            self.short_put.option.risk_free_rate,
+            self.short_put.option.dividend_yield,
+            self.short_put.option.quantity,
+            piecewise::premium(lower, OptionStyle::Put, Side::Short)?,
+            piecewise::premium(middle, OptionStyle::Call, Side::Short)?,
+            piecewise::premium(upper, OptionStyle::Call, Side::Long)?,
+            self.short_put.open_fee,
+            self.short_put.close_fee,
+        )
+    }
+}
+
+impl Profit for JadeLizard {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        piecewise::profit_at(&self.legs(), price)
+    }
+}
+
+impl ProbabilityAnalysis for JadeLizard {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, true)
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, false)
+    }
+}
+
+impl Greeks for JadeLizard {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self.legs().into_iter().map(|leg| &leg.option).collect())
+    }
+}
+
+impl DeltaNeutrality for JadeLizard {}
+
+impl PnLCalculator for JadeLizard {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl(
+            &self.legs(),
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl_at_expiration(&self.legs(), underlying_price)
+    }
+}
+
+test_strategy_traits!(JadeLizard, test_jade_lizard_implementations);
+
+#[cfg(test)]
+mod tests_jade_lizard {
+    use super::*;
+    use crate::assert_decimal_eq;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> JadeLizard {
+        JadeLizard::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            pos_or_panic!(110.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(3.0),
+            pos_or_panic!(3.0),
+            pos_or_panic!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_jade_lizard_break_even_points() {
+        let strategy = create_strategy();
+        assert!(strategy.validate());
+        assert_eq!(strategy.break_even_points.len(), 1);
+        assert_decimal_eq!(
+            strategy.break_even_points[0].to_dec(),
+            dec!(89.5),
+            dec!(0.01)
+        );
+        for point in &strategy.break_even_points {
+            let pnl = strategy.calculate_profit_at(point).unwrap();
+            assert!(pnl.abs() < dec!(0.05), "P&L {pnl} at break-even {point}");
+        }
+    }
+
+    #[test]
+    fn test_jade_lizard_max_profit_and_loss() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.get_max_profit().unwrap().to_dec(),
+            dec!(5.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.get_max_loss().unwrap().to_dec(),
+            dec!(89.5),
+            dec!(1e-9)
+        );
+    }
+
+    #[test]
+    fn test_jade_lizard_profit_at_expiration() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap(),
+            dec!(5.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(85.0)).unwrap(),
+            dec!(-4.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(120.0)).unwrap(),
+            dec!(0.5),
+            dec!(1e-9)
+        );
+        let pnl = strategy
+            .calculate_pnl_at_expiration(&pos_or_panic!(100.0))
+            .unwrap();
+        assert_eq!(
+            pnl.total_pnl(),
+            Some(strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_jade_lizard_rejects_invalid_parameters() {
+        let result = JadeLizard::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(110.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(3.0),
+            pos_or_panic!(3.0),
+            pos_or_panic!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_jade_lizard_get_strategy_from_positions() {
+        let strategy = create_strategy();
+        let positions = vec![
+            strategy.long_call.clone(),
+            strategy.short_call.clone(),
+            strategy.short_put.clone(),
+        ];
+        let rebuilt = JadeLizard::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.break_even_points, strategy.break_even_points);
+        assert_eq!(rebuilt.get_positions().unwrap().len(), 3);
+
+        let mut wrong = positions.clone();
+        wrong[0].option.side = match wrong[0].option.side {
+            Side::Long => Side::Short,
+            Side::Short => Side::Long,
+        };
+        assert!(JadeLizard::get_strategy(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_jade_lizard_find_optimal() {
+        let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+            "SPY".to_string(),
+            None,
+            6,
+            spos!(2.5),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(100.0))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("SPY".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        ))
+        .unwrap();
+        let mut strategy = create_strategy();
+        strategy.find_optimal(&chain, FindOptimalSide::All, OptimizationCriteria::Area);
+        assert!(strategy.validate());
+        for leg in strategy.get_positions().unwrap() {
+            assert!(
+                chain
+                    .options
+                    .iter()
+                    .any(|quote| quote.strike_price == leg.option.strike_price)
+            );
+        }
+    }
+}
@@ -0,0 +1,1008 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Long Calendar Spread (also called a time spread or horizontal spread) sells a near-term option
+//! and buys a longer-dated option of the same style at the same strike. The trade is opened for a
+//! net debit and profits from the faster time decay of the short front-month option, which is worth
+//! most when the underlying settles close to the strike at the front expiry.
+//!
+//! The strategy has two components:
+//! 1. **Short near-term option**: Collects premium that decays quickly as the front expiry approaches.
+//! 2. **Long far-term option**: Retains most of its time value when the front option expires and caps
+//!    the risk of the short leg.
+//!
+//! Because the legs expire on different dates, the profit and loss profile is measured at the front
+//! expiry: the short leg settles at intrinsic value while the long leg is valued with Black–Scholes
+//! at its own implied volatility and remaining time. Break-even points, maximum profit and loss and
+//! the probability ranges are all derived from that profile. The position is long vega, so a rise
+//! in back-month implied volatility helps it.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::horizon;
+use crate::{
+    ExpirationDate, Options,
+    chains::{StrategyLegs, chain::OptionChain},
+    error::{
+        GreeksError, OperationErrorKind, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, OptionType, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    series::OptionSeries,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use chrono::Utc;
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+use utoipa::ToSchema;
+
+pub(super) const LONG_CALENDAR_SPREAD_DESCRIPTION: &str = "A Long Calendar Spread sells a near-term option \
+    and buys a longer-dated option of the same style and strike for a net debit. \
+    It profits from the faster time decay of the front-month option and is most profitable \
+    when the underlying is close to the strike at the front expiry.";
+
+/// # LongCalendarSpread
+///
+/// Represents a Long Calendar Spread: a short near-term option and a long far-term option of the
+/// same style at the same strike.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::LongCalendarSpread`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The prices at the front expiry at which the strategy neither makes nor loses money.
+/// * `near_leg`: The short near-term option.
+/// * `far_leg`: The long far-term option.
+///
+/// ## Risk and Reward
+/// The maximum loss is close to the net debit paid and occurs when the underlying moves far from the
+/// strike, where both options converge to the same intrinsic value. The maximum profit is reached
+/// with the underlying at the strike on the front expiry and depends on the time value left in the
+/// far leg, so it is measured on the front-expiry profile rather than in closed form.
+///
+/// ## Break-Even Points
+/// There are usually two break-even points, one on each side of the strike, found numerically on
+/// the front-expiry profile.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct LongCalendarSpread {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a LongCalendarSpread strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Front-expiry prices where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The near-term option that is sold
+    pub(super) near_leg: Position,
+    /// The far-term option that is bought
+    pub(super) far_leg: Position,
+}
+
+impl LongCalendarSpread {
+    /// # Creates a new Long Calendar Spread strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `strike`: Strike price shared by both legs
+    /// * `option_style`: Style (call or put) shared by both legs
+    /// * `near_expiration`: Expiration date of the short near-term option
+    /// * `far_expiration`: Expiration date of the long far-term option
+    /// * `near_implied_volatility`: Implied volatility of the near-term option
+    /// * `far_implied_volatility`: Implied volatility of the far-term option
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts for both legs of the strategy
+    /// * `premium_near`: Premium received for the near-term option
+    /// * `premium_far`: Premium paid for the far-term option
+    /// * `open_fee_near`: Transaction fee for opening the near-term position
+    /// * `close_fee_near`: Transaction fee for closing the near-term position
+    /// * `open_fee_far`: Transaction fee for opening the far-term position
+    /// * `close_fee_far`: Transaction fee for closing the far-term position
+    ///
+    /// ## Returns
+    /// A fully initialized `LongCalendarSpread` with its front-expiry break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the near expiration is not
+    /// strictly earlier than the far one, and propagates pricing errors from
+    /// the break-even calculation.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        strike: Positive,
+        option_style: OptionStyle,
+        near_expiration: ExpirationDate,
+        far_expiration: ExpirationDate,
+        near_implied_volatility: Positive,
+        far_implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_near: Positive,
+        premium_far: Positive,
+        open_fee_near: Positive,
+        close_fee_near: Positive,
+        open_fee_far: Positive,
+        close_fee_far: Positive,
+    ) -> Result<Self, StrategyError> {
+        let mut strategy = LongCalendarSpread::default();
+
+        let near_option = Options::new(
+            OptionType::European,
+            Side::Short,
+            underlying_symbol.clone(),
+            strike,
+            near_expiration,
+            near_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let near_leg = Position::new(
+            near_option,
+            premium_near,
+            Utc::now(),
+            open_fee_near,
+            close_fee_near,
+            None,
+            None,
+        );
+        strategy.add_position(&near_leg)?;
+
+        let far_option = Options::new(
+            OptionType::European,
+            Side::Long,
+            underlying_symbol,
+            strike,
+            far_expiration,
+            far_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let far_leg = Position::new(
+            far_option,
+            premium_far,
+            Utc::now(),
+            open_fee_far,
+            close_fee_far,
+            None,
+            None,
+        );
+        strategy.add_position(&far_leg)?;
+
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Long Calendar Spread new",
+                "near expiration must be earlier than far expiration",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    /// Searches every pair of expirations in `series` (near before far) and
+    /// every strike quoted in both chains for the calendar that scores best
+    /// under `criteria`, and replaces `self` with it.
+    ///
+    /// The near leg is sold at the bid and the far leg bought at the ask of
+    /// their own chains, and each leg keeps the implied volatility quoted
+    /// for its expiry. The style, quantity, rate and fees of `self` are kept.
+    pub fn find_optimal_in_series(
+        &mut self,
+        series: &OptionSeries,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let mut best: Option<LongCalendarSpread> = None;
+
+        for ((near_expiration, near_chain), (far_expiration, far_chain)) in
+            horizon::expiry_pairs(series)
+        {
+            let mut template = self.clone();
+            template.near_leg.option.expiration_date = *near_expiration;
+            template.far_leg.option.expiration_date = *far_expiration;
+
+            for near_quote in near_chain.options.iter() {
+                if !horizon::strike_in_side(near_chain, near_quote, &side) {
+                    continue;
+                }
+                let Some(far_quote) = horizon::quote_at(far_chain, near_quote.strike_price) else {
+                    continue;
+                };
+                let legs = StrategyLegs::TwoLegs {
+                    first: near_quote,
+                    second: far_quote,
+                };
+                let strategy = match template.create_strategy(near_chain, &legs) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        debug!(error = %e, "skipping invalid calendar combination");
+                        continue;
+                    }
+                };
+                if let Some(value) = horizon::score(&strategy, &criteria)
+                    && value > best_value
+                {
+                    best_value = value;
+                    best = Some(strategy);
+                }
+            }
+        }
+
+        if let Some(strategy) = best {
+            *self = strategy;
+        }
+    }
+}
+
+impl StrategyConstructor for LongCalendarSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        let [first, second] = vec_positions else {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Long Calendar Spread get_strategy".to_string(),
+                    reason: "Must have exactly 2 options".to_string(),
+                },
+            ));
+        };
+        let (near_leg, far_leg) = match (first.option.side, second.option.side) {
+            (Side::Short, Side::Long) => (first, second),
+            (Side::Long, Side::Short) => (second, first),
+            _ => {
+                return Err(StrategyError::OperationError(
+                    OperationErrorKind::InvalidParameters {
+                        operation: "Long Calendar Spread get_strategy".to_string(),
+                        reason: "Long Calendar Spread requires one short and one long option"
+                            .to_string(),
+                    },
+                ));
+            }
+        };
+        if near_leg.option.option_style != far_leg.option.option_style
+            || near_leg.option.strike_price != far_leg.option.strike_price
+        {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Long Calendar Spread get_strategy".to_string(),
+                    reason: "Both options must share style and strike".to_string(),
+                },
+            ));
+        }
+
+        let mut strategy = LongCalendarSpread {
+            name: "Long Calendar Spread".to_string(),
+            kind: StrategyType::LongCalendarSpread,
+            description: LONG_CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg: near_leg.clone(),
+            far_leg: far_leg.clone(),
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Long Calendar Spread get_strategy".to_string(),
+                    reason: "The short option must expire before the long option".to_string(),
+                },
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for LongCalendarSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = horizon::front_expiry_break_even_points(&self.get_positions()?)?;
+        Ok(())
+    }
+}
+
+impl Validable for LongCalendarSpread {
+    fn validate(&self) -> bool {
+        let near = &self.near_leg.option;
+        let far = &self.far_leg.option;
+        let ordered = match (
+            near.expiration_date.get_days(),
+            far.expiration_date.get_days(),
+        ) {
+            (Ok(near_days), Ok(far_days)) => near_days < far_days,
+            _ => false,
+        };
+        self.near_leg.validate()
+            && self.far_leg.validate()
+            && near.side == Side::Short
+            && far.side == Side::Long
+            && near.option_style == far.option_style
+            && near.strike_price == far.strike_price
+            && ordered
+    }
+}
+
+impl Positionable for LongCalendarSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match position.option.side {
+            Side::Short => self.near_leg = position.clone(),
+            Side::Long => self.far_leg = position.clone(),
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(vec![&self.near_leg, &self.far_leg])
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        let leg = match side {
+            Side::Short => &mut self.near_leg,
+            Side::Long => &mut self.far_leg,
+        };
+        if leg.option.option_style == *option_style && leg.option.strike_price == *strike {
+            Ok(vec![leg])
+        } else {
+            Err(PositionError::invalid_position_type(
+                *side,
+                "Strike not found in positions".to_string(),
+            ))
+        }
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for LongCalendarSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for LongCalendarSpread {
+    fn get_title(&self) -> String {
+        format!(
+            "{:?} Strategy: \n\t{}\n\t{}",
+            self.kind,
+            self.near_leg.get_title(),
+            self.far_leg.get_title()
+        )
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            })
+            .collect()
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.implied_volatility,
+                )
+            })
+            .collect()
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.quantity,
+                )
+            })
+            .collect()
+    }
+    fn one_option(&self) -> &Options {
+        self.near_leg.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.near_leg.one_option_mut()
+    }
+    /// Moves the front expiry to `expiration_date` and shifts the far leg by
+    /// the same amount, keeping the gap between the two expirations.
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        self.far_leg.option.expiration_date = horizon::rolled_expiration(
+            &self.far_leg.option.expiration_date,
+            &self.near_leg.option.expiration_date,
+            &expiration_date,
+        )?;
+        self.near_leg.option.expiration_date = expiration_date;
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.underlying_price = *price;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        Ok(())
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.implied_volatility = *volatility;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        Ok(())
+    }
+}
+
+impl Strategies for LongCalendarSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        horizon::front_expiry_max_profit(&self.get_positions()?)
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        horizon::front_expiry_max_loss(&self.get_positions()?)
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        horizon::front_expiry_profit_area(&self.get_positions()?)
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        horizon::front_expiry_profit_ratio(&self.get_positions()?)
+    }
+}
+
+impl Optimizable for LongCalendarSpread {
+    type Strategy = LongCalendarSpread;
+
+    /// Searches the strikes of `option_chain` for the best calendar at the
+    /// current near and far expirations.
+    ///
+    /// The chain is taken as the front month: the near leg is sold at its
+    /// bid, while the far leg is priced with Black–Scholes at the far
+    /// expiry and the chain's implied volatility for the strike. Use
+    /// [`LongCalendarSpread::find_optimal_in_series`] to search quoted
+    /// expiries instead.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let mut best: Option<LongCalendarSpread> = None;
+
+        for near_quote in option_chain.options.iter() {
+            if !horizon::strike_in_side(option_chain, near_quote, &side) {
+                continue;
+            }
+            let far_quote = match horizon::repriced_quote(
+                near_quote,
+                &self.far_leg.option,
+                option_chain.underlying_price,
+                self.far_leg.option.expiration_date,
+            ) {
+                Ok(quote) => quote,
+                Err(e) => {
+                    debug!(error = %e, "skipping strike without a far-expiry price");
+                    continue;
+                }
+            };
+            let legs = StrategyLegs::TwoLegs {
+                first: near_quote,
+                second: &far_quote,
+            };
+            let strategy = match self.create_strategy(option_chain, &legs) {
+                Ok(s) => s,
+                Err(e) => {
+                    debug!(error = %e, "skipping invalid calendar combination");
+                    continue;
+                }
+            };
+            if let Some(value) = horizon::score(&strategy, &criteria)
+                && value > best_value
+            {
+                best_value = value;
+                best = Some(strategy);
+            }
+        }
+
+        if let Some(strategy) = best {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `LongCalendarSpread` from a near quote (`first`) and a
+    /// far quote (`second`) at the same strike.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs are not
+    /// `TwoLegs`, the strikes differ, or the near bid or far ask is missing.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (near, far) = match legs {
+            StrategyLegs::TwoLegs { first, second } => (first, second),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "LongCalendarSpread requires exactly two legs (TwoLegs)",
+                ));
+            }
+        };
+        if near.strike_price != far.strike_price {
+            return Err(StrategyError::invalid_parameters(
+                "create_strategy",
+                "calendar legs must share the same strike",
+            ));
+        }
+        let style = self.near_leg.option.option_style;
+        let premium_near = horizon::leg_premium(near, style, Side::Short).ok_or_else(|| {
+            StrategyError::operation_not_supported("create_strategy", "missing bid for near leg")
+        })?;
+        let premium_far = horizon::leg_premium(far, style, Side::Long).ok_or_else(|| {
+            StrategyError::operation_not_supported("create_strategy", "missing ask for far leg")
+        })?;
+
+        LongCalendarSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            near.strike_price,
+            style,
+            self.near_leg.option.expiration_date,
+            self.far_leg.option.expiration_date,
+            near.implied_volatility,
+            far.implied_volatility,
+            self.near_leg.option.risk_free_rate,
+            self.near_leg.option.dividend_yield,
+            self.near_leg.option.quantity,
+            premium_near,
+            premium_far,
+            self.near_leg.open_fee,
+            self.near_leg.close_fee,
+            self.far_leg.open_fee,
+            self.far_leg.close_fee,
+        )
+    }
+}
+
+impl Profit for LongCalendarSpread {
+    /// Profit at the front expiry, with the far leg valued at its remaining
+    /// time value.
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        horizon::profit_at_front_expiry(&[&self.near_leg, &self.far_leg], price)
+    }
+}
+
+impl ProbabilityAnalysis for LongCalendarSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        horizon::front_expiry_ranges(
+            &[&self.near_leg, &self.far_leg],
+            &self.break_even_points,
+            true,
+        )
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        horizon::front_expiry_ranges(
+            &[&self.near_leg, &self.far_leg],
+            &self.break_even_points,
+            false,
+        )
+    }
+}
+
+impl Greeks for LongCalendarSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.near_leg.option, &self.far_leg.option])
+    }
+}
+
+impl DeltaNeutrality for LongCalendarSpread {}
+
+impl PnLCalculator for LongCalendarSpread {
+    /// P&L with `expiration_date` left on the near leg; the far leg is aged
+    /// by the same elapsed time.
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        horizon::aged_pnl(
+            &[&self.near_leg, &self.far_leg],
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    /// Realized P&L of closing the spread at the front expiry, with the far
+    /// leg sold back at its own implied volatility.
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        horizon::front_expiry_pnl(&[&self.near_leg, &self.far_leg], underlying_price)
+    }
+}
+
+test_strategy_traits!(
+    LongCalendarSpread,
+    test_long_calendar_spread_implementations
+);
+
+#[cfg(test)]
+mod tests_long_calendar_spread {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::series::OptionSeriesBuildParams;
+    use crate::utils::Len;
+    use num_traits::ToPrimitive;
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> LongCalendarSpread {
+        LongCalendarSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(100.0),
+            OptionStyle::Call,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(90.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.22),
+            dec!(0.03),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.4),
+            pos_or_panic!(4.6),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_long_calendar_new_has_two_break_even_points() {
+        let strategy = create_strategy();
+        assert!(strategy.validate());
+        assert_eq!(strategy.break_even_points.len(), 2);
+        let (low, high) = (strategy.break_even_points[0], strategy.break_even_points[1]);
+        assert!(low < pos_or_panic!(100.0) && high > pos_or_panic!(100.0));
+        for point in [low, high] {
+            let pnl = strategy.calculate_profit_at(&point).unwrap();
+            assert!(pnl.abs() < dec!(0.05), "P&L {pnl} at break-even {point}");
+        }
+    }
+
+    #[test]
+    fn test_long_calendar_profit_uses_far_leg_time_value() {
+        let strategy = create_strategy();
+        let price = pos_or_panic!(100.0);
+
+        let mut far = strategy.far_leg.option.clone();
+        far.expiration_date = ExpirationDate::Days(pos_or_panic!(60.0));
+        let far_value = far.calculate_price_black_scholes().unwrap();
+        let expected = far_value - dec!(4.6) + dec!(2.4);
+
+        let profit = strategy.calculate_profit_at(&price).unwrap();
+        assert!(
+            (profit - expected).abs() < dec!(1e-9),
+            "{profit} vs {expected}"
+        );
+        assert_eq!(
+            strategy
+                .calculate_pnl_at_expiration(&price)
+                .unwrap()
+                .total_pnl(),
+            Some(profit)
+        );
+    }
+
+    #[test]
+    fn test_long_calendar_front_expiry_values() {
+        // At the 30-day expiry the near call settles at intrinsic and the far
+        // call is worth its Black-Scholes value with 60 days left at 22%:
+        // P&L(S) = C(S, 100, 60/365, 0.22, 3%) - 4.6 + 2.4 - max(S - 100, 0).
+        let strategy = create_strategy();
+        for (price, expected) in [
+            (90.0, dec!(-1.650203)),
+            (100.0, dec!(1.599916)),
+            (110.0, dec!(-1.097687)),
+        ] {
+            let profit = strategy
+                .calculate_profit_at(&Positive::new(price).unwrap())
+                .unwrap();
+            assert!(
+                (profit - expected).abs() < dec!(0.0001),
+                "{profit} vs {expected} at {price}"
+            );
+        }
+        let expected = [dec!(96.5352), dec!(104.3464)];
+        for (point, expected) in strategy.break_even_points.iter().zip(expected) {
+            assert!((point.to_dec() - expected).abs() < dec!(0.01), "{point}");
+        }
+    }
+
+    #[test]
+    fn test_long_calendar_max_profit_near_strike() {
+        let strategy = create_strategy();
+        let max_profit = strategy.get_max_profit().unwrap();
+        let at_strike = strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap();
+        assert!((max_profit.to_dec() - at_strike).abs() < dec!(0.1));
+        let max_loss = strategy.get_max_loss().unwrap();
+        assert!(max_loss <= pos_or_panic!(2.2) + pos_or_panic!(0.01));
+        assert!(strategy.get_profit_ratio().unwrap() > Decimal::ZERO);
+        assert!(strategy.get_profit_area().unwrap() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_long_calendar_probability_ranges() {
+        let strategy = create_strategy();
+        let profit_ranges = strategy.get_profit_ranges().unwrap();
+        let loss_ranges = strategy.get_loss_ranges().unwrap();
+        assert_eq!(profit_ranges.len(), 1);
+        assert_eq!(loss_ranges.len(), 2);
+        let total: Positive = profit_ranges
+            .iter()
+            .chain(loss_ranges.iter())
+            .map(|r| r.probability)
+            .sum();
+        assert!(
+            (total.to_f64() - 1.0).abs() < 0.01,
+            "total probability {total}"
+        );
+    }
+
+    #[test]
+    fn test_long_calendar_rejects_inverted_expirations() {
+        let result = LongCalendarSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(100.0),
+            OptionStyle::Put,
+            ExpirationDate::Days(pos_or_panic!(90.0)),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.2),
+            dec!(0.03),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.0),
+            pos_or_panic!(4.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_long_calendar_get_strategy_from_positions() {
+        let strategy = create_strategy();
+        let positions = vec![strategy.far_leg.clone(), strategy.near_leg.clone()];
+        let rebuilt = LongCalendarSpread::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.near_leg.option.side, Side::Short);
+        assert_eq!(rebuilt.break_even_points, strategy.break_even_points);
+
+        let mut mismatched = positions.clone();
+        mismatched[0].option.strike_price = pos_or_panic!(105.0);
+        assert!(LongCalendarSpread::get_strategy(&mismatched).is_err());
+    }
+
+    #[test]
+    fn test_long_calendar_set_expiration_keeps_gap() {
+        let mut strategy = create_strategy();
+        strategy
+            .set_expiration_date(ExpirationDate::Days(pos_or_panic!(10.0)))
+            .unwrap();
+        assert_eq!(
+            strategy.far_leg.option.expiration_date.get_days().unwrap(),
+            pos_or_panic!(70.0)
+        );
+    }
+
+    #[test]
+    fn test_long_calendar_atm_is_close_to_delta_neutral() {
+        let strategy = create_strategy();
+        assert_eq!(strategy.get_options().unwrap().len(), 2);
+        assert!(strategy.delta().unwrap().abs() < dec!(0.1));
+    }
+
+    #[test]
+    fn test_long_calendar_pnl_ages_both_legs() {
+        let strategy = create_strategy();
+        let price = pos_or_panic!(100.0);
+        let iv = pos_or_panic!(0.2);
+        let pnl = strategy
+            .calculate_pnl(&price, ExpirationDate::Days(pos_or_panic!(10.0)), &iv)
+            .unwrap();
+        let near = strategy
+            .near_leg
+            .calculate_pnl(&price, ExpirationDate::Days(pos_or_panic!(10.0)), &iv)
+            .unwrap();
+        let far = strategy
+            .far_leg
+            .calculate_pnl(&price, ExpirationDate::Days(pos_or_panic!(70.0)), &iv)
+            .unwrap();
+        assert_eq!(pnl.total_pnl(), (near + far).total_pnl());
+    }
+
+    #[test]
+    fn test_long_calendar_find_optimal_in_series() {
+        let chain_params = OptionChainBuildParams::new(
+            "SPY".to_string(),
+            None,
+            5,
+            spos!(1.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(100.0))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.03)),
+                spos!(0.0),
+                Some("SPY".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        );
+        let params = OptionSeriesBuildParams::new(
+            chain_params,
+            vec![
+                pos_or_panic!(30.0),
+                pos_or_panic!(60.0),
+                pos_or_panic!(90.0),
+            ],
+        );
+        let series = OptionSeries::build_series(&params).unwrap();
+        assert_eq!(series.len(), 3);
+
+        let mut strategy = create_strategy();
+        strategy.find_optimal_in_series(&series, FindOptimalSide::All, OptimizationCriteria::Ratio);
+        assert!(strategy.validate());
+        let near_days = strategy.near_leg.option.expiration_date.get_days().unwrap();
+        let far_days = strategy.far_leg.option.expiration_date.get_days().unwrap();
+        assert!(near_days < far_days);
+        assert!(strategy.get_profit_ratio().unwrap().to_f64().unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_long_calendar_find_optimal_single_chain() {
+        let chain =
+            OptionChain::load_from_json("examples/Chains/SP500-18-oct-2024-5781.88.json").unwrap();
+        let mut strategy = LongCalendarSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            pos_or_panic!(5780.0),
+            OptionStyle::Call,
+            ExpirationDate::Days(pos_or_panic!(2.0)),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.17),
+            pos_or_panic!(0.17),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(20.0),
+            pos_or_panic!(80.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        strategy.find_optimal(&chain, FindOptimalSide::Center, OptimizationCriteria::Area);
+        assert!(strategy.validate());
+        assert_eq!(
+            &strategy.near_leg.option.strike_price,
+            chain.atm_strike().unwrap()
+        );
+    }
+}
@@ -4,7 +4,7 @@
 // indices (fixed-length buffers, just-pushed slices, etc.).
 #![allow(clippy::indexing_slicing)]
 
-use super::base::{BreakEvenable, Positionable, StrategyType};
+use super::base::{BreakEvenable, Positionable, StrategyBasics, StrategyType};
 use crate::backtesting::results::{SimulationResult, SimulationStatsResult};
 use crate::chains::OptionChain;
 use crate::error::{
@@ -765,7 +765,15 @@ where
     }
 }
 
-impl Strategable for LongCall {}
+impl Strategable for LongCall {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
 
 #[cfg(test)]
 mod tests_get_strategy {
@@ -4,7 +4,7 @@
 // indices (fixed-length buffers, just-pushed slices, etc.).
 #![allow(clippy::indexing_slicing)]
 
-use super::base::{BreakEvenable, Positionable, StrategyType};
+use super::base::{BreakEvenable, Positionable, StrategyBasics, StrategyType};
 use crate::backtesting::results::{SimulationResult, SimulationStatsResult};
 
 use crate::chains::OptionChain;
@@ -771,7 +771,15 @@ where
     }
 }
 
-impl Strategable for LongPut {}
+impl Strategable for LongPut {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
 
 test_strategy_traits!(LongPut, test_long_put_implementations);
 
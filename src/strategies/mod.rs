@@ -16,20 +16,39 @@
 //! - `base`: Provides the base traits and structures for the strategies.
 //! - `bear_call_spread`: Implements the Bear Call Spread strategy.
 //! - `bear_put_spread`: Implements the Bear Put Spread strategy.
+//! - `box_spread`: Implements the Box Spread strategy.
 //! - `bull_call_spread`: Implements the Bull Call Spread strategy.
 //! - `bull_put_spread`: Implements the Bull Put Spread strategy.
 //! - `butterfly_spread`: Implements the Butterfly Spread strategy.
+//! - `call_back_spread`: Implements the Call Back Spread strategy.
+//! - `call_broken_wing_butterfly`: Implements the Call Broken Wing Butterfly strategy.
 //! - `call_butterfly`: Implements the Call Butterfly strategy.
+//! - `call_condor`: Implements the Call Condor strategy.
 //! - `collar`: Implements the Collar strategy.
 //! - `covered_call`: Implements the Covered Call strategy.
 //! - `custom`: Provides utilities for creating custom strategies.
+//! - `diagonal_spread`: Implements the Diagonal Spread strategy.
+//! - `double_calendar_spread`: Implements the Double Calendar Spread strategy.
 //! - `iron_butterfly`: Implements the Iron Butterfly strategy.
 //! - `iron_condor`: Implements the Iron Condor strategy.
+//! - `jade_lizard`: Implements the Jade Lizard strategy.
+//! - `long_calendar_spread`: Implements the Long Calendar Spread strategy.
+//! - `multi_objective`: Ranks candidate strategies on several objectives under constraints.
 //! - `poor_mans_covered_call`: Implements the Poor Man's Covered Call strategy.
 //! - `probabilities`: Provides probability calculations for the strategies.
 //! - `protective_put`: Implements the Protective Put strategy.
+//! - `put_back_spread`: Implements the Put Back Spread strategy.
+//! - `put_broken_wing_butterfly`: Implements the Put Broken Wing Butterfly strategy.
+//! - `put_condor`: Implements the Put Condor strategy.
+//! - `ratio_call_spread`: Implements the Ratio Call Spread strategy.
+//! - `ratio_put_spread`: Implements the Ratio Put Spread strategy.
+//! - `reverse_jade_lizard`: Implements the Reverse Jade Lizard strategy.
+//! - `risk_reversal`: Implements the Risk Reversal strategy.
+//! - `short_calendar_spread`: Implements the Short Calendar Spread strategy.
 //! - `straddle`: Implements the Straddle strategy.
 //! - `strangle`: Implements the Strangle strategy.
+//! - `strap`: Implements the Strap strategy.
+//! - `strip`: Implements the Strip strategy.
 //! - `utils`: Provides utility functions for the strategies.
 //!
 //! ## Usage
@@ -206,14 +225,22 @@ pub mod base;
 pub mod bear_call_spread;
 /// Bear Put Spread strategy implementation  
 pub mod bear_put_spread;
+/// Box Spread strategy implementation
+pub mod box_spread;
 /// Internal module for strategy building utilities
 mod build;
 /// Bull Call Spread strategy implementation
 pub mod bull_call_spread;
 /// Bull Put Spread strategy implementation
 pub mod bull_put_spread;
+/// Call Back Spread strategy implementation
+pub mod call_back_spread;
+/// Call Broken Wing Butterfly strategy implementation
+pub mod call_broken_wing_butterfly;
 /// Call Butterfly strategy implementation  
 pub mod call_butterfly;
+/// Call Condor strategy implementation
+pub mod call_condor;
 /// Collar strategy implementation
 pub mod collar;
 /// Covered Call strategy implementation
@@ -224,6 +251,10 @@ pub mod custom;
 pub mod default;
 /// Delta-neutral strategy implementation and utilities
 pub mod delta_neutral;
+/// Diagonal Spread strategy implementation
+pub mod diagonal_spread;
+/// Double Calendar Spread strategy implementation
+pub mod double_calendar_spread;
 
 /// The `graph` module provides functionality for creating, managing, and
 /// manipulating graph data structures. Common use cases include representing
@@ -244,12 +275,18 @@ pub mod delta_neutral;
 /// For details on available graph types, functionalities, and examples, refer
 /// to the corresponding methods and structs within the module.
 pub mod graph;
+/// Front-expiry valuation shared by multi-expiry strategies
+mod horizon;
 /// Iron Butterfly strategy implementation
 pub mod iron_butterfly;
 /// Iron Condor strategy implementation
 pub mod iron_condor;
+/// Jade Lizard strategy implementation
+pub mod jade_lizard;
 /// Butterfly Spread strategy implementation
 pub mod long_butterfly_spread;
+/// Long Calendar Spread strategy implementation
+pub mod long_calendar_spread;
 /// Long Call strategy implementation
 pub mod long_call;
 /// Long Put strategy implementation
@@ -260,16 +297,36 @@ pub mod long_straddle;
 pub mod long_strangle;
 /// Macros for options strategies
 pub mod macros;
+/// Multi-objective ranking of candidate strategies
+pub mod multi_objective;
+/// Closed-form expiry analysis shared by single-expiry strategies
+mod piecewise;
 /// Poor Man's Covered Call strategy implementation
 pub mod poor_mans_covered_call;
 /// Probability calculations for options strategies
 pub mod probabilities;
 /// Protective Put strategy implementation
 pub mod protective_put;
+/// Put Back Spread strategy implementation
+pub mod put_back_spread;
+/// Put Broken Wing Butterfly strategy implementation
+pub mod put_broken_wing_butterfly;
+/// Put Condor strategy implementation
+pub mod put_condor;
+/// Ratio Call Spread strategy implementation
+pub mod ratio_call_spread;
+/// Ratio Put Spread strategy implementation
+pub mod ratio_put_spread;
+/// Reverse Jade Lizard strategy implementation
+pub mod reverse_jade_lizard;
+/// Risk Reversal strategy implementation
+pub mod risk_reversal;
 /// Shared traits for strategy categories
 pub mod shared;
 /// Short Call strategy implementation
 pub mod short_butterfly_spread;
+/// Short Calendar Spread strategy implementation
+pub mod short_calendar_spread;
 /// Short Call strategy implementation
 pub mod short_call;
 /// Short Put strategy implementation
@@ -278,17 +335,25 @@ pub mod short_put;
 pub mod short_straddle;
 /// Short Strangle strategy implementation
 pub mod short_strangle;
+/// Strap strategy implementation
+pub mod strap;
+/// Strip strategy implementation
+pub mod strip;
 /// Utility functions for options calculations and analysis
 pub mod utils;
 
 pub use base::{BasicAble, Strategable, Strategies, StrategyBasics, Validable};
 pub use bear_call_spread::BearCallSpread;
 pub use bear_put_spread::BearPutSpread;
+pub use box_spread::BoxSpread;
 pub use build::model::StrategyRequest;
 pub use build::traits::StrategyConstructor;
 pub use bull_call_spread::BullCallSpread;
 pub use bull_put_spread::BullPutSpread;
+pub use call_back_spread::CallBackSpread;
+pub use call_broken_wing_butterfly::CallBrokenWingButterfly;
 pub use call_butterfly::CallButterfly;
+pub use call_condor::CallCondor;
 pub use collar::Collar;
 pub use covered_call::CoveredCall;
 pub use delta_neutral::{
@@ -296,23 +361,40 @@ pub use delta_neutral::{
     AdjustmentTarget, DELTA_THRESHOLD, DeltaAdjustment, DeltaInfo, DeltaNeutrality,
     PortfolioGreeks,
 };
+pub use diagonal_spread::DiagonalSpread;
+pub use double_calendar_spread::DoubleCalendarSpread;
 pub use iron_butterfly::IronButterfly;
 pub use iron_condor::IronCondor;
+pub use jade_lizard::JadeLizard;
 pub use long_butterfly_spread::LongButterflySpread;
+pub use long_calendar_spread::LongCalendarSpread;
 pub use long_call::LongCall;
 pub use long_put::LongPut;
 pub use long_straddle::LongStraddle;
 pub use long_strangle::LongStrangle;
+pub use multi_objective::{
+    MultiObjectiveOptimizer, Objective, ObjectiveFn, OptimizationConstraints, RankedCandidate,
+};
 pub use poor_mans_covered_call::PoorMansCoveredCall;
 pub use protective_put::ProtectivePut;
+pub use put_back_spread::PutBackSpread;
+pub use put_broken_wing_butterfly::PutBrokenWingButterfly;
+pub use put_condor::PutCondor;
+pub use ratio_call_spread::RatioCallSpread;
+pub use ratio_put_spread::RatioPutSpread;
+pub use reverse_jade_lizard::ReverseJadeLizard;
+pub use risk_reversal::RiskReversal;
 pub use shared::{
     ButterflyStrategy, CondorStrategy, SpreadStrategy, StraddleStrategy, StrangleStrategy,
     aggregate_fees, aggregate_premiums, calculate_profit_ratio, credit_spread_break_even,
     debit_spread_break_even,
 };
 pub use short_butterfly_spread::ShortButterflySpread;
+pub use short_calendar_spread::ShortCalendarSpread;
 pub use short_call::ShortCall;
 pub use short_put::ShortPut;
 pub use short_straddle::ShortStraddle;
 pub use short_strangle::ShortStrangle;
+pub use strap::Strap;
+pub use strip::Strip;
 pub use utils::FindOptimalSide;
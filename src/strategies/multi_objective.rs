@@ -0,0 +1,634 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # Multi-Objective Strategy Optimization
+//!
+//! [`Optimizable::find_optimal`] scans a chain for the single candidate that
+//! scores best under one [`OptimizationCriteria`](super::utils::OptimizationCriteria)
+//! and writes it into the strategy. The [`MultiObjectiveOptimizer`] in this
+//! module instead scores every candidate on several [`Objective`]s, discards
+//! those that break the hard [`OptimizationConstraints`] and returns the rest
+//! ranked by Pareto front, so a screen can show the top-N combinations with
+//! their scores.
+//!
+//! Candidates are generated with the strategy's own
+//! [`Optimizable::filter_combinations`] and
+//! [`Optimizable::create_strategy`], so only strategies that implement those
+//! two methods produce candidates from a chain. Strategies built elsewhere,
+//! for example across the expirations of a series, can be ranked with
+//! [`MultiObjectiveOptimizer::rank`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use optionstratlib::chains::chain::OptionChain;
+//! use optionstratlib::strategies::{
+//!     BullCallSpread, FindOptimalSide, MultiObjectiveOptimizer, Objective,
+//!     OptimizationConstraints,
+//! };
+//! use optionstratlib::ExpirationDate;
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal_macros::dec;
+//!
+//! let chain = OptionChain::load_from_json("examples/Chains/SP500-18-oct-2024-5781.88.json")?;
+//! let template = BullCallSpread::new(
+//!     "SP500".to_string(),
+//!     chain.underlying_price,
+//!     pos_or_panic!(5750.0),
+//!     pos_or_panic!(5800.0),
+//!     ExpirationDate::Days(pos_or_panic!(2.0)),
+//!     pos_or_panic!(0.18),
+//!     dec!(0.05),
+//!     Positive::ZERO,
+//!     Positive::ONE,
+//!     pos_or_panic!(60.0),
+//!     pos_or_panic!(30.0),
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//! )?;
+//! let optimizer = MultiObjectiveOptimizer::new(vec![
+//!     Objective::ProbabilityOfProfit,
+//!     Objective::ExpectedValue,
+//! ])
+//! .with_constraints(OptimizationConstraints::default().with_max_loss(pos_or_panic!(50.0)))
+//! .with_top_n(10);
+//!
+//! let ranked = optimizer.optimize(
+//!     &template,
+//!     &chain,
+//!     FindOptimalSide::Range(pos_or_panic!(5700.0), pos_or_panic!(5850.0)),
+//! )?;
+//! assert!(ranked.len() <= 10);
+//! for candidate in &ranked {
+//!     println!("front {}: {:?}", candidate.front, candidate.scores);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::chains::utils::OptionDataGroup;
+use crate::error::StrategyError;
+use crate::greeks::Greeks;
+use crate::model::types::OptionStyle;
+use crate::strategies::base::Optimizable;
+use crate::strategies::probabilities::ProbabilityAnalysis;
+use crate::strategies::utils::FindOptimalSide;
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::fmt;
+use tracing::debug;
+
+/// Scoring function of an [`Objective::Custom`].
+pub type ObjectiveFn<S> = Box<dyn Fn(&S) -> Result<Decimal, StrategyError> + Send + Sync>;
+
+/// A quantity the optimizer maximizes. Objectives that should be minimized
+/// can be expressed as a [`Objective::Custom`] returning the negated value.
+pub enum Objective<S> {
+    /// Probability-weighted P&L at expiration, from
+    /// [`ProbabilityAnalysis::expected_value`].
+    ExpectedValue,
+    /// Probability that the strategy finishes with a profit, from
+    /// [`ProbabilityAnalysis::probability_of_profit`].
+    ProbabilityOfProfit,
+    /// Expected value per unit of capital at risk, taking the maximum loss
+    /// as the margin of a defined-risk position.
+    ReturnOnMargin,
+    /// Kelly fraction `p - (1 - p) / b`, with `p` the probability of profit
+    /// and `b` the maximum profit over the maximum loss.
+    KellyFraction,
+    /// The strategy's profit ratio, as used by `OptimizationCriteria::Ratio`.
+    ProfitRatio,
+    /// The strategy's profit area, as used by `OptimizationCriteria::Area`.
+    ProfitArea,
+    /// A user-supplied score.
+    Custom {
+        /// Name reported for the objective
+        name: String,
+        /// Scoring function
+        score: ObjectiveFn<S>,
+    },
+}
+
+impl<S> Objective<S> {
+    /// Builds a [`Objective::Custom`] from a closure.
+    pub fn custom<F>(name: impl Into<String>, score: F) -> Self
+    where
+        F: Fn(&S) -> Result<Decimal, StrategyError> + Send + Sync + 'static,
+    {
+        Objective::Custom {
+            name: name.into(),
+            score: Box::new(score),
+        }
+    }
+
+    /// Short name of the objective.
+    pub fn name(&self) -> &str {
+        match self {
+            Objective::ExpectedValue => "ExpectedValue",
+            Objective::ProbabilityOfProfit => "ProbabilityOfProfit",
+            Objective::ReturnOnMargin => "ReturnOnMargin",
+            Objective::KellyFraction => "KellyFraction",
+            Objective::ProfitRatio => "ProfitRatio",
+            Objective::ProfitArea => "ProfitArea",
+            Objective::Custom { name, .. } => name,
+        }
+    }
+}
+
+impl<S> fmt::Debug for Objective<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl<S: ProbabilityAnalysis> Objective<S> {
+    /// Scores `strategy` on this objective.
+    ///
+    /// # Errors
+    ///
+    /// Propagates the errors of the underlying profit, loss and probability
+    /// calculations, and returns `StrategyError::OperationError` when the
+    /// maximum loss needed by `ReturnOnMargin` or `KellyFraction` is zero.
+    pub fn evaluate(&self, strategy: &S) -> Result<Decimal, StrategyError> {
+        match self {
+            Objective::ExpectedValue => Ok(strategy.expected_value(None, None)?.to_dec()),
+            Objective::ProbabilityOfProfit => {
+                Ok(strategy.probability_of_profit(None, None)?.to_dec())
+            }
+            Objective::ReturnOnMargin => {
+                let margin = nonzero_max_loss(strategy, "ReturnOnMargin")?;
+                Ok(strategy.expected_value(None, None)?.to_dec() / margin)
+            }
+            Objective::KellyFraction => {
+                let loss = nonzero_max_loss(strategy, "KellyFraction")?;
+                let p = strategy.probability_of_profit(None, None)?.to_dec();
+                let payoff = strategy.get_max_profit()?.to_dec() / loss;
+                if payoff.is_zero() {
+                    return Ok(Decimal::NEGATIVE_ONE);
+                }
+                Ok(p - (Decimal::ONE - p) / payoff)
+            }
+            Objective::ProfitRatio => strategy.get_profit_ratio(),
+            Objective::ProfitArea => strategy.get_profit_area(),
+            Objective::Custom { score, .. } => score(strategy),
+        }
+    }
+}
+
+fn nonzero_max_loss<S: ProbabilityAnalysis>(
+    strategy: &S,
+    objective: &str,
+) -> Result<Decimal, StrategyError> {
+    let loss = strategy.get_max_loss()?.to_dec();
+    if loss.is_zero() {
+        return Err(StrategyError::invalid_parameters(
+            objective,
+            "maximum loss is zero",
+        ));
+    }
+    Ok(loss)
+}
+
+/// Hard limits a candidate must meet to be ranked. Unset limits are not
+/// checked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OptimizationConstraints {
+    /// Largest acceptable maximum loss; unlimited-loss strategies always fail it
+    pub max_loss: Option<Positive>,
+    /// Largest acceptable absolute net delta
+    pub max_net_delta: Option<Positive>,
+    /// Smallest acceptable net premium received
+    pub min_credit: Option<Positive>,
+    /// Smallest open interest accepted on every quote used
+    pub min_open_interest: Option<u64>,
+    /// Widest bid-ask spread accepted on the quote of every leg
+    pub max_bid_ask_spread: Option<Positive>,
+}
+
+impl OptimizationConstraints {
+    /// Sets the maximum acceptable loss.
+    pub fn with_max_loss(mut self, max_loss: Positive) -> Self {
+        self.max_loss = Some(max_loss);
+        self
+    }
+
+    /// Sets the maximum acceptable absolute net delta.
+    pub fn with_max_net_delta(mut self, max_net_delta: Positive) -> Self {
+        self.max_net_delta = Some(max_net_delta);
+        self
+    }
+
+    /// Sets the minimum net credit.
+    pub fn with_min_credit(mut self, min_credit: Positive) -> Self {
+        self.min_credit = Some(min_credit);
+        self
+    }
+
+    /// Sets the minimum open interest of every quote.
+    pub fn with_min_open_interest(mut self, min_open_interest: u64) -> Self {
+        self.min_open_interest = Some(min_open_interest);
+        self
+    }
+
+    /// Sets the maximum bid-ask spread of every leg.
+    pub fn with_max_bid_ask_spread(mut self, max_bid_ask_spread: Positive) -> Self {
+        self.max_bid_ask_spread = Some(max_bid_ask_spread);
+        self
+    }
+
+    /// Whether `strategy` meets the loss, delta and credit limits. Values
+    /// that cannot be computed count as violations.
+    pub fn allows_strategy<S: Optimizable + Greeks>(&self, strategy: &S) -> bool {
+        let loss_ok = self.max_loss.is_none_or(|limit| {
+            strategy
+                .get_max_loss()
+                .is_ok_and(|loss| loss != Positive::MAX && loss <= limit)
+        });
+        let delta_ok = self.max_net_delta.is_none_or(|limit| {
+            strategy
+                .delta()
+                .is_ok_and(|delta| delta.abs() <= limit.to_dec())
+        });
+        let credit_ok = self.min_credit.is_none_or(|limit| {
+            strategy
+                .get_net_premium_received()
+                .is_ok_and(|credit| credit >= limit)
+        });
+        loss_ok && delta_ok && credit_ok
+    }
+
+    /// Whether the quotes a strategy was built from meet the open-interest
+    /// and bid-ask limits. The spread is measured on the side (call or put)
+    /// of each leg; a missing bid, ask or open interest is a violation.
+    pub fn allows_quotes<S: Optimizable>(&self, strategy: &S, quotes: &[&OptionData]) -> bool {
+        let interest_ok = self.min_open_interest.is_none_or(|limit| {
+            quotes
+                .iter()
+                .all(|quote| quote.open_interest.is_some_and(|oi| oi >= limit))
+        });
+        let spread_ok = self.max_bid_ask_spread.is_none_or(|limit| {
+            strategy.get_positions().is_ok_and(|positions| {
+                positions.iter().all(|position| {
+                    let option = &position.option;
+                    quotes
+                        .iter()
+                        .find(|quote| quote.strike_price == option.strike_price)
+                        .and_then(|quote| bid_ask_spread(quote, option.option_style))
+                        .is_some_and(|spread| spread <= limit.to_dec())
+                })
+            })
+        });
+        interest_ok && spread_ok
+    }
+}
+
+fn bid_ask_spread(quote: &OptionData, style: OptionStyle) -> Option<Decimal> {
+    let (bid, ask) = match style {
+        OptionStyle::Call => (quote.call_bid?, quote.call_ask?),
+        OptionStyle::Put => (quote.put_bid?, quote.put_ask?),
+    };
+    Some(ask.to_dec() - bid.to_dec())
+}
+
+fn group_quotes<'a>(group: &OptionDataGroup<'a>) -> Vec<&'a OptionData> {
+    match group {
+        OptionDataGroup::One(first) => vec![*first],
+        OptionDataGroup::Two(first, second) => vec![*first, *second],
+        OptionDataGroup::Three(first, second, third) => vec![*first, *second, *third],
+        OptionDataGroup::Four(first, second, third, fourth) => {
+            vec![*first, *second, *third, *fourth]
+        }
+        OptionDataGroup::Any(quotes) => quotes.clone(),
+    }
+}
+
+/// A candidate strategy with its objective scores and Pareto rank.
+#[derive(Debug, Clone)]
+pub struct RankedCandidate<S> {
+    /// The candidate strategy
+    pub strategy: S,
+    /// Scores in the order of the optimizer's objectives
+    pub scores: Vec<Decimal>,
+    /// Pareto front, `0` for candidates no other candidate dominates
+    pub front: usize,
+}
+
+/// Scores candidate strategies on several objectives under hard
+/// constraints and ranks them by Pareto front.
+///
+/// Within a front, candidates are ordered by their scores compared
+/// objective by objective, so the first objective breaks ties first.
+#[derive(Debug)]
+pub struct MultiObjectiveOptimizer<S> {
+    /// Objectives to maximize
+    pub objectives: Vec<Objective<S>>,
+    /// Hard constraints every ranked candidate satisfies
+    pub constraints: OptimizationConstraints,
+    /// Maximum number of candidates returned, all of them if `None`
+    pub top_n: Option<usize>,
+}
+
+impl<S> MultiObjectiveOptimizer<S> {
+    /// Creates an optimizer for `objectives` with no constraints and no limit
+    /// on the number of results.
+    pub fn new(objectives: Vec<Objective<S>>) -> Self {
+        MultiObjectiveOptimizer {
+            objectives,
+            constraints: OptimizationConstraints::default(),
+            top_n: None,
+        }
+    }
+
+    /// Sets the hard constraints.
+    pub fn with_constraints(mut self, constraints: OptimizationConstraints) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Limits the result to the `top_n` best candidates.
+    pub fn with_top_n(mut self, top_n: usize) -> Self {
+        self.top_n = Some(top_n);
+        self
+    }
+}
+
+impl<S> MultiObjectiveOptimizer<S>
+where
+    S: Optimizable<Strategy = S> + ProbabilityAnalysis + Greeks,
+{
+    /// Builds every candidate `template` offers from `chain` for `side`,
+    /// keeps those that are valid and meet the constraints, and returns them
+    /// ranked. `template` supplies the expiration, quantity, rates and fees
+    /// of the candidates and is not modified.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if no objective is set.
+    pub fn optimize(
+        &self,
+        template: &S,
+        chain: &OptionChain,
+        side: FindOptimalSide,
+    ) -> Result<Vec<RankedCandidate<S>>, StrategyError> {
+        self.check_objectives()?;
+        let candidates = template
+            .filter_combinations(chain, side)
+            .filter_map(|group| {
+                let legs = group.strategy_legs()?;
+                let strategy = match template.create_strategy(chain, &legs) {
+                    Ok(strategy) => strategy,
+                    Err(e) => {
+                        debug!(error = %e, "skipping invalid strategy combination");
+                        return None;
+                    }
+                };
+                self.constraints
+                    .allows_quotes(&strategy, &group_quotes(&group))
+                    .then_some(strategy)
+            });
+        Ok(self.rank_candidates(candidates))
+    }
+
+    /// Ranks strategies built by the caller. Only the loss, delta and credit
+    /// constraints are checked, since the quotes behind each strategy are
+    /// not known.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if no objective is set.
+    pub fn rank<I>(&self, strategies: I) -> Result<Vec<RankedCandidate<S>>, StrategyError>
+    where
+        I: IntoIterator<Item = S>,
+    {
+        self.check_objectives()?;
+        Ok(self.rank_candidates(strategies.into_iter()))
+    }
+
+    fn check_objectives(&self) -> Result<(), StrategyError> {
+        if self.objectives.is_empty() {
+            return Err(StrategyError::invalid_parameters(
+                "MultiObjectiveOptimizer",
+                "at least one objective is required",
+            ));
+        }
+        Ok(())
+    }
+
+    fn score(&self, strategy: &S) -> Option<Vec<Decimal>> {
+        self.objectives
+            .iter()
+            .map(|objective| match objective.evaluate(strategy) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    debug!(objective = objective.name(), error = %e, "skipping unscorable candidate");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn rank_candidates(&self, strategies: impl Iterator<Item = S>) -> Vec<RankedCandidate<S>> {
+        let scored: Vec<(S, Vec<Decimal>)> = strategies
+            .filter(|strategy| strategy.validate() && self.constraints.allows_strategy(strategy))
+            .filter_map(|strategy| {
+                let scores = self.score(&strategy)?;
+                Some((strategy, scores))
+            })
+            .collect();
+        let score_sets: Vec<&[Decimal]> = scored.iter().map(|(_, s)| s.as_slice()).collect();
+        let fronts = pareto_fronts(&score_sets);
+
+        let mut ranked: Vec<RankedCandidate<S>> = scored
+            .into_iter()
+            .zip(fronts)
+            .map(|((strategy, scores), front)| RankedCandidate {
+                strategy,
+                scores,
+                front,
+            })
+            .collect();
+        ranked.sort_by(|a, b| a.front.cmp(&b.front).then_with(|| b.scores.cmp(&a.scores)));
+        if let Some(top_n) = self.top_n {
+            ranked.truncate(top_n);
+        }
+        ranked
+    }
+}
+
+/// Whether `a` is at least as good as `b` on every objective and strictly
+/// better on one.
+fn dominates(a: &[Decimal], b: &[Decimal]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+}
+
+/// Pareto front of every score vector: `0` for the non-dominated ones, `1`
+/// for those only dominated by front `0`, and so on.
+fn pareto_fronts(scores: &[&[Decimal]]) -> Vec<usize> {
+    let mut fronts: Vec<Option<usize>> = vec![None; scores.len()];
+    let mut level = 0;
+    while fronts.iter().any(Option::is_none) {
+        let open: Vec<&[Decimal]> = scores
+            .iter()
+            .zip(&fronts)
+            .filter(|(_, front)| front.is_none())
+            .map(|(s, _)| *s)
+            .collect();
+        for (candidate, front) in scores.iter().zip(fronts.iter_mut()) {
+            if front.is_none() && !open.iter().any(|other| dominates(other, candidate)) {
+                *front = Some(level);
+            }
+        }
+        level += 1;
+    }
+    fronts.into_iter().map(|f| f.unwrap_or(level)).collect()
+}
+
+#[cfg(test)]
+mod tests_multi_objective {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::strategies::base::Positionable;
+    use crate::strategies::{BullCallSpread, Strategies};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn chain() -> OptionChain {
+        OptionChain::build_chain(&OptionChainBuildParams::new(
+            "SPY".to_string(),
+            None,
+            6,
+            spos!(2.5),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(100.0))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("SPY".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        ))
+        .unwrap()
+    }
+
+    fn template() -> BullCallSpread {
+        BullCallSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(6.5),
+            pos_or_panic!(1.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pareto_fronts() {
+        let a = [dec!(3), dec!(1)];
+        let b = [dec!(1), dec!(3)];
+        let c = [dec!(1), dec!(1)];
+        let d = [dec!(0), dec!(0)];
+        let fronts = pareto_fronts(&[&a, &b, &c, &d]);
+        assert_eq!(fronts, vec![0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_optimize_ranks_by_front() {
+        let chain = chain();
+        let optimizer = MultiObjectiveOptimizer::new(vec![
+            Objective::ProbabilityOfProfit,
+            Objective::ProfitRatio,
+        ]);
+        let ranked = optimizer
+            .optimize(&template(), &chain, FindOptimalSide::All)
+            .unwrap();
+        assert!(ranked.len() > 1);
+        assert_eq!(ranked[0].front, 0);
+        assert!(ranked.windows(2).all(|w| w[0].front <= w[1].front));
+        for candidate in ranked.iter().filter(|c| c.front == 0) {
+            assert_eq!(candidate.scores.len(), 2);
+            assert!(
+                !ranked
+                    .iter()
+                    .any(|other| dominates(&other.scores, &candidate.scores))
+            );
+        }
+    }
+
+    #[test]
+    fn test_optimize_applies_constraints_and_top_n() {
+        let chain = chain();
+        let limit = pos_or_panic!(4.0);
+        let optimizer = MultiObjectiveOptimizer::new(vec![Objective::ExpectedValue])
+            .with_constraints(OptimizationConstraints::default().with_max_loss(limit))
+            .with_top_n(3);
+        let ranked = optimizer
+            .optimize(&template(), &chain, FindOptimalSide::All)
+            .unwrap();
+        assert!(!ranked.is_empty() && ranked.len() <= 3);
+        for candidate in &ranked {
+            assert!(candidate.strategy.get_max_loss().unwrap() <= limit);
+        }
+        assert!(ranked.windows(2).all(|w| w[0].scores >= w[1].scores));
+
+        let illiquid = MultiObjectiveOptimizer::new(vec![Objective::ExpectedValue])
+            .with_constraints(OptimizationConstraints::default().with_min_open_interest(u64::MAX));
+        assert!(
+            illiquid
+                .optimize(&template(), &chain, FindOptimalSide::All)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_custom_and_kelly_objectives() {
+        let optimizer = MultiObjectiveOptimizer::new(vec![
+            Objective::custom("NegativeWidth", |s: &BullCallSpread| {
+                let positions = s.get_positions()?;
+                let width: Decimal = positions
+                    .iter()
+                    .map(|p| p.option.strike_price.to_dec())
+                    .sum();
+                Ok(-width)
+            }),
+            Objective::KellyFraction,
+            Objective::ReturnOnMargin,
+        ]);
+        assert_eq!(
+            format!("{:?}", optimizer.objectives),
+            "[NegativeWidth, KellyFraction, ReturnOnMargin]"
+        );
+        let ranked = optimizer.rank(vec![template()]).unwrap();
+        assert_eq!(ranked.len(), 1);
+        let kelly = ranked[0].scores[1];
+        assert!(kelly < Decimal::ONE);
+    }
+
+    #[test]
+    fn test_optimizer_requires_objectives() {
+        let optimizer: MultiObjectiveOptimizer<BullCallSpread> =
+            MultiObjectiveOptimizer::new(vec![]);
+        assert!(optimizer.rank(vec![template()]).is_err());
+    }
+}
@@ -0,0 +1,507 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Closed-form analysis of single-expiry strategies.
+//!
+//! At expiration every leg pays a piecewise-linear function of the
+//! underlying price with a kink at its strike, so the strategy's P&L is
+//! piecewise linear with kinks at the strikes. Its break-even points,
+//! maximum profit and maximum loss follow exactly from the P&L at zero and
+//! at each strike plus the slopes beyond the outermost strikes, which is
+//! how the ratio, back-spread, lizard, condor, box and risk-reversal
+//! strategies compute them.
+
+use super::horizon;
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::error::probability::ProbabilityError;
+use crate::error::strategies::ProfitLossErrorKind;
+use crate::error::{PricingError, StrategyError};
+use crate::model::ProfitLossRange;
+use crate::model::decimal::finite_decimal;
+use crate::model::position::Position;
+use crate::model::types::{OptionBasicType, OptionStyle, Side};
+use crate::pnl::PnLCalculator;
+use crate::pnl::utils::PnL;
+use crate::strategies::utils::{FindOptimalSide, OptimizationCriteria};
+use crate::strategies::{Strategies, Validable};
+use crate::{ExpirationDate, Options};
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+
+/// Fraction of the spot added beyond the outermost strikes when bounding
+/// the area and ratio of strategies with unlimited profit or loss.
+const ANALYSIS_MARGIN: f64 = 0.25;
+
+/// Shared parameters from which the legs of a single-expiry strategy are
+/// built.
+pub(crate) struct LegFactory {
+    pub(crate) symbol: String,
+    pub(crate) underlying_price: Positive,
+    pub(crate) expiration: ExpirationDate,
+    pub(crate) implied_volatility: Positive,
+    pub(crate) risk_free_rate: Decimal,
+    pub(crate) dividend_yield: Positive,
+    pub(crate) open_fee: Positive,
+    pub(crate) close_fee: Positive,
+}
+
+impl LegFactory {
+    /// A European position on `quantity` contracts.
+    pub(crate) fn position(
+        &self,
+        option_style: OptionStyle,
+        side: Side,
+        strike: Positive,
+        quantity: Positive,
+        premium: Positive,
+    ) -> Position {
+        let option = Options::new(
+            crate::model::types::OptionType::European,
+            side,
+            self.symbol.clone(),
+            strike,
+            self.expiration,
+            self.implied_volatility,
+            quantity,
+            self.underlying_price,
+            self.risk_free_rate,
+            option_style,
+            self.dividend_yield,
+            None,
+        );
+        Position::new(
+            option,
+            premium,
+            chrono::Utc::now(),
+            self.open_fee,
+            self.close_fee,
+            None,
+            None,
+        )
+    }
+}
+
+/// Sorts `positions` by strike (calls before puts, longs before shorts at
+/// equal strikes) and checks them against `pattern`.
+///
+/// # Errors
+///
+/// Returns `StrategyError::OperationError` if the number of positions or
+/// any style or side differs from `pattern`.
+pub(crate) fn match_legs<const N: usize>(
+    positions: &[Position],
+    pattern: [(OptionStyle, Side); N],
+    operation: &str,
+) -> Result<[Position; N], StrategyError> {
+    let key = |p: &Position| {
+        (
+            p.option.strike_price,
+            p.option.option_style == OptionStyle::Put,
+            p.option.side == Side::Short,
+        )
+    };
+    let mut sorted = positions.to_vec();
+    sorted.sort_by_key(key);
+    let legs: [Position; N] = sorted.try_into().map_err(|_| {
+        StrategyError::invalid_parameters(operation, &format!("Must have exactly {N} options"))
+    })?;
+    for (leg, (style, side)) in legs.iter().zip(pattern) {
+        if leg.option.option_style != style || leg.option.side != side {
+            return Err(StrategyError::invalid_parameters(
+                operation,
+                &format!(
+                    "expected a {side:?} {style:?} at strike {}",
+                    leg.option.strike_price
+                ),
+            ));
+        }
+    }
+    Ok(legs)
+}
+
+/// P&L at expiration with the underlying at `price`.
+///
+/// # Errors
+///
+/// Propagates payoff errors of the legs.
+pub(crate) fn profit_at(
+    positions: &[&Position],
+    price: &Positive,
+) -> Result<Decimal, PricingError> {
+    let price = Some(price);
+    positions
+        .iter()
+        .map(|position| position.pnl_at_expiration(&price))
+        .sum()
+}
+
+/// Change of the expiry P&L per unit of underlying above the highest
+/// strike: the net number of long calls.
+fn slope_above(positions: &[&Position]) -> Decimal {
+    positions
+        .iter()
+        .filter(|p| p.option.option_style == OptionStyle::Call)
+        .map(|p| signed_quantity(p))
+        .sum()
+}
+
+fn signed_quantity(position: &Position) -> Decimal {
+    match position.option.side {
+        Side::Long => position.option.quantity.to_dec(),
+        Side::Short => -position.option.quantity.to_dec(),
+    }
+}
+
+/// `(price, P&L)` at zero and at every distinct strike, in increasing
+/// price order.
+fn nodes(positions: &[&Position]) -> Result<Vec<(Decimal, Decimal)>, PricingError> {
+    let mut prices: Vec<Positive> = positions.iter().map(|p| p.option.strike_price).collect();
+    prices.push(Positive::ZERO);
+    prices.sort();
+    prices.dedup();
+    prices
+        .into_iter()
+        .map(|price| Ok((price.to_dec(), profit_at(positions, &price)?)))
+        .collect()
+}
+
+/// Exact break-even points of the expiry P&L, rounded to two decimals.
+///
+/// # Errors
+///
+/// Propagates payoff errors of the legs.
+pub(crate) fn break_even_points(positions: &[&Position]) -> Result<Vec<Positive>, StrategyError> {
+    let nodes = nodes(positions)?;
+    let mut points = Vec::new();
+    for pair in nodes.windows(2) {
+        let &[(x0, y0), (x1, y1)] = pair else {
+            continue;
+        };
+        if y1 == Decimal::ZERO && y0 != Decimal::ZERO {
+            points.push(x1);
+        } else if y0 * y1 < Decimal::ZERO {
+            points.push(x0 - y0 * (x1 - x0) / (y1 - y0));
+        }
+    }
+    if let Some(&(x, y)) = nodes.last() {
+        let slope = slope_above(positions);
+        if y * slope < Decimal::ZERO {
+            points.push(x - y / slope);
+        }
+    }
+    points
+        .into_iter()
+        .map(|point| Ok(Positive::new_decimal(point)?.round_to(2)))
+        .collect()
+}
+
+/// Maximum profit at expiration, `Positive::MAX` when it is unlimited.
+///
+/// # Errors
+///
+/// Returns `StrategyError::ProfitLossError` when the strategy cannot make
+/// a profit at any price.
+pub(crate) fn max_profit(positions: &[&Position]) -> Result<Positive, StrategyError> {
+    if slope_above(positions) > Decimal::ZERO {
+        return Ok(Positive::MAX);
+    }
+    let best = nodes(positions)?
+        .into_iter()
+        .map(|(_, pnl)| pnl)
+        .max()
+        .unwrap_or(Decimal::ZERO);
+    if best <= Decimal::ZERO {
+        return Err(StrategyError::ProfitLossError(
+            ProfitLossErrorKind::MaxProfitError {
+                reason: "Max profit is negative".to_string(),
+            },
+        ));
+    }
+    Ok(Positive::new_decimal(best)?)
+}
+
+/// Maximum loss at expiration as a positive amount, `Positive::MAX` when
+/// it is unlimited.
+///
+/// # Errors
+///
+/// Returns `StrategyError::ProfitLossError` when the strategy cannot lose
+/// at any price.
+pub(crate) fn max_loss(positions: &[&Position]) -> Result<Positive, StrategyError> {
+    if slope_above(positions) < Decimal::ZERO {
+        return Ok(Positive::MAX);
+    }
+    let worst = nodes(positions)?
+        .into_iter()
+        .map(|(_, pnl)| pnl)
+        .min()
+        .unwrap_or(Decimal::ZERO);
+    if worst >= Decimal::ZERO {
+        return Err(StrategyError::ProfitLossError(
+            ProfitLossErrorKind::MaxLossError {
+                reason: "Max loss must be negative".to_string(),
+            },
+        ));
+    }
+    Ok(Positive::new_decimal(-worst)?)
+}
+
+/// `(price, P&L)` nodes extended by `ANALYSIS_MARGIN` of the spot beyond
+/// the highest strike, which bounds strategies with unlimited upside.
+fn bounded_nodes(positions: &[&Position]) -> Result<Vec<(Decimal, Decimal)>, PricingError> {
+    let mut nodes = nodes(positions)?;
+    let spot = positions
+        .first()
+        .map_or(Decimal::ZERO, |p| p.option.underlying_price.to_dec());
+    if let Some(&(x, y)) = nodes.last() {
+        let margin = spot * Decimal::try_from(ANALYSIS_MARGIN).unwrap_or(Decimal::ZERO);
+        nodes.push((x + margin, y + slope_above(positions) * margin));
+    }
+    Ok(nodes)
+}
+
+/// Area under the positive part of the expiry P&L between zero and the
+/// bounded upper edge, per unit of underlying price.
+///
+/// # Errors
+///
+/// Returns `StrategyError::NumericConversion` if the area is not finite.
+pub(crate) fn profit_area(positions: &[&Position]) -> Result<Decimal, StrategyError> {
+    let nodes = bounded_nodes(positions)?;
+    let mut area = Decimal::ZERO;
+    for pair in nodes.windows(2) {
+        let &[(x0, y0), (x1, y1)] = pair else {
+            continue;
+        };
+        area += if y0 >= Decimal::ZERO && y1 >= Decimal::ZERO {
+            (y0 + y1) * (x1 - x0) / Decimal::TWO
+        } else if y0 > Decimal::ZERO || y1 > Decimal::ZERO {
+            // One end is negative: keep the triangle above zero.
+            let top = y0.max(y1);
+            top * top * (x1 - x0) / ((y1 - y0).abs() * Decimal::TWO)
+        } else {
+            Decimal::ZERO
+        };
+    }
+    let spot = positions
+        .first()
+        .map_or(1.0, |p| p.option.underlying_price.to_f64());
+    let result = area.to_f64().unwrap_or(f64::NAN) / spot;
+    finite_decimal(result).ok_or_else(|| StrategyError::numeric_conversion(result))
+}
+
+/// Best profit over worst loss on the bounded price range, in percent
+/// (zero when the strategy cannot lose or cannot win).
+///
+/// # Errors
+///
+/// Returns `StrategyError::NumericConversion` if the ratio is not finite.
+pub(crate) fn profit_ratio(positions: &[&Position]) -> Result<Decimal, StrategyError> {
+    let nodes = bounded_nodes(positions)?;
+    let best = nodes.iter().map(|&(_, y)| y).max().unwrap_or(Decimal::ZERO);
+    let worst = nodes.iter().map(|&(_, y)| y).min().unwrap_or(Decimal::ZERO);
+    let result = if best > Decimal::ZERO && worst < Decimal::ZERO {
+        (best / -worst).to_f64().unwrap_or(f64::NAN) * 100.0
+    } else {
+        0.0
+    };
+    finite_decimal(result).ok_or_else(|| StrategyError::numeric_conversion(result))
+}
+
+/// Profit (`profit = true`) or loss ranges between the break-even points
+/// with the probability of the underlying finishing in each.
+///
+/// # Errors
+///
+/// Propagates range construction and probability errors.
+pub(crate) fn ranges(
+    positions: &[&Position],
+    break_even_points: &[Positive],
+    profit: bool,
+) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+    // With a single expiry the front expiry is the strategy's expiry.
+    horizon::front_expiry_ranges(positions, break_even_points, profit)
+}
+
+/// Whether the quotes of a candidate pass the optimiser's `side` filter.
+/// [`FindOptimalSide::Center`] accepts multi-strike candidates whose
+/// strikes bracket the spot, and single-strike ones at the money.
+pub(crate) fn quotes_in_side(
+    chain: &OptionChain,
+    quotes: &[&OptionData],
+    side: &FindOptimalSide,
+) -> bool {
+    match side {
+        FindOptimalSide::Center if quotes.len() > 1 => {
+            let spot = chain.underlying_price;
+            quotes.iter().any(|q| q.strike_price <= spot)
+                && quotes.iter().any(|q| q.strike_price >= spot)
+        }
+        other => quotes
+            .iter()
+            .all(|quote| horizon::strike_in_side(chain, quote, other)),
+    }
+}
+
+/// The valid candidate scoring best under `criteria`.
+pub(crate) fn select_best<S, I>(candidates: I, criteria: &OptimizationCriteria) -> Option<S>
+where
+    S: Strategies + Validable,
+    I: Iterator<Item = Result<S, StrategyError>>,
+{
+    let mut best: Option<(S, Decimal)> = None;
+    for candidate in candidates {
+        let strategy = match candidate {
+            Ok(s) if s.validate() => s,
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::debug!(error = %e, "skipping invalid strategy combination");
+                continue;
+            }
+        };
+        if let Some(value) = horizon::score(&strategy, criteria)
+            && best
+                .as_ref()
+                .is_none_or(|(_, best_value)| value > *best_value)
+        {
+            best = Some((strategy, value));
+        }
+    }
+    best.map(|(strategy, _)| strategy)
+}
+
+/// Sum of [`Position::calculate_pnl`] over `positions`.
+///
+/// # Errors
+///
+/// Propagates pricing errors of the legs.
+pub(crate) fn pnl(
+    positions: &[&Position],
+    market_price: &Positive,
+    expiration_date: ExpirationDate,
+    implied_volatility: &Positive,
+) -> Result<PnL, PricingError> {
+    positions
+        .iter()
+        .map(|position| position.calculate_pnl(market_price, expiration_date, implied_volatility))
+        .sum()
+}
+
+/// Sum of [`Position::calculate_pnl_at_expiration`] over `positions`.
+///
+/// # Errors
+///
+/// Propagates payoff errors of the legs.
+pub(crate) fn pnl_at_expiration(
+    positions: &[&Position],
+    underlying_price: &Positive,
+) -> Result<PnL, PricingError> {
+    positions
+        .iter()
+        .map(|position| position.calculate_pnl_at_expiration(underlying_price))
+        .sum()
+}
+
+/// Premium of a leg of `style` traded on `side` at `quote`.
+///
+/// # Errors
+///
+/// Returns `StrategyError::OperationError` when the needed bid or ask is
+/// missing.
+pub(crate) fn premium(
+    quote: &OptionData,
+    style: OptionStyle,
+    side: Side,
+) -> Result<Positive, StrategyError> {
+    horizon::leg_premium(quote, style, side).ok_or_else(|| {
+        StrategyError::operation_not_supported(
+            "create_strategy",
+            &format!("missing {side:?} {style:?} quote at {}", quote.strike_price),
+        )
+    })
+}
+
+fn basic_type(position: &Position) -> OptionBasicType<'_> {
+    OptionBasicType {
+        option_style: &position.option.option_style,
+        side: &position.option.side,
+        strike_price: &position.option.strike_price,
+        expiration_date: &position.option.expiration_date,
+    }
+}
+
+/// `BasicAble::get_option_basic_type` over `legs`.
+pub(crate) fn basic_types<'a>(legs: &[&'a Position]) -> HashSet<OptionBasicType<'a>> {
+    legs.iter().map(|&leg| basic_type(leg)).collect()
+}
+
+/// `BasicAble::get_implied_volatility` over `legs`.
+pub(crate) fn implied_volatilities<'a>(
+    legs: &[&'a Position],
+) -> HashMap<OptionBasicType<'a>, &'a Positive> {
+    legs.iter()
+        .map(|&leg| (basic_type(leg), &leg.option.implied_volatility))
+        .collect()
+}
+
+/// `BasicAble::get_quantity` over `legs`.
+pub(crate) fn quantities<'a>(legs: &[&'a Position]) -> HashMap<OptionBasicType<'a>, &'a Positive> {
+    legs.iter()
+        .map(|&leg| (basic_type(leg), &leg.option.quantity))
+        .collect()
+}
+
+/// Applies `update` to every leg and reprices its premium with
+/// Black–Scholes.
+///
+/// # Errors
+///
+/// Propagates Black–Scholes pricing errors.
+pub(crate) fn reprice_legs<F>(legs: &mut [&mut Position], update: F) -> Result<(), StrategyError>
+where
+    F: Fn(&mut Options),
+{
+    for leg in legs.iter_mut() {
+        update(&mut leg.option);
+        leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+            .unwrap_or(Positive::ZERO);
+    }
+    Ok(())
+}
+
+/// The legs among `legs` matching style, side and strike.
+///
+/// # Errors
+///
+/// Returns `PositionError` when no leg matches.
+pub(crate) fn find_legs<'a>(
+    legs: Vec<&'a mut Position>,
+    option_style: &OptionStyle,
+    side: &Side,
+    strike: &Positive,
+) -> Result<Vec<&'a mut Position>, crate::error::position::PositionError> {
+    let found: Vec<&mut Position> = legs
+        .into_iter()
+        .filter(|leg| {
+            leg.option.option_style == *option_style
+                && leg.option.side == *side
+                && leg.option.strike_price == *strike
+        })
+        .collect();
+    if found.is_empty() {
+        Err(
+            crate::error::position::PositionError::invalid_position_type(
+                *side,
+                "Strike not found in positions".to_string(),
+            ),
+        )
+    } else {
+        Ok(found)
+    }
+}
@@ -0,0 +1,671 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Put Back Spread (put ratio backspread) sells puts at a higher strike and buys a larger
+//! number of puts at a lower strike. It is the bearish mirror of the call back spread: the short puts
+//! finance the long puts and the position is net long puts below the lower strike.
+//!
+//! The strategy has two components:
+//! 1. **Long lower-strike puts**: More contracts than the short leg, gaining as the underlying falls.
+//! 2. **Short higher-strike puts**: Finance the trade and cap the gain between the strikes.
+//!
+//! The worst outcome is the underlying finishing at the lower strike, where the short puts are in the
+//! money and the long puts expire worthless. Above the upper strike the result is the net premium.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::piecewise::{self, LegFactory};
+use crate::{
+    ExpirationDate, Options,
+    chains::{StrategyLegs, chain::OptionChain, utils::OptionDataGroup},
+    error::{
+        GreeksError, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+
+pub(super) const PUT_BACK_SPREAD_DESCRIPTION: &str = "A Put Back Spread sells puts at a higher strike and buys more puts \
+    at a lower strike. It gains strongly in a sell-off, has limited risk that peaks at the \
+    lower strike and keeps the net credit if the underlying rises.";
+
+/// # PutBackSpread
+///
+/// Represents a Put Back Spread: a larger number of long puts at the lower strike and short puts at
+/// the upper strike.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::PutBackSpread`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The underlying prices at expiration at which the strategy neither makes nor loses money.
+/// * `long_put`: The long puts at the lower strike.
+/// * `short_put`: The short puts at the upper strike.
+///
+/// ## Risk and Reward
+/// The maximum profit is reached with the underlying at zero, where the excess long puts pay their
+/// full strike. The maximum loss is the width between the strikes times the short quantity less the
+/// net credit (or plus the net debit), reached with the underlying at the lower strike.
+///
+/// ## Break-Even Points
+/// There is a lower break-even point below the long strike and, when the spread is opened for a
+/// credit, an upper one between the strikes.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct PutBackSpread {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a PutBackSpread strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Prices at expiration where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The long puts at the lower strike
+    pub(super) long_put: Position,
+    /// The short puts at the upper strike
+    pub(super) short_put: Position,
+}
+
+impl PutBackSpread {
+    /// # Creates a new Put Back Spread strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `lower_strike`: Strike price of the long puts
+    /// * `upper_strike`: Strike price of the short puts
+    /// * `expiration`: Expiration date shared by all the options
+    /// * `implied_volatility`: Implied volatility used to price the options
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts of the short puts at the upper strike
+    /// * `ratio`: Number of long puts bought per short put sold
+    /// * `premium_long_put`: Premium paid for each long put
+    /// * `premium_short_put`: Premium received for each short put
+    /// * `open_fee`: Transaction fee per contract for opening each position
+    /// * `close_fee`: Transaction fee per contract for closing each position
+    ///
+    /// ## Returns
+    /// A fully initialized `PutBackSpread` with its break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the parameters are invalid: the long strike must
+    /// be below the short strike and the long puts must outnumber the short puts.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        lower_strike: Positive,
+        upper_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        ratio: Positive,
+        premium_long_put: Positive,
+        premium_short_put: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let factory = LegFactory {
+            symbol: underlying_symbol,
+            underlying_price,
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            open_fee,
+            close_fee,
+        };
+        let mut strategy = PutBackSpread {
+            long_put: factory.position(
+                OptionStyle::Put,
+                Side::Long,
+                lower_strike,
+                quantity * ratio,
+                premium_long_put,
+            ),
+            short_put: factory.position(
+                OptionStyle::Put,
+                Side::Short,
+                upper_strike,
+                quantity,
+                premium_short_put,
+            ),
+            ..PutBackSpread::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Put Back Spread new",
+                "the long strike must be below the short strike and the long puts must outnumber the short puts",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    /// Number of long puts bought per short put sold, or zero for an empty strategy.
+    pub fn ratio(&self) -> Positive {
+        let base = self.short_put.option.quantity;
+        if base == Positive::ZERO {
+            Positive::ZERO
+        } else {
+            self.long_put.option.quantity / base
+        }
+    }
+
+    fn legs(&self) -> [&Position; 2] {
+        [&self.long_put, &self.short_put]
+    }
+
+    fn legs_mut(&mut self) -> [&mut Position; 2] {
+        [&mut self.long_put, &mut self.short_put]
+    }
+}
+
+impl StrategyConstructor for PutBackSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        let [long_put, short_put] = piecewise::match_legs(
+            vec_positions,
+            [
+                (OptionStyle::Put, Side::Long),
+                (OptionStyle::Put, Side::Short),
+            ],
+            "Put Back Spread get_strategy",
+        )?;
+        let mut strategy = PutBackSpread {
+            long_put,
+            short_put,
+            ..PutBackSpread::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Put Back Spread get_strategy",
+                "the long strike must be below the short strike and the long puts must outnumber the short puts",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for PutBackSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = piecewise::break_even_points(&self.legs())?;
+        Ok(())
+    }
+}
+
+impl Validable for PutBackSpread {
+    fn validate(&self) -> bool {
+        self.legs().iter().all(|leg| leg.validate())
+            && self.long_put.option.strike_price < self.short_put.option.strike_price
+            && self.long_put.option.quantity > self.short_put.option.quantity
+    }
+}
+
+impl Positionable for PutBackSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Put, Side::Long) => self.long_put = position.clone(),
+            (OptionStyle::Put, Side::Short) => self.short_put = position.clone(),
+            _ => {
+                return Err(PositionError::invalid_position_type(
+                    position.option.side,
+                    "Call is not part of a Put Back Spread".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(self.legs().to_vec())
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        piecewise::find_legs(self.legs_mut().into(), option_style, side, strike)
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for PutBackSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for PutBackSpread {
+    fn get_title(&self) -> String {
+        let legs: Vec<String> = self.legs().iter().map(|leg| leg.get_title()).collect();
+        format!("{:?} Strategy: \n\t{}", self.kind, legs.join("\n\t"))
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        piecewise::basic_types(&self.legs())
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::implied_volatilities(&self.legs())
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::quantities(&self.legs())
+    }
+    fn one_option(&self) -> &Options {
+        self.long_put.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.long_put.one_option_mut()
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        for leg in self.legs_mut() {
+            leg.option.expiration_date = expiration_date;
+        }
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.underlying_price = *price;
+        })
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.implied_volatility = *volatility;
+        })
+    }
+}
+
+impl Strategies for PutBackSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_profit(&self.legs())
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_loss(&self.legs())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_area(&self.legs())
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_ratio(&self.legs())
+    }
+}
+
+impl Optimizable for PutBackSpread {
+    type Strategy = PutBackSpread;
+
+    /// Combinations of two increasing strikes of `option_chain` that pass the
+    /// `side` filter.
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        option_chain
+            .get_double_iter()
+            .filter(move |(lower, upper)| {
+                piecewise::quotes_in_side(option_chain, &[lower, upper], &side)
+            })
+            .map(|(lower, upper)| OptionDataGroup::Two(lower, upper))
+    }
+
+    /// Searches every combination of two increasing strikes of `option_chain` for
+    /// the strategy that scores best under `criteria` and replaces `self`
+    /// with it.
+    ///
+    /// Long legs are bought at the ask and short legs sold at the bid, and the
+    /// expiration, quantity, ratio, rate and fees of `self` are kept.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let candidates = self
+            .filter_combinations(option_chain, side)
+            .filter_map(|group| group.strategy_legs())
+            .map(|legs| self.create_strategy(option_chain, &legs));
+        if let Some(strategy) = piecewise::select_best(candidates, &criteria) {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `PutBackSpread` from two quotes in increasing strike order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs have the wrong
+    /// shape, a needed bid or ask is missing, or the resulting strategy is
+    /// invalid.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (lower, upper) = match legs {
+            StrategyLegs::TwoLegs {
+                first: lower,
+                second: upper,
+            } => (lower, upper),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "PutBackSpread requires exactly two legs (TwoLegs)",
+                ));
+            }
+        };
+        PutBackSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            lower.strike_price,
+            upper.strike_price,
+            self.long_put.option.expiration_date,
+            lower.implied_volatility,
+            self.long_put.option.risk_free_rate,
+            self.long_put.option.dividend_yield,
+            self.short_put.option.quantity,
+            self.ratio(),
+            piecewise::premium(lower, OptionStyle::Put, Side::Long)?,
+            piecewise::premium(upper, OptionStyle::Put, Side::Short)?,
+            self.long_put.open_fee,
+            self.long_put.close_fee,
+        )
+    }
+}
+
+impl Profit for PutBackSpread {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        piecewise::profit_at(&self.legs(), price)
+    }
+}
+
+impl ProbabilityAnalysis for PutBackSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, true)
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, false)
+    }
+}
+
+impl Greeks for PutBackSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self.legs().into_iter().map(|leg| &leg.option).collect())
+    }
+}
+
+impl DeltaNeutrality for PutBackSpread {}
+
+impl PnLCalculator for PutBackSpread {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl(
+            &self.legs(),
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl_at_expiration(&self.legs(), underlying_price)
+    }
+}
+
+test_strategy_traits!(PutBackSpread, test_put_back_spread_implementations);
+
+#[cfg(test)]
+mod tests_put_back_spread {
+    use super::*;
+    use crate::assert_decimal_eq;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> PutBackSpread {
+        PutBackSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.0),
+            pos_or_panic!(2.0),
+            pos_or_panic!(7.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_put_back_spread_break_even_points() {
+        let strategy = create_strategy();
+        assert!(strategy.validate());
+        assert_eq!(strategy.break_even_points.len(), 2);
+        assert_decimal_eq!(
+            strategy.break_even_points[0].to_dec(),
+            dec!(88.0),
+            dec!(0.01)
+        );
+        assert_decimal_eq!(
+            strategy.break_even_points[1].to_dec(),
+            dec!(102.0),
+            dec!(0.01)
+        );
+        for point in &strategy.break_even_points {
+            let pnl = strategy.calculate_profit_at(point).unwrap();
+            assert!(pnl.abs() < dec!(0.05), "P&L {pnl} at break-even {point}");
+        }
+    }
+
+    #[test]
+    fn test_put_back_spread_max_profit_and_loss() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.get_max_profit().unwrap().to_dec(),
+            dec!(88.0),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.get_max_loss().unwrap().to_dec(),
+            dec!(7.0),
+            dec!(1e-9)
+        );
+    }
+
+    #[test]
+    fn test_put_back_spread_profit_at_expiration() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap(),
+            dec!(-2.0),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(85.0)).unwrap(),
+            dec!(3.0),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(115.0)).unwrap(),
+            dec!(3.0),
+            dec!(1e-9)
+        );
+        let pnl = strategy
+            .calculate_pnl_at_expiration(&pos_or_panic!(100.0))
+            .unwrap();
+        assert_eq!(
+            pnl.total_pnl(),
+            Some(strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_put_back_spread_rejects_invalid_parameters() {
+        let result = PutBackSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(95.0),
+            pos_or_panic!(105.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.0),
+            pos_or_panic!(2.0),
+            pos_or_panic!(7.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_put_back_spread_get_strategy_from_positions() {
+        let strategy = create_strategy();
+        let positions = vec![strategy.short_put.clone(), strategy.long_put.clone()];
+        let rebuilt = PutBackSpread::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.break_even_points, strategy.break_even_points);
+        assert_eq!(rebuilt.get_positions().unwrap().len(), 2);
+
+        let mut wrong = positions.clone();
+        wrong[0].option.side = match wrong[0].option.side {
+            Side::Long => Side::Short,
+            Side::Short => Side::Long,
+        };
+        assert!(PutBackSpread::get_strategy(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_put_back_spread_find_optimal() {
+        let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+            "SPY".to_string(),
+            None,
+            6,
+            spos!(2.5),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(100.0))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("SPY".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        ))
+        .unwrap();
+        let mut strategy = create_strategy();
+        strategy.find_optimal(&chain, FindOptimalSide::All, OptimizationCriteria::Area);
+        assert!(strategy.validate());
+        for leg in strategy.get_positions().unwrap() {
+            assert!(
+                chain
+                    .options
+                    .iter()
+                    .any(|quote| quote.strike_price == leg.option.strike_price)
+            );
+        }
+    }
+}
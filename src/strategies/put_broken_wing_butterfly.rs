@@ -0,0 +1,701 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Put Broken Wing Butterfly is a put butterfly whose two wings have different widths: one long
+//! put at the lower strike, two short puts at the body and one long put at the upper strike, with the
+//! lower wing usually skipping a strike.
+//!
+//! The strategy has three components:
+//! 1. **Long lower-wing put**: Caps the risk of the second short put at a skipped strike.
+//! 2. **Two short body puts**: Collect premium and set the peak of the payoff.
+//! 3. **Long upper-wing put**: Starts the profit zone above the body.
+//!
+//! Widening the lower wing reduces the debit, or turns it into a credit, at the cost of a larger but
+//! still limited loss when the underlying finishes below the lower wing.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::piecewise::{self, LegFactory};
+use crate::{
+    ExpirationDate, Options,
+    chains::{StrategyLegs, chain::OptionChain, utils::OptionDataGroup},
+    error::{
+        GreeksError, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+
+pub(super) const PUT_BROKEN_WING_BUTTERFLY_DESCRIPTION: &str = "A Put Broken Wing Butterfly buys a lower put, sells two puts at the body \
+    and buys an upper put with a different wing width. It profits most at the body and has \
+    limited risk that is skewed towards the wider wing.";
+
+/// # PutBrokenWingButterfly
+///
+/// Represents a Put Broken Wing Butterfly: long puts at the lower and upper wings and twice that
+/// quantity of short puts at the body, with wings of unequal width.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::PutBrokenWingButterfly`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The underlying prices at expiration at which the strategy neither makes nor loses money.
+/// * `long_put_low`: The long put at the lower wing.
+/// * `short_put`: The two short puts at the body.
+/// * `long_put_high`: The long put at the upper wing.
+///
+/// ## Risk and Reward
+/// The maximum profit is reached with the underlying at the body and equals the upper wing width
+/// plus the net credit (or minus the net debit). The maximum loss is limited: the net debit above the
+/// upper wing, or the difference between the two wing widths below the lower wing.
+///
+/// ## Break-Even Points
+/// There is a break-even point inside each wing of the butterfly, unless the spread was opened for
+/// a credit on a side where the payoff never turns negative.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct PutBrokenWingButterfly {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a PutBrokenWingButterfly strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Prices at expiration where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The long put at the lower wing
+    pub(super) long_put_low: Position,
+    /// The two short puts at the body
+    pub(super) short_put: Position,
+    /// The long put at the upper wing
+    pub(super) long_put_high: Position,
+}
+
+impl PutBrokenWingButterfly {
+    /// # Creates a new Put Broken Wing Butterfly strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `lower_strike`: Strike price of the lower long put
+    /// * `middle_strike`: Strike price of the short puts
+    /// * `upper_strike`: Strike price of the upper long put
+    /// * `expiration`: Expiration date shared by all the options
+    /// * `implied_volatility`: Implied volatility used to price the options
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts of each wing; the body is twice this size
+    /// * `premium_long_put_low`: Premium paid for the lower long put
+    /// * `premium_short_put`: Premium received for each short put
+    /// * `premium_long_put_high`: Premium paid for the upper long put
+    /// * `open_fee`: Transaction fee per contract for opening each position
+    /// * `close_fee`: Transaction fee per contract for closing each position
+    ///
+    /// ## Returns
+    /// A fully initialized `PutBrokenWingButterfly` with its break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the parameters are invalid: the strikes must be
+    /// increasing with wings of different widths and the body must be twice each wing.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        lower_strike: Positive,
+        middle_strike: Positive,
+        upper_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_long_put_low: Positive,
+        premium_short_put: Positive,
+        premium_long_put_high: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let factory = LegFactory {
+            symbol: underlying_symbol,
+            underlying_price,
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            open_fee,
+            close_fee,
+        };
+        let mut strategy = PutBrokenWingButterfly {
+            long_put_low: factory.position(
+                OptionStyle::Put,
+                Side::Long,
+                lower_strike,
+                quantity,
+                premium_long_put_low,
+            ),
+            short_put: factory.position(
+                OptionStyle::Put,
+                Side::Short,
+                middle_strike,
+                quantity * Positive::TWO,
+                premium_short_put,
+            ),
+            long_put_high: factory.position(
+                OptionStyle::Put,
+                Side::Long,
+                upper_strike,
+                quantity,
+                premium_long_put_high,
+            ),
+            ..PutBrokenWingButterfly::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Put Broken Wing Butterfly new",
+                "the strikes must be increasing with wings of different widths and the body must be twice each wing",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    fn legs(&self) -> [&Position; 3] {
+        [&self.long_put_low, &self.short_put, &self.long_put_high]
+    }
+
+    fn legs_mut(&mut self) -> [&mut Position; 3] {
+        [
+            &mut self.long_put_low,
+            &mut self.short_put,
+            &mut self.long_put_high,
+        ]
+    }
+}
+
+impl StrategyConstructor for PutBrokenWingButterfly {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        let [long_put_low, short_put, long_put_high] = piecewise::match_legs(
+            vec_positions,
+            [
+                (OptionStyle::Put, Side::Long),
+                (OptionStyle::Put, Side::Short),
+                (OptionStyle::Put, Side::Long),
+            ],
+            "Put Broken Wing Butterfly get_strategy",
+        )?;
+        let mut strategy = PutBrokenWingButterfly {
+            long_put_low,
+            short_put,
+            long_put_high,
+            ..PutBrokenWingButterfly::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Put Broken Wing Butterfly get_strategy",
+                "the strikes must be increasing with wings of different widths and the body must be twice each wing",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for PutBrokenWingButterfly {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = piecewise::break_even_points(&self.legs())?;
+        Ok(())
+    }
+}
+
+impl Validable for PutBrokenWingButterfly {
+    fn validate(&self) -> bool {
+        self.legs().iter().all(|leg| leg.validate())
+            && self.long_put_low.option.strike_price < self.short_put.option.strike_price
+            && self.short_put.option.strike_price < self.long_put_high.option.strike_price
+            && self.short_put.option.strike_price - self.long_put_low.option.strike_price
+                != self.long_put_high.option.strike_price - self.short_put.option.strike_price
+            && self.short_put.option.quantity == self.long_put_low.option.quantity * Positive::TWO
+            && self.long_put_low.option.quantity == self.long_put_high.option.quantity
+    }
+}
+
+impl Positionable for PutBrokenWingButterfly {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Put, Side::Long) => {
+                if position.option.strike_price < self.short_put.option.strike_price {
+                    self.long_put_low = position.clone();
+                } else {
+                    self.long_put_high = position.clone();
+                }
+            }
+            (OptionStyle::Put, Side::Short) => self.short_put = position.clone(),
+            _ => {
+                return Err(PositionError::invalid_position_type(
+                    position.option.side,
+                    "Call is not part of a Put Broken Wing Butterfly".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(self.legs().to_vec())
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        piecewise::find_legs(self.legs_mut().into(), option_style, side, strike)
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for PutBrokenWingButterfly {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for PutBrokenWingButterfly {
+    fn get_title(&self) -> String {
+        let legs: Vec<String> = self.legs().iter().map(|leg| leg.get_title()).collect();
+        format!("{:?} Strategy: \n\t{}", self.kind, legs.join("\n\t"))
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        piecewise::basic_types(&self.legs())
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::implied_volatilities(&self.legs())
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        piecewise::quantities(&self.legs())
+    }
+    fn one_option(&self) -> &Options {
+        self.long_put_low.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.long_put_low.one_option_mut()
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        for leg in self.legs_mut() {
+            leg.option.expiration_date = expiration_date;
+        }
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.underlying_price = *price;
+        })
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        piecewise::reprice_legs(&mut self.legs_mut(), |option| {
+            option.implied_volatility = *volatility;
+        })
+    }
+}
+
+impl Strategies for PutBrokenWingButterfly {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_profit(&self.legs())
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        piecewise::max_loss(&self.legs())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_area(&self.legs())
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        piecewise::profit_ratio(&self.legs())
+    }
+}
+
+impl Optimizable for PutBrokenWingButterfly {
+    type Strategy = PutBrokenWingButterfly;
+
+    /// Combinations of three increasing strikes of `option_chain` that pass the
+    /// `side` filter.
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        option_chain
+            .get_triple_iter()
+            .filter(move |(lower, middle, upper)| {
+                piecewise::quotes_in_side(option_chain, &[lower, middle, upper], &side)
+            })
+            .map(|(lower, middle, upper)| OptionDataGroup::Three(lower, middle, upper))
+    }
+
+    /// Searches every combination of three increasing strikes of `option_chain` for
+    /// the strategy that scores best under `criteria` and replaces `self`
+    /// with it.
+    ///
+    /// Long legs are bought at the ask and short legs sold at the bid, and the
+    /// expiration, quantity, rate and fees of `self` are kept.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let candidates = self
+            .filter_combinations(option_chain, side)
+            .filter_map(|group| group.strategy_legs())
+            .map(|legs| self.create_strategy(option_chain, &legs));
+        if let Some(strategy) = piecewise::select_best(candidates, &criteria) {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `PutBrokenWingButterfly` from three quotes in increasing strike order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs have the wrong
+    /// shape, a needed bid or ask is missing, or the resulting strategy is
+    /// invalid.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (lower, middle, upper) = match legs {
+            StrategyLegs::ThreeLegs {
+                first: lower,
+                second: middle,
+                third: upper,
+            } => (lower, middle, upper),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "PutBrokenWingButterfly requires exactly three legs (ThreeLegs)",
+                ));
+            }
+        };
+        PutBrokenWingButterfly::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            lower.strike_price,
+            middle.strike_price,
+            upper.strike_price,
+            self.long_put_low.option.expiration_date,
+            lower.implied_volatility,
+            self.long_put_low.option.risk_free_rate,
+            self.long_put_low.option.dividend_yield,
+            self.long_put_low.option.quantity,
+            piecewise::premium(lower, OptionStyle::Put, Side::Long)?,
+            piecewise::premium(middle, OptionStyle::Put, Side::Short)?,
+            piecewise::premium(upper, OptionStyle::Put, Side::Long)?,
+            self.long_put_low.open_fee,
+            self.long_put_low.close_fee,
+        )
+    }
+}
+
+impl Profit for PutBrokenWingButterfly {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        piecewise::profit_at(&self.legs(), price)
+    }
+}
+
+impl ProbabilityAnalysis for PutBrokenWingButterfly {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, true)
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        piecewise::ranges(&self.legs(), &self.break_even_points, false)
+    }
+}
+
+impl Greeks for PutBrokenWingButterfly {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self.legs().into_iter().map(|leg| &leg.option).collect())
+    }
+}
+
+impl DeltaNeutrality for PutBrokenWingButterfly {}
+
+impl PnLCalculator for PutBrokenWingButterfly {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl(
+            &self.legs(),
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        piecewise::pnl_at_expiration(&self.legs(), underlying_price)
+    }
+}
+
+test_strategy_traits!(
+    PutBrokenWingButterfly,
+    test_put_broken_wing_butterfly_implementations
+);
+
+#[cfg(test)]
+mod tests_put_broken_wing_butterfly {
+    use super::*;
+    use crate::assert_decimal_eq;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use positive::{pos_or_panic, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> PutBrokenWingButterfly {
+        PutBrokenWingButterfly::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(85.0),
+            pos_or_panic!(100.0),
+            pos_or_panic!(110.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.0),
+            pos_or_panic!(5.0),
+            pos_or_panic!(11.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_put_broken_wing_butterfly_break_even_points() {
+        let strategy = create_strategy();
+        assert!(strategy.validate());
+        assert_eq!(strategy.break_even_points.len(), 2);
+        assert_decimal_eq!(
+            strategy.break_even_points[0].to_dec(),
+            dec!(92.5),
+            dec!(0.01)
+        );
+        assert_decimal_eq!(
+            strategy.break_even_points[1].to_dec(),
+            dec!(107.5),
+            dec!(0.01)
+        );
+        for point in &strategy.break_even_points {
+            let pnl = strategy.calculate_profit_at(point).unwrap();
+            assert!(pnl.abs() < dec!(0.05), "P&L {pnl} at break-even {point}");
+        }
+    }
+
+    #[test]
+    fn test_put_broken_wing_butterfly_max_profit_and_loss() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.get_max_profit().unwrap().to_dec(),
+            dec!(7.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.get_max_loss().unwrap().to_dec(),
+            dec!(7.5),
+            dec!(1e-9)
+        );
+    }
+
+    #[test]
+    fn test_put_broken_wing_butterfly_profit_at_expiration() {
+        let strategy = create_strategy();
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap(),
+            dec!(7.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(75.0)).unwrap(),
+            dec!(-7.5),
+            dec!(1e-9)
+        );
+        assert_decimal_eq!(
+            strategy.calculate_profit_at(&pos_or_panic!(120.0)).unwrap(),
+            dec!(-2.5),
+            dec!(1e-9)
+        );
+        let pnl = strategy
+            .calculate_pnl_at_expiration(&pos_or_panic!(100.0))
+            .unwrap();
+        assert_eq!(
+            pnl.total_pnl(),
+            Some(strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_put_broken_wing_butterfly_rejects_invalid_parameters() {
+        let result = PutBrokenWingButterfly::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(90.0),
+            pos_or_panic!(100.0),
+            pos_or_panic!(110.0),
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            pos_or_panic!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(1.0),
+            pos_or_panic!(5.0),
+            pos_or_panic!(11.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_put_broken_wing_butterfly_get_strategy_from_positions() {
+        let strategy = create_strategy();
+        let positions = vec![
+            strategy.long_put_high.clone(),
+            strategy.short_put.clone(),
+            strategy.long_put_low.clone(),
+        ];
+        let rebuilt = PutBrokenWingButterfly::get_strategy(&positions).unwrap();
+        assert_eq!(rebuilt.break_even_points, strategy.break_even_points);
+        assert_eq!(rebuilt.get_positions().unwrap().len(), 3);
+
+        let mut wrong = positions.clone();
+        wrong[0].option.side = match wrong[0].option.side {
+            Side::Long => Side::Short,
+            Side::Short => Side::Long,
+        };
+        assert!(PutBrokenWingButterfly::get_strategy(&wrong).is_err());
+    }
+
+    #[test]
+    fn test_put_broken_wing_butterfly_find_optimal() {
+        let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+            "SPY".to_string(),
+            None,
+            6,
+            spos!(2.5),
+            dec!(-0.2),
+            dec!(0.1),
+            pos_or_panic!(0.02),
+            2,
+            OptionDataPriceParams::new(
+                Some(Box::new(pos_or_panic!(100.0))),
+                Some(ExpirationDate::Days(pos_or_panic!(30.0))),
+                Some(dec!(0.05)),
+                spos!(0.0),
+                Some("SPY".to_string()),
+            ),
+            pos_or_panic!(0.2),
+        ))
+        .unwrap();
+        let mut strategy = create_strategy();
+        strategy.find_optimal(&chain, FindOptimalSide::All, OptimizationCriteria::Area);
+        assert!(strategy.validate());
+        for leg in strategy.get_positions().unwrap() {
+            assert!(
+                chain
+                    .options
+                    .iter()
+                    .any(|quote| quote.strike_price == leg.option.strike_price)
+            );
+        }
+    }
+}
@@ -0,0 +1,852 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//!
+//! A Short Calendar Spread (reverse calendar) buys a near-term option and sells a longer-dated
+//! option of the same style at the same strike. It is opened for a net credit and is the mirror
+//! image of the long calendar: it profits when the underlying moves well away from the strike
+//! before the front expiry, where both options converge to intrinsic value, and loses when the
+//! underlying pins the strike.
+//!
+//! The strategy has two components:
+//! 1. **Long near-term option**: Gains from a large move in the underlying before the front expiry.
+//! 2. **Short far-term option**: Finances the near-term option and keeps its time value as long as
+//!    the underlying stays away from the strike.
+//!
+//! The profit and loss profile is measured at the front expiry, with the short far-term option
+//! valued with Black–Scholes at its own implied volatility and remaining time. The position is
+//! short vega, so a fall in back-month implied volatility helps it.
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::horizon;
+use crate::{
+    ExpirationDate, Options,
+    chains::{StrategyLegs, chain::OptionChain},
+    error::{
+        GreeksError, OperationErrorKind, PricingError,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::StrategyError,
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, OptionType, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    series::OptionSeries,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+    test_strategy_traits,
+};
+use chrono::Utc;
+use positive::Positive;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+use utoipa::ToSchema;
+
+pub(super) const SHORT_CALENDAR_SPREAD_DESCRIPTION: &str = "A Short Calendar Spread buys a near-term option \
+    and sells a longer-dated option of the same style and strike for a net credit. \
+    It profits when the underlying moves far from the strike before the front expiry \
+    and loses when the underlying stays close to the strike.";
+
+/// # ShortCalendarSpread
+///
+/// Represents a Short Calendar Spread: a long near-term option and a short far-term option of the
+/// same style at the same strike.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::ShortCalendarSpread`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: The prices at the front expiry at which the strategy neither makes nor loses money.
+/// * `near_leg`: The long near-term option.
+/// * `far_leg`: The short far-term option.
+///
+/// ## Risk and Reward
+/// The maximum profit is close to the net credit received and is reached when the underlying moves
+/// far from the strike. The maximum loss occurs with the underlying at the strike on the front
+/// expiry, where the short far leg keeps the most time value, and is measured on the front-expiry
+/// profile.
+///
+/// ## Break-Even Points
+/// There are usually two break-even points, one on each side of the strike, found numerically on
+/// the front-expiry profile.
+#[derive(Clone, DebugPretty, DisplaySimple, Serialize, Deserialize, ToSchema)]
+pub struct ShortCalendarSpread {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a ShortCalendarSpread strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Front-expiry prices where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The near-term option that is bought
+    pub(super) near_leg: Position,
+    /// The far-term option that is sold
+    pub(super) far_leg: Position,
+}
+
+impl ShortCalendarSpread {
+    /// # Creates a new Short Calendar Spread strategy instance
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `strike`: Strike price shared by both legs
+    /// * `option_style`: Style (call or put) shared by both legs
+    /// * `near_expiration`: Expiration date of the long near-term option
+    /// * `far_expiration`: Expiration date of the short far-term option
+    /// * `near_implied_volatility`: Implied volatility of the near-term option
+    /// * `far_implied_volatility`: Implied volatility of the far-term option
+    /// * `risk_free_rate`: Risk-free interest rate used in options pricing models
+    /// * `dividend_yield`: Expected dividend yield of the underlying security
+    /// * `quantity`: Number of contracts for both legs of the strategy
+    /// * `premium_near`: Premium paid for the near-term option
+    /// * `premium_far`: Premium received for the far-term option
+    /// * `open_fee_near`: Transaction fee for opening the near-term position
+    /// * `close_fee_near`: Transaction fee for closing the near-term position
+    /// * `open_fee_far`: Transaction fee for opening the far-term position
+    /// * `close_fee_far`: Transaction fee for closing the far-term position
+    ///
+    /// ## Returns
+    /// A fully initialized `ShortCalendarSpread` with its front-expiry break-even points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the near expiration is not
+    /// strictly earlier than the far one, and propagates pricing errors from
+    /// the break-even calculation.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        strike: Positive,
+        option_style: OptionStyle,
+        near_expiration: ExpirationDate,
+        far_expiration: ExpirationDate,
+        near_implied_volatility: Positive,
+        far_implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_near: Positive,
+        premium_far: Positive,
+        open_fee_near: Positive,
+        close_fee_near: Positive,
+        open_fee_far: Positive,
+        close_fee_far: Positive,
+    ) -> Result<Self, StrategyError> {
+        let mut strategy = ShortCalendarSpread::default();
+
+        let near_option = Options::new(
+            OptionType::European,
+            Side::Long,
+            underlying_symbol.clone(),
+            strike,
+            near_expiration,
+            near_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let near_leg = Position::new(
+            near_option,
+            premium_near,
+            Utc::now(),
+            open_fee_near,
+            close_fee_near,
+            None,
+            None,
+        );
+        strategy.add_position(&near_leg)?;
+
+        let far_option = Options::new(
+            OptionType::European,
+            Side::Short,
+            underlying_symbol,
+            strike,
+            far_expiration,
+            far_implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let far_leg = Position::new(
+            far_option,
+            premium_far,
+            Utc::now(),
+            open_fee_far,
+            close_fee_far,
+            None,
+            None,
+        );
+        strategy.add_position(&far_leg)?;
+
+        if !strategy.validate() {
+            return Err(StrategyError::invalid_parameters(
+                "Short Calendar Spread new",
+                "near expiration must be earlier than far expiration",
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    /// Searches every pair of expirations in `series` (near before far) and
+    /// every strike quoted in both chains for the calendar that scores best
+    /// under `criteria`, and replaces `self` with it.
+    ///
+    /// The near leg is bought at the ask and the far leg sold at the bid of
+    /// their own chains, and each leg keeps the implied volatility quoted
+    /// for its expiry. The style, quantity, rate and fees of `self` are kept.
+    pub fn find_optimal_in_series(
+        &mut self,
+        series: &OptionSeries,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let mut best: Option<ShortCalendarSpread> = None;
+
+        for ((near_expiration, near_chain), (far_expiration, far_chain)) in
+            horizon::expiry_pairs(series)
+        {
+            let mut template = self.clone();
+            template.near_leg.option.expiration_date = *near_expiration;
+            template.far_leg.option.expiration_date = *far_expiration;
+
+            for near_quote in near_chain.options.iter() {
+                if !horizon::strike_in_side(near_chain, near_quote, &side) {
+                    continue;
+                }
+                let Some(far_quote) = horizon::quote_at(far_chain, near_quote.strike_price) else {
+                    continue;
+                };
+                let legs = StrategyLegs::TwoLegs {
+                    first: near_quote,
+                    second: far_quote,
+                };
+                let strategy = match template.create_strategy(near_chain, &legs) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        debug!(error = %e, "skipping invalid calendar combination");
+                        continue;
+                    }
+                };
+                if let Some(value) = horizon::score(&strategy, &criteria)
+                    && value > best_value
+                {
+                    best_value = value;
+                    best = Some(strategy);
+                }
+            }
+        }
+
+        if let Some(strategy) = best {
+            *self = strategy;
+        }
+    }
+}
+
+impl StrategyConstructor for ShortCalendarSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        let [first, second] = vec_positions else {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Short Calendar Spread get_strategy".to_string(),
+                    reason: "Must have exactly 2 options".to_string(),
+                },
+            ));
+        };
+        let (near_leg, far_leg) = match (first.option.side, second.option.side) {
+            (Side::Long, Side::Short) => (first, second),
+            (Side::Short, Side::Long) => (second, first),
+            _ => {
+                return Err(StrategyError::OperationError(
+                    OperationErrorKind::InvalidParameters {
+                        operation: "Short Calendar Spread get_strategy".to_string(),
+                        reason: "Short Calendar Spread requires one long and one short option"
+                            .to_string(),
+                    },
+                ));
+            }
+        };
+        if near_leg.option.option_style != far_leg.option.option_style
+            || near_leg.option.strike_price != far_leg.option.strike_price
+        {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Short Calendar Spread get_strategy".to_string(),
+                    reason: "Both options must share style and strike".to_string(),
+                },
+            ));
+        }
+
+        let mut strategy = ShortCalendarSpread {
+            name: "Short Calendar Spread".to_string(),
+            kind: StrategyType::ShortCalendarSpread,
+            description: SHORT_CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            near_leg: near_leg.clone(),
+            far_leg: far_leg.clone(),
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Short Calendar Spread get_strategy".to_string(),
+                    reason: "The long option must expire before the short option".to_string(),
+                },
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for ShortCalendarSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = horizon::front_expiry_break_even_points(&self.get_positions()?)?;
+        Ok(())
+    }
+}
+
+impl Validable for ShortCalendarSpread {
+    fn validate(&self) -> bool {
+        let near = &self.near_leg.option;
+        let far = &self.far_leg.option;
+        let ordered = match (
+            near.expiration_date.get_days(),
+            far.expiration_date.get_days(),
+        ) {
+            (Ok(near_days), Ok(far_days)) => near_days < far_days,
+            _ => false,
+        };
+        self.near_leg.validate()
+            && self.far_leg.validate()
+            && near.side == Side::Long
+            && far.side == Side::Short
+            && near.option_style == far.option_style
+            && near.strike_price == far.strike_price
+            && ordered
+    }
+}
+
+impl Positionable for ShortCalendarSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match position.option.side {
+            Side::Long => self.near_leg = position.clone(),
+            Side::Short => self.far_leg = position.clone(),
+        }
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(vec![&self.near_leg, &self.far_leg])
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        let leg = match side {
+            Side::Long => &mut self.near_leg,
+            Side::Short => &mut self.far_leg,
+        };
+        if leg.option.option_style == *option_style && leg.option.strike_price == *strike {
+            Ok(vec![leg])
+        } else {
+            Err(PositionError::invalid_position_type(
+                *side,
+                "Strike not found in positions".to_string(),
+            ))
+        }
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self
+            .get_position(
+                &position.option.option_style,
+                &position.option.side,
+                &position.option.strike_price,
+            )?
+            .into_iter()
+            .next();
+        if let Some(leg) = leg {
+            *leg = position.clone();
+        }
+        Ok(())
+    }
+}
+
+impl Strategable for ShortCalendarSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for ShortCalendarSpread {
+    fn get_title(&self) -> String {
+        format!(
+            "{:?} Strategy: \n\t{}\n\t{}",
+            self.kind,
+            self.near_leg.get_title(),
+            self.far_leg.get_title()
+        )
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            })
+            .collect()
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.implied_volatility,
+                )
+            })
+            .collect()
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.near_leg.option, &self.far_leg.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.quantity,
+                )
+            })
+            .collect()
+    }
+    fn one_option(&self) -> &Options {
+        self.near_leg.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.near_leg.one_option_mut()
+    }
+    /// Moves the front expiry to `expiration_date` and shifts the far leg by
+    /// the same amount, keeping the gap between the two expirations.
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        self.far_leg.option.expiration_date = horizon::rolled_expiration(
+            &self.far_leg.option.expiration_date,
+            &self.near_leg.option.expiration_date,
+            &expiration_date,
+        )?;
+        self.near_leg.option.expiration_date = expiration_date;
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.underlying_price = *price;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        Ok(())
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        for leg in [&mut self.near_leg, &mut self.far_leg] {
+            leg.option.implied_volatility = *volatility;
+            leg.premium = Positive::new_decimal(leg.option.calculate_price_black_scholes()?.abs())
+                .unwrap_or(Positive::ZERO);
+        }
+        Ok(())
+    }
+}
+
+impl Strategies for ShortCalendarSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        horizon::front_expiry_max_profit(&self.get_positions()?)
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        horizon::front_expiry_max_loss(&self.get_positions()?)
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        horizon::front_expiry_profit_area(&self.get_positions()?)
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        horizon::front_expiry_profit_ratio(&self.get_positions()?)
+    }
+}
+
+impl Optimizable for ShortCalendarSpread {
+    type Strategy = ShortCalendarSpread;
+
+    /// Searches the strikes of `option_chain` for the best calendar at the
+    /// current near and far expirations.
+    ///
+    /// The chain is taken as the front month: the near leg is bought at its
+    /// ask, while the far leg is priced with Black–Scholes at the far
+    /// expiry and the chain's implied volatility for the strike. Use
+    /// [`ShortCalendarSpread::find_optimal_in_series`] to search quoted
+    /// expiries instead.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let mut best: Option<ShortCalendarSpread> = None;
+
+        for near_quote in option_chain.options.iter() {
+            if !horizon::strike_in_side(option_chain, near_quote, &side) {
+                continue;
+            }
+            let far_quote = match horizon::repriced_quote(
+                near_quote,
+                &self.far_leg.option,
+                option_chain.underlying_price,
+                self.far_leg.option.expiration_date,
+            ) {
+                Ok(quote) => quote,
+                Err(e) => {
+                    debug!(error = %e, "skipping strike without a far-expiry price");
+                    continue;
+                }
+            };
+            let legs = StrategyLegs::TwoLegs {
+                first: near_quote,
+                second: &far_quote,
+            };
+            let strategy = match self.create_strategy(option_chain, &legs) {
+                Ok(s) => s,
+                Err(e) => {
+                    debug!(error = %e, "skipping invalid calendar combination");
+                    continue;
+                }
+            };
+            if let Some(value) = horizon::score(&strategy, &criteria)
+                && value > best_value
+            {
+                best_value = value;
+                best = Some(strategy);
+            }
+        }
+
+        if let Some(strategy) = best {
+            *self = strategy;
+        }
+    }
+
+    /// Constructs a `ShortCalendarSpread` from a near quote (`first`) and a
+    /// far quote (`second`) at the same strike.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the legs are not
+    /// `TwoLegs`, the strikes differ, or the near ask or far bid is missing.
+    fn create_strategy(
+        &self,
+        chain: &OptionChain,
+        legs: &StrategyLegs,
+    ) -> Result<Self::Strategy, StrategyError> {
+        let (near, far) = match legs {
+            StrategyLegs::TwoLegs { first, second } => (first, second),
+            _ => {
+                return Err(StrategyError::operation_not_supported(
+                    "create_strategy",
+                    "ShortCalendarSpread requires exactly two legs (TwoLegs)",
+                ));
+            }
+        };
+        if near.strike_price != far.strike_price {
+            return Err(StrategyError::invalid_parameters(
+                "create_strategy",
+                "calendar legs must share the same strike",
+            ));
+        }
+        let style = self.near_leg.option.option_style;
+        let premium_near = horizon::leg_premium(near, style, Side::Long).ok_or_else(|| {
+            StrategyError::operation_not_supported("create_strategy", "missing ask for near leg")
+        })?;
+        let premium_far = horizon::leg_premium(far, style, Side::Short).ok_or_else(|| {
+            StrategyError::operation_not_supported("create_strategy", "missing bid for far leg")
+        })?;
+
+        ShortCalendarSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            near.strike_price,
+            style,
+            self.near_leg.option.expiration_date,
+            self.far_leg.option.expiration_date,
+            near.implied_volatility,
+            far.implied_volatility,
+            self.near_leg.option.risk_free_rate,
+            self.near_leg.option.dividend_yield,
+            self.near_leg.option.quantity,
+            premium_near,
+            premium_far,
+            self.near_leg.open_fee,
+            self.near_leg.close_fee,
+            self.far_leg.open_fee,
+            self.far_leg.close_fee,
+        )
+    }
+}
+
+impl Profit for ShortCalendarSpread {
+    /// Profit at the front expiry, with the far leg valued at its remaining
+    /// time value.
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, PricingError> {
+        horizon::profit_at_front_expiry(&[&self.near_leg, &self.far_leg], price)
+    }
+}
+
+impl ProbabilityAnalysis for ShortCalendarSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        horizon::front_expiry_ranges(
+            &[&self.near_leg, &self.far_leg],
+            &self.break_even_points,
+            true,
+        )
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        horizon::front_expiry_ranges(
+            &[&self.near_leg, &self.far_leg],
+            &self.break_even_points,
+            false,
+        )
+    }
+}
+
+impl Greeks for ShortCalendarSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.near_leg.option, &self.far_leg.option])
+    }
+}
+
+impl DeltaNeutrality for ShortCalendarSpread {}
+
+impl PnLCalculator for ShortCalendarSpread {
+    /// P&L with `expiration_date` left on the near leg; the far leg is aged
+    /// by the same elapsed time.
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, PricingError> {
+        horizon::aged_pnl(
+            &[&self.near_leg, &self.far_leg],
+            market_price,
+            expiration_date,
+            implied_volatility,
+        )
+    }
+
+    /// Realized P&L of closing the spread at the front expiry, with the far
+    /// leg sold back at its own implied volatility.
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, PricingError> {
+        horizon::front_expiry_pnl(&[&self.near_leg, &self.far_leg], underlying_price)
+    }
+}
+
+test_strategy_traits!(
+    ShortCalendarSpread,
+    test_short_calendar_spread_implementations
+);
+
+#[cfg(test)]
+mod tests_short_calendar_spread {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> ShortCalendarSpread {
+        ShortCalendarSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(100.0),
+            OptionStyle::Put,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(90.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.22),
+            dec!(0.03),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.2),
+            pos_or_panic!(3.9),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_short_calendar_loses_at_strike_and_profits_away() {
+        let strategy = create_strategy();
+        assert!(strategy.validate());
+        assert_eq!(strategy.break_even_points.len(), 2);
+        let at_strike = strategy.calculate_profit_at(&pos_or_panic!(100.0)).unwrap();
+        assert!(at_strike < Decimal::ZERO);
+        let far_away = strategy.calculate_profit_at(&pos_or_panic!(70.0)).unwrap();
+        assert!(far_away > Decimal::ZERO);
+
+        let max_loss = strategy.get_max_loss().unwrap();
+        assert!((max_loss.to_dec() + at_strike).abs() < dec!(0.1));
+        assert!(strategy.get_max_profit().unwrap() > Positive::ZERO);
+    }
+
+    #[test]
+    fn test_short_calendar_front_expiry_values() {
+        // Long the 30-day put, short the 90-day put: at the front expiry
+        // P&L(S) = max(100 - S, 0) - 2.2 + 3.9 - P(S, 100, 60/365, 0.22, 3%).
+        let strategy = create_strategy();
+        for (price, expected) in [
+            (90.0, dec!(1.64214)),
+            (100.0, dec!(-1.60798)),
+            (110.0, dec!(1.089624)),
+        ] {
+            let profit = strategy
+                .calculate_profit_at(&Positive::new(price).unwrap())
+                .unwrap();
+            assert!(
+                (profit - expected).abs() < dec!(0.0001),
+                "{profit} vs {expected} at {price}"
+            );
+        }
+        let expected = [dec!(96.5142), dec!(104.375)];
+        for (point, expected) in strategy.break_even_points.iter().zip(expected) {
+            assert!((point.to_dec() - expected).abs() < dec!(0.01), "{point}");
+        }
+        // The worst case is the front expiring at the strike.
+        let max_loss = strategy.get_max_loss().unwrap().to_dec();
+        assert!(
+            (max_loss - dec!(1.60798)).abs() < dec!(0.0001),
+            "{max_loss}"
+        );
+    }
+
+    #[test]
+    fn test_short_calendar_mirrors_long_calendar() {
+        let short = create_strategy();
+        let long = crate::strategies::LongCalendarSpread::new(
+            "SPY".to_string(),
+            pos_or_panic!(100.0),
+            pos_or_panic!(100.0),
+            OptionStyle::Put,
+            ExpirationDate::Days(pos_or_panic!(30.0)),
+            ExpirationDate::Days(pos_or_panic!(90.0)),
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.22),
+            dec!(0.03),
+            Positive::ZERO,
+            Positive::ONE,
+            pos_or_panic!(2.2),
+            pos_or_panic!(3.9),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        for price in [80.0, 95.0, 100.0, 112.0] {
+            let price = Positive::new(price).unwrap();
+            let sum = short.calculate_profit_at(&price).unwrap()
+                + long.calculate_profit_at(&price).unwrap();
+            assert!(sum.abs() < dec!(1e-9));
+        }
+        assert_eq!(short.break_even_points, long.break_even_points);
+        assert_eq!(short.get_profit_ranges().unwrap().len(), 2);
+        assert_eq!(short.get_loss_ranges().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_short_calendar_get_strategy_requires_long_near_leg() {
+        let strategy = create_strategy();
+        let mut positions = vec![strategy.near_leg.clone(), strategy.far_leg.clone()];
+        assert!(ShortCalendarSpread::get_strategy(&positions).is_ok());
+        positions[0].option.expiration_date = ExpirationDate::Days(pos_or_panic!(120.0));
+        assert!(ShortCalendarSpread::get_strategy(&positions).is_err());
+    }
+}
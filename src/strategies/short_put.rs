@@ -4,7 +4,7 @@
 // indices (fixed-length buffers, just-pushed slices, etc.).
 #![allow(clippy::indexing_slicing)]
 
-use super::base::{BreakEvenable, Positionable, StrategyType};
+use super::base::{BreakEvenable, Positionable, StrategyBasics, StrategyType};
 use crate::backtesting::results::{SimulationResult, SimulationStatsResult};
 
 use crate::chains::OptionChain;
@@ -778,7 +778,15 @@ where
     }
 }
 
-impl Strategable for ShortPut {}
+impl Strategable for ShortPut {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
 
 test_strategy_traits!(ShortPut, test_short_put_implementations);
 
@@ -0,0 +1,721 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Exchange trading calendars and day-count bases for time to expiry.
+//!
+//! A [`TradingCalendar`] knows the regular session, holidays and early closes
+//! of an exchange. Built-in calendars cover NYSE, CME and Eurex; holiday lists
+//! can be loaded on top of them or used to build a custom calendar.
+//!
+//! A [`DayCountBasis`] turns the interval to an [`ExpirationDate`] into a year
+//! fraction. The default, Actual/365 Fixed, is the calendar-day convention used
+//! throughout the library. The calendar-aware bases stop the clock outside
+//! business days or outside trading sessions, which is what drives weekend
+//! and 0DTE theta.
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, Offset, Utc, Weekday,
+};
+use expiration_date::ExpirationDate;
+use expiration_date::error::ExpirationDateError;
+use positive::Positive;
+use positive::constants::DAYS_IN_A_YEAR;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use utoipa::ToSchema;
+
+/// Trading days in a year for the business-day and trading-minute bases.
+const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Exchanges with built-in holiday rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Exchange {
+    /// New York Stock Exchange (and Cboe equity options), 09:30–16:00 New York.
+    Nyse,
+    /// CME Group equity-index options, 08:30–15:00 Chicago, on US holidays.
+    Cme,
+    /// Eurex, 09:00–17:30 Frankfurt.
+    Eurex,
+}
+
+/// Time zone of an exchange, with its daylight saving rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum MarketZone {
+    /// US Eastern time.
+    NewYork,
+    /// US Central time.
+    Chicago,
+    /// Central European time.
+    Frankfurt,
+    /// UTC, without daylight saving.
+    Utc,
+}
+
+impl MarketZone {
+    /// Returns the UTC offset of the zone on a local date.
+    #[must_use]
+    pub fn offset(&self, date: NaiveDate) -> FixedOffset {
+        let hours = match self {
+            MarketZone::NewYork => -5 + i32::from(us_daylight_saving(date)),
+            MarketZone::Chicago => -6 + i32::from(us_daylight_saving(date)),
+            MarketZone::Frankfurt => 1 + i32::from(eu_summer_time(date)),
+            MarketZone::Utc => 0,
+        };
+        FixedOffset::east_opt(hours * 3600).unwrap_or_else(|| Utc.fix())
+    }
+
+    /// Converts a local wall-clock time to UTC.
+    #[must_use]
+    pub fn to_utc(&self, date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+        let local = date.and_time(time);
+        DateTime::<Utc>::from_naive_utc_and_offset(
+            local - Duration::seconds(i64::from(self.offset(date).local_minus_utc())),
+            Utc,
+        )
+    }
+
+    /// Returns the local date of a UTC instant.
+    #[must_use]
+    pub fn local_date(&self, instant: DateTime<Utc>) -> NaiveDate {
+        let provisional = instant.date_naive();
+        instant
+            .with_timezone(&self.offset(provisional))
+            .date_naive()
+    }
+}
+
+/// Settlement convention of an expiring option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Settlement {
+    /// Settled on the opening print of the expiration date (e.g. monthly SPX).
+    AmSettled,
+    /// Settled on the close of the expiration date (e.g. SPXW, equity options,
+    /// 0DTE at 16:00 New York).
+    PmSettled,
+}
+
+/// Trading sessions, holidays and early closes of an exchange.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TradingCalendar {
+    /// Name of the calendar.
+    pub name: String,
+    /// Exchange whose holiday rules apply, if any.
+    pub exchange: Option<Exchange>,
+    /// Time zone of the session times.
+    pub zone: MarketZone,
+    /// Local opening time of the regular session.
+    pub open: NaiveTime,
+    /// Local closing time of the regular session.
+    pub close: NaiveTime,
+    /// Additional non-trading dates.
+    #[serde(default)]
+    pub holidays: BTreeSet<NaiveDate>,
+    /// Additional early closes, by date, with their local closing time.
+    #[serde(default)]
+    pub early_closes: BTreeMap<NaiveDate, NaiveTime>,
+}
+
+impl TradingCalendar {
+    /// Creates a calendar without holidays, trading on weekdays between
+    /// `open` and `close` local time.
+    #[must_use]
+    pub fn new(name: &str, zone: MarketZone, open: NaiveTime, close: NaiveTime) -> Self {
+        Self {
+            name: name.to_string(),
+            exchange: None,
+            zone,
+            open,
+            close,
+            holidays: BTreeSet::new(),
+            early_closes: BTreeMap::new(),
+        }
+    }
+
+    /// Creates the built-in calendar of an exchange.
+    #[must_use]
+    pub fn for_exchange(exchange: Exchange) -> Self {
+        let (name, zone, open, close) = match exchange {
+            Exchange::Nyse => ("NYSE", MarketZone::NewYork, hm(9, 30), hm(16, 0)),
+            Exchange::Cme => ("CME", MarketZone::Chicago, hm(8, 30), hm(15, 0)),
+            Exchange::Eurex => ("Eurex", MarketZone::Frankfurt, hm(9, 0), hm(17, 30)),
+        };
+        Self {
+            exchange: Some(exchange),
+            ..Self::new(name, zone, open, close)
+        }
+    }
+
+    /// New York Stock Exchange calendar.
+    #[must_use]
+    pub fn nyse() -> Self {
+        Self::for_exchange(Exchange::Nyse)
+    }
+
+    /// CME Group equity-index calendar.
+    #[must_use]
+    pub fn cme() -> Self {
+        Self::for_exchange(Exchange::Cme)
+    }
+
+    /// Eurex calendar.
+    #[must_use]
+    pub fn eurex() -> Self {
+        Self::for_exchange(Exchange::Eurex)
+    }
+
+    /// Adds non-trading dates.
+    #[must_use]
+    pub fn with_holidays(mut self, holidays: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(holidays);
+        self
+    }
+
+    /// Adds an early close at `close` local time.
+    #[must_use]
+    pub fn with_early_close(mut self, date: NaiveDate, close: NaiveTime) -> Self {
+        self.early_closes.insert(date, close);
+        self
+    }
+
+    /// Adds the holidays of a list with one `YYYY-MM-DD` date per line. Blank
+    /// lines and text after `#` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpirationDateError::ChronoParseError`] for a line that is
+    /// not a date.
+    pub fn with_holiday_list(self, list: &str) -> Result<Self, ExpirationDateError> {
+        let dates = list
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| NaiveDate::parse_from_str(line, "%Y-%m-%d"))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.with_holidays(dates))
+    }
+
+    /// Returns `true` when the exchange is closed all day on a weekday.
+    #[must_use]
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date)
+            || match self.exchange {
+                Some(Exchange::Nyse | Exchange::Cme) => us_holiday(date),
+                Some(Exchange::Eurex) => eurex_holiday(date),
+                None => false,
+            }
+    }
+
+    /// Returns `true` when the exchange has a session on `date`.
+    #[must_use]
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+
+    /// Returns the first trading day after `date`.
+    #[must_use]
+    pub fn next_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut next = date;
+        while let Some(day) = next.succ_opt() {
+            next = day;
+            if self.is_trading_day(next) {
+                break;
+            }
+        }
+        next
+    }
+
+    /// Returns the last trading day before `date`.
+    #[must_use]
+    pub fn previous_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut previous = date;
+        while let Some(day) = previous.pred_opt() {
+            previous = day;
+            if self.is_trading_day(previous) {
+                break;
+            }
+        }
+        previous
+    }
+
+    /// Returns the local closing time on `date`, accounting for early closes.
+    #[must_use]
+    pub fn closing_time(&self, date: NaiveDate) -> NaiveTime {
+        if let Some(close) = self.early_closes.get(&date) {
+            return *close;
+        }
+        let early = match self.exchange {
+            Some(Exchange::Nyse) if us_early_close(date) => Some(hm(13, 0)),
+            Some(Exchange::Cme) if us_early_close(date) => Some(hm(12, 0)),
+            _ => None,
+        };
+        early.unwrap_or(self.close)
+    }
+
+    /// Returns the session on `date` as UTC instants, or `None` when the
+    /// exchange is closed.
+    #[must_use]
+    pub fn session(&self, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        self.is_trading_day(date).then(|| {
+            (
+                self.zone.to_utc(date, self.open),
+                self.zone.to_utc(date, self.closing_time(date)),
+            )
+        })
+    }
+
+    /// Length of the regular session in minutes.
+    #[must_use]
+    pub fn session_minutes(&self) -> f64 {
+        (self.close - self.open).num_seconds() as f64 / 60.0
+    }
+
+    /// Number of trading days after the date of `start` up to and including
+    /// the date of `end`, both taken in the exchange's time zone.
+    #[must_use]
+    pub fn business_days_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> u32 {
+        let first = self.zone.local_date(start);
+        let last = self.zone.local_date(end);
+        first
+            .iter_days()
+            .skip(1)
+            .take_while(|date| *date <= last)
+            .filter(|date| self.is_trading_day(*date))
+            .fold(0, |count, _| count + 1)
+    }
+
+    /// Trading minutes between two instants, counting only the time inside
+    /// sessions.
+    #[must_use]
+    pub fn trading_minutes_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+        self.overlap(start, end, |date| self.session(date)) / 60.0
+    }
+
+    /// Business days between two instants as a fraction: every business day
+    /// counts for its full 24 hours, weekends and holidays count for nothing.
+    #[must_use]
+    pub fn business_time_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+        let seconds = self.overlap(start, end, |date| {
+            let next = date.succ_opt()?;
+            self.is_trading_day(date).then(|| {
+                (
+                    self.zone.to_utc(date, NaiveTime::MIN),
+                    self.zone.to_utc(next, NaiveTime::MIN),
+                )
+            })
+        });
+        seconds / SECONDS_PER_DAY
+    }
+
+    /// Returns the expiration of an option expiring on `date` with the given
+    /// settlement: the session open for AM-settled options, the session close
+    /// for PM-settled ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpirationDateError::InvalidDateTime`] when the exchange is
+    /// closed on `date`.
+    pub fn expiration(
+        &self,
+        date: NaiveDate,
+        settlement: Settlement,
+    ) -> Result<ExpirationDate, ExpirationDateError> {
+        let (open, close) = self.session(date).ok_or_else(|| {
+            ExpirationDateError::InvalidDateTime(format!("{} is closed on {date}", self.name))
+        })?;
+        Ok(ExpirationDate::DateTime(match settlement {
+            Settlement::AmSettled => open,
+            Settlement::PmSettled => close,
+        }))
+    }
+
+    /// Seconds of `[start, end)` falling inside the per-date windows returned
+    /// by `window`.
+    fn overlap(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        window: impl Fn(NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)>,
+    ) -> f64 {
+        if end <= start {
+            return 0.0;
+        }
+        let last = self.zone.local_date(end);
+        self.zone
+            .local_date(start)
+            .iter_days()
+            .take_while(|date| *date <= last)
+            .filter_map(window)
+            .map(|(from, to)| {
+                let from = from.max(start);
+                let to = to.min(end);
+                if to > from {
+                    (to - from).num_seconds() as f64
+                } else {
+                    0.0
+                }
+            })
+            .sum()
+    }
+}
+
+/// Convention turning the time to an expiration into a year fraction.
+///
+/// Pricing, Greeks and theta read the time to expiry of an option through its
+/// basis, and theta is reported per unit day of the basis.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum DayCountBasis {
+    /// Calendar days over 365.
+    #[default]
+    Actual365Fixed,
+    /// Calendar days over 360.
+    Actual360,
+    /// Time elapsed on business days of the calendar, over 252 days.
+    BusinessDays(TradingCalendar),
+    /// Time elapsed inside trading sessions of the calendar, over 252 regular
+    /// sessions.
+    TradingMinutes(TradingCalendar),
+}
+
+impl DayCountBasis {
+    /// Returns `true` for the default Actual/365 Fixed basis.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        matches!(self, DayCountBasis::Actual365Fixed)
+    }
+
+    /// Number of unit days in a year; theta is reported per unit day.
+    #[must_use]
+    pub fn days_per_year(&self) -> Decimal {
+        match self {
+            DayCountBasis::Actual365Fixed => DAYS_IN_A_YEAR.to_dec(),
+            DayCountBasis::Actual360 => dec!(360),
+            DayCountBasis::BusinessDays(_) | DayCountBasis::TradingMinutes(_) => dec!(252),
+        }
+    }
+
+    /// Year fraction between two instants.
+    #[must_use]
+    pub fn year_fraction(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+        if end <= start {
+            return 0.0;
+        }
+        let calendar_days = (end - start).num_seconds() as f64 / SECONDS_PER_DAY;
+        match self {
+            DayCountBasis::Actual365Fixed => calendar_days / DAYS_IN_A_YEAR.to_f64(),
+            DayCountBasis::Actual360 => calendar_days / 360.0,
+            DayCountBasis::BusinessDays(calendar) => {
+                calendar.business_time_between(start, end) / TRADING_DAYS_PER_YEAR
+            }
+            DayCountBasis::TradingMinutes(calendar) => {
+                calendar.trading_minutes_between(start, end)
+                    / (calendar.session_minutes() * TRADING_DAYS_PER_YEAR)
+            }
+        }
+    }
+
+    /// Years from now to `expiration`.
+    ///
+    /// The default basis defers to [`ExpirationDate::get_years`]. The other
+    /// bases resolve `ExpirationDate::Days` as that many calendar days from
+    /// now.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpirationDateError`] when the expiration cannot be resolved
+    /// or the year fraction is not a valid [`Positive`].
+    pub fn years_to(&self, expiration: &ExpirationDate) -> Result<Positive, ExpirationDateError> {
+        if self.is_default() {
+            return expiration.get_years();
+        }
+        let now = Utc::now();
+        let end = match expiration {
+            ExpirationDate::Days(days) => {
+                let seconds = (days.to_f64() * SECONDS_PER_DAY).round() as i64;
+                now + Duration::try_seconds(seconds).ok_or_else(|| {
+                    ExpirationDateError::ArithmeticOverflow(format!("{days} days"))
+                })?
+            }
+            ExpirationDate::DateTime(date_time) => *date_time,
+        };
+        Ok(Positive::new(self.year_fraction(now, end))?)
+    }
+}
+
+fn hm(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or(NaiveTime::MIN)
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// `n`-th (1-based) `weekday` of a month, or the last one when `n` is 0.
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> Option<NaiveDate> {
+    if n == 0 {
+        let first_next = if month == 12 {
+            ymd(year + 1, 1, 1)?
+        } else {
+            ymd(year, month + 1, 1)?
+        };
+        let mut date = first_next.pred_opt()?;
+        while date.weekday() != weekday {
+            date = date.pred_opt()?;
+        }
+        return Some(date);
+    }
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+}
+
+/// Easter Sunday in the Gregorian calendar (anonymous Gregorian algorithm).
+fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    ymd(year, u32::try_from(month).ok()?, u32::try_from(day).ok()?)
+}
+
+/// Weekday on which a fixed-date holiday is observed: Saturday moves to
+/// Friday and Sunday to Monday.
+fn observed(date: Option<NaiveDate>) -> Option<NaiveDate> {
+    let date = date?;
+    match date.weekday() {
+        Weekday::Sat => date.pred_opt(),
+        Weekday::Sun => date.succ_opt(),
+        _ => Some(date),
+    }
+}
+
+/// NYSE holidays, which CME equity-index options also observe.
+fn us_holiday(date: NaiveDate) -> bool {
+    let year = date.year();
+    let mut holidays = vec![
+        nth_weekday(year, 1, Weekday::Mon, 3),
+        nth_weekday(year, 2, Weekday::Mon, 3),
+        easter(year).and_then(|sunday| sunday.checked_sub_signed(Duration::days(2))),
+        nth_weekday(year, 5, Weekday::Mon, 0),
+        observed(ymd(year, 7, 4)),
+        nth_weekday(year, 9, Weekday::Mon, 1),
+        nth_weekday(year, 11, Weekday::Thu, 4),
+        observed(ymd(year, 12, 25)),
+    ];
+    // New Year's Day falling on a Saturday is not observed on the Friday.
+    if let Some(new_year) = ymd(year, 1, 1).filter(|day| day.weekday() != Weekday::Sat) {
+        holidays.push(observed(Some(new_year)));
+    }
+    if year >= 2022 {
+        holidays.push(observed(ymd(year, 6, 19)));
+    }
+    holidays.contains(&Some(date))
+}
+
+/// NYSE early closes at 13:00: the eves of Independence Day and Christmas
+/// from Monday to Thursday, and the day after Thanksgiving.
+fn us_early_close(date: NaiveDate) -> bool {
+    let year = date.year();
+    let weekday_eve =
+        |month, day| ymd(year, month, day).filter(|eve| eve.weekday().num_days_from_monday() < 4);
+    [
+        weekday_eve(7, 3),
+        weekday_eve(12, 24),
+        nth_weekday(year, 11, Weekday::Thu, 4).and_then(|thanksgiving| thanksgiving.succ_opt()),
+    ]
+    .contains(&Some(date))
+}
+
+/// Eurex holidays: New Year, Good Friday, Easter Monday, Labour Day and
+/// Christmas Eve to New Year's Eve except the 27th–30th.
+fn eurex_holiday(date: NaiveDate) -> bool {
+    let year = date.year();
+    let easter_sunday = easter(year);
+    [
+        ymd(year, 1, 1),
+        easter_sunday.and_then(|sunday| sunday.checked_sub_signed(Duration::days(2))),
+        easter_sunday.and_then(|sunday| sunday.succ_opt()),
+        ymd(year, 5, 1),
+        ymd(year, 12, 24),
+        ymd(year, 12, 25),
+        ymd(year, 12, 26),
+        ymd(year, 12, 31),
+    ]
+    .contains(&Some(date))
+}
+
+/// US daylight saving time: second Sunday of March to first Sunday of
+/// November.
+fn us_daylight_saving(date: NaiveDate) -> bool {
+    let year = date.year();
+    match (
+        nth_weekday(year, 3, Weekday::Sun, 2),
+        nth_weekday(year, 11, Weekday::Sun, 1),
+    ) {
+        (Some(start), Some(end)) => date >= start && date < end,
+        _ => false,
+    }
+}
+
+/// European summer time: last Sunday of March to last Sunday of October.
+fn eu_summer_time(date: NaiveDate) -> bool {
+    let year = date.year();
+    match (
+        nth_weekday(year, 3, Weekday::Sun, 0),
+        nth_weekday(year, 10, Weekday::Sun, 0),
+    ) {
+        (Some(start), Some(end)) => date >= start && date < end,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests_calendar {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_nyse_holidays_and_early_closes() {
+        let nyse = TradingCalendar::nyse();
+        // Good Friday, Juneteenth, Thanksgiving and observed Christmas 2026.
+        for holiday in [
+            date(2026, 4, 3),
+            date(2026, 6, 19),
+            date(2026, 11, 26),
+            date(2027, 12, 24),
+        ] {
+            assert!(!nyse.is_trading_day(holiday), "{holiday}");
+        }
+        assert!(!nyse.is_trading_day(date(2026, 10, 17)));
+        assert!(nyse.is_trading_day(date(2026, 10, 16)));
+        assert_eq!(nyse.closing_time(date(2026, 11, 27)), hm(13, 0));
+        assert_eq!(nyse.closing_time(date(2026, 11, 30)), hm(16, 0));
+        assert_eq!(nyse.next_trading_day(date(2026, 4, 2)), date(2026, 4, 6));
+        assert_eq!(
+            nyse.previous_trading_day(date(2026, 4, 6)),
+            date(2026, 4, 2)
+        );
+
+        let eurex = TradingCalendar::eurex();
+        assert!(!eurex.is_trading_day(date(2026, 4, 6)));
+        assert!(!eurex.is_trading_day(date(2026, 12, 31)));
+        assert!(eurex.is_trading_day(date(2026, 11, 26)));
+    }
+
+    #[test]
+    fn test_session_times_follow_daylight_saving() {
+        let nyse = TradingCalendar::nyse();
+        let (open, close) = nyse.session(date(2026, 7, 15)).unwrap();
+        assert_eq!(open, utc(2026, 7, 15, 13, 30));
+        assert_eq!(close, utc(2026, 7, 15, 20, 0));
+        let (_, close) = nyse.session(date(2026, 12, 15)).unwrap();
+        assert_eq!(close, utc(2026, 12, 15, 21, 0));
+
+        let (open, _) = TradingCalendar::eurex().session(date(2026, 1, 15)).unwrap();
+        assert_eq!(open, utc(2026, 1, 15, 8, 0));
+
+        let cme = TradingCalendar::cme();
+        assert_eq!(
+            cme.session(date(2026, 7, 15)).unwrap(),
+            nyse.session(date(2026, 7, 15)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_expiration_settlement_times() {
+        let nyse = TradingCalendar::nyse();
+        let pm = nyse
+            .expiration(date(2026, 10, 16), Settlement::PmSettled)
+            .unwrap();
+        assert_eq!(pm, ExpirationDate::DateTime(utc(2026, 10, 16, 20, 0)));
+        let am = nyse
+            .expiration(date(2026, 10, 16), Settlement::AmSettled)
+            .unwrap();
+        assert_eq!(am, ExpirationDate::DateTime(utc(2026, 10, 16, 13, 30)));
+        assert!(
+            nyse.expiration(date(2026, 10, 17), Settlement::PmSettled)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_weekend_does_not_decay_on_business_bases() {
+        let nyse = TradingCalendar::nyse();
+        // Friday close to Monday close.
+        let friday = utc(2026, 10, 16, 20, 0);
+        let monday = utc(2026, 10, 19, 20, 0);
+        assert_eq!(nyse.business_days_between(friday, monday), 1);
+        assert!((nyse.business_time_between(friday, monday) - 1.0).abs() < 1e-12);
+        assert!((nyse.trading_minutes_between(friday, monday) - 390.0).abs() < 1e-12);
+
+        let business = DayCountBasis::BusinessDays(nyse.clone());
+        let minutes = DayCountBasis::TradingMinutes(nyse);
+        let calendar = DayCountBasis::Actual365Fixed;
+        assert!((business.year_fraction(friday, monday) - 1.0 / 252.0).abs() < 1e-12);
+        assert!((minutes.year_fraction(friday, monday) - 1.0 / 252.0).abs() < 1e-12);
+        assert!((calendar.year_fraction(friday, monday) - 3.0 / 365.0).abs() < 1e-12);
+        assert_eq!(business.days_per_year(), dec!(252));
+    }
+
+    #[test]
+    fn test_days_expiry_ignores_the_reference_datetime() {
+        // `get_days` on a dated expiry stores that date as the thread's
+        // reference datetime; a relative expiry must still count from now.
+        let distant = ExpirationDate::DateTime(Utc::now() + Duration::days(3_650));
+        assert!(distant.get_days().unwrap() > Positive::ZERO);
+        let years = DayCountBasis::Actual360
+            .years_to(&ExpirationDate::Days(Positive::new(90.0).unwrap()))
+            .unwrap();
+        assert!((years.to_f64() - 0.25).abs() < 1e-6, "{years}");
+    }
+
+    #[test]
+    fn test_zero_dte_trading_minutes() {
+        let minutes = DayCountBasis::TradingMinutes(TradingCalendar::nyse());
+        // 14:00 to the 16:00 close in New York, summer time.
+        let fraction = minutes.year_fraction(utc(2026, 7, 15, 18, 0), utc(2026, 7, 15, 20, 0));
+        assert!((fraction - 120.0 / (390.0 * 252.0)).abs() < 1e-12);
+        // Overnight time outside the session does not count.
+        let overnight = minutes.year_fraction(utc(2026, 7, 15, 20, 0), utc(2026, 7, 16, 13, 30));
+        assert_eq!(overnight, 0.0);
+    }
+
+    #[test]
+    fn test_holiday_list_and_serde() {
+        let calendar = TradingCalendar::new("Custom", MarketZone::Utc, hm(8, 0), hm(16, 0))
+            .with_holiday_list("# closures\n2026-10-19\n\n2026-10-20 # maintenance\n")
+            .unwrap()
+            .with_early_close(date(2026, 10, 21), hm(12, 0));
+        assert!(!calendar.is_trading_day(date(2026, 10, 19)));
+        assert!(!calendar.is_trading_day(date(2026, 10, 20)));
+        assert_eq!(calendar.closing_time(date(2026, 10, 21)), hm(12, 0));
+        assert!(calendar.with_holiday_list("not a date").is_err());
+
+        let basis = DayCountBasis::TradingMinutes(TradingCalendar::eurex());
+        let json = serde_json::to_string(&basis).unwrap();
+        assert_eq!(serde_json::from_str::<DayCountBasis>(&json).unwrap(), basis);
+    }
+}
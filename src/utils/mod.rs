@@ -183,11 +183,16 @@ pub mod file;
 /// Module for time-related utilities.
 pub mod time;
 
+/// Exchange trading calendars, settlement times and day-count bases for time
+/// to expiry.
+pub mod calendar;
+
 /// This module contains traits and type definitions used throughout the library.  It provides
 /// functionality for defining and implementing common traits, as well as type aliases for
 /// convenience.
 mod traits;
 
+pub use calendar::{DayCountBasis, Exchange, MarketZone, Settlement, TradingCalendar};
 #[cfg(feature = "async")]
 pub use csv::read_ohlcv_from_zip_async;
 pub use csv::{OhlcvCandle, read_ohlcv_from_zip};
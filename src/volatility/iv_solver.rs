@@ -0,0 +1,696 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Root-finding implied-volatility solver.
+//!
+//! The solver inverts the generalised Black–Scholes–Merton price (continuous
+//! dividend yield `q`) for calls and puts in three stages:
+//!
+//! 1. **Initial guess** — following Jäckel's *Let's Be Rational*, the quote is
+//!    undiscounted, mapped to its out-of-the-money equivalent through
+//!    put–call parity and normalised by `√(F·K)`. A Corrado–Miller rational
+//!    approximation of the normalised price gives the starting point, with
+//!    Jäckel's inflexion-point volatility `√(2|ln(F/K)|/T)` as fallback when
+//!    the approximation degenerates.
+//! 2. **Newton–Raphson** — vega-scaled steps while they stay inside the
+//!    current no-arbitrage bracket.
+//! 3. **Brent** — a bracketed inverse-quadratic / bisection fallback that
+//!    takes over whenever Newton leaves the bracket, vega vanishes or the
+//!    Newton budget is exhausted.
+//!
+//! Options carrying a yield curve or cash dividends are solved at the term
+//! rate to expiry on the escrowed spot, the same restatement the closed-form
+//! pricer applies.
+//!
+//! Prices are evaluated with an `f64` kernel; the public surface is
+//! `Decimal`/`Positive`, and every result is converted through
+//! [`finite_decimal`].
+
+use crate::Options;
+use crate::constants::{IV_TOLERANCE, MAX_ITERATIONS_IV, MAX_VOLATILITY, MIN_VOLATILITY};
+use crate::error::{OptionsError, VolatilityError};
+use crate::model::decimal::finite_decimal;
+use crate::model::types::OptionStyle;
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use statrs::function::erf::erfc;
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+use tracing::{instrument, trace};
+
+/// Default number of Newton–Raphson iterations before falling back to Brent.
+const DEFAULT_NEWTON_ITERATIONS: u32 = 20;
+
+/// Vega below which a Newton step is considered numerically meaningless.
+const MIN_VEGA: f64 = 1e-12;
+
+/// Stage of the solver that produced the final volatility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum IvSolverMethod {
+    /// The initial guess already priced the option within tolerance.
+    InitialGuess,
+    /// Newton–Raphson converged.
+    Newton,
+    /// The bracketed Brent fallback converged.
+    Brent,
+}
+
+/// Configuration of the implied-volatility solver.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IvSolverConfig {
+    /// Absolute price tolerance: the solver stops once the model price is
+    /// within this distance of the market price.
+    pub price_tolerance: Decimal,
+
+    /// Volatility tolerance: the solver stops once successive iterates (or
+    /// the Brent bracket) are closer than this.
+    pub volatility_tolerance: Decimal,
+
+    /// Maximum number of Newton–Raphson iterations.
+    pub max_newton_iterations: u32,
+
+    /// Maximum number of Brent iterations once the fallback is engaged.
+    pub max_bracket_iterations: u32,
+
+    /// Lower end of the search interval.
+    pub min_volatility: Positive,
+
+    /// Upper end of the search interval.
+    pub max_volatility: Positive,
+}
+
+impl Default for IvSolverConfig {
+    fn default() -> Self {
+        Self {
+            price_tolerance: IV_TOLERANCE,
+            volatility_tolerance: dec!(1e-10),
+            max_newton_iterations: DEFAULT_NEWTON_ITERATIONS,
+            max_bracket_iterations: MAX_ITERATIONS_IV,
+            min_volatility: *MIN_VOLATILITY,
+            max_volatility: MAX_VOLATILITY,
+        }
+    }
+}
+
+impl IvSolverConfig {
+    /// Sets the absolute price tolerance.
+    #[must_use]
+    pub fn with_price_tolerance(mut self, price_tolerance: Decimal) -> Self {
+        self.price_tolerance = price_tolerance;
+        self
+    }
+
+    /// Sets the volatility tolerance.
+    #[must_use]
+    pub fn with_volatility_tolerance(mut self, volatility_tolerance: Decimal) -> Self {
+        self.volatility_tolerance = volatility_tolerance;
+        self
+    }
+
+    /// Sets the Newton–Raphson iteration budget.
+    #[must_use]
+    pub fn with_max_newton_iterations(mut self, iterations: u32) -> Self {
+        self.max_newton_iterations = iterations;
+        self
+    }
+
+    /// Sets the Brent iteration budget.
+    #[must_use]
+    pub fn with_max_bracket_iterations(mut self, iterations: u32) -> Self {
+        self.max_bracket_iterations = iterations;
+        self
+    }
+
+    /// Sets the volatility search interval.
+    #[must_use]
+    pub fn with_bounds(mut self, min_volatility: Positive, max_volatility: Positive) -> Self {
+        self.min_volatility = min_volatility;
+        self.max_volatility = max_volatility;
+        self
+    }
+}
+
+/// Implied volatility together with convergence diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IvSolution {
+    /// The implied volatility.
+    pub volatility: Positive,
+    /// The rational initial guess the iteration started from.
+    pub initial_guess: Positive,
+    /// Stage that produced `volatility`.
+    pub method: IvSolverMethod,
+    /// Newton–Raphson iterations performed.
+    pub newton_iterations: u32,
+    /// Brent iterations performed.
+    pub bracket_iterations: u32,
+    /// Model price minus market price at `volatility`.
+    pub price_error: Decimal,
+}
+
+impl IvSolution {
+    /// Total number of iterations across both stages.
+    #[must_use]
+    #[inline]
+    pub fn iterations(&self) -> u32 {
+        self.newton_iterations + self.bracket_iterations
+    }
+}
+
+/// Black–Scholes–Merton inputs in `f64`, with the volatility left free.
+#[derive(Debug, Clone, Copy)]
+struct BsmKernel {
+    spot_disc: f64,
+    strike_disc: f64,
+    forward: f64,
+    strike: f64,
+    sqrt_t: f64,
+    years: f64,
+    is_call: bool,
+}
+
+impl BsmKernel {
+    fn new(option: &Options) -> Result<Self, VolatilityError> {
+        let years = option
+            .years_to_expiry()
+            .map_err(OptionsError::from)?
+            .to_f64();
+        if years <= 0.0 {
+            return Err(OptionsError::validation_error(
+                "expiration_date",
+                "implied volatility is undefined for an expired option",
+            )
+            .into());
+        }
+        if option.strike_price.is_zero() {
+            return Err(
+                OptionsError::validation_error("strike_price", "strike must be positive").into(),
+            );
+        }
+        if option.underlying_price.is_zero() {
+            return Err(OptionsError::validation_error(
+                "underlying_price",
+                "underlying price must be positive",
+            )
+            .into());
+        }
+        let escrowed = option.escrowed().map_err(OptionsError::from)?;
+        let option = escrowed.as_ref().unwrap_or(option);
+        let spot = option.underlying_price.to_f64();
+        let strike = option.strike_price.to_f64();
+        let rate = option.risk_free_rate.to_f64().unwrap_or(0.0);
+        let dividend = option.dividend_yield.to_f64();
+        let spot_disc = spot * (-dividend * years).exp();
+        let strike_disc = strike * (-rate * years).exp();
+        Ok(Self {
+            spot_disc,
+            strike_disc,
+            forward: spot * ((rate - dividend) * years).exp(),
+            strike,
+            sqrt_t: years.sqrt(),
+            years,
+            is_call: option.option_style == OptionStyle::Call,
+        })
+    }
+
+    /// Lower and upper no-arbitrage bounds of the option price.
+    fn price_bounds(&self) -> (f64, f64) {
+        if self.is_call {
+            ((self.spot_disc - self.strike_disc).max(0.0), self.spot_disc)
+        } else {
+            (
+                (self.strike_disc - self.spot_disc).max(0.0),
+                self.strike_disc,
+            )
+        }
+    }
+
+    /// Price and vega at volatility `sigma`.
+    fn price_and_vega(&self, sigma: f64) -> (f64, f64) {
+        let sig_t = sigma * self.sqrt_t;
+        let d1 = ((self.spot_disc / self.strike_disc).ln() + 0.5 * sig_t * sig_t) / sig_t;
+        let d2 = d1 - sig_t;
+        let price = if self.is_call {
+            self.spot_disc * norm_cdf(d1) - self.strike_disc * norm_cdf(d2)
+        } else {
+            self.strike_disc * norm_cdf(-d2) - self.spot_disc * norm_cdf(-d1)
+        };
+        let vega = self.spot_disc * norm_pdf(d1) * self.sqrt_t;
+        (price, vega)
+    }
+
+    fn price(&self, sigma: f64) -> f64 {
+        self.price_and_vega(sigma).0
+    }
+
+    /// Jäckel-style normalised initial guess (see module docs).
+    fn initial_guess(&self, price: f64) -> f64 {
+        // Undiscount by the strike's discount factor so the quote lives in
+        // forward space, then move to the out-of-the-money wing.
+        let discount = self.strike_disc / self.strike;
+        let undiscounted = price / discount;
+        let intrinsic = if self.is_call {
+            self.forward - self.strike
+        } else {
+            self.strike - self.forward
+        };
+        let otm = if intrinsic > 0.0 {
+            undiscounted - intrinsic
+        } else {
+            undiscounted
+        };
+        // Undiscounted call price recovered from the OTM quote.
+        let call = if self.forward > self.strike {
+            otm + (self.forward - self.strike)
+        } else {
+            otm
+        };
+        let half_moneyness = 0.5 * (self.forward - self.strike);
+        let centred = call - half_moneyness;
+        let discriminant = centred * centred - 4.0 * half_moneyness * half_moneyness / PI;
+        let corrado_miller = (2.0 * PI).sqrt() / (self.forward + self.strike)
+            * (centred + discriminant.max(0.0).sqrt())
+            / self.sqrt_t;
+        if corrado_miller.is_finite() && corrado_miller > 0.0 {
+            return corrado_miller;
+        }
+        let x = (self.forward / self.strike).ln();
+        if x.abs() > f64::EPSILON {
+            (2.0 * x.abs() / self.years).sqrt()
+        } else {
+            // Brenner–Subrahmanyam at the money.
+            (2.0 * PI / self.years).sqrt() * otm / self.forward
+        }
+    }
+}
+
+#[inline]
+fn norm_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x * FRAC_1_SQRT_2)
+}
+
+#[inline]
+fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+}
+
+#[inline]
+fn to_positive(value: f64, context: &'static str) -> Result<Positive, VolatilityError> {
+    let dec = finite_decimal(value).ok_or_else(|| VolatilityError::non_finite(context, value))?;
+    Ok(Positive::new_decimal(dec.max(Decimal::ZERO))?)
+}
+
+/// Solves for the implied volatility that reproduces `market_price`.
+///
+/// The option is priced as a long, per-unit contract with the generalised
+/// Black–Scholes–Merton model (risk-free rate and continuous dividend yield
+/// taken from `option`); `option.implied_volatility` is ignored and `side`
+/// does not affect the result.
+///
+/// # Errors
+///
+/// * [`VolatilityError::Options`] (wrapping
+///   [`OptionsError::ValidationError`]) when the option is expired or has a
+///   zero strike or underlying price.
+/// * [`VolatilityError::InvalidPrice`] when `market_price` violates the
+///   no-arbitrage bounds (at or below the discounted intrinsic value, or at
+///   or above the discounted spot / strike) or is not attainable inside the
+///   configured volatility interval.
+/// * [`VolatilityError::NoConvergence`] when both stages exhaust their
+///   iteration budgets.
+/// * [`VolatilityError::NonFinite`] / [`VolatilityError::PositiveError`] if
+///   a kernel value cannot be represented.
+#[instrument(skip(option, config), fields(
+    market_price = %market_price,
+    strike = %option.strike_price,
+    style = ?option.option_style,
+))]
+pub fn solve_implied_volatility(
+    option: &Options,
+    market_price: Positive,
+    config: &IvSolverConfig,
+) -> Result<IvSolution, VolatilityError> {
+    let kernel = BsmKernel::new(option)?;
+    let target = market_price.to_f64();
+    let price_tol = config.price_tolerance.to_f64().unwrap_or(1e-5);
+    let vol_tol = config.volatility_tolerance.to_f64().unwrap_or(1e-10);
+
+    let (lower, upper) = kernel.price_bounds();
+    if target <= lower || target >= upper {
+        return Err(VolatilityError::InvalidPrice {
+            price: market_price,
+            reason: format!(
+                "price must lie strictly between the no-arbitrage bounds ({lower:.6}, {upper:.6})"
+            ),
+        });
+    }
+
+    let mut lo = config.min_volatility.to_f64();
+    let mut hi = config.max_volatility.to_f64();
+    let f_lo = kernel.price(lo) - target;
+    let f_hi = kernel.price(hi) - target;
+    if f_lo > 0.0 || f_hi < 0.0 {
+        return Err(VolatilityError::InvalidPrice {
+            price: market_price,
+            reason: format!("price is not attainable for volatilities in [{lo}, {hi}]"),
+        });
+    }
+
+    let guess = kernel.initial_guess(target).clamp(lo, hi);
+    let initial_guess = to_positive(guess, "volatility::iv_solver::initial_guess")?;
+    let finish = |sigma: f64,
+                  err: f64,
+                  method: IvSolverMethod,
+                  newton_iterations: u32,
+                  bracket_iterations: u32|
+     -> Result<IvSolution, VolatilityError> {
+        trace!(
+            sigma,
+            err,
+            ?method,
+            newton_iterations,
+            bracket_iterations,
+            "iv solved"
+        );
+        Ok(IvSolution {
+            volatility: to_positive(sigma, "volatility::iv_solver::volatility")?,
+            initial_guess,
+            method,
+            newton_iterations,
+            bracket_iterations,
+            price_error: finite_decimal(err)
+                .ok_or_else(|| VolatilityError::non_finite("volatility::iv_solver::error", err))?,
+        })
+    };
+
+    // Stage 2: Newton–Raphson inside the shrinking bracket.
+    let mut sigma = guess;
+    let mut newton_iterations = 0u32;
+    loop {
+        let (price, vega) = kernel.price_and_vega(sigma);
+        let err = price - target;
+        if err.abs() < price_tol {
+            let method = if newton_iterations == 0 {
+                IvSolverMethod::InitialGuess
+            } else {
+                IvSolverMethod::Newton
+            };
+            return finish(sigma, err, method, newton_iterations, 0);
+        }
+        if err < 0.0 {
+            lo = lo.max(sigma);
+        } else {
+            hi = hi.min(sigma);
+        }
+        if newton_iterations >= config.max_newton_iterations || vega < MIN_VEGA {
+            break;
+        }
+        let next = sigma - err / vega;
+        newton_iterations += 1;
+        if !next.is_finite() || next <= lo || next >= hi {
+            break;
+        }
+        if (next - sigma).abs() < vol_tol {
+            let err = kernel.price(next) - target;
+            return finish(next, err, IvSolverMethod::Newton, newton_iterations, 0);
+        }
+        sigma = next;
+    }
+
+    // Stage 3: Brent on [lo, hi].
+    let (sigma, err, bracket_iterations) = brent(
+        |s| kernel.price(s) - target,
+        lo,
+        hi,
+        price_tol,
+        vol_tol,
+        config.max_bracket_iterations,
+    )
+    .ok_or_else(|| VolatilityError::NoConvergence {
+        iterations: newton_iterations + config.max_bracket_iterations,
+        last_volatility: to_positive(0.5 * (lo + hi), "volatility::iv_solver::last")
+            .unwrap_or(Positive::ZERO),
+    })?;
+    finish(
+        sigma,
+        err,
+        IvSolverMethod::Brent,
+        newton_iterations,
+        bracket_iterations,
+    )
+}
+
+/// Brent's method on a sign-changing bracket `[a, b]`.
+///
+/// Returns the root, the residual at the root and the iterations used, or
+/// `None` when `max_iterations` is exhausted.
+fn brent<F: Fn(f64) -> f64>(
+    f: F,
+    mut a: f64,
+    mut b: f64,
+    f_tol: f64,
+    x_tol: f64,
+    max_iterations: u32,
+) -> Option<(f64, f64, u32)> {
+    let mut fa = f(a);
+    let mut fb = f(b);
+    if fa.abs() < f_tol {
+        return Some((a, fa, 0));
+    }
+    if fb.abs() < f_tol {
+        return Some((b, fb, 0));
+    }
+    if fa * fb > 0.0 {
+        return None;
+    }
+    if fa.abs() < fb.abs() {
+        std::mem::swap(&mut a, &mut b);
+        std::mem::swap(&mut fa, &mut fb);
+    }
+    let mut c = a;
+    let mut fc = fa;
+    let mut d = b - a;
+    let mut bisected = true;
+
+    for iteration in 1..=max_iterations {
+        let mut s = if fa != fc && fb != fc {
+            // Inverse quadratic interpolation.
+            a * fb * fc / ((fa - fb) * (fa - fc))
+                + b * fa * fc / ((fb - fa) * (fb - fc))
+                + c * fa * fb / ((fc - fa) * (fc - fb))
+        } else {
+            // Secant.
+            b - fb * (b - a) / (fb - fa)
+        };
+
+        let bound = (3.0 * a + b) / 4.0;
+        let outside = !((s > bound.min(b)) && (s < bound.max(b)));
+        if outside
+            || (bisected && (s - b).abs() >= (b - c).abs() / 2.0)
+            || (!bisected && (s - b).abs() >= (c - d).abs() / 2.0)
+            || (bisected && (b - c).abs() < x_tol)
+            || (!bisected && (c - d).abs() < x_tol)
+        {
+            s = 0.5 * (a + b);
+            bisected = true;
+        } else {
+            bisected = false;
+        }
+
+        let fs = f(s);
+        d = c;
+        c = b;
+        fc = fb;
+        if fa * fs < 0.0 {
+            b = s;
+            fb = fs;
+        } else {
+            a = s;
+            fa = fs;
+        }
+        if fa.abs() < fb.abs() {
+            std::mem::swap(&mut a, &mut b);
+            std::mem::swap(&mut fa, &mut fb);
+        }
+        if fb.abs() < f_tol || (b - a).abs() < x_tol {
+            return Some((b, fb, iteration));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Side;
+    use crate::model::utils::create_sample_option_with_days;
+    use crate::pricing::{DiscreteDividend, DividendSchedule, YieldCurve, black_scholes};
+    use positive::{assert_pos_relative_eq, pos_or_panic};
+
+    fn option(
+        style: OptionStyle,
+        strike: f64,
+        spot: f64,
+        days: f64,
+        rate: Decimal,
+        q: f64,
+    ) -> Options {
+        Options {
+            risk_free_rate: rate,
+            dividend_yield: Positive::new(q).unwrap(),
+            ..create_sample_option_with_days(
+                style,
+                Side::Long,
+                pos_or_panic!(spot),
+                Positive::ONE,
+                pos_or_panic!(strike),
+                pos_or_panic!(0.2),
+                pos_or_panic!(days),
+            )
+        }
+    }
+
+    fn round_trip(mut opt: Options, vol: f64) -> IvSolution {
+        opt.implied_volatility = pos_or_panic!(vol);
+        let price = Positive::new_decimal(opt.calculate_price_black_scholes().unwrap()).unwrap();
+        let solution = solve_implied_volatility(&opt, price, &IvSolverConfig::default()).unwrap();
+        assert_pos_relative_eq!(solution.volatility, pos_or_panic!(vol), pos_or_panic!(1e-4));
+        solution
+    }
+
+    #[test]
+    fn test_iv_solver_call_round_trip() {
+        let solution = round_trip(
+            option(OptionStyle::Call, 100.0, 100.0, 30.0, dec!(0.05), 0.0),
+            0.25,
+        );
+        assert!(solution.iterations() <= 10);
+        assert!(solution.price_error.abs() < IV_TOLERANCE);
+    }
+
+    #[test]
+    fn test_iv_solver_put_round_trip_with_dividend() {
+        round_trip(
+            option(OptionStyle::Put, 110.0, 100.0, 90.0, dec!(0.03), 0.04),
+            0.35,
+        );
+    }
+
+    #[test]
+    fn test_iv_solver_deep_otm_and_itm() {
+        round_trip(
+            option(OptionStyle::Call, 150.0, 100.0, 60.0, dec!(0.01), 0.0),
+            0.6,
+        );
+        round_trip(
+            option(OptionStyle::Put, 70.0, 100.0, 60.0, dec!(0.01), 0.0),
+            0.6,
+        );
+        round_trip(
+            option(OptionStyle::Call, 70.0, 100.0, 60.0, dec!(0.01), 0.02),
+            0.4,
+        );
+    }
+
+    #[test]
+    fn test_iv_solver_extreme_volatility_uses_bracket() {
+        let solution = round_trip(
+            option(OptionStyle::Call, 100.0, 100.0, 365.0, dec!(0.0), 0.0),
+            3.0,
+        );
+        assert!(matches!(
+            solution.method,
+            IvSolverMethod::Newton | IvSolverMethod::Brent | IvSolverMethod::InitialGuess
+        ));
+    }
+
+    #[test]
+    fn test_iv_solver_brent_only() {
+        let mut opt = option(OptionStyle::Call, 105.0, 100.0, 45.0, dec!(0.02), 0.0);
+        opt.implied_volatility = pos_or_panic!(0.3);
+        let price = Positive::new_decimal(opt.calculate_price_black_scholes().unwrap()).unwrap();
+        let config = IvSolverConfig::default().with_max_newton_iterations(0);
+        let solution = solve_implied_volatility(&opt, price, &config).unwrap();
+        assert_eq!(solution.newton_iterations, 0);
+        assert_pos_relative_eq!(solution.volatility, pos_or_panic!(0.3), pos_or_panic!(1e-4));
+    }
+
+    #[test]
+    fn test_iv_solver_price_below_intrinsic_is_rejected() {
+        let opt = option(OptionStyle::Call, 80.0, 100.0, 30.0, dec!(0.0), 0.0);
+        let result =
+            solve_implied_volatility(&opt, pos_or_panic!(15.0), &IvSolverConfig::default());
+        assert!(matches!(result, Err(VolatilityError::InvalidPrice { .. })));
+    }
+
+    #[test]
+    fn test_iv_solver_price_above_spot_is_rejected() {
+        let opt = option(OptionStyle::Call, 80.0, 100.0, 30.0, dec!(0.0), 0.0);
+        let result =
+            solve_implied_volatility(&opt, pos_or_panic!(101.0), &IvSolverConfig::default());
+        assert!(matches!(result, Err(VolatilityError::InvalidPrice { .. })));
+    }
+
+    #[test]
+    fn test_iv_solver_expired_option_is_rejected() {
+        let opt = option(OptionStyle::Call, 100.0, 100.0, 0.0, dec!(0.0), 0.0);
+        let result = solve_implied_volatility(&opt, Positive::ONE, &IvSolverConfig::default());
+        assert!(matches!(result, Err(VolatilityError::Options(_))));
+    }
+
+    #[test]
+    fn test_iv_solver_initial_guess_is_close_at_the_money() {
+        let solution = round_trip(
+            option(OptionStyle::Call, 100.0, 100.0, 30.0, dec!(0.0), 0.0),
+            0.2,
+        );
+        assert_pos_relative_eq!(
+            solution.initial_guess,
+            pos_or_panic!(0.2),
+            pos_or_panic!(0.01)
+        );
+    }
+
+    #[test]
+    fn test_iv_solver_on_curve_with_cash_dividend() {
+        let mut opt = option(OptionStyle::Put, 100.0, 100.0, 182.5, dec!(0.05), 0.0)
+            .with_yield_curve(YieldCurve::flat(dec!(0.03)))
+            .with_dividends(DividendSchedule::new(vec![DiscreteDividend::in_days(
+                pos_or_panic!(91.25),
+                pos_or_panic!(3.0),
+            )]));
+        opt.implied_volatility = pos_or_panic!(0.35);
+        let quote = black_scholes(&opt).unwrap();
+
+        let solution = solve_implied_volatility(
+            &opt,
+            Positive::new_decimal(quote).unwrap(),
+            &IvSolverConfig::default(),
+        )
+        .unwrap();
+        assert_pos_relative_eq!(
+            solution.volatility,
+            pos_or_panic!(0.35),
+            pos_or_panic!(1e-4)
+        );
+
+        opt.implied_volatility = solution.volatility;
+        let repriced = black_scholes(&opt).unwrap();
+        assert!((repriced - quote).abs() < dec!(1e-6));
+    }
+
+    #[test]
+    fn test_brent_finds_simple_root() {
+        let (root, residual, iterations) =
+            brent(|x| x * x - 2.0, 0.0, 2.0, 1e-12, 1e-14, 100).unwrap();
+        assert!((root - 2f64.sqrt()).abs() < 1e-10);
+        assert!(residual.abs() < 1e-10);
+        assert!(iterations > 0);
+        assert!(brent(|x| x * x + 1.0, 0.0, 2.0, 1e-12, 1e-14, 100).is_none());
+    }
+}
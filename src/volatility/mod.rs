@@ -20,6 +20,7 @@ use positive::pos_or_panic;
 //! - EWMA (Exponentially Weighted Moving Average)
 //! - GARCH(1,1)
 //! - Heston Stochastic Volatility
+//! - SABR Implied Volatility (Hagan et al., 2002)
 //! - Implied Volatility
 //! - Uncertain Volatility Bounds
 //! - Volatility Surface Interpolation
@@ -128,6 +129,43 @@ use positive::pos_or_panic;
 //! let heston_vol = simulate_heston_volatility(kappa, theta, xi, v0, dt, steps);
 //! ```
 //!
+//! ### SABR Implied Volatility
+//!
+//! Hagan's lognormal expansion of the SABR model gives the Black volatility
+//! of any strike from four parameters:
+//!
+//! ```rust
+//! use positive::{Positive, pos_or_panic};
+//! use rust_decimal_macros::dec;
+//! use optionstratlib::volatility::{SabrParams, sabr_implied_volatility};
+//!
+//! let params = SabrParams::new(pos_or_panic!(0.2), dec!(0.5), dec!(-0.3), pos_or_panic!(0.4))?;
+//! let vol = sabr_implied_volatility(Positive::HUNDRED, pos_or_panic!(90.0), Positive::ONE, &params)?;
+//! # Ok::<(), optionstratlib::error::VolatilityError>(())
+//! ```
+//!
+//! ### SVI and SSVI Surfaces
+//!
+//! [`SviSlice`] fits Gatheral's raw SVI to the implied volatilities of one
+//! `OptionChain`, and [`SsviSurface`] fits the surface SVI parameterisation
+//! to a whole `OptionSeries`. Both report their fit error, can be checked
+//! for butterfly and calendar arbitrage, and return a volatility for any
+//! strike (and, for SSVI, any maturity):
+//!
+//! ```rust
+//! use positive::{Positive, pos_or_panic};
+//! use optionstratlib::volatility::{SviSlice, VolatilityModel};
+//!
+//! let quotes: Vec<(Positive, Positive)> = [(80.0, 0.28), (90.0, 0.24), (100.0, 0.21), (110.0, 0.20), (120.0, 0.205)]
+//!     .iter()
+//!     .map(|&(k, v)| (pos_or_panic!(k), pos_or_panic!(v)))
+//!     .collect();
+//! let slice = SviSlice::calibrate(Positive::HUNDRED, Positive::ONE, &quotes)?;
+//! let vol = slice.volatility_at(pos_or_panic!(95.0))?;
+//! assert!(slice.arbitrage_report().is_arbitrage_free());
+//! # Ok::<(), optionstratlib::error::VolatilityError>(())
+//! ```
+//!
 //! ## Time Frame Handling
 //!
 //! The module includes utilities for converting between different time frames:
@@ -156,7 +194,8 @@ use positive::pos_or_panic;
 //! ## Implementation Notes
 //!
 //! - All volatility calculations ensure non-negative results
-//! - Implied volatility uses Newton-Raphson method with bounds
+//! - Implied volatility uses a rational initial guess, Newton-Raphson and a
+//!   bracketed Brent fallback
 //! - Surface interpolation uses bilinear interpolation
 //! - Time scaling follows the square root of time rule
 //! - Numerical stability is ensured through bounds checking
@@ -168,7 +207,14 @@ use positive::pos_or_panic;
 //! - RiskMetrics™ Technical Document for EWMA
 //! - Heston (1993) stochastic volatility model
 //! - GARCH by Bollerslev (1986)
+//! - Hagan, Kumar, Lesniewski & Woodward (2002), "Managing Smile Risk"
+//! - Gatheral & Jacquier (2014), "Arbitrage-free SVI volatility surfaces"
 
+mod iv_solver;
+pub(crate) mod optimize;
+mod sabr;
+mod ssvi;
+mod svi;
 mod traits;
 mod utils;
 
@@ -179,4 +225,11 @@ pub use utils::{
     uncertain_volatility_bounds, volatility_for_dt,
 };
 
-pub use traits::{AtmIvProvider, VolatilitySmile};
+pub use iv_solver::{IvSolution, IvSolverConfig, IvSolverMethod, solve_implied_volatility};
+pub use sabr::{SabrParams, SabrSmile, sabr_implied_volatility};
+pub use ssvi::{SsviNode, SsviParams, SsviSlice, SsviSurface};
+pub use svi::{
+    ArbitrageReport, ButterflyViolation, CalendarViolation, CalibrationReport, SVI_MIN_QUOTES,
+    SviParams, SviSlice, svi_arbitrage_report,
+};
+pub use traits::{AtmIvProvider, VolatilityModel, VolatilitySmile};
@@ -0,0 +1,182 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Derivative-free minimisation used by the model calibrations.
+//!
+//! The Nelder–Mead simplex method needs nothing but objective evaluations,
+//! which suits calibration objectives with penalties and clamped inner
+//! solves that are not smooth enough for gradient methods.
+
+/// Outcome of a Nelder–Mead minimisation.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Minimum {
+    /// Best point found.
+    pub(crate) point: Vec<f64>,
+    /// Objective value at `point`.
+    pub(crate) value: f64,
+    /// Number of simplex iterations performed.
+    pub(crate) iterations: usize,
+}
+
+/// Minimises `objective` with the Nelder–Mead simplex method.
+///
+/// The initial simplex is `start` plus one vertex per coordinate displaced
+/// by the matching entry of `steps`. Iteration stops when the spread of the
+/// objective over the simplex falls below `tolerance` or after
+/// `max_iterations`. Non-finite objective values are treated as `+∞`, so
+/// infeasible regions can be signalled by returning `f64::NAN` or
+/// `f64::INFINITY`.
+pub(crate) fn nelder_mead<F>(
+    mut objective: F,
+    start: &[f64],
+    steps: &[f64],
+    tolerance: f64,
+    max_iterations: usize,
+) -> Minimum
+where
+    F: FnMut(&[f64]) -> f64,
+{
+    let mut eval = |x: &[f64]| {
+        let value = objective(x);
+        if value.is_finite() {
+            value
+        } else {
+            f64::INFINITY
+        }
+    };
+    let n = start.len();
+    let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(n + 1);
+    simplex.push((start.to_vec(), eval(start)));
+    for (i, &step) in steps.iter().enumerate().take(n) {
+        let mut vertex = start.to_vec();
+        if let Some(x) = vertex.get_mut(i) {
+            *x += step;
+        }
+        let value = eval(&vertex);
+        simplex.push((vertex, value));
+    }
+
+    let mut iterations = 0;
+    while iterations < max_iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (Some(best), Some(worst)) = (simplex.first(), simplex.last()) else {
+            break;
+        };
+        let (best_value, worst_value) = (best.1, worst.1);
+        if worst_value.is_finite() && (worst_value - best_value).abs() <= tolerance {
+            break;
+        }
+        iterations += 1;
+
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| {
+                simplex
+                    .iter()
+                    .take(n)
+                    .map(|(x, _)| x.get(j).copied().unwrap_or(0.0))
+                    .sum::<f64>()
+                    / n as f64
+            })
+            .collect();
+        let worst_point = worst.0.clone();
+        let along = |t: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(&worst_point)
+                .map(|(c, w)| c + t * (w - c))
+                .collect()
+        };
+        let second_worst = simplex
+            .get(n.saturating_sub(1))
+            .map_or(worst_value, |v| v.1);
+
+        let reflected = along(-1.0);
+        let reflected_value = eval(&reflected);
+        let replacement = if reflected_value < best_value {
+            let expanded = along(-2.0);
+            let expanded_value = eval(&expanded);
+            if expanded_value < reflected_value {
+                Some((expanded, expanded_value))
+            } else {
+                Some((reflected, reflected_value))
+            }
+        } else if reflected_value < second_worst {
+            Some((reflected, reflected_value))
+        } else {
+            let (contracted, contracted_value) = if reflected_value < worst_value {
+                let outside = along(-0.5);
+                let value = eval(&outside);
+                (outside, value)
+            } else {
+                let inside = along(0.5);
+                let value = eval(&inside);
+                (inside, value)
+            };
+            if contracted_value < worst_value.min(reflected_value) {
+                Some((contracted, contracted_value))
+            } else {
+                None
+            }
+        };
+
+        match replacement {
+            Some(vertex) => {
+                if let Some(last) = simplex.last_mut() {
+                    *last = vertex;
+                }
+            }
+            None => {
+                // Shrink every vertex towards the best one.
+                let best_point = best.0.clone();
+                for (x, value) in simplex.iter_mut().skip(1) {
+                    for (xi, bi) in x.iter_mut().zip(&best_point) {
+                        *xi = bi + 0.5 * (*xi - bi);
+                    }
+                    *value = eval(x);
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (point, value) = simplex
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| (start.to_vec(), f64::INFINITY));
+    Minimum {
+        point,
+        value,
+        iterations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nelder_mead_minimises_rosenbrock() {
+        let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let minimum = nelder_mead(rosenbrock, &[-1.2, 1.0], &[0.5, 0.5], 1e-14, 5_000);
+        assert!((minimum.point[0] - 1.0).abs() < 1e-4, "{minimum:?}");
+        assert!((minimum.point[1] - 1.0).abs() < 1e-4, "{minimum:?}");
+        assert!(minimum.value < 1e-8);
+    }
+
+    #[test]
+    fn test_nelder_mead_avoids_infeasible_region() {
+        let constrained = |x: &[f64]| {
+            if x[0] < 0.5 {
+                f64::NAN
+            } else {
+                (x[0] - 0.2).powi(2)
+            }
+        };
+        let minimum = nelder_mead(constrained, &[2.0], &[0.5], 1e-12, 500);
+        assert!(minimum.point[0] >= 0.5);
+        assert!((minimum.point[0] - 0.5).abs() < 1e-3, "{minimum:?}");
+    }
+}
@@ -0,0 +1,336 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! SABR implied volatility.
+//!
+//! The SABR model of Hagan, Kumar, Lesniewski & Woodward (2002) describes the
+//! forward `F` and its volatility `α` as
+//!
+//! ```text
+//! dF = α F^β dW₁
+//! dα = ν α dW₂,      d⟨W₁, W₂⟩ = ρ dt
+//! ```
+//!
+//! and admits the well-known asymptotic expansion of the Black (lognormal)
+//! implied volatility used here. `β` fixes the backbone (0 normal, 1
+//! lognormal), `ρ` the skew and `ν` the curvature of the smile.
+
+use crate::curves::{Curve, Point2D};
+use crate::error::VolatilityError;
+use crate::volatility::{VolatilityModel, VolatilitySmile};
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::collections::BTreeSet;
+use tracing::debug;
+
+/// Below this `|z|` the ratio `z / x(z)` is replaced by its Taylor expansion.
+const SMALL_Z: f64 = 1e-7;
+
+/// Parameters of the SABR model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SabrParams {
+    /// Initial volatility level `α`.
+    pub alpha: Positive,
+    /// Backbone exponent `β` in `[0, 1]`.
+    pub beta: Decimal,
+    /// Correlation `ρ` between the forward and its volatility, in `(-1, 1)`.
+    pub rho: Decimal,
+    /// Volatility of volatility `ν`.
+    pub nu: Positive,
+}
+
+impl SabrParams {
+    /// Creates a validated parameter set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidParameter` when `alpha` is zero,
+    /// `beta` lies outside `[0, 1]` or `rho` outside `(-1, 1)`.
+    pub fn new(
+        alpha: Positive,
+        beta: Decimal,
+        rho: Decimal,
+        nu: Positive,
+    ) -> Result<Self, VolatilityError> {
+        let params = Self {
+            alpha,
+            beta,
+            rho,
+            nu,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn validate(&self) -> Result<(), VolatilityError> {
+        if self.alpha == Positive::ZERO {
+            return Err(VolatilityError::InvalidParameter {
+                parameter: "alpha",
+                reason: "must be strictly positive".to_string(),
+            });
+        }
+        if self.beta < Decimal::ZERO || self.beta > Decimal::ONE {
+            return Err(VolatilityError::InvalidParameter {
+                parameter: "beta",
+                reason: format!("{} must lie in [0, 1]", self.beta),
+            });
+        }
+        if self.rho <= Decimal::NEGATIVE_ONE || self.rho >= Decimal::ONE {
+            return Err(VolatilityError::InvalidParameter {
+                parameter: "rho",
+                reason: format!("{} must lie in (-1, 1)", self.rho),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Computes the Black implied volatility of the SABR model with Hagan's
+/// expansion.
+///
+/// # Arguments
+///
+/// * `forward` - Forward price of the underlying for the option's expiry.
+/// * `strike` - Strike of the option.
+/// * `time_to_expiry` - Time to expiry in years.
+/// * `params` - SABR parameters.
+///
+/// # Errors
+///
+/// Returns `VolatilityError::InvalidParameter` for invalid `params`,
+/// `VolatilityError::InvalidPrice` when the forward or strike is zero, and
+/// `VolatilityError::NumericalFailure` when the expansion produces a
+/// non-positive or non-finite volatility (which can happen far in the wings
+/// for long expiries).
+pub fn sabr_implied_volatility(
+    forward: Positive,
+    strike: Positive,
+    time_to_expiry: Positive,
+    params: &SabrParams,
+) -> Result<Positive, VolatilityError> {
+    params.validate()?;
+    for price in [forward, strike] {
+        if price == Positive::ZERO {
+            return Err(VolatilityError::InvalidPrice {
+                price,
+                reason: "SABR requires a strictly positive forward and strike".to_string(),
+            });
+        }
+    }
+    let f = forward.to_f64();
+    let k = strike.to_f64();
+    let t = time_to_expiry.to_f64();
+    let alpha = params.alpha.to_f64();
+    let beta = params.beta.to_f64().unwrap_or(1.0);
+    let rho = params.rho.to_f64().unwrap_or(0.0);
+    let nu = params.nu.to_f64();
+
+    let one_minus_beta = 1.0 - beta;
+    let log_moneyness = (f / k).ln();
+    let fk_pow = (f * k).powf(one_minus_beta / 2.0);
+    let lm2 = log_moneyness * log_moneyness;
+    let omb2 = one_minus_beta * one_minus_beta;
+    let denominator = fk_pow * (1.0 + omb2 / 24.0 * lm2 + omb2 * omb2 / 1920.0 * lm2 * lm2);
+
+    let z = nu / alpha * fk_pow * log_moneyness;
+    let z_over_x = if z.abs() < SMALL_Z {
+        1.0 - rho * z / 2.0
+    } else {
+        let x = (((1.0 - 2.0 * rho * z + z * z).sqrt() + z - rho) / (1.0 - rho)).ln();
+        z / x
+    };
+    let correction = 1.0
+        + (omb2 * alpha * alpha / (24.0 * fk_pow * fk_pow)
+            + rho * beta * nu * alpha / (4.0 * fk_pow)
+            + (2.0 - 3.0 * rho * rho) * nu * nu / 24.0)
+            * t;
+
+    let vol = alpha / denominator * z_over_x * correction;
+    if !vol.is_finite() || vol <= 0.0 {
+        return Err(VolatilityError::NumericalFailure {
+            reason: format!("SABR expansion gave volatility {vol} at strike {strike}"),
+        });
+    }
+    Ok(Positive::new(vol)?)
+}
+
+/// Implied volatility smile of a SABR parameter set for one expiry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SabrSmile {
+    /// Forward price for the expiry.
+    pub forward: Positive,
+    /// Time to expiry in years.
+    pub time_to_expiry: Positive,
+    /// SABR model parameters.
+    pub params: SabrParams,
+    /// Strikes at which [`VolatilitySmile::smile`] samples the model.
+    pub strikes: Vec<Positive>,
+}
+
+impl SabrSmile {
+    /// Creates a smile for the given forward, expiry and strikes.
+    #[must_use]
+    pub fn new(
+        forward: Positive,
+        time_to_expiry: Positive,
+        params: SabrParams,
+        strikes: Vec<Positive>,
+    ) -> Self {
+        Self {
+            forward,
+            time_to_expiry,
+            params,
+            strikes,
+        }
+    }
+}
+
+impl VolatilityModel for SabrSmile {
+    fn volatility_at(&self, strike: Positive) -> Result<Positive, VolatilityError> {
+        sabr_implied_volatility(self.forward, strike, self.time_to_expiry, &self.params)
+    }
+}
+
+impl VolatilitySmile for SabrSmile {
+    /// Samples the SABR volatility at every strike of the smile, skipping
+    /// strikes where the expansion breaks down.
+    fn smile(&self) -> Curve {
+        let points: BTreeSet<Point2D> = self
+            .strikes
+            .iter()
+            .filter_map(|&strike| match self.volatility_at(strike) {
+                Ok(vol) => Some(Point2D::new(strike.to_dec(), vol.to_dec())),
+                Err(e) => {
+                    debug!("SABR smile skipped strike {}: {}", strike, e);
+                    None
+                }
+            })
+            .collect();
+        Curve::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn params(rho: Decimal, nu: f64) -> SabrParams {
+        SabrParams::new(pos_or_panic!(2.0), dec!(0.5), rho, pos_or_panic!(nu)).unwrap()
+    }
+
+    #[test]
+    fn test_sabr_lognormal_without_vol_of_vol_is_flat() {
+        let flat = SabrParams::new(
+            pos_or_panic!(0.25),
+            Decimal::ONE,
+            Decimal::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        for strike in [50.0, 100.0, 200.0] {
+            let vol = sabr_implied_volatility(
+                Positive::HUNDRED,
+                pos_or_panic!(strike),
+                Positive::TWO,
+                &flat,
+            )
+            .unwrap();
+            assert!((vol.to_f64() - 0.25).abs() < 1e-12, "{strike}: {vol}");
+        }
+    }
+
+    #[test]
+    fn test_sabr_atm_matches_closed_form_and_is_continuous() {
+        let p = params(dec!(-0.3), 0.4);
+        let (f, t, alpha, beta, rho, nu) = (100.0f64, 1.0, 2.0, 0.5, -0.3, 0.4);
+        let f_pow = f.powf(1.0 - beta);
+        let expected = alpha / f_pow
+            * (1.0
+                + ((1.0 - beta).powi(2) * alpha * alpha / (24.0 * f_pow * f_pow)
+                    + rho * beta * nu * alpha / (4.0 * f_pow)
+                    + (2.0 - 3.0 * rho * rho) * nu * nu / 24.0)
+                    * t);
+        let atm = sabr_implied_volatility(Positive::HUNDRED, Positive::HUNDRED, Positive::ONE, &p)
+            .unwrap();
+        assert!((atm.to_f64() - expected).abs() < 1e-12);
+
+        let near = sabr_implied_volatility(
+            Positive::HUNDRED,
+            pos_or_panic!(100.0001),
+            Positive::ONE,
+            &p,
+        )
+        .unwrap();
+        assert!((near.to_f64() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sabr_smile_shape_follows_rho() {
+        let strikes: Vec<Positive> = [80.0, 90.0, 100.0, 110.0, 120.0]
+            .iter()
+            .map(|&k| pos_or_panic!(k))
+            .collect();
+        let skewed = SabrSmile::new(
+            Positive::HUNDRED,
+            Positive::ONE,
+            params(dec!(-0.5), 0.4),
+            strikes.clone(),
+        )
+        .smile();
+        let vols: Vec<Decimal> = skewed.points.iter().map(|p| p.y).collect();
+        assert_eq!(vols.len(), 5);
+        assert!(vols.windows(2).all(|w| w[0] > w[1]), "{vols:?}");
+
+        let symmetric = SabrSmile::new(
+            Positive::HUNDRED,
+            Positive::ONE,
+            SabrParams::new(
+                pos_or_panic!(0.2),
+                Decimal::ONE,
+                Decimal::ZERO,
+                Positive::ONE,
+            )
+            .unwrap(),
+            strikes,
+        );
+        let atm = symmetric.volatility_at(Positive::HUNDRED).unwrap();
+        assert!(symmetric.volatility_at(pos_or_panic!(80.0)).unwrap() > atm);
+        assert!(symmetric.volatility_at(pos_or_panic!(120.0)).unwrap() > atm);
+    }
+
+    #[test]
+    fn test_sabr_rejects_invalid_parameters() {
+        assert!(matches!(
+            SabrParams::new(Positive::ONE, dec!(1.5), Decimal::ZERO, Positive::ONE),
+            Err(VolatilityError::InvalidParameter {
+                parameter: "beta",
+                ..
+            })
+        ));
+        assert!(matches!(
+            SabrParams::new(Positive::ONE, Decimal::ONE, Decimal::ONE, Positive::ONE),
+            Err(VolatilityError::InvalidParameter {
+                parameter: "rho",
+                ..
+            })
+        ));
+        assert!(
+            SabrParams::new(Positive::ZERO, Decimal::ONE, Decimal::ZERO, Positive::ONE).is_err()
+        );
+        assert!(
+            sabr_implied_volatility(
+                Positive::HUNDRED,
+                Positive::ZERO,
+                Positive::ONE,
+                &params(Decimal::ZERO, 0.3)
+            )
+            .is_err()
+        );
+    }
+}
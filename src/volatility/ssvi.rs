@@ -0,0 +1,585 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! SSVI (surface SVI) calibration across expiries.
+//!
+//! Gatheral & Jacquier (2014) parameterise the whole surface of total
+//! implied variance with the at-the-money total variance curve `θ(t)` and a
+//! power-law curvature function `φ(θ) = η / (θ^γ (1 + θ)^{1-γ})`:
+//!
+//! ```text
+//! w(k, θ) = θ/2 (1 + ρ φ k + √((φ k + ρ)² + 1 - ρ²))
+//! ```
+//!
+//! With `θ` non-decreasing in `t` and `γ ∈ (0, 1]` the surface is free of
+//! calendar arbitrage; butterfly arbitrage is excluded when
+//! `θ φ (1 + |ρ|) < 4` and `θ φ² (1 + |ρ|) ≤ 4`, which the calibration
+//! enforces at every quoted expiry.
+
+use crate::curves::{Curve, Point2D};
+use crate::error::VolatilityError;
+use crate::model::decimal::finite_decimal;
+use crate::series::OptionSeries;
+use crate::volatility::optimize::nelder_mead;
+use crate::volatility::svi::{
+    ArbitrageReport, CalibrationReport, SliceQuotes, arbitrage_grid, durrleman,
+};
+use crate::volatility::{VolatilityModel, VolatilitySmile};
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::collections::BTreeSet;
+use tracing::{debug, instrument};
+
+/// Global SSVI parameters shared by every expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SsviParams {
+    /// Spot-volatility correlation, in `(-1, 1)`.
+    pub rho: Decimal,
+    /// Level `η` of the curvature function.
+    pub eta: Positive,
+    /// Decay `γ` of the curvature function, in `(0, 1]`.
+    pub gamma: Decimal,
+}
+
+impl SsviParams {
+    /// Creates a validated parameter set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidParameter` when `rho` lies outside
+    /// `(-1, 1)`, `eta` is zero or `gamma` lies outside `(0, 1]`.
+    pub fn new(rho: Decimal, eta: Positive, gamma: Decimal) -> Result<Self, VolatilityError> {
+        if rho <= Decimal::NEGATIVE_ONE || rho >= Decimal::ONE {
+            return Err(VolatilityError::InvalidParameter {
+                parameter: "rho",
+                reason: format!("{rho} must lie in (-1, 1)"),
+            });
+        }
+        if eta == Positive::ZERO {
+            return Err(VolatilityError::InvalidParameter {
+                parameter: "eta",
+                reason: "must be strictly positive".to_string(),
+            });
+        }
+        if gamma <= Decimal::ZERO || gamma > Decimal::ONE {
+            return Err(VolatilityError::InvalidParameter {
+                parameter: "gamma",
+                reason: format!("{gamma} must lie in (0, 1]"),
+            });
+        }
+        Ok(Self { rho, eta, gamma })
+    }
+}
+
+/// SSVI parameters in `f64` with analytic `k`-derivatives.
+#[derive(Debug, Clone, Copy)]
+struct RawSsvi {
+    rho: f64,
+    eta: f64,
+    gamma: f64,
+}
+
+impl From<&SsviParams> for RawSsvi {
+    fn from(params: &SsviParams) -> Self {
+        Self {
+            rho: params.rho.to_f64().unwrap_or(0.0),
+            eta: params.eta.to_f64(),
+            gamma: params.gamma.to_f64().unwrap_or(0.5),
+        }
+    }
+}
+
+impl RawSsvi {
+    fn phi(&self, theta: f64) -> f64 {
+        self.eta / (theta.powf(self.gamma) * (1.0 + theta).powf(1.0 - self.gamma))
+    }
+
+    fn root(&self, phi_k: f64) -> f64 {
+        ((phi_k + self.rho).powi(2) + 1.0 - self.rho * self.rho).sqrt()
+    }
+
+    fn w(&self, k: f64, theta: f64) -> f64 {
+        let phi_k = self.phi(theta) * k;
+        theta / 2.0 * (1.0 + self.rho * phi_k + self.root(phi_k))
+    }
+
+    fn dw(&self, k: f64, theta: f64) -> f64 {
+        let phi = self.phi(theta);
+        let phi_k = phi * k;
+        theta * phi / 2.0 * (self.rho + (phi_k + self.rho) / self.root(phi_k))
+    }
+
+    fn d2w(&self, k: f64, theta: f64) -> f64 {
+        let phi = self.phi(theta);
+        theta * phi * phi / 2.0 * (1.0 - self.rho * self.rho) / self.root(phi * k).powi(3)
+    }
+
+    /// Gatheral–Jacquier sufficient conditions for no butterfly arbitrage.
+    fn butterfly_free(&self, theta: f64) -> bool {
+        let phi = self.phi(theta);
+        let wing = 1.0 + self.rho.abs();
+        theta * phi * wing < 4.0 && theta * phi * phi * wing <= 4.0
+    }
+
+    fn to_params(self) -> Result<SsviParams, VolatilityError> {
+        let decimal = |value: f64, context: &'static str| {
+            finite_decimal(value).ok_or_else(|| VolatilityError::non_finite(context, value))
+        };
+        SsviParams::new(
+            decimal(self.rho, "volatility::ssvi::rho")?,
+            Positive::new(self.eta)?,
+            decimal(self.gamma, "volatility::ssvi::gamma")?,
+        )
+    }
+}
+
+/// One quoted expiry of a calibrated SSVI surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SsviNode {
+    /// Time to expiry in years.
+    pub time_to_expiry: Positive,
+    /// Forward price of the expiry.
+    pub forward: Positive,
+    /// At-the-money total implied variance `θ`.
+    pub atm_variance: Positive,
+    /// Lowest quoted log-forward moneyness.
+    pub min_log_moneyness: Decimal,
+    /// Highest quoted log-forward moneyness.
+    pub max_log_moneyness: Decimal,
+}
+
+/// A calibrated, arbitrage-free SSVI implied volatility surface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsviSurface {
+    /// Fitted global parameters.
+    pub params: SsviParams,
+    /// Spot price of the underlying, anchoring forwards at `t = 0`.
+    pub spot: Positive,
+    /// Quoted expiries in increasing maturity.
+    pub nodes: Vec<SsviNode>,
+    /// Fit quality against every calibration quote.
+    pub report: CalibrationReport,
+}
+
+impl SsviSurface {
+    /// Calibrates SSVI to the implied volatilities of every chain in a series.
+    ///
+    /// The at-the-money total variance of each expiry is read off the
+    /// quotes (linear interpolation in log-moneyness) and made
+    /// non-decreasing in maturity; `(ρ, η, γ)` are then fitted to all
+    /// quotes in total variance subject to the no-butterfly conditions.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationFailed` when the series has no
+    /// chain with at least two quotes or no admissible fit is found,
+    /// `VolatilityError::InvalidTime` for an expiry that cannot be
+    /// converted to years, and `VolatilityError::PositiveError` for a
+    /// non-positive forward.
+    #[instrument(skip(series), fields(symbol = %series.symbol, chains = series.chains.len()))]
+    pub fn calibrate(series: &OptionSeries) -> Result<Self, VolatilityError> {
+        let mut slices = Vec::with_capacity(series.chains.len());
+        for (expiration, chain) in &series.chains {
+            let time_to_expiry =
+                expiration
+                    .get_years()
+                    .map_err(|e| VolatilityError::InvalidTime {
+                        time: Positive::ZERO,
+                        reason: e.to_string(),
+                    })?;
+            if time_to_expiry == Positive::ZERO {
+                continue;
+            }
+            let slice = SliceQuotes::from_chain_at(chain, time_to_expiry)?;
+            if slice.quotes.len() >= 2 {
+                slices.push(slice);
+            }
+        }
+        if slices.is_empty() {
+            return Err(VolatilityError::CalibrationFailed {
+                model: "SSVI",
+                reason: "no expiry with at least two implied volatility quotes".to_string(),
+            });
+        }
+        slices.sort_by_key(|slice| slice.time_to_expiry);
+
+        let mut data: Vec<(f64, Vec<(f64, f64)>)> = Vec::with_capacity(slices.len());
+        let mut floor = 0.0f64;
+        for slice in &slices {
+            let points = slice.total_variances();
+            floor = atm_total_variance(&points).max(floor);
+            data.push((floor, points));
+        }
+        if floor <= 0.0 {
+            return Err(VolatilityError::CalibrationFailed {
+                model: "SSVI",
+                reason: "at-the-money total variance is zero".to_string(),
+            });
+        }
+
+        let decode = |x: &[f64]| -> Option<RawSsvi> {
+            let (r, e, g) = (x.first()?, x.get(1)?, x.get(2)?);
+            Some(RawSsvi {
+                rho: MAX_RHO * r.tanh(),
+                eta: e.exp(),
+                gamma: 1.0 / (1.0 + (-g).exp()),
+            })
+        };
+        let objective = |x: &[f64]| {
+            let Some(raw) = decode(x) else {
+                return f64::INFINITY;
+            };
+            let mut sse = 0.0;
+            for (theta, points) in &data {
+                if *theta > 0.0 && !raw.butterfly_free(*theta) {
+                    return f64::INFINITY;
+                }
+                sse += points
+                    .iter()
+                    .map(|&(k, w)| (raw.w(k, theta.max(f64::MIN_POSITIVE)) - w).powi(2))
+                    .sum::<f64>();
+            }
+            sse
+        };
+        let minimum = nelder_mead(objective, &[-0.3, 0.0, 0.0], &[0.5, 0.5, 0.5], 1e-20, 4_000);
+        let raw = decode(&minimum.point)
+            .filter(|_| minimum.value.is_finite())
+            .ok_or_else(|| VolatilityError::CalibrationFailed {
+                model: "SSVI",
+                reason: "no admissible fit found".to_string(),
+            })?;
+        debug!(
+            sse = minimum.value,
+            iterations = minimum.iterations,
+            "SSVI fit"
+        );
+
+        let mut errors = Vec::new();
+        let mut nodes = Vec::with_capacity(slices.len());
+        for (slice, (theta, points)) in slices.iter().zip(&data) {
+            let t = slice.time_to_expiry.to_f64();
+            errors.extend(
+                points
+                    .iter()
+                    .zip(&slice.quotes)
+                    .map(|((k, _), (_, vol))| (raw.w(*k, *theta) / t).sqrt() - vol.to_f64()),
+            );
+            let (low, high) = points
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (k, _)| {
+                    (lo.min(*k), hi.max(*k))
+                });
+            nodes.push(SsviNode {
+                time_to_expiry: slice.time_to_expiry,
+                forward: slice.forward,
+                atm_variance: Positive::new(*theta)?,
+                min_log_moneyness: finite_decimal(low).unwrap_or(Decimal::ZERO),
+                max_log_moneyness: finite_decimal(high).unwrap_or(Decimal::ZERO),
+            });
+        }
+        Ok(Self {
+            params: raw.to_params()?,
+            spot: series.underlying_price,
+            nodes,
+            report: CalibrationReport::from_errors(errors.into_iter(), minimum.iterations)?,
+        })
+    }
+
+    /// Forward price and at-the-money total variance at `t` years.
+    ///
+    /// Both are interpolated linearly in `t` between nodes (`ln F` for the
+    /// forward), anchored at `(0, ln S)` and `(0, 0)`; beyond the last node
+    /// the last carry rate and variance rate are extended.
+    fn interpolate(&self, t: f64) -> (f64, f64) {
+        let spot = self.spot.to_f64().ln();
+        let mut previous = (0.0, spot, 0.0);
+        for node in &self.nodes {
+            let current = (
+                node.time_to_expiry.to_f64(),
+                node.forward.to_f64().ln(),
+                node.atm_variance.to_f64(),
+            );
+            if current.0 <= previous.0 {
+                continue;
+            }
+            if t <= current.0 {
+                let weight = (t - previous.0) / (current.0 - previous.0);
+                return (
+                    (previous.1 + weight * (current.1 - previous.1)).exp(),
+                    previous.2 + weight * (current.2 - previous.2),
+                );
+            }
+            previous = current;
+        }
+        if previous.0 > 0.0 {
+            let scale = t / previous.0;
+            (
+                (spot + scale * (previous.1 - spot)).exp(),
+                scale * previous.2,
+            )
+        } else {
+            (self.spot.to_f64(), 0.0)
+        }
+    }
+
+    /// Implied volatility of the surface at any strike and expiry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidPrice` for a zero strike,
+    /// `VolatilityError::InvalidTime` for a zero expiry and
+    /// `VolatilityError::NumericalFailure` if the surface degenerates.
+    pub fn implied_volatility(
+        &self,
+        strike: Positive,
+        time_to_expiry: Positive,
+    ) -> Result<Positive, VolatilityError> {
+        self.slice(time_to_expiry, Vec::new())?
+            .volatility_at(strike)
+    }
+
+    /// Extracts the smile at `time_to_expiry`, sampled at `strikes` by
+    /// [`VolatilitySmile::smile`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidTime` for a zero expiry.
+    pub fn slice(
+        &self,
+        time_to_expiry: Positive,
+        strikes: Vec<Positive>,
+    ) -> Result<SsviSlice, VolatilityError> {
+        if time_to_expiry == Positive::ZERO {
+            return Err(VolatilityError::InvalidTime {
+                time: time_to_expiry,
+                reason: "SSVI needs a strictly positive expiry".to_string(),
+            });
+        }
+        let (forward, theta) = self.interpolate(time_to_expiry.to_f64());
+        Ok(SsviSlice {
+            params: self.params,
+            forward: Positive::new(forward)?,
+            time_to_expiry,
+            atm_variance: Positive::new(theta)?,
+            strikes,
+        })
+    }
+
+    /// Checks the fitted surface for butterfly arbitrage at every quoted
+    /// expiry and calendar arbitrage between consecutive expiries, over the
+    /// quoted log-moneyness ranges.
+    #[must_use]
+    pub fn arbitrage_report(&self) -> ArbitrageReport {
+        let raw = RawSsvi::from(&self.params);
+        let range = |node: &SsviNode| {
+            (
+                node.min_log_moneyness.to_f64().unwrap_or(0.0),
+                node.max_log_moneyness.to_f64().unwrap_or(0.0),
+            )
+        };
+        let mut report = ArbitrageReport::default();
+        for node in &self.nodes {
+            let theta = node.atm_variance.to_f64();
+            let (low, high) = range(node);
+            for k in arbitrage_grid(low, high) {
+                let g = durrleman(k, raw.w(k, theta), raw.dw(k, theta), raw.d2w(k, theta));
+                report.push_butterfly(node.time_to_expiry, k, g);
+            }
+        }
+        for pair in self.nodes.windows(2) {
+            let [earlier, later] = pair else { continue };
+            let ((lo_a, hi_a), (lo_b, hi_b)) = (range(earlier), range(later));
+            let (low, high) = (lo_a.max(lo_b), hi_a.min(hi_b));
+            if low > high {
+                continue;
+            }
+            let (first, second) = (earlier.atm_variance.to_f64(), later.atm_variance.to_f64());
+            for k in arbitrage_grid(low, high) {
+                report.push_calendar(
+                    k,
+                    earlier.time_to_expiry,
+                    later.time_to_expiry,
+                    raw.w(k, first) - raw.w(k, second),
+                );
+            }
+        }
+        report
+    }
+}
+
+/// Largest admissible `|ρ|` during calibration.
+const MAX_RHO: f64 = 0.999;
+
+/// At-the-money total variance, interpolated linearly in log-moneyness
+/// between the quotes bracketing `k = 0` (nearest quote otherwise).
+fn atm_total_variance(points: &[(f64, f64)]) -> f64 {
+    let below = points
+        .iter()
+        .filter(|p| p.0 <= 0.0)
+        .max_by(|a, b| a.0.total_cmp(&b.0));
+    let above = points
+        .iter()
+        .filter(|p| p.0 >= 0.0)
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+    match (below, above) {
+        (Some(lo), Some(hi)) if hi.0 > lo.0 => lo.1 + (hi.1 - lo.1) * (-lo.0) / (hi.0 - lo.0),
+        (Some(p), _) | (None, Some(p)) => p.1,
+        (None, None) => 0.0,
+    }
+}
+
+/// The SSVI smile of one expiry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsviSlice {
+    /// Global SSVI parameters.
+    pub params: SsviParams,
+    /// Forward price for the expiry.
+    pub forward: Positive,
+    /// Time to expiry in years.
+    pub time_to_expiry: Positive,
+    /// At-the-money total implied variance `θ`.
+    pub atm_variance: Positive,
+    /// Strikes at which [`VolatilitySmile::smile`] samples the model.
+    pub strikes: Vec<Positive>,
+}
+
+impl VolatilityModel for SsviSlice {
+    fn volatility_at(&self, strike: Positive) -> Result<Positive, VolatilityError> {
+        if strike == Positive::ZERO {
+            return Err(VolatilityError::InvalidPrice {
+                price: strike,
+                reason: "SSVI requires a strictly positive strike".to_string(),
+            });
+        }
+        let k = (strike.to_f64() / self.forward.to_f64()).ln();
+        let w = RawSsvi::from(&self.params).w(k, self.atm_variance.to_f64());
+        if !w.is_finite() || w <= 0.0 {
+            return Err(VolatilityError::NumericalFailure {
+                reason: format!("SSVI total variance {w} at strike {strike}"),
+            });
+        }
+        Ok(Positive::new((w / self.time_to_expiry.to_f64()).sqrt())?)
+    }
+}
+
+impl VolatilitySmile for SsviSlice {
+    /// Samples the SSVI volatility at every strike of the slice.
+    fn smile(&self) -> Curve {
+        let points: BTreeSet<Point2D> = self
+            .strikes
+            .iter()
+            .filter_map(|&strike| {
+                self.volatility_at(strike)
+                    .ok()
+                    .map(|vol| Point2D::new(strike.to_dec(), vol.to_dec()))
+            })
+            .collect();
+        Curve::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::chains::chain::OptionChain;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    const REFERENCE: RawSsvi = RawSsvi {
+        rho: -0.4,
+        eta: 1.2,
+        gamma: 0.4,
+    };
+
+    /// A series whose quotes are generated by `REFERENCE` with
+    /// `θ(t) = 0.04 t` and no carry.
+    fn synthetic_series() -> OptionSeries {
+        let mut series = OptionSeries::new("TEST".to_string(), Positive::HUNDRED);
+        for days in [30.0, 91.0, 182.0, 365.0] {
+            let t = days / 365.0;
+            let theta = 0.04 * t;
+            let mut chain =
+                OptionChain::new("TEST", Positive::HUNDRED, format!("{days}"), None, None);
+            for i in 0..13 {
+                let strike = 70.0 + 5.0 * i as f64;
+                let vol = (REFERENCE.w((strike / 100.0f64).ln(), theta) / t).sqrt();
+                chain.add_option(
+                    pos_or_panic!(strike),
+                    None,
+                    None,
+                    None,
+                    None,
+                    pos_or_panic!(vol),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                );
+            }
+            series
+                .chains
+                .insert(ExpirationDate::Days(pos_or_panic!(days)), chain);
+        }
+        series
+    }
+
+    #[test]
+    fn test_ssvi_calibration_recovers_surface() {
+        let surface = SsviSurface::calibrate(&synthetic_series()).unwrap();
+        assert_eq!(surface.nodes.len(), 4);
+        assert_eq!(surface.report.quotes, 52);
+        assert!(
+            surface.report.max_error < dec!(0.001),
+            "{:?}",
+            surface.report
+        );
+        let fitted = RawSsvi::from(&surface.params);
+        assert!((fitted.rho - REFERENCE.rho).abs() < 0.02, "{fitted:?}");
+        assert!(surface.arbitrage_report().is_arbitrage_free());
+
+        // Off-grid strike and maturity between the 91 and 182 day nodes.
+        let t = 120.0 / 365.0;
+        let vol = surface
+            .implied_volatility(pos_or_panic!(97.5), pos_or_panic!(t))
+            .unwrap();
+        let expected = (REFERENCE.w((0.975f64).ln(), 0.04 * t) / t).sqrt();
+        assert!(
+            (vol.to_f64() - expected).abs() < 2e-3,
+            "{vol} vs {expected}"
+        );
+
+        let smile = surface
+            .slice(Positive::ONE, vec![pos_or_panic!(90.0), Positive::HUNDRED])
+            .unwrap()
+            .smile();
+        assert_eq!(smile.points.len(), 2);
+    }
+
+    #[test]
+    fn test_ssvi_detects_calendar_arbitrage() {
+        let mut surface = SsviSurface::calibrate(&synthetic_series()).unwrap();
+        // Inverting the term structure of θ makes total variance fall with maturity.
+        let first = surface.nodes[0].atm_variance;
+        surface.nodes[1].atm_variance = first / Positive::TWO;
+        let report = surface.arbitrage_report();
+        assert!(!report.calendar.is_empty());
+    }
+
+    #[test]
+    fn test_ssvi_rejects_empty_series_and_invalid_params() {
+        let series = OptionSeries::new("TEST".to_string(), Positive::HUNDRED);
+        assert!(matches!(
+            SsviSurface::calibrate(&series),
+            Err(VolatilityError::CalibrationFailed { model: "SSVI", .. })
+        ));
+        assert!(SsviParams::new(dec!(-0.3), pos_or_panic!(1.0), Decimal::ZERO).is_err());
+        assert!(SsviParams::new(Decimal::ONE, pos_or_panic!(1.0), dec!(0.5)).is_err());
+    }
+}
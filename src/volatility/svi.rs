@@ -0,0 +1,784 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! SVI (stochastic volatility inspired) smile calibration.
+//!
+//! Gatheral's raw SVI parameterisation gives the total implied variance
+//! `w = σ²_imp · T` of one expiry as a function of the log-forward moneyness
+//! `k = ln(K / F)`:
+//!
+//! ```text
+//! w(k) = a + b (ρ (k - m) + √((k - m)² + σ²))
+//! ```
+//!
+//! Calibration follows the quasi-explicit approach of Zeliade (2009): for a
+//! fixed `(m, σ)` the fit is linear in `(a, bρ, b)` and solved in closed
+//! form, so the simplex search only runs over two parameters. The fitted
+//! slice is checked for butterfly arbitrage with Durrleman's condition
+//!
+//! ```text
+//! g(k) = (1 - k w' / (2w))² - (w'² / 4)(1/w + 1/4) + w'' / 2 ≥ 0,
+//! ```
+//!
+//! which is equivalent to a non-negative risk-neutral density, and sets of
+//! slices are checked for calendar arbitrage (total variance must not
+//! decrease with maturity at fixed `k`).
+
+use crate::chains::chain::OptionChain;
+use crate::curves::{Curve, Point2D};
+use crate::error::VolatilityError;
+use crate::model::decimal::finite_decimal;
+use crate::volatility::optimize::nelder_mead;
+use crate::volatility::{VolatilityModel, VolatilitySmile};
+use num_traits::ToPrimitive;
+use positive::Positive;
+use rust_decimal::Decimal;
+use std::collections::BTreeSet;
+use tracing::{debug, instrument};
+
+/// Minimum number of quotes needed to fit the five SVI parameters.
+pub const SVI_MIN_QUOTES: usize = 5;
+
+/// Number of log-moneyness points on which arbitrage conditions are checked.
+pub(crate) const ARBITRAGE_GRID_POINTS: usize = 101;
+
+/// Tolerance below which a negative `g(k)` or a variance decrease is
+/// attributed to rounding rather than arbitrage.
+pub(crate) const ARBITRAGE_TOLERANCE: f64 = 1e-9;
+
+/// Largest admissible `|ρ|` during calibration, keeping the fit away from
+/// the degenerate linear smile.
+const MAX_ABS_RHO: f64 = 0.999;
+
+/// Simplex starting points for `σ`, to avoid local minima of the outer fit.
+const SIGMA_STARTS: [f64; 3] = [0.05, 0.2, 0.5];
+
+/// Raw SVI parameters of one expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SviParams {
+    /// Overall level of total variance.
+    pub a: Decimal,
+    /// Slope of the wings.
+    pub b: Positive,
+    /// Skew, in `(-1, 1)`.
+    pub rho: Decimal,
+    /// Horizontal translation of the smile.
+    pub m: Decimal,
+    /// Curvature at the minimum, strictly positive.
+    pub sigma: Positive,
+}
+
+impl SviParams {
+    /// Creates a validated parameter set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidParameter` when `rho` lies outside
+    /// `(-1, 1)`, `sigma` is zero, or the minimum total variance
+    /// `a + b σ √(1 - ρ²)` is negative.
+    pub fn new(
+        a: Decimal,
+        b: Positive,
+        rho: Decimal,
+        m: Decimal,
+        sigma: Positive,
+    ) -> Result<Self, VolatilityError> {
+        let params = Self {
+            a,
+            b,
+            rho,
+            m,
+            sigma,
+        };
+        if rho <= Decimal::NEGATIVE_ONE || rho >= Decimal::ONE {
+            return Err(VolatilityError::InvalidParameter {
+                parameter: "rho",
+                reason: format!("{rho} must lie in (-1, 1)"),
+            });
+        }
+        if sigma == Positive::ZERO {
+            return Err(VolatilityError::InvalidParameter {
+                parameter: "sigma",
+                reason: "must be strictly positive".to_string(),
+            });
+        }
+        let raw = RawSvi::from(&params);
+        let minimum = raw.a + raw.b * raw.sigma * (1.0 - raw.rho * raw.rho).sqrt();
+        if minimum < 0.0 {
+            return Err(VolatilityError::InvalidParameter {
+                parameter: "a",
+                reason: format!("minimum total variance {minimum} is negative"),
+            });
+        }
+        Ok(params)
+    }
+
+    /// Total implied variance `w(k)` at log-forward moneyness `k`.
+    #[must_use]
+    pub fn total_variance(&self, log_moneyness: Decimal) -> Decimal {
+        let k = log_moneyness.to_f64().unwrap_or(0.0);
+        finite_decimal(RawSvi::from(self).w(k)).unwrap_or(Decimal::ZERO)
+    }
+}
+
+/// SVI parameters in `f64` with analytic derivatives.
+#[derive(Debug, Clone, Copy)]
+struct RawSvi {
+    a: f64,
+    b: f64,
+    rho: f64,
+    m: f64,
+    sigma: f64,
+}
+
+impl From<&SviParams> for RawSvi {
+    fn from(params: &SviParams) -> Self {
+        Self {
+            a: params.a.to_f64().unwrap_or(0.0),
+            b: params.b.to_f64(),
+            rho: params.rho.to_f64().unwrap_or(0.0),
+            m: params.m.to_f64().unwrap_or(0.0),
+            sigma: params.sigma.to_f64(),
+        }
+    }
+}
+
+impl RawSvi {
+    fn w(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        self.a + self.b * (self.rho * x + (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    fn dw(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        self.b * (self.rho + x / (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    fn d2w(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        let r2 = x * x + self.sigma * self.sigma;
+        self.b * self.sigma * self.sigma / (r2 * r2.sqrt())
+    }
+
+    fn to_params(self) -> Result<SviParams, VolatilityError> {
+        let decimal = |value: f64, context: &'static str| {
+            finite_decimal(value).ok_or_else(|| VolatilityError::non_finite(context, value))
+        };
+        Ok(SviParams {
+            a: decimal(self.a, "volatility::svi::a")?,
+            b: Positive::new(self.b)?,
+            rho: decimal(self.rho, "volatility::svi::rho")?,
+            m: decimal(self.m, "volatility::svi::m")?,
+            sigma: Positive::new(self.sigma)?,
+        })
+    }
+}
+
+/// Durrleman's butterfly function `g(k)`; negative values mean a negative
+/// risk-neutral density.
+pub(crate) fn durrleman(k: f64, w: f64, dw: f64, d2w: f64) -> f64 {
+    let term = 1.0 - k * dw / (2.0 * w);
+    term * term - dw * dw / 4.0 * (1.0 / w + 0.25) + d2w / 2.0
+}
+
+/// Goodness of fit of a volatility calibration, in implied volatility units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalibrationReport {
+    /// Root mean squared implied volatility error over the quotes.
+    pub rmse: Decimal,
+    /// Largest absolute implied volatility error over the quotes.
+    pub max_error: Decimal,
+    /// Number of quotes used in the fit.
+    pub quotes: usize,
+    /// Simplex iterations performed by the optimiser.
+    pub iterations: usize,
+}
+
+impl CalibrationReport {
+    /// Builds a report from `(model, market)` implied volatility pairs.
+    pub(crate) fn from_errors(
+        errors: impl Iterator<Item = f64>,
+        iterations: usize,
+    ) -> Result<Self, VolatilityError> {
+        let (mut count, mut sum_sq, mut max_error) = (0usize, 0.0, 0.0f64);
+        for error in errors {
+            count += 1;
+            sum_sq += error * error;
+            max_error = max_error.max(error.abs());
+        }
+        let rmse = if count == 0 {
+            0.0
+        } else {
+            (sum_sq / count as f64).sqrt()
+        };
+        Ok(Self {
+            rmse: finite_decimal(rmse).ok_or_else(|| {
+                VolatilityError::non_finite("volatility::calibration::rmse", rmse)
+            })?,
+            max_error: finite_decimal(max_error).ok_or_else(|| {
+                VolatilityError::non_finite("volatility::calibration::max_error", max_error)
+            })?,
+            quotes: count,
+            iterations,
+        })
+    }
+}
+
+/// A point where a smile implies a negative risk-neutral density.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButterflyViolation {
+    /// Expiry of the offending slice, in years.
+    pub time_to_expiry: Positive,
+    /// Log-forward moneyness `ln(K / F)` of the violation.
+    pub log_moneyness: Decimal,
+    /// Value of Durrleman's `g(k)`, negative at a violation.
+    pub density: Decimal,
+}
+
+/// A point where total implied variance decreases with maturity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarViolation {
+    /// Log-forward moneyness `ln(K / F)` of the violation.
+    pub log_moneyness: Decimal,
+    /// Earlier expiry, in years.
+    pub earlier: Positive,
+    /// Later expiry, in years.
+    pub later: Positive,
+    /// Amount by which the later total variance falls below the earlier one.
+    pub variance_drop: Decimal,
+}
+
+/// Static-arbitrage diagnostics of a fitted smile or surface.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArbitrageReport {
+    /// Butterfly (negative density) violations.
+    pub butterfly: Vec<ButterflyViolation>,
+    /// Calendar (decreasing total variance) violations.
+    pub calendar: Vec<CalendarViolation>,
+}
+
+impl ArbitrageReport {
+    /// Returns `true` when no violation was found.
+    #[must_use]
+    pub fn is_arbitrage_free(&self) -> bool {
+        self.butterfly.is_empty() && self.calendar.is_empty()
+    }
+
+    pub(crate) fn push_butterfly(&mut self, time_to_expiry: Positive, k: f64, g: f64) {
+        if g < -ARBITRAGE_TOLERANCE
+            && let (Some(log_moneyness), Some(density)) = (finite_decimal(k), finite_decimal(g))
+        {
+            self.butterfly.push(ButterflyViolation {
+                time_to_expiry,
+                log_moneyness,
+                density,
+            });
+        }
+    }
+
+    pub(crate) fn push_calendar(&mut self, k: f64, earlier: Positive, later: Positive, drop: f64) {
+        if drop > ARBITRAGE_TOLERANCE
+            && let (Some(log_moneyness), Some(variance_drop)) =
+                (finite_decimal(k), finite_decimal(drop))
+        {
+            self.calendar.push(CalendarViolation {
+                log_moneyness,
+                earlier,
+                later,
+                variance_drop,
+            });
+        }
+    }
+}
+
+/// Evenly spaced grid of `ARBITRAGE_GRID_POINTS` points on `[low, high]`.
+pub(crate) fn arbitrage_grid(low: f64, high: f64) -> impl Iterator<Item = f64> {
+    let step = (high - low) / (ARBITRAGE_GRID_POINTS - 1) as f64;
+    (0..ARBITRAGE_GRID_POINTS).map(move |i| low + step * i as f64)
+}
+
+/// Market quotes of one expiry prepared for calibration.
+#[derive(Debug, Clone)]
+pub(crate) struct SliceQuotes {
+    pub(crate) forward: Positive,
+    pub(crate) time_to_expiry: Positive,
+    /// `(strike, implied volatility)` with a non-zero volatility.
+    pub(crate) quotes: Vec<(Positive, Positive)>,
+}
+
+impl SliceQuotes {
+    /// Extracts the forward, expiry and implied volatilities of a chain.
+    pub(crate) fn from_chain(chain: &OptionChain) -> Result<Self, VolatilityError> {
+        let time_to_expiry = chain
+            .get_expiration()
+            .ok_or_else(|| VolatilityError::InvalidTime {
+                time: Positive::ZERO,
+                reason: format!(
+                    "chain expiration '{}' cannot be parsed",
+                    chain.get_expiration_date()
+                ),
+            })?
+            .get_years()
+            .map_err(|e| VolatilityError::InvalidTime {
+                time: Positive::ZERO,
+                reason: e.to_string(),
+            })?;
+        Self::from_chain_at(chain, time_to_expiry)
+    }
+
+    /// Like [`SliceQuotes::from_chain`] with an externally supplied expiry.
+    pub(crate) fn from_chain_at(
+        chain: &OptionChain,
+        time_to_expiry: Positive,
+    ) -> Result<Self, VolatilityError> {
+        let rate = chain.risk_free_rate.and_then(|r| r.to_f64()).unwrap_or(0.0);
+        let dividend = chain.dividend_yield.map_or(0.0, |q| q.to_f64());
+        let forward =
+            chain.underlying_price.to_f64() * ((rate - dividend) * time_to_expiry.to_f64()).exp();
+        let quotes = chain
+            .options
+            .iter()
+            .filter(|option| option.implied_volatility > Positive::ZERO)
+            .map(|option| (option.strike_price, option.implied_volatility))
+            .collect();
+        Ok(Self {
+            forward: Positive::new(forward)?,
+            time_to_expiry,
+            quotes,
+        })
+    }
+
+    /// `(k, w)` pairs: log-forward moneyness and market total variance.
+    pub(crate) fn total_variances(&self) -> Vec<(f64, f64)> {
+        let t = self.time_to_expiry.to_f64();
+        let f = self.forward.to_f64();
+        self.quotes
+            .iter()
+            .map(|(strike, vol)| ((strike.to_f64() / f).ln(), vol.to_f64().powi(2) * t))
+            .collect()
+    }
+}
+
+/// A calibrated SVI smile for one expiry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SviSlice {
+    /// Fitted raw SVI parameters.
+    pub params: SviParams,
+    /// Forward price of the expiry.
+    pub forward: Positive,
+    /// Time to expiry in years.
+    pub time_to_expiry: Positive,
+    /// Strikes of the calibration quotes, sampled by [`VolatilitySmile::smile`].
+    pub strikes: Vec<Positive>,
+    /// Fit quality against the calibration quotes.
+    pub report: CalibrationReport,
+}
+
+impl SviSlice {
+    /// Fits SVI to `(strike, implied volatility)` quotes of one expiry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationFailed` with fewer than
+    /// [`SVI_MIN_QUOTES`] quotes or when no finite fit exists,
+    /// `VolatilityError::InvalidTime` for a zero expiry, and
+    /// `VolatilityError::InvalidPrice` for a zero forward.
+    #[instrument(skip(quotes), fields(quotes = quotes.len()))]
+    pub fn calibrate(
+        forward: Positive,
+        time_to_expiry: Positive,
+        quotes: &[(Positive, Positive)],
+    ) -> Result<Self, VolatilityError> {
+        Self::fit(&SliceQuotes {
+            forward,
+            time_to_expiry,
+            quotes: quotes.to_vec(),
+        })
+    }
+
+    /// Fits SVI to the implied volatilities of an option chain.
+    ///
+    /// The forward is `S e^{(r - q)T}` with the chain's rate and dividend
+    /// yield (zero when absent); strikes with a zero implied volatility are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidTime` when the chain's expiration
+    /// cannot be parsed, and any error of [`SviSlice::calibrate`].
+    pub fn from_chain(chain: &OptionChain) -> Result<Self, VolatilityError> {
+        Self::fit(&SliceQuotes::from_chain(chain)?)
+    }
+
+    fn fit(slice: &SliceQuotes) -> Result<Self, VolatilityError> {
+        if slice.time_to_expiry == Positive::ZERO {
+            return Err(VolatilityError::InvalidTime {
+                time: slice.time_to_expiry,
+                reason: "SVI needs a strictly positive expiry".to_string(),
+            });
+        }
+        if slice.forward == Positive::ZERO {
+            return Err(VolatilityError::InvalidPrice {
+                price: slice.forward,
+                reason: "SVI needs a strictly positive forward".to_string(),
+            });
+        }
+        if slice.quotes.len() < SVI_MIN_QUOTES {
+            return Err(VolatilityError::CalibrationFailed {
+                model: "SVI",
+                reason: format!(
+                    "needs at least {SVI_MIN_QUOTES} quotes, got {}",
+                    slice.quotes.len()
+                ),
+            });
+        }
+        let points = slice.total_variances();
+        let start_m = points
+            .iter()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(0.0, |p| p.0);
+
+        let mut best: Option<(RawSvi, f64, usize)> = None;
+        for sigma in SIGMA_STARTS {
+            let minimum = nelder_mead(
+                |x: &[f64]| {
+                    let (m, ln_sigma) = (x.first().copied(), x.get(1).copied());
+                    match (m, ln_sigma) {
+                        (Some(m), Some(ln_sigma)) => inner_fit(&points, m, ln_sigma.exp()).1,
+                        _ => f64::INFINITY,
+                    }
+                },
+                &[start_m, sigma.ln()],
+                &[0.1, 0.5],
+                1e-18,
+                2_000,
+            );
+            if let (Some(&m), Some(&ln_sigma)) = (minimum.point.first(), minimum.point.get(1)) {
+                let (raw, sse) = inner_fit(&points, m, ln_sigma.exp());
+                if best.as_ref().is_none_or(|b| sse < b.1) {
+                    best = Some((raw, sse, minimum.iterations));
+                }
+            }
+        }
+        let Some((raw, sse, iterations)) = best.filter(|b| b.1.is_finite()) else {
+            return Err(VolatilityError::CalibrationFailed {
+                model: "SVI",
+                reason: "no finite fit found".to_string(),
+            });
+        };
+        debug!(sse, iterations, "SVI fit");
+
+        let t = slice.time_to_expiry.to_f64();
+        let report = CalibrationReport::from_errors(
+            points
+                .iter()
+                .zip(&slice.quotes)
+                .map(|((k, _), (_, vol))| (raw.w(*k).max(0.0) / t).sqrt() - vol.to_f64()),
+            iterations,
+        )?;
+        Ok(Self {
+            params: raw.to_params()?,
+            forward: slice.forward,
+            time_to_expiry: slice.time_to_expiry,
+            strikes: slice.quotes.iter().map(|(strike, _)| *strike).collect(),
+            report,
+        })
+    }
+
+    /// Log-forward moneyness range covered by the calibration strikes.
+    fn moneyness_range(&self) -> (f64, f64) {
+        let f = self.forward.to_f64();
+        self.strikes
+            .iter()
+            .map(|k| (k.to_f64() / f).ln())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), k| {
+                (lo.min(k), hi.max(k))
+            })
+    }
+
+    /// Checks Durrleman's butterfly condition over the calibrated strike
+    /// range.
+    #[must_use]
+    pub fn arbitrage_report(&self) -> ArbitrageReport {
+        let mut report = ArbitrageReport::default();
+        let raw = RawSvi::from(&self.params);
+        let (low, high) = self.moneyness_range();
+        if low <= high {
+            for k in arbitrage_grid(low, high) {
+                let g = durrleman(k, raw.w(k), raw.dw(k), raw.d2w(k));
+                report.push_butterfly(self.time_to_expiry, k, g);
+            }
+        }
+        report
+    }
+}
+
+/// Checks a set of SVI slices for butterfly arbitrage within each slice and
+/// calendar arbitrage between consecutive expiries.
+///
+/// Calendar spreads are compared at equal log-forward moneyness over the
+/// range of strikes common to both slices.
+#[must_use]
+pub fn svi_arbitrage_report(slices: &[SviSlice]) -> ArbitrageReport {
+    let mut sorted: Vec<&SviSlice> = slices.iter().collect();
+    sorted.sort_by_key(|slice| slice.time_to_expiry);
+    let mut report = ArbitrageReport::default();
+    for slice in &sorted {
+        report.butterfly.extend(slice.arbitrage_report().butterfly);
+    }
+    for pair in sorted.windows(2) {
+        let [earlier, later] = pair else { continue };
+        let (lo_a, hi_a) = earlier.moneyness_range();
+        let (lo_b, hi_b) = later.moneyness_range();
+        let (low, high) = (lo_a.max(lo_b), hi_a.min(hi_b));
+        if low > high {
+            continue;
+        }
+        let (first, second) = (RawSvi::from(&earlier.params), RawSvi::from(&later.params));
+        for k in arbitrage_grid(low, high) {
+            report.push_calendar(
+                k,
+                earlier.time_to_expiry,
+                later.time_to_expiry,
+                first.w(k) - second.w(k),
+            );
+        }
+    }
+    report
+}
+
+/// Closed-form linear fit of `(a, b, ρ)` for fixed `(m, σ)`; returns the
+/// constrained parameters and their sum of squared total-variance errors.
+fn inner_fit(points: &[(f64, f64)], m: f64, sigma: f64) -> (RawSvi, f64) {
+    let features = |k: f64| {
+        let x = k - m;
+        [1.0, x, (x * x + sigma * sigma).sqrt()]
+    };
+    let mut normal = [[0.0f64; 3]; 3];
+    let mut rhs = [0.0f64; 3];
+    for &(k, w) in points {
+        let f = features(k);
+        for (row, fi) in normal.iter_mut().zip(f) {
+            for (cell, fj) in row.iter_mut().zip(f) {
+                *cell += fi * fj;
+            }
+        }
+        for (r, fi) in rhs.iter_mut().zip(f) {
+            *r += fi * w;
+        }
+    }
+    let [_, c, b] = solve3(normal, rhs).unwrap_or([0.0; 3]);
+
+    // Project onto the admissible set: b ≥ 0, |ρ| < 1, Lee's wing bound
+    // b (1 + |ρ|) ≤ 2, and a non-negative minimum variance. The level is
+    // refitted after the projection; without one it equals the linear fit.
+    let mut b = b.max(0.0);
+    let rho = if b > 0.0 {
+        (c / b).clamp(-MAX_ABS_RHO, MAX_ABS_RHO)
+    } else {
+        0.0
+    };
+    b = b.min(2.0 / (1.0 + rho.abs()));
+    let a = points
+        .iter()
+        .map(|&(k, w)| {
+            let x = k - m;
+            w - b * (rho * x + (x * x + sigma * sigma).sqrt())
+        })
+        .sum::<f64>()
+        / points.len().max(1) as f64;
+    let a = a.max(-b * sigma * (1.0 - rho * rho).sqrt());
+    let raw = RawSvi {
+        a,
+        b,
+        rho,
+        m,
+        sigma,
+    };
+    let sse = points.iter().map(|&(k, w)| (raw.w(k) - w).powi(2)).sum();
+    (raw, sse)
+}
+
+/// Solves a 3 × 3 linear system by Cramer's rule.
+fn solve3(m: [[f64; 3]; 3], r: [f64; 3]) -> Option<[f64; 3]> {
+    let det = |m: &[[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let d = det(&m);
+    if d.abs() < 1e-300 || !d.is_finite() {
+        return None;
+    }
+    let mut out = [0.0; 3];
+    for (col, slot) in out.iter_mut().enumerate() {
+        let mut replaced = m;
+        for (row, value) in replaced.iter_mut().zip(r) {
+            if let Some(cell) = row.get_mut(col) {
+                *cell = value;
+            }
+        }
+        *slot = det(&replaced) / d;
+    }
+    Some(out)
+}
+
+impl VolatilityModel for SviSlice {
+    fn volatility_at(&self, strike: Positive) -> Result<Positive, VolatilityError> {
+        if strike == Positive::ZERO {
+            return Err(VolatilityError::InvalidPrice {
+                price: strike,
+                reason: "SVI requires a strictly positive strike".to_string(),
+            });
+        }
+        let k = (strike.to_f64() / self.forward.to_f64()).ln();
+        let w = RawSvi::from(&self.params).w(k);
+        if !w.is_finite() || w <= 0.0 {
+            return Err(VolatilityError::NumericalFailure {
+                reason: format!("SVI total variance {w} at strike {strike}"),
+            });
+        }
+        Ok(Positive::new((w / self.time_to_expiry.to_f64()).sqrt())?)
+    }
+}
+
+impl VolatilitySmile for SviSlice {
+    /// Samples the fitted SVI volatility at the calibration strikes.
+    fn smile(&self) -> Curve {
+        let points: BTreeSet<Point2D> = self
+            .strikes
+            .iter()
+            .filter_map(|&strike| {
+                self.volatility_at(strike)
+                    .ok()
+                    .map(|vol| Point2D::new(strike.to_dec(), vol.to_dec()))
+            })
+            .collect();
+        Curve::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use positive::pos_or_panic;
+    use rust_decimal_macros::dec;
+
+    fn reference() -> RawSvi {
+        RawSvi {
+            a: 0.01,
+            b: 0.1,
+            rho: -0.4,
+            m: 0.05,
+            sigma: 0.2,
+        }
+    }
+
+    fn quotes(raw: &RawSvi, forward: f64, t: f64) -> Vec<(Positive, Positive)> {
+        (0..15)
+            .map(|i| {
+                let strike = 70.0 + 5.0 * i as f64;
+                let k = (strike / forward).ln();
+                (pos_or_panic!(strike), pos_or_panic!((raw.w(k) / t).sqrt()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_svi_recovers_generating_parameters() {
+        let raw = reference();
+        let slice =
+            SviSlice::calibrate(Positive::HUNDRED, Positive::ONE, &quotes(&raw, 100.0, 1.0))
+                .unwrap();
+        let fitted = RawSvi::from(&slice.params);
+        assert!((fitted.a - raw.a).abs() < 1e-4, "{fitted:?}");
+        assert!((fitted.b - raw.b).abs() < 1e-4, "{fitted:?}");
+        assert!((fitted.rho - raw.rho).abs() < 1e-3, "{fitted:?}");
+        assert!((fitted.m - raw.m).abs() < 1e-3, "{fitted:?}");
+        assert!((fitted.sigma - raw.sigma).abs() < 1e-3, "{fitted:?}");
+        assert!(slice.report.rmse < dec!(0.0001));
+        assert_eq!(slice.report.quotes, 15);
+        assert!(slice.arbitrage_report().is_arbitrage_free());
+
+        // Off-grid strikes are priced off the fitted smile.
+        let off_grid = slice.volatility_at(pos_or_panic!(97.5)).unwrap();
+        let expected = (raw.w((97.5f64 / 100.0).ln())).sqrt();
+        assert!((off_grid.to_f64() - expected).abs() < 1e-4);
+        assert_eq!(slice.smile().points.len(), 15);
+    }
+
+    #[test]
+    fn test_svi_detects_butterfly_arbitrage() {
+        // A large wing slope with a tiny curvature makes the density negative.
+        let params = SviParams::new(
+            dec!(0.001),
+            pos_or_panic!(1.5),
+            dec!(-0.9),
+            Decimal::ZERO,
+            pos_or_panic!(0.01),
+        )
+        .unwrap();
+        let slice = SviSlice {
+            params,
+            forward: Positive::HUNDRED,
+            time_to_expiry: Positive::ONE,
+            strikes: vec![pos_or_panic!(60.0), pos_or_panic!(160.0)],
+            report: CalibrationReport::from_errors(std::iter::empty(), 0).unwrap(),
+        };
+        let report = slice.arbitrage_report();
+        assert!(!report.butterfly.is_empty());
+        assert!(report.butterfly.iter().all(|v| v.density < Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_svi_detects_calendar_arbitrage() {
+        let raw = reference();
+        let short = SviSlice::calibrate(
+            Positive::HUNDRED,
+            pos_or_panic!(0.25),
+            &quotes(&raw, 100.0, 0.25),
+        )
+        .unwrap();
+        let mut long = short.clone();
+        long.time_to_expiry = Positive::ONE;
+        assert!(svi_arbitrage_report(&[short.clone(), long.clone()]).is_arbitrage_free());
+
+        // Lowering the later slice's level puts its total variance below the earlier one.
+        long.params.a -= dec!(0.005);
+        let report = svi_arbitrage_report(&[long, short]);
+        assert!(!report.calendar.is_empty());
+        assert!(report.calendar.iter().all(|v| v.earlier < v.later));
+    }
+
+    #[test]
+    fn test_svi_rejects_too_few_quotes_and_invalid_params() {
+        let few = &quotes(&reference(), 100.0, 1.0)[..3];
+        assert!(matches!(
+            SviSlice::calibrate(Positive::HUNDRED, Positive::ONE, few),
+            Err(VolatilityError::CalibrationFailed { model: "SVI", .. })
+        ));
+        assert!(
+            SviParams::new(
+                dec!(-0.1),
+                pos_or_panic!(0.1),
+                Decimal::ZERO,
+                Decimal::ZERO,
+                pos_or_panic!(0.1)
+            )
+            .is_err()
+        );
+        assert!(
+            SviParams::new(
+                dec!(0.01),
+                pos_or_panic!(0.1),
+                Decimal::ONE,
+                Decimal::ZERO,
+                pos_or_panic!(0.1)
+            )
+            .is_err()
+        );
+    }
+}
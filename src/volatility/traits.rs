@@ -81,6 +81,22 @@ pub trait VolatilitySmile {
     fn smile(&self) -> Curve;
 }
 
+/// A parametric model returning the implied volatility at any strike.
+///
+/// Where [`VolatilitySmile`] samples a smile on a fixed set of strikes, a
+/// `VolatilityModel` can be queried at arbitrary strikes, which is what
+/// [`OptionChain::apply_volatility_model`] needs to price every strike of
+/// a chain off the same model.
+pub trait VolatilityModel {
+    /// Returns the Black–Scholes implied volatility of the model at `strike`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`VolatilityError`] when the model cannot produce a
+    /// positive, finite volatility at `strike`.
+    fn volatility_at(&self, strike: Positive) -> Result<Positive, VolatilityError>;
+}
+
 /// Trait for providing at-the-money implied volatility.
 ///
 /// This trait defines a method to retrieve the at-the-money (ATM) implied volatility.
@@ -9,18 +9,17 @@
 // indices (fixed-length buffers, just-pushed slices, etc.).
 #![allow(clippy::indexing_slicing)]
 
-use crate::constants::{MAX_VOLATILITY, MIN_VOLATILITY};
 use crate::error::VolatilityError;
 use crate::model::decimal::{
     d_add, d_div, d_mul, d_sub, d_sum, decimal_normal_sample, finite_decimal,
 };
 use crate::model::utils::sub_floor_zero;
 use crate::utils::time::TimeFrame;
+use crate::volatility::iv_solver::{IvSolverConfig, solve_implied_volatility};
 use crate::{ExpirationDate, OptionStyle, OptionType, Options, Side};
 use num_traits::{FromPrimitive, ToPrimitive};
 use positive::Positive;
 use rand::random;
-use rayon::prelude::*;
 use rust_decimal::{Decimal, MathematicalOps};
 use tracing::instrument;
 
@@ -191,36 +190,33 @@ pub fn ewma_volatility(
 
 /// Calculates the implied volatility of an option given its market price.
 ///
-/// This function uses the Newton-Raphson method to iteratively approximate the implied
-/// volatility that corresponds to the observed market price of the option. The implied
-/// volatility is updated within the `Options` struct provided as a mutable reference.
+/// Delegates to [`solve_implied_volatility`]: a Jäckel-style rational initial
+/// guess refined by Newton–Raphson on vega, with a bracketed Brent fallback.
+/// Calls and puts are both supported and the option's risk-free rate and
+/// dividend yield are honoured. On success the solved volatility is written
+/// back into `options.implied_volatility`.
 ///
 /// # Parameters
-/// - `market_price`: The observed market price of the option.
-/// - `options`: A mutable reference to an `Options` struct, which should contain the necessary
-///   methods and fields such as `implied_volatility`, `calculate_price_black_scholes()`, and `vega()`.
-/// - `max_iterations`: The maximum number of iterations allowed for the Newton-Raphson method.
+/// - `market_price`: The observed per-unit market price of the option.
+/// - `options`: A mutable reference to the `Options` being calibrated. Its
+///   `side` is ignored; the price is matched as a long premium.
+/// - `max_iterations`: The Newton–Raphson iteration budget. Non-positive
+///   values skip Newton entirely. The Brent fallback is bounded separately by
+///   `MAX_ITERATIONS_IV`.
 ///
 /// # Returns
-/// The function returns the estimated implied volatility of the option.
+/// The implied volatility that reproduces `market_price`.
 ///
-/// # Remarks
-/// - If the price difference between the calculated and market price is within the tolerated threshold (`TOLERANCE`),
-///   the current implied volatility is returned.
-/// - The function ensures that the implied volatility stays positive.
+/// Use [`solve_implied_volatility`] directly to obtain convergence
+/// diagnostics ([`IvSolution`](crate::volatility::IvSolution)).
 ///
 /// # Errors
 ///
-/// The implementation is a parallel grid search over
-/// `100 * max_iterations` candidate volatilities rather than a
-/// Newton–Raphson iteration. It returns
-/// `VolatilityError::IvNotFound` when the best candidate is the
-/// lower boundary `1 / (100 * max_iterations)` (the search never
-/// improved), `VolatilityError::NoValidVolatility` when every grid
-/// point failed the Black–Scholes evaluation, and
-/// `VolatilityError::PositiveError` when the boundary `Positive`
-/// conversion fails. Black–Scholes errors on individual candidates
-/// are discarded by the parallel filter rather than propagated.
+/// Returns `VolatilityError::InvalidPrice` when `market_price` violates the
+/// no-arbitrage bounds of the option, `VolatilityError::Options` when the
+/// option is expired or has a zero strike or underlying price, and
+/// `VolatilityError::NoConvergence` when neither stage converges within its
+/// budget.
 #[instrument(skip(options), fields(
     market_price = %market_price,
     strike = %options.strike_price,
@@ -231,38 +227,11 @@ pub fn implied_volatility(
     options: &mut Options,
     max_iterations: i64,
 ) -> Result<Positive, VolatilityError> {
-    let base_option = options.clone();
-    let iterations = 100 * max_iterations;
-    let result = (1..iterations)
-        .into_par_iter()
-        .map(|i| {
-            let mut option = base_option.clone();
-            option.side = Side::Long; // Ensure the option is long
-            let iv = Positive::new(i as f64 / iterations as f64).unwrap_or(Positive::ZERO);
-            option.implied_volatility = iv;
-
-            match option.calculate_price_black_scholes() {
-                Ok(price) => {
-                    let diff = (price - market_price.to_dec()).abs();
-                    Some((iv, diff))
-                }
-                Err(_) => None,
-            }
-        })
-        .filter_map(|x| x) // Remove errors
-        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-
-    match result {
-        Some((best_iv, _)) => {
-            let iv = best_iv.clamp(*MIN_VOLATILITY, MAX_VOLATILITY);
-            if iv == Positive::new(1f64 / iterations as f64)? {
-                Err(VolatilityError::IvNotFound)
-            } else {
-                Ok(iv)
-            }
-        }
-        None => Err(VolatilityError::NoValidVolatility),
-    }
+    let newton_iterations = u32::try_from(max_iterations.max(0)).unwrap_or(u32::MAX);
+    let config = IvSolverConfig::default().with_max_newton_iterations(newton_iterations);
+    let solution = solve_implied_volatility(options, market_price, &config)?;
+    options.implied_volatility = solution.volatility;
+    Ok(solution.volatility)
 }
 
 /// Calculates the implied volatility (IV) of an option given its parameters.
@@ -291,7 +260,7 @@ pub fn implied_volatility(
 ///
 /// This function internally creates an `Options` object with the given parameters,
 /// and calls the `implied_volatility` function with the option data. The iteration
-/// limit for the IV calculation is set to 10.
+/// budget for the Newton–Raphson stage is set to 10.
 ///
 /// Ensure that all input parameters are valid and conform to the expected types
 /// and ranges for meaningful results.
@@ -1052,7 +1021,7 @@ mod tests_implied_volatility {
 
         let iv = result.unwrap();
         assert!(iv >= *MIN_VOLATILITY && iv <= MAX_VOLATILITY);
-        assert_pos_relative_eq!(iv, pos_or_panic!(0.437), pos_or_panic!(1e-3));
+        assert_pos_relative_eq!(iv, pos_or_panic!(0.43745), pos_or_panic!(1e-3));
     }
 
     #[test]
@@ -117,6 +117,7 @@ fn crr_binomial_converges_to_black_scholes() {
             asset: spot,
             volatility: iv,
             int_rate: rate,
+            dividend_yield: Positive::ZERO,
             strike,
             expiry,
             no_steps: steps,
@@ -139,6 +140,7 @@ fn crr_binomial_converges_to_black_scholes() {
                 asset: spot,
                 volatility: iv,
                 int_rate: rate,
+                dividend_yield: Positive::ZERO,
                 strike,
                 expiry: expiry50,
                 no_steps: n50_steps,
@@ -4,18 +4,24 @@
    Date: 2024
 ******************************************************************************/
 
-use optionstratlib::model::types::{OptionStyle, OptionType, Side};
-use optionstratlib::pricing::{Priceable, PricingEngine, price_option};
+use optionstratlib::error::PricingError;
+use optionstratlib::model::types::{BarrierType, OptionStyle, OptionType, Side};
+use optionstratlib::pricing::{
+    DiscreteDividend, DividendSchedule, HestonParams, LsmConfig, MonteCarloConfig, Priceable,
+    PricingEngine, YieldCurve, price_option,
+};
 use optionstratlib::simulation::simulator::Simulator;
 use optionstratlib::simulation::steps::{Step, Xstep, Ystep};
 use optionstratlib::simulation::{WalkParams, WalkType, WalkTypeAble};
 use optionstratlib::utils::TimeFrame;
+use optionstratlib::volatility::SabrParams;
 use optionstratlib::{ExpirationDate, Options};
 use positive::{Positive, pos_or_panic};
 use rust_decimal_macros::dec;
 use std::convert::Infallible;
 use std::error::Error;
 use std::fmt::Display;
+use std::num::NonZeroUsize;
 use std::ops::AddAssign;
 
 // A minimal walker for testing
@@ -60,6 +66,9 @@ fn create_test_option() -> Options {
         option_style: OptionStyle::Call,
         dividend_yield: pos_or_panic!(0.01),
         exotic_params: None,
+        yield_curve: None,
+        dividends: Default::default(),
+        day_count: Default::default(),
     }
 }
 
@@ -353,3 +362,264 @@ fn test_error_handling() {
 // implements the stochastic differential equations for each model.
 // The simple_generator used in these tests is deterministic and for testing
 // the API structure only.
+
+#[test]
+fn test_price_option_binomial_american_put_exceeds_european() {
+    let mut american = create_test_option();
+    american.option_type = OptionType::American;
+    american.option_style = OptionStyle::Put;
+    american.underlying_price = pos_or_panic!(90.0);
+    let mut european = american.clone();
+    european.option_type = OptionType::European;
+
+    let engine = PricingEngine::Binomial {
+        steps: NonZeroUsize::new(200).unwrap(),
+    };
+    let american_price = price_option(&american, &engine).unwrap();
+    let european_price = price_option(&european, &engine).unwrap();
+    assert!(american_price > european_price);
+    assert!(american_price >= pos_or_panic!(10.0), "at least intrinsic");
+}
+
+#[test]
+fn test_price_option_barone_adesi_whaley_matches_lattice() {
+    let mut option = create_test_option();
+    option.option_type = OptionType::American;
+    option.option_style = OptionStyle::Put;
+    option.dividend_yield = Positive::ZERO;
+    option.expiration_date = ExpirationDate::Days(pos_or_panic!(180.0));
+
+    let baw = price_option(&option, &PricingEngine::BaroneAdesiWhaley).unwrap();
+    let lattice = option
+        .price(&PricingEngine::Binomial {
+            steps: NonZeroUsize::new(500).unwrap(),
+        })
+        .unwrap();
+    assert!((baw.to_dec() - lattice.to_dec()).abs() < dec!(0.05));
+}
+
+#[test]
+fn test_price_option_barone_adesi_whaley_on_curve_with_dividends() {
+    let mut option = create_test_option();
+    option.option_type = OptionType::American;
+    option.option_style = OptionStyle::Put;
+    option.dividend_yield = Positive::ZERO;
+    option.yield_curve = Some(YieldCurve::flat(dec!(0.02)));
+    option.dividends = DividendSchedule::new(vec![DiscreteDividend::in_days(
+        pos_or_panic!(10.0),
+        Positive::TWO,
+    )]);
+    let on_curve = price_option(&option, &PricingEngine::BaroneAdesiWhaley).unwrap();
+
+    // The same put at the term rate and the escrowed spot, without either.
+    let mut restated = option.clone();
+    restated.underlying_price = Positive::new_decimal(
+        option.underlying_price.to_dec() - option.dividends_present_value().unwrap(),
+    )
+    .unwrap();
+    restated.risk_free_rate = dec!(0.02);
+    restated.yield_curve = None;
+    restated.dividends = Default::default();
+    let scalar = price_option(&restated, &PricingEngine::BaroneAdesiWhaley).unwrap();
+    assert!((on_curve.to_dec() - scalar.to_dec()).abs() < dec!(1e-9));
+
+    // Both the lower rate and the dividend make the put dearer.
+    let mut plain = restated;
+    plain.underlying_price = option.underlying_price;
+    plain.risk_free_rate = option.risk_free_rate;
+    let plain = price_option(&plain, &PricingEngine::BaroneAdesiWhaley).unwrap();
+    assert!(on_curve > plain);
+}
+
+#[test]
+fn test_price_option_closed_form_exotic_routes_barrier() {
+    let mut option = create_test_option();
+    option.option_type = OptionType::Barrier {
+        barrier_type: BarrierType::UpAndOut,
+        barrier_level: pos_or_panic!(130.0),
+        rebate: None,
+    };
+
+    let exotic = price_option(&option, &PricingEngine::ClosedFormExotic).unwrap();
+    let closed_form = price_option(&option, &PricingEngine::ClosedFormBS).unwrap();
+    assert_eq!(exotic, closed_form);
+}
+
+#[test]
+fn test_price_option_telegraph_european() {
+    let option = create_test_option();
+    let engine = PricingEngine::Telegraph {
+        steps: NonZeroUsize::new(100).unwrap(),
+    };
+    assert!(price_option(&option, &engine).is_ok());
+}
+
+#[test]
+fn test_price_option_rejects_unsupported_combinations() {
+    let european = create_test_option();
+    let mut american = create_test_option();
+    american.option_type = OptionType::American;
+
+    let cases = [
+        (&european, PricingEngine::BaroneAdesiWhaley),
+        (&european, PricingEngine::ClosedFormExotic),
+        (
+            &american,
+            PricingEngine::Telegraph {
+                steps: NonZeroUsize::new(10).unwrap(),
+            },
+        ),
+    ];
+    for (option, engine) in cases {
+        assert!(!engine.supports(&option.option_type));
+        let result = price_option(option, &engine);
+        assert!(
+            matches!(result, Err(PricingError::UnsupportedOptionType { .. })),
+            "{} should reject {}",
+            engine.name(),
+            option.option_type
+        );
+    }
+}
+
+#[test]
+fn test_pricing_engine_supports_matrix() {
+    let steps = NonZeroUsize::new(10).unwrap();
+    let bermuda = OptionType::Bermuda {
+        exercise_dates: vec![pos_or_panic!(0.1)],
+    };
+    assert!(PricingEngine::Binomial { steps }.supports(&OptionType::American));
+    assert!(PricingEngine::Binomial { steps }.supports(&bermuda));
+    assert!(PricingEngine::ClosedFormBS.supports(&OptionType::American));
+    assert!(PricingEngine::ClosedFormBS.supports(&bermuda));
+    assert!(PricingEngine::BaroneAdesiWhaley.supports(&OptionType::American));
+    assert!(PricingEngine::ClosedFormBlack76.supports(&OptionType::American));
+    assert!(!PricingEngine::ClosedFormBlack76.supports(&bermuda));
+}
+
+#[test]
+fn test_bermuda_under_closed_form_engines_keeps_the_carry() {
+    // With no early exercise dates a Bermuda option is European, so the
+    // lattice behind the closed-form engines must reproduce their price.
+    for (engine, carry) in [
+        (PricingEngine::ClosedFormBS, pos_or_panic!(0.04)),
+        (PricingEngine::ClosedFormGK, pos_or_panic!(0.07)),
+    ] {
+        let european = Options {
+            option_style: OptionStyle::Put,
+            dividend_yield: carry,
+            expiration_date: ExpirationDate::Days(pos_or_panic!(180.0)),
+            ..create_test_option()
+        };
+        let bermuda = Options {
+            option_type: OptionType::Bermuda {
+                exercise_dates: vec![],
+            },
+            ..european.clone()
+        };
+        let closed_form = price_option(&european, &engine).unwrap();
+        let lattice = price_option(&bermuda, &engine).unwrap();
+        assert!(
+            (closed_form.to_dec() - lattice.to_dec()).abs() < dec!(0.02),
+            "{engine:?}: {closed_form} vs {lattice}"
+        );
+    }
+}
+
+#[test]
+fn test_price_option_monte_carlo_gbm_close_to_black_scholes() {
+    let option = create_test_option();
+    let config = MonteCarloConfig::default()
+        .with_paths(NonZeroUsize::new(20_000).unwrap())
+        .with_steps(NonZeroUsize::new(1).unwrap());
+    let mc = price_option(&option, &PricingEngine::MonteCarloGbm { config }).unwrap();
+    let bs = price_option(&option, &PricingEngine::ClosedFormBS).unwrap();
+    assert!(
+        (mc.to_dec() - bs.to_dec()).abs() < dec!(0.05),
+        "{mc} vs {bs}"
+    );
+}
+
+#[test]
+fn test_price_option_longstaff_schwartz_on_simulator() -> Result<(), Box<dyn Error>> {
+    let mut option = create_test_option();
+    option.option_type = OptionType::American;
+    let params = WalkParams {
+        size: 31,
+        init_step: Step {
+            x: Xstep::new(
+                Positive::ONE,
+                TimeFrame::Day,
+                ExpirationDate::Days(pos_or_panic!(30.0)),
+            ),
+            y: Ystep::new(0, option.underlying_price),
+        },
+        walk_type: WalkType::GeometricBrownian {
+            dt: Positive::ONE,
+            drift: dec!(0.0),
+            volatility: option.implied_volatility,
+        },
+        walker: Box::new(TestWalker),
+    };
+    let simulator = Simulator::new("LSM".to_string(), 8, &params, simple_generator)?;
+    let engine = PricingEngine::LongstaffSchwartz {
+        simulator,
+        config: LsmConfig::default(),
+    };
+    assert!(engine.supports(&OptionType::American));
+    let price = price_option(&option, &engine)?;
+    // Every deterministic path rises, so the call is held and finishes deeper in the money.
+    assert!(price > pos_or_panic!(5.0));
+
+    option.option_type = OptionType::Asian {
+        averaging_type: optionstratlib::model::types::AsianAveragingType::Arithmetic,
+    };
+    assert!(matches!(
+        price_option(&option, &engine),
+        Err(PricingError::UnsupportedOptionType { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_price_option_heston_and_sabr_engines() -> Result<(), Box<dyn Error>> {
+    let option = create_test_option();
+    let bs = price_option(&option, &PricingEngine::ClosedFormBS)?;
+
+    // Constant variance equal to the option's implied variance reproduces Black-Scholes.
+    let variance = option.implied_volatility * option.implied_volatility;
+    let heston = PricingEngine::Heston {
+        params: HestonParams::new(
+            variance,
+            Positive::TWO,
+            variance,
+            pos_or_panic!(0.001),
+            dec!(0.0),
+        )?,
+    };
+    let heston_price = price_option(&option, &heston)?;
+    assert!((heston_price.to_f64() - bs.to_f64()).abs() < 1e-4);
+
+    // Lognormal SABR without vol of vol is a flat smile at alpha.
+    let sabr = PricingEngine::Sabr {
+        params: SabrParams::new(
+            option.implied_volatility,
+            dec!(1.0),
+            dec!(0.0),
+            Positive::ZERO,
+        )?,
+    };
+    let sabr_price = price_option(&option, &sabr)?;
+    assert!((sabr_price.to_f64() - bs.to_f64()).abs() < 1e-9);
+
+    let mut american = option.clone();
+    american.option_type = OptionType::American;
+    for engine in [&heston, &sabr] {
+        assert!(!engine.supports(&OptionType::American));
+        assert!(matches!(
+            price_option(&american, engine),
+            Err(PricingError::UnsupportedOptionType { .. })
+        ));
+    }
+    Ok(())
+}
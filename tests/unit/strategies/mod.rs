@@ -10,7 +10,9 @@ mod long_call_test;
 mod long_put_test;
 mod optimal;
 mod optimal_center;
+mod piecewise_greeks_test;
 mod protective_put_test;
 mod short_call_test;
 mod short_put_test;
 mod simple;
+mod support;
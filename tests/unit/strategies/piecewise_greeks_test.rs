@@ -0,0 +1,548 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! Greeks and delta-neutral adjustments of the single-expiry strategies,
+//! all on a 100 spot with 30 days to expiry and 20% volatility.
+
+use super::support::{Resizing, neutralising_adjustments, position_greek};
+use optionstratlib::error::GreeksError;
+use optionstratlib::error::greeks::DeltaNeutralityErrorKind;
+use optionstratlib::greeks::{gamma, theta, vega};
+use optionstratlib::model::types::{Action, OptionStyle, Side};
+use optionstratlib::strategies::delta_neutral::DeltaNeutrality;
+use optionstratlib::strategies::{
+    BoxSpread, CallBackSpread, CallBrokenWingButterfly, CallCondor, JadeLizard, PutBackSpread,
+    PutBrokenWingButterfly, PutCondor, RatioCallSpread, RatioPutSpread, ReverseJadeLizard,
+    RiskReversal, Strap, Strip,
+};
+use optionstratlib::{ExpirationDate, assert_decimal_eq};
+use positive::{Positive, pos_or_panic};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::cmp::Ordering;
+use std::cmp::Ordering::{Equal, Greater, Less};
+
+const SPOT: Positive = Positive::HUNDRED;
+const RATE: Decimal = dec!(0.05);
+
+fn expiry() -> ExpirationDate {
+    ExpirationDate::Days(pos_or_panic!(30.0))
+}
+
+fn volatility() -> Positive {
+    pos_or_panic!(0.2)
+}
+
+/// How the strategy can be brought to delta neutral.
+enum Neutralising {
+    /// It already is.
+    Already,
+    /// By resizing any one of these legs.
+    Resizings(Vec<Resizing>),
+    /// Not by resizing its own legs, whose deltas share a sign.
+    SameSignLegs,
+}
+
+/// What a strategy reports, taken before its concrete type is erased.
+struct Observed {
+    delta: Decimal,
+    gamma: Decimal,
+    vega: Decimal,
+    theta: Decimal,
+    is_neutral: bool,
+    resizings: Result<Vec<Resizing>, GreeksError>,
+}
+
+fn observe<S: DeltaNeutrality + Clone>(strategy: S) -> Observed {
+    Observed {
+        delta: strategy.delta().unwrap(),
+        gamma: position_greek(&strategy, gamma),
+        vega: position_greek(&strategy, vega),
+        theta: position_greek(&strategy, theta),
+        is_neutral: strategy.is_delta_neutral(),
+        resizings: neutralising_adjustments(&strategy),
+    }
+}
+
+struct Case {
+    name: &'static str,
+    observed: Observed,
+    delta: Decimal,
+    /// Signs of the position gamma, vega and theta.
+    exposure: [Ordering; 3],
+    neutralising: Neutralising,
+}
+
+fn sign(value: Decimal) -> Ordering {
+    if value.abs() < dec!(1e-9) {
+        Equal
+    } else {
+        value.cmp(&Decimal::ZERO)
+    }
+}
+
+fn resize(action: Action, style: OptionStyle, side: Side, strike: f64) -> Resizing {
+    (action, style, side, pos_or_panic!(strike))
+}
+
+fn cases() -> Vec<Case> {
+    use Action::{Buy, Sell};
+    use OptionStyle::{Call, Put};
+    use Side::{Long, Short};
+    let long_volatility = [Greater, Greater, Less];
+    let short_volatility = [Less, Less, Greater];
+    vec![
+        Case {
+            // The synthetic long at 95 and short at 105 cancel: only the
+            // carry on the locked-in payoff remains.
+            name: "box spread",
+            observed: observe(
+                BoxSpread::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    pos_or_panic!(7.5),
+                    Positive::TWO,
+                    pos_or_panic!(2.5),
+                    pos_or_panic!(6.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: Decimal::ZERO,
+            exposure: [Equal, Equal, Greater],
+            neutralising: Neutralising::Already,
+        },
+        Case {
+            name: "call back spread",
+            observed: observe(
+                CallBackSpread::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    Positive::TWO,
+                    pos_or_panic!(7.0),
+                    Positive::TWO,
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(-0.3872),
+            exposure: long_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Sell, Call, Short, 95.0),
+                resize(Buy, Call, Long, 105.0),
+            ]),
+        },
+        Case {
+            name: "ratio call spread",
+            observed: observe(
+                RatioCallSpread::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    Positive::TWO,
+                    pos_or_panic!(6.5),
+                    Positive::TWO,
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(0.3872),
+            exposure: short_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Sell, Call, Long, 95.0),
+                resize(Buy, Call, Short, 105.0),
+            ]),
+        },
+        Case {
+            name: "put back spread",
+            observed: observe(
+                PutBackSpread::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    Positive::TWO,
+                    Positive::TWO,
+                    pos_or_panic!(7.0),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(0.4538),
+            exposure: long_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Buy, Put, Long, 95.0),
+                resize(Sell, Put, Short, 105.0),
+            ]),
+        },
+        Case {
+            name: "ratio put spread",
+            observed: observe(
+                RatioPutSpread::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    Positive::TWO,
+                    Positive::TWO,
+                    pos_or_panic!(6.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(-0.4538),
+            exposure: short_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Buy, Put, Short, 95.0),
+                resize(Sell, Put, Long, 105.0),
+            ]),
+        },
+        Case {
+            name: "call broken-wing butterfly",
+            observed: observe(
+                CallBrokenWingButterfly::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(90.0),
+                    pos_or_panic!(100.0),
+                    pos_or_panic!(115.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    pos_or_panic!(11.5),
+                    pos_or_panic!(5.0),
+                    Positive::ONE,
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(-0.0965),
+            exposure: short_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Buy, Call, Long, 90.0),
+                resize(Sell, Call, Short, 100.0),
+                resize(Buy, Call, Long, 115.0),
+            ]),
+        },
+        Case {
+            name: "put broken-wing butterfly",
+            observed: observe(
+                PutBrokenWingButterfly::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(85.0),
+                    pos_or_panic!(100.0),
+                    pos_or_panic!(110.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    Positive::ONE,
+                    pos_or_panic!(5.0),
+                    pos_or_panic!(11.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(-0.0224),
+            exposure: short_volatility,
+            // The 85 put is too far out of the money to hedge with.
+            neutralising: Neutralising::Resizings(vec![
+                resize(Buy, Put, Short, 100.0),
+                resize(Sell, Put, Long, 110.0),
+            ]),
+        },
+        Case {
+            name: "call condor",
+            observed: observe(
+                CallCondor::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(90.0),
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    pos_or_panic!(110.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    pos_or_panic!(11.0),
+                    pos_or_panic!(7.0),
+                    Positive::TWO,
+                    pos_or_panic!(0.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(-0.0337),
+            exposure: short_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Buy, Call, Long, 90.0),
+                resize(Sell, Call, Short, 95.0),
+                resize(Sell, Call, Short, 105.0),
+                resize(Buy, Call, Long, 110.0),
+            ]),
+        },
+        Case {
+            // Same exposure as the call condor on the same strikes, by
+            // put-call parity.
+            name: "put condor",
+            observed: observe(
+                PutCondor::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(90.0),
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    pos_or_panic!(110.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    pos_or_panic!(0.5),
+                    Positive::TWO,
+                    pos_or_panic!(7.0),
+                    pos_or_panic!(11.0),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(-0.0337),
+            exposure: short_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Buy, Put, Short, 95.0),
+                resize(Buy, Put, Short, 105.0),
+                resize(Sell, Put, Long, 110.0),
+            ]),
+        },
+        Case {
+            name: "jade lizard",
+            observed: observe(
+                JadeLizard::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    pos_or_panic!(110.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    pos_or_panic!(3.0),
+                    pos_or_panic!(3.0),
+                    pos_or_panic!(0.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(-0.0074),
+            exposure: short_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Buy, Put, Short, 95.0),
+                resize(Sell, Call, Short, 105.0),
+                resize(Buy, Call, Long, 110.0),
+            ]),
+        },
+        Case {
+            name: "reverse jade lizard",
+            observed: observe(
+                ReverseJadeLizard::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(90.0),
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    Positive::ONE,
+                    pos_or_panic!(3.5),
+                    pos_or_panic!(3.0),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(-0.0929),
+            exposure: short_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Buy, Put, Short, 95.0),
+                resize(Sell, Call, Short, 105.0),
+            ]),
+        },
+        Case {
+            // The long 105 call is nearer the money than the short 95 put, so
+            // it slightly outweighs it in gamma and vega.
+            name: "risk reversal",
+            observed: observe(
+                RiskReversal::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    pos_or_panic!(95.0),
+                    pos_or_panic!(105.0),
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    Positive::TWO,
+                    pos_or_panic!(1.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(0.3863),
+            exposure: long_volatility,
+            neutralising: Neutralising::SameSignLegs,
+        },
+        Case {
+            name: "strap",
+            observed: observe(
+                Strap::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    SPOT,
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    Positive::TWO,
+                    pos_or_panic!(4.0),
+                    pos_or_panic!(3.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(0.6199),
+            exposure: long_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Sell, Call, Long, 100.0),
+                resize(Buy, Put, Long, 100.0),
+            ]),
+        },
+        Case {
+            name: "strip",
+            observed: observe(
+                Strip::new(
+                    "SPY".to_string(),
+                    SPOT,
+                    SPOT,
+                    expiry(),
+                    volatility(),
+                    RATE,
+                    Positive::ZERO,
+                    Positive::ONE,
+                    Positive::TWO,
+                    pos_or_panic!(4.0),
+                    pos_or_panic!(3.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                )
+                .unwrap(),
+            ),
+            delta: dec!(-0.3801),
+            exposure: long_volatility,
+            neutralising: Neutralising::Resizings(vec![
+                resize(Buy, Call, Long, 100.0),
+                resize(Sell, Put, Long, 100.0),
+            ]),
+        },
+    ]
+}
+
+#[test]
+fn test_greeks_signs_and_delta_adjustments() {
+    for case in cases() {
+        let name = case.name;
+        let observed = case.observed;
+        assert_decimal_eq!(observed.delta, case.delta, dec!(0.0001));
+        assert_eq!(
+            [
+                sign(observed.gamma),
+                sign(observed.vega),
+                sign(observed.theta)
+            ],
+            case.exposure,
+            "{name}: gamma {}, vega {}, theta {}",
+            observed.gamma,
+            observed.vega,
+            observed.theta
+        );
+        match case.neutralising {
+            Neutralising::Already => {
+                assert!(observed.is_neutral, "{name}");
+                assert_eq!(observed.resizings.unwrap(), Vec::new(), "{name}");
+            }
+            Neutralising::Resizings(expected) => {
+                assert!(!observed.is_neutral, "{name}");
+                assert_eq!(observed.resizings.unwrap(), expected, "{name}");
+            }
+            Neutralising::SameSignLegs => {
+                assert!(!observed.is_neutral, "{name}");
+                assert!(
+                    matches!(
+                        observed.resizings,
+                        Err(GreeksError::DeltaNeutrality(
+                            DeltaNeutralityErrorKind::SameSignDeltas
+                        ))
+                    ),
+                    "{name}"
+                );
+            }
+        }
+    }
+}
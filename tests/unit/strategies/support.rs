@@ -0,0 +1,76 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! Helpers shared by the strategy tests.
+
+use optionstratlib::Options;
+use optionstratlib::error::GreeksError;
+use optionstratlib::model::types::{Action, OptionStyle, Side};
+use optionstratlib::strategies::base::Positionable;
+use optionstratlib::strategies::delta_neutral::{DeltaAdjustment, DeltaNeutrality};
+use positive::Positive;
+use rust_decimal::Decimal;
+
+/// A single-leg resizing: buy or sell more of the leg with this style, side
+/// and strike.
+pub type Resizing = (Action, OptionStyle, Side, Positive);
+
+/// Side-signed sum of `greek` over the legs of `strategy`. The option Greeks
+/// other than delta are those of a long holder, so this is the position's
+/// exposure.
+pub fn position_greek<S, F>(strategy: &S, greek: F) -> Decimal
+where
+    S: Positionable,
+    F: Fn(&Options) -> Result<Decimal, GreeksError>,
+{
+    strategy
+        .get_positions()
+        .unwrap()
+        .iter()
+        .map(|leg| {
+            let value = greek(&leg.option).unwrap();
+            match leg.option.side {
+                Side::Long => value,
+                Side::Short => -value,
+            }
+        })
+        .sum()
+}
+
+/// The single-leg resizings suggested for `strategy`, after checking that
+/// each one alone makes it delta neutral.
+pub fn neutralising_adjustments<S>(strategy: &S) -> Result<Vec<Resizing>, GreeksError>
+where
+    S: DeltaNeutrality + Clone,
+{
+    let mut resizings = Vec::new();
+    for adjustment in strategy.delta_adjustments()? {
+        let resizing = match &adjustment {
+            DeltaAdjustment::BuyOptions {
+                strike,
+                option_style,
+                side,
+                ..
+            } => (Action::Buy, *option_style, *side, *strike),
+            DeltaAdjustment::SellOptions {
+                strike,
+                option_style,
+                side,
+                ..
+            } => (Action::Sell, *option_style, *side, *strike),
+            _ => continue,
+        };
+        let mut hedged = strategy.clone();
+        hedged.apply_single_adjustment(&adjustment).unwrap();
+        assert!(
+            hedged.is_delta_neutral(),
+            "{adjustment:?} leaves delta {}",
+            hedged.delta().unwrap()
+        );
+        resizings.push(resizing);
+    }
+    Ok(resizings)
+}